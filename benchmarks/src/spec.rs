@@ -272,6 +272,8 @@ impl WorkloadSpec {
                     name: None,
                     inner: nom_sql::CacheInner::Statement(Box::new(stmt)),
                     always: false,
+                    index_type: None,
+                    max_mem: None,
                 };
 
                 let _ = conn.query_drop(create_cache_query.to_string()).await;
@@ -193,6 +193,8 @@ impl ArbitraryQueryParameters {
             name: Some("q".into()),
             inner: nom_sql::CacheInner::Statement(Box::new(stmt)),
             always: false,
+            index_type: None,
+            max_mem: None,
         };
 
         conn.query_drop(create_cache_query.to_string()).await?;
@@ -68,6 +68,9 @@ pub enum BinaryOperator {
     /// `/`
     Divide,
 
+    /// MySQL `DIV`
+    IntDivide,
+
     /// `?`
     JsonExists,
 
@@ -147,6 +150,10 @@ impl BinaryOperator {
             }
             Multiply => Self::Multiply,
             Divide => Self::Divide,
+            IntDivide if dialect.engine() != SqlEngine::MySQL => {
+                unsupported!("'{op}' not available in {}", dialect.engine())
+            }
+            IntDivide => Self::IntDivide,
             Like => Self::Like,
             NotLike => Self::NotLike,
             ILike => Self::ILike,
@@ -187,6 +194,19 @@ impl BinaryOperator {
         Ok(res)
     }
 
+    /// Returns `true` if this operator compares its operands (`=`, `!=`, `>`, `>=`, `<`, or `<=`)
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            Self::Equal
+                | Self::NotEqual
+                | Self::Greater
+                | Self::GreaterOrEqual
+                | Self::Less
+                | Self::LessOrEqual
+        )
+    }
+
     /// Checks this operator's input types, returning
     /// [`ReadySetError::InvalidQuery`](readyset_errors::ReadySetError::InvalidQuery) if either is
     /// unexpected.
@@ -297,6 +317,21 @@ impl BinaryOperator {
             | Self::JsonKeyExtractText
             | Self::JsonKeyPathExtractText => Ok(DfType::DEFAULT_TEXT),
 
+            Self::IntDivide => Ok(DfType::BigInt),
+
+            // Arithmetic involving a NUMERIC is always done in (and returns) NUMERIC, so that no
+            // precision is lost
+            Self::Add | Self::Subtract | Self::Multiply | Self::Divide
+                if matches!(left_type, DfType::Numeric { .. }) =>
+            {
+                Ok(left_type.clone())
+            }
+            Self::Add | Self::Subtract | Self::Multiply | Self::Divide
+                if matches!(right_type, DfType::Numeric { .. }) =>
+            {
+                Ok(right_type.clone())
+            }
+
             _ => Ok(left_type.clone()),
         }
     }
@@ -323,6 +358,7 @@ impl fmt::Display for BinaryOperator {
             Self::Subtract | Self::JsonSubtract => "-",
             Self::Multiply => "*",
             Self::Divide => "/",
+            Self::IntDivide => "DIV",
             Self::JsonExists => "?",
             Self::JsonAnyExists => "?|",
             Self::JsonAllExists => "?&",
@@ -342,6 +378,28 @@ impl fmt::Display for BinaryOperator {
 mod tests {
     use super::*;
 
+    #[test]
+    fn numeric_arithmetic_output_type() {
+        let numeric = DfType::Numeric {
+            prec: 30,
+            scale: 10,
+        };
+        for op in [
+            BinaryOperator::Add,
+            BinaryOperator::Subtract,
+            BinaryOperator::Multiply,
+            BinaryOperator::Divide,
+        ] {
+            assert_eq!(op.output_type(&DfType::Double, &numeric).unwrap(), numeric);
+            assert_eq!(op.output_type(&numeric, &DfType::Float).unwrap(), numeric);
+            assert_eq!(op.output_type(&DfType::Int, &numeric).unwrap(), numeric);
+            assert_eq!(
+                op.output_type(&DfType::Double, &DfType::Double).unwrap(),
+                DfType::Double
+            );
+        }
+    }
+
     #[test]
     fn json_subtract_lowering() {
         assert_eq!(
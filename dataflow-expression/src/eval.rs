@@ -2,6 +2,7 @@ use std::borrow::Borrow;
 
 use readyset_data::{Array, ArrayD, DfType, DfValue, IxDyn};
 use readyset_errors::{invalid_err, unsupported, ReadySetError, ReadySetResult};
+use rust_decimal::prelude::ToPrimitive;
 use serde_json::Value as JsonValue;
 
 use crate::like::{CaseInsensitive, CaseSensitive, LikePattern};
@@ -79,13 +80,26 @@ impl Expr {
                     Subtract => Ok((non_null!(left) - non_null!(right))?),
                     Multiply => Ok((non_null!(left) * non_null!(right))?),
                     Divide => Ok((non_null!(left) / non_null!(right))?),
+                    IntDivide => {
+                        // Integers already divide with integer division, so we only need to
+                        // truncate the result of dividing floats or decimals
+                        Ok(match (non_null!(left) / non_null!(right))? {
+                            DfValue::Float(f) => DfValue::from(f.trunc() as i64),
+                            DfValue::Double(f) => DfValue::from(f.trunc() as i64),
+                            DfValue::Numeric(d) => {
+                                d.trunc().to_i64().map_or(DfValue::None, DfValue::from)
+                            }
+                            quotient => quotient,
+                        })
+                    }
                     And => Ok((non_null!(left).is_truthy() && non_null!(right).is_truthy()).into()),
                     Or => Ok((non_null!(left).is_truthy() || non_null!(right).is_truthy()).into()),
-                    Equal => Ok((non_null!(left)
-                        == &non_null!(right).coerce_to(left_ty, right_ty)?)
+                    Equal => Ok(left
+                        .sql_eq(&non_null!(right).coerce_to(left_ty, right_ty)?)
                         .into()),
-                    NotEqual => Ok((non_null!(left)
-                        != &non_null!(right).coerce_to(left_ty, right_ty)?)
+                    NotEqual => Ok(left
+                        .sql_eq(&non_null!(right).coerce_to(left_ty, right_ty)?)
+                        .map(|eq| !eq)
                         .into()),
                     Greater => Ok((non_null!(left) > non_null!(right)).into()),
                     GreaterOrEqual => Ok((non_null!(left) >= non_null!(right)).into()),
@@ -302,6 +316,7 @@ mod tests {
     use nom_sql::Dialect::*;
     use nom_sql::{parse_expr, SqlType};
     use readyset_data::{ArrayD, Collation, DfType, IxDyn, PgEnumMetadata};
+    use rust_decimal::Decimal;
     use serde_json::json;
     use Expr::*;
 
@@ -359,6 +374,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn eval_divide() {
+        assert_eq!(
+            eval_expr("7 / 2", MySQL),
+            DfValue::from(Decimal::new(35, 1))
+        );
+        assert_eq!(eval_expr("7 / 0", MySQL), DfValue::None);
+        assert_eq!(eval_expr("7 / 2", PostgreSQL), DfValue::from(3));
+    }
+
+    #[test]
+    fn eval_int_divide() {
+        assert_eq!(eval_expr("7 DIV 2", MySQL), DfValue::from(3));
+        assert_eq!(eval_expr("-7 DIV 2", MySQL), DfValue::from(-3));
+        assert_eq!(eval_expr("7.5 DIV 2", MySQL), DfValue::from(3));
+        assert_eq!(eval_expr("7 DIV 0", MySQL), DfValue::None);
+    }
+
     #[test]
     fn eval_json_exists() {
         let expr = Op {
@@ -889,6 +922,27 @@ mod tests {
         assert_eq!(res, DfValue::None)
     }
 
+    #[test]
+    fn eq_null() {
+        for op in [BinaryOperator::Equal, BinaryOperator::NotEqual] {
+            let expr = Expr::Op {
+                left: Box::new(column_with_type(0, DfType::Int)),
+                op,
+                right: Box::new(column_with_type(1, DfType::Int)),
+                ty: DfType::Bool,
+            };
+            for row in [
+                [DfValue::None, DfValue::None],
+                [DfValue::from(1), DfValue::None],
+                [DfValue::None, DfValue::from(1)],
+            ] {
+                let res = expr.eval(&row).unwrap();
+                assert_eq!(res, DfValue::None);
+                assert!(!res.is_truthy());
+            }
+        }
+    }
+
     #[test]
     fn enum_eq_string_postgres() {
         let expr = Expr::Op {
@@ -20,8 +20,8 @@ use vec1::Vec1;
 pub use crate::binary_operator::*;
 pub use crate::lower::LowerContext;
 pub use crate::post_lookup::{
-    PostLookup, PostLookupAggregate, PostLookupAggregateFunction, PostLookupAggregates,
-    PreInsertion, ReaderProcessing,
+    ConstantColumn, PostLookup, PostLookupAggregate, PostLookupAggregateFunction,
+    PostLookupAggregates, PreInsertion, ReaderProcessing,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -1,7 +1,7 @@
 use std::iter;
 
 use launchpad::redacted::Sensitive;
-use nom_sql::{Column, Expr as AstExpr, FunctionExpr, InValue, Relation, UnaryOperator};
+use nom_sql::{Column, Expr as AstExpr, FunctionExpr, InValue, Relation, SqlType, UnaryOperator};
 use readyset_data::dialect::SqlEngine;
 use readyset_data::{DfType, DfValue};
 use readyset_errors::{internal, invalid_err, unsupported, ReadySetError, ReadySetResult};
@@ -118,6 +118,25 @@ fn mysql_least_greatest_compare_as(arg_types: Vec<&DfType>) -> DfType {
     DfType::VarBinary(u16::MAX)
 }
 
+/// In MySQL, comparing a number to a string converts the string to a number, so that (for example)
+/// `age = '25'` is true for rows where `age` is 25. To match that, if `expr` is a string literal
+/// being compared against an expression of numeric type `other_ty`, convert it to `other_ty` up
+/// front. Strings which aren't valid values of `other_ty` are left alone.
+fn mysql_coerce_numeric_string(expr: Expr, other_ty: &DfType) -> Expr {
+    match expr {
+        Expr::Literal { val, ty } if ty.is_any_text() && other_ty.is_numeric() => {
+            match val.coerce_to(other_ty, &ty) {
+                Ok(val) => Expr::Literal {
+                    val,
+                    ty: other_ty.clone(),
+                },
+                Err(_) => Expr::Literal { val, ty },
+            }
+        }
+        expr => expr,
+    }
+}
+
 impl BuiltinFunction {
     pub(crate) fn from_name_and_args<A>(
         name: &str,
@@ -340,6 +359,8 @@ impl Expr {
     ///   AND x != z AND ...`
     /// - Replacing unary negation with `(expr * -1)`
     /// - Replacing unary NOT with `(expr != 1)`
+    /// - In MySQL, converting string literals compared against numeric expressions to numbers
+    /// - In MySQL, dividing integers with `/` as decimals
     /// - Inferring the type of each node in the expression AST.
     pub fn lower<C>(expr: AstExpr, dialect: Dialect, context: C) -> ReadySetResult<Self>
     where
@@ -394,10 +415,31 @@ impl Expr {
                 Ok(Self::Column { index, ty })
             }
             AstExpr::BinaryOp { lhs, op, rhs } => {
-                let left = Box::new(Self::lower(*lhs, dialect, context.clone())?);
-                let right = Box::new(Self::lower(*rhs, dialect, context)?);
+                let mut left = Box::new(Self::lower(*lhs, dialect, context.clone())?);
+                let mut right = Box::new(Self::lower(*rhs, dialect, context)?);
                 let op = BinaryOperator::from_sql_op(op, dialect, left.ty(), right.ty())?;
 
+                if op.is_comparison() && dialect.engine() == SqlEngine::MySQL {
+                    let left_ty = left.ty().clone();
+                    *left = mysql_coerce_numeric_string(*left, right.ty());
+                    *right = mysql_coerce_numeric_string(*right, &left_ty);
+                }
+
+                // In MySQL, `/` always returns a decimal (or a float, if either operand is a
+                // float), even if both operands are integers - integer division is done with `DIV`
+                // instead. Dividing two integers as decimals gets us the same result.
+                if op == BinaryOperator::Divide
+                    && dialect.engine() == SqlEngine::MySQL
+                    && left.ty().is_any_int()
+                    && right.ty().is_any_int()
+                {
+                    left = Box::new(Self::Cast {
+                        expr: left,
+                        to_type: SqlType::Numeric(None),
+                        ty: DfType::DEFAULT_NUMERIC,
+                    });
+                }
+
                 if matches!(
                     op,
                     BinaryOperator::JsonPathExtract | BinaryOperator::JsonPathExtractUnquote
@@ -489,10 +531,14 @@ impl Expr {
 
                     let lhs = Self::lower(*lhs, dialect, context.clone())?;
                     let make_comparison = |rhs| -> ReadySetResult<_> {
+                        let mut rhs = Self::lower(rhs, dialect, context.clone())?;
+                        if dialect.engine() == SqlEngine::MySQL {
+                            rhs = mysql_coerce_numeric_string(rhs, lhs.ty());
+                        }
                         Ok(Self::Op {
                             left: Box::new(lhs.clone()),
                             op: comparison_op,
-                            right: Box::new(Self::lower(rhs, dialect, context.clone())?),
+                            right: Box::new(rhs),
                             ty: DfType::Bool, // type of =/!= is always bool
                         })
                     };
@@ -1090,4 +1136,65 @@ pub(crate) mod tests {
             }
         )
     }
+
+    #[test]
+    fn mysql_compare_numeric_column_to_string() {
+        let lower = |expr: &str, dialect| {
+            let expr = parse_expr(ParserDialect::MySQL, expr).unwrap();
+            Expr::lower(
+                expr,
+                dialect,
+                resolve_columns(|c| {
+                    if c == "t.age".into() {
+                        Ok((0, DfType::Int))
+                    } else {
+                        internal!("what's this column!?")
+                    }
+                }),
+            )
+            .unwrap()
+        };
+        let matches = |expr: &Expr, age: i32| expr.eval(&[DfValue::from(age)]).unwrap().is_truthy();
+
+        let expr = lower("t.age = '25'", Dialect::DEFAULT_MYSQL);
+        assert_eq!(
+            expr,
+            Expr::Op {
+                left: Box::new(Expr::Column {
+                    index: 0,
+                    ty: DfType::Int
+                }),
+                op: BinaryOperator::Equal,
+                right: Box::new(Expr::Literal {
+                    val: 25.into(),
+                    ty: DfType::Int
+                }),
+                ty: DfType::Bool
+            }
+        );
+        assert!(matches(&expr, 25));
+        assert!(!matches(&expr, 26));
+
+        let expr = lower("'25' < t.age", Dialect::DEFAULT_MYSQL);
+        assert!(matches(&expr, 100));
+        assert!(!matches(&expr, 3));
+
+        let expr = lower("t.age IN ('1', '25')", Dialect::DEFAULT_MYSQL);
+        assert!(matches(&expr, 25));
+        assert!(!matches(&expr, 2));
+
+        // Strings that aren't numbers are left alone
+        let expr = lower("t.age != 'abc'", Dialect::DEFAULT_MYSQL);
+        assert!(matches!(
+            expr,
+            Expr::Op { right, .. } if right.ty().is_any_text()
+        ));
+
+        // Postgres doesn't convert strings to numbers this way
+        let expr = lower("t.age = '25'", Dialect::DEFAULT_POSTGRESQL);
+        assert!(matches!(
+            expr,
+            Expr::Op { right, .. } if right.ty().is_unknown()
+        ));
+    }
 }
@@ -3,10 +3,11 @@ use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::sync::Arc;
 
-use nom_sql::OrderType;
+use nom_sql::{OrderType, SqlIdentifier};
 use partial_map::InsertionOrder;
-use readyset_data::DfValue;
+use readyset_data::{DfType, DfValue};
 use readyset_errors::{internal, ReadySetResult};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 /// Representation of an aggregate function
@@ -34,7 +35,7 @@ impl PostLookupAggregateFunction {
     /// This forms a semigroup.
     pub fn apply(&self, val1: &DfValue, val2: &DfValue) -> ReadySetResult<DfValue> {
         match self {
-            PostLookupAggregateFunction::Sum => val1 + val2,
+            PostLookupAggregateFunction::Sum => sum(val1, val2),
             PostLookupAggregateFunction::Product => val1 * val2,
             PostLookupAggregateFunction::GroupConcat { separator } => Ok(format!(
                 "{}{}{}",
@@ -49,6 +50,27 @@ impl PostLookupAggregateFunction {
     }
 }
 
+/// Add together two already-aggregated values for a post-lookup `SUM` (or `COUNT`).
+///
+/// Integers are added as `i128`s, and the result is widened to a decimal if it doesn't fit in a
+/// 64-bit integer, rather than overflowing the way same-width integer arithmetic would.
+fn sum(val1: &DfValue, val2: &DfValue) -> ReadySetResult<DfValue> {
+    let as_i128 = |val: &DfValue| match *val {
+        DfValue::Int(i) => Some(i128::from(i)),
+        DfValue::UnsignedInt(i) => Some(i128::from(i)),
+        _ => None,
+    };
+
+    match (as_i128(val1), as_i128(val2)) {
+        (Some(i1), Some(i2)) => {
+            // Can't overflow, since both values fit in 64 bits
+            let sum = i1 + i2;
+            Ok(DfValue::try_from(sum).unwrap_or_else(|_| Decimal::from(sum).into()))
+        }
+        _ => val1 + val2,
+    }
+}
+
 /// Representation of a single aggregate function to be performed on a column post-lookup
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 pub struct PostLookupAggregate<Column = usize> {
@@ -120,12 +142,19 @@ impl ReaderProcessing {
         returned_cols: Option<Vec<usize>>,
         default_row: Option<Vec<DfValue>>,
         aggregates: Option<PostLookupAggregates>,
+        constant_columns: Vec<ConstantColumn>,
     ) -> ReadySetResult<Self> {
         if let Some(cols) = &returned_cols {
             if cols.iter().enumerate().any(|(i, v)| i != *v) {
                 internal!("Returned columns must be projected in order");
             }
         }
+        if constant_columns
+            .windows(2)
+            .any(|w| w[0].index >= w[1].index)
+        {
+            internal!("Constant columns must be in order of their index");
+        }
 
         let post_processing = PostLookup {
             order_by,
@@ -133,6 +162,7 @@ impl ReaderProcessing {
             returned_cols,
             default_row: default_row.map(|r| Arc::new(r.into_boxed_slice())),
             aggregates,
+            constant_columns,
         };
 
         let pre_processing = PreInsertion {
@@ -179,6 +209,27 @@ pub struct PostLookup {
     /// Note that currently these are only performed on each key individually, not the overall
     /// result set returned by all keys in a multi-key lookup
     pub aggregates: Option<PostLookupAggregates>,
+    /// Columns with the same value in every row returned by the query, ordered by their index.
+    ///
+    /// These aren't stored in the reader's state, and are instead inserted into each row (after
+    /// the `returned_cols` have been selected) as it's returned.
+    #[serde(default)]
+    pub constant_columns: Vec<ConstantColumn>,
+}
+
+/// A column projected by a query which has the same value in every row, such as a literal in the
+/// `SELECT` list, and so can be added to rows after they're read from a reader rather than being
+/// stored in its state
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct ConstantColumn {
+    /// The index of the column within the rows returned by the query
+    pub index: usize,
+    /// The name of the column
+    pub name: SqlIdentifier,
+    /// The type of the column
+    pub ty: DfType,
+    /// The value of the column in every row
+    pub value: DfValue,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, Eq, PartialEq)]
@@ -3,8 +3,15 @@
 //! The way the authentication scheme works:
 //!
 //! 1. The server sends 20-bytes of [random data](AuthData) along with the initial handshake packet
-//! 2. The client returns a 20-byte random response based on the algorithm in [`hash_password`]
-//! 3. The server runs the same algorithm, and checks the response against the result
+//! 2. The client returns a 20-byte response, computed as:
+//!
+//!    ```notrust
+//!    SHA1(password) XOR SHA1("20-bytes random data from server" <concat> SHA1(SHA1(password)))
+//!    ```
+//!
+//! 3. The server, which only needs to know `SHA1(SHA1(password))` (see [`native_password_hash`]),
+//!    recovers `SHA1(password)` from the response and checks that its hash matches (see
+//!    [`verify_password`])
 //!
 //! [0]: https://dev.mysql.com/doc/internals/en/secure-password-authentication.html
 
@@ -15,6 +22,11 @@ use crate::error::MsqlSrvError;
 
 pub type AuthData = [u8; 20];
 
+/// The hash of a user's password that the server checks clients' responses against:
+/// `SHA1(SHA1(password))`. This is the same value MySQL stores for users authenticating with
+/// `mysql_native_password`, which it displays hex-encoded and prefixed with `*`.
+pub type PasswordHash = [u8; 20];
+
 /// The name of the (currently only) supported auth plugin
 pub const AUTH_PLUGIN_NAME: &str = "mysql_native_password";
 
@@ -39,29 +51,48 @@ fn sha1(input: &[u8]) -> [u8; 20] {
     hasher.finalize().into()
 }
 
-/// Hash a password alongside random challenge data per the mysql [secure password authentication
-/// algorithm][0].
-///
-/// The algorithm is:
-///
-/// ```notrust
-/// SHA1(password) XOR SHA1("20-bytes random data from server" <concat> SHA1(SHA1(password)))
-/// ```
-///
-/// [0]: https://dev.mysql.com/doc/internals/en/secure-password-authentication.html
-pub fn hash_password(password: &[u8], auth_data: &AuthData) -> [u8; 20] {
-    let mut res = sha1(password);
+/// Compute the [`PasswordHash`] of the given cleartext password
+pub fn native_password_hash(password: &[u8]) -> PasswordHash {
+    sha1(&sha1(password))
+}
+
+/// Returns the SHA1 of the challenge data concatenated with the password hash, which the client
+/// XORs with `SHA1(password)` to produce its response
+fn salted_hash(password_hash: &PasswordHash, auth_data: &AuthData) -> [u8; 20] {
     let mut salted = [0u8; 40];
     salted[..20].clone_from_slice(auth_data);
-    salted[20..].clone_from_slice(&sha1(&res));
-    xor_slice_mut(&mut res, &sha1(&salted));
-    res
+    salted[20..].clone_from_slice(password_hash);
+    sha1(&salted)
+}
+
+/// Check a client's response to the challenge data against the hash of the user's password, per
+/// the mysql [secure password authentication algorithm][0].
+///
+/// [0]: https://dev.mysql.com/doc/internals/en/secure-password-authentication.html
+pub fn verify_password(
+    password_hash: &PasswordHash,
+    auth_data: &AuthData,
+    response: &[u8],
+) -> bool {
+    let mut candidate = match <[u8; 20]>::try_from(response) {
+        Ok(candidate) => candidate,
+        Err(_) => return false,
+    };
+    xor_slice_mut(&mut candidate, &salted_hash(password_hash, auth_data));
+    sha1(&candidate) == *password_hash
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Compute the response a client sends for the given password and challenge data
+    fn hash_password(password: &[u8], auth_data: &AuthData) -> [u8; 20] {
+        let mut res = sha1(password);
+        xor_slice_mut(&mut res, &salted_hash(&sha1(&res), auth_data));
+        res
+    }
+
     #[test]
     fn hash_password_works() {
         let auth_data: AuthData = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
@@ -75,4 +106,30 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn verify_password_works() {
+        let auth_data: AuthData = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
+        let password_hash = native_password_hash(b"password");
+        assert!(verify_password(
+            &password_hash,
+            &auth_data,
+            &hash_password(b"password", &auth_data)
+        ));
+        assert!(!verify_password(
+            &password_hash,
+            &auth_data,
+            &hash_password(b"wrong", &auth_data)
+        ));
+        assert!(!verify_password(&password_hash, &auth_data, &[]));
+    }
+
+    #[test]
+    fn native_password_hash_matches_mysql() {
+        // SELECT PASSWORD('password') in MySQL 5.7
+        assert_eq!(
+            hex::encode_upper(native_password_hash(b"password")),
+            "2470C0C06DEE42FD1618BB99005ADCA2EC9D1E19"
+        );
+    }
 }
@@ -103,8 +103,8 @@
 //!         }
 //!     }
 //!
-//!     fn password_for_username(&self, _username: &str) -> Option<Vec<u8>> {
-//!         Some(b"password".to_vec())
+//!     async fn password_hash_for_username(&mut self, _username: &str) -> Option<PasswordHash> {
+//!         Some(native_password_hash(b"password"))
 //!     }
 //!
 //!     fn version(&self) -> String {
@@ -173,7 +173,8 @@ use tokio::net;
 use tracing::{debug, trace};
 use writers::write_err;
 
-use crate::authentication::{generate_auth_data, hash_password, AUTH_PLUGIN_NAME};
+use crate::authentication::{generate_auth_data, verify_password, AUTH_PLUGIN_NAME};
+pub use crate::authentication::{native_password_hash, PasswordHash};
 pub use crate::myc::constants::{ColumnFlags, ColumnType, StatusFlags};
 pub use crate::writers::prepare_column_definitions;
 
@@ -249,6 +250,12 @@ pub trait MySqlShim<W: AsyncWrite + Unpin + Send> {
     /// Provides the server's version information along with ReadySet indications
     fn version(&self) -> String;
 
+    /// The id of this connection, which is sent to the client during the handshake and can be
+    /// used to refer to this connection from other connections (such as with `KILL QUERY`)
+    fn connection_id(&self) -> u32 {
+        8
+    }
+
     /// Called when the client executes a previously prepared statement.
     ///
     /// Any parameters included with the client's command is given in `params`.
@@ -275,10 +282,14 @@ pub trait MySqlShim<W: AsyncWrite + Unpin + Send> {
     /// Called when client switches database.
     async fn on_init(&mut self, _: &str, _: InitWriter<'_, W>) -> io::Result<()>;
 
-    /// Retrieve the password for the user with the given username, if any.
+    /// Retrieve the hash of the password for the user with the given username, if any. See
+    /// [`native_password_hash`] to compute the hash of a cleartext password.
     ///
     /// If the user doesn't exist, return [`None`].
-    fn password_for_username(&self, username: &str) -> Option<Vec<u8>>;
+    async fn password_hash_for_username(&mut self, username: &str) -> Option<PasswordHash>;
+
+    /// Called once the client has successfully authenticated as the user with the given username
+    fn on_authenticated(&mut self, _username: &str) {}
 
     /// Return false if password checking should be skipped entirely
     fn require_authentication(&self) -> bool {
@@ -379,7 +390,7 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
         );
         init_packet.extend_from_slice(&[10]); // protocol 10
         init_packet.extend_from_slice(self.shim.version().as_bytes());
-        init_packet.extend_from_slice(&[0x08, 0x00, 0x00, 0x00]); // TODO: connection ID
+        init_packet.extend_from_slice(&self.shim.connection_id().to_le_bytes());
         init_packet.extend_from_slice(&auth_data[..8]);
         init_packet.push(0);
         init_packet.extend_from_slice(&CAPABILITIES.to_le_bytes()[..2]);
@@ -485,19 +496,19 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
         };
 
         let auth_success = !self.shim.require_authentication()
-            || self
-                .shim
-                .password_for_username(&username)
-                .map_or(false, |password| {
-                    let expected = hash_password(&password, &auth_data);
+            || match self.shim.password_hash_for_username(&username).await {
+                Some(password_hash) => {
                     let actual = handshake_password.as_slice();
-                    trace!(?expected, ?actual);
-                    expected == actual
-                });
+                    trace!(?actual);
+                    verify_password(&password_hash, &auth_data, actual)
+                }
+                None => false,
+            };
 
         if auth_success {
             debug!(%username, "Successfully authenticated client");
-            writers::write_ok_packet(&mut self.writer, 0, 0, StatusFlags::empty()).await?;
+            self.shim.on_authenticated(&username);
+            writers::write_ok_packet(&mut self.writer, 0, 0, StatusFlags::empty(), 0).await?;
         } else {
             debug!(%username, ?client_auth_plugin, "Received incorrect password");
             writers::write_err(
@@ -561,7 +572,8 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
                         })?
                         .long_data
                         .clear();
-                    writers::write_ok_packet(&mut self.writer, 0, 0, StatusFlags::empty()).await?;
+                    writers::write_ok_packet(&mut self.writer, 0, 0, StatusFlags::empty(), 0)
+                        .await?;
                 }
                 Command::Execute { stmt, params } => {
                     let state = stmts.get_mut(&stmt).ok_or_else(|| {
@@ -623,7 +635,8 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
                         .await?;
                 }
                 Command::Ping => {
-                    writers::write_ok_packet(&mut self.writer, 0, 0, StatusFlags::empty()).await?;
+                    writers::write_ok_packet(&mut self.writer, 0, 0, StatusFlags::empty(), 0)
+                        .await?;
                     self.writer.flush().await?;
                 }
                 Command::ComSetOption(_) => {
@@ -632,7 +645,8 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
                     // statements, so failure with any one will be forwarded to the underlying
                     // database as a single statement, meaning that the underlying database does
                     // not need to have multi-statement support enabled for this connection.
-                    writers::write_ok_packet(&mut self.writer, 0, 0, StatusFlags::empty()).await?;
+                    writers::write_ok_packet(&mut self.writer, 0, 0, StatusFlags::empty(), 0)
+                        .await?;
                     self.writer.flush().await?;
                 }
                 Command::Quit => {
@@ -1,7 +1,7 @@
 use std::borrow::Borrow;
 use std::collections::HashMap;
-use std::io;
 use std::sync::Arc;
+use std::{io, mem};
 
 use tokio::io::AsyncWrite;
 
@@ -22,7 +22,7 @@ pub struct InitWriter<'a, W: AsyncWrite + Unpin> {
 impl<'a, W: AsyncWrite + Unpin + 'a> InitWriter<'a, W> {
     /// Tell client that database context has been changed
     pub async fn ok(self) -> io::Result<()> {
-        writers::write_ok_packet(self.writer, 0, 0, StatusFlags::empty()).await
+        writers::write_ok_packet(self.writer, 0, 0, StatusFlags::empty(), 0).await
     }
 
     /// Tell client that there was a problem changing the database context.
@@ -89,9 +89,11 @@ enum Finalizer {
         rows: u64,
         last_insert_id: u64,
         status_flags: Option<StatusFlags>,
+        warnings: u16,
     },
     Eof {
         status_flags: Option<StatusFlags>,
+        warnings: u16,
     },
 }
 
@@ -118,6 +120,9 @@ pub struct QueryResultWriter<'a, W: AsyncWrite + Unpin> {
     pub(crate) is_bin: bool,
     pub(crate) writer: &'a mut PacketWriter<W>,
     last_end: Option<Finalizer>,
+    /// The number of warnings to report to the client for the next resultset completed with
+    /// `complete_one` or `completed`
+    warnings: u16,
 }
 
 impl<'a, W: AsyncWrite + Unpin> QueryResultWriter<'a, W> {
@@ -126,17 +131,23 @@ impl<'a, W: AsyncWrite + Unpin> QueryResultWriter<'a, W> {
             is_bin,
             writer,
             last_end: None,
+            warnings: 0,
         }
     }
 
+    /// Sets the number of warnings to report to the client for the next resultset completed with
+    /// [`complete_one`](struct.QueryResultWriter.html#method.complete_one) or
+    /// [`completed`](struct.QueryResultWriter.html#method.completed), which the client can
+    /// retrieve with `SHOW WARNINGS`.
+    pub fn set_warnings(mut self, warnings: u16) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
     async fn finalize(&mut self, more_exists: bool) -> io::Result<()> {
         let mut status = match self.last_end {
-            Some(Finalizer::Ok {
-                rows: _,
-                last_insert_id: _,
-                status_flags,
-            })
-            | Some(Finalizer::Eof { status_flags }) => {
+            Some(Finalizer::Ok { status_flags, .. })
+            | Some(Finalizer::Eof { status_flags, .. }) => {
                 if let Some(sf) = status_flags {
                     sf
                 } else {
@@ -153,9 +164,14 @@ impl<'a, W: AsyncWrite + Unpin> QueryResultWriter<'a, W> {
             Some(Finalizer::Ok {
                 rows,
                 last_insert_id,
+                warnings,
                 ..
-            }) => writers::write_ok_packet(self.writer, rows, last_insert_id, status).await,
-            Some(Finalizer::Eof { .. }) => writers::write_eof_packet(self.writer, status).await,
+            }) => {
+                writers::write_ok_packet(self.writer, rows, last_insert_id, status, warnings).await
+            }
+            Some(Finalizer::Eof { warnings, .. }) => {
+                writers::write_eof_packet(self.writer, status, warnings).await
+            }
         }
     }
 
@@ -200,6 +216,7 @@ impl<'a, W: AsyncWrite + Unpin> QueryResultWriter<'a, W> {
             rows,
             last_insert_id,
             status_flags,
+            warnings: mem::take(&mut self.warnings),
         });
         Ok(self)
     }
@@ -279,6 +296,8 @@ pub struct RowWriter<'a, W: AsyncWrite + Unpin> {
     // Optionally holds the status flags from the last ok packet that we have
     // received from communicating with mysql over fallback.
     last_status_flags: Option<StatusFlags>,
+    /// The number of warnings to report to the client when finish() gets called
+    warnings: u16,
     /// A buffer to hold row data
     row_data: Option<Vec<u8>>,
 }
@@ -304,6 +323,7 @@ where
 
             finished: false,
             last_status_flags: None,
+            warnings: 0,
 
             row_data: None,
         };
@@ -458,12 +478,14 @@ impl<'a, W: AsyncWrite + Unpin + 'a> RowWriter<'a, W> {
                 rows: self.col as u64,
                 last_insert_id: 0,
                 status_flags: self.last_status_flags.take(),
+                warnings: self.warnings,
             });
             Ok(())
         } else {
             // we wrote out at least one row
             self.result.last_end = Some(Finalizer::Eof {
                 status_flags: self.last_status_flags.take(),
+                warnings: self.warnings,
             });
             Ok(())
         }
@@ -475,6 +497,13 @@ impl<'a, W: AsyncWrite + Unpin + 'a> RowWriter<'a, W> {
         self
     }
 
+    /// Sets the number of warnings to be eventually written out when finish() gets called, which
+    /// the client can retrieve with `SHOW WARNINGS`.
+    pub fn set_warnings(mut self, warnings: u16) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
     /// Reply to the client's query with an error.
     ///
     /// This also calls `no_more_results` implicitly.
@@ -12,9 +12,16 @@ use crate::{Column, ErrorKind};
 pub(crate) async fn write_eof_packet<W: AsyncWrite + Unpin>(
     w: &mut PacketWriter<W>,
     s: StatusFlags,
+    warnings: u16,
 ) -> io::Result<()> {
     let mut buf = w.get_buffer();
-    buf.extend([0xFE, 0x00, 0x00, s.bits() as u8, (s.bits() >> 8) as u8]);
+    buf.extend([
+        0xFE,
+        warnings as u8,
+        (warnings >> 8) as u8,
+        s.bits() as u8,
+        (s.bits() >> 8) as u8,
+    ]);
     w.enqueue_packet(buf);
     Ok(())
 }
@@ -24,6 +31,7 @@ pub(crate) async fn write_ok_packet<W: AsyncWrite + Unpin>(
     rows: u64,
     last_insert_id: u64,
     s: StatusFlags,
+    warnings: u16,
 ) -> io::Result<()> {
     const MAX_OK_PACKET_LEN: usize = 1 + 9 + 9 + 2 + 2;
     let mut buf = w.get_buffer();
@@ -32,7 +40,7 @@ pub(crate) async fn write_ok_packet<W: AsyncWrite + Unpin>(
     buf.write_lenenc_int(rows)?;
     buf.write_lenenc_int(last_insert_id)?;
     buf.write_u16::<LittleEndian>(s.bits())?;
-    buf.write_all(&[0x00, 0x00])?; // no warnings
+    buf.write_u16::<LittleEndian>(warnings)?;
     w.enqueue_packet(buf);
     Ok(())
 }
@@ -183,7 +191,7 @@ where
     if empty && only_eof_on_nonempty {
         Ok(())
     } else {
-        write_eof_packet(w, StatusFlags::empty()).await
+        write_eof_packet(w, StatusFlags::empty(), 0).await
     }
 }
 
@@ -213,5 +221,5 @@ where
     let i = i.into_iter();
     w.enqueue_raw(cached).await?;
     w.seq = w.seq.wrapping_add((1 + i.len()) as u8);
-    write_eof_packet(w, StatusFlags::empty()).await
+    write_eof_packet(w, StatusFlags::empty(), 0).await
 }
@@ -18,8 +18,8 @@ use async_trait::async_trait;
 use mysql::prelude::Queryable;
 use mysql::Row;
 use mysql_srv::{
-    CachedSchema, Column, ErrorKind, InitWriter, MySqlIntermediary, MySqlShim, ParamParser,
-    QueryResultWriter, StatementMetaWriter,
+    native_password_hash, CachedSchema, Column, ErrorKind, InitWriter, MySqlIntermediary,
+    MySqlShim, ParamParser, PasswordHash, QueryResultWriter, StatementMetaWriter,
 };
 use tokio::io::AsyncWrite;
 use tokio::net::tcp::OwnedWriteHalf;
@@ -115,9 +115,9 @@ where
         }
     }
 
-    fn password_for_username(&self, username: &str) -> Option<Vec<u8>> {
+    async fn password_hash_for_username(&mut self, username: &str) -> Option<PasswordHash> {
         if username == "user" {
-            Some(b"password".to_vec())
+            Some(native_password_hash(b"password"))
         } else {
             None
         }
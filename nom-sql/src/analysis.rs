@@ -58,6 +58,8 @@ impl ReferredTables for SqlQuery {
             | SqlQuery::Use(_)
             | SqlQuery::Show(_)
             | SqlQuery::Explain(_)
+            | SqlQuery::WaitForReplication(_)
+            | SqlQuery::KillQuery(_)
             | SqlQuery::DropCache(_)
             | SqlQuery::DropAllCaches(_) => HashSet::new(),
         }
@@ -346,7 +348,7 @@ impl SelectStatement {
                     columns_to_visit.extend(cols);
                     None
                 }
-                JoinConstraint::Empty => None,
+                JoinConstraint::Natural | JoinConstraint::Empty => None,
             }))
             .chain(&self.where_clause)
             .chain(&self.having)
@@ -20,10 +20,10 @@ use crate::{
     CreateCacheStatement, CreateTableStatement, CreateViewStatement, DeleteStatement,
     DropAllCachesStatement, DropCacheStatement, DropTableStatement, DropViewStatement,
     ExplainStatement, Expr, FieldDefinitionExpr, FieldReference, FunctionExpr, GroupByClause,
-    InValue, InsertStatement, JoinClause, JoinConstraint, JoinRightSide, Literal, OrderClause,
-    Relation, SelectSpecification, SelectStatement, SetNames, SetPostgresParameter, SetStatement,
-    SetVariables, ShowStatement, SqlIdentifier, SqlQuery, SqlType, TableExpr, TableKey,
-    UpdateStatement, UseStatement,
+    InValue, InsertStatement, JoinClause, JoinConstraint, JoinRightSide, KillQueryStatement,
+    Literal, OrderClause, Relation, SelectSpecification, SelectStatement, SetNames,
+    SetPostgresParameter, SetStatement, SetVariables, ShowStatement, SqlIdentifier, SqlQuery,
+    SqlType, TableExpr, TableKey, UpdateStatement, UseStatement, WaitForReplicationStatement,
 };
 
 /// Each method of the `Visitor` trait is a hook to be potentially overridden when recursively
@@ -382,7 +382,24 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_explain_statement(
         &mut self,
-        _explain_statement: &'ast ExplainStatement,
+        explain_statement: &'ast ExplainStatement,
+    ) -> Result<(), Self::Error> {
+        match explain_statement {
+            ExplainStatement::ReadySet(stmt) => self.visit_select_statement(stmt),
+            ExplainStatement::Graphviz { .. } | ExplainStatement::LastStatement => Ok(()),
+        }
+    }
+
+    fn visit_wait_for_replication_statement(
+        &mut self,
+        _wait_for_replication_statement: &'ast WaitForReplicationStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn visit_kill_query_statement(
+        &mut self,
+        _kill_query_statement: &'ast KillQueryStatement,
     ) -> Result<(), Self::Error> {
         Ok(())
     }
@@ -546,7 +563,7 @@ pub fn walk_join_constraint<'ast, V: Visitor<'ast>>(
             }
             Ok(())
         }
-        JoinConstraint::Empty => Ok(()),
+        JoinConstraint::Natural | JoinConstraint::Empty => Ok(()),
     }
 }
 
@@ -818,7 +835,9 @@ pub fn walk_column_constraint<'a, V: Visitor<'a>>(
     column_constraint: &'a ColumnConstraint,
 ) -> Result<(), V::Error> {
     match column_constraint {
-        ColumnConstraint::DefaultValue(expr) => visitor.visit_expr(expr),
+        ColumnConstraint::DefaultValue(expr) | ColumnConstraint::Generated { expr, .. } => {
+            visitor.visit_expr(expr)
+        }
         ColumnConstraint::Null
         | ColumnConstraint::NotNull
         | ColumnConstraint::CharacterSet(_)
@@ -1077,6 +1096,10 @@ pub fn walk_sql_query<'a, V: Visitor<'a>>(
         SqlQuery::Use(statement) => visitor.visit_use_statement(statement),
         SqlQuery::Show(statement) => visitor.visit_show_statement(statement),
         SqlQuery::Explain(statement) => visitor.visit_explain_statement(statement),
+        SqlQuery::WaitForReplication(statement) => {
+            visitor.visit_wait_for_replication_statement(statement)
+        }
+        SqlQuery::KillQuery(statement) => visitor.visit_kill_query_statement(statement),
     }
 }
 
@@ -20,10 +20,10 @@ use crate::{
     CreateCacheStatement, CreateTableStatement, CreateViewStatement, DeleteStatement,
     DropAllCachesStatement, DropCacheStatement, DropTableStatement, DropViewStatement,
     ExplainStatement, Expr, FieldDefinitionExpr, FieldReference, FunctionExpr, GroupByClause,
-    InValue, InsertStatement, JoinClause, JoinConstraint, JoinRightSide, Literal, OrderClause,
-    Relation, SelectSpecification, SelectStatement, SetNames, SetPostgresParameter, SetStatement,
-    SetVariables, ShowStatement, SqlIdentifier, SqlQuery, SqlType, TableExpr, TableKey,
-    UpdateStatement, UseStatement,
+    InValue, InsertStatement, JoinClause, JoinConstraint, JoinRightSide, KillQueryStatement,
+    Literal, OrderClause, Relation, SelectSpecification, SelectStatement, SetNames,
+    SetPostgresParameter, SetStatement, SetVariables, ShowStatement, SqlIdentifier, SqlQuery,
+    SqlType, TableExpr, TableKey, UpdateStatement, UseStatement, WaitForReplicationStatement,
 };
 
 /// Each method of the `VisitorMut` trait is a hook to be potentially overridden when recursively
@@ -394,7 +394,24 @@ pub trait VisitorMut<'ast>: Sized {
 
     fn visit_explain_statement(
         &mut self,
-        _explain_statement: &'ast mut ExplainStatement,
+        explain_statement: &'ast mut ExplainStatement,
+    ) -> Result<(), Self::Error> {
+        match explain_statement {
+            ExplainStatement::ReadySet(stmt) => self.visit_select_statement(stmt),
+            ExplainStatement::Graphviz { .. } | ExplainStatement::LastStatement => Ok(()),
+        }
+    }
+
+    fn visit_wait_for_replication_statement(
+        &mut self,
+        _wait_for_replication_statement: &'ast mut WaitForReplicationStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn visit_kill_query_statement(
+        &mut self,
+        _kill_query_statement: &'ast mut KillQueryStatement,
     ) -> Result<(), Self::Error> {
         Ok(())
     }
@@ -558,7 +575,7 @@ pub fn walk_join_constraint<'ast, V: VisitorMut<'ast>>(
             }
             Ok(())
         }
-        JoinConstraint::Empty => Ok(()),
+        JoinConstraint::Natural | JoinConstraint::Empty => Ok(()),
     }
 }
 
@@ -830,7 +847,9 @@ pub fn walk_column_constraint<'a, V: VisitorMut<'a>>(
     column_constraint: &'a mut ColumnConstraint,
 ) -> Result<(), V::Error> {
     match column_constraint {
-        ColumnConstraint::DefaultValue(expr) => visitor.visit_expr(expr),
+        ColumnConstraint::DefaultValue(expr) | ColumnConstraint::Generated { expr, .. } => {
+            visitor.visit_expr(expr)
+        }
         ColumnConstraint::Null
         | ColumnConstraint::NotNull
         | ColumnConstraint::CharacterSet(_)
@@ -1089,6 +1108,10 @@ pub fn walk_sql_query<'a, V: VisitorMut<'a>>(
         SqlQuery::Use(statement) => visitor.visit_use_statement(statement),
         SqlQuery::Show(statement) => visitor.visit_show_statement(statement),
         SqlQuery::Explain(statement) => visitor.visit_explain_statement(statement),
+        SqlQuery::WaitForReplication(statement) => {
+            visitor.visit_wait_for_replication_statement(statement)
+        }
+        SqlQuery::KillQuery(statement) => visitor.visit_kill_query_statement(statement),
     }
 }
 
@@ -5,7 +5,7 @@ use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case};
 use nom::combinator::{map, opt};
 use nom::multi::many0;
-use nom::sequence::{delimited, preceded, tuple};
+use nom::sequence::{delimited, preceded, terminated, tuple};
 use nom_locate::LocatedSpan;
 use serde::{Deserialize, Serialize};
 
@@ -73,6 +73,13 @@ pub enum ColumnConstraint {
     /// NOTE(grfn): Yes, this really is its own special thing, not just an expression - see
     /// <https://dev.mysql.com/doc/refman/8.0/en/timestamp-initialization.html>
     OnUpdateCurrentTimestamp,
+    /// A generated column, whose value is computed from an expression over the other columns in
+    /// the row: `[GENERATED ALWAYS] AS (expr) [VIRTUAL | STORED]`
+    Generated {
+        expr: Expr,
+        /// Whether the column is stored (materialized upstream) rather than virtual
+        stored: bool,
+    },
 }
 
 impl fmt::Display for ColumnConstraint {
@@ -89,6 +96,14 @@ impl fmt::Display for ColumnConstraint {
             ColumnConstraint::PrimaryKey => write!(f, "PRIMARY KEY"),
             ColumnConstraint::Unique => write!(f, "UNIQUE"),
             ColumnConstraint::OnUpdateCurrentTimestamp => write!(f, "ON UPDATE CURRENT_TIMESTAMP"),
+            ColumnConstraint::Generated { ref expr, stored } => {
+                write!(f, "GENERATED ALWAYS AS ({}) ", expr)?;
+                if stored {
+                    write!(f, "STORED")
+                } else {
+                    write!(f, "VIRTUAL")
+                }
+            }
         }
     }
 }
@@ -143,6 +158,22 @@ impl ColumnSpecification {
             _ => None,
         })
     }
+
+    /// If this is a generated column, returns the expression used to compute its value
+    pub fn generated_expr(&self) -> Option<&Expr> {
+        self.constraints.iter().find_map(|c| match c {
+            ColumnConstraint::Generated { ref expr, .. } => Some(expr),
+            _ => None,
+        })
+    }
+
+    /// Returns the name of the collation declared for this column with a `COLLATE` clause, if any
+    pub fn collation(&self) -> Option<&str> {
+        self.constraints.iter().find_map(|c| match c {
+            ColumnConstraint::Collation(ref collation) => Some(collation.as_str()),
+            _ => None,
+        })
+    }
 }
 
 fn default(
@@ -159,6 +190,44 @@ fn default(
     }
 }
 
+fn generated(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], ColumnConstraint> {
+    move |i| {
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = opt(tuple((
+            tag_no_case("generated"),
+            whitespace1,
+            tag_no_case("always"),
+            whitespace1,
+        )))(i)?;
+        let (i, _) = tag_no_case("as")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, expr) = delimited(
+            terminated(tag("("), whitespace0),
+            expression(dialect),
+            preceded(whitespace0, tag(")")),
+        )(i)?;
+        let (i, stored) = opt(preceded(
+            whitespace1,
+            alt((
+                map(tag_no_case("stored"), |_| true),
+                map(tag_no_case("virtual"), |_| false),
+            )),
+        ))(i)?;
+        let (i, _) = whitespace0(i)?;
+
+        Ok((
+            i,
+            ColumnConstraint::Generated {
+                expr,
+                // Generated columns are virtual by default
+                stored: stored.unwrap_or(false),
+            },
+        ))
+    }
+}
+
 pub fn on_update_current_timestamp(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], ColumnConstraint> {
     let (i, _) = tag_no_case("on")(i)?;
     let (i, _) = whitespace1(i)?;
@@ -228,6 +297,7 @@ pub fn column_constraint(
             null,
             auto_increment,
             default(dialect),
+            generated(dialect),
             primary_key,
             unique,
             character_set,
@@ -276,7 +346,7 @@ mod tests {
 
     mod mysql {
         use super::*;
-        use crate::FunctionExpr;
+        use crate::{BinaryOperator, FunctionExpr};
 
         #[test]
         fn multiple_constraints() {
@@ -336,6 +406,49 @@ mod tests {
                 ColumnConstraint::DefaultValue(Expr::Literal(Literal::Boolean(true)))
             ));
         }
+
+        #[test]
+        fn generated_stored() {
+            let input = b"`c` int GENERATED ALWAYS AS (`a` + `b`) STORED NOT NULL";
+            let cspec = column_specification(Dialect::MySQL)(LocatedSpan::new(input))
+                .unwrap()
+                .1;
+            assert_eq!(
+                cspec.constraints,
+                vec![
+                    ColumnConstraint::Generated {
+                        expr: Expr::BinaryOp {
+                            lhs: Box::new(Expr::Column("a".into())),
+                            op: BinaryOperator::Add,
+                            rhs: Box::new(Expr::Column("b".into())),
+                        },
+                        stored: true,
+                    },
+                    ColumnConstraint::NotNull,
+                ]
+            );
+            assert!(cspec.generated_expr().is_some());
+
+            let round_tripped = column_specification(Dialect::MySQL)(LocatedSpan::new(
+                cspec.to_string().as_bytes(),
+            ))
+            .unwrap()
+            .1;
+            assert_eq!(round_tripped, cspec);
+        }
+
+        #[test]
+        fn generated_virtual_by_default() {
+            let input = b"`c` int AS (`a` * 2)";
+            let cspec = column_specification(Dialect::MySQL)(LocatedSpan::new(input))
+                .unwrap()
+                .1;
+            assert_eq!(cspec.constraints.len(), 1);
+            assert!(matches!(
+                cspec.constraints[0],
+                ColumnConstraint::Generated { stored: false, .. }
+            ));
+        }
     }
 
     mod postgres {
@@ -3,7 +3,7 @@ use std::{fmt, str};
 
 use derive_more::{Display, From};
 use nom::branch::alt;
-use nom::bytes::complete::{is_not, tag, tag_no_case};
+use nom::bytes::complete::{is_not, tag, tag_no_case, take};
 use nom::character::complete::digit1;
 use nom::combinator::{map, map_res, opt};
 use nom::multi::{separated_list0, separated_list1};
@@ -22,7 +22,7 @@ use crate::expression::expression;
 use crate::order::{order_type, OrderType};
 use crate::select::{nested_selection, selection, SelectStatement};
 use crate::table::{relation, Relation};
-use crate::whitespace::{whitespace0, whitespace1};
+use crate::whitespace::{eol_comment, multiline_comment, whitespace0, whitespace1};
 use crate::{Dialect, NomSqlResult, SqlIdentifier};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
@@ -148,11 +148,31 @@ pub struct CreateCacheStatement {
     pub name: Option<Relation>,
     pub inner: CacheInner,
     pub always: bool,
+    /// The type of index to use for the cache's reader, overriding the one inferred from the
+    /// query, if requested with a `/* readyset: index=<btree|hash> */` annotation comment
+    /// anywhere in the statement
+    #[serde(default)]
+    pub index_type: Option<IndexType>,
+    /// The maximum number of bytes of memory the cache's reader may use before it starts evicting
+    /// its least recently used keys, if requested with a `/* readyset: max_mem=<size> */`
+    /// annotation comment anywhere in the statement
+    #[serde(default)]
+    pub max_mem: Option<u64>,
 }
 
 impl fmt::Display for CreateCacheStatement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "CREATE CACHE ")?;
+        if let Some(index_type) = self.index_type {
+            let index_type = match index_type {
+                IndexType::BTree => "btree",
+                IndexType::Hash => "hash",
+            };
+            write!(f, "/* readyset: index={} */ ", index_type)?;
+        }
+        if let Some(max_mem) = self.max_mem {
+            write!(f, "/* readyset: max_mem={} */ ", max_mem)?;
+        }
         if self.always {
             write!(f, "ALWAYS ")?;
         }
@@ -676,11 +696,84 @@ pub fn cached_query_inner(
     }
 }
 
+/// Returns the contents of all the multiline (`/* ... */`) comments in the given query text,
+/// skipping over string literals, quoted identifiers and end-of-line comments so that the text of a
+/// comment inside any of those isn't mistaken for a comment itself
+fn multiline_comments(dialect: Dialect, mut query: LocatedSpan<&[u8]>) -> Vec<&[u8]> {
+    let mut comments = vec![];
+    while !query.fragment().is_empty() {
+        if let Ok((rest, comment)) = multiline_comment(query) {
+            comments.push(comment);
+            query = rest;
+            continue;
+        }
+
+        match alt((
+            map(dialect.string_literal(), |_| ()),
+            map(dialect.identifier(), |_| ()),
+            map(eol_comment("--"), |_| ()),
+            map(eol_comment("#"), |_| ()),
+            map(take(1usize), |_| ()),
+        ))(query)
+        {
+            Ok((rest, ())) => query = rest,
+            Err(_) => break,
+        }
+    }
+    comments
+}
+
+/// Look for an annotation comment of the form `/* readyset: <key>=<value> */` with the given key
+/// in the given query text, returning its (trimmed) value if found
+fn annotation<'a>(dialect: Dialect, query: &'a [u8], key: &str) -> Option<&'a str> {
+    multiline_comments(dialect, LocatedSpan::new(query))
+        .into_iter()
+        .find_map(|comment| {
+            let comment = str::from_utf8(comment).ok()?;
+            let (k, value) = comment.trim().strip_prefix("readyset:")?.split_once('=')?;
+            k.trim().eq_ignore_ascii_case(key).then(|| value.trim())
+        })
+}
+
+/// Look for an annotation comment of the form `/* readyset: index=<btree|hash> */` in the given
+/// query text, returning the index type it requests if found
+fn index_type_annotation(dialect: Dialect, query: &[u8]) -> Option<IndexType> {
+    match annotation(dialect, query, "index")?
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "btree" => Some(IndexType::BTree),
+        "hash" => Some(IndexType::Hash),
+        _ => None,
+    }
+}
+
+/// Look for an annotation comment of the form `/* readyset: max_mem=<size> */` in the given query
+/// text, returning the number of bytes it requests if found. The size is a number of bytes,
+/// optionally followed by one of the (case-insensitive, power-of-two) units `B`, `KB`, `MB` or `GB`
+fn max_mem_annotation(dialect: Dialect, query: &[u8]) -> Option<u64> {
+    let value = annotation(dialect, query, "max_mem")?.to_ascii_uppercase();
+    let (digits, unit) = value.split_at(
+        value
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(value.len()),
+    );
+    let multiplier: u64 = match unit.trim() {
+        "" | "B" => 1,
+        "KB" => 1 << 10,
+        "MB" => 1 << 20,
+        "GB" => 1 << 30,
+        _ => return None,
+    };
+    digits.parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
 /// Parse a [`CreateCacheStatement`]
 pub fn create_cached_query(
     dialect: Dialect,
 ) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], CreateCacheStatement> {
     move |i| {
+        let start = i;
         let (i, _) = tag_no_case("create")(i)?;
         let (i, _) = whitespace1(i)?;
         let (i, _) = tag_no_case("cache")(i)?;
@@ -690,12 +783,17 @@ pub fn create_cached_query(
         let (i, _) = tag_no_case("from")(i)?;
         let (i, _) = whitespace1(i)?;
         let (i, inner) = cached_query_inner(dialect)(i)?;
+        // Comments are consumed as whitespace by the parsers above, so look for annotations in
+        // the raw text of the statement
+        let text = &start.fragment()[..(i.location_offset() - start.location_offset())];
         Ok((
             i,
             CreateCacheStatement {
                 name,
                 inner,
                 always: always.is_some(),
+                index_type: index_type_annotation(dialect, text),
+                max_mem: max_mem_annotation(dialect, text),
             },
         ))
     }
@@ -1444,6 +1542,92 @@ mod tests {
             );
         }
 
+        #[test]
+        fn create_cached_query_with_index_type_annotation() {
+            let res = test_parse!(
+                create_cached_query(Dialect::MySQL),
+                b"CREATE CACHE foo FROM SELECT /* readyset: index=btree */ id FROM users WHERE name = ?"
+            );
+            assert_eq!(res.index_type, Some(IndexType::BTree));
+
+            let res = test_parse!(
+                create_cached_query(Dialect::MySQL),
+                b"CREATE CACHE /* readyset: index = HASH */ FROM SELECT id FROM users WHERE name = ?"
+            );
+            assert_eq!(res.index_type, Some(IndexType::Hash));
+
+            let res = test_parse!(
+                create_cached_query(Dialect::MySQL),
+                b"CREATE CACHE FROM SELECT /* not an annotation */ id FROM users WHERE name = ?"
+            );
+            assert_eq!(res.index_type, None);
+
+            let res = test_parse!(
+                create_cached_query(Dialect::MySQL),
+                b"CREATE CACHE FROM SELECT id FROM users WHERE name = '/* readyset: index=hash */'"
+            );
+            assert_eq!(res.index_type, None);
+
+            let res = test_parse!(
+                create_cached_query(Dialect::PostgreSQL),
+                b"CREATE CACHE FROM SELECT \"/* readyset: index=hash */\" FROM users WHERE name = $1"
+            );
+            assert_eq!(res.index_type, None);
+        }
+
+        #[test]
+        fn display_create_query_cache_with_index_type() {
+            let stmt = test_parse!(
+                create_cached_query(Dialect::MySQL),
+                b"CREATE CACHE foo FROM SELECT /* readyset: index=btree */ id FROM users WHERE name = ?"
+            );
+            let res = stmt.to_string();
+            assert_eq!(
+                res,
+                "CREATE CACHE /* readyset: index=btree */ `foo` FROM SELECT `id` FROM `users` WHERE (`name` = ?)"
+            );
+            assert_eq!(
+                test_parse!(create_cached_query(Dialect::MySQL), res.as_bytes()),
+                stmt
+            );
+        }
+
+        #[test]
+        fn create_cached_query_with_max_mem_annotation() {
+            let res = test_parse!(
+                create_cached_query(Dialect::MySQL),
+                b"CREATE CACHE foo FROM SELECT /* readyset: max_mem=100MB */ id FROM users WHERE name = ?"
+            );
+            assert_eq!(res.max_mem, Some(100 * 1024 * 1024));
+            assert_eq!(res.index_type, None);
+
+            let res = test_parse!(
+                create_cached_query(Dialect::MySQL),
+                b"CREATE CACHE /* readyset: index=hash */ /* readyset: max_mem = 512 */ FROM SELECT id FROM users WHERE name = ?"
+            );
+            assert_eq!(res.max_mem, Some(512));
+            assert_eq!(res.index_type, Some(IndexType::Hash));
+
+            let res = test_parse!(
+                create_cached_query(Dialect::MySQL),
+                b"CREATE CACHE FROM SELECT /* readyset: max_mem=lots */ id FROM users WHERE name = ?"
+            );
+            assert_eq!(res.max_mem, None);
+
+            let res = test_parse!(
+                create_cached_query(Dialect::MySQL),
+                b"CREATE CACHE foo FROM SELECT /* readyset: max_mem=2kb */ id FROM users WHERE name = ?"
+            );
+            assert_eq!(res.max_mem, Some(2048));
+            assert_eq!(
+                test_parse!(
+                    create_cached_query(Dialect::MySQL),
+                    res.to_string().as_bytes()
+                ),
+                res
+            );
+        }
+
         #[test]
         fn lobsters_indexes() {
             let qstring = "CREATE TABLE `comments` (
@@ -8,8 +8,9 @@ use nom_locate::LocatedSpan;
 use serde::{Deserialize, Serialize};
 
 use crate::common::statement_terminator;
+use crate::select::{nested_selection, SelectStatement};
 use crate::whitespace::whitespace1;
-use crate::NomSqlResult;
+use crate::{Dialect, NomSqlResult};
 
 /// EXPLAIN statements
 ///
@@ -20,6 +21,11 @@ pub enum ExplainStatement {
     Graphviz { simplified: bool },
     /// Provides metadata about the last statement that was executed.
     LastStatement,
+    /// Describe the plan ReadySet would use to cache the given query.
+    ///
+    /// Plain `EXPLAIN <query>` isn't parsed as an [`ExplainStatement`], so that it's proxied to
+    /// the upstream database to return the upstream's own query plan.
+    ReadySet(Box<SelectStatement>),
 }
 
 impl Display for ExplainStatement {
@@ -33,6 +39,7 @@ impl Display for ExplainStatement {
                 write!(f, "GRAPHVIZ;")
             }
             ExplainStatement::LastStatement => write!(f, "LAST STATEMENT;"),
+            ExplainStatement::ReadySet(stmt) => write!(f, "READYSET {};", stmt),
         }
     }
 }
@@ -48,28 +55,45 @@ fn explain_graphviz(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], ExplainStateme
     ))
 }
 
-pub(crate) fn explain_statement(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], ExplainStatement> {
-    let (i, _) = tag_no_case("explain")(i)?;
-    let (i, _) = whitespace1(i)?;
-    let (i, stmt) = alt((
-        explain_graphviz,
-        map(
-            tuple((tag_no_case("last"), whitespace1, tag_no_case("statement"))),
-            |_| ExplainStatement::LastStatement,
-        ),
-    ))(i)?;
-    let (i, _) = statement_terminator(i)?;
-    Ok((i, stmt))
+fn explain_readyset(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], ExplainStatement> {
+    move |i| {
+        let (i, _) = tag_no_case("readyset")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, stmt) = nested_selection(dialect)(i)?;
+        Ok((i, ExplainStatement::ReadySet(Box::new(stmt))))
+    }
+}
+
+pub(crate) fn explain_statement(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], ExplainStatement> {
+    move |i| {
+        let (i, _) = tag_no_case("explain")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, stmt) = alt((
+            explain_graphviz,
+            map(
+                tuple((tag_no_case("last"), whitespace1, tag_no_case("statement"))),
+                |_| ExplainStatement::LastStatement,
+            ),
+            explain_readyset(dialect),
+        ))(i)?;
+        let (i, _) = statement_terminator(i)?;
+        Ok((i, stmt))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Relation, TableExpr};
 
     #[test]
     fn explain_graphviz() {
         assert_eq!(
-            explain_statement(LocatedSpan::new(b"explain graphviz;"))
+            explain_statement(Dialect::MySQL)(LocatedSpan::new(b"explain graphviz;"))
                 .unwrap()
                 .1,
             ExplainStatement::Graphviz { simplified: false }
@@ -79,10 +103,39 @@ mod tests {
     #[test]
     fn explain_last_statement() {
         assert_eq!(
-            explain_statement(LocatedSpan::new(b"explain last statement;"))
+            explain_statement(Dialect::MySQL)(LocatedSpan::new(b"explain last statement;"))
                 .unwrap()
                 .1,
             ExplainStatement::LastStatement
         );
     }
+
+    #[test]
+    fn explain_readyset() {
+        let res = explain_statement(Dialect::MySQL)(LocatedSpan::new(
+            b"EXPLAIN READYSET SELECT id FROM users WHERE name = ?;",
+        ))
+        .unwrap()
+        .1;
+        let stmt = match &res {
+            ExplainStatement::ReadySet(stmt) => stmt,
+            _ => panic!("unexpected explain statement: {res:?}"),
+        };
+        assert_eq!(stmt.tables, vec![TableExpr::from(Relation::from("users"))]);
+        let displayed = res.to_string();
+        assert_eq!(
+            explain_statement(Dialect::MySQL)(LocatedSpan::new(displayed.as_bytes()))
+                .unwrap()
+                .1,
+            res
+        );
+    }
+
+    #[test]
+    fn plain_explain_is_not_parsed() {
+        assert!(explain_statement(Dialect::MySQL)(LocatedSpan::new(
+            b"EXPLAIN SELECT id FROM users;"
+        ))
+        .is_err());
+    }
 }
@@ -184,6 +184,11 @@ pub enum BinaryOperator {
     Multiply,
     /// `/`
     Divide,
+    /// `DIV`
+    ///
+    /// MySQL-specific integer division operator. Divides its operands and discards the fractional
+    /// part of the result.
+    IntDivide,
 
     /// `?`
     ///
@@ -290,6 +295,7 @@ impl Display for BinaryOperator {
             Self::Subtract => "-",
             Self::Multiply => "*",
             Self::Divide => "/",
+            Self::IntDivide => "DIV",
             Self::QuestionMark => "?",
             Self::QuestionMarkPipe => "?|",
             Self::QuestionMarkAnd => "?&",
@@ -577,6 +583,9 @@ fn infix_no_and_or(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], TokenTree> {
             Ok((i, BinaryOperator::IsNot))
         },
         map(pair(tag_no_case("is"), whitespace1), |_| BinaryOperator::Is),
+        map(pair(tag_no_case("div"), whitespace1), |_| {
+            BinaryOperator::IntDivide
+        }),
         // Sigils are separated due to `alt` limit.
         //
         // NOTE: The order here matters or else some of these will be incorrectly partially parsed,
@@ -786,6 +795,7 @@ where
             Infix(Subtract) => Affix::Infix(Precedence(11), Associativity::Right),
             Infix(Multiply) => Affix::Infix(Precedence(12), Associativity::Right),
             Infix(Divide) => Affix::Infix(Precedence(12), Associativity::Right),
+            Infix(IntDivide) => Affix::Infix(Precedence(12), Associativity::Right),
             Prefix(Not) => Affix::Prefix(Precedence(6)),
             Prefix(Neg) => Affix::Prefix(Precedence(5)),
             Primary(_) => Affix::Nilfix,
@@ -1921,6 +1931,11 @@ mod tests {
                  and (dayofweek(`c`.`d`) between 1 and 3))",
                 )
             }
+
+            #[test]
+            fn int_divide() {
+                parses_same(Dialect::MySQL, "x DIV 2 + 1 = 3", "((x DIV 2) + 1) = 3")
+            }
         }
 
         mod conditions {
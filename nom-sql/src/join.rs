@@ -54,7 +54,10 @@ pub enum JoinOperator {
 
 impl JoinOperator {
     pub fn is_inner_join(&self) -> bool {
-        matches!(self, JoinOperator::Join | JoinOperator::InnerJoin)
+        matches!(
+            self,
+            JoinOperator::Join | JoinOperator::InnerJoin | JoinOperator::StraightJoin
+        )
     }
 }
 
@@ -67,7 +70,7 @@ impl fmt::Display for JoinOperator {
             JoinOperator::RightJoin => write!(f, "RIGHT JOIN")?,
             JoinOperator::InnerJoin => write!(f, "INNER JOIN")?,
             JoinOperator::CrossJoin => write!(f, "CROSS JOIN")?,
-            JoinOperator::StraightJoin => write!(f, "STRAIGHT JOIN")?,
+            JoinOperator::StraightJoin => write!(f, "STRAIGHT_JOIN")?,
         }
         Ok(())
     }
@@ -77,6 +80,9 @@ impl fmt::Display for JoinOperator {
 pub enum JoinConstraint {
     On(Expr),
     Using(Vec<Column>),
+    /// A `NATURAL` join, which implicitly joins on all the columns with the same names in both
+    /// sides of the join
+    Natural,
     Empty,
 }
 
@@ -93,7 +99,7 @@ impl fmt::Display for JoinConstraint {
                     .collect::<Vec<_>>()
                     .join(", ")
             )?,
-            JoinConstraint::Empty => {}
+            JoinConstraint::Natural | JoinConstraint::Empty => {}
         }
         Ok(())
     }
@@ -150,4 +156,39 @@ mod tests {
         assert_eq!(q, expected_stmt);
         assert_eq!(expected, q.to_string());
     }
+
+    #[test]
+    fn straight_join() {
+        let qstring = "SELECT tags.* FROM tags \
+                       STRAIGHT_JOIN taggings ON (tags.id = taggings.tag_id)";
+        let expected = "SELECT `tags`.* FROM `tags` \
+                       STRAIGHT_JOIN `taggings` ON (`tags`.`id` = `taggings`.`tag_id`)";
+
+        let q = selection(Dialect::MySQL)(LocatedSpan::new(qstring.as_bytes()))
+            .unwrap()
+            .1;
+        assert_eq!(q.join[0].operator, JoinOperator::StraightJoin);
+        assert!(q.join[0].operator.is_inner_join());
+        assert_eq!(expected, q.to_string());
+    }
+
+    #[test]
+    fn natural_join() {
+        let qstring = "SELECT * FROM t1 NATURAL JOIN t2";
+        let expected = "SELECT * FROM `t1` NATURAL JOIN `t2`";
+
+        let q = selection(Dialect::MySQL)(LocatedSpan::new(qstring.as_bytes()))
+            .unwrap()
+            .1;
+        assert_eq!(q.join[0].operator, JoinOperator::Join);
+        assert_eq!(q.join[0].constraint, JoinConstraint::Natural);
+        assert_eq!(expected, q.to_string());
+    }
+
+    #[test]
+    fn natural_join_with_constraint() {
+        let qstring = "SELECT * FROM t1 NATURAL JOIN t2 ON t1.x = t2.x";
+        let res = selection(Dialect::MySQL)(LocatedSpan::new(qstring.as_bytes()));
+        assert!(res.is_err());
+    }
 }
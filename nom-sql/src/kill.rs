@@ -0,0 +1,64 @@
+use std::fmt::{self, Display};
+
+use nom::bytes::complete::tag_no_case;
+use nom::character::complete::digit1;
+use nom::combinator::map_parser;
+use nom_locate::LocatedSpan;
+use serde::{Deserialize, Serialize};
+
+use crate::common::statement_terminator;
+use crate::whitespace::whitespace1;
+use crate::NomSqlResult;
+
+/// `KILL QUERY` statements, which cancel the statement currently being executed by the connection
+/// with the given id
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct KillQueryStatement {
+    /// The id of the connection whose current statement should be cancelled
+    pub connection_id: u64,
+}
+
+impl Display for KillQueryStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KILL QUERY {}", self.connection_id)
+    }
+}
+
+pub(crate) fn kill_query(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], KillQueryStatement> {
+    let (i, _) = tag_no_case("kill")(i)?;
+    let (i, _) = whitespace1(i)?;
+    let (i, _) = tag_no_case("query")(i)?;
+    let (i, _) = whitespace1(i)?;
+    let (i, connection_id) = map_parser(digit1, nom::character::complete::u64)(i)?;
+    let (i, _) = statement_terminator(i)?;
+    Ok((i, KillQueryStatement { connection_id }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_kill_query() {
+        for q in ["KILL QUERY 8", "kill  query 8;"] {
+            assert_eq!(
+                kill_query(LocatedSpan::new(q.as_bytes())).unwrap().1,
+                KillQueryStatement { connection_id: 8 }
+            );
+        }
+    }
+
+    #[test]
+    fn kill_query_requires_connection_id() {
+        assert!(kill_query(LocatedSpan::new(b"KILL QUERY".as_slice())).is_err());
+        assert!(kill_query(LocatedSpan::new(b"KILL QUERY abc".as_slice())).is_err());
+    }
+
+    #[test]
+    fn format_kill_query() {
+        assert_eq!(
+            KillQueryStatement { connection_id: 8 }.to_string(),
+            "KILL QUERY 8"
+        );
+    }
+}
@@ -30,6 +30,7 @@ pub use self::explain::ExplainStatement;
 pub use self::expression::{BinaryOperator, Expr, FunctionExpr, InValue, UnaryOperator};
 pub use self::insert::InsertStatement;
 pub use self::join::{JoinConstraint, JoinOperator, JoinRightSide};
+pub use self::kill::KillQueryStatement;
 pub use self::literal::{
     embedded_literal, literal, raw_string_literal, utf8_string_literal, Double, Float,
     ItemPlaceholder, Literal, QuotingStyle,
@@ -48,6 +49,7 @@ pub use self::sql_type::{EnumVariants, SqlType};
 pub use self::table::{replicator_table_list, Relation, TableExpr};
 pub use self::update::UpdateStatement;
 pub use self::use_statement::UseStatement;
+pub use self::wait::WaitForReplicationStatement;
 
 pub mod parser;
 
@@ -70,6 +72,7 @@ mod expression;
 mod insert;
 mod join;
 mod keywords;
+mod kill;
 mod literal;
 mod order;
 mod rename;
@@ -82,6 +85,7 @@ mod table;
 mod transaction;
 mod update;
 mod use_statement;
+mod wait;
 pub mod whitespace;
 
 pub type NomSqlResult<I, O> = IResult<LocatedSpan<I>, O, NomSqlError<I>>;
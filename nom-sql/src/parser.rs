@@ -2,7 +2,9 @@ use std::{fmt, str};
 
 use launchpad::redacted::Sensitive;
 use nom::branch::alt;
-use nom::combinator::map;
+use nom::combinator::{eof, map};
+use nom::multi::many1;
+use nom::sequence::terminated;
 use nom_locate::LocatedSpan;
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +22,7 @@ use crate::drop::{
 use crate::explain::{explain_statement, ExplainStatement};
 use crate::expression::expression;
 use crate::insert::{insertion, InsertStatement};
+use crate::kill::{kill_query, KillQueryStatement};
 use crate::rename::{rename_table, RenameTableStatement};
 use crate::select::{selection, SelectStatement};
 use crate::set::{set, SetStatement};
@@ -31,6 +34,7 @@ use crate::transaction::{
 };
 use crate::update::{updating, UpdateStatement};
 use crate::use_statement::{use_statement, UseStatement};
+use crate::wait::{wait_for_replication, WaitForReplicationStatement};
 use crate::whitespace::whitespace0;
 use crate::{Dialect, DropAllCachesStatement, Expr, NomSqlResult, SqlType, TableKey};
 
@@ -58,6 +62,8 @@ pub enum SqlQuery {
     Use(UseStatement),
     Show(ShowStatement),
     Explain(ExplainStatement),
+    WaitForReplication(WaitForReplicationStatement),
+    KillQuery(KillQueryStatement),
 }
 
 impl fmt::Display for SqlQuery {
@@ -84,6 +90,8 @@ impl fmt::Display for SqlQuery {
             SqlQuery::Use(ref use_db) => write!(f, "{}", use_db),
             SqlQuery::Show(ref show) => write!(f, "{}", show),
             SqlQuery::Explain(ref explain) => write!(f, "{}", explain),
+            SqlQuery::WaitForReplication(ref wait) => write!(f, "{}", wait),
+            SqlQuery::KillQuery(ref kill) => write!(f, "{}", kill),
         }
     }
 }
@@ -121,6 +129,8 @@ impl SqlQuery {
             Self::Use(_) => "USE",
             Self::Show(_) => "SHOW",
             Self::Explain(_) => "EXPLAIN",
+            Self::WaitForReplication(_) => "WAIT FOR REPLICATION",
+            Self::KillQuery(_) => "KILL QUERY",
         }
     }
 
@@ -155,11 +165,25 @@ pub fn sql_query(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResul
             map(rename_table(dialect), SqlQuery::RenameTable),
             map(use_statement(dialect), SqlQuery::Use),
             map(show(dialect), SqlQuery::Show),
-            map(explain_statement, SqlQuery::Explain),
+            // `alt` only supports up to 21 alternatives, so the remaining statements are parsed
+            // in a nested `alt`
+            alt((
+                map(explain_statement(dialect), SqlQuery::Explain),
+                map(wait_for_replication, SqlQuery::WaitForReplication),
+                map(kill_query, SqlQuery::KillQuery),
+            )),
         ))(i)
     }
 }
 
+/// Parse a sequence of one or more SQL statements, separated by semicolons, consuming the entire
+/// input
+pub fn sql_queries(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Vec<SqlQuery>> {
+    move |i| terminated(many1(sql_query(dialect)), terminated(whitespace0, eof))(i)
+}
+
 macro_rules! export_parser {
     ($parser: ident -> $ret:ty, $parse_bytes: ident, $parse: ident) => {
         pub fn $parse_bytes<T>(dialect: Dialect, input: T) -> Result<$ret, String>
@@ -186,6 +210,7 @@ macro_rules! export_parser {
 }
 
 export_parser!(sql_query -> SqlQuery, parse_query_bytes, parse_query);
+export_parser!(sql_queries -> Vec<SqlQuery>, parse_queries_bytes, parse_queries);
 export_parser!(selection -> SelectStatement, parse_select_statement_bytes, parse_select_statement);
 export_parser!(expression -> Expr, parse_expr_bytes, parse_expr);
 export_parser!(create_table -> CreateTableStatement, parse_create_table_bytes, parse_create_table);
@@ -351,6 +376,32 @@ mod tests {
         assert_eq!(res, SqlQuery::DropAllCaches(DropAllCachesStatement {}));
     }
 
+    #[test]
+    fn multiple_queries() {
+        let res = parse_queries(
+            Dialect::MySQL,
+            "SELECT * FROM t WHERE x = 'a;b';
+             -- a comment; with a semicolon
+             CREATE CACHE q FROM SELECT y FROM t WHERE x = ?;
+
+             DROP ALL CACHES",
+        )
+        .unwrap();
+        assert_eq!(
+            res.iter().map(|q| q.to_string()).collect::<Vec<_>>(),
+            vec![
+                "SELECT * FROM `t` WHERE (`x` = 'a;b')",
+                "CREATE CACHE `q` FROM SELECT `y` FROM `t` WHERE (`x` = ?)",
+                "DROP ALL CACHES",
+            ]
+        );
+    }
+
+    #[test]
+    fn multiple_queries_with_trailing_garbage() {
+        parse_queries(Dialect::MySQL, "SELECT * FROM t; not a query").unwrap_err();
+    }
+
     mod mysql {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
@@ -55,6 +55,9 @@ pub struct JoinClause {
 
 impl fmt::Display for JoinClause {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.constraint == JoinConstraint::Natural {
+            return write!(f, "NATURAL {} {}", self.operator, self.right);
+        }
         write!(f, "{}", self.operator)?;
         write!(f, " {}", self.right)?;
         write!(f, " {}", self.constraint)?;
@@ -66,6 +69,9 @@ impl fmt::Display for JoinClause {
 pub struct CommonTableExpr {
     pub name: SqlIdentifier,
     pub statement: SelectStatement,
+    /// Whether this CTE was declared in a `WITH RECURSIVE` clause, which allows it to refer to
+    /// itself
+    pub recursive: bool,
 }
 
 impl fmt::Display for CommonTableExpr {
@@ -114,7 +120,11 @@ impl SelectStatement {
 impl fmt::Display for SelectStatement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if !self.ctes.is_empty() {
-            write!(f, "WITH {} ", self.ctes.iter().join(", "))?;
+            write!(f, "WITH ")?;
+            if self.ctes.iter().any(|cte| cte.recursive) {
+                write!(f, "RECURSIVE ")?;
+            }
+            write!(f, "{} ", self.ctes.iter().join(", "))?;
         }
 
         write!(f, "SELECT ")?;
@@ -276,7 +286,7 @@ fn join_constraint(
 // Parse JOIN clause
 fn join_clause(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], JoinClause> {
     move |i| {
-        let (remaining_input, (_, _natural, operator, _, right, _, constraint)) = tuple((
+        let (remaining_input, (_, natural, operator, _, right, _, constraint)) = tuple((
             whitespace0,
             opt(terminated(tag_no_case("natural"), whitespace1)),
             join_operator,
@@ -286,6 +296,18 @@ fn join_clause(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<
             join_constraint(dialect),
         ))(i)?;
 
+        let constraint = match (natural, constraint) {
+            (None, constraint) => constraint,
+            (Some(_), JoinConstraint::Empty) => JoinConstraint::Natural,
+            // NATURAL joins can't have an explicit join constraint
+            (Some(_), _) => {
+                return Err(nom::Err::Error(NomSqlError {
+                    input: i,
+                    kind: ErrorKind::Tag,
+                }))
+            }
+        };
+
         Ok((
             remaining_input,
             JoinClause {
@@ -457,7 +479,10 @@ fn from_clause(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<
     }
 }
 
-fn cte(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], CommonTableExpr> {
+fn cte(
+    dialect: Dialect,
+    recursive: bool,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], CommonTableExpr> {
     move |i| {
         let (i, name) = dialect.identifier()(i)?;
         let (i, _) = whitespace1(i)?;
@@ -470,7 +495,14 @@ fn cte(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], C
         let (i, _) = whitespace0(i)?;
         let (i, _) = tag(")")(i)?;
 
-        Ok((i, CommonTableExpr { name, statement }))
+        Ok((
+            i,
+            CommonTableExpr {
+                name,
+                statement,
+                recursive,
+            },
+        ))
     }
 }
 
@@ -480,7 +512,8 @@ fn ctes(
     move |i| {
         let (i, _) = tag_no_case("with")(i)?;
         let (i, _) = whitespace1(i)?;
-        let (i, ctes) = separated_list1(ws_sep_comma, cte(dialect))(i)?;
+        let (i, recursive) = opt(terminated(tag_no_case("recursive"), whitespace1))(i)?;
+        let (i, ctes) = separated_list1(ws_sep_comma, cte(dialect, recursive.is_some()))(i)?;
         let (i, _) = whitespace0(i)?;
 
         Ok((i, ctes))
@@ -1638,6 +1671,21 @@ mod tests {
         assert_eq!(query.ctes[1].name, "min_val");
     }
 
+    #[test]
+    fn recursive_cte() {
+        let qstr = "WITH RECURSIVE r AS (SELECT r.x FROM r) SELECT x FROM r";
+        let res = selection(Dialect::MySQL)(LocatedSpan::new(qstr.as_bytes()));
+        assert!(res.is_ok(), "error parsing query: {}", res.err().unwrap());
+        let (rem, query) = res.unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(query.ctes.len(), 1);
+        assert!(query.ctes[0].recursive);
+        assert_eq!(
+            query.to_string(),
+            "WITH RECURSIVE `r` AS (SELECT `r`.`x` FROM `r`) SELECT `x` FROM `r`"
+        );
+    }
+
     #[test]
     fn format_ctes() {
         let query = SelectStatement {
@@ -1651,6 +1699,7 @@ mod tests {
                     tables: vec![TableExpr::from(Relation::from("t"))],
                     ..Default::default()
                 },
+                recursive: false,
             }],
             fields: vec![FieldDefinitionExpr::Expr {
                 expr: Expr::Column("x".into()),
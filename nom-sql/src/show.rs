@@ -21,6 +21,7 @@ pub enum ShowStatement {
     ProxiedQueries(Option<QueryID>),
     ReadySetStatus,
     ReadySetVersion,
+    Warnings,
 }
 
 impl fmt::Display for ShowStatement {
@@ -45,6 +46,7 @@ impl fmt::Display for ShowStatement {
             }
             Self::ReadySetStatus => write!(f, "READYSET STATUS"),
             Self::ReadySetVersion => write!(f, "READYSET VERSION"),
+            Self::Warnings => write!(f, "WARNINGS"),
         }
     }
 }
@@ -115,6 +117,7 @@ pub fn show(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u
             )),
             map(show_tables(dialect), ShowStatement::Tables),
             map(tag_no_case("events"), |_| ShowStatement::Events),
+            map(tag_no_case("warnings"), |_| ShowStatement::Warnings),
         ))(i)?;
         Ok((i, statement))
     }
@@ -291,6 +294,15 @@ mod tests {
         assert_eq!(res2, ShowStatement::Events);
     }
 
+    #[test]
+    fn show_warnings() {
+        let res = show(Dialect::MySQL)(LocatedSpan::new(b"SHOW WARNINGS"))
+            .unwrap()
+            .1;
+        assert_eq!(res, ShowStatement::Warnings);
+        assert_eq!(res.to_string(), "SHOW WARNINGS");
+    }
+
     #[test]
     fn show_caches() {
         let qstring1 = "SHOW CACHES";
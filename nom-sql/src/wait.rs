@@ -0,0 +1,59 @@
+use std::fmt::{self, Display};
+
+use nom::bytes::complete::tag_no_case;
+use nom_locate::LocatedSpan;
+use serde::{Deserialize, Serialize};
+
+use crate::common::statement_terminator;
+use crate::whitespace::whitespace1;
+use crate::NomSqlResult;
+
+/// `WAIT FOR REPLICATION` statements, which block until ReadySet has replicated all writes that
+/// were committed to the upstream database before the statement was issued
+///
+/// This is a non-standard ReadySet-specific extension to SQL
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct WaitForReplicationStatement {}
+
+impl Display for WaitForReplicationStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WAIT FOR REPLICATION")
+    }
+}
+
+pub(crate) fn wait_for_replication(
+    i: LocatedSpan<&[u8]>,
+) -> NomSqlResult<&[u8], WaitForReplicationStatement> {
+    let (i, _) = tag_no_case("wait")(i)?;
+    let (i, _) = whitespace1(i)?;
+    let (i, _) = tag_no_case("for")(i)?;
+    let (i, _) = whitespace1(i)?;
+    let (i, _) = tag_no_case("replication")(i)?;
+    let (i, _) = statement_terminator(i)?;
+    Ok((i, WaitForReplicationStatement {}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_wait_for_replication() {
+        for q in ["WAIT FOR REPLICATION", "wait  for replication;"] {
+            assert_eq!(
+                wait_for_replication(LocatedSpan::new(q.as_bytes()))
+                    .unwrap()
+                    .1,
+                WaitForReplicationStatement {}
+            );
+        }
+    }
+
+    #[test]
+    fn format_wait_for_replication() {
+        assert_eq!(
+            WaitForReplicationStatement {}.to_string(),
+            "WAIT FOR REPLICATION"
+        );
+    }
+}
@@ -20,6 +20,7 @@ const ID_CLOSE_COMPLETE: u8 = b'3';
 const ID_COMMAND_COMPLETE: u8 = b'C';
 const ID_DATA_ROW: u8 = b'D';
 const ID_ERROR_RESPONSE: u8 = b'E';
+const ID_NOTICE_RESPONSE: u8 = b'N';
 const ID_PARAMETER_DESCRIPTION: u8 = b't';
 const ID_PARAMETER_STATUS: u8 = b'S';
 const ID_PARSE_COMPLETE: u8 = b'1';
@@ -44,6 +45,7 @@ const ERROR_RESPONSE_SEVERITY_ERROR: &str = "ERROR";
 const ERROR_RESPONSE_SEVERITY_FATAL: &str = "FATAL";
 const ERROR_RESPONSE_SEVERITY_PANIC: &str = "PANIC";
 const ERROR_RESPONSE_TERMINATOR: u8 = b'\0';
+const NOTICE_RESPONSE_SEVERITY_WARNING: &str = "WARNING";
 
 const BOOL_FALSE_TEXT_REP: &str = "f";
 const BOOL_TRUE_TEXT_REP: &str = "t";
@@ -205,6 +207,21 @@ where
             put_u8(ERROR_RESPONSE_TERMINATOR, dst);
         }
 
+        // Notice responses have the same fields as error responses
+        NoticeResponse { sqlstate, message } => {
+            put_u8(ID_NOTICE_RESPONSE, dst);
+            put_i32(LENGTH_PLACEHOLDER, dst);
+            put_u8(ERROR_RESPONSE_S_FIELD, dst);
+            put_str(NOTICE_RESPONSE_SEVERITY_WARNING, dst);
+            put_u8(ERROR_RESPONSE_V_FIELD, dst);
+            put_str(NOTICE_RESPONSE_SEVERITY_WARNING, dst);
+            put_u8(ERROR_RESPONSE_C_FIELD, dst);
+            put_str(sqlstate.code(), dst);
+            put_u8(ERROR_RESPONSE_M_FIELD, dst);
+            put_str(&message, dst);
+            put_u8(ERROR_RESPONSE_TERMINATOR, dst);
+        }
+
         ParameterDescription {
             parameter_data_types,
         } => {
@@ -835,6 +852,34 @@ mod tests {
         assert_eq!(buf, exp);
     }
 
+    #[test]
+    fn test_encode_notice_response() {
+        let mut codec = Codec::<Vec<Value>>::new();
+        let mut buf = BytesMut::new();
+        codec
+            .encode(
+                NoticeResponse {
+                    sqlstate: SqlState::WARNING,
+                    message: "incomplete kringle".to_string(),
+                },
+                &mut buf,
+            )
+            .unwrap();
+        let mut exp = BytesMut::new();
+        exp.put_u8(b'N'); // message id
+        exp.put_i32(4 + 1 + 8 + 1 + 8 + 1 + 6 + 1 + 19 + 1); // message length
+        exp.put_u8(b'S'); // field id
+        exp.extend_from_slice(b"WARNING\0");
+        exp.put_u8(b'V'); // field id
+        exp.extend_from_slice(b"WARNING\0");
+        exp.put_u8(b'C'); // field id
+        exp.extend_from_slice(b"01000\0");
+        exp.put_u8(b'M'); // field id
+        exp.extend_from_slice(b"incomplete kringle\0");
+        exp.put_u8(b'\0'); // terminator
+        assert_eq!(buf, exp);
+    }
+
     #[test]
     fn test_encode_error_response_after_encoding_failure() {
         struct UnserializableValue;
@@ -38,9 +38,15 @@ pub enum Error {
     #[error("parse error: {0}")]
     ParseError(String),
 
+    #[error("query canceled: {0}")]
+    QueryCanceled(String),
+
     #[error("unimplemented: {0}")]
     Unimplemented(String),
 
+    #[error("unique violation: {0}")]
+    UniqueViolation(String),
+
     #[error("unknown: {0}")]
     Unknown(String),
 
@@ -113,6 +113,12 @@ pub trait Backend {
     ///
     /// * `statement_id` - The identifier of the prepared statement to close.
     async fn on_close(&mut self, statement_id: u32) -> Result<(), Error>;
+
+    /// Returns any warnings about the results of the last call to `on_query` or `on_execute`,
+    /// which are sent to the frontend as notices along with those results.
+    fn warnings(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 /// A description of a column, either in the parameters to a query or in a resultset
@@ -42,6 +42,11 @@ pub enum BackendMessage<R> {
         sqlstate: SqlState,
         message: String,
     },
+    /// A warning about the results of a request, which doesn't prevent the request from completing
+    NoticeResponse {
+        sqlstate: SqlState,
+        message: String,
+    },
     ParameterDescription {
         parameter_data_types: Vec<Type>,
     },
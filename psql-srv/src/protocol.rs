@@ -374,6 +374,7 @@ impl Protocol {
                         .get(portal_name.borrow() as &str)
                         .ok_or_else(|| Error::MissingPreparedStatement(portal_name.to_string()))?;
                     let response = backend.on_execute(*prepared_statement_id, params).await?;
+                    send_warnings(backend.warnings(), channel).await?;
                     let res = if let Select { resultset, .. } = response {
                         Ok(Response::Select {
                             header: None,
@@ -407,6 +408,7 @@ impl Protocol {
                 // prepared statement.
                 Query { query } => {
                     let response = backend.on_query(query.borrow()).await?;
+                    send_warnings(backend.warnings(), channel).await?;
                     if let Select { schema, resultset } = response {
                         let mut field_descriptions = Vec::with_capacity(schema.len());
                         for i in schema {
@@ -549,6 +551,27 @@ impl Protocol {
     }
 }
 
+/// Sends any warnings about the results of the last query or execute to the frontend as notices,
+/// ahead of the results themselves
+async fn send_warnings<C, R>(
+    warnings: Vec<String>,
+    channel: &mut Channel<C, R>,
+) -> Result<(), Error>
+where
+    C: AsyncRead + AsyncWrite + Unpin,
+    R: IntoIterator<Item: TryInto<Value, Error = Error>>,
+{
+    for message in warnings {
+        channel
+            .send(Response::<R, Vec<R>>::Message(NoticeResponse {
+                sqlstate: SqlState::WARNING,
+                message,
+            }))
+            .await?;
+    }
+    Ok(())
+}
+
 fn make_error_response<R>(error: Error) -> BackendMessage<R> {
     let sqlstate = match error {
         Error::AuthenticationFailure(_) => SqlState::INVALID_PASSWORD,
@@ -561,7 +584,9 @@ fn make_error_response<R>(error: Error) -> BackendMessage<R> {
         Error::MissingPortal(_) => SqlState::UNDEFINED_PSTATEMENT,
         Error::MissingPreparedStatement(_) => SqlState::UNDEFINED_PSTATEMENT,
         Error::ParseError(_) => SqlState::INVALID_PSTATEMENT_DEFINITION,
+        Error::QueryCanceled(_) => SqlState::QUERY_CANCELED,
         Error::Unimplemented(_) => SqlState::FEATURE_NOT_SUPPORTED,
+        Error::UniqueViolation(_) => SqlState::UNIQUE_VIOLATION,
         Error::Unknown(_) => SqlState::INTERNAL_ERROR,
         Error::Unsupported(_) => SqlState::FEATURE_NOT_SUPPORTED,
         Error::UnsupportedMessage(_) => SqlState::FEATURE_NOT_SUPPORTED,
@@ -2793,6 +2793,7 @@ impl Subquery {
                 query.ctes.push(CommonTableExpr {
                     name: subquery_name.clone(),
                     statement: subquery,
+                    recursive: false,
                 });
                 (
                     JoinRightSide::Table(TableExpr::from(Relation {
@@ -0,0 +1,202 @@
+//! Audit logging of DDL statements processed by the adapter.
+//!
+//! When enabled, every DDL statement (`CREATE TABLE`, `CREATE CACHE`, `DROP TABLE`, etc.) received
+//! by the adapter, whether executed directly or as a prepared statement, is appended to the audit
+//! log as a single line of JSON, giving operators a record of changes to the shape of the
+//! deployment.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use nom_sql::SqlQuery;
+use readyset_sql_passes::anonymize::anonymize_query_literals;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::utils::leading_keyword;
+
+/// A single entry in the audit log
+#[derive(Debug, Serialize)]
+struct AuditLogEntry<'a> {
+    /// The time at which the statement was received, in RFC 3339 format
+    timestamp: String,
+    /// The address of the client that sent the statement, if known
+    client_addr: Option<SocketAddr>,
+    /// The user the client authenticated as, if known
+    user: Option<&'a str>,
+    /// The statement itself, with all literals redacted
+    statement: String,
+}
+
+/// A handle to an append-only audit log file, which can be cloned and shared between connections.
+///
+/// `write_all` may split an entry across several writes, so writers take turns through a mutex to
+/// keep entries from concurrent connections from being interleaved.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    file: Arc<Mutex<File>>,
+}
+
+impl AuditLog {
+    /// Open the audit log at the given path for appending, creating it if it doesn't exist
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+
+    /// Returns the statement to record in the audit log for `query`, with all literals redacted so
+    /// that any passwords or other secrets it contains don't end up in the log, or `None` if it
+    /// isn't DDL.
+    ///
+    /// `parsed` is the result of parsing `query`, if that succeeded. Statements we couldn't parse
+    /// are recognized as DDL by their leading keyword instead, and have their string literals
+    /// redacted textually.
+    pub(crate) fn ddl_statement(query: &str, parsed: Option<&SqlQuery>) -> Option<String> {
+        match parsed {
+            Some(parsed) if Self::is_ddl(parsed) => {
+                let mut statement = parsed.clone();
+                anonymize_query_literals(&mut statement);
+                Some(statement.to_string())
+            }
+            Some(_) => None,
+            None => {
+                let keyword = leading_keyword(query);
+                ["CREATE", "ALTER", "DROP", "RENAME", "TRUNCATE"]
+                    .iter()
+                    .any(|ddl| keyword.eq_ignore_ascii_case(ddl))
+                    .then(|| redact_string_literals(query.trim()))
+            }
+        }
+    }
+
+    /// Returns true if the given query is DDL, which should be recorded in the audit log
+    fn is_ddl(query: &SqlQuery) -> bool {
+        matches!(
+            query,
+            SqlQuery::CreateTable(_)
+                | SqlQuery::CreateView(_)
+                | SqlQuery::AlterTable(_)
+                | SqlQuery::DropTable(_)
+                | SqlQuery::DropView(_)
+                | SqlQuery::RenameTable(_)
+                | SqlQuery::CreateCache(_)
+                | SqlQuery::DropCache(_)
+                | SqlQuery::DropAllCaches(_)
+        )
+    }
+
+    /// Append an entry for the given statement, as returned by [`AuditLog::ddl_statement`], to the
+    /// audit log.
+    ///
+    /// The write itself happens on the blocking threadpool, so it doesn't block the async runtime.
+    /// Failures to write to the log are logged, but otherwise ignored.
+    pub(crate) async fn record(
+        &self,
+        client_addr: Option<SocketAddr>,
+        user: Option<&str>,
+        statement: String,
+    ) {
+        let entry = AuditLogEntry {
+            timestamp: Utc::now().to_rfc3339(),
+            client_addr,
+            user,
+            statement,
+        };
+
+        let res = match serde_json::to_vec(&entry) {
+            Ok(mut line) => {
+                line.push(b'\n');
+                let file = Arc::clone(&self.file);
+                tokio::task::spawn_blocking(move || {
+                    // Nothing panics while holding the lock, but if something ever does keep
+                    // writing entries rather than failing every write after it
+                    let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+                    file.write_all(&line)
+                })
+                .await
+                .unwrap_or_else(|e| Err(io::Error::new(io::ErrorKind::Other, e)))
+            }
+            Err(e) => Err(e.into()),
+        };
+        if let Err(error) = res {
+            warn!(%error, "Failed to write to audit log");
+        }
+    }
+}
+
+/// Replaces the contents of every single-quoted string literal in `query` with `<anonymized>`, for
+/// statements we couldn't parse to redact their literals properly
+fn redact_string_literals(query: &str) -> String {
+    let mut redacted = String::with_capacity(query.len());
+    let mut chars = query.chars().peekable();
+    while let Some(c) = chars.next() {
+        redacted.push(c);
+        if c != '\'' {
+            continue;
+        }
+
+        // Skip to the end of the literal, allowing for both doubled and backslash-escaped quotes
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '\'' if chars.peek() == Some(&'\'') => {
+                    chars.next();
+                }
+                '\'' => break,
+                _ => {}
+            }
+        }
+        redacted.push_str("<anonymized>'");
+    }
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::{parse_query, Dialect};
+
+    use super::*;
+
+    #[test]
+    fn ddl_statement_redacts_literals() {
+        let query = "CREATE CACHE FROM SELECT * FROM t WHERE x = 'hunter2'";
+        let parsed = parse_query(Dialect::MySQL, query).unwrap();
+        let statement = AuditLog::ddl_statement(query, Some(&parsed)).unwrap();
+        assert!(!statement.contains("hunter2"), "{statement}");
+    }
+
+    #[test]
+    fn ddl_statement_ignores_other_statements() {
+        let query = "SELECT * FROM t WHERE x = 'y'";
+        let parsed = parse_query(Dialect::MySQL, query).unwrap();
+        assert_eq!(AuditLog::ddl_statement(query, Some(&parsed)), None);
+        assert_eq!(
+            AuditLog::ddl_statement("INSERT INTO t VALUES ('y')", None),
+            None
+        );
+    }
+
+    #[test]
+    fn unparsed_ddl_statement() {
+        assert_eq!(
+            AuditLog::ddl_statement(
+                "/* comment */ CREATE USER u IDENTIFIED BY 'it''s a \\'secret\\'' ",
+                None
+            )
+            .as_deref(),
+            Some("/* comment */ CREATE USER u IDENTIFIED BY '<anonymized>'")
+        );
+        assert_eq!(
+            AuditLog::ddl_statement("alter user u password 'x", None).as_deref(),
+            Some("alter user u password '<anonymized>'")
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! Looking up the users allowed to connect to the adapter.
+//!
+//! Clients connecting to the adapter are authenticated against an [`AuthBackend`], shared between
+//! all connections. By default the adapter allows a fixed set of users ([`StaticUsers`]), which is
+//! configured with `--username` and `--password`. With `--auth-backend http`, users are instead
+//! looked up from an external HTTP service ([`HttpAuthBackend`]), so that credentials can be
+//! managed centrally for a whole deployment.
+//!
+//! Auth backends return a hash of the password for a user rather than checking a username and
+//! password pair, since the MySQL protocol never sends the client's password in cleartext -
+//! instead, the adapter checks the client's response to a random challenge against the hash. The
+//! hash is the same one MySQL stores for users authenticating with `mysql_native_password` (see
+//! [`PasswordHash`]), so the auth service never needs to store or send cleartext passwords.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use readyset_errors::{ReadySetError, ReadySetResult};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+use tracing::{debug, trace};
+
+/// The hash of a user's password that clients are authenticated against: `SHA1(SHA1(password))`
+pub type PasswordHash = [u8; 20];
+
+/// Compute the [`PasswordHash`] of the given cleartext password
+pub fn password_hash(password: &str) -> PasswordHash {
+    let sha1 = |input: &[u8]| -> PasswordHash { Sha1::digest(input).into() };
+    sha1(&sha1(password.as_bytes()))
+}
+
+/// A source of the users allowed to connect to the adapter
+#[async_trait]
+pub trait AuthBackend: Debug + Send + Sync {
+    /// Look up the hash of the password for the user with the given username, returning `None` if
+    /// the user doesn't exist
+    async fn password_hash_for_user(&self, username: &str) -> ReadySetResult<Option<PasswordHash>>;
+
+    /// Returns true if `password` is the correct password for the user with the given username
+    async fn authenticate(&self, username: &str, password: &str) -> ReadySetResult<bool> {
+        Ok(self.password_hash_for_user(username).await? == Some(password_hash(password)))
+    }
+}
+
+/// An [`AuthBackend`] which allows a fixed set of users, configured when the adapter starts
+#[derive(Debug, Clone, Default)]
+pub struct StaticUsers {
+    /// Map from username to password hash
+    users: HashMap<String, PasswordHash>,
+}
+
+impl StaticUsers {
+    /// Create a new [`StaticUsers`] from a map from username to password
+    pub fn new(users: HashMap<String, String>) -> Self {
+        Self {
+            users: users
+                .into_iter()
+                .map(|(username, password)| (username, password_hash(&password)))
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl AuthBackend for StaticUsers {
+    async fn password_hash_for_user(&self, username: &str) -> ReadySetResult<Option<PasswordHash>> {
+        Ok(self.users.get(username).copied())
+    }
+}
+
+/// The body of a successful response from the auth service
+#[derive(Debug, Deserialize)]
+struct UserResponse {
+    password_hash: String,
+}
+
+impl UserResponse {
+    /// Parse the hex-encoded password hash in the response, which may be prefixed with `*` as in
+    /// the output of MySQL's `PASSWORD()` function
+    fn password_hash(&self) -> ReadySetResult<PasswordHash> {
+        let encoded = self
+            .password_hash
+            .strip_prefix('*')
+            .unwrap_or(&self.password_hash);
+        let mut password_hash = PasswordHash::default();
+        hex::decode_to_slice(encoded, &mut password_hash).map_err(|e| {
+            ReadySetError::AuthBackendFailure(format!(
+                "auth service returned an invalid password hash: {e}"
+            ))
+        })?;
+        Ok(password_hash)
+    }
+}
+
+#[derive(Debug)]
+struct CachedUser {
+    password_hash: PasswordHash,
+    fetched_at: Instant,
+}
+
+/// An [`AuthBackend`] which looks up users from an external HTTP service.
+///
+/// To look up a user, the backend makes a `GET` request to the service's URL with the username in
+/// the `user` query parameter. The service should respond with `200 OK` and a JSON body of the
+/// form `{"password_hash": "*2470C0C06DEE42FD1618BB99005ADCA2EC9D1E19"}` if the user exists, where
+/// the password hash is hex-encoded (see [`PasswordHash`]), or `404 Not Found` if it doesn't. Any
+/// other response is treated as an error, which causes the client's connection to be rejected.
+///
+/// Users that exist are cached for `cache_ttl`, so that clients opening many connections don't
+/// each result in a request to the service. At most `cache_size` users are cached at once, with
+/// the least recently fetched user evicted to make room for a new one. Missing users are never
+/// cached, so that a user is allowed to connect as soon as they're created in the service.
+#[derive(Debug)]
+pub struct HttpAuthBackend {
+    client: reqwest::Client,
+    url: String,
+    cache_ttl: Duration,
+    cache_size: usize,
+    cache: Mutex<HashMap<String, CachedUser>>,
+}
+
+impl HttpAuthBackend {
+    /// Create a new [`HttpAuthBackend`] which looks up users from the service at `url`, caching
+    /// up to `cache_size` users for `cache_ttl`
+    pub fn new(url: String, cache_ttl: Duration, cache_size: usize) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+            cache_ttl,
+            cache_size,
+            cache: Default::default(),
+        }
+    }
+
+    fn cached(&self, username: &str) -> Option<PasswordHash> {
+        self.cache
+            .lock()
+            .get(username)
+            .filter(|cached| cached.fetched_at.elapsed() < self.cache_ttl)
+            .map(|cached| cached.password_hash)
+    }
+
+    fn insert_cached(&self, username: &str, password_hash: PasswordHash) {
+        if self.cache_size == 0 {
+            return;
+        }
+
+        let mut cache = self.cache.lock();
+        if cache.len() >= self.cache_size && !cache.contains_key(username) {
+            cache.retain(|_, cached| cached.fetched_at.elapsed() < self.cache_ttl);
+            if cache.len() >= self.cache_size {
+                let oldest = cache
+                    .iter()
+                    .min_by_key(|(_, cached)| cached.fetched_at)
+                    .map(|(username, _)| username.clone());
+                if let Some(oldest) = oldest {
+                    cache.remove(&oldest);
+                }
+            }
+        }
+        cache.insert(
+            username.to_owned(),
+            CachedUser {
+                password_hash,
+                fetched_at: Instant::now(),
+            },
+        );
+    }
+
+    async fn fetch(&self, username: &str) -> ReadySetResult<Option<PasswordHash>> {
+        let err = |e: reqwest::Error| ReadySetError::AuthBackendFailure(e.to_string());
+        let response = self
+            .client
+            .get(&self.url)
+            .query(&[("user", username)])
+            .send()
+            .await
+            .map_err(err)?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => Ok(Some(
+                response
+                    .json::<UserResponse>()
+                    .await
+                    .map_err(err)?
+                    .password_hash()?,
+            )),
+            status => Err(ReadySetError::AuthBackendFailure(format!(
+                "auth service returned unexpected status {status}"
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl AuthBackend for HttpAuthBackend {
+    async fn password_hash_for_user(&self, username: &str) -> ReadySetResult<Option<PasswordHash>> {
+        if let Some(password_hash) = self.cached(username) {
+            trace!(%username, "Found user in auth cache");
+            return Ok(Some(password_hash));
+        }
+
+        let password_hash = self.fetch(username).await?;
+        debug!(%username, found = password_hash.is_some(), "Looked up user from auth service");
+        match password_hash {
+            Some(password_hash) => self.insert_cached(username, password_hash),
+            None => {
+                self.cache.lock().remove(username);
+            }
+        }
+        Ok(password_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Body, Request, Response, Server};
+
+    use super::*;
+
+    /// Start a mock auth service which knows about two users, `alice` and `carol`, both with the
+    /// password `password`. Returns the URL of the service, and a count of the requests it has
+    /// received.
+    fn start_mock_auth_service() -> (String, Arc<AtomicUsize>) {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let make_svc = make_service_fn({
+            let requests = Arc::clone(&requests);
+            move |_| {
+                let requests = Arc::clone(&requests);
+                async move {
+                    Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                        requests.fetch_add(1, Ordering::SeqCst);
+                        let response = match req.uri().query() {
+                            Some("user=alice") | Some("user=carol") => Response::new(Body::from(
+                                r#"{"password_hash": "*2470C0C06DEE42FD1618BB99005ADCA2EC9D1E19"}"#,
+                            )),
+                            Some("user=broken") => Response::builder()
+                                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                                .body(Body::empty())
+                                .unwrap(),
+                            _ => Response::builder()
+                                .status(StatusCode::NOT_FOUND)
+                                .body(Body::empty())
+                                .unwrap(),
+                        };
+                        async move { Ok::<_, Infallible>(response) }
+                    }))
+                }
+            }
+        });
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_svc);
+        let url = format!("http://{}/users", server.local_addr());
+        tokio::spawn(server);
+        (url, requests)
+    }
+
+    #[tokio::test]
+    async fn static_users() {
+        let users = StaticUsers::new(HashMap::from([("alice".to_owned(), "password".to_owned())]));
+        assert!(users.authenticate("alice", "password").await.unwrap());
+        assert!(!users.authenticate("alice", "wrong").await.unwrap());
+        assert!(!users.authenticate("bob", "password").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn http_accepts_and_rejects() {
+        let (url, _) = start_mock_auth_service();
+        let backend = HttpAuthBackend::new(url, Duration::from_secs(60), 16);
+
+        assert_eq!(
+            backend.password_hash_for_user("alice").await.unwrap(),
+            Some(password_hash("password"))
+        );
+        assert!(backend.authenticate("alice", "password").await.unwrap());
+        assert!(!backend.authenticate("alice", "wrong").await.unwrap());
+        assert!(!backend.authenticate("bob", "password").await.unwrap());
+        assert!(matches!(
+            backend.authenticate("broken", "password").await,
+            Err(ReadySetError::AuthBackendFailure(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn http_caches_lookups() {
+        let (url, requests) = start_mock_auth_service();
+
+        let backend = HttpAuthBackend::new(url.clone(), Duration::from_secs(60), 16);
+        backend.password_hash_for_user("alice").await.unwrap();
+        backend.password_hash_for_user("alice").await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // Missing users aren't cached
+        backend.password_hash_for_user("bob").await.unwrap();
+        backend.password_hash_for_user("bob").await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+        let backend = HttpAuthBackend::new(url, Duration::ZERO, 16);
+        backend.password_hash_for_user("alice").await.unwrap();
+        backend.password_hash_for_user("alice").await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn http_cache_is_bounded() {
+        let (url, requests) = start_mock_auth_service();
+
+        let backend = HttpAuthBackend::new(url, Duration::from_secs(60), 1);
+        backend.password_hash_for_user("alice").await.unwrap();
+        backend.password_hash_for_user("carol").await.unwrap();
+        assert_eq!(backend.cache.lock().len(), 1);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+        // Looking up carol evicted alice
+        backend.password_hash_for_user("carol").await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        backend.password_hash_for_user("alice").await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+}
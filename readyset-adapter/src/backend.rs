@@ -74,6 +74,7 @@ use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::fmt::{self, Debug};
 use std::marker::PhantomData;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -81,37 +82,53 @@ use futures::future::{self, OptionFuture};
 use launchpad::redacted::Sensitive;
 use mysql_common::row::convert::{FromRow, FromRowError};
 use nom_sql::{
-    CacheInner, CreateCacheStatement, DeleteStatement, Dialect, DropCacheStatement,
-    InsertStatement, Relation, SelectStatement, SetStatement, ShowStatement, SqlIdentifier,
-    SqlQuery, UpdateStatement, UseStatement,
+    CacheInner, CreateCacheStatement, DeleteStatement, Dialect, DropCacheStatement, Expr,
+    FieldDefinitionExpr, FunctionExpr, InsertStatement, KillQueryStatement, Relation,
+    SelectStatement, SetStatement, ShowStatement, SqlIdentifier, SqlQuery, UpdateStatement,
+    UseStatement,
 };
 use readyset::consistency::Timestamp;
 use readyset::query::*;
+use readyset::replication::ReplicationOffset;
 use readyset::results::Results;
 use readyset::{ColumnSchema, ViewCreateRequest};
 pub use readyset_client_metrics::QueryDestination;
-use readyset_client_metrics::{recorded, EventType, QueryExecutionEvent, SqlQueryType};
+use readyset_client_metrics::{recorded, EventType, QueryExecutionEvent, QueryTags, SqlQueryType};
 use readyset_data::{DfType, DfValue};
 use readyset_errors::ReadySetError::{self, PreparedStatementMissing};
-use readyset_errors::{internal, internal_err, unsupported, ReadySetResult};
+use readyset_errors::{internal, internal_err, unsupported, unsupported_err, ReadySetResult};
 use readyset_telemetry_reporter::{TelemetryBuilder, TelemetryEvent, TelemetrySender};
 use readyset_tracing::instrument_root;
-use readyset_version::READYSET_VERSION;
 use timestamp_service::client::{TimestampClient, WriteId, WriteKey};
 use tokio::sync::mpsc::UnboundedSender;
-use tracing::{error, instrument, trace, warn};
+use tracing::{error, instrument, trace, warn, Level};
 
+use crate::audit_log::AuditLog;
+use crate::auth::{AuthBackend, StaticUsers};
 use crate::backend::noria_connector::ExecuteSelectContext;
+use crate::controller_health::ControllerHealth;
+use crate::feature_flags::{FeatureFlags, READ_ONLY};
+use crate::query_cancellation::{ConnectionCancellation, QueryCanceller};
 use crate::query_handler::SetBehavior;
 use crate::query_status_cache::QueryStatusCache;
 use crate::upstream_database::NoriaCompare;
 pub use crate::upstream_database::UpstreamPrepare;
-use crate::{rewrite, QueryHandler, UpstreamDatabase, UpstreamDestination};
+use crate::utils::leading_keyword;
+use crate::{query_validation, rewrite, QueryHandler, UpstreamDatabase, UpstreamDestination};
 
 pub mod noria_connector;
+pub mod timestamp_format;
 
-use self::noria_connector::MetaVariable;
 pub use self::noria_connector::NoriaConnector;
+pub use self::timestamp_format::TimestampFormat;
+
+/// How often to poll ReadySet's replication offsets while handling a `WAIT FOR REPLICATION`
+/// statement
+const WAIT_FOR_REPLICATION_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How long a `WAIT FOR REPLICATION` statement waits for ReadySet to catch up before returning an
+/// error
+const WAIT_FOR_REPLICATION_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// Query metadata used to plan query prepare
 #[allow(clippy::large_enum_variant)]
@@ -151,6 +168,32 @@ pub enum UnsupportedSetMode {
     Allow,
 }
 
+/// How to behave when receiving `SELECT` statements that ReadySet can't support, when an upstream
+/// database is configured
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UnsupportedQueryMode {
+    /// Return an error to the client
+    Error,
+    /// Proxy the query to the upstream database (the default)
+    Proxy,
+    /// Proxy the query to the upstream database, and log a warning that it was proxied
+    LogAndProxy,
+}
+
+/// How to behave when an `UPDATE` or `DELETE` statement executed against ReadySet doesn't affect
+/// any rows
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ZeroRowWriteMode {
+    /// Return success, with an affected row count of 0 (the default)
+    #[default]
+    Success,
+    /// Return success, along with a warning to the client that the statement didn't affect any
+    /// rows
+    Warn,
+    /// Return an error to the client
+    Error,
+}
+
 /// A state machine representing how statements are proxied upstream for a particular instance of a
 /// backend.
 ///
@@ -253,7 +296,7 @@ impl ProxyState {
 pub struct BackendBuilder {
     slowlog: bool,
     dialect: Dialect,
-    users: HashMap<String, String>,
+    auth_backend: Arc<dyn AuthBackend>,
     require_authentication: bool,
     ticket: Option<Timestamp>,
     timestamp_client: Option<TimestampClient>,
@@ -261,11 +304,22 @@ pub struct BackendBuilder {
     query_log_ad_hoc_queries: bool,
     validate_queries: bool,
     fail_invalidated_queries: bool,
+    validation_mismatch_log_level: Level,
     unsupported_set_mode: UnsupportedSetMode,
+    unsupported_query_mode: UnsupportedQueryMode,
+    zero_row_write_mode: ZeroRowWriteMode,
     migration_mode: MigrationMode,
     query_max_failure_seconds: u64,
     fallback_recovery_seconds: u64,
     telemetry_sender: Option<TelemetrySender>,
+    read_only: bool,
+    feature_flags: FeatureFlags,
+    audit_log: Option<AuditLog>,
+    client_addr: Option<SocketAddr>,
+    query_canceller: QueryCanceller,
+    read_your_writes_timeout: Option<Duration>,
+    controller_health: Option<ControllerHealth>,
+    max_prepared_statements: Option<usize>,
 }
 
 impl Default for BackendBuilder {
@@ -273,7 +327,7 @@ impl Default for BackendBuilder {
         BackendBuilder {
             slowlog: false,
             dialect: Dialect::MySQL,
-            users: Default::default(),
+            auth_backend: Arc::new(StaticUsers::default()),
             require_authentication: true,
             ticket: None,
             timestamp_client: None,
@@ -281,11 +335,22 @@ impl Default for BackendBuilder {
             query_log_ad_hoc_queries: false,
             validate_queries: false,
             fail_invalidated_queries: false,
+            validation_mismatch_log_level: Level::WARN,
             unsupported_set_mode: UnsupportedSetMode::Error,
+            unsupported_query_mode: UnsupportedQueryMode::Proxy,
+            zero_row_write_mode: ZeroRowWriteMode::default(),
             migration_mode: MigrationMode::InRequestPath,
             query_max_failure_seconds: (i64::MAX / 1000) as u64,
             fallback_recovery_seconds: 0,
             telemetry_sender: None,
+            read_only: false,
+            feature_flags: FeatureFlags::new(),
+            audit_log: None,
+            client_addr: None,
+            query_canceller: QueryCanceller::new(),
+            read_your_writes_timeout: None,
+            controller_health: None,
+            max_prepared_statements: None,
         }
     }
 }
@@ -297,11 +362,17 @@ impl BackendBuilder {
 
     pub fn build<DB: UpstreamDatabase, Handler>(
         self,
-        noria: NoriaConnector,
+        mut noria: NoriaConnector,
         upstream: Option<DB>,
         query_status_cache: &'static QueryStatusCache,
     ) -> Backend<DB, Handler> {
         metrics::increment_gauge!(recorded::CONNECTED_CLIENTS, 1.0);
+        if let Some(telemetry_sender) = &self.telemetry_sender {
+            if let Err(e) = telemetry_sender.send_event(TelemetryEvent::ConnectionOpened) {
+                warn!(error = %e, "Failed to send connection opened metric");
+            }
+        }
+        noria.set_max_prepared_statements(self.max_prepared_statements);
 
         let proxy_state = if upstream.is_some() {
             ProxyState::Fallback
@@ -312,16 +383,22 @@ impl BackendBuilder {
         Backend {
             noria,
             upstream,
-            users: self.users,
+            auth_backend: self.auth_backend,
             query_log_sender: self.query_log_sender,
             last_query: None,
             state: BackendState {
                 proxy_state,
                 parsed_query_cache: HashMap::new(),
-                prepared_statements: Vec::new(),
+                prepared_statements: HashMap::new(),
+                next_prepared_id: 0,
                 query_status_cache,
                 ticket: self.ticket,
                 timestamp_client: self.timestamp_client,
+                user: None,
+                unreplicated_writes: false,
+                timestamp_format: TimestampFormat::default(),
+                connected_at: Instant::now(),
+                query_count: 0,
             },
             settings: BackendSettings {
                 slowlog: self.slowlog,
@@ -329,13 +406,23 @@ impl BackendBuilder {
                 require_authentication: self.require_authentication,
                 validate_queries: self.validate_queries,
                 fail_invalidated_queries: self.fail_invalidated_queries,
+                validation_mismatch_log_level: self.validation_mismatch_log_level,
                 unsupported_set_mode: self.unsupported_set_mode,
+                unsupported_query_mode: self.unsupported_query_mode,
+                zero_row_write_mode: self.zero_row_write_mode,
                 migration_mode: self.migration_mode,
                 query_max_failure_duration: Duration::new(self.query_max_failure_seconds, 0),
                 query_log_ad_hoc_queries: self.query_log_ad_hoc_queries,
                 fallback_recovery_duration: Duration::new(self.fallback_recovery_seconds, 0),
+                read_only: self.read_only,
+                feature_flags: self.feature_flags,
+                audit_log: self.audit_log,
+                client_addr: self.client_addr,
+                read_your_writes_timeout: self.read_your_writes_timeout,
+                controller_health: self.controller_health,
             },
             telemetry_sender: self.telemetry_sender,
+            cancellation: self.query_canceller.register(),
             _query_handler: PhantomData,
         }
     }
@@ -360,8 +447,15 @@ impl BackendBuilder {
         self
     }
 
+    /// Allow connections from the users in the given map from username to password
     pub fn users(mut self, users: HashMap<String, String>) -> Self {
-        self.users = users;
+        self.auth_backend = Arc::new(StaticUsers::new(users));
+        self
+    }
+
+    /// Look up the users allowed to connect to the backend with the given [`AuthBackend`]
+    pub fn auth_backend(mut self, auth_backend: Arc<dyn AuthBackend>) -> Self {
+        self.auth_backend = auth_backend;
         self
     }
 
@@ -391,11 +485,27 @@ impl BackendBuilder {
         self
     }
 
+    /// Sets the level at which mismatches found by query validation are logged
+    pub fn validation_mismatch_log_level(mut self, level: Level) -> Self {
+        self.validation_mismatch_log_level = level;
+        self
+    }
+
     pub fn unsupported_set_mode(mut self, unsupported_set_mode: UnsupportedSetMode) -> Self {
         self.unsupported_set_mode = unsupported_set_mode;
         self
     }
 
+    pub fn unsupported_query_mode(mut self, unsupported_query_mode: UnsupportedQueryMode) -> Self {
+        self.unsupported_query_mode = unsupported_query_mode;
+        self
+    }
+
+    pub fn zero_row_write_mode(mut self, zero_row_write_mode: ZeroRowWriteMode) -> Self {
+        self.zero_row_write_mode = zero_row_write_mode;
+        self
+    }
+
     pub fn migration_mode(mut self, q: MigrationMode) -> Self {
         self.migration_mode = q;
         self
@@ -415,6 +525,68 @@ impl BackendBuilder {
         self.telemetry_sender = Some(telemetry_sender);
         self
     }
+
+    /// Specifies whether the backend should reject all write statements (`INSERT`, `UPDATE` and
+    /// `DELETE`) with a [`ReadySetError::ReadOnly`] error, rather than executing them.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Specifies the [`FeatureFlags`] for the deployment, which are consulted (in addition to
+    /// this backend's own settings) to enable opt-in behavior such as read-only mode.
+    pub fn feature_flags(mut self, feature_flags: FeatureFlags) -> Self {
+        self.feature_flags = feature_flags;
+        self
+    }
+
+    /// Specifies an [`AuditLog`] to record every DDL statement processed by the backend to
+    pub fn audit_log(mut self, audit_log: Option<AuditLog>) -> Self {
+        self.audit_log = audit_log;
+        self
+    }
+
+    /// Sets the registry of connections used to cancel queries with `KILL QUERY`. Backends built
+    /// with the same [`QueryCanceller`] can cancel each other's queries.
+    pub fn query_canceller(mut self, query_canceller: QueryCanceller) -> Self {
+        self.query_canceller = query_canceller;
+        self
+    }
+
+    /// Specifies the address of the client connected to the backend, to be recorded in the audit
+    /// log
+    pub fn client_addr(mut self, client_addr: Option<SocketAddr>) -> Self {
+        self.client_addr = client_addr;
+        self
+    }
+
+    /// Enables read-your-writes consistency within each session: once a session has written to
+    /// the upstream database, its subsequent reads from ReadySet wait up to `timeout` for ReadySet
+    /// to replicate those writes, and are proxied to the upstream database if it doesn't do so in
+    /// time. `None` (the default) disables this.
+    pub fn read_your_writes_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.read_your_writes_timeout = timeout;
+        self
+    }
+
+    /// Proxy all reads to the upstream database (other than those for caches created with
+    /// `ALWAYS`) while the given [`ControllerHealth`] reports the controller as lost. Statements
+    /// prepared while the controller is lost are only ever executed against the upstream
+    /// database. `None` (the default) keeps serving reads from ReadySet regardless of the
+    /// controller's health.
+    pub fn controller_health(mut self, controller_health: Option<ControllerHealth>) -> Self {
+        self.controller_health = controller_health;
+        self
+    }
+
+    /// Limit each connection to at most this many statements prepared against ReadySet at once.
+    /// Once the limit is reached, preparing a new statement deallocates the least-recently-used
+    /// prepared statement, which is transparently prepared against ReadySet again if it's executed
+    /// later. `None` (the default) places no limit on the number of prepared statements.
+    pub fn max_prepared_statements(mut self, max: Option<usize>) -> Self {
+        self.max_prepared_statements = max;
+        self
+    }
 }
 
 /// A [`CachedPreparedStatement`] stores the data needed for an immediate
@@ -443,6 +615,11 @@ where
     /// If statement was successfully rewritten, will store all information necessary to install
     /// the view in readyset
     view_request: Option<ViewCreateRequest>,
+    /// Tags extracted from the leading comment of the statement, attached to each execution
+    tags: QueryTags,
+    /// If the audit log is enabled and this is a DDL statement, the redacted statement to record
+    /// in the audit log each time it's executed
+    audit_statement: Option<String>,
 }
 
 impl<DB> CachedPreparedStatement<DB>
@@ -483,8 +660,8 @@ where
     noria: NoriaConnector,
     /// Optional connector to the upstream DB. Used for fallback reads and all writes if it exists
     upstream: Option<DB>,
-    /// Map from username to password for all users allowed to connect to the db
-    pub users: HashMap<String, String>,
+    /// Source of the users allowed to connect to the db
+    pub auth_backend: Arc<dyn AuthBackend>,
 
     query_log_sender: Option<UnboundedSender<QueryExecutionEvent>>,
 
@@ -500,6 +677,10 @@ where
     /// Provides the ability to send [`TelemetryEvent`]s to Segment
     telemetry_sender: Option<TelemetrySender>,
 
+    /// This connection's registration with the [`QueryCanceller`], which assigns its connection id
+    /// and allows its reads to be cancelled with `KILL QUERY`
+    cancellation: ConnectionCancellation,
+
     _query_handler: PhantomData<Handler>,
 }
 
@@ -513,8 +694,11 @@ where
     query_status_cache: &'static QueryStatusCache,
     // a cache of all previously parsed queries
     parsed_query_cache: HashMap<String, SqlQuery>,
-    // all queries previously prepared on noria or upstream, mapped by their ID.
-    prepared_statements: Vec<CachedPreparedStatement<DB>>,
+    // all queries previously prepared on noria or upstream, mapped by their ID. Statements are
+    // removed when the client closes them.
+    prepared_statements: HashMap<u32, CachedPreparedStatement<DB>>,
+    /// The id to assign to the next prepared statement
+    next_prepared_id: u32,
     /// Current RYW ticket. `None` if RYW is not enabled. This `ticket` will
     /// be updated as the client makes writes so as to be an accurate low watermark timestamp
     /// required to make RYW-consistent reads. On reads, the client will pass in this ticket to be
@@ -524,6 +708,18 @@ where
     /// is responsible for creating accurate RYW timestamps/tickets based on writes made by the
     /// Backend client.
     timestamp_client: Option<TimestampClient>,
+    /// The user the client connected to this backend authenticated as, if known
+    user: Option<String>,
+    /// Whether this session has written to the upstream database since ReadySet was last
+    /// observed to have replicated its writes. Only used if read-your-writes is enabled.
+    unreplicated_writes: bool,
+    /// How timestamps in results read from ReadySet are rendered for this session, set with
+    /// `SET readyset_timestamp_format`
+    timestamp_format: TimestampFormat,
+    /// When this connection was opened
+    connected_at: Instant,
+    /// The number of queries and prepared statement executions run over this connection
+    query_count: u64,
 }
 
 /// Settings that have no state and are constant for a given [`Backend`]
@@ -538,6 +734,11 @@ struct BackendSettings {
     validate_queries: bool,
     /// How to behave when receiving unsupported `SET` statements
     unsupported_set_mode: UnsupportedSetMode,
+    /// How to behave when receiving `SELECT` statements that ReadySet can't support
+    unsupported_query_mode: UnsupportedQueryMode,
+    /// How to behave when an `UPDATE` or `DELETE` executed against ReadySet doesn't affect any
+    /// rows
+    zero_row_write_mode: ZeroRowWriteMode,
     /// How this backend handles migrations, See MigrationMode.
     migration_mode: MigrationMode,
     /// The maximum duration that a query can continuously fail for before we enter into a recovery
@@ -547,6 +748,75 @@ struct BackendSettings {
     /// repeatedly failed for query_max_failure_duration.
     fallback_recovery_duration: Duration,
     fail_invalidated_queries: bool,
+    /// The level at which mismatches found by query validation are logged
+    validation_mismatch_log_level: Level,
+    /// Whether to reject all write statements, rather than executing them either against ReadySet
+    /// or the upstream database
+    read_only: bool,
+    /// The feature flags for the deployment, which may be changed while the backend is running
+    feature_flags: FeatureFlags,
+    /// If set, every DDL statement processed by this backend is recorded in this audit log
+    audit_log: Option<AuditLog>,
+    /// The address of the connected client, recorded in the audit log
+    client_addr: Option<SocketAddr>,
+    /// If set, reads from ReadySet made after this session has written to the upstream database
+    /// wait up to this long for ReadySet to replicate those writes
+    read_your_writes_timeout: Option<Duration>,
+    /// If set, reads are proxied to the upstream database while this reports that the controller
+    /// has been lost
+    controller_health: Option<ControllerHealth>,
+}
+
+impl BackendSettings {
+    /// Returns true if write statements should be rejected, either because the backend was
+    /// configured to be read-only or because the [`READ_ONLY`] feature flag is enabled for the
+    /// deployment
+    fn is_read_only(&self) -> bool {
+        self.read_only || self.feature_flags.is_enabled(READ_ONLY)
+    }
+
+    /// Returns a [`ReadySetError::ReadOnly`] error if we're running in read-only mode and `query`
+    /// would write data.
+    ///
+    /// This has to be checked before deciding whether to proxy `query`, so that writes are never
+    /// sent upstream. Queries which we failed to parse (`parsed` is `None`) are classified by
+    /// their leading keyword.
+    fn check_read_only(&self, query: &str, parsed: Option<&SqlQuery>) -> ReadySetResult<()> {
+        if !self.is_read_only() {
+            return Ok(());
+        }
+
+        let write = match parsed {
+            Some(parsed @ (SqlQuery::Insert(_) | SqlQuery::Update(_) | SqlQuery::Delete(_))) => {
+                Some(parsed.query_type())
+            }
+            Some(_) => None,
+            None => unparsed_write_type(query),
+        };
+
+        match write {
+            Some(statement) => Err(ReadySetError::ReadOnly {
+                statement: statement.to_owned(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns true if reads should be proxied upstream because the controller has been lost
+    fn controller_lost(&self) -> bool {
+        self.controller_health
+            .as_ref()
+            .map_or(false, ControllerHealth::is_lost)
+    }
+}
+
+/// Returns the type of write statement `query` is, based on its first keyword (skipping any
+/// leading comments), or `None` if it doesn't look like a write.
+fn unparsed_write_type(query: &str) -> Option<&'static str> {
+    let keyword = leading_keyword(query);
+    ["INSERT", "UPDATE", "DELETE", "REPLACE"]
+        .into_iter()
+        .find(|write| keyword.eq_ignore_ascii_case(write))
 }
 
 /// QueryInfo holds information regarding the last query that was sent along this connection
@@ -555,6 +825,64 @@ struct BackendSettings {
 pub struct QueryInfo {
     pub destination: QueryDestination,
     pub noria_error: String,
+    /// A warning about the results of the query, if any, such as that they're incomplete
+    pub warning: String,
+}
+
+/// Builds the warning to report about the results of a query, or an empty string if there's
+/// nothing to warn about.
+///
+/// Queries warn if they returned partial results because some of the shards of the cache they read
+/// from were unavailable, if they were writes that truncated values too long for their column, or,
+/// if `zero_row_write_mode` is [`ZeroRowWriteMode::Warn`], if they were writes that didn't affect
+/// any rows.
+fn query_warning(event: &QueryExecutionEvent, zero_row_write_mode: ZeroRowWriteMode) -> String {
+    match (event.unavailable_shards, event.rows_affected) {
+        (Some(n), _) if n > 0 => {
+            format!("Results are incomplete: {n} shard(s) of the cache were unavailable")
+        }
+        _ if !event.truncated_columns.is_empty() => format!(
+            "Data truncated for column(s) {}",
+            event.truncated_columns.join(", ")
+        ),
+        (_, Some(0)) if zero_row_write_mode == ZeroRowWriteMode::Warn => {
+            "Statement did not affect any rows".to_owned()
+        }
+        _ => String::new(),
+    }
+}
+
+/// Generates the response to a `SHOW WARNINGS` query for the given warning about the results of the
+/// last query, in the format used by MySQL
+fn show_warnings(warning: &str) -> noria_connector::QueryResult<'static> {
+    /// The MySQL error code for an unknown error, which we report all of our warnings with
+    const ER_UNKNOWN_ERROR: u32 = 1105;
+
+    let column = |name: &str, column_type| ColumnSchema {
+        column: nom_sql::Column {
+            name: name.into(),
+            table: None,
+        },
+        column_type,
+        base: None,
+    };
+
+    noria_connector::QueryResult::from_owned(
+        SelectSchema {
+            use_bogo: false,
+            schema: Cow::Owned(vec![
+                column("Level", DfType::DEFAULT_TEXT),
+                column("Code", DfType::UnsignedInt),
+                column("Message", DfType::DEFAULT_TEXT),
+            ]),
+            columns: Cow::Owned(vec!["Level".into(), "Code".into(), "Message".into()]),
+        },
+        vec![Results::new(vec![vec![
+            DfValue::from("Warning"),
+            DfValue::from(ER_UNKNOWN_ERROR),
+            DfValue::from(warning),
+        ]])],
+    )
 }
 
 impl FromRow for QueryInfo {
@@ -573,6 +901,8 @@ impl FromRow for QueryInfo {
                     res.noria_error = std::str::from_utf8(d)
                         .map_err(|_| FromRowError(row.clone()))?
                         .to_string();
+                } else if c.name_str() == "ReadySet_warning" {
+                    res.warning = dest.to_string();
                 } else {
                     return Err(FromRowError(row.clone()));
                 }
@@ -686,6 +1016,17 @@ where
     Upstream(DB::QueryResult<'a>),
 }
 
+impl<'a, DB: UpstreamDatabase> QueryResult<'a, DB> {
+    /// Render any timestamps in results from ReadySet in the given format. Results from the
+    /// upstream database are returned unchanged.
+    fn with_timestamp_format(self, format: TimestampFormat) -> Self {
+        match self {
+            Self::Noria(r) => Self::Noria(format.apply(r)),
+            r => r,
+        }
+    }
+}
+
 impl<'a, DB: UpstreamDatabase> From<noria_connector::QueryResult<'a>> for QueryResult<'a, DB> {
     fn from(r: noria_connector::QueryResult<'a>) -> Self {
         Self::Noria(r)
@@ -718,6 +1059,12 @@ where
     DB: 'static + UpstreamDatabase,
     Handler: 'static + QueryHandler,
 {
+    /// Returns the id assigned to this connection, which can be passed to `KILL QUERY` from
+    /// another connection to cancel the query this connection is running
+    pub fn connection_id(&self) -> u32 {
+        self.cancellation.id()
+    }
+
     pub fn version(&self) -> String {
         self.upstream
             .as_ref()
@@ -725,18 +1072,26 @@ where
             .unwrap_or_else(|| DB::DEFAULT_DB_VERSION.to_string())
     }
 
-    /// The identifier of the last prepared statement (which is always the last in the vector)
+    /// The identifier of the last prepared statement
     pub fn last_prepared_id(&self) -> u32 {
-        (self.state.prepared_statements.len() - 1)
-            .try_into()
-            .expect("Too many prepared statements")
+        self.state.next_prepared_id - 1
     }
 
     /// The identifier we can reserve for the next prepared statement
     pub fn next_prepared_id(&self) -> u32 {
-        (self.state.prepared_statements.len())
-            .try_into()
-            .expect("Too many prepared statements")
+        self.state.next_prepared_id
+    }
+
+    /// Deallocate the prepared statement with the given id, after the client closes it
+    pub fn remove_statement(&mut self, id: u32) {
+        if let Some(cached_statement) = self.state.prepared_statements.remove(&id) {
+            if matches!(
+                cached_statement.prep,
+                PrepareResult::Noria(_) | PrepareResult::Both(..)
+            ) {
+                self.noria.remove_prepared_statement(id);
+            }
+        }
     }
 
     /// Switch the active database for this backend to the given named database.
@@ -758,6 +1113,49 @@ where
         Ok(())
     }
 
+    /// Compares the rows returned by ReadySet for a query with the rows returned by executing the
+    /// same query against the upstream database, reporting any mismatch between them, and returns
+    /// the (unchanged) ReadySet result.
+    ///
+    /// Failing to execute the query upstream is logged, but otherwise doesn't affect the result.
+    fn validate_results<'a>(
+        upstream_rows: Result<Vec<Vec<DfValue>>, DB::Error>,
+        statement: &SelectStatement,
+        result: noria_connector::QueryResult<'a>,
+        level: Level,
+    ) -> noria_connector::QueryResult<'a> {
+        let (rows, schema) = match result {
+            noria_connector::QueryResult::Select { rows, schema } => (rows.into_vec(), schema),
+            result => return result,
+        };
+
+        match upstream_rows {
+            Ok(upstream_rows) => {
+                // Only compare the columns that are actually returned to the client. A row that's
+                // missing any of those columns is left short, so the missing values are reported
+                // as part of the mismatch.
+                let positions = schema
+                    .schema
+                    .iter()
+                    .filter_map(|cs| schema.columns.iter().position(|c| *c == cs.column.name))
+                    .collect::<Vec<_>>();
+                let readyset_rows = rows
+                    .iter()
+                    .map(|row| {
+                        positions
+                            .iter()
+                            .map_while(|&i| row.get(i).cloned())
+                            .collect()
+                    })
+                    .collect();
+                query_validation::report_mismatch(statement, upstream_rows, readyset_rows, level);
+            }
+            Err(error) => warn!(%error, "Could not execute query upstream for validation"),
+        }
+
+        noria_connector::QueryResult::from_owned(schema, vec![Results::new(rows)])
+    }
+
     /// Executes query on the upstream database, for when it cannot be parsed or executed by noria.
     /// Returns the query result, or an error if fallback is not configured
     #[instrument_root(level = "info")]
@@ -803,9 +1201,21 @@ where
         event: &mut QueryExecutionEvent,
     ) -> Result<PrepareResult<DB>, DB::Error> {
         let prep_idx = self.next_prepared_id();
+        let view_request = ViewCreateRequest::new(
+            select_meta.rewritten.clone(),
+            self.noria.schema_search_path().to_owned(),
+        );
 
-        let do_noria = select_meta.should_do_noria;
         let do_migrate = select_meta.must_migrate;
+        // If previous attempts to migrate this query have failed, only prepare against the
+        // upstream database until the backoff has elapsed
+        let do_noria = select_meta.should_do_noria
+            && !(do_migrate
+                && self.upstream.is_some()
+                && !self
+                    .state
+                    .query_status_cache
+                    .migration_allowed(&view_request));
 
         let up_prep: OptionFuture<_> = self.upstream.as_mut().map(|u| u.prepare(query)).into();
         let noria_prep: OptionFuture<_> = do_noria
@@ -829,6 +1239,7 @@ where
         self.last_query = destination.map(|d| QueryInfo {
             destination: d,
             noria_error: String::new(),
+            warning: String::new(),
         });
 
         // Update noria migration state for query
@@ -845,45 +1256,45 @@ where
                             if self.settings.fail_invalidated_queries {
                                 internal!("Query comparison failed to validate: {}", e);
                             }
-                            warn!(error = %e,
-                                  query = %Sensitive(&select_meta.stmt),
-                                  "Query compare failed");
+                            query_validation::report_schema_mismatch(
+                                &select_meta.stmt,
+                                &e,
+                                self.settings.validation_mismatch_log_level,
+                            );
                             state = MigrationState::Unsupported;
                         }
                     }
                 }
 
-                self.state.query_status_cache.update_query_migration_state(
-                    &ViewCreateRequest::new(
-                        select_meta.rewritten.clone(),
-                        self.noria.schema_search_path().to_owned(),
-                    ),
-                    state,
-                );
+                if do_migrate {
+                    self.state
+                        .query_status_cache
+                        .migration_succeeded(&view_request);
+                }
+                self.state
+                    .query_status_cache
+                    .update_query_migration_state(&view_request, state);
             }
             Some(Err(e)) => {
                 if e.caused_by_view_not_found() {
                     warn!(error = %e, "View not found during mirror_prepare()");
-                    self.state.query_status_cache.update_query_migration_state(
-                        &ViewCreateRequest::new(
-                            select_meta.rewritten.clone(),
-                            self.noria.schema_search_path().to_owned(),
-                        ),
-                        MigrationState::Pending,
-                    );
+                    self.state
+                        .query_status_cache
+                        .update_query_migration_state(&view_request, MigrationState::Pending);
                 } else if e.caused_by_unsupported() {
-                    self.state.query_status_cache.update_query_migration_state(
-                        &ViewCreateRequest::new(
-                            select_meta.rewritten.clone(),
-                            self.noria.schema_search_path().to_owned(),
-                        ),
-                        MigrationState::Unsupported,
-                    );
+                    self.state
+                        .query_status_cache
+                        .update_query_migration_state(&view_request, MigrationState::Unsupported);
                 } else {
                     error!(
                         error = %e,
                         "Error received from noria during mirror_prepare()"
                     );
+                    if do_migrate && !e.is_networking_related() {
+                        self.state
+                            .query_status_cache
+                            .migration_failed(&view_request);
+                    }
                 }
                 event.set_noria_error(e);
             }
@@ -919,6 +1330,7 @@ where
             self.last_query = Some(QueryInfo {
                 destination: QueryDestination::Upstream,
                 noria_error: String::new(),
+                warning: String::new(),
             });
             res
         } else {
@@ -933,6 +1345,7 @@ where
             self.last_query = Some(QueryInfo {
                 destination: QueryDestination::Readyset,
                 noria_error: String::new(),
+                warning: String::new(),
             });
             Ok(PrepareResult::Noria(res))
         }
@@ -1006,6 +1419,11 @@ where
         }
 
         match self.parse_query(query) {
+            Ok(SqlQuery::Select(_))
+                if self.upstream.is_some() && self.settings.controller_lost() =>
+            {
+                PrepareMeta::Proxy
+            }
             Ok(SqlQuery::Select(stmt)) => self.plan_prepare_select(stmt),
             Ok(
                 query @ SqlQuery::Insert(_)
@@ -1035,6 +1453,23 @@ where
         query: &str,
         event: &mut QueryExecutionEvent,
     ) -> Result<PrepareResult<DB>, DB::Error> {
+        // SELECTs ReadySet can't support are only prepared upstream if the unsupported query mode
+        // allows it
+        let unsupported_select = match meta {
+            PrepareMeta::FailedToRewrite => true,
+            PrepareMeta::FailedToParse => leading_keyword(query).eq_ignore_ascii_case("SELECT"),
+            PrepareMeta::Select(PrepareSelectMeta {
+                should_do_noria, ..
+            }) => !should_do_noria,
+            _ => false,
+        };
+        if unsupported_select
+            && self.upstream.is_some()
+            && !Self::should_proxy_unsupported(&self.settings, query)
+        {
+            unsupported!("Query is not supported by ReadySet");
+        }
+
         match meta {
             PrepareMeta::Proxy
             | PrepareMeta::FailedToParse
@@ -1051,6 +1486,7 @@ where
                 self.last_query = Some(QueryInfo {
                     destination: QueryDestination::Upstream,
                     noria_error: String::new(),
+                    warning: String::new(),
                 });
 
                 res
@@ -1066,10 +1502,23 @@ where
     /// Prepares `query` to be executed later using the reader/writer belonging
     /// to the calling `Backend` struct and adds the prepared query
     /// to the calling struct's map of prepared queries with a unique id.
-    #[instrument_root(level = "info")]
+    #[instrument_root(level = "info", fields(tags = tracing::field::Empty))]
     pub async fn prepare(&mut self, query: &str) -> Result<&PrepareResult<DB>, DB::Error> {
         self.last_query = None;
         let mut query_event = QueryExecutionEvent::new(EventType::Prepare);
+        query_event.tags = QueryTags::from_leading_comment(query);
+        record_query_tags(&query_event.tags);
+
+        if self.settings.is_read_only() {
+            let parsed = self.parse_query(query).ok();
+            self.settings.check_read_only(query, parsed.as_ref())?;
+        }
+
+        let audit_statement = if self.settings.audit_log.is_some() {
+            AuditLog::ddl_statement(query, self.parse_query(query).ok().as_ref())
+        } else {
+            None
+        };
 
         let meta = self.plan_prepare(query).await;
         let res = self.do_prepare(&meta, query, &mut query_event).await?;
@@ -1118,11 +1567,20 @@ where
             parsed_query,
             view_request,
             always,
+            tags: query_event.tags,
+            audit_statement,
         };
 
-        self.state.prepared_statements.push(cache_entry);
-
-        Ok(&self.state.prepared_statements.last().unwrap().prep)
+        let statement_id = self.state.next_prepared_id;
+        self.state.next_prepared_id = statement_id
+            .checked_add(1)
+            .ok_or_else(|| internal_err!("Too many prepared statements"))?;
+        Ok(&self
+            .state
+            .prepared_statements
+            .entry(statement_id)
+            .or_insert(cache_entry)
+            .prep)
     }
 
     /// Executes a prepared statement on ReadySet
@@ -1145,15 +1603,38 @@ where
                 let ctx = ExecuteSelectContext::Prepared { q_id: *id, params };
                 noria.execute_select(ctx, ticket, event).await
             }
+            // Take ownership of the results of writes, which borrow from `noria`, so that we can
+            // look up which columns they truncated
             Insert {
                 statement_id: id, ..
-            } => noria.execute_prepared_insert(*id, params).await,
+            } => {
+                let res = noria
+                    .execute_prepared_insert(*id, params)
+                    .await
+                    .map(noria_connector::QueryResult::into_owned);
+                event.truncated_columns = noria.take_truncated_columns();
+                res
+            }
             Update {
                 statement_id: id, ..
-            } => noria.execute_prepared_update(*id, params).await,
+            } => {
+                let res = noria
+                    .execute_prepared_update(*id, params)
+                    .await
+                    .map(noria_connector::QueryResult::into_owned);
+                event.truncated_columns = noria.take_truncated_columns();
+                res
+            }
             Delete {
                 statement_id: id, ..
-            } => noria.execute_prepared_delete(*id, params).await,
+            } => {
+                let res = noria
+                    .execute_prepared_delete(*id, params)
+                    .await
+                    .map(noria_connector::QueryResult::into_owned);
+                event.truncated_columns = noria.take_truncated_columns();
+                res
+            }
         }
         .map(Into::into);
 
@@ -1193,6 +1674,10 @@ where
     }
 
     /// Execute on ReadySet, and if fails execute on upstream
+    ///
+    /// If `validate` is set to the statement being executed, the results returned by ReadySet are
+    /// also validated against the results of executing the statement upstream, reporting any
+    /// mismatch at the given level.
     #[allow(clippy::too_many_arguments)] // meh.
     async fn execute_cascade<'a>(
         noria: &'a mut NoriaConnector,
@@ -1202,6 +1687,7 @@ where
         params: &[DfValue],
         ex_info: Option<&mut ExecutionInfo>,
         ticket: Option<Timestamp>,
+        validate: Option<(&SelectStatement, Level)>,
         event: &mut QueryExecutionEvent,
     ) -> Result<QueryResult<'a, DB>, DB::Error> {
         let noria_res = Self::execute_noria(noria, noria_prep, params, ticket, event).await;
@@ -1210,7 +1696,20 @@ where
                 if let Some(info) = ex_info {
                     info.execute_succeeded();
                 }
-                Ok(noria_ok)
+                match (noria_ok, validate, upstream.as_mut()) {
+                    (QueryResult::Noria(result), Some((statement, level)), Some(upstream)) => {
+                        Ok(Self::validate_results(
+                            upstream
+                                .execute_rows(upstream_prep.statement_id, params)
+                                .await,
+                            statement,
+                            result,
+                            level,
+                        )
+                        .into())
+                    }
+                    (noria_ok, ..) => Ok(noria_ok),
+                }
             }
             Err(noria_err) => {
                 if let Some(info) = ex_info {
@@ -1223,6 +1722,10 @@ where
                         info.execute_unsupported();
                     }
                 }
+                // The client asked for the statement to be aborted, not retried elsewhere
+                if noria_err.is_statement_timeout() {
+                    return Err(noria_err.into());
+                }
                 if !matches!(noria_err, ReadySetError::ReaderMissingKey) {
                     warn!(error = %noria_err,
                           "Error received from noria, sending query to fallback");
@@ -1233,9 +1736,10 @@ where
         }
     }
 
-    /// Attempts to migrate a query on noria, after it was marked as Successful in the cache. If the
-    /// migration is successful, the cached entry is marked as such and will attempt to resolve
-    /// noria first in the future
+    /// Attempts to migrate a query on noria, after it was marked as Successful in the cache, or
+    /// (if `create_if_not_exist` is true) after previous failed attempts to migrate it have backed
+    /// off. If the migration is successful, the cached entry is marked as such and will attempt to
+    /// resolve noria first in the future
     ///
     /// # Panics
     ///
@@ -1245,6 +1749,7 @@ where
         noria: &mut NoriaConnector,
         cached_entry: &mut CachedPreparedStatement<DB>,
         id: u32,
+        create_if_not_exist: bool,
     ) -> ReadySetResult<()> {
         debug_assert!(cached_entry.migration_state.is_pending());
 
@@ -1267,7 +1772,7 @@ where
                     .prepare_select(
                         stmt.clone(),
                         id,
-                        false,
+                        create_if_not_exist,
                         cached_entry
                             .view_request
                             .as_ref()
@@ -1286,13 +1791,77 @@ where
         Ok(())
     }
 
+    /// If ReadySet deallocated the given statement to stay under the prepared statement limit,
+    /// prepare it against ReadySet again using its parsed query, so that it can be executed
+    /// transparently.
+    ///
+    /// If preparing the statement again fails but it was also prepared against the upstream
+    /// database, it's marked as pending migration and executed against the upstream database
+    /// instead.
+    async fn reprepare_noria_if_evicted(
+        noria: &mut NoriaConnector,
+        cached_entry: &mut CachedPreparedStatement<DB>,
+        id: u32,
+    ) -> ReadySetResult<()> {
+        if !matches!(
+            cached_entry.prep,
+            PrepareResult::Noria(_) | PrepareResult::Both(..)
+        ) || noria.has_prepared_statement(id)
+        {
+            return Ok(());
+        }
+
+        let parsed_statement = cached_entry
+            .parsed_query
+            .as_ref()
+            .ok_or_else(|| internal_err!("Statements prepared on ReadySet must be parsed"))?;
+
+        trace!(id, "Re-preparing statement deallocated by ReadySet");
+        let res = match &**parsed_statement {
+            SqlQuery::Select(stmt) => {
+                noria
+                    .prepare_select(
+                        stmt.clone(),
+                        id,
+                        false,
+                        cached_entry
+                            .view_request
+                            .as_ref()
+                            .map(|pr| pr.schema_search_path.clone()),
+                    )
+                    .await
+            }
+            SqlQuery::Insert(stmt) => noria.prepare_insert(stmt.clone(), id).await,
+            SqlQuery::Update(stmt) => noria.prepare_update(stmt.clone(), id).await,
+            SqlQuery::Delete(stmt) => noria.prepare_delete(stmt.clone(), id).await,
+            _ => internal!("Only SELECT, INSERT, UPDATE and DELETE can be prepared on ReadySet"),
+        };
+
+        match (res, &mut cached_entry.prep) {
+            (Ok(noria_prep), PrepareResult::Noria(prep) | PrepareResult::Both(prep, _)) => {
+                *prep = noria_prep;
+                Ok(())
+            }
+            (Err(e), prep @ PrepareResult::Both(..)) => {
+                warn!(error = %e, "Failed to re-prepare statement on ReadySet");
+                prep.make_upstream_only();
+                if cached_entry.migration_state == MigrationState::Successful {
+                    cached_entry.migration_state = MigrationState::Pending;
+                }
+                Ok(())
+            }
+            (Err(e), _) => Err(e),
+            (Ok(_), PrepareResult::Upstream(_)) => Ok(()),
+        }
+    }
+
     /// Iterate over the cache of the prepared statements, and invalidate those that are
     /// equal to the one provided
     fn invalidate_prepared_statements_cache(&mut self, stmt: &ViewCreateRequest) {
         // Linear scan, but we shouldn't be doing it often, right?
         self.state
             .prepared_statements
-            .iter_mut()
+            .values_mut()
             .filter_map(
                 |CachedPreparedStatement {
                      prep,
@@ -1318,7 +1887,7 @@ where
     /// A [`QueryExecutionEvent`], is used to track metrics and behavior scoped to the
     /// execute operation.
     // TODO(andrew, justin): add RYW support for executing prepared queries
-    #[instrument_root(level = "info", fields(id))]
+    #[instrument_root(level = "info", fields(id, tags = tracing::field::Empty))]
     #[inline]
     pub async fn execute(
         &mut self,
@@ -1326,15 +1895,30 @@ where
         params: &[DfValue],
     ) -> Result<QueryResult<'_, DB>, DB::Error> {
         self.last_query = None;
+        self.state.query_count += 1;
         let cached_statement = self
             .state
             .prepared_statements
-            .get_mut(id as usize)
+            .get_mut(&id)
             .ok_or(PreparedStatementMissing { statement_id: id })?;
 
         let mut event = QueryExecutionEvent::new(EventType::Execute);
         event.query = cached_statement.parsed_query.clone();
         event.query_id = cached_statement.query_id;
+        event.tags = cached_statement.tags.clone();
+        record_query_tags(&event.tags);
+
+        if let (Some(audit_log), Some(statement)) =
+            (&self.settings.audit_log, &cached_statement.audit_statement)
+        {
+            audit_log
+                .record(
+                    self.settings.client_addr,
+                    self.state.user.as_deref(),
+                    statement.clone(),
+                )
+                .await;
+        }
 
         let upstream = &mut self.upstream;
         let noria = &mut self.noria;
@@ -1343,23 +1927,55 @@ where
         if cached_statement.migration_state.is_pending() {
             // We got a statement with a pending migration, we want to check if migration is
             // finished by now
+            let view_request = cached_statement
+                .view_request
+                .clone()
+                .expect("Pending must have view_request set");
             let new_migration_state = self
                 .state
                 .query_status_cache
-                .query_migration_state(
-                    cached_statement
-                        .view_request
-                        .as_ref()
-                        .expect("Pending must have view_request set"),
-                )
+                .query_migration_state(&view_request)
                 .1;
 
             if new_migration_state == MigrationState::Successful {
                 // Attempt to prepare on ReadySet
-                let _ = Self::update_noria_prepare(noria, cached_statement, id).await;
+                let _ = Self::update_noria_prepare(noria, cached_statement, id, false).await;
+            } else if new_migration_state == MigrationState::Pending
+                && self.settings.migration_mode == MigrationMode::InRequestPath
+                && self
+                    .state
+                    .query_status_cache
+                    .migration_allowed(&view_request)
+            {
+                // The statement was prepared while previous failed attempts to migrate the query
+                // were backing off (or its migration failed), so try to migrate it again now that
+                // the backoff has elapsed
+                if let Err(e) = Self::update_noria_prepare(noria, cached_statement, id, true).await
+                {
+                    if e.caused_by_unsupported() {
+                        self.state.query_status_cache.update_query_migration_state(
+                            &view_request,
+                            MigrationState::Unsupported,
+                        );
+                    } else if !e.is_networking_related() && !e.caused_by_view_not_found() {
+                        self.state
+                            .query_status_cache
+                            .migration_failed(&view_request);
+                    }
+                    event.set_noria_error(&e);
+                } else {
+                    self.state
+                        .query_status_cache
+                        .migration_succeeded(&view_request);
+                    self.state
+                        .query_status_cache
+                        .update_query_migration_state(&view_request, MigrationState::Successful);
+                }
             }
         }
 
+        Self::reprepare_noria_if_evicted(noria, cached_statement, id).await?;
+
         let should_fallback = {
             if cached_statement.always {
                 false
@@ -1380,43 +1996,89 @@ where
                 } else if always_readyset {
                     false
                 } else {
-                    is_recovering || self.state.proxy_state.should_proxy()
+                    is_recovering
+                        || self.state.proxy_state.should_proxy()
+                        || self.settings.controller_lost()
                 }
             }
         };
 
-        let result = match &cached_statement.prep {
-            PrepareResult::Noria(prep) => {
-                Self::execute_noria(noria, prep, params, ticket, &mut event)
-                    .await
-                    .map_err(Into::into)
-            }
-            PrepareResult::Upstream(prep) => {
-                Self::execute_upstream(upstream, prep, params, &mut event, false).await
-            }
-            PrepareResult::Both(.., uprep) if should_fallback => {
-                Self::execute_upstream(upstream, uprep, params, &mut event, false).await
-            }
-            PrepareResult::Both(nprep, uprep) => {
-                if cached_statement.execution_info.is_none() {
-                    cached_statement.execution_info = Some(ExecutionInfo {
-                        state: ExecutionState::Failed,
-                        last_transition_time: Instant::now(),
-                    });
+        let read_your_writes_timeout = self.settings.read_your_writes_timeout;
+        let zero_row_write_mode = self.settings.zero_row_write_mode;
+        let validate_queries = self.settings.validate_queries;
+        let validation_mismatch_log_level = self.settings.validation_mismatch_log_level;
+        let unreplicated_writes = &mut self.state.unreplicated_writes;
+        let cancellation = &self.cancellation;
+        let upstream_cancel = upstream.as_ref().map(|db| db.cancel_handle());
+        let result = cancellation
+            .run(upstream_cancel, async {
+                // Don't read from ReadySet until it reflects this session's writes
+                let caught_up = should_fallback
+                    || !matches!(cached_statement.prep, PrepareResult::Both(..))
+                    || Self::wait_for_session_writes(
+                        noria,
+                        upstream.as_mut(),
+                        read_your_writes_timeout,
+                        unreplicated_writes,
+                    )
+                    .await;
+                match &cached_statement.prep {
+                    PrepareResult::Noria(prep) => {
+                        Self::execute_noria(noria, prep, params, ticket, &mut event)
+                            .await
+                            .and_then(|res| {
+                                if let QueryResult::Noria(noria_res) = &res {
+                                    check_rows_affected(
+                                        zero_row_write_mode,
+                                        noria_res,
+                                        &mut event,
+                                    )?;
+                                }
+                                Ok(res)
+                            })
+                            .map_err(Into::into)
+                    }
+                    PrepareResult::Upstream(prep) => {
+                        if matches!(
+                            cached_statement.parsed_query.as_deref(),
+                            Some(SqlQuery::Insert(_) | SqlQuery::Update(_) | SqlQuery::Delete(_))
+                        ) {
+                            *unreplicated_writes = true;
+                        }
+                        Self::execute_upstream(upstream, prep, params, &mut event, false).await
+                    }
+                    PrepareResult::Both(.., uprep) if should_fallback || !caught_up => {
+                        Self::execute_upstream(upstream, uprep, params, &mut event, false).await
+                    }
+                    PrepareResult::Both(nprep, uprep) => {
+                        let validate = match cached_statement.parsed_query.as_deref() {
+                            Some(SqlQuery::Select(stmt)) if validate_queries => {
+                                Some((stmt, validation_mismatch_log_level))
+                            }
+                            _ => None,
+                        };
+                        if cached_statement.execution_info.is_none() {
+                            cached_statement.execution_info = Some(ExecutionInfo {
+                                state: ExecutionState::Failed,
+                                last_transition_time: Instant::now(),
+                            });
+                        }
+                        Self::execute_cascade(
+                            noria,
+                            upstream,
+                            nprep,
+                            uprep,
+                            params,
+                            cached_statement.execution_info.as_mut(),
+                            ticket,
+                            validate,
+                            &mut event,
+                        )
+                        .await
+                    }
                 }
-                Self::execute_cascade(
-                    noria,
-                    upstream,
-                    nprep,
-                    uprep,
-                    params,
-                    cached_statement.execution_info.as_mut(),
-                    ticket,
-                    &mut event,
-                )
-                .await
-            }
-        };
+            })
+            .await;
 
         if let Some(e) = event.noria_error.as_ref() {
             if e.caused_by_view_not_found() {
@@ -1442,10 +2104,11 @@ where
                 .as_ref()
                 .map(|e| e.to_string())
                 .unwrap_or_default(),
+            warning: query_warning(&event, self.settings.zero_row_write_mode),
         });
         log_query(self.query_log_sender.as_ref(), event, self.settings.slowlog);
 
-        result
+        result.map(|r| r.with_timestamp_format(self.state.timestamp_format))
     }
 
     /// Should only be called with a SqlQuery that is of type StartTransaction, Commit, or
@@ -1490,7 +2153,7 @@ where
 
     /// Generates response to the `EXPLAIN LAST STATEMENT` query
     fn explain_last_statement(&self) -> ReadySetResult<noria_connector::QueryResult<'static>> {
-        let (destination, error) = self
+        let (destination, error, warning) = self
             .last_query
             .as_ref()
             .map(|info| {
@@ -1500,16 +2163,68 @@ where
                         s if s.is_empty() => "ok".to_string(),
                         s => s.clone(),
                     },
+                    match &info.warning {
+                        s if s.is_empty() => "none".to_string(),
+                        s => s.clone(),
+                    },
                 )
             })
-            .unwrap_or_else(|| ("unknown".to_string(), "ok".to_string()));
+            .unwrap_or_else(|| ("unknown".to_string(), "ok".to_string(), "none".to_string()));
 
         Ok(noria_connector::QueryResult::Meta(vec![
             ("Query_destination", destination).into(),
             ("ReadySet_error", error).into(),
+            ("ReadySet_warning", warning).into(),
         ]))
     }
 
+    /// If `stmt` is a MySQL `SELECT LAST_INSERT_ID()` which should be answered by the adapter,
+    /// returns the name of the column to return the result in.
+    ///
+    /// The value of `LAST_INSERT_ID()` is specific to the session which made the insert, so if the
+    /// session's inserts have been made against ReadySet (always the case if there's no upstream
+    /// database), the upstream database can't know it.
+    fn last_insert_id_column(&self, stmt: &SelectStatement) -> Option<SqlIdentifier> {
+        if self.settings.dialect != Dialect::MySQL
+            || (self.upstream.is_some() && self.noria.last_insert_id() == 0)
+            || !stmt.tables.is_empty()
+            || !stmt.join.is_empty()
+        {
+            return None;
+        }
+        match stmt.fields.as_slice() {
+            [FieldDefinitionExpr::Expr {
+                expr: expr @ Expr::Call(FunctionExpr::Call { name, arguments }),
+                alias,
+            }] if name.eq_ignore_ascii_case("last_insert_id") && arguments.is_empty() => {
+                Some(alias.clone().unwrap_or_else(|| expr.to_string().into()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Generates the response to a `SELECT LAST_INSERT_ID()` query, returning the value in a
+    /// column with the given name
+    fn last_insert_id(&self, column: SqlIdentifier) -> noria_connector::QueryResult<'static> {
+        noria_connector::QueryResult::from_owned(
+            SelectSchema {
+                use_bogo: false,
+                schema: Cow::Owned(vec![ColumnSchema {
+                    column: nom_sql::Column {
+                        name: column.clone(),
+                        table: None,
+                    },
+                    column_type: DfType::UnsignedBigInt,
+                    base: None,
+                }]),
+                columns: Cow::Owned(vec![column]),
+            },
+            vec![Results::new(vec![vec![DfValue::from(
+                self.noria.last_insert_id(),
+            )]])],
+        )
+    }
+
     /// Forwards a `CREATE CACHE` request to noria
     async fn create_cached_query(
         &mut self,
@@ -1517,6 +2232,8 @@ where
         mut stmt: SelectStatement,
         override_schema_search_path: Option<Vec<SqlIdentifier>>,
         always: bool,
+        index_type: Option<nom_sql::IndexType>,
+        max_mem: Option<u64>,
     ) -> ReadySetResult<noria_connector::QueryResult<'static>> {
         // If we have another query with the same name, drop that query first
         if let Some(name) = name {
@@ -1532,7 +2249,14 @@ where
         // Now migrate the new query
         rewrite::process_query(&mut stmt, self.noria.server_supports_pagination())?;
         self.noria
-            .handle_create_cached_query(name, &stmt, override_schema_search_path, always)
+            .handle_create_cached_query(
+                name,
+                &stmt,
+                override_schema_search_path,
+                always,
+                index_type,
+                max_mem,
+            )
             .await?;
         self.state.query_status_cache.update_query_migration_state(
             &ViewCreateRequest::new(stmt.clone(), self.noria.schema_search_path().to_owned()),
@@ -1568,7 +2292,7 @@ where
     async fn drop_all_caches(&mut self) -> ReadySetResult<noria_connector::QueryResult<'static>> {
         self.noria.drop_all_caches().await?;
         self.state.query_status_cache.clear();
-        self.state.prepared_statements.iter_mut().for_each(
+        self.state.prepared_statements.values_mut().for_each(
             |CachedPreparedStatement {
                  prep,
                  migration_state,
@@ -1583,6 +2307,109 @@ where
         Ok(noria_connector::QueryResult::Empty)
     }
 
+    /// Responds to a `WAIT FOR REPLICATION` statement, by blocking until ReadySet has replicated
+    /// every write committed to the upstream database before the statement was received, then
+    /// returning the same status information as `SHOW READYSET STATUS`.
+    ///
+    /// Without an upstream database all writes go directly to ReadySet, so there's nothing to wait
+    /// for. Returns [`ReadySetError::ReplicationWaitTimeout`] if ReadySet doesn't catch up within
+    /// [`WAIT_FOR_REPLICATION_TIMEOUT`].
+    async fn wait_for_replication(
+        &mut self,
+    ) -> Result<noria_connector::QueryResult<'static>, DB::Error> {
+        let target = match self.upstream.as_mut() {
+            Some(upstream) => upstream.replication_offset().await?,
+            None => None,
+        };
+
+        if let Some(target) = target {
+            if !Self::poll_replication_offset(
+                &mut self.noria,
+                &target,
+                WAIT_FOR_REPLICATION_TIMEOUT,
+            )
+            .await
+            {
+                return Err(ReadySetError::ReplicationWaitTimeout {
+                    timeout_secs: WAIT_FOR_REPLICATION_TIMEOUT.as_secs(),
+                }
+                .into());
+            }
+        }
+
+        Ok(self.noria.readyset_status().await?)
+    }
+
+    /// Poll ReadySet's replication offsets every [`WAIT_FOR_REPLICATION_POLL_INTERVAL`] until they
+    /// reach `target`, giving up after `timeout`.
+    ///
+    /// Returns true if ReadySet caught up to `target` before the timeout elapsed. Errors loading
+    /// the offsets are logged and retried until the timeout.
+    async fn poll_replication_offset(
+        noria: &mut NoriaConnector,
+        target: &ReplicationOffset,
+        timeout: Duration,
+    ) -> bool {
+        tokio::time::timeout(timeout, async {
+            loop {
+                match noria
+                    .replication_offsets()
+                    .await
+                    .and_then(|offsets| Ok(offsets.max_offset()?.cloned()))
+                {
+                    Ok(Some(offset)) if offset >= *target => break,
+                    Ok(_) => {}
+                    Err(error) => {
+                        warn!(%error, "Failed to load ReadySet replication offsets");
+                    }
+                }
+                tokio::time::sleep(WAIT_FOR_REPLICATION_POLL_INTERVAL).await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+
+    /// If read-your-writes is enabled (`timeout` is set) and this session has written to the
+    /// upstream database since ReadySet was last known to have replicated its writes, wait up to
+    /// `timeout` for ReadySet to catch up to the upstream database's current replication offset.
+    ///
+    /// Returns false if ReadySet might not yet reflect the session's writes, in which case the
+    /// read should be proxied to the upstream database instead.
+    async fn wait_for_session_writes(
+        noria: &mut NoriaConnector,
+        upstream: Option<&mut DB>,
+        timeout: Option<Duration>,
+        unreplicated_writes: &mut bool,
+    ) -> bool {
+        let (Some(timeout), Some(upstream)) = (timeout, upstream) else {
+            return true;
+        };
+        if !*unreplicated_writes {
+            return true;
+        }
+
+        let target = match upstream.replication_offset().await {
+            Ok(Some(target)) => target,
+            Ok(None) => {
+                // The upstream database doesn't expose replication offsets, so there's nothing we
+                // can wait for
+                *unreplicated_writes = false;
+                return true;
+            }
+            Err(error) => {
+                warn!(%error, "Failed to load upstream replication offset for read-your-writes");
+                return false;
+            }
+        };
+
+        let caught_up = Self::poll_replication_offset(noria, &target, timeout).await;
+        if caught_up {
+            *unreplicated_writes = false;
+        }
+        caught_up
+    }
+
     /// Responds to a `SHOW PROXIED QUERIES` query
     async fn show_proxied_queries(
         &mut self,
@@ -1657,10 +2484,15 @@ where
             SqlQuery::Explain(nom_sql::ExplainStatement::Graphviz { simplified }) => {
                 self.noria.graphviz(*simplified).await
             }
+            SqlQuery::Explain(nom_sql::ExplainStatement::ReadySet(stmt)) => {
+                self.noria.explain_query(stmt).await
+            }
             SqlQuery::CreateCache(CreateCacheStatement {
                 name,
                 inner,
                 always,
+                index_type,
+                max_mem,
             }) => {
                 let (stmt, search_path) = match inner {
                     CacheInner::Statement(st) => (*st.clone(), None),
@@ -1691,8 +2523,15 @@ where
                     trace!("No telemetry sender. not sending metric for CREATE CACHE");
                 }
 
-                self.create_cached_query(name.as_ref(), stmt, search_path, *always)
-                    .await
+                self.create_cached_query(
+                    name.as_ref(),
+                    stmt,
+                    search_path,
+                    *always,
+                    *index_type,
+                    *max_mem,
+                )
+                .await
             }
             SqlQuery::DropCache(DropCacheStatement { name }) => self.drop_cached_query(name).await,
             SqlQuery::DropAllCaches(_) => self.drop_all_caches().await,
@@ -1709,7 +2548,33 @@ where
                 self.noria.verbose_views(query_id).await
             }
             SqlQuery::Show(ShowStatement::ReadySetStatus) => self.noria.readyset_status().await,
-            SqlQuery::Show(ShowStatement::ReadySetVersion) => readyset_version(),
+            SqlQuery::Set(set) if let Some(format) = TimestampFormat::from_set_statement(set) => {
+                // Postgres clients decode results using the column types described when a
+                // statement was prepared, so changing the types of columns in results on the fly
+                // would leave them unable to decode the results of statements prepared earlier
+                if self.settings.dialect != Dialect::MySQL {
+                    return Some(Err(unsupported_err!(
+                        "{} is only supported for MySQL",
+                        TimestampFormat::VARIABLE
+                    )));
+                }
+                format.map(|format| {
+                    trace!(%format, "Setting timestamp format");
+                    self.state.timestamp_format = format;
+                    noria_connector::QueryResult::Empty
+                })
+            }
+            SqlQuery::Show(ShowStatement::ReadySetVersion) => self.noria.readyset_version().await,
+            SqlQuery::Select(stmt) if let Some(column) = self.last_insert_id_column(stmt) => {
+                Ok(self.last_insert_id(column))
+            }
+            // Warnings about our own results are only known to us, so SHOW WARNINGS is only proxied
+            // if the last query didn't have any
+            SqlQuery::Show(ShowStatement::Warnings)
+                if let Some(warning) = self.last_query_warning() =>
+            {
+                Ok(show_warnings(warning))
+            }
             SqlQuery::Show(ShowStatement::ProxiedQueries(q_id)) => {
                 // Log a telemetry event
                 if let Some(ref telemetry_sender) = self.telemetry_sender {
@@ -1737,7 +2602,7 @@ where
     #[allow(clippy::too_many_arguments)]
     async fn query_adhoc_select<'a>(
         noria: &'a mut NoriaConnector,
-        upstream: Option<&'a mut DB>,
+        mut upstream: Option<&'a mut DB>,
         settings: &BackendSettings,
         state: &mut BackendState<DB>,
         original_query: &'a str,
@@ -1781,6 +2646,40 @@ where
                     &status.execution_info.unwrap().last_transition_time,
                 );
             }
+            if status.migration_state == MigrationState::Unsupported
+                && upstream.is_some()
+                && !Self::should_proxy_unsupported(settings, original_query)
+            {
+                return Err(ReadySetError::Unsupported(
+                    "Query is not supported by ReadySet".to_owned(),
+                )
+                .into());
+            }
+            return Self::query_fallback(upstream, original_query, event).await;
+        }
+
+        // If previous attempts to migrate this query have failed, don't try again until the
+        // backoff has elapsed
+        let may_migrate = settings.migration_mode == MigrationMode::InRequestPath
+            && status.migration_state == MigrationState::Pending;
+        if !status.always
+            && upstream.is_some()
+            && may_migrate
+            && !state.query_status_cache.migration_allowed(view_request)
+        {
+            return Self::query_fallback(upstream, original_query, event).await;
+        }
+
+        // Don't read from ReadySet until it reflects this session's writes
+        if !status.always
+            && !Self::wait_for_session_writes(
+                noria,
+                upstream.as_deref_mut(),
+                settings.read_your_writes_timeout,
+                &mut state.unreplicated_writes,
+            )
+            .await
+        {
             return Self::query_fallback(upstream, original_query, event).await;
         }
 
@@ -1806,6 +2705,9 @@ where
         match noria_res {
             Ok(noria_ok) => {
                 // We managed to select on ReadySet, good for us
+                if may_migrate {
+                    state.query_status_cache.migration_succeeded(view_request);
+                }
                 status.migration_state = MigrationState::Successful;
                 if let Some(i) = status.execution_info.as_mut() {
                     i.execute_succeeded()
@@ -1815,7 +2717,16 @@ where
                         .query_status_cache
                         .update_query_status(view_request, status);
                 }
-                Ok(noria_ok.into())
+                match upstream {
+                    Some(upstream) if settings.validate_queries => Ok(Self::validate_results(
+                        upstream.query_rows(original_query).await,
+                        &view_request.statement,
+                        noria_ok,
+                        settings.validation_mismatch_log_level,
+                    )
+                    .into()),
+                    _ => Ok(noria_ok.into()),
+                }
             }
             Err(noria_err) => {
                 event.set_noria_error(&noria_err);
@@ -1834,7 +2745,15 @@ where
                     status.migration_state = MigrationState::Unsupported;
                 };
 
+                if may_migrate
+                    && status.migration_state == MigrationState::Pending
+                    && !noria_err.is_networking_related()
+                {
+                    state.query_status_cache.migration_failed(view_request);
+                }
+
                 let always = status.always;
+                let unsupported = status.migration_state == MigrationState::Unsupported;
 
                 if status != original_status {
                     state
@@ -1846,6 +2765,14 @@ where
                 // query.
                 match (always, upstream) {
                     (true, _) | (_, None) => Err(noria_err.into()),
+                    // The client asked for the statement to be aborted, not retried elsewhere
+                    (false, Some(_)) if noria_err.is_statement_timeout() => Err(noria_err.into()),
+                    (false, Some(_))
+                        if unsupported
+                            && !Self::should_proxy_unsupported(settings, original_query) =>
+                    {
+                        Err(noria_err.into())
+                    }
                     (false, Some(fallback)) => {
                         event.destination = Some(QueryDestination::ReadysetThenUpstream);
                         let _t = event.start_upstream_timer();
@@ -1859,6 +2786,19 @@ where
         }
     }
 
+    /// Returns whether a `SELECT` statement that ReadySet can't support should be proxied to the
+    /// upstream database, according to the configured [`UnsupportedQueryMode`]
+    fn should_proxy_unsupported(settings: &BackendSettings, query: &str) -> bool {
+        match settings.unsupported_query_mode {
+            UnsupportedQueryMode::Error => false,
+            UnsupportedQueryMode::Proxy => true,
+            UnsupportedQueryMode::LogAndProxy => {
+                warn!(query = %Sensitive(&query), "Proxying unsupported query to upstream");
+                true
+            }
+        }
+    }
+
     /// Checks if noria should try to execute a given select and in the process mutates the
     /// supplied select statement by rewriting it.
     /// Returns whether noria should try the select, along with the query status if it was obtained
@@ -1870,7 +2810,9 @@ where
                 .is_ok()
             {
                 let s = self.state.query_status_cache.query_status(q);
-                let should_try = if self.state.proxy_state.should_proxy() {
+                let should_try = if self.state.proxy_state.should_proxy()
+                    || (self.upstream.is_some() && self.settings.controller_lost())
+                {
                     s.always
                 } else {
                     true
@@ -1945,6 +2887,14 @@ where
                 trace!(?search_path, "Setting search_path");
                 noria.set_schema_search_path(search_path);
             }
+            SetBehavior::SetStatementTimeout(timeout) => {
+                trace!(?timeout, "Setting statement timeout");
+                noria.set_statement_timeout(timeout);
+            }
+            SetBehavior::SetStrictMode(strict) => {
+                trace!(%strict, "Setting strict mode");
+                noria.set_strict_mode(strict);
+            }
         }
 
         Ok(())
@@ -1987,6 +2937,7 @@ where
                     | SqlQuery::Delete(DeleteStatement { table: t, .. }) => {
                         event.sql_type = SqlQueryType::Write;
                         let _t = event.start_upstream_timer();
+                        state.unreplicated_writes = true;
 
                         // Update ticket if RYW enabled
                         let query_result = if cfg!(feature = "ryw") {
@@ -2052,7 +3003,9 @@ where
                     SqlQuery::CreateCache(_)
                     | SqlQuery::DropCache(_)
                     | SqlQuery::DropAllCaches(_)
-                    | SqlQuery::Explain(_) => {
+                    | SqlQuery::Explain(_)
+                    | SqlQuery::WaitForReplication(_)
+                    | SqlQuery::KillQuery(_) => {
                         unreachable!("path returns prior")
                     }
                 }
@@ -2089,6 +3042,16 @@ where
                     }
                 };
 
+                // Take ownership of the results, which borrow from `noria`, so that we can look up
+                // which columns the statement truncated
+                let res = res.map(noria_connector::QueryResult::into_owned);
+                event.truncated_columns = noria.take_truncated_columns();
+
+                let res = res.and_then(|res| {
+                    check_rows_affected(settings.zero_row_write_mode, &res, event)?;
+                    Ok(res)
+                });
+
                 event.readyset_duration = Some(start.elapsed());
                 event.noria_error = res.as_ref().err().cloned();
                 Ok(QueryResult::Noria(res?))
@@ -2099,31 +3062,72 @@ where
     }
 
     /// Executes `query` using the reader/writer belonging to the calling `Backend` struct.
-    #[instrument_root(level = "info")]
+    #[instrument_root(level = "info", fields(tags = tracing::field::Empty))]
     #[inline]
     pub async fn query<'a>(&'a mut self, query: &'a str) -> Result<QueryResult<'a, DB>, DB::Error> {
+        self.state.query_count += 1;
         let mut event = QueryExecutionEvent::new(EventType::Query);
+        event.tags = QueryTags::from_leading_comment(query);
+        record_query_tags(&event.tags);
         let query_log_sender = self.query_log_sender.clone();
         let slowlog = self.settings.slowlog;
+        let zero_row_write_mode = self.settings.zero_row_write_mode;
+        let timestamp_format = self.state.timestamp_format;
 
         let parse_result = {
             let _t = event.start_parse_timer();
             self.parse_query(query)
         };
 
+        if let Some(audit_log) = &self.settings.audit_log {
+            if let Some(statement) = AuditLog::ddl_statement(query, parse_result.as_ref().ok()) {
+                audit_log
+                    .record(
+                        self.settings.client_addr,
+                        self.state.user.as_deref(),
+                        statement,
+                    )
+                    .await;
+            }
+        }
+
         let result = match parse_result {
+            // Writes are rejected outright in read-only mode, before deciding whether to proxy
+            // them, including writes which we failed to parse
+            _ if let Err(e) = self
+                .settings
+                .check_read_only(query, parse_result.as_ref().ok()) =>
+            {
+                Err(e.into())
+            }
             // Parse error, but no fallback exists
             Err(e) if !self.has_fallback() => {
                 error!("{}", e);
                 Err(e.into())
             }
+            // SELECTs we can't parse are unsupported, so are only proxied if the unsupported query
+            // mode allows it
+            Err(_)
+                if leading_keyword(query).eq_ignore_ascii_case("SELECT")
+                    && !Self::should_proxy_unsupported(&self.settings, query) =>
+            {
+                Err(
+                    ReadySetError::Unsupported("Query is not supported by ReadySet".to_owned())
+                        .into(),
+                )
+            }
             // Parse error, send to fallback
             Err(e) => {
                 if !matches!(e, ReadySetError::ReaderMissingKey) {
                     warn!(error = %e, "Error received from noria, sending query to fallback");
                 }
-                let fallback_res =
-                    Self::query_fallback(self.upstream.as_mut(), query, &mut event).await;
+                let fallback_res = Self::cancellable_fallback(
+                    &self.cancellation,
+                    self.upstream.as_mut(),
+                    query,
+                    &mut event,
+                )
+                .await;
                 if fallback_res.is_ok() {
                     self.state.query_status_cache.insert(query);
 
@@ -2161,6 +3165,17 @@ where
                 .await
             }
             // ReadySet extensions should never be proxied.
+            Ok(SqlQuery::WaitForReplication(_)) => {
+                self.wait_for_replication().await.map(QueryResult::Noria)
+            }
+            // Connection ids are assigned by the adapter, so `KILL QUERY` always refers to one of
+            // our own connections rather than a connection to the upstream database
+            Ok(SqlQuery::KillQuery(KillQueryStatement { connection_id })) => self
+                .cancellation
+                .canceller()
+                .kill_query(connection_id, self.state.user.as_deref())
+                .map(|()| QueryResult::Noria(noria_connector::QueryResult::Empty))
+                .map_err(Into::into),
             Ok(ref parsed_query) if let Some(noria_extension) = self.query_noria_extensions(parsed_query, &mut event).await => {
                 noria_extension.map(Into::into).map_err(Into::into)
             }
@@ -2183,7 +3198,13 @@ where
             Ok(ref parsed_query) if Handler::requires_fallback(parsed_query) => {
                 if self.has_fallback() {
                     // Query requires a fallback and we can send it to fallback
-                    Self::query_fallback(self.upstream.as_mut(), query, &mut event).await
+                    Self::cancellable_fallback(
+                        &self.cancellation,
+                        self.upstream.as_mut(),
+                        query,
+                        &mut event,
+                    )
+                    .await
                 } else {
                     // Query requires a fallback, but none is available
                     Handler::default_response(parsed_query)
@@ -2202,24 +3223,40 @@ where
                     if self.settings.query_log_ad_hoc_queries {
                         event.query = Some(Arc::new(SqlQuery::Select(stmt.clone())));
                     }
-                    Self::query_adhoc_select(
-                        &mut self.noria,
+                    self.cancellation
+                        .run(
+                            self.upstream.as_ref().map(|db| db.cancel_handle()),
+                            Self::query_adhoc_select(
+                                &mut self.noria,
+                                self.upstream.as_mut(),
+                                &self.settings,
+                                &mut self.state,
+                                query,
+                                stmt,
+                                &view_request,
+                                status,
+                                &mut event,
+                            ),
+                        )
+                        .await
+                } else {
+                    Self::cancellable_fallback(
+                        &self.cancellation,
                         self.upstream.as_mut(),
-                        &self.settings,
-                        &mut self.state,
                         query,
-                        stmt,
-                        &view_request,
-                        status,
                         &mut event,
                     )
                     .await
-                } else {
-                    Self::query_fallback(self.upstream.as_mut(), query, &mut event).await
                 }
             }
             Ok(_) if self.state.proxy_state.should_proxy() => {
-                Self::query_fallback(self.upstream.as_mut(), query, &mut event).await
+                Self::cancellable_fallback(
+                    &self.cancellation,
+                    self.upstream.as_mut(),
+                    query,
+                    &mut event,
+                )
+                .await
             }
             Ok(parsed_query) => {
                 Self::query_adhoc_non_select(
@@ -2242,11 +3279,29 @@ where
                 .as_ref()
                 .map(|e| e.to_string())
                 .unwrap_or_default(),
+            warning: query_warning(&event, zero_row_write_mode),
         });
 
         log_query(query_log_sender.as_ref(), event, slowlog);
 
-        result
+        result.map(|r| r.with_timestamp_format(timestamp_format))
+    }
+
+    /// Proxies `query` to the upstream database with [`Self::query_fallback`], allowing it to be
+    /// cancelled with `KILL QUERY` while it's running
+    async fn cancellable_fallback<'a>(
+        cancellation: &ConnectionCancellation,
+        upstream: Option<&'a mut DB>,
+        query: &'a str,
+        event: &mut QueryExecutionEvent,
+    ) -> Result<QueryResult<'a, DB>, DB::Error> {
+        let upstream_cancel = upstream.as_deref().map(|db| db.cancel_handle());
+        cancellation
+            .run(
+                upstream_cancel,
+                Self::query_fallback(upstream, query, event),
+            )
+            .await
     }
 
     /// Whether or not we have fallback enabled.
@@ -2287,6 +3342,28 @@ where
     pub fn does_require_authentication(&self) -> bool {
         self.settings.require_authentication
     }
+
+    /// Record the user the connected client successfully authenticated as
+    pub fn set_authenticated_user(&mut self, user: &str) {
+        self.state.user = Some(user.to_owned());
+        self.cancellation.set_user(user);
+        self.noria.set_user(user.to_owned());
+    }
+
+    /// Returns how timestamps in results read from ReadySet are currently rendered for this
+    /// session
+    pub fn timestamp_format(&self) -> TimestampFormat {
+        self.state.timestamp_format
+    }
+
+    /// Returns the warning about the results of the last query or prepared statement execution, if
+    /// any, which should be sent to the client along with those results
+    pub fn last_query_warning(&self) -> Option<&str> {
+        self.last_query
+            .as_ref()
+            .map(|info| info.warning.as_str())
+            .filter(|warning| !warning.is_empty())
+    }
 }
 
 impl<DB, Handler> Drop for Backend<DB, Handler>
@@ -2295,6 +3372,24 @@ where
 {
     fn drop(&mut self) {
         metrics::decrement_gauge!(recorded::CONNECTED_CLIENTS, 1.0);
+        if let Some(telemetry_sender) = &self.telemetry_sender {
+            if let Err(e) = telemetry_sender.send_event_with_payload(
+                TelemetryEvent::ConnectionClosed,
+                TelemetryBuilder::new()
+                    .connection_duration_ms(self.state.connected_at.elapsed().as_millis() as u64)
+                    .query_count(self.state.query_count)
+                    .build(),
+            ) {
+                warn!(error = %e, "Failed to send connection closed metric");
+            }
+        }
+    }
+}
+
+/// Records the tags attached to a query on the current span, if there are any
+fn record_query_tags(tags: &QueryTags) {
+    if !tags.is_empty() {
+        tracing::Span::current().record("tags", tracing::field::display(tags));
     }
 }
 
@@ -2324,11 +3419,104 @@ fn log_query(
     }
 }
 
-fn readyset_version() -> ReadySetResult<noria_connector::QueryResult<'static>> {
-    Ok(noria_connector::QueryResult::MetaWithHeader(
-        <Vec<(String, String)>>::from(READYSET_VERSION.clone())
-            .into_iter()
-            .map(MetaVariable::from)
-            .collect(),
-    ))
+/// Record the number of rows affected by an `UPDATE` or `DELETE` statement executed against
+/// ReadySet in `event`, returning an error if it didn't affect any rows and `mode` is
+/// [`ZeroRowWriteMode::Error`].
+///
+/// Warnings for [`ZeroRowWriteMode::Warn`] are sent to the client along with the results, via
+/// [`query_warning`].
+fn check_rows_affected(
+    mode: ZeroRowWriteMode,
+    res: &noria_connector::QueryResult<'_>,
+    event: &mut QueryExecutionEvent,
+) -> ReadySetResult<()> {
+    let (statement, rows_affected) = match *res {
+        noria_connector::QueryResult::Update {
+            num_rows_updated, ..
+        } => ("UPDATE", num_rows_updated),
+        noria_connector::QueryResult::Delete { num_rows_deleted } => ("DELETE", num_rows_deleted),
+        _ => return Ok(()),
+    };
+    event.rows_affected = Some(rows_affected);
+
+    if rows_affected == 0 && mode == ZeroRowWriteMode::Error {
+        return Err(ReadySetError::NoRowsAffected {
+            statement: statement.to_owned(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_row_deletes() {
+        let no_match = noria_connector::QueryResult::Delete {
+            num_rows_deleted: 0,
+        };
+        let mut event = QueryExecutionEvent::new(EventType::Query);
+        check_rows_affected(ZeroRowWriteMode::default(), &no_match, &mut event).unwrap();
+        assert_eq!(event.rows_affected, Some(0));
+        assert_eq!(query_warning(&event, ZeroRowWriteMode::default()), "");
+        check_rows_affected(ZeroRowWriteMode::Warn, &no_match, &mut event).unwrap();
+        assert_eq!(
+            query_warning(&event, ZeroRowWriteMode::Warn),
+            "Statement did not affect any rows"
+        );
+        let err = check_rows_affected(ZeroRowWriteMode::Error, &no_match, &mut event).unwrap_err();
+        assert!(matches!(err, ReadySetError::NoRowsAffected { .. }));
+
+        let deleted = noria_connector::QueryResult::Delete {
+            num_rows_deleted: 1,
+        };
+        let mut event = QueryExecutionEvent::new(EventType::Query);
+        check_rows_affected(ZeroRowWriteMode::Error, &deleted, &mut event).unwrap();
+        assert_eq!(event.rows_affected, Some(1));
+        assert_eq!(query_warning(&event, ZeroRowWriteMode::Warn), "");
+    }
+
+    #[test]
+    fn zero_row_updates() {
+        let no_match = noria_connector::QueryResult::Update {
+            num_rows_updated: 0,
+            last_inserted_id: 0,
+        };
+        let mut event = QueryExecutionEvent::new(EventType::Execute);
+        check_rows_affected(ZeroRowWriteMode::default(), &no_match, &mut event).unwrap();
+        check_rows_affected(ZeroRowWriteMode::Error, &no_match, &mut event).unwrap_err();
+    }
+
+    #[test]
+    fn truncated_writes_warn() {
+        let mut event = QueryExecutionEvent::new(EventType::Execute);
+        event.rows_affected = Some(1);
+        assert_eq!(query_warning(&event, ZeroRowWriteMode::default()), "");
+
+        event.truncated_columns = vec!["a".to_owned(), "b".to_owned()];
+        assert_eq!(
+            query_warning(&event, ZeroRowWriteMode::default()),
+            "Data truncated for column(s) a, b"
+        );
+    }
+
+    #[test]
+    fn unparsed_writes() {
+        assert_eq!(
+            unparsed_write_type("insert into t values (1)"),
+            Some("INSERT")
+        );
+        assert_eq!(
+            unparsed_write_type("  REPLACE INTO t VALUES (1)"),
+            Some("REPLACE")
+        );
+        assert_eq!(
+            unparsed_write_type("/* tag='x' */ -- comment\nDELETE FROM t"),
+            Some("DELETE")
+        );
+        assert_eq!(unparsed_write_type("SELECT * FROM inserts"), None);
+        assert_eq!(unparsed_write_type("UPDATEx"), None);
+        assert_eq!(unparsed_write_type(""), None);
+    }
 }
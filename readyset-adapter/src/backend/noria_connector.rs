@@ -1,27 +1,35 @@
 use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
-use std::fmt;
+use std::future::Future;
 use std::ops::Bound;
 use std::sync::{atomic, Arc, RwLock};
+use std::time::Duration;
+use std::{fmt, mem};
 
-use dataflow_expression::{BinaryOperator as DfBinaryOperator, Expr as DfExpr};
+use dataflow_expression::{BinaryOperator as DfBinaryOperator, Expr as DfExpr, LowerContext};
+use futures::future::BoxFuture;
 use itertools::Itertools;
 use launchpad::redacted::Sensitive;
 use nom_sql::analysis::visit_mut::VisitorMut;
 use nom_sql::{
-    self, BinaryOperator, ColumnConstraint, DeleteStatement, Expr, InsertStatement, Literal,
-    Relation, SelectStatement, SqlIdentifier, SqlQuery, UnaryOperator, UpdateStatement,
+    self, BinaryOperator, Column, ColumnConstraint, CreateTableStatement, DeleteStatement, Expr,
+    InsertStatement, Literal, Relation, SelectStatement, SqlIdentifier, SqlQuery, UnaryOperator,
+    UpdateStatement,
 };
+use petgraph::graph::NodeIndex;
+use rand::Rng;
 use readyset::consistency::Timestamp;
-use readyset::internal::LocalNodeIndex;
+use readyset::internal::{IndexType, LocalNodeIndex};
 use readyset::recipe::changelist::{Change, ChangeList, IntoChanges};
+use readyset::replication::ReplicationOffsets;
 use readyset::results::{ResultIterator, Results};
 use readyset::{
-    ColumnSchema, KeyColumnIdx, KeyComparison, ReadQuery, ReaderAddress, ReadySetError,
-    ReadySetHandle, ReadySetResult, SchemaType, Table, TableOperation, View, ViewCreateRequest,
-    ViewPlaceholder, ViewQuery, ViewSchema,
+    ColumnSchema, KeyColumnIdx, KeyComparison, LookupResult, ReadQuery, ReadReplyStats,
+    ReaderAddress, ReadySetError, ReadySetHandle, ReadySetResult, SchemaType, Table, View,
+    ViewCreateRequest, ViewPlaceholder, ViewQuery, ViewSchema,
 };
+use readyset_client_metrics::recorded;
 use readyset_data::{DfType, DfValue, Dialect};
 use readyset_errors::ReadySetError::PreparedStatementMissing;
 use readyset_errors::{
@@ -29,7 +37,8 @@ use readyset_errors::{
 };
 use readyset_server::worker::readers::{CallResult, ReadRequestHandler};
 use readyset_sql_passes::anonymize::anonymize_literals;
-use tracing::{error, info, instrument, trace};
+use readyset_version::READYSET_VERSION;
+use tracing::{debug, error, info, instrument, trace, warn};
 use vec1::vec1;
 
 use crate::backend::SelectSchema;
@@ -51,6 +60,9 @@ pub(crate) struct PreparedSelectStatement {
     name: Relation,
     statement: Box<nom_sql::SelectStatement>,
     processed_query_params: ProcessedQueryParams,
+    /// Whether the statement might be a lookup on the primary key of a base table, which can be
+    /// served directly from the table's state (see [`NoriaConnector::base_table_point_lookup`])
+    point_lookup: bool,
 }
 
 impl fmt::Debug for PreparedStatement {
@@ -66,6 +78,101 @@ impl fmt::Debug for PreparedStatement {
     }
 }
 
+/// The statements prepared against ReadySet by a single [`NoriaConnector`], optionally capped at a
+/// maximum number of statements.
+///
+/// Once the cap is reached, preparing a new statement deallocates the least-recently-used one (the
+/// one which was least recently prepared or executed). Executing a deallocated statement returns
+/// [`ReadySetError::PreparedStatementMissing`], and the statement must be prepared again before it
+/// can be used.
+#[derive(Debug, Default)]
+struct PreparedStatementCache {
+    /// Map from statement id to the statement, along with the tick at which it was last used
+    statements: HashMap<StatementID, (PreparedStatement, u64)>,
+    /// Incremented every time a statement is used
+    tick: u64,
+    /// The maximum number of statements to keep, or `None` for no limit
+    max_size: Option<usize>,
+}
+
+impl PreparedStatementCache {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Register a new prepared statement with the given id, first evicting the least-recently-used
+    /// statements if the cache is full
+    fn insert(&mut self, id: StatementID, statement: PreparedStatement) {
+        if let Some(max_size) = self.max_size {
+            self.evict_to(max_size.saturating_sub(1));
+        }
+        let tick = self.next_tick();
+        if self.statements.insert(id, (statement, tick)).is_none() {
+            metrics::increment_gauge!(recorded::PREPARED_STATEMENT_CACHE_SIZE, 1.0);
+        }
+    }
+
+    /// Look up the prepared statement with the given id, marking it as the most recently used
+    /// statement
+    fn get(&mut self, id: StatementID) -> Option<&PreparedStatement> {
+        let tick = self.next_tick();
+        self.statements.get_mut(&id).map(|(statement, last_used)| {
+            *last_used = tick;
+            &*statement
+        })
+    }
+
+    /// Evict the least-recently-used statements until there are at most `size` statements left
+    fn evict_to(&mut self, size: usize) {
+        while self.statements.len() > size {
+            #[allow(clippy::unwrap_used)] // The cache can't be empty, since len() > size >= 0
+            let id = *self
+                .statements
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .unwrap()
+                .0;
+            trace!(id, "Evicting least-recently-used prepared statement");
+            self.statements.remove(&id);
+            metrics::decrement_gauge!(recorded::PREPARED_STATEMENT_CACHE_SIZE, 1.0);
+        }
+    }
+
+    /// Deallocate the prepared statement with the given id, if it exists
+    fn remove(&mut self, id: StatementID) {
+        if self.statements.remove(&id).is_some() {
+            metrics::decrement_gauge!(recorded::PREPARED_STATEMENT_CACHE_SIZE, 1.0);
+        }
+    }
+
+    fn contains(&self, id: StatementID) -> bool {
+        self.statements.contains_key(&id)
+    }
+
+    /// Set the maximum number of statements to keep, evicting statements immediately if there are
+    /// already more than that
+    fn set_max_size(&mut self, max_size: Option<usize>) {
+        self.max_size = max_size;
+        if let Some(max_size) = max_size {
+            self.evict_to(max_size);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.statements.len()
+    }
+}
+
+impl Drop for PreparedStatementCache {
+    fn drop(&mut self) {
+        metrics::decrement_gauge!(
+            recorded::PREPARED_STATEMENT_CACHE_SIZE,
+            self.statements.len() as f64
+        );
+    }
+}
+
 /// Wrapper around a NoriaBackendInner which may not have been successfully
 /// created. When this is the case, this wrapper allows returning an error
 /// from any call that requires NoriaBackendInner through an error
@@ -362,7 +469,11 @@ pub struct NoriaConnector {
     /// Global and thread-local cache of view endpoints and prepared statements.
     view_cache: ViewCache,
 
-    prepared_statement_cache: HashMap<StatementID, PreparedStatement>,
+    prepared_statement_cache: PreparedStatementCache,
+
+    /// The first auto-increment id generated by the most recent insert made through this
+    /// connector that generated one, or 0 if there hasn't been one yet
+    last_insert_id: u64,
 
     /// Set of views that have failed on previous requests. Separate from the backend
     /// to allow returning references to schemas from views all the way to mysql-srv,
@@ -385,6 +496,27 @@ pub struct NoriaConnector {
     /// supports a multi-element schema search path, the concept of "currently connected database"
     /// in MySQL can be thought of as a schema search path that only has one element.
     schema_search_path: Vec<SqlIdentifier>,
+
+    /// Whether values which are too long for the column they're being written to should be
+    /// rejected with an error (if `true`) or truncated (if `false`).
+    ///
+    /// Defaults to `true`, which matches PostgreSQL, but is set from the upstream database's
+    /// `sql_mode` for MySQL connections.
+    strict_mode: bool,
+
+    /// The columns whose values were truncated by writes since the last call to
+    /// [`NoriaConnector::take_truncated_columns`], because strict mode is disabled.
+    truncated_columns: Vec<String>,
+
+    /// The maximum amount of time a single read from a cache may take before it's aborted with
+    /// [`ReadySetError::StatementTimeout`], set with `SET statement_timeout` or
+    /// `SET max_execution_time`. Statements proxied upstream are bounded by the upstream database
+    /// itself, since the SET statement is proxied there too.
+    statement_timeout: Option<Duration>,
+
+    /// The user the client connected as, if it authenticated. Recorded as the creator of any
+    /// caches created through this connector.
+    user: Option<String>,
 }
 
 mod request_handler {
@@ -431,6 +563,33 @@ impl ReadBehavior {
     }
 }
 
+/// [`LowerContext`] for the expressions of generated columns, which can refer to any of the other
+/// columns in the table
+#[derive(Clone)]
+struct GeneratedColumnLowerContext<'a> {
+    schema: &'a CreateTableStatement,
+    dialect: Dialect,
+}
+
+impl<'a> LowerContext for GeneratedColumnLowerContext<'a> {
+    fn resolve_column(&self, col: Column) -> ReadySetResult<(usize, DfType)> {
+        let (idx, field) = self
+            .schema
+            .fields
+            .iter()
+            .find_position(|f| f.column.name == col.name)
+            .ok_or_else(|| ReadySetError::NoSuchColumn(col.name.to_string()))?;
+        Ok((
+            idx,
+            DfType::from_sql_type(&field.sql_type, self.dialect, |_| None)?,
+        ))
+    }
+
+    fn resolve_type(&self, _ty: Relation) -> Option<DfType> {
+        None
+    }
+}
+
 /// Used when we can determine that the params for 'OFFSET ?' or 'LIMIT ?' passed in
 /// with an execute statement will result in an empty resultset
 async fn short_circuit_empty_resultset(getter: &mut View) -> ReadySetResult<QueryResult<'_>> {
@@ -502,12 +661,17 @@ impl NoriaConnector {
             },
             auto_increments,
             view_cache: ViewCache::new(query_cache),
-            prepared_statement_cache: HashMap::new(),
+            prepared_statement_cache: PreparedStatementCache::default(),
+            last_insert_id: 0,
             failed_views: HashSet::new(),
             read_behavior,
             read_request_handler: request_handler::LocalReadHandler::new(read_request_handler),
             dialect,
             schema_search_path,
+            strict_mode: true,
+            truncated_columns: Vec::new(),
+            statement_timeout: None,
+            user: None,
         }
     }
 
@@ -526,6 +690,22 @@ impl NoriaConnector {
         Ok(QueryResult::Meta(vec![(label, graphviz).into()]))
     }
 
+    /// Returns a description of the plan ReadySet would use to cache the given query, without
+    /// creating a cache for it
+    pub(crate) async fn explain_query(
+        &mut self,
+        statement: &nom_sql::SelectStatement,
+    ) -> ReadySetResult<QueryResult<'static>> {
+        let query = ViewCreateRequest::new(statement.clone(), self.schema_search_path.clone());
+        let dialect = self.dialect;
+        let plan = noria_await!(
+            self.inner.get_mut()?,
+            self.inner.get_mut()?.noria.explain_query(query, dialect)
+        )?;
+
+        Ok(QueryResult::Meta(vec![("READYSET PLAN", plan).into()]))
+    }
+
     pub(crate) async fn verbose_views(
         &mut self,
         query_id: &Option<String>,
@@ -607,6 +787,18 @@ impl NoriaConnector {
         Ok(table_handle.node)
     }
 
+    /// Returns the index of the reader node for the view with the given name, so that queries
+    /// made through this connector can be correlated with the per-node metrics reported by the
+    /// controller.
+    pub async fn view_node_index(&mut self, name: &str) -> ReadySetResult<NodeIndex> {
+        let view = self
+            .inner
+            .get_mut()?
+            .get_noria_view(&name.into(), false)
+            .await?;
+        Ok(*view.node())
+    }
+
     pub async fn handle_insert(
         &mut self,
         q: &nom_sql::InsertStatement,
@@ -719,7 +911,7 @@ impl NoriaConnector {
     ) -> ReadySetResult<QueryResult<'_>> {
         let prep: PreparedStatement = self
             .prepared_statement_cache
-            .get(&q_id)
+            .get(q_id)
             .ok_or(PreparedStatementMissing { statement_id: q_id })?
             .clone();
         trace!("delegate");
@@ -738,8 +930,9 @@ impl NoriaConnector {
                     &SqlQuery::Insert(q.clone()),
                     schema,
                     self.dialect,
-                )
-                .unwrap()
+                    self.strict_mode,
+                    &mut self.truncated_columns,
+                )?
                 .unwrap();
                 self.do_insert(q, vec![coerced_params]).await
             }
@@ -783,13 +976,16 @@ impl NoriaConnector {
                 unsupported!("DELETE only supports WHERE-clauses on primary keys")
             }
             Some(flattened) => {
-                let count = flattened.len() as u64;
+                let mut count = 0;
                 trace!("delete::execute");
                 for key in flattened {
-                    if let Err(e) = mutator.delete(key).await {
-                        error!(error = %e, "failed");
-                        return Err(e);
-                    };
+                    match mutator.delete(key).await {
+                        Ok(n) => count += n,
+                        Err(e) => {
+                            error!(error = %e, "failed");
+                            return Err(e);
+                        }
+                    }
                 }
                 trace!("delete::done");
                 Ok(QueryResult::Delete {
@@ -852,7 +1048,7 @@ impl NoriaConnector {
     ) -> ReadySetResult<QueryResult<'_>> {
         let prep: PreparedStatement = self
             .prepared_statement_cache
-            .get(&q_id)
+            .get(q_id)
             .ok_or(PreparedStatementMissing { statement_id: q_id })?
             .clone();
 
@@ -909,7 +1105,7 @@ impl NoriaConnector {
     ) -> ReadySetResult<QueryResult<'_>> {
         let prep: PreparedStatement = self
             .prepared_statement_cache
-            .get(&q_id)
+            .get(q_id)
             .ok_or(PreparedStatementMissing { statement_id: q_id })?
             .clone();
 
@@ -952,15 +1148,109 @@ impl NoriaConnector {
         ))
     }
 
+    /// Returns the build information of this adapter, along with the current schema version of the
+    /// leader's recipe if the leader can be reached.
+    pub(crate) async fn readyset_version(&mut self) -> ReadySetResult<QueryResult<'static>> {
+        let mut version = <Vec<(String, String)>>::from(READYSET_VERSION.clone());
+        match self.schema_version().await {
+            Ok(schema_version) => {
+                version.push(("schema version".to_string(), schema_version.to_string()))
+            }
+            Err(error) => warn!(%error, "Could not retrieve schema version from the leader"),
+        }
+
+        Ok(QueryResult::MetaWithHeader(
+            version.into_iter().map(MetaVariable::from).collect(),
+        ))
+    }
+
+    /// Returns the current schema version of the leader's recipe
+    async fn schema_version(&mut self) -> ReadySetResult<usize> {
+        noria_await!(
+            self.inner.get_mut()?,
+            self.inner.get_mut()?.noria.schema_version()
+        )
+    }
+
+    /// Returns the current replication offsets of the schema and all tables in ReadySet
+    pub(crate) async fn replication_offsets(&mut self) -> ReadySetResult<ReplicationOffsets> {
+        noria_await!(
+            self.inner.get_mut()?,
+            self.inner.get_mut()?.noria.replication_offsets()
+        )
+    }
+
     /// Set the schema search path
     pub fn set_schema_search_path(&mut self, search_path: Vec<SqlIdentifier>) {
         self.schema_search_path = search_path;
     }
 
+    /// Set whether values too long for the column they're being written to should be rejected
+    /// (`true`) or truncated (`false`)
+    pub fn set_strict_mode(&mut self, strict: bool) {
+        self.strict_mode = strict;
+    }
+
+    /// Returns the columns whose values were truncated to fit by writes since the last time this
+    /// was called
+    pub fn take_truncated_columns(&mut self) -> Vec<String> {
+        mem::take(&mut self.truncated_columns)
+    }
+
+    /// Set the maximum amount of time a single read from a cache may take, or `None` for no limit
+    pub fn set_statement_timeout(&mut self, timeout: Option<Duration>) {
+        self.statement_timeout = timeout;
+    }
+
+    /// Set the user that caches created through this connector are recorded as being created by
+    pub fn set_user(&mut self, user: String) {
+        self.user = Some(user);
+    }
+
+    /// Record the user this connector is connected as, if any, as the creator of the caches
+    /// created by `changelist`
+    fn with_user(&self, changelist: ChangeList) -> ChangeList {
+        match &self.user {
+            Some(user) => changelist.with_user(user.clone()),
+            None => changelist,
+        }
+    }
+
     /// Returns a reference to the currently configured schema search path
     pub fn schema_search_path(&self) -> &[SqlIdentifier] {
         self.schema_search_path.as_ref()
     }
+
+    /// Set the maximum number of statements that can be prepared against ReadySet at once, or
+    /// `None` for no limit. Once the limit is reached, preparing a new statement deallocates the
+    /// least-recently-used prepared statement.
+    pub fn set_max_prepared_statements(&mut self, max: Option<usize>) {
+        self.prepared_statement_cache.set_max_size(max)
+    }
+
+    /// Returns the number of statements currently prepared against ReadySet
+    pub fn num_prepared_statements(&self) -> usize {
+        self.prepared_statement_cache.len()
+    }
+
+    /// Returns true if the statement with the given id is currently prepared against ReadySet,
+    /// and hasn't been deallocated to stay under the [prepared statement
+    /// limit](Self::set_max_prepared_statements)
+    pub fn has_prepared_statement(&self, statement_id: u32) -> bool {
+        self.prepared_statement_cache.contains(statement_id)
+    }
+
+    /// Deallocate the statement with the given id prepared against ReadySet, if any
+    pub fn remove_prepared_statement(&mut self, statement_id: u32) {
+        self.prepared_statement_cache.remove(statement_id)
+    }
+
+    /// Returns the first auto-increment id generated by the most recent insert made through this
+    /// connector that generated one, or 0 if there hasn't been one yet. This is the value of
+    /// MySQL's `LAST_INSERT_ID()` function for the session.
+    pub fn last_insert_id(&self) -> u64 {
+        self.last_insert_id
+    }
 }
 
 impl NoriaConnector {
@@ -972,17 +1262,21 @@ impl NoriaConnector {
         statement: &nom_sql::SelectStatement,
         override_schema_search_path: Option<Vec<SqlIdentifier>>,
         always: bool,
+        index_type: Option<nom_sql::IndexType>,
+        max_mem: Option<u64>,
     ) -> ReadySetResult<()> {
         let name = name.cloned().unwrap_or_else(|| {
             utils::generate_query_name(statement, self.schema_search_path()).into()
         });
         let schema_search_path =
             override_schema_search_path.unwrap_or_else(|| self.schema_search_path.clone());
-        let changelist = ChangeList::from_change(
-            Change::create_cache(name.clone(), statement.clone(), always),
-            self.dialect,
-        )
-        .with_schema_search_path(schema_search_path.clone());
+        let changelist = self.with_user(
+            ChangeList::from_change(
+                Change::create_cache(name.clone(), statement.clone(), always, index_type, max_mem),
+                self.dialect,
+            )
+            .with_schema_search_path(schema_search_path.clone()),
+        );
 
         noria_await!(
             self.inner.get_mut()?,
@@ -1020,11 +1314,13 @@ impl NoriaConnector {
                         info!(query = %Sensitive(q), name = %qname, "adding ad-hoc query");
                     }
 
-                    let changelist = ChangeList::from_change(
-                        Change::create_cache(qname.clone(), q.clone(), false),
-                        self.dialect,
-                    )
-                    .with_schema_search_path(self.schema_search_path.clone());
+                    let changelist = self.with_user(
+                        ChangeList::from_change(
+                            Change::create_cache(qname.clone(), q.clone(), false, None, None),
+                            self.dialect,
+                        )
+                        .with_schema_search_path(self.schema_search_path.clone()),
+                    );
 
                     if let Err(e) = noria_await!(
                         self.inner.get_mut()?,
@@ -1159,6 +1455,24 @@ impl NoriaConnector {
                 }
             }
 
+            // handle generated columns
+            trace!("insert::generated columns");
+            let lower_context = GeneratedColumnLowerContext {
+                schema,
+                dialect: self.dialect,
+            };
+            let generated_columns = schema
+                .fields
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, c)| c.generated_expr().map(|expr| (idx, c, expr)))
+                .map(|(idx, c, expr)| {
+                    let expr = DfExpr::lower(expr.clone(), self.dialect, lower_context.clone())?;
+                    let ty = DfType::from_sql_type(&c.sql_type, self.dialect, |_| None)?;
+                    Ok((idx, &c.column, expr, ty))
+                })
+                .collect::<ReadySetResult<Vec<_>>>()?;
+
             trace!("insert::construct ops");
 
             for (ri, row) in data.iter().enumerate() {
@@ -1225,6 +1539,16 @@ impl NoriaConnector {
                         .coerce_to(&target_type, &DfType::Unknown)?; // No from_ty, we're inserting literals
                     buf[ri][idx] = value;
                 }
+
+                // Compute the values of any generated columns that weren't given explicitly. This
+                // happens after all the other values in the row have been filled in, and in column
+                // order, since generated columns can refer to other (earlier) generated columns
+                for (idx, column, expr, ty) in &generated_columns {
+                    if !columns_specified.contains(column) {
+                        let value = expr.eval(&buf[ri])?.coerce_to(ty, expr.ty())?;
+                        buf[ri][*idx] = value;
+                    }
+                }
             }
             Ok(())
         })?;
@@ -1254,12 +1578,19 @@ impl NoriaConnector {
             r
         } else {
             trace!("insert::simple");
-            let buf: Vec<_> = buf.into_iter().map(TableOperation::Insert).collect();
-            let r = putter.perform_all(buf).await;
+            let r = putter.insert_many_unique(buf).await;
             trace!("insert::simple::complete");
             r
         };
-        result?;
+        result.map_err(|e| match e {
+            // Surface duplicate keys directly, so they can be reported to the client as such
+            ReadySetError::TableError { source, .. } if source.is_duplicate_key() => *source,
+            e => e,
+        })?;
+        if let Some(id) = first_inserted_id {
+            // Like MySQL, inserts that don't generate an id leave the last insert id alone
+            self.last_insert_id = id as u64;
+        }
         Ok(QueryResult::Insert {
             num_rows_inserted: data.len() as u64,
             first_inserted_id: first_inserted_id.unwrap_or(0) as u64,
@@ -1283,8 +1614,14 @@ impl NoriaConnector {
                 // no update on views
                 unsupported!();
             };
-            let coerced_params =
-                utils::coerce_params(params, &SqlQuery::Update(q.clone()), schema, self.dialect)?;
+            let coerced_params = utils::coerce_params(
+                params,
+                &SqlQuery::Update(q.clone()),
+                schema,
+                self.dialect,
+                self.strict_mode,
+                &mut self.truncated_columns,
+            )?;
             utils::extract_update(
                 q,
                 coerced_params.map(|p| p.into_iter()),
@@ -1294,12 +1631,11 @@ impl NoriaConnector {
         };
 
         trace!("update::update");
-        mutator.update(key, updates).await?;
+        let num_rows_updated = mutator.update(key, updates).await?;
         trace!("update::complete");
-        // TODO: return meaningful fields for (num_rows_updated, last_inserted_id) rather than
-        // hardcoded (1,0)
+        // TODO: return a meaningful last_inserted_id rather than a hardcoded 0
         Ok(QueryResult::Update {
-            num_rows_updated: 1,
+            num_rows_updated,
             last_inserted_id: 0,
         })
     }
@@ -1321,19 +1657,21 @@ impl NoriaConnector {
                 // no delete on views
                 unsupported!();
             };
-            let coerced_params =
-                utils::coerce_params(params, &SqlQuery::Delete(q.clone()), schema, self.dialect)?;
+            let coerced_params = utils::coerce_params(
+                params,
+                &SqlQuery::Delete(q.clone()),
+                schema,
+                self.dialect,
+                self.strict_mode,
+                &mut self.truncated_columns,
+            )?;
             utils::extract_delete(q, coerced_params.map(|p| p.into_iter()), schema)?
         };
 
         trace!("delete::delete");
-        mutator.delete(key).await?;
+        let num_rows_deleted = mutator.delete(key).await?;
         trace!("delete::complete");
-        // TODO: return meaningful fields for (num_rows_deleted, last_inserted_id) rather than
-        // hardcoded (1,0)
-        Ok(QueryResult::Delete {
-            num_rows_deleted: 1,
-        })
+        Ok(QueryResult::Delete { num_rows_deleted })
     }
 
     #[instrument(level = "info", skip(self, statement))]
@@ -1383,11 +1721,16 @@ impl NoriaConnector {
             .schema()
             .ok_or_else(|| internal_err!("no schema for view '{}'", qname))?;
 
+        // Name each parameter after the column it's compared against in the query (rather than the
+        // name of the column projected by the view, which may be an alias), and keep the base
+        // column so the client can see its constraints, including nullability
         let mut params: Vec<_> = getter_schema
             .to_cols(&client_param_columns, SchemaType::ProjectedSchema)?
             .into_iter()
-            .map(|cs| {
+            .zip(&client_param_columns)
+            .map(|(cs, param_column)| {
                 let mut cs = cs.clone();
+                cs.column.name = param_column.name.clone();
                 cs.column.table = Some(qname.clone());
                 cs
             })
@@ -1396,6 +1739,7 @@ impl NoriaConnector {
         trace!(id = statement_id, "select::registered");
         let ps = PreparedSelectStatement {
             name: qname,
+            point_lookup: utils::base_table_point_lookup(&statement).is_some(),
             statement: Box::new(statement),
             processed_query_params,
         };
@@ -1410,6 +1754,79 @@ impl NoriaConnector {
         })
     }
 
+    /// If `statement` is a lookup on the primary key of a single base table, of the form `SELECT *
+    /// FROM t WHERE t.pk = <literal>` or `SELECT * FROM t WHERE t.pk = ?`, read the matching rows
+    /// directly from the base table's state, without going through a view for the query.
+    ///
+    /// Returns `Ok(None)` if the query isn't of that form, in which case it should be executed
+    /// against a view as usual.
+    async fn base_table_point_lookup(
+        &mut self,
+        statement: &SelectStatement,
+        params: &[DfValue],
+    ) -> ReadySetResult<Option<QueryResult<'static>>> {
+        let (table, column, value) = match utils::base_table_point_lookup(statement) {
+            Some(lookup) => lookup,
+            None => return Ok(None),
+        };
+
+        let dialect = self.dialect;
+        let mutator = match self.inner.get_mut()?.get_noria_table(table).await {
+            Ok(t) => t,
+            // The query might be selecting from a view rather than a table
+            Err(e) if e.caused_by_table_not_found() || e.caused_by_invalid_node_type() => {
+                return Ok(None)
+            }
+            Err(e) => return Err(e),
+        };
+        let table_schema = match mutator.schema() {
+            Some(schema) => schema.clone(),
+            None => return Ok(None),
+        };
+        match utils::get_primary_key(&table_schema).as_slice() {
+            [(_, pk)] if pk.name == column.name => {}
+            _ => return Ok(None),
+        }
+        let key = match value {
+            Literal::Placeholder(_) => match params {
+                [param] => param.clone(),
+                _ => return Ok(None),
+            },
+            value => DfValue::try_from(value)?,
+        };
+        let key_type = match table_schema
+            .fields
+            .iter()
+            .find(|cs| cs.column.name == column.name)
+        {
+            Some(cs) => DfType::from_sql_type(&cs.sql_type, dialect, |_| None)?,
+            None => return Ok(None),
+        };
+        let key = key.coerce_to(&key_type, &DfType::Unknown)?;
+
+        trace!(%table, "select::lookup base table");
+        let rows = mutator.lookup(vec![key]).await?;
+
+        let schema = table_schema
+            .fields
+            .iter()
+            .map(|cs| ColumnSchema::from_base(cs.clone(), table.clone(), dialect))
+            .collect::<ReadySetResult<Vec<_>>>()?;
+        let columns = table_schema
+            .fields
+            .into_iter()
+            .map(|cs| cs.column.name)
+            .collect::<Vec<_>>();
+        Ok(Some(QueryResult::from_owned(
+            SelectSchema {
+                use_bogo: false,
+                schema: Cow::Owned(schema),
+                columns: Cow::Owned(columns),
+            },
+            vec![Results::new(rows)],
+        )))
+    }
+
     #[instrument(level = "debug", skip(self, event))]
     pub(crate) async fn execute_select(
         &mut self,
@@ -1419,12 +1836,25 @@ impl NoriaConnector {
     ) -> ReadySetResult<QueryResult<'_>> {
         let (qname, statement, processed_query_params, params) = match ctx {
             ExecuteSelectContext::Prepared { q_id, params } => {
+                let point_lookup = match self.prepared_statement_cache.get(q_id) {
+                    Some(PreparedStatement::Select(ps)) if ps.point_lookup => {
+                        Some(ps.statement.clone())
+                    }
+                    _ => None,
+                };
+                if let Some(statement) = point_lookup {
+                    if let Some(res) = self.base_table_point_lookup(&statement, params).await? {
+                        return Ok(res);
+                    }
+                }
+
                 let PreparedSelectStatement {
                     name,
                     statement,
                     processed_query_params,
+                    ..
                 } = {
-                    match self.prepared_statement_cache.get(&q_id) {
+                    match self.prepared_statement_cache.get(q_id) {
                         Some(PreparedStatement::Select(ps)) => ps,
                         Some(_) => internal!(),
                         None => return Err(PreparedStatementMissing { statement_id: q_id }),
@@ -1443,6 +1873,9 @@ impl NoriaConnector {
                 create_if_missing,
             } => {
                 verify_no_placeholders(&mut statement, query)?;
+                if let Some(res) = self.base_table_point_lookup(&statement, &[]).await? {
+                    return Ok(res);
+                }
                 let processed_query_params =
                     rewrite::process_query(&mut statement, self.server_supports_pagination())?;
                 let name = self.get_view(&statement, false, create_if_missing).await?;
@@ -1472,6 +1905,7 @@ impl NoriaConnector {
             self.read_request_handler.as_mut(),
             event,
             self.dialect,
+            self.statement_timeout,
         )
         .await;
 
@@ -1532,6 +1966,17 @@ fn verify_no_placeholders(statement: &mut SelectStatement, query: &str) -> Ready
     }
 }
 
+/// Record the type and width of the index used to perform a lookup against a view with the given
+/// `index_type` and `key_map` in `event`
+fn record_lookup_index(
+    event: &mut readyset_client_metrics::QueryExecutionEvent,
+    index_type: Option<IndexType>,
+    key_map: &[(ViewPlaceholder, KeyColumnIdx)],
+) {
+    event.index_type = index_type;
+    event.key_width = Some(key_map.len());
+}
+
 /// Build a [`ViewQuery`] for performing a lookup of the given `q` with the given `raw_keys`,
 /// provided `getter_schema` and `key_map` from the [`View`] itself.
 #[allow(clippy::too_many_arguments)]
@@ -1775,6 +2220,112 @@ fn build_view_query(
     })
 }
 
+/// The maximum number of times a view read which fails with a transient error (see
+/// [`ReadySetError::is_transient`]) is retried before the error is returned
+const MAX_TRANSIENT_READ_RETRIES: u32 = 3;
+
+/// The delay before the first retry of a view read which failed with a transient error. Each
+/// subsequent retry doubles the delay.
+const TRANSIENT_READ_RETRY_BASE_DELAY: Duration = Duration::from_millis(10);
+
+/// Run `attempt` on `state`, retrying it for as long as it fails with a transient error (see
+/// [`ReadySetError::is_transient`]), up to [`MAX_TRANSIENT_READ_RETRIES`] times. Before each retry,
+/// waits for an exponentially increasing, jittered delay.
+async fn retry_transient_errors<S, T, F>(state: &mut S, mut attempt: F) -> ReadySetResult<T>
+where
+    F: for<'a> FnMut(&'a mut S) -> BoxFuture<'a, ReadySetResult<T>>,
+{
+    let mut retries = 0;
+    loop {
+        match attempt(state).await {
+            Err(error) if error.is_transient() && retries < MAX_TRANSIENT_READ_RETRIES => {
+                debug!(%error, retries, "Retrying view read after transient error");
+                let max_delay = TRANSIENT_READ_RETRY_BASE_DELAY * 2u32.pow(retries);
+                // Jitter between half and all of the delay, so that many clients reading from the
+                // same view don't all retry at once
+                let delay = rand::thread_rng().gen_range(max_delay / 2..=max_delay);
+                retries += 1;
+                tokio::time::sleep(delay).await;
+            }
+            res => return res,
+        }
+    }
+}
+
+/// Run `read`, aborting it with [`ReadySetError::StatementTimeout`] if it doesn't finish within
+/// `timeout`. Only reads from caches are run with a timeout, since dropping them partway through
+/// has no effects beyond the read itself.
+async fn with_statement_timeout<F, T>(timeout: Option<Duration>, read: F) -> ReadySetResult<T>
+where
+    F: Future<Output = ReadySetResult<T>>,
+{
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, read)
+            .await
+            .unwrap_or(Err(ReadySetError::StatementTimeout)),
+        None => read.await,
+    }
+}
+
+/// Perform a single lookup of the given [`ViewQuery`] on the supplied [`View`], returning the raw
+/// results and the stats for the read.
+async fn lookup(
+    getter: &mut View,
+    vq: ViewQuery,
+    read_request_handler: Option<&mut ReadRequestHandler>,
+) -> ReadySetResult<(ResultIterator, Option<ReadReplyStats>)> {
+    #[cfg(feature = "failure_injection")]
+    if let Some(delay) = fail::eval(readyset::failpoints::SLOW_CACHE_READS, |delay| {
+        delay
+            .and_then(|millis| millis.parse().ok())
+            .map(Duration::from_millis)
+    })
+    .flatten()
+    {
+        tokio::time::sleep(delay).await;
+    }
+
+    if let Some(rh) = read_request_handler {
+        let request = readyset::Tagged::from(ReadQuery::Normal {
+            target: ReaderAddress {
+                node: *getter.node(),
+                name: getter.name().clone(),
+                shard: 0,
+            },
+            query: vq.clone(),
+        });
+
+        // Query the local reader if it is a read query, otherwise default to the traditional
+        // View API.
+        let tag = request.tag;
+        if let ReadQuery::Normal { target, query } = request.v {
+            // Issue a normal read query returning the raw unserialized results.
+            let result = match rh.handle_normal_read_query(tag, target, query, true) {
+                CallResult::Immediate(result) => result?,
+                CallResult::Async(chan) => chan.await?,
+            };
+
+            let (mut results, stats) =
+                match result.v.into_normal().ok_or_else(|| {
+                    internal_err!("Unexpected response type from reader service")
+                })?? {
+                    LookupResult::Results(results, stats) => (results, stats),
+                    LookupResult::NonBlockingMiss => return Err(ReadySetError::ReaderMissingKey),
+                };
+            let data = results
+                .pop()
+                .ok_or_else(|| internal_err!("Expected a single result set for local reader"))?
+                .into_unserialized()
+                .expect("Requested raw result");
+            return Ok((data, Some(stats)));
+        }
+    }
+
+    let data = getter.raw_lookup(vq).await?;
+    let stats = data.total_stats();
+    Ok((data, stats))
+}
+
 /// Run the supplied [`SelectStatement`] on the supplied [`View`]
 /// Assumption: the [`View`] was created for that specific [`SelectStatement`]
 #[allow(clippy::needless_lifetimes)] // clippy erroneously thinks the timelife can be elided
@@ -1789,6 +2340,7 @@ async fn do_read<'a>(
     read_request_handler: Option<&'a mut ReadRequestHandler>,
     event: &mut readyset_client_metrics::QueryExecutionEvent,
     dialect: Dialect,
+    statement_timeout: Option<Duration>,
 ) -> ReadySetResult<QueryResult<'a>> {
     let (limit, _) = processed_query_params.limit_offset_params(params)?;
     if limit == Some(0) {
@@ -1809,45 +2361,33 @@ async fn do_read<'a>(
     )?;
 
     event.num_keys = Some(vq.key_comparisons.len() as _);
-
-    let data = if let Some(rh) = read_request_handler {
-        let request = readyset::Tagged::from(ReadQuery::Normal {
-            target: ReaderAddress {
-                node: *getter.node(),
-                name: getter.name().clone(),
-                shard: 0,
+    record_lookup_index(event, getter.index_type(), getter.key_map());
+
+    let (data, stats) = with_statement_timeout(
+        statement_timeout,
+        retry_transient_errors(
+            &mut (&mut *getter, read_request_handler),
+            |(getter, read_request_handler)| {
+                Box::pin(lookup(
+                    getter,
+                    vq.clone(),
+                    read_request_handler.as_deref_mut(),
+                ))
             },
-            query: vq.clone(),
-        });
-
-        // Query the local reader if it is a read query, otherwise default to the traditional
-        // View API.
-        let tag = request.tag;
-        if let ReadQuery::Normal { target, query } = request.v {
-            // Issue a normal read query returning the raw unserialized results.
-            let result = match rh.handle_normal_read_query(tag, target, query, true) {
-                CallResult::Immediate(result) => result?,
-                CallResult::Async(chan) => chan.await?,
-            };
-
-            result
-                .v
-                .into_normal()
-                .ok_or_else(|| internal_err!("Unexpected response type from reader service"))??
-                .into_results()
-                .ok_or(ReadySetError::ReaderMissingKey)?
-                .pop()
-                .ok_or_else(|| internal_err!("Expected a single result set for local reader"))?
-                .into_unserialized()
-                .expect("Requested raw result")
-        } else {
-            getter.raw_lookup(vq).await?
-        }
-    } else {
-        getter.raw_lookup(vq).await?
-    };
-
-    event.cache_misses = data.total_stats().map(|s| s.cache_misses);
+        ),
+    )
+    .await?;
+
+    event.cache_misses = stats.as_ref().map(|s| s.cache_misses);
+    event.rows_examined = stats.as_ref().map(|s| s.rows_examined);
+    event.unavailable_shards = stats.map(|s| s.unavailable_shards);
+    if let Some(unavailable_shards) = event.unavailable_shards.filter(|&n| n > 0) {
+        warn!(
+            view = %getter.name(),
+            unavailable_shards,
+            "Returning partial results from a view with unavailable shards"
+        );
+    }
 
     trace!("select::complete");
 
@@ -1926,6 +2466,7 @@ mod tests {
         use lazy_static::lazy_static;
         use nom_sql::{parse_query, Column, Dialect, SelectStatement};
         use readyset::ColumnBase;
+        use readyset_client_metrics::{EventType, QueryExecutionEvent};
 
         use super::*;
 
@@ -1942,6 +2483,7 @@ mod tests {
                             table: "t".into(),
                             column: "x".into(),
                             constraints: vec![],
+                            outer_join_nullable: false,
                         }),
                     },
                     ColumnSchema {
@@ -1954,6 +2496,7 @@ mod tests {
                             table: "t".into(),
                             column: "y".into(),
                             constraints: vec![],
+                            outer_join_nullable: false,
                         }),
                     }
                 ],
@@ -1968,6 +2511,7 @@ mod tests {
                             table: "t".into(),
                             column: "x".into(),
                             constraints: vec![],
+                            outer_join_nullable: false,
                         }),
                     },
                     ColumnSchema {
@@ -1980,6 +2524,7 @@ mod tests {
                             table: "t".into(),
                             column: "y".into(),
                             constraints: vec![],
+                            outer_join_nullable: false,
                         }),
                     }
                 ],
@@ -2053,6 +2598,20 @@ mod tests {
             );
         }
 
+        #[test]
+        fn lookup_records_view_index() {
+            let key_map = [
+                (ViewPlaceholder::OneToOne(1), 0),
+                (ViewPlaceholder::OneToOne(2), 1),
+            ];
+
+            // Equality lookups against a view with a range index still hit the range index
+            let mut event = QueryExecutionEvent::new(EventType::Execute);
+            record_lookup_index(&mut event, Some(IndexType::BTreeMap), &key_map);
+            assert_eq!(event.index_type, Some(IndexType::BTreeMap));
+            assert_eq!(event.key_width, Some(2));
+        }
+
         #[test]
         fn ilike_and_equality() {
             let query = make_build_query(
@@ -2254,4 +2813,134 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    mod prepared_statement_cache {
+        use nom_sql::{parse_query, Dialect};
+
+        use super::*;
+
+        fn statement(query: &str) -> PreparedStatement {
+            match parse_query(Dialect::MySQL, query).unwrap() {
+                SqlQuery::Delete(stmt) => PreparedStatement::Delete(stmt),
+                q => panic!("Expected DELETE statement, got {q:?}"),
+            }
+        }
+
+        #[test]
+        fn evicts_least_recently_used() {
+            let mut cache = PreparedStatementCache::default();
+            cache.set_max_size(Some(2));
+            cache.insert(1, statement("DELETE FROM t WHERE a = ?"));
+            cache.insert(2, statement("DELETE FROM t WHERE b = ?"));
+            // Using 1 makes 2 the least recently used
+            assert!(cache.get(1).is_some());
+            cache.insert(3, statement("DELETE FROM t WHERE c = ?"));
+
+            assert_eq!(cache.len(), 2);
+            assert!(cache.get(2).is_none());
+            assert!(cache.get(1).is_some());
+            assert!(cache.get(3).is_some());
+
+            // Re-preparing the evicted statement evicts the next least recently used one
+            cache.insert(2, statement("DELETE FROM t WHERE b = ?"));
+            assert!(cache.get(2).is_some());
+            assert!(cache.get(1).is_none());
+        }
+
+        #[test]
+        fn remove() {
+            let mut cache = PreparedStatementCache::default();
+            cache.insert(1, statement("DELETE FROM t WHERE a = ?"));
+            assert!(cache.contains(1));
+            cache.remove(1);
+            assert!(!cache.contains(1));
+            assert_eq!(cache.len(), 0);
+            // Removing a statement that doesn't exist does nothing
+            cache.remove(1);
+        }
+
+        #[test]
+        fn lowering_max_size_evicts() {
+            let mut cache = PreparedStatementCache::default();
+            for id in 0..5 {
+                cache.insert(id, statement("DELETE FROM t WHERE a = ?"));
+            }
+            assert_eq!(cache.len(), 5);
+            cache.set_max_size(Some(1));
+            assert_eq!(cache.len(), 1);
+            assert!(cache.get(4).is_some());
+        }
+    }
+
+    mod retry_transient_errors {
+        use super::*;
+
+        /// Run [`retry_transient_errors`] with an attempt which fails with `error` the given number
+        /// of times before succeeding, returning the result and the number of attempts made
+        async fn run(failures: usize, error: ReadySetError) -> (ReadySetResult<DfValue>, usize) {
+            let mut attempts = 0;
+            let res = retry_transient_errors(&mut attempts, |attempts| {
+                *attempts += 1;
+                let res = if *attempts > failures {
+                    Ok(DfValue::from(1))
+                } else {
+                    Err(error.clone())
+                };
+                Box::pin(async move { res })
+            })
+            .await;
+            (res, attempts)
+        }
+
+        #[tokio::test]
+        async fn recovers_from_transient_error() {
+            let (res, attempts) = run(1, ReadySetError::ViewNotYetAvailable).await;
+            assert_eq!(res.unwrap(), DfValue::from(1));
+            assert_eq!(attempts, 2);
+        }
+
+        #[tokio::test]
+        async fn gives_up_after_max_retries() {
+            let (res, attempts) = run(usize::MAX, ReadySetError::ServiceUnavailable).await;
+            res.unwrap_err();
+            assert_eq!(attempts, MAX_TRANSIENT_READ_RETRIES as usize + 1);
+        }
+
+        #[tokio::test]
+        async fn does_not_retry_logical_errors() {
+            let (res, attempts) = run(1, ReadySetError::ViewNotFound("v".into())).await;
+            assert!(res.unwrap_err().caused_by_view_not_found());
+            assert_eq!(attempts, 1);
+        }
+    }
+
+    mod statement_timeout {
+        use super::*;
+
+        #[tokio::test]
+        async fn aborts_slow_reads() {
+            let res = with_statement_timeout(Some(Duration::from_millis(50)), async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                ReadySetResult::Ok(1)
+            })
+            .await;
+            assert!(res.unwrap_err().is_statement_timeout());
+
+            let res = with_statement_timeout(Some(Duration::from_secs(5)), async {
+                ReadySetResult::Ok(1)
+            })
+            .await;
+            assert_eq!(res.unwrap(), 1);
+        }
+
+        #[tokio::test]
+        async fn no_timeout() {
+            let res = with_statement_timeout(None, async {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                ReadySetResult::Ok(1)
+            })
+            .await;
+            assert_eq!(res.unwrap(), 1);
+        }
+    }
 }
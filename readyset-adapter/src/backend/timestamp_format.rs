@@ -0,0 +1,383 @@
+//! Per-connection control over how timestamps in results read from ReadySet are rendered.
+//!
+//! Clients can change the format for their connection with:
+//!
+//! ```sql
+//! SET readyset_timestamp_format = 'epoch_ms' | 'rfc3339' | 'native'
+//! ```
+//!
+//! The setting is handled entirely by the adapter, and is never proxied to the upstream database,
+//! so it only applies to results returned from ReadySet - results of queries proxied upstream are
+//! always returned as-is.
+//!
+//! The `rfc3339` format renders all values as text, so it also renders `REAL`, `DOUBLE` and
+//! `DECIMAL` values as their exact decimal representation, rather than leaving them to be rounded
+//! by the client.
+//!
+//! The setting is only supported for MySQL, whose clients decode each result set using the column
+//! types sent along with it. Postgres clients decode the results of a prepared statement using the
+//! column types described when it was prepared, which we can't change after the fact.
+
+use std::fmt;
+use std::str::FromStr;
+
+use nom_sql::{
+    Expr, Literal, PostgresParameterValue, PostgresParameterValueInner, SetPostgresParameterValue,
+    SetStatement,
+};
+use readyset::results::Results;
+use readyset::ColumnSchema;
+use readyset_data::{DfType, DfValue};
+use readyset_errors::{invalid_err, ReadySetError, ReadySetResult};
+
+use super::noria_connector::QueryResult;
+use super::SelectSchema;
+
+/// How timestamps in results read from ReadySet are rendered for a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampFormat {
+    /// Return timestamps using the native timestamp types of the SQL protocol
+    #[default]
+    Native,
+    /// Return timestamps as a `BIGINT` number of milliseconds since the unix epoch
+    EpochMs,
+    /// Return timestamps as text in RFC 3339 format, and decimal numbers as text in their exact
+    /// decimal representation
+    Rfc3339,
+}
+
+impl TimestampFormat {
+    /// The name of the session variable used to set the timestamp format
+    pub const VARIABLE: &'static str = "readyset_timestamp_format";
+
+    /// If `set` sets the timestamp format (and nothing else), returns the value it's being set to
+    pub fn from_set_statement(set: &SetStatement) -> Option<ReadySetResult<Self>> {
+        let value = match set {
+            SetStatement::Variable(set) => match set.variables.as_slice() {
+                [(var, value)] if var.name.eq_ignore_ascii_case(Self::VARIABLE) => match value {
+                    Expr::Literal(Literal::String(s)) => Some(s.as_str()),
+                    _ => None,
+                },
+                _ => return None,
+            },
+            SetStatement::PostgresParameter(set)
+                if set.name.eq_ignore_ascii_case(Self::VARIABLE) =>
+            {
+                match &set.value {
+                    SetPostgresParameterValue::Value(PostgresParameterValue::Single(
+                        PostgresParameterValueInner::Literal(Literal::String(s)),
+                    )) => Some(s.as_str()),
+                    SetPostgresParameterValue::Value(PostgresParameterValue::Single(
+                        PostgresParameterValueInner::Identifier(s),
+                    )) => Some(s.as_str()),
+                    _ => None,
+                }
+            }
+            _ => return None,
+        };
+
+        Some(value.map_or_else(
+            || {
+                Err(invalid_err!(
+                    "{} must be set to a string literal",
+                    Self::VARIABLE
+                ))
+            },
+            str::parse,
+        ))
+    }
+
+    fn is_timestamp_type(ty: &DfType) -> bool {
+        matches!(
+            ty,
+            DfType::DateTime { .. } | DfType::Timestamp { .. } | DfType::TimestampTz { .. }
+        )
+    }
+
+    fn is_decimal_type(ty: &DfType) -> bool {
+        matches!(ty, DfType::Float | DfType::Double | DfType::Numeric { .. })
+    }
+
+    /// Returns true if values of the given type are rendered differently in this format
+    fn rewrites(self, ty: &DfType) -> bool {
+        match self {
+            Self::Native => false,
+            Self::EpochMs => Self::is_timestamp_type(ty),
+            Self::Rfc3339 => Self::is_timestamp_type(ty) || Self::is_decimal_type(ty),
+        }
+    }
+
+    /// Returns the type that values of the given type are rendered as in this format
+    fn column_type(self, ty: &DfType) -> DfType {
+        match self {
+            _ if !self.rewrites(ty) => ty.clone(),
+            Self::EpochMs => DfType::BigInt,
+            _ => DfType::DEFAULT_TEXT,
+        }
+    }
+
+    /// Render a single value of a column rewritten by this format
+    fn format_value(self, value: &DfValue) -> DfValue {
+        match (self, value) {
+            (Self::EpochMs, DfValue::TimestampTz(ts)) => {
+                DfValue::Int(ts.to_chrono().timestamp_millis())
+            }
+            (Self::Rfc3339, DfValue::TimestampTz(ts)) => ts.to_chrono().to_rfc3339().into(),
+            (Self::Rfc3339, DfValue::Float(_) | DfValue::Double(_) | DfValue::Numeric(_)) => {
+                value.to_string().into()
+            }
+            _ => value.clone(),
+        }
+    }
+
+    /// Rewrite the columns of the given result set from ReadySet to be rendered in this format.
+    /// Results which aren't a set of rows, or which don't have any columns this format rewrites,
+    /// are returned unchanged.
+    ///
+    /// Rewriting a result set collects all its rows, so this is a no-op for
+    /// [`TimestampFormat::Native`] to avoid that cost for connections that never change the
+    /// setting.
+    pub fn apply(self, result: QueryResult<'_>) -> QueryResult<'_> {
+        let (rows, schema) = match result {
+            QueryResult::Select { rows, schema }
+                if schema
+                    .schema
+                    .iter()
+                    .any(|cs| self.rewrites(&cs.column_type)) =>
+            {
+                (rows, schema)
+            }
+            result => return result,
+        };
+
+        let rewritten_columns = schema
+            .schema
+            .iter()
+            .enumerate()
+            .filter(|(_, cs)| self.rewrites(&cs.column_type))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        let rows = rows
+            .into_iter()
+            .map(|mut row| {
+                for &i in &rewritten_columns {
+                    if let Some(value) = row.get_mut(i) {
+                        *value = self.format_value(value);
+                    }
+                }
+                row
+            })
+            .collect();
+        let schema = SelectSchema {
+            schema: schema
+                .schema
+                .iter()
+                .map(|cs| ColumnSchema {
+                    column_type: self.column_type(&cs.column_type),
+                    ..cs.clone()
+                })
+                .collect(),
+            ..schema
+        };
+
+        QueryResult::from_owned(schema, vec![Results::new(rows)])
+    }
+}
+
+impl FromStr for TimestampFormat {
+    type Err = ReadySetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "native" => Ok(Self::Native),
+            "epoch_ms" => Ok(Self::EpochMs),
+            "rfc3339" => Ok(Self::Rfc3339),
+            _ => Err(invalid_err!(
+                "Unknown value for {}: '{s}' (expected one of 'native', 'epoch_ms', or \
+                 'rfc3339')",
+                Self::VARIABLE
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TimestampFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Native => write!(f, "native"),
+            Self::EpochMs => write!(f, "epoch_ms"),
+            Self::Rfc3339 => write!(f, "rfc3339"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use chrono::NaiveDate;
+    use nom_sql::{parse_query, Dialect, SqlQuery};
+    use rust_decimal::Decimal;
+
+    use super::*;
+
+    fn parse_set(query: &str) -> SetStatement {
+        parse_set_with_dialect(Dialect::MySQL, query)
+    }
+
+    fn parse_set_with_dialect(dialect: Dialect, query: &str) -> SetStatement {
+        match parse_query(dialect, query).unwrap() {
+            SqlQuery::Set(set) => set,
+            q => panic!("Expected SET statement, got {q:?}"),
+        }
+    }
+
+    fn timestamp_result() -> QueryResult<'static> {
+        let column = |name: &str, column_type| ColumnSchema {
+            column: nom_sql::Column {
+                name: name.into(),
+                table: Some("t".into()),
+            },
+            column_type,
+            base: None,
+        };
+        QueryResult::from_owned(
+            SelectSchema {
+                use_bogo: false,
+                schema: Cow::Owned(vec![
+                    column("id", DfType::Int),
+                    column(
+                        "created_at",
+                        DfType::Timestamp {
+                            subsecond_digits: 0,
+                        },
+                    ),
+                    column("price", DfType::Numeric { prec: 10, scale: 2 }),
+                    column("score", DfType::Double),
+                ]),
+                columns: Cow::Owned(vec![
+                    "id".into(),
+                    "created_at".into(),
+                    "price".into(),
+                    "score".into(),
+                ]),
+            },
+            vec![Results::new(vec![vec![
+                DfValue::Int(1),
+                DfValue::TimestampTz(NaiveDate::from_ymd(2020, 1, 23).and_hms(17, 8, 24).into()),
+                Decimal::new(1999, 2).into(),
+                DfValue::Double(0.1),
+            ]])],
+        )
+    }
+
+    fn rows_and_types(result: QueryResult<'_>) -> (Vec<Vec<DfValue>>, Vec<DfType>) {
+        match result {
+            QueryResult::Select { rows, schema } => (
+                rows.into_vec(),
+                schema
+                    .schema
+                    .iter()
+                    .map(|cs| cs.column_type.clone())
+                    .collect(),
+            ),
+            r => panic!("Expected select result, got {r:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_set_statement() {
+        assert_eq!(
+            TimestampFormat::from_set_statement(&parse_set(
+                "SET readyset_timestamp_format = 'epoch_ms'"
+            ))
+            .unwrap()
+            .unwrap(),
+            TimestampFormat::EpochMs
+        );
+        assert_eq!(
+            TimestampFormat::from_set_statement(&parse_set(
+                "SET READYSET_TIMESTAMP_FORMAT = 'RFC3339'"
+            ))
+            .unwrap()
+            .unwrap(),
+            TimestampFormat::Rfc3339
+        );
+        assert!(TimestampFormat::from_set_statement(&parse_set(
+            "SET readyset_timestamp_format = 'yesterday'"
+        ))
+        .unwrap()
+        .is_err());
+        assert!(
+            TimestampFormat::from_set_statement(&parse_set("SET time_zone = '+00:00'")).is_none()
+        );
+        assert_eq!(
+            TimestampFormat::from_set_statement(&parse_set_with_dialect(
+                Dialect::PostgreSQL,
+                "SET readyset_timestamp_format TO 'epoch_ms'"
+            ))
+            .unwrap()
+            .unwrap(),
+            TimestampFormat::EpochMs
+        );
+    }
+
+    #[test]
+    fn native_leaves_results_alone() {
+        let (rows, types) = rows_and_types(TimestampFormat::Native.apply(timestamp_result()));
+        assert_eq!(
+            types[1],
+            DfType::Timestamp {
+                subsecond_digits: 0
+            }
+        );
+        assert!(matches!(rows[0][1], DfValue::TimestampTz(_)));
+        assert_eq!(rows[0][2], Decimal::new(1999, 2).into());
+    }
+
+    #[test]
+    fn epoch_ms() {
+        let (rows, types) = rows_and_types(TimestampFormat::EpochMs.apply(timestamp_result()));
+        assert_eq!(
+            types,
+            vec![
+                DfType::Int,
+                DfType::BigInt,
+                DfType::Numeric { prec: 10, scale: 2 },
+                DfType::Double
+            ]
+        );
+        assert_eq!(
+            rows,
+            vec![vec![
+                DfValue::Int(1),
+                DfValue::Int(1579799304000),
+                Decimal::new(1999, 2).into(),
+                DfValue::Double(0.1)
+            ]]
+        );
+    }
+
+    #[test]
+    fn rfc3339() {
+        let (rows, types) = rows_and_types(TimestampFormat::Rfc3339.apply(timestamp_result()));
+        assert_eq!(
+            types,
+            vec![
+                DfType::Int,
+                DfType::DEFAULT_TEXT,
+                DfType::DEFAULT_TEXT,
+                DfType::DEFAULT_TEXT
+            ]
+        );
+        assert_eq!(
+            rows,
+            vec![vec![
+                DfValue::Int(1),
+                DfValue::from("2020-01-23T17:08:24+00:00"),
+                DfValue::from("19.99"),
+                DfValue::from("0.1")
+            ]]
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! Tracking whether the ReadySet controller is reachable, so that the adapter can keep serving
+//! queries from the upstream database while it isn't.
+//!
+//! When the adapter is started with `--fallback-on-controller-loss`, a [`ControllerHealth`] shared
+//! between all connections is kept up to date by [`ControllerHealth::monitor_loop`]. Once the
+//! controller has been unreachable for longer than the configured grace period, the controller is
+//! considered lost, and backends proxy all queries to the upstream database. The monitor keeps
+//! checking the controller, and as soon as it's reachable again queries are served from ReadySet
+//! once more.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use readyset::ReadySetHandle;
+use tokio::select;
+use tracing::{debug, info, warn};
+
+#[derive(Debug)]
+struct Inner {
+    /// Whether the controller has been unreachable for longer than `grace_period`
+    lost: AtomicBool,
+    /// The time of the first failed check in the current run of failed checks, if the most recent
+    /// check failed
+    unreachable_since: Mutex<Option<Instant>>,
+    /// How long the controller must be continuously unreachable before it's considered lost
+    grace_period: Duration,
+}
+
+/// A view of whether the ReadySet controller is reachable, shared between all the connections to
+/// an adapter.
+///
+/// Cloning a [`ControllerHealth`] returns a handle to the same state.
+#[derive(Debug, Clone)]
+pub struct ControllerHealth {
+    inner: Arc<Inner>,
+}
+
+impl ControllerHealth {
+    /// Create a new [`ControllerHealth`], which considers the controller lost once it's been
+    /// unreachable for longer than `grace_period`. The controller starts out reachable.
+    pub fn new(grace_period: Duration) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                lost: AtomicBool::new(false),
+                unreachable_since: Mutex::new(None),
+                grace_period,
+            }),
+        }
+    }
+
+    /// Returns true if the controller has been unreachable for longer than the grace period, and
+    /// hasn't been reachable since.
+    pub fn is_lost(&self) -> bool {
+        self.inner.lost.load(Ordering::Acquire)
+    }
+
+    /// Record the result of checking whether the controller is reachable
+    pub fn record_check(&self, reachable: bool) {
+        self.record_check_at(reachable, Instant::now())
+    }
+
+    fn record_check_at(&self, reachable: bool, now: Instant) {
+        let mut unreachable_since = self.inner.unreachable_since.lock();
+        if reachable {
+            *unreachable_since = None;
+            if self.inner.lost.swap(false, Ordering::AcqRel) {
+                info!("ReadySet controller is reachable again; resuming serving queries from ReadySet");
+            }
+            return;
+        }
+
+        let since = *unreachable_since.get_or_insert(now);
+        if now.duration_since(since) >= self.inner.grace_period
+            && !self.inner.lost.swap(true, Ordering::AcqRel)
+        {
+            warn!(
+                grace_period = ?self.inner.grace_period,
+                "Lost connection to the ReadySet controller; proxying all queries upstream"
+            );
+        }
+    }
+
+    /// Check whether the controller is reachable every `interval`, until a shutdown signal is
+    /// received on `shutdown_recv`.
+    ///
+    /// The controller is considered unreachable if it fails to respond within `interval`, returns
+    /// an error, or reports that it isn't ready.
+    pub async fn monitor_loop(
+        self,
+        mut controller: ReadySetHandle,
+        interval: Duration,
+        mut shutdown_recv: tokio::sync::broadcast::Receiver<()>,
+    ) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            select! {
+                _ = ticker.tick() => {
+                    let reachable = match tokio::time::timeout(
+                        interval,
+                        controller.leader_ready(),
+                    )
+                    .await
+                    {
+                        Ok(Ok(ready)) => ready,
+                        Ok(Err(error)) => {
+                            debug!(%error, "Failed to check ReadySet controller health");
+                            false
+                        }
+                        Err(_) => {
+                            debug!("Timed out checking ReadySet controller health");
+                            false
+                        }
+                    };
+                    self.record_check(reachable);
+                }
+                _ = shutdown_recv.recv() => {
+                    info!("Controller health monitor shutting down after shut down signal received");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lost_after_grace_period() {
+        let health = ControllerHealth::new(Duration::from_secs(10));
+        let start = Instant::now();
+        assert!(!health.is_lost());
+
+        health.record_check_at(false, start);
+        health.record_check_at(false, start + Duration::from_secs(5));
+        assert!(!health.is_lost());
+
+        health.record_check_at(false, start + Duration::from_secs(10));
+        assert!(health.is_lost());
+        assert!(health.clone().is_lost());
+
+        health.record_check_at(true, start + Duration::from_secs(11));
+        assert!(!health.is_lost());
+    }
+
+    #[test]
+    fn intermittent_failures_are_not_loss() {
+        let health = ControllerHealth::new(Duration::from_secs(10));
+        let start = Instant::now();
+
+        health.record_check_at(false, start);
+        health.record_check_at(true, start + Duration::from_secs(8));
+        health.record_check_at(false, start + Duration::from_secs(12));
+        assert!(!health.is_lost());
+    }
+}
@@ -0,0 +1,94 @@
+//! Per-deployment feature flags, resolved from the controller.
+//!
+//! Feature flags are set for a whole deployment with [`ReadySetHandle::set_feature_flag`], and
+//! stored by the controller in the authority. Each adapter loads the current value of all the
+//! flags when it starts up, and then periodically refreshes them with
+//! [`FeatureFlags::refresh_loop`], so flipping a flag changes the behavior of every adapter in the
+//! deployment without needing to restart them.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use readyset::ReadySetHandle;
+use readyset_errors::ReadySetResult;
+use tokio::select;
+use tracing::{debug, info, warn};
+
+/// When enabled, reject all write statements as if every adapter in the deployment had been
+/// started with `--read-only`
+pub const READ_ONLY: &str = "read_only";
+
+/// A view of the feature flags set for the deployment, shared between all the connections to an
+/// adapter.
+///
+/// Cloning a [`FeatureFlags`] returns a handle to the same set of flags.
+#[derive(Debug, Clone, Default)]
+pub struct FeatureFlags {
+    flags: Arc<RwLock<BTreeMap<String, bool>>>,
+}
+
+impl FeatureFlags {
+    /// Create a new set of feature flags, all of which are disabled until they're loaded with
+    /// [`FeatureFlags::refresh`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if the feature flag with the given name is enabled. Flags which have never
+    /// been set for the deployment are disabled.
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.flags.read().get(name).copied().unwrap_or(false)
+    }
+
+    /// Load the current value of all the feature flags for the deployment from the controller
+    pub async fn refresh(&self, controller: &mut ReadySetHandle) -> ReadySetResult<()> {
+        let flags = controller.feature_flags().await?;
+        debug!(?flags, "Refreshed feature flags");
+        *self.flags.write() = flags;
+        Ok(())
+    }
+
+    /// Refresh the feature flags from the controller every `interval`, until a shutdown signal is
+    /// received on `shutdown_recv`.
+    ///
+    /// Failing to load the flags is logged, and leaves the previously loaded values in place.
+    pub async fn refresh_loop(
+        self,
+        mut controller: ReadySetHandle,
+        interval: Duration,
+        mut shutdown_recv: tokio::sync::broadcast::Receiver<()>,
+    ) {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            select! {
+                _ = interval.tick() => {
+                    if let Err(error) = self.refresh(&mut controller).await {
+                        warn!(%error, "Failed to refresh feature flags");
+                    }
+                }
+                _ = shutdown_recv.recv() => {
+                    info!("Feature flag refresh shutting down after shut down signal received");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unset_flags_are_disabled() {
+        let flags = FeatureFlags::new();
+        assert!(!flags.is_enabled(READ_ONLY));
+
+        flags.flags.write().insert(READ_ONLY.to_owned(), true);
+        assert!(flags.is_enabled(READ_ONLY));
+        assert!(flags.clone().is_enabled(READ_ONLY));
+        assert!(!flags.is_enabled("some_other_flag"));
+    }
+}
@@ -10,7 +10,7 @@ use futures::TryFutureExt;
 use health_reporter::{HealthReporter as AdapterHealthReporter, State};
 use hyper::header::CONTENT_TYPE;
 use hyper::service::make_service_fn;
-use hyper::{self, Body, Method, Request, Response};
+use hyper::{self, Body, Method, Request, Response, StatusCode};
 use metrics_exporter_prometheus::PrometheusHandle;
 use readyset::query::DeniedQuery;
 use readyset_client_metrics::recorded;
@@ -21,7 +21,9 @@ use tokio::sync::mpsc::Sender;
 use tokio_stream::wrappers::TcpListenerStream;
 use tower::Service;
 
+use crate::controller_health::ControllerHealth;
 use crate::query_status_cache::QueryStatusCache;
+use crate::upstream_health::UpstreamHealth;
 
 /// Routes requests from an HTTP server to expose metrics data from the adapter.
 /// To see the supported http requests and their respective routing, see
@@ -36,6 +38,12 @@ pub struct NoriaAdapterHttpRouter {
     pub valve: Valve,
     /// Used to retrieve the current health of the adapter.
     pub health_reporter: AdapterHealthReporter,
+    /// Used to determine whether the ReadySet controller is reachable, as part of the adapter's
+    /// readiness check.
+    pub controller_health: ControllerHealth,
+    /// Used to determine whether the upstream database is reachable, as part of the adapter's
+    /// readiness check. `None` if the adapter isn't running with an upstream database.
+    pub upstream_health: Option<UpstreamHealth>,
     /// Used to communicate externally that a failpoint request has been received and successfully
     /// handled.
     /// Most commonly used to block on further startup action if --wait-for-failpoint is supplied
@@ -113,6 +121,56 @@ impl Service<Request<Body>> for NoriaAdapterHttpRouter {
     ///
     ///   `curl -X GET <adapter>:<adapter-port>/health`
     ///
+    /// ## Liveness Check
+    ///
+    /// Check whether the adapter process is alive. Returns 200 as long as the HTTP router is able
+    /// to respond, regardless of the state of any of the adapter's dependencies, so that
+    /// orchestrators can tell a crashed adapter apart from one that's waiting on its
+    /// dependencies.
+    ///
+    /// * **URL**
+    ///
+    ///   `/livez`
+    ///
+    /// * **Method:**
+    ///
+    ///   `GET`
+    ///
+    /// * **Success Response:**
+    ///
+    ///     * **Code:** 200 <br />
+    ///
+    /// * **Sample Call:**
+    ///
+    ///   `curl -X GET <adapter>:<adapter-port>/livez`
+    ///
+    /// ## Readiness Check
+    ///
+    /// Check whether the adapter is ready to serve queries: the adapter has finished starting up
+    /// and isn't shutting down, the ReadySet controller is reachable (or has been unreachable for
+    /// less than `--controller-loss-grace-period`), and the upstream database (if any) is
+    /// reachable.
+    ///
+    /// * **URL**
+    ///
+    ///   `/readyz`
+    ///
+    /// * **Method:**
+    ///
+    ///   `GET`
+    ///
+    /// * **Success Response:**
+    ///
+    ///     * **Code:** 200 <br />
+    ///
+    /// * **Error Response:**
+    ///
+    ///     * **Code:** 503 Service Unavailable <br /> **Content:** `"Adapter is not ready: ..."`
+    ///
+    /// * **Sample Call:**
+    ///
+    ///   `curl -X GET <adapter>:<adapter-port>/readyz`
+    ///
     /// ## Allow List
     ///
     /// List of SQL queries that will be handled by ReadySet as opposed to being passed through to
@@ -169,6 +227,36 @@ impl Service<Request<Body>> for NoriaAdapterHttpRouter {
     ///
     ///   `curl -X GET <adapter>:<adapter-port>/deny-list`
     ///
+    /// ## Plan Cache
+    ///
+    /// Every query seen by the adapter, anonymized, along with its id (which is also the name of
+    /// the query's view in ReadySet, unless it was cached with an explicit name) and whether it's
+    /// currently `cached`, `pending`, or `unsupported`. Useful for diagnosing why a particular
+    /// query isn't being served from ReadySet.
+    ///
+    /// * **URL**
+    ///
+    ///   `/plan_cache`
+    ///
+    /// * **Method:**
+    ///
+    ///   `GET`
+    ///
+    /// * **Success Response:**
+    ///
+    ///   Plan cache as a JSON Array.
+    ///
+    ///     * **Code:** 200 <br /> **Content:** `[ ... ]`
+    ///
+    /// * **Error Response:**
+    ///
+    ///     * **Code:** 500 Internal Server Error <br /> **Content:** `"plan cache failed to be
+    ///       converted into a json string"`
+    ///
+    /// * **Sample Call:**
+    ///
+    ///   `curl -X GET <adapter>:<adapter-port>/plan_cache`
+    ///
     /// ## Prometheus
     ///
     /// Endpoint for Prometheus metric API calls.
@@ -285,6 +373,23 @@ impl Service<Request<Body>> for NoriaAdapterHttpRouter {
                     Ok(res.unwrap())
                 })
             }
+            (&Method::GET, "/plan_cache") => {
+                let query_cache = self.query_cache;
+                Box::pin(async move {
+                    let plan_cache = query_cache.plan_cache();
+                    let res = match serde_json::to_string(&plan_cache) {
+                        Ok(json) => res
+                            .header(CONTENT_TYPE, "application/json")
+                            .body(hyper::Body::from(json)),
+                        Err(_) => res.status(500).header(CONTENT_TYPE, "text/plain").body(
+                            hyper::Body::from(
+                                "plan cache failed to be converted into a json string".to_string(),
+                            ),
+                        ),
+                    };
+                    Ok(res.unwrap())
+                })
+            }
             (&Method::GET, "/health") => {
                 let state = self.health_reporter.health().state;
                 Box::pin(async move {
@@ -303,6 +408,40 @@ impl Service<Request<Body>> for NoriaAdapterHttpRouter {
                     Ok(res.unwrap())
                 })
             }
+            (&Method::GET, "/livez") => Box::pin(async move {
+                let res = res
+                    .status(200)
+                    .header(CONTENT_TYPE, "text/plain")
+                    .body(hyper::Body::from("Adapter is alive"));
+
+                Ok(res.unwrap())
+            }),
+            (&Method::GET, "/readyz") => {
+                let state = self.health_reporter.state();
+                let controller_lost = self.controller_health.is_lost();
+                let upstream_unreachable = self
+                    .upstream_health
+                    .as_ref()
+                    .map_or(false, |upstream_health| !upstream_health.is_reachable());
+                Box::pin(async move {
+                    let res = res.header(CONTENT_TYPE, "text/plain");
+                    let res = if state != State::Healthy {
+                        res.status(StatusCode::SERVICE_UNAVAILABLE).body(
+                            format!("Adapter is not ready: adapter is in {} state", state).into(),
+                        )
+                    } else if controller_lost {
+                        res.status(StatusCode::SERVICE_UNAVAILABLE)
+                            .body("Adapter is not ready: ReadySet controller is unreachable".into())
+                    } else if upstream_unreachable {
+                        res.status(StatusCode::SERVICE_UNAVAILABLE)
+                            .body("Adapter is not ready: upstream database is unreachable".into())
+                    } else {
+                        res.status(200).body("Adapter is ready".into())
+                    };
+
+                    Ok(res.unwrap())
+                })
+            }
             (&Method::GET, "/prometheus") => {
                 let body = self.prometheus_handle.as_ref().map(|x| x.render());
                 let res = res.header(CONTENT_TYPE, "text/plain");
@@ -325,3 +464,83 @@ impl Service<Request<Body>> for NoriaAdapterHttpRouter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn router(controller_health: ControllerHealth) -> NoriaAdapterHttpRouter {
+        let (_, valve) = Valve::new();
+        let mut health_reporter = AdapterHealthReporter::new();
+        health_reporter.set_state(State::Healthy);
+        NoriaAdapterHttpRouter {
+            listen_addr: "127.0.0.1:0".parse().unwrap(),
+            query_cache: Box::leak(Box::new(QueryStatusCache::new())),
+            valve,
+            health_reporter,
+            controller_health,
+            upstream_health: Some(UpstreamHealth::new()),
+            failpoint_channel: None,
+            prometheus_handle: None,
+        }
+    }
+
+    async fn get_status(router: &mut NoriaAdapterHttpRouter, path: &str) -> StatusCode {
+        let req = Request::get(path).body(Body::empty()).unwrap();
+        router.call(req).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn ready_when_controller_reachable() {
+        let mut router = router(ControllerHealth::new(Duration::ZERO));
+        assert_eq!(get_status(&mut router, "/livez").await, StatusCode::OK);
+        assert_eq!(get_status(&mut router, "/readyz").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn live_but_not_ready_during_controller_outage() {
+        let controller_health = ControllerHealth::new(Duration::ZERO);
+        let mut router = router(controller_health.clone());
+
+        controller_health.record_check(false);
+        assert!(controller_health.is_lost());
+        assert_eq!(get_status(&mut router, "/livez").await, StatusCode::OK);
+        assert_eq!(
+            get_status(&mut router, "/readyz").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        controller_health.record_check(true);
+        assert_eq!(get_status(&mut router, "/readyz").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn live_but_not_ready_during_upstream_outage() {
+        let upstream_health = UpstreamHealth::new();
+        let mut router = router(ControllerHealth::new(Duration::ZERO));
+        router.upstream_health = Some(upstream_health.clone());
+
+        upstream_health.record_check(false);
+        assert_eq!(get_status(&mut router, "/livez").await, StatusCode::OK);
+        assert_eq!(
+            get_status(&mut router, "/readyz").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        upstream_health.record_check(true);
+        assert_eq!(get_status(&mut router, "/readyz").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn not_ready_before_startup_finishes() {
+        let mut router = router(ControllerHealth::new(Duration::ZERO));
+        router.health_reporter = AdapterHealthReporter::new();
+        assert_eq!(get_status(&mut router, "/livez").await, StatusCode::OK);
+        assert_eq!(
+            get_status(&mut router, "/readyz").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+}
@@ -9,18 +9,27 @@
 #![feature(generic_associated_types)]
 #![deny(unreachable_pub)]
 
+pub mod audit_log;
+pub mod auth;
 pub mod backend;
+pub mod controller_health;
 pub mod fallback_cache;
+pub mod feature_flags;
 pub mod http_router;
 pub mod migration_handler;
+pub mod preload;
 pub mod proxied_queries_reporter;
+pub mod query_cancellation;
 mod query_handler;
 pub mod query_status_cache;
+pub mod query_validation;
 pub mod rewrite;
 pub mod upstream_database;
+pub mod upstream_health;
 mod utils;
 pub mod views_synchronizer;
 
+pub use crate::audit_log::AuditLog;
 pub use crate::backend::{Backend, BackendBuilder};
 pub use crate::query_handler::{QueryHandler, SetBehavior};
 pub use crate::upstream_database::{
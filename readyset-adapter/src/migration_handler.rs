@@ -16,12 +16,12 @@ use readyset::recipe::changelist::{Change, ChangeList};
 use readyset::{ReadySetHandle, ReadySetResult, ViewCreateRequest};
 use readyset_client_metrics::recorded;
 use tokio::select;
-use tracing::{error, info, instrument, warn};
+use tracing::{error, info, instrument, warn, Level};
 
 use crate::backend::{noria_connector, NoriaConnector};
 use crate::query_status_cache::QueryStatusCache;
 use crate::upstream_database::{IsFatalError, NoriaCompare};
-use crate::{utils, UpstreamDatabase};
+use crate::{query_validation, utils, UpstreamDatabase};
 
 pub struct MigrationHandler<DB> {
     /// Connection used to issue prepare requests to ReadySet.
@@ -44,6 +44,9 @@ pub struct MigrationHandler<DB> {
     /// migration.
     validate_queries: bool,
 
+    /// The level at which mismatches found by query validation are logged.
+    validation_mismatch_log_level: Level,
+
     /// The minimum interval between subsequent polls to the query
     /// status cache. In practice it may be longer if the queries
     /// that require processing take longer than `min_poll_interval`.
@@ -74,6 +77,7 @@ where
         query_status_cache: &'static QueryStatusCache,
         dialect: Dialect,
         validate_queries: bool,
+        validation_mismatch_log_level: Level,
         min_poll_interval: std::time::Duration,
         max_retry: std::time::Duration,
         shutdown_recv: tokio::sync::broadcast::Receiver<()>,
@@ -85,6 +89,7 @@ where
             dialect,
             query_status_cache,
             validate_queries,
+            validation_mismatch_log_level,
             min_poll_interval,
             max_retry,
             shutdown_recv,
@@ -206,10 +211,10 @@ where
                             .meta
                             .compare(schema, params)
                         {
-                            warn!(
-                                error = %e,
-                                query = %Sensitive(&view_request.statement),
-                                "Query compare failed"
+                            query_validation::report_schema_mismatch(
+                                &view_request.statement,
+                                &e,
+                                self.validation_mismatch_log_level,
                             );
                             // TODO(justin): Fix setting migration state to unsupported with
                             // validate_queries.
@@ -278,7 +283,7 @@ where
         let qname =
             utils::generate_query_name(&view_request.statement, &view_request.schema_search_path);
         let changelist = ChangeList::from_change(
-            Change::create_cache(qname, view_request.statement.clone(), false),
+            Change::create_cache(qname, view_request.statement.clone(), false, None, None),
             self.dialect,
         )
         .with_schema_search_path(view_request.schema_search_path.clone());
@@ -0,0 +1,164 @@
+//! Creating caches for a list of queries when the adapter starts up.
+//!
+//! When the adapter is started with `--preload-queries`, the queries listed in the given file are
+//! migrated before the adapter starts accepting connections, so that caches for them already exist
+//! by the time clients connect.
+
+use launchpad::redacted::Sensitive;
+use nom_sql::{CacheInner, CreateCacheStatement, Dialect, SqlQuery};
+use readyset::query::MigrationState;
+use readyset::ViewCreateRequest;
+use readyset_errors::{unsupported, ReadySetResult};
+use tracing::{info, warn};
+
+use crate::backend::NoriaConnector;
+use crate::query_status_cache::QueryStatusCache;
+use crate::rewrite;
+
+/// Create a cache for each of the semicolon-separated statements in `queries`, returning the
+/// number of caches that were successfully created.
+///
+/// Each statement can be either a `CREATE CACHE` statement or a bare `SELECT` statement, which is
+/// cached under a generated name. Statements are parsed and migrated one at a time, so a statement
+/// that fails to parse or migrate is logged and skipped, rather than preventing the remaining
+/// statements from being cached.
+pub async fn preload_queries(
+    noria: &mut NoriaConnector,
+    query_status_cache: &QueryStatusCache,
+    dialect: Dialect,
+    queries: &str,
+) -> usize {
+    let mut created = 0;
+    for statement in split_statements(dialect, queries) {
+        let query = match nom_sql::parse_query(dialect, statement) {
+            Ok(query) => query,
+            Err(error) => {
+                warn!(query = %Sensitive(&statement), %error, "Failed to parse preloaded query");
+                continue;
+            }
+        };
+        match preload_query(noria, query_status_cache, query).await {
+            Ok(()) => created += 1,
+            Err(error) => warn!(query = %Sensitive(&statement), %error, "Failed to preload query"),
+        }
+    }
+
+    info!(created, "Finished preloading queries");
+    created
+}
+
+/// Splits `queries` into its semicolon-separated statements, ignoring any semicolons within quotes
+/// or comments. Comments before and after each statement are left out, and statements that are
+/// empty or consist only of comments are skipped.
+fn split_statements(dialect: Dialect, queries: &str) -> Vec<&str> {
+    let mut statements = vec![];
+    // The byte range of the current statement, from the start of its first token to the end of its
+    // last one
+    let mut current: Option<(usize, usize)> = None;
+    let mut chars = queries.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                // Quotes are escaped by doubling them, which just looks like two adjacent quoted
+                // strings here, or by a backslash in MySQL
+                while let Some((_, next)) = chars.next() {
+                    if next == '\\' && dialect == Dialect::MySQL {
+                        chars.next();
+                    } else if next == c {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.peek().map(|&(_, c)| c) == Some('-') => {
+                chars.find(|&(_, c)| c == '\n');
+                continue;
+            }
+            '#' if dialect == Dialect::MySQL => {
+                chars.find(|&(_, c)| c == '\n');
+                continue;
+            }
+            '/' if chars.peek().map(|&(_, c)| c) == Some('*') => {
+                chars.next();
+                while let Some((_, c)) = chars.next() {
+                    if c == '*' && chars.next_if(|&(_, c)| c == '/').is_some() {
+                        break;
+                    }
+                }
+                continue;
+            }
+            ';' => {
+                if let Some((start, end)) = current.take() {
+                    statements.push(&queries[start..end]);
+                }
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            _ => {}
+        }
+
+        let end = chars.peek().map_or(queries.len(), |&(j, _)| j);
+        current = Some((current.map_or(i, |(start, _)| start), end));
+    }
+    if let Some((start, end)) = current {
+        statements.push(&queries[start..end]);
+    }
+    statements
+}
+
+async fn preload_query(
+    noria: &mut NoriaConnector,
+    query_status_cache: &QueryStatusCache,
+    query: SqlQuery,
+) -> ReadySetResult<()> {
+    let (name, mut stmt, always, index_type, max_mem) = match query {
+        SqlQuery::Select(stmt) => (None, stmt, false, None, None),
+        SqlQuery::CreateCache(CreateCacheStatement {
+            name,
+            inner: CacheInner::Statement(stmt),
+            always,
+            index_type,
+            max_mem,
+        }) => (name, *stmt, always, index_type, max_mem),
+        SqlQuery::CreateCache(CreateCacheStatement {
+            inner: CacheInner::Id(_),
+            ..
+        }) => unsupported!("Preloaded caches must be created from a query, not a query ID"),
+        _ => unsupported!("Only SELECT and CREATE CACHE statements can be preloaded"),
+    };
+
+    rewrite::process_query(&mut stmt, noria.server_supports_pagination())?;
+    noria
+        .handle_create_cached_query(name.as_ref(), &stmt, None, always, index_type, max_mem)
+        .await?;
+
+    let view_request = ViewCreateRequest::new(stmt, noria.schema_search_path().to_owned());
+    query_status_cache.update_query_migration_state(&view_request, MigrationState::Successful);
+    query_status_cache.always_attempt_readyset(&view_request, always);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_statements() {
+        assert_eq!(
+            split_statements(
+                Dialect::MySQL,
+                "SELECT a FROM t WHERE b = 'x;y' AND c = \"it\\\";s\";
+                 -- a comment; with a semicolon
+                 /* another; comment */ ;
+                 CREATE CACHE FROM SELECT `a;b` FROM t # trailing; comment
+                 ;;  SELECT 1"
+            ),
+            vec![
+                "SELECT a FROM t WHERE b = 'x;y' AND c = \"it\\\";s\"",
+                "CREATE CACHE FROM SELECT `a;b` FROM t",
+                "SELECT 1",
+            ]
+        );
+        assert!(split_statements(Dialect::PostgreSQL, " -- nothing here\n ; ").is_empty());
+    }
+}
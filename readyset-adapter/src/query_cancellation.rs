@@ -0,0 +1,313 @@
+//! Cancelling in-flight queries from other connections with `KILL QUERY`.
+//!
+//! Every connection to the adapter is registered with a [`QueryCanceller`] shared between all
+//! connections, which assigns it the connection id reported to the client. Reads executed by the
+//! connection are run via [`ConnectionCancellation::run`], which aborts them if another
+//! connection authenticated as the same user issues `KILL QUERY <id>` for that connection while
+//! they're in progress. If the query
+//! may be running against the upstream database, the upstream database is also asked to cancel it,
+//! so that it stops doing work for a result nobody will read.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+use readyset_errors::{ReadySetError, ReadySetResult};
+use tokio::sync::oneshot;
+use tracing::warn;
+
+use crate::upstream_database::UpstreamCancelHandle;
+
+/// A query that's currently being run by a connection
+struct RunningQuery {
+    /// Aborts the query
+    cancel: oneshot::Sender<()>,
+    /// If the query may be running against the upstream database, a handle used to cancel it
+    /// there
+    upstream: Option<Box<dyn UpstreamCancelHandle>>,
+}
+
+/// A connection registered with a [`QueryCanceller`]
+#[derive(Default)]
+struct Connection {
+    /// The user the connection authenticated as, if known
+    user: Option<String>,
+    /// The query being run by the connection, if any
+    running: Option<RunningQuery>,
+}
+
+struct Inner {
+    /// The id to assign to the next connection that's registered
+    next_id: AtomicU32,
+    /// All registered connections, by id
+    connections: Mutex<HashMap<u32, Connection>>,
+}
+
+/// A registry of all the connections to the adapter, which can be used to cancel the query any of
+/// them is currently running.
+///
+/// Cloning a [`QueryCanceller`] returns a handle to the same registry.
+#[derive(Clone)]
+pub struct QueryCanceller {
+    inner: Arc<Inner>,
+}
+
+impl fmt::Debug for QueryCanceller {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryCanceller")
+            .field("next_id", &self.inner.next_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for QueryCanceller {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                // Connection ids start at 1, as they do in MySQL
+                next_id: AtomicU32::new(1),
+                connections: Default::default(),
+            }),
+        }
+    }
+}
+
+impl QueryCanceller {
+    /// Create a new, empty, registry of connections
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new connection, returning a handle which assigns it its connection id and
+    /// allows the queries it runs to be cancelled. The connection is deregistered when the
+    /// returned handle is dropped.
+    pub fn register(&self) -> ConnectionCancellation {
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        #[allow(clippy::unwrap_used)] // Only panics if the lock is poisoned
+        self.inner
+            .connections
+            .lock()
+            .unwrap()
+            .insert(id, Connection::default());
+        ConnectionCancellation {
+            id,
+            canceller: self.clone(),
+        }
+    }
+
+    /// Cancel the query currently being run by the connection with the given id, if any, on behalf
+    /// of a connection authenticated as `user`.
+    ///
+    /// Returns [`ReadySetError::UnknownConnection`] if no connection with that id is registered,
+    /// and [`ReadySetError::KillDenied`] if that connection authenticated as a different user. If
+    /// the connection exists but isn't currently running a query, this does nothing.
+    pub fn kill_query(&self, connection_id: u64, user: Option<&str>) -> ReadySetResult<()> {
+        let unknown = || ReadySetError::UnknownConnection { id: connection_id };
+        let id = u32::try_from(connection_id).map_err(|_| unknown())?;
+        #[allow(clippy::unwrap_used)] // Only panics if the lock is poisoned
+        let mut connections = self.inner.connections.lock().unwrap();
+        let connection = connections.get_mut(&id).ok_or_else(unknown)?;
+        if connection.user.as_deref() != user {
+            return Err(ReadySetError::KillDenied { id: connection_id });
+        }
+        if let Some(RunningQuery { cancel, upstream }) = connection.running.take() {
+            // If the receiver has been dropped the query has already finished, so there's nothing
+            // to cancel
+            if cancel.send(()).is_ok() {
+                if let Some(upstream) = upstream {
+                    tokio::spawn(async move {
+                        if let Err(error) = upstream.cancel().await {
+                            warn!(%error, "Failed to cancel query on the upstream database");
+                        }
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A handle to a single connection registered with a [`QueryCanceller`]
+#[derive(Debug)]
+pub struct ConnectionCancellation {
+    id: u32,
+    canceller: QueryCanceller,
+}
+
+impl ConnectionCancellation {
+    /// Returns the id assigned to this connection
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the registry this connection is registered with
+    pub fn canceller(&self) -> &QueryCanceller {
+        &self.canceller
+    }
+
+    /// Record the user this connection authenticated as. Only connections authenticated as the
+    /// same user can cancel its queries.
+    pub fn set_user(&self, user: &str) {
+        #[allow(clippy::unwrap_used)] // Only panics if the lock is poisoned
+        if let Some(connection) = self
+            .canceller
+            .inner
+            .connections
+            .lock()
+            .unwrap()
+            .get_mut(&self.id)
+        {
+            connection.user = Some(user.to_owned());
+        }
+    }
+
+    /// Run `query`, aborting it with [`ReadySetError::QueryCancelled`] if it's cancelled with
+    /// [`QueryCanceller::kill_query`] before it completes.
+    ///
+    /// If `query` may run against the upstream database, `upstream` should be a handle to the
+    /// upstream connection it uses, so that the query can also be cancelled there.
+    pub async fn run<F, T, E>(
+        &self,
+        upstream: Option<Box<dyn UpstreamCancelHandle>>,
+        query: F,
+    ) -> Result<T, E>
+    where
+        F: Future<Output = Result<T, E>>,
+        E: From<ReadySetError>,
+    {
+        let (cancel, rx) = oneshot::channel();
+        #[allow(clippy::unwrap_used)] // Only panics if the lock is poisoned
+        self.canceller
+            .inner
+            .connections
+            .lock()
+            .unwrap()
+            .entry(self.id)
+            .or_default()
+            .running = Some(RunningQuery { cancel, upstream });
+
+        let res = tokio::select! {
+            biased;
+            Ok(()) = rx => Err(ReadySetError::QueryCancelled.into()),
+            res = query => res,
+        };
+
+        #[allow(clippy::unwrap_used)] // Only panics if the lock is poisoned
+        if let Some(connection) = self
+            .canceller
+            .inner
+            .connections
+            .lock()
+            .unwrap()
+            .get_mut(&self.id)
+        {
+            connection.running = None;
+        }
+
+        res
+    }
+}
+
+impl Drop for ConnectionCancellation {
+    fn drop(&mut self) {
+        #[allow(clippy::unwrap_used)] // Only panics if the lock is poisoned
+        self.canceller
+            .inner
+            .connections
+            .lock()
+            .unwrap()
+            .remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn connections_get_distinct_ids() {
+        let canceller = QueryCanceller::new();
+        let conn1 = canceller.register();
+        let conn2 = canceller.register();
+        assert_ne!(conn1.id(), conn2.id());
+    }
+
+    #[tokio::test]
+    async fn kill_idle_connection_does_nothing() {
+        let canceller = QueryCanceller::new();
+        let conn = canceller.register();
+        canceller.kill_query(conn.id().into(), None).unwrap();
+        assert_eq!(
+            conn.run(None, async { ReadySetResult::Ok(1) })
+                .await
+                .unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn kill_unknown_connection() {
+        let canceller = QueryCanceller::new();
+        let conn = canceller.register();
+        let id = conn.id();
+        drop(conn);
+        assert!(matches!(
+            canceller.kill_query(id.into(), None),
+            Err(ReadySetError::UnknownConnection { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn kill_stalled_query() {
+        let canceller = QueryCanceller::new();
+        let conn = canceller.register();
+        let id = conn.id();
+
+        let killer = {
+            let canceller = canceller.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                canceller.kill_query(id.into(), None)
+            })
+        };
+
+        let res = tokio::time::timeout(
+            Duration::from_secs(5),
+            conn.run(None, futures::future::pending::<ReadySetResult<()>>()),
+        )
+        .await
+        .expect("Killed query should abort promptly");
+        assert!(res.unwrap_err().is_query_cancelled());
+        killer.await.unwrap().unwrap();
+
+        // Subsequent queries on the same connection aren't affected
+        assert_eq!(
+            conn.run(None, async { ReadySetResult::Ok(1) })
+                .await
+                .unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn kill_other_users_query() {
+        let canceller = QueryCanceller::new();
+        let conn = canceller.register();
+        conn.set_user("alice");
+        assert!(matches!(
+            canceller.kill_query(conn.id().into(), Some("bob")),
+            Err(ReadySetError::KillDenied { .. })
+        ));
+        assert!(matches!(
+            canceller.kill_query(conn.id().into(), None),
+            Err(ReadySetError::KillDenied { .. })
+        ));
+        canceller
+            .kill_query(conn.id().into(), Some("alice"))
+            .unwrap();
+    }
+}
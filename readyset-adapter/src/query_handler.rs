@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use nom_sql::{SqlIdentifier, SqlQuery};
 use readyset::ReadySetResult;
 
@@ -14,6 +16,13 @@ pub enum SetBehavior {
     SetAutocommit(bool),
     /// This `SET` statement represents the current schema search path being changed
     SetSearchPath(Vec<SqlIdentifier>),
+    /// This `SET` statement sets the maximum amount of time any single statement run by the
+    /// session may take, or disables the timeout if `None`
+    SetStatementTimeout(Option<Duration>),
+    /// This `SET` statement changes whether strict mode is enabled (which controls whether values
+    /// too long for their column are rejected or truncated when written), and should otherwise be
+    /// proxied upstream
+    SetStrictMode(bool),
 }
 
 impl SetBehavior {
@@ -10,8 +10,67 @@ use anyhow::anyhow;
 use dashmap::DashMap;
 use launchpad::hash::hash;
 use readyset::query::*;
+use readyset_sql_passes::anonymize::Anonymizer;
+use serde::Serialize;
 use tracing::error;
 
+/// The status of a query in the [`QueryStatusCache`], as reported by the adapter's `/plan_cache`
+/// HTTP endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanCacheStatus {
+    /// The query has been migrated, and is being served from ReadySet
+    Cached,
+    /// We haven't yet determined whether the query is supported by ReadySet
+    Pending,
+    /// The query is not supported by ReadySet, and is proxied to the upstream database
+    Unsupported,
+}
+
+impl From<MigrationState> for PlanCacheStatus {
+    fn from(state: MigrationState) -> Self {
+        match state {
+            MigrationState::Successful => Self::Cached,
+            MigrationState::Pending | MigrationState::DryRunSucceeded => Self::Pending,
+            MigrationState::Unsupported => Self::Unsupported,
+        }
+    }
+}
+
+/// A single query known to the [`QueryStatusCache`], as reported by the adapter's `/plan_cache`
+/// HTTP endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlanCacheEntry {
+    /// The id of the query. Unless the query was cached with an explicit name via `CREATE CACHE`,
+    /// this is also the name of the view for the query in ReadySet.
+    pub id: String,
+    /// The query, with its literals and identifiers anonymized
+    pub query: String,
+    /// The current status of the query
+    pub status: PlanCacheStatus,
+    /// Whether the query is always executed against ReadySet, and never proxied upstream
+    pub always: bool,
+}
+
+/// The default amount of time to wait before retrying a migration for a query after its first
+/// failure. Each subsequent failure doubles the amount of time to wait, up to
+/// [`MAX_MIGRATION_BACKOFF`].
+const INITIAL_MIGRATION_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The maximum amount of time to wait before retrying a migration for a query that has
+/// repeatedly failed to migrate.
+const MAX_MIGRATION_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// Tracks failed attempts to migrate a single query, so that we can avoid retrying the migration on
+/// every execution of the query
+#[derive(Debug, Clone, Copy)]
+struct MigrationBackoff {
+    /// The number of consecutive failed attempts to migrate the query
+    attempts: u32,
+    /// The earliest time at which we should attempt to migrate the query again
+    next_attempt: Instant,
+}
+
 /// A metadata cache for all queries that have been processed by this
 /// adapter. Thread-safe.
 #[derive(Debug)]
@@ -29,6 +88,15 @@ pub struct QueryStatusCache {
     /// Holds the current style of migration, whether async or explicit, which may change the
     /// behavior of some internal methods.
     style: MigrationStyle,
+
+    /// A thread-safe hash map that holds the backoff state of each query whose most recent
+    /// migration attempts in the request path have failed. Queries are removed from this map once
+    /// they are migrated successfully.
+    migration_backoffs: DashMap<Query, MigrationBackoff>,
+
+    /// The amount of time to wait before retrying a migration for a query after its first
+    /// failure.
+    initial_migration_backoff: Duration,
 }
 
 impl Default for QueryStatusCache {
@@ -44,6 +112,8 @@ impl QueryStatusCache {
             statuses: DashMap::new(),
             ids: DashMap::new(),
             style: MigrationStyle::InRequestPath,
+            migration_backoffs: DashMap::new(),
+            initial_migration_backoff: INITIAL_MIGRATION_BACKOFF,
         }
     }
 
@@ -94,9 +164,18 @@ impl QueryStatusCache {
             statuses: DashMap::new(),
             ids: DashMap::new(),
             style,
+            migration_backoffs: DashMap::new(),
+            initial_migration_backoff: INITIAL_MIGRATION_BACKOFF,
         }
     }
 
+    /// Sets the amount of time to wait before retrying a migration for a query after its first
+    /// failure, which doubles with each subsequent failure. Defaults to 1 second.
+    pub fn initial_migration_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_migration_backoff = backoff;
+        self
+    }
+
     /// This function returns the id and query migration state of a query. If the query does not
     /// exist within the query status cache, an entry is created and the query is set to
     /// PendingMigration.
@@ -222,6 +301,69 @@ impl QueryStatusCache {
         false
     }
 
+    /// Returns true if we should attempt to migrate the given query, or false if a previous attempt
+    /// to migrate it failed recently enough that we're still backing off.
+    pub fn migration_allowed<Q>(&self, q: &Q) -> bool
+    where
+        Q: Hash + Eq,
+        Query: Borrow<Q>,
+    {
+        self.migration_allowed_at(q, Instant::now())
+    }
+
+    fn migration_allowed_at<Q>(&self, q: &Q, now: Instant) -> bool
+    where
+        Q: Hash + Eq,
+        Query: Borrow<Q>,
+    {
+        self.migration_backoffs
+            .get(q)
+            .map_or(true, |b| now >= b.next_attempt)
+    }
+
+    /// Record that an attempt to migrate the given query failed, so that we don't attempt to
+    /// migrate it again until an exponentially increasing backoff period has elapsed.
+    pub fn migration_failed<Q>(&self, q: &Q)
+    where
+        Q: Clone + Hash + Eq,
+        Query: From<Q> + Borrow<Q>,
+    {
+        self.migration_failed_at(q, Instant::now())
+    }
+
+    fn migration_failed_at<Q>(&self, q: &Q, now: Instant)
+    where
+        Q: Clone + Hash + Eq,
+        Query: From<Q> + Borrow<Q>,
+    {
+        let attempts = self
+            .migration_backoffs
+            .get(q)
+            .map_or(0, |b| b.attempts)
+            .saturating_add(1);
+        let backoff = self
+            .initial_migration_backoff
+            .checked_mul(2u32.saturating_pow(attempts - 1))
+            .map_or(MAX_MIGRATION_BACKOFF, |b| b.min(MAX_MIGRATION_BACKOFF));
+        self.migration_backoffs.insert(
+            q.clone().into(),
+            MigrationBackoff {
+                attempts,
+                next_attempt: now + backoff,
+            },
+        );
+    }
+
+    /// Record that the given query was migrated successfully, resetting any backoff from previous
+    /// failed attempts to migrate it.
+    pub fn migration_succeeded<Q>(&self, q: &Q)
+    where
+        Q: Hash + Eq,
+        Query: Borrow<Q>,
+    {
+        self.migration_backoffs.remove(q);
+    }
+
     /// Updates a queries migration state to `m` unless the queries migration state was
     /// `MigrationState::Unsupported`. An unsupported query cannot currently become supported once
     /// again.
@@ -366,6 +508,32 @@ impl QueryStatusCache {
         }
     }
 
+    /// Returns an entry for every query in the cache, with its status, ordered by query id.
+    ///
+    /// Queries are anonymized, so the result is safe to expose for debugging.
+    pub fn plan_cache(&self) -> Vec<PlanCacheEntry> {
+        let mut anonymizer = Anonymizer::new();
+        let mut entries = self
+            .ids
+            .iter()
+            .map(|r| {
+                let (migration_state, always) = self
+                    .statuses
+                    .get(Borrow::<Query>::borrow(r.value()))
+                    .map(|s| (s.migration_state, s.always))
+                    .unwrap_or_else(|| (MigrationState::default_for_query(r.value()), false));
+                PlanCacheEntry {
+                    id: r.key().to_string(),
+                    query: r.value().to_anonymized_string(&mut anonymizer),
+                    status: migration_state.into(),
+                    always,
+                }
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by(|e1, e2| e1.id.cmp(&e2.id));
+        entries
+    }
+
     /// Returns a query given a query hash
     pub fn query(&self, id: &str) -> Option<Query> {
         let id = QueryId::new(u64::from_str_radix(id.strip_prefix("q_")?, 16).ok()?);
@@ -563,4 +731,100 @@ mod tests {
         cache.clear();
         assert_eq!(cache.allow_list().len(), 0);
     }
+
+    #[test]
+    fn plan_cache() {
+        let cache = QueryStatusCache::new();
+        let cached = ViewCreateRequest::new(
+            select_statement("SELECT * FROM t1 WHERE id = 1").unwrap(),
+            vec![],
+        );
+        let pending = ViewCreateRequest::new(select_statement("SELECT * FROM t2").unwrap(), vec![]);
+        cache.insert(pending.clone());
+        cache.update_query_migration_state(&cached, MigrationState::Successful);
+        cache.insert("SELECT unparseable".to_string());
+
+        let entries = cache.plan_cache();
+        assert_eq!(entries.len(), 3);
+
+        let (cached_id, _) = cache.query_migration_state(&cached);
+        let cached_entry = entries
+            .iter()
+            .find(|e| e.id == cached_id.to_string())
+            .unwrap();
+        assert_eq!(cached_entry.status, PlanCacheStatus::Cached);
+        // Identifiers are anonymized
+        assert!(!cached_entry.query.contains("t1"));
+
+        let (pending_id, _) = cache.query_migration_state(&pending);
+        assert_eq!(
+            entries
+                .iter()
+                .find(|e| e.id == pending_id.to_string())
+                .unwrap()
+                .status,
+            PlanCacheStatus::Pending
+        );
+        assert_eq!(
+            entries
+                .iter()
+                .filter(|e| e.status == PlanCacheStatus::Unsupported)
+                .count(),
+            1
+        );
+
+        let json = serde_json::to_value(cached_entry).unwrap();
+        assert_eq!(json["status"], "cached");
+    }
+
+    #[test]
+    fn migration_backoff() {
+        let cache = QueryStatusCache::new();
+        let query = ViewCreateRequest::new(select_statement("SELECT * FROM t1").unwrap(), vec![]);
+        let other = ViewCreateRequest::new(select_statement("SELECT * FROM t2").unwrap(), vec![]);
+        let start = Instant::now();
+
+        assert!(cache.migration_allowed_at(&query, start));
+
+        cache.migration_failed_at(&query, start);
+        assert!(!cache.migration_allowed_at(&query, start));
+        assert!(!cache.migration_allowed_at(&query, start + Duration::from_millis(500)));
+        assert!(cache.migration_allowed_at(&query, start + INITIAL_MIGRATION_BACKOFF));
+        // Other queries are unaffected
+        assert!(cache.migration_allowed_at(&other, start));
+
+        // The backoff doubles with each subsequent failure
+        let retry = start + INITIAL_MIGRATION_BACKOFF;
+        cache.migration_failed_at(&query, retry);
+        assert!(!cache.migration_allowed_at(&query, retry + INITIAL_MIGRATION_BACKOFF));
+        assert!(cache.migration_allowed_at(&query, retry + INITIAL_MIGRATION_BACKOFF * 2));
+
+        cache.migration_succeeded(&query);
+        assert!(cache.migration_allowed_at(&query, retry));
+    }
+
+    #[test]
+    fn configured_migration_backoff() {
+        let backoff = Duration::from_millis(10);
+        let cache = QueryStatusCache::new().initial_migration_backoff(backoff);
+        let query = ViewCreateRequest::new(select_statement("SELECT * FROM t1").unwrap(), vec![]);
+        let now = Instant::now();
+
+        cache.migration_failed_at(&query, now);
+        assert!(!cache.migration_allowed_at(&query, now));
+        assert!(cache.migration_allowed_at(&query, now + backoff));
+    }
+
+    #[test]
+    fn migration_backoff_is_capped() {
+        let cache = QueryStatusCache::new();
+        let query = ViewCreateRequest::new(select_statement("SELECT * FROM t1").unwrap(), vec![]);
+        let now = Instant::now();
+
+        for _ in 0..100 {
+            cache.migration_failed_at(&query, now);
+        }
+        assert!(!cache.migration_allowed_at(&query, now));
+        assert!(cache.migration_allowed_at(&query, now + MAX_MIGRATION_BACKOFF));
+    }
 }
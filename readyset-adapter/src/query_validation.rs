@@ -0,0 +1,279 @@
+//! Reporting mismatches found when validating queries executed against ReadySet against the
+//! upstream database.
+//!
+//! When the adapter is started with `--validate-queries`, the schemas of queries that ReadySet can
+//! serve are checked against the upstream database when they're prepared, and the results of
+//! queries served by ReadySet are checked against the results of the same query executed against
+//! the upstream database. Any mismatch that is found is logged (at the level configured with
+//! `--validation-mismatch-log-level`) and counted by the [`QUERY_VALIDATION_MISMATCH`] metric,
+//! labeled by the query with all its literals anonymized.
+
+use std::fmt::{self, Display};
+
+use metrics::counter;
+use nom_sql::{SelectStatement, SqlQuery};
+use readyset_client_metrics::recorded::QUERY_VALIDATION_MISMATCH;
+use readyset_data::DfValue;
+use readyset_sql_passes::anonymize::anonymize_query_literals;
+use tracing::{debug, error, info, trace, warn, Level};
+
+/// A single value that differs between the upstream and ReadySet results for a query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueMismatch {
+    /// The index of the row containing the value
+    pub row: usize,
+    /// The index of the column containing the value
+    pub column: usize,
+    /// The value returned by the upstream database, or `None` if the upstream row has no value
+    /// for this column
+    pub upstream: Option<DfValue>,
+    /// The value returned by ReadySet, or `None` if the ReadySet row has no value for this column
+    pub readyset: Option<DfValue>,
+}
+
+/// A structured description of the ways in which the results of a query executed against ReadySet
+/// differ from the results of the same query executed against the upstream database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultDiff {
+    /// The number of rows returned by the upstream database
+    pub upstream_rows: usize,
+    /// The number of rows returned by ReadySet
+    pub readyset_rows: usize,
+    /// The individual values that differ between the rows present in both results
+    pub mismatches: Vec<ValueMismatch>,
+}
+
+impl Display for ResultDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.upstream_rows != self.readyset_rows {
+            write!(
+                f,
+                "upstream returned {} rows but ReadySet returned {} rows",
+                self.upstream_rows, self.readyset_rows
+            )?;
+            if !self.mismatches.is_empty() {
+                write!(f, "; ")?;
+            }
+        }
+
+        let show = |f: &mut fmt::Formatter<'_>, v: &Option<DfValue>| match v {
+            Some(v) => write!(f, "{:?}", v),
+            None => write!(f, "<missing>"),
+        };
+        for (i, mismatch) in self.mismatches.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(
+                f,
+                "row {} column {}: upstream ",
+                mismatch.row, mismatch.column
+            )?;
+            show(f, &mismatch.upstream)?;
+            write!(f, ", ReadySet ")?;
+            show(f, &mismatch.readyset)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Compare the rows returned by the upstream database with the rows returned by ReadySet for the
+/// same query, returning a description of any differences between them, or `None` if the results
+/// are the same.
+///
+/// Rows are compared positionally, so both sets of rows should be in the same order. Values are
+/// compared using [`DfValue::value_eq`], so values that are equal but represented differently
+/// (such as an integer returned upstream and a decimal returned by ReadySet) are not considered to
+/// differ.
+pub fn diff_results(upstream: &[Vec<DfValue>], readyset: &[Vec<DfValue>]) -> Option<ResultDiff> {
+    let mut mismatches = vec![];
+    for (row, (upstream_row, readyset_row)) in upstream.iter().zip(readyset).enumerate() {
+        for column in 0..upstream_row.len().max(readyset_row.len()) {
+            let upstream = upstream_row.get(column);
+            let readyset = readyset_row.get(column);
+            let equal = match (upstream, readyset) {
+                (Some(u), Some(r)) => u.value_eq(r),
+                _ => false,
+            };
+            if !equal {
+                mismatches.push(ValueMismatch {
+                    row,
+                    column,
+                    upstream: upstream.cloned(),
+                    readyset: readyset.cloned(),
+                });
+            }
+        }
+    }
+
+    if mismatches.is_empty() && upstream.len() == readyset.len() {
+        None
+    } else {
+        Some(ResultDiff {
+            upstream_rows: upstream.len(),
+            readyset_rows: readyset.len(),
+            mismatches,
+        })
+    }
+}
+
+/// Returns the given query with all of its literals anonymized, for use as a metric label
+fn anonymized_query(query: &SelectStatement) -> String {
+    let mut query = SqlQuery::Select(query.clone());
+    anonymize_query_literals(&mut query);
+    query.to_string()
+}
+
+/// Compare the rows returned by the upstream database for `query` with the rows returned by
+/// ReadySet, and report any differences between them found by [`diff_results`], returning true if
+/// there were any.
+///
+/// If the query has no `ORDER BY` clause the order of its rows is unspecified, so the rows are
+/// sorted before they're compared.
+pub fn report_mismatch(
+    query: &SelectStatement,
+    mut upstream: Vec<Vec<DfValue>>,
+    mut readyset: Vec<Vec<DfValue>>,
+    level: Level,
+) -> bool {
+    if query.order.is_none() {
+        upstream.sort();
+        readyset.sort();
+    }
+
+    match diff_results(&upstream, &readyset) {
+        Some(diff) => {
+            record_mismatch(query, &diff, level);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Report that validating the schema of `query` against the upstream database found a mismatch,
+/// described by `mismatch`.
+pub fn report_schema_mismatch<M>(query: &SelectStatement, mismatch: &M, level: Level)
+where
+    M: Display + ?Sized,
+{
+    record_mismatch(query, mismatch, level)
+}
+
+/// Log the given `mismatch` at the given `level`, and increment the [`QUERY_VALIDATION_MISMATCH`]
+/// metric for `query`.
+fn record_mismatch<M>(query: &SelectStatement, mismatch: &M, level: Level)
+where
+    M: Display + ?Sized,
+{
+    let anonymized = anonymized_query(query);
+    counter!(QUERY_VALIDATION_MISMATCH, 1, "query" => anonymized.clone());
+
+    macro_rules! log_mismatch {
+        ($log:ident) => {
+            $log!(query = %anonymized, %mismatch, "Query validation found a mismatch")
+        };
+    }
+    match level {
+        Level::ERROR => log_mismatch!(error),
+        Level::WARN => log_mismatch!(warn),
+        Level::INFO => log_mismatch!(info),
+        Level::DEBUG => log_mismatch!(debug),
+        Level::TRACE => log_mismatch!(trace),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+    use nom_sql::{parse_query, Dialect};
+    use rust_decimal::Decimal;
+
+    use super::*;
+
+    fn rows(rows: &[&[DfValue]]) -> Vec<Vec<DfValue>> {
+        rows.iter().map(|r| r.to_vec()).collect()
+    }
+
+    #[test]
+    fn equal_results_have_no_diff() {
+        let upstream = rows(&[&[DfValue::Int(1), DfValue::from("a")]]);
+        let readyset = rows(&[&[DfValue::from(Decimal::new(10, 1)), DfValue::from("a")]]);
+        assert_eq!(diff_results(&upstream, &readyset), None);
+    }
+
+    #[test]
+    fn mismatched_row_counts() {
+        let upstream = rows(&[&[DfValue::Int(1)], &[DfValue::Int(2)]]);
+        let readyset = rows(&[&[DfValue::Int(1)]]);
+        let diff = diff_results(&upstream, &readyset).unwrap();
+        assert_eq!(diff.upstream_rows, 2);
+        assert_eq!(diff.readyset_rows, 1);
+        assert!(diff.mismatches.is_empty());
+    }
+
+    #[test]
+    fn deliberate_mismatch_is_diffed_and_counted() {
+        let upstream = rows(&[
+            &[DfValue::Int(1), DfValue::from("a")],
+            &[DfValue::Int(2), DfValue::from("b")],
+        ]);
+        let readyset = rows(&[
+            &[DfValue::Int(1), DfValue::from("a")],
+            &[DfValue::Int(2), DfValue::from("c")],
+        ]);
+        let diff = diff_results(&upstream, &readyset).unwrap();
+        assert_eq!(
+            diff.mismatches,
+            vec![ValueMismatch {
+                row: 1,
+                column: 1,
+                upstream: Some(DfValue::from("b")),
+                readyset: Some(DfValue::from("c")),
+            }]
+        );
+        assert!(diff.to_string().starts_with("row 1 column 1: upstream "));
+
+        // Without an ORDER BY, rows returned in a different order aren't a mismatch
+        let unordered = match parse_query(Dialect::MySQL, "SELECT a, b FROM t").unwrap() {
+            SqlQuery::Select(stmt) => stmt,
+            _ => unreachable!(),
+        };
+        assert!(!report_mismatch(
+            &unordered,
+            upstream.clone(),
+            upstream.iter().rev().cloned().collect(),
+            Level::WARN
+        ));
+
+        let recorder = DebuggingRecorder::per_thread();
+        let snapshotter = recorder.snapshotter();
+        recorder.install().unwrap();
+
+        let query = match parse_query(Dialect::MySQL, "SELECT a, b FROM t WHERE a = 1").unwrap() {
+            SqlQuery::Select(stmt) => stmt,
+            _ => unreachable!(),
+        };
+        assert!(report_mismatch(&query, upstream, readyset, Level::WARN));
+
+        let counts = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter(|(key, ..)| key.key().name() == QUERY_VALIDATION_MISMATCH)
+            .map(|(key, _, _, value)| {
+                let label = key
+                    .key()
+                    .labels()
+                    .find(|l| l.key() == "query")
+                    .map(|l| l.value().to_owned());
+                (label, value)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            counts,
+            vec![(Some(anonymized_query(&query)), DebugValue::Counter(1))]
+        );
+        assert!(!anonymized_query(&query).contains('1'));
+    }
+}
@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::cmp::max;
+use std::cmp::{max, Ordering};
 use std::convert::{TryFrom, TryInto};
 use std::fmt::Debug;
 use std::{iter, mem};
@@ -81,6 +81,7 @@ pub fn process_query(
 
     let auto_parameters = auto_parametrize_query(query);
     let rewritten_in_conditions = collapse_where_in(query)?;
+    sort_literal_in_lists(query);
     number_placeholders(query)?;
     Ok(ProcessedQueryParams {
         reordered_placeholders,
@@ -353,6 +354,47 @@ fn collapse_where_in(query: &mut SelectStatement) -> ReadySetResult<Vec<Rewritte
     Ok(res)
 }
 
+struct SortLiteralInListsVisitor;
+
+impl<'ast> VisitorMut<'ast> for SortLiteralInListsVisitor {
+    type Error = !;
+
+    fn visit_expr(&mut self, expression: &'ast mut Expr) -> Result<(), Self::Error> {
+        if let Expr::In {
+            rhs: InValue::List(list),
+            ..
+        } = expression
+        {
+            if list.iter().all(|e| {
+                matches!(
+                    e,
+                    Expr::Literal(lit) if !matches!(lit, Literal::Placeholder(_))
+                )
+            }) {
+                list.sort_by(|a, b| match (a, b) {
+                    (Expr::Literal(a), Expr::Literal(b)) => {
+                        a.partial_cmp(b).unwrap_or(Ordering::Equal)
+                    }
+                    _ => Ordering::Equal,
+                });
+            }
+        }
+
+        visit_mut::walk_expr(self, expression)
+    }
+}
+
+/// Sort the elements of all IN lists in the given `query` which consist entirely of
+/// (non-placeholder) literals into a canonical order.
+///
+/// IN lists which survive [`auto_parametrize_query`] and [`collapse_where_in`] are handled by
+/// filter nodes in dataflow, and so become part of the query itself - sorting them ensures that
+/// semantically identical queries such as `x IN (1, 2, 3)` and `x IN (3, 1, 2)` hash to the same
+/// query name, and thus share a view.
+fn sort_literal_in_lists(query: &mut SelectStatement) {
+    let Ok(()) = SortLiteralInListsVisitor.visit_select_statement(query);
+}
+
 /// Given a slice of parameters provided by the user and the list of [`RewrittenIn`] returned by
 /// [`collapse_where_in`] on a query, construct a vector of lookup keys for executing that query
 fn explode_params<'param, 'a, T>(
@@ -1283,6 +1325,22 @@ mod tests {
             assert_eq!(query, expected);
         }
 
+        #[test]
+        fn literal_in_list_order_is_normalized() {
+            // Aggregates prevent the IN list from being auto-parametrized, so it stays in the
+            // query
+            let mut q1 = parse_select_statement("SELECT count(*) FROM users WHERE id IN (1, 2, 3)");
+            let mut q2 = parse_select_statement("SELECT count(*) FROM users WHERE id IN (3, 1, 2)");
+            process_query(&mut q1, false).unwrap();
+            process_query(&mut q2, false).unwrap();
+
+            assert_eq!(q1, q2);
+            assert_eq!(
+                crate::utils::generate_query_name(&q1, &[]),
+                crate::utils::generate_query_name(&q2, &[])
+            );
+        }
+
         #[test]
         fn no_keys() {
             let (keys, query) = process_and_make_keys("SELECT * FROM test", vec![]);
@@ -4,6 +4,7 @@ use std::fmt::Debug;
 use async_trait::async_trait;
 pub use database_utils::UpstreamConfig;
 use nom_sql::SqlIdentifier;
+use readyset::replication::ReplicationOffset;
 use readyset::ColumnSchema;
 use readyset_client_metrics::QueryDestination;
 use readyset_data::DfValue;
@@ -39,6 +40,15 @@ pub trait NoriaCompare {
         -> Result<(), Self::Error>;
 }
 
+/// A handle to a connection to the upstream database which can be used to cancel the query that
+/// connection is currently running, from outside of that connection.
+#[async_trait]
+pub trait UpstreamCancelHandle: Send + Sync + 'static {
+    /// Ask the upstream database to cancel the query the connection is currently running. Does
+    /// nothing if the connection isn't running a query.
+    async fn cancel(&self) -> Result<(), anyhow::Error>;
+}
+
 pub trait IsFatalError {
     fn is_fatal(&self) -> bool;
 }
@@ -140,6 +150,22 @@ pub trait UpstreamDatabase: Sized + Send {
     where
         S: AsRef<str> + Send + Sync + 'a;
 
+    /// Execute a raw, un-prepared read query, and return all of its result rows converted to
+    /// [`DfValue`]s, for comparison against the results returned by ReadySet when validating
+    /// queries
+    async fn query_rows<S>(&mut self, query: S) -> Result<Vec<Vec<DfValue>>, Self::Error>
+    where
+        S: AsRef<str> + Send + Sync;
+
+    /// Execute a read statement that was prepared earlier with [`prepare`](Self::prepare) with the
+    /// given params, and return all of its result rows converted to [`DfValue`]s, for comparison
+    /// against the results returned by ReadySet when validating queries
+    async fn execute_rows(
+        &mut self,
+        statement_id: u32,
+        params: &[DfValue],
+    ) -> Result<Vec<Vec<DfValue>>, Self::Error>;
+
     /// Execute a raw, un-prepared write query, constructing and returning a RYW ticket for the
     /// write
     // TODO: newtype RYW ticket, not just String
@@ -159,6 +185,10 @@ pub trait UpstreamDatabase: Sized + Send {
     /// Handle rolling back the ongoing transaction for this connection to the upstream db.
     async fn rollback<'a>(&'a mut self) -> Result<Self::QueryResult<'a>, Self::Error>;
 
+    /// Returns a handle which can be used to cancel the query this connection is running while
+    /// it's in progress
+    fn cancel_handle(&self) -> Box<dyn UpstreamCancelHandle>;
+
     /// Return schema dump from the upstream database, for inclusion in a query analysis bundle.
     async fn schema_dump(&mut self) -> Result<Vec<u8>, anyhow::Error>;
 
@@ -168,4 +198,20 @@ pub trait UpstreamDatabase: Sized + Send {
     /// supports a multi-element schema search path, the concept of "currently connected database"
     /// in MySQL can be thought of as a schema search path that only has one element
     async fn schema_search_path(&mut self) -> Result<Vec<SqlIdentifier>, Self::Error>;
+
+    /// Query the upstream database for whether strict mode is enabled for this connection - that
+    /// is, whether values which are too long for the column they're written to are rejected with
+    /// an error rather than truncated.
+    ///
+    /// Defaults to `true`, for databases (such as PostgreSQL) which always reject such values.
+    async fn strict_mode(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    /// Query the upstream database for the current position of its replication log - the offset
+    /// ReadySet needs to have replicated up to in order to have seen every write committed to the
+    /// upstream database so far.
+    ///
+    /// Returns `None` if the upstream database isn't writing a replication log
+    async fn replication_offset(&mut self) -> Result<Option<ReplicationOffset>, Self::Error>;
 }
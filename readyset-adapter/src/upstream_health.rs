@@ -0,0 +1,136 @@
+//! Tracking whether the upstream database is reachable, so that the adapter can report itself as
+//! not ready to serve queries while it isn't.
+//!
+//! When the adapter is started with an upstream database, an [`UpstreamHealth`] is kept up to date
+//! by [`UpstreamHealth::monitor_loop`], which periodically runs a trivial query against the
+//! upstream database over a dedicated connection.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::select;
+use tracing::{debug, info, warn};
+
+use crate::upstream_database::{UpstreamConfig, UpstreamDatabase};
+
+/// A view of whether the upstream database is reachable, shared between the adapter's HTTP router
+/// and the task monitoring the upstream database.
+///
+/// Cloning an [`UpstreamHealth`] returns a handle to the same state.
+#[derive(Debug, Clone)]
+pub struct UpstreamHealth {
+    reachable: Arc<AtomicBool>,
+}
+
+impl Default for UpstreamHealth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UpstreamHealth {
+    /// Create a new [`UpstreamHealth`]. The upstream database starts out reachable.
+    pub fn new() -> Self {
+        Self {
+            reachable: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    /// Returns true if the most recent check of the upstream database succeeded
+    pub fn is_reachable(&self) -> bool {
+        self.reachable.load(Ordering::Acquire)
+    }
+
+    /// Record the result of checking whether the upstream database is reachable
+    pub fn record_check(&self, reachable: bool) {
+        let was_reachable = self.reachable.swap(reachable, Ordering::AcqRel);
+        if was_reachable && !reachable {
+            warn!("Lost connection to the upstream database");
+        } else if !was_reachable && reachable {
+            info!("Upstream database is reachable again");
+        }
+    }
+
+    /// Check whether the upstream database is reachable every `interval`, until a shutdown signal
+    /// is received on `shutdown_recv`.
+    ///
+    /// The upstream database is considered unreachable if connecting to it or running a query
+    /// against it fails, or doesn't complete within `interval`.
+    pub async fn monitor_loop<U>(
+        self,
+        upstream_config: UpstreamConfig,
+        interval: Duration,
+        mut shutdown_recv: tokio::sync::broadcast::Receiver<()>,
+    ) where
+        U: UpstreamDatabase,
+    {
+        let mut ticker = tokio::time::interval(interval);
+        let mut upstream: Option<U> = None;
+        loop {
+            select! {
+                _ = ticker.tick() => {
+                    let reachable = match tokio::time::timeout(
+                        interval,
+                        check_upstream(&mut upstream, &upstream_config),
+                    )
+                    .await
+                    {
+                        Ok(Ok(())) => true,
+                        Ok(Err(error)) => {
+                            debug!(%error, "Failed to check upstream database health");
+                            false
+                        }
+                        Err(_) => {
+                            debug!("Timed out checking upstream database health");
+                            upstream = None;
+                            false
+                        }
+                    };
+                    self.record_check(reachable);
+                }
+                _ = shutdown_recv.recv() => {
+                    info!("Upstream health monitor shutting down after shut down signal received");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Run a trivial query against the upstream database, connecting to it first if `upstream` doesn't
+/// already hold a connection. The connection is only kept for the next check if the query
+/// succeeds.
+async fn check_upstream<U>(
+    upstream: &mut Option<U>,
+    upstream_config: &UpstreamConfig,
+) -> Result<(), U::Error>
+where
+    U: UpstreamDatabase,
+{
+    let mut conn = match upstream.take() {
+        Some(conn) => conn,
+        None => U::connect(upstream_config.clone(), None).await?,
+    };
+    conn.query_rows("SELECT 1").await?;
+    *upstream = Some(conn);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_most_recent_check() {
+        let health = UpstreamHealth::new();
+        assert!(health.is_reachable());
+
+        health.record_check(false);
+        assert!(!health.is_reachable());
+        assert!(!health.clone().is_reachable());
+
+        health.record_check(true);
+        assert!(health.is_reachable());
+    }
+}
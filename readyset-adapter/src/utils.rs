@@ -6,13 +6,16 @@ use launchpad::hash::hash;
 use nom_sql::analysis::visit::{self, Visitor};
 use nom_sql::{
     BinaryOperator, Column, ColumnConstraint, CreateTableStatement, DeleteStatement, Expr,
-    InsertStatement, Literal, SelectStatement, SqlIdentifier, SqlQuery, TableKey, UpdateStatement,
+    FieldDefinitionExpr, InsertStatement, Literal, Relation, SelectStatement, SqlIdentifier,
+    SqlQuery, TableKey, UpdateStatement,
 };
 use readyset::{Modification, Operation};
 use readyset_data::{DfType, DfValue, Dialect};
 use readyset_errors::{
-    bad_request_err, invariant, invariant_eq, unsupported, unsupported_err, ReadySetResult,
+    bad_request_err, invariant, invariant_eq, unsupported, unsupported_err, ReadySetError,
+    ReadySetResult,
 };
+use tracing::warn;
 
 // Helper for flatten_conditional - returns true if the
 // expression is "valid" (i.e. not something like `a = 1 AND a = 2`.
@@ -167,6 +170,64 @@ pub(crate) fn get_primary_key(schema: &CreateTableStatement) -> Vec<(usize, &Col
         .collect()
 }
 
+/// If `query` is of the form `SELECT * FROM t WHERE t.c = <literal>`, returns the table being
+/// selected from along with the column and literal it's being filtered on. The literal may be a
+/// placeholder, if `query` is a prepared statement.
+///
+/// This doesn't check that `c` is actually the primary key of `t` - that's left up to the caller,
+/// since it requires knowing the schema of the table.
+pub(crate) fn base_table_point_lookup(
+    query: &SelectStatement,
+) -> Option<(&Relation, &Column, &Literal)> {
+    let table = match query.tables.as_slice() {
+        [table] => table,
+        _ => return None,
+    };
+    if query.distinct
+        || !query.ctes.is_empty()
+        || !query.join.is_empty()
+        || query.group_by.is_some()
+        || query.having.is_some()
+        || query.order.is_some()
+        || query.limit.is_some()
+        || query.offset.is_some()
+    {
+        return None;
+    }
+
+    let refers_to_table = |name: &Relation| {
+        *name == table.table
+            || (name.schema.is_none()
+                && (name.name == table.table.name || table.alias.iter().any(|a| *a == name.name)))
+    };
+    if !query.fields.iter().all(|field| match field {
+        FieldDefinitionExpr::All => true,
+        FieldDefinitionExpr::AllInTable(t) => refers_to_table(t),
+        FieldDefinitionExpr::Expr { .. } => false,
+    }) {
+        return None;
+    }
+
+    let (column, value) = match query.where_clause.as_ref()? {
+        Expr::BinaryOp {
+            lhs: box Expr::Column(c),
+            op: BinaryOperator::Equal,
+            rhs: box Expr::Literal(l),
+        }
+        | Expr::BinaryOp {
+            lhs: box Expr::Literal(l),
+            op: BinaryOperator::Equal,
+            rhs: box Expr::Column(c),
+        } => (c, l),
+        _ => return None,
+    };
+    if !column.table.iter().all(refers_to_table) {
+        return None;
+    }
+
+    Some((&table.table, column, value))
+}
+
 /// Gets parameter columns and binops in positions that can be evaluated by ReadySet.
 trait BinopsParameterColumns {
     fn get_binops_parameter_columns(&self) -> Vec<(&Column, BinaryOperator)>;
@@ -520,19 +581,56 @@ where
 }
 
 /// coerce params to correct sql types
+///
+/// If a string parameter is too long for the (`CHAR` or `VARCHAR`) column it's being written to,
+/// then if `strict` is true [`ReadySetError::DataTooLong`] is returned, and otherwise the value is
+/// truncated to fit (like MySQL does when not in strict mode) and the name of the column is pushed
+/// onto `truncated_columns`.
 pub(crate) fn coerce_params(
     params: Option<&[DfValue]>,
     q: &SqlQuery,
     schema: &CreateTableStatement,
     dialect: Dialect,
+    strict: bool,
+    truncated_columns: &mut Vec<String>,
 ) -> ReadySetResult<Option<Vec<DfValue>>> {
     if let Some(prms) = params {
+        let param_columns = get_parameter_columns(q);
+        if prms.len() != param_columns.len() {
+            return Err(ReadySetError::WrongParameterCount {
+                expected: param_columns.len(),
+                got: prms.len(),
+            });
+        }
+
         let mut coerced_params = vec![];
-        for (i, col) in get_parameter_columns(q).iter().enumerate() {
+        for (i, col) in param_columns.iter().enumerate() {
             for field in &schema.fields {
                 if col.name == field.column.name {
                     let target_type = DfType::from_sql_type(&field.sql_type, dialect, |_| None)?;
 
+                    if let DfType::Char(max_length, ..) | DfType::VarChar(max_length, ..) =
+                        target_type
+                    {
+                        if prms[i]
+                            .as_str()
+                            .map_or(false, |s| s.chars().count() > max_length as usize)
+                        {
+                            if strict {
+                                return Err(ReadySetError::DataTooLong {
+                                    column: field.column.name.to_string(),
+                                    max_length,
+                                });
+                            }
+                            warn!(
+                                column = %field.column.name,
+                                %max_length,
+                                "Truncating value which is too long for column"
+                            );
+                            truncated_columns.push(field.column.name.to_string());
+                        }
+                    }
+
                     coerced_params.push(DfValue::coerce_to(
                         &prms[i],
                         &target_type,
@@ -555,6 +653,25 @@ pub(crate) fn generate_query_name(
     format!("q_{:x}", hash(&(statement, schema_search_path)))
 }
 
+/// Returns the first keyword of `query`, skipping any leading whitespace and comments, for
+/// recognizing the kind of a statement we couldn't parse
+pub(crate) fn leading_keyword(query: &str) -> &str {
+    let mut rest = query.trim_start();
+    loop {
+        if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment.split_once("*/").map_or("", |(_, r)| r).trim_start();
+        } else if let Some(comment) = rest.strip_prefix("--").or_else(|| rest.strip_prefix('#')) {
+            rest = comment.split_once('\n').map_or("", |(_, r)| r).trim_start();
+        } else {
+            break;
+        }
+    }
+
+    rest.split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use nom_sql::{self, Dialect, SqlQuery};
@@ -857,6 +974,139 @@ mod tests {
         assert!(pc.is_empty());
     }
 
+    #[test]
+    fn base_table_point_lookups() {
+        let parse = |q| match nom_sql::parse_query(Dialect::MySQL, q).unwrap() {
+            SqlQuery::Select(s) => s,
+            _ => unreachable!(),
+        };
+
+        for q in [
+            "SELECT * FROM t WHERE id = 1",
+            "SELECT t.* FROM t WHERE t.id = 1",
+            "SELECT * FROM t AS t2 WHERE 1 = t2.id",
+        ] {
+            let query = parse(q);
+            let (table, column, value) = base_table_point_lookup(&query).unwrap();
+            assert_eq!(*table, Relation::from("t"), "{}", q);
+            assert_eq!(column.name, "id", "{}", q);
+            assert_eq!(*value, Literal::UnsignedInteger(1), "{}", q);
+        }
+
+        let query = parse("SELECT * FROM t WHERE id = ?");
+        let (_, column, value) = base_table_point_lookup(&query).unwrap();
+        assert_eq!(column.name, "id");
+        assert!(matches!(value, Literal::Placeholder(_)));
+
+        for q in [
+            "SELECT a FROM t WHERE id = 1",
+            "SELECT * FROM t WHERE id > 1",
+            "SELECT * FROM t WHERE id = 1 AND a = 2",
+            "SELECT * FROM t WHERE u.id = 1",
+            "SELECT * FROM t JOIN u ON t.id = u.id WHERE t.id = 1",
+            "SELECT * FROM t WHERE id = 1 LIMIT 1",
+        ] {
+            assert!(base_table_point_lookup(&parse(q)).is_none(), "{}", q);
+        }
+    }
+
+    #[test]
+    fn coerce_params_wrong_parameter_count() {
+        let schema = get_schema("CREATE TABLE t (a int, b text, PRIMARY KEY(a))");
+        let insert =
+            nom_sql::parse_query(Dialect::MySQL, "INSERT INTO t (a, b) VALUES (?, ?)").unwrap();
+        let update =
+            nom_sql::parse_query(Dialect::MySQL, "UPDATE t SET b = ? WHERE a = ?").unwrap();
+
+        for q in [&insert, &update] {
+            let too_few = coerce_params(
+                Some(&[DfValue::from(1)][..]),
+                q,
+                &schema,
+                readyset_data::Dialect::DEFAULT_MYSQL,
+                true,
+                &mut vec![],
+            );
+            assert_eq!(
+                too_few.unwrap_err(),
+                ReadySetError::WrongParameterCount {
+                    expected: 2,
+                    got: 1
+                }
+            );
+
+            let too_many = coerce_params(
+                Some(&[DfValue::from(1), DfValue::from("x"), DfValue::from(3)][..]),
+                q,
+                &schema,
+                readyset_data::Dialect::DEFAULT_MYSQL,
+                true,
+                &mut vec![],
+            );
+            assert_eq!(
+                too_many.unwrap_err(),
+                ReadySetError::WrongParameterCount {
+                    expected: 2,
+                    got: 3
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn coerce_params_too_long() {
+        let schema = get_schema("CREATE TABLE t (a int, b varchar(3), PRIMARY KEY(a))");
+        let insert =
+            nom_sql::parse_query(Dialect::MySQL, "INSERT INTO t (a, b) VALUES (?, ?)").unwrap();
+        let params = [DfValue::from(1), DfValue::from("abcdef")];
+
+        let strict = coerce_params(
+            Some(&params[..]),
+            &insert,
+            &schema,
+            readyset_data::Dialect::DEFAULT_MYSQL,
+            true,
+            &mut vec![],
+        );
+        assert_eq!(
+            strict.unwrap_err(),
+            ReadySetError::DataTooLong {
+                column: "b".into(),
+                max_length: 3
+            }
+        );
+
+        let mut truncated_columns = vec![];
+        let non_strict = coerce_params(
+            Some(&params[..]),
+            &insert,
+            &schema,
+            readyset_data::Dialect::DEFAULT_MYSQL,
+            false,
+            &mut truncated_columns,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(non_strict, vec![DfValue::from(1), DfValue::from("abc")]);
+        assert_eq!(truncated_columns, vec!["b".to_owned()]);
+
+        // Values that fit are left alone in either mode
+        for strict in [true, false] {
+            let res = coerce_params(
+                Some(&[DfValue::from(1), DfValue::from("abc")][..]),
+                &insert,
+                &schema,
+                readyset_data::Dialect::DEFAULT_MYSQL,
+                strict,
+                &mut truncated_columns,
+            )
+            .unwrap()
+            .unwrap();
+            assert_eq!(res, vec![DfValue::from(1), DfValue::from("abc")]);
+            assert_eq!(truncated_columns, vec!["b".to_owned()]);
+        }
+    }
+
     #[test]
     fn test_update_parameter_columns() {
         let update = "UPDATE t SET a = ? WHERE b = ?";
@@ -1,11 +1,13 @@
 #![deny(macro_use_extern_crate)]
 
+mod query_event_log;
 mod query_logger;
 
 use std::collections::HashMap;
 use std::io;
 use std::marker::Send;
 use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::atomic::AtomicUsize;
 use std::sync::{Arc, Mutex, RwLock};
@@ -28,22 +30,29 @@ use readyset::consensus::{AuthorityControl, AuthorityType, ConsulAuthority};
 use readyset::failpoints;
 use readyset::metrics::recorded;
 use readyset::{ReadySetError, ReadySetHandle, ViewCreateRequest};
+use readyset_adapter::auth::{AuthBackend, HttpAuthBackend, StaticUsers};
 use readyset_adapter::backend::noria_connector::{NoriaConnector, ReadBehavior};
 use readyset_adapter::backend::MigrationMode;
+use readyset_adapter::controller_health::ControllerHealth;
 use readyset_adapter::fallback_cache::{
     DiskModeledCache, EvictionModeledCache, FallbackCache, SimpleFallbackCache,
 };
+use readyset_adapter::feature_flags::FeatureFlags;
 use readyset_adapter::http_router::NoriaAdapterHttpRouter;
 use readyset_adapter::migration_handler::MigrationHandler;
 use readyset_adapter::proxied_queries_reporter::ProxiedQueriesReporter;
 use readyset_adapter::query_status_cache::{MigrationStyle, QueryStatusCache};
+use readyset_adapter::upstream_health::UpstreamHealth;
 use readyset_adapter::views_synchronizer::ViewsSynchronizer;
-use readyset_adapter::{Backend, BackendBuilder, QueryHandler, UpstreamDatabase};
+use readyset_adapter::{
+    preload, AuditLog, Backend, BackendBuilder, QueryHandler, UpstreamDatabase,
+};
 use readyset_dataflow::Readers;
 use readyset_server::metrics::{CompositeMetricsRecorder, MetricsRecorder};
 use readyset_server::worker::readers::{retry_misses, Ack, BlockingRead, ReadRequestHandler};
 use readyset_telemetry_reporter::{TelemetryBuilder, TelemetryEvent, TelemetryInitializer};
 use readyset_version::*;
+use socket2::{SockRef, TcpKeepalive};
 use stream_cancel::Valve;
 use tokio::net;
 use tokio::net::UdpSocket;
@@ -52,6 +61,8 @@ use tokio_stream::wrappers::TcpListenerStream;
 use tracing::{debug, debug_span, error, info, span, warn, Level};
 use tracing_futures::Instrument;
 
+use crate::query_event_log::QueryEventLog;
+
 // How frequently to try to establish an http registration for the first time or if the last tick
 // failed and we need to establish a new one
 const REGISTER_HTTP_INIT_INTERVAL: Duration = Duration::from_secs(2);
@@ -65,6 +76,12 @@ const AWS_METADATA_TOKEN_ENDPOINT: &str = "http://169.254.169.254/latest/api/tok
 /// Timeout to use when connecting to the upstream database
 const UPSTREAM_CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// How frequently to check whether the controller is reachable
+const CONTROLLER_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How frequently to check whether the upstream database is reachable, if one is configured
+const UPSTREAM_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
 #[async_trait]
 pub trait ConnectionHandler {
     type UpstreamDatabase: UpstreamDatabase;
@@ -130,6 +147,124 @@ impl From<UnsupportedSetMode> for readyset_adapter::backend::UnsupportedSetMode
     }
 }
 
+/// How to behave when receiving `SELECT` statements that ReadySet can't support.
+///
+/// Corresponds to the variants of [`readyset_adapter::backend::UnsupportedQueryMode`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UnsupportedQueryMode {
+    /// Return an error to the client
+    Error,
+    /// Proxy the query to the upstream database (the default)
+    Proxy,
+    /// Proxy the query to the upstream database, and log a warning that it was proxied
+    LogAndProxy,
+}
+
+impl Default for UnsupportedQueryMode {
+    fn default() -> Self {
+        Self::Proxy
+    }
+}
+
+impl FromStr for UnsupportedQueryMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(Self::Error),
+            "proxy" => Ok(Self::Proxy),
+            "log-and-proxy" => Ok(Self::LogAndProxy),
+            _ => bail!(
+                "Invalid value for unsupported_query_mode; expected one of \"error\", \"proxy\" \
+                 or \"log-and-proxy\""
+            ),
+        }
+    }
+}
+
+impl From<UnsupportedQueryMode> for readyset_adapter::backend::UnsupportedQueryMode {
+    fn from(mode: UnsupportedQueryMode) -> Self {
+        match mode {
+            UnsupportedQueryMode::Error => Self::Error,
+            UnsupportedQueryMode::Proxy => Self::Proxy,
+            UnsupportedQueryMode::LogAndProxy => Self::LogAndProxy,
+        }
+    }
+}
+
+/// How to behave when an `UPDATE` or `DELETE` statement executed against ReadySet doesn't affect
+/// any rows.
+///
+/// Corresponds to the variants of [`readyset_adapter::backend::ZeroRowWriteMode`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ZeroRowWriteMode {
+    /// Return success, with an affected row count of 0 (the default)
+    Success,
+    /// Return success, and send the client a warning
+    Warn,
+    /// Return an error to the client
+    Error,
+}
+
+impl Default for ZeroRowWriteMode {
+    fn default() -> Self {
+        Self::Success
+    }
+}
+
+impl FromStr for ZeroRowWriteMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "success" => Ok(Self::Success),
+            "warn" => Ok(Self::Warn),
+            "error" => Ok(Self::Error),
+            _ => bail!(
+                "Invalid value for zero_row_write_mode; expected one of \"success\", \"warn\" \
+                 or \"error\""
+            ),
+        }
+    }
+}
+
+impl From<ZeroRowWriteMode> for readyset_adapter::backend::ZeroRowWriteMode {
+    fn from(mode: ZeroRowWriteMode) -> Self {
+        match mode {
+            ZeroRowWriteMode::Success => Self::Success,
+            ZeroRowWriteMode::Warn => Self::Warn,
+            ZeroRowWriteMode::Error => Self::Error,
+        }
+    }
+}
+
+/// Where to look up the users allowed to connect to the adapter
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AuthBackendType {
+    /// Allow only the user given by `--username` and `--password` (the default)
+    Static,
+    /// Look up users from the HTTP service at `--auth-service-url`
+    Http,
+}
+
+impl Default for AuthBackendType {
+    fn default() -> Self {
+        Self::Static
+    }
+}
+
+impl FromStr for AuthBackendType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "static" => Ok(Self::Static),
+            "http" => Ok(Self::Http),
+            _ => bail!("Invalid value for auth_backend; expected one of \"static\" or \"http\""),
+        }
+    }
+}
+
 pub struct NoriaAdapter<H>
 where
     H: ConnectionHandler,
@@ -219,6 +354,54 @@ pub struct Options {
     #[clap(long, env = "VALIDATE_QUERIES", requires("upstream-db-url"))]
     validate_queries: bool,
 
+    /// The level at which to log mismatches found between ReadySet and the upstream database when
+    /// validating queries with --validate-queries.
+    #[clap(long, env = "VALIDATION_MISMATCH_LOG_LEVEL", default_value = "warn")]
+    validation_mismatch_log_level: Level,
+
+    /// Reject all write statements (INSERT, UPDATE, and DELETE) with an error, rather than
+    /// executing them. Reads are still served from ReadySet, or proxied to the upstream database
+    /// if one is configured.
+    #[clap(long, env = "READ_ONLY")]
+    read_only: bool,
+
+    /// Provide read-your-writes consistency within each connection: after a connection writes to
+    /// the upstream database, its reads wait up to this many milliseconds for ReadySet to
+    /// replicate the write before being served from ReadySet, and are proxied to the upstream
+    /// database if it takes longer than that. Disabled if not set.
+    #[clap(long, env = "READ_YOUR_WRITES_TIMEOUT_MS", requires("upstream-db-url"))]
+    read_your_writes_timeout_ms: Option<u64>,
+
+    /// The maximum number of statements each connection can have prepared against ReadySet at
+    /// once. Once a connection reaches the limit, preparing another statement deallocates the
+    /// connection's least-recently-used prepared statement, which is transparently prepared
+    /// against ReadySet again if it's executed later. Unlimited if not set.
+    #[clap(long, env = "MAX_PREPARED_STATEMENTS")]
+    max_prepared_statements: Option<usize>,
+
+    /// Proxy all queries to the upstream database while the ReadySet controller is unreachable,
+    /// rather than returning errors, and resume serving queries from ReadySet once it's reachable
+    /// again.
+    #[clap(long, env = "FALLBACK_ON_CONTROLLER_LOSS", requires("upstream-db-url"))]
+    fallback_on_controller_loss: bool,
+
+    /// How long, in seconds, the controller must be continuously unreachable before the adapter
+    /// reports itself as not ready on `/readyz`, and before queries are proxied to the upstream
+    /// database with --fallback-on-controller-loss.
+    #[clap(long, env = "CONTROLLER_LOSS_GRACE_PERIOD", default_value = "10")]
+    controller_loss_grace_period: u64,
+
+    /// Return the rows from the shards that are available, rather than an error, when reading
+    /// from a sharded cache while some of its shards are unavailable. Reads that return partial
+    /// results are logged, and the warning is reported by `EXPLAIN LAST STATEMENT`.
+    #[clap(long, env = "ALLOW_PARTIAL_RESULTS")]
+    allow_partial_results: bool,
+
+    /// Specifies the interval in seconds at which to refresh the deployment's feature flags from
+    /// the controller.
+    #[clap(long, env = "FEATURE_FLAG_REFRESH_INTERVAL", default_value = "30")]
+    feature_flag_refresh_interval: u64,
+
     /// IP:PORT to host endpoint for scraping metrics from the adapter.
     #[clap(
         long,
@@ -238,6 +421,38 @@ pub struct Options {
     #[clap(long, env = "ALLOWED_PASSWORD", short = 'p')]
     password: Option<RedactedString>,
 
+    /// Configure where the users allowed to connect to the adapter are looked up. Ignored if
+    /// --allow-unauthenticated-connections is passed.
+    ///
+    /// The possible values are:
+    ///
+    /// * "static" (default) - allow only the user given by --username and --password
+    /// * "http" - look up users from the HTTP service at --auth-service-url. The service is sent a
+    ///   GET request with the username in the `user` query parameter, and should respond with a
+    ///   JSON body of the form `{"password_hash": "*..."}` containing the user's
+    ///   `mysql_native_password` hash (as returned by MySQL's `PASSWORD()` function), or with a
+    ///   404 if the user doesn't exist.
+    #[clap(
+        long,
+        env = "AUTH_BACKEND",
+        default_value = "static",
+        possible_values = &["static", "http"],
+        parse(try_from_str)
+    )]
+    auth_backend: AuthBackendType,
+
+    /// URL of the HTTP service to look up users from with --auth-backend http
+    #[clap(long, env = "AUTH_SERVICE_URL", required_if_eq("auth-backend", "http"))]
+    auth_service_url: Option<String>,
+
+    /// How long, in seconds, to cache users looked up with --auth-backend http
+    #[clap(long, env = "AUTH_CACHE_TTL", default_value = "60")]
+    auth_cache_ttl: u64,
+
+    /// Maximum number of users to cache with --auth-backend http
+    #[clap(long, env = "AUTH_CACHE_SIZE", default_value = "1024")]
+    auth_cache_size: usize,
+
     /// Enable recording and exposing Prometheus metrics
     #[clap(long, env = "PROMETHEUS_METRICS")]
     prometheus_metrics: bool,
@@ -253,6 +468,34 @@ pub struct Options {
     #[clap(long, hide = true, env = "QUERY_LOG_AD_HOC", requires = "query-log")]
     query_log_ad_hoc: bool,
 
+    /// Write every query execution event, as a line of JSON containing the (anonymized) query,
+    /// its execution durations and its number of cache misses, to the file at the given path.
+    #[clap(long, env = "QUERY_EVENT_LOG")]
+    query_event_log: Option<PathBuf>,
+
+    /// The size in bytes past which the file given by --query-event-log is rotated. The rotated
+    /// file is moved to the same path with a `.1` suffix, replacing any previously rotated file.
+    #[clap(
+        long,
+        env = "QUERY_EVENT_LOG_MAX_SIZE",
+        default_value = "104857600",
+        requires = "query-event-log"
+    )]
+    query_event_log_max_size: u64,
+
+    /// Append a line of JSON recording the time, client address, user, and (redacted) text of
+    /// every DDL statement processed by the adapter to the file at the given path.
+    #[clap(long, env = "AUDIT_LOG")]
+    audit_log: Option<PathBuf>,
+
+    /// Path to a file containing a list of semicolon-separated queries to create caches for on
+    /// startup, before accepting any connections.
+    ///
+    /// Each query can be either a `CREATE CACHE` statement or a bare `SELECT` statement. Queries
+    /// that fail to be cached are logged and skipped.
+    #[clap(long, env = "PRELOAD_QUERIES")]
+    preload_queries: Option<PathBuf>,
+
     /// Use the AWS EC2 metadata service to determine the external address of this noria adapter's
     /// http endpoint.
     #[clap(long)]
@@ -289,6 +532,40 @@ pub struct Options {
     )]
     unsupported_set_mode: UnsupportedSetMode,
 
+    /// Configure how ReadySet behaves when receiving SELECT statements that it can't cache, if an
+    /// upstream database is configured.
+    ///
+    /// The possible values are:
+    ///
+    /// * "proxy" (default) - proxy the query to the upstream database
+    /// * "log-and-proxy" - proxy the query to the upstream database, and log a warning
+    /// * "error" - return an error to the client
+    #[clap(
+        long,
+        env = "UNSUPPORTED_QUERY_MODE",
+        default_value = "proxy",
+        possible_values = &["error", "proxy", "log-and-proxy"],
+        parse(try_from_str)
+    )]
+    unsupported_query_mode: UnsupportedQueryMode,
+
+    /// Configure how ReadySet behaves when an UPDATE or DELETE statement executed against ReadySet
+    /// (when no upstream database is configured) doesn't affect any rows.
+    ///
+    /// The possible values are:
+    ///
+    /// * "success" (default) - return success, with an affected row count of 0
+    /// * "warn" - return success, and send the client a warning
+    /// * "error" - return an error to the client
+    #[clap(
+        long,
+        env = "ZERO_ROW_WRITE_MODE",
+        default_value = "success",
+        possible_values = &["success", "warn", "error"],
+        parse(try_from_str)
+    )]
+    zero_row_write_mode: ZeroRowWriteMode,
+
     // TODO(DAN): require explicit migrations
     /// Specifies the polling interval in seconds for requesting views from the Leader.
     #[clap(long, env = "OUTPUTS_POLLING_INTERVAL", default_value = "300")]
@@ -331,6 +608,15 @@ pub struct Options {
     #[clap(long, env = "NON_BLOCKING_READS")]
     non_blocking_reads: bool,
 
+    /// Disable Nagle's algorithm (set `TCP_NODELAY`) on accepted client connections.
+    #[clap(long, env = "TCP_NODELAY")]
+    tcp_nodelay: bool,
+
+    /// If set, enable TCP keepalive on accepted client connections, sending the first keepalive
+    /// probe after the connection has been idle for this many seconds.
+    #[clap(long, env = "TCP_KEEPALIVE_SECS")]
+    tcp_keepalive_secs: Option<u64>,
+
     /// Run ReadySet in standalone mode, running a readyset-server and readyset-mysql instance
     /// within this adapter.
     #[clap(long, env = "STANDALONE", conflicts_with = "embedded-readers")]
@@ -422,19 +708,23 @@ where
         let rt = tokio::runtime::Runtime::new()?;
         rt.block_on(async { options.tracing.init("adapter") })?;
         info!(?options, "Starting ReadySet adapter");
-        let users: &'static HashMap<String, String> = Box::leak(Box::new(
-            if !options.allow_unauthenticated_connections {
-                hashmap! {
-                    options.username.ok_or_else(|| {
-                        anyhow!("Must specify --username/-u unless --allow-unauthenticated-connections is passed")
-                    })? => options.password.map(|x| x.0).ok_or_else(|| {
-                        anyhow!("Must specify --password/-p unless --allow-unauthenticated-connections is passed")
-                    })?
-                }
-            } else {
-                HashMap::new()
-            },
-        ));
+        let auth_backend: Arc<dyn AuthBackend> = match options.auth_backend {
+            _ if options.allow_unauthenticated_connections => Arc::new(StaticUsers::default()),
+            AuthBackendType::Static => Arc::new(StaticUsers::new(hashmap! {
+                options.username.ok_or_else(|| {
+                    anyhow!("Must specify --username/-u unless --allow-unauthenticated-connections is passed")
+                })? => options.password.map(|x| x.0).ok_or_else(|| {
+                    anyhow!("Must specify --password/-p unless --allow-unauthenticated-connections is passed")
+                })?
+            })),
+            AuthBackendType::Http => Arc::new(HttpAuthBackend::new(
+                options.auth_service_url.ok_or_else(|| {
+                    anyhow!("Must specify --auth-service-url with --auth-backend http")
+                })?,
+                Duration::from_secs(options.auth_cache_ttl),
+                options.auth_cache_size,
+            )),
+        };
         info!(version = %VERSION_STR_ONELINE);
 
         let telemetry_sender = rt.block_on(async {
@@ -480,7 +770,7 @@ where
         let deployment = options.deployment.clone();
         let migration_request_timeout = options.migration_request_timeout_ms;
         let controller_request_timeout = options.controller_request_timeout_ms;
-        let rh = rt.block_on(async {
+        let mut rh = rt.block_on(async {
             let authority = authority
                 .to_authority(&authority_address, &deployment)
                 .await;
@@ -495,6 +785,7 @@ where
                 .await,
             )
         })?;
+        rh.set_allow_partial_results(options.allow_partial_results);
 
         rs_connect.in_scope(|| info!("ReadySetHandle created"));
 
@@ -563,10 +854,44 @@ where
 
         let (shutdown_sender, shutdown_recv) = tokio::sync::broadcast::channel(1);
 
+        let audit_log = options
+            .audit_log
+            .as_ref()
+            .map(|path| {
+                AuditLog::open(path)
+                    .map_err(|e| anyhow!("Could not open audit log {}: {}", path.display(), e))
+            })
+            .transpose()?;
+
+        let preload_queries = options
+            .preload_queries
+            .as_ref()
+            .map(|path| {
+                std::fs::read_to_string(path).map_err(|e| {
+                    anyhow!(
+                        "Could not read preload queries file {}: {}",
+                        path.display(),
+                        e
+                    )
+                })
+            })
+            .transpose()?;
+
+        let query_event_log = options
+            .query_event_log
+            .as_ref()
+            .map(|path| {
+                QueryEventLog::open(path, options.query_event_log_max_size).map_err(|e| {
+                    anyhow!("Could not open query event log {}: {}", path.display(), e)
+                })
+            })
+            .transpose()?;
+
         // Gate query log code path on the log flag existing.
-        let qlog_sender = if options.query_log {
+        let qlog_sender = if options.query_log || query_event_log.is_some() {
             rs_connect.in_scope(|| info!("Query logs are enabled. Spawning query logger"));
             let (qlog_sender, qlog_receiver) = tokio::sync::mpsc::unbounded_channel();
+            let record_metrics = options.query_log;
 
             let runtime = tokio::runtime::Builder::new_current_thread()
                 .enable_all()
@@ -579,7 +904,12 @@ where
                 .name("Query logger".to_string())
                 .stack_size(2 * 1024 * 1024) // Use the same value tokio is using
                 .spawn(move || {
-                    runtime.block_on(query_logger::QueryLogger::run(qlog_receiver, shutdown_recv));
+                    runtime.block_on(query_logger::QueryLogger::run(
+                        qlog_receiver,
+                        record_metrics,
+                        query_event_log,
+                        shutdown_recv,
+                    ));
                     runtime.shutdown_background();
                 })?;
 
@@ -634,6 +964,39 @@ where
 
         rs_connect.in_scope(|| info!(?migration_mode));
 
+        // Keep track of whether the controller is reachable, both to report the adapter's readiness
+        // and (with --fallback-on-controller-loss) to decide when to proxy queries upstream
+        let controller_health =
+            ControllerHealth::new(Duration::from_secs(options.controller_loss_grace_period));
+        {
+            rs_connect.in_scope(|| info!("Spawning controller health monitor task"));
+            let fut = controller_health.clone().monitor_loop(
+                rh.clone(),
+                CONTROLLER_HEALTH_CHECK_INTERVAL,
+                shutdown_sender.subscribe(),
+            );
+            rt.handle().spawn(fut);
+        }
+
+        // Keep track of whether the upstream database is reachable, to report the adapter's
+        // readiness
+        let upstream_health = options
+            .server_worker_options
+            .replicator_config
+            .upstream_db_url
+            .is_some()
+            .then(|| {
+                let upstream_health = UpstreamHealth::new();
+                rs_connect.in_scope(|| info!("Spawning upstream health monitor task"));
+                let fut = upstream_health.clone().monitor_loop::<H::UpstreamDatabase>(
+                    options.server_worker_options.replicator_config.clone(),
+                    UPSTREAM_HEALTH_CHECK_INTERVAL,
+                    shutdown_sender.subscribe(),
+                );
+                rt.handle().spawn(fut);
+                upstream_health
+            });
+
         // Spawn a task for handling this adapter's HTTP request server.
         // This step is done as the last thing before accepting connections because it is used as
         // the health check for the service.
@@ -652,6 +1015,8 @@ where
                 valve,
                 prometheus_handle,
                 health_reporter: health_reporter.clone(),
+                controller_health: controller_health.clone(),
+                upstream_health,
                 failpoint_channel: tx,
             };
 
@@ -718,6 +1083,7 @@ where
             let loop_interval = options.migration_task_interval;
             let max_retry = options.max_processing_minutes;
             let validate_queries = options.validate_queries;
+            let validation_mismatch_log_level = options.validation_mismatch_log_level;
             let dry_run = matches!(migration_style, MigrationStyle::Explicit);
             let upstream_config = options.server_worker_options.replicator_config.clone();
             let expr_dialect = self.expr_dialect;
@@ -776,6 +1142,7 @@ where
                     query_status_cache,
                     expr_dialect,
                     validate_queries,
+                    validation_mismatch_log_level,
                     std::time::Duration::from_millis(loop_interval),
                     std::time::Duration::from_secs(max_retry * 60),
                     shutdown_recv,
@@ -888,10 +1255,85 @@ where
 
         rs_connect.in_scope(|| info!(supported = %server_supports_pagination));
 
+        // Load the deployment's feature flags before we start accepting connections, then keep
+        // them up to date in the background so that flipping a flag doesn't require a restart
+        let feature_flags = FeatureFlags::new();
+        {
+            let mut rh = rh.clone();
+            if let Err(error) = rt.block_on(feature_flags.refresh(&mut rh)) {
+                rs_connect.in_scope(|| warn!(%error, "Failed to load feature flags"));
+            }
+            rs_connect.in_scope(|| info!("Spawning feature flag refresh task"));
+            let fut = feature_flags.clone().refresh_loop(
+                rh,
+                Duration::from_secs(options.feature_flag_refresh_interval),
+                shutdown_sender.subscribe(),
+            );
+            rt.handle().spawn(fut);
+        }
+
+        // Only fall back to the upstream database on controller loss if we've been asked to; the
+        // controller's health is always reported by the HTTP router's readiness check
+        let fallback_controller_health = options
+            .fallback_on_controller_loss
+            .then(|| controller_health.clone());
+
+        // Create caches for any preloaded queries before we start accepting connections, so that
+        // the caches already exist by the time clients connect
+        if let Some(queries) = preload_queries {
+            let rh = rh.clone();
+            let (auto_increments, query_cache) = (auto_increments.clone(), query_cache.clone());
+            let upstream_config = upstream_config.clone();
+            let fallback_cache = fallback_cache.clone();
+            let (parse_dialect, expr_dialect) = (self.parse_dialect, self.expr_dialect);
+            let fut = async move {
+                let schema_search_path = if upstream_config.upstream_db_url.is_some() {
+                    let ssp_res = async {
+                        H::UpstreamDatabase::connect(upstream_config, fallback_cache)
+                            .await?
+                            .schema_search_path()
+                            .await
+                    }
+                    .await;
+                    match ssp_res {
+                        Ok(ssp) => ssp,
+                        Err(error) => {
+                            warn!(%error, "Could not load schema search path from upstream");
+                            return;
+                        }
+                    }
+                } else {
+                    Default::default()
+                };
+
+                let mut noria = NoriaConnector::new(
+                    rh,
+                    auto_increments,
+                    query_cache,
+                    noria_read_behavior,
+                    expr_dialect,
+                    schema_search_path,
+                    server_supports_pagination,
+                )
+                .await;
+
+                preload::preload_queries(&mut noria, query_status_cache, parse_dialect, &queries)
+                    .await;
+            };
+            rt.block_on(fut.instrument(span!(Level::INFO, "Preloading queries")));
+        }
+
         let expr_dialect = self.expr_dialect;
         while let Some(Ok(s)) = rt.block_on(listener.next()) {
             let connection = span!(Level::DEBUG, "connection", addr = ?s.peer_addr().unwrap());
             connection.in_scope(|| info!("Accepted new connection"));
+            if let Err(error) = configure_client_socket(
+                &s,
+                options.tcp_nodelay,
+                options.tcp_keepalive_secs.map(Duration::from_secs),
+            ) {
+                connection.in_scope(|| warn!(%error, "Failed to configure client socket"));
+            }
 
             // bunch of stuff to move into the async block below
             let rh = rh.clone();
@@ -899,20 +1341,34 @@ where
             let mut connection_handler = self.connection_handler.clone();
             let backend_builder = BackendBuilder::new()
                 .slowlog(options.log_slow)
-                .users(users.clone())
+                .auth_backend(auth_backend.clone())
                 .require_authentication(!options.allow_unauthenticated_connections)
                 .dialect(self.parse_dialect)
                 .query_log(qlog_sender.clone(), options.query_log_ad_hoc)
                 .validate_queries(options.validate_queries, options.fail_invalidated_queries)
+                .validation_mismatch_log_level(options.validation_mismatch_log_level)
                 .unsupported_set_mode(if options.allow_unsupported_set {
                     readyset_adapter::backend::UnsupportedSetMode::Allow
                 } else {
                     options.unsupported_set_mode.into()
                 })
+                .unsupported_query_mode(options.unsupported_query_mode.into())
+                .zero_row_write_mode(options.zero_row_write_mode.into())
                 .migration_mode(migration_mode)
                 .query_max_failure_seconds(options.query_max_failure_seconds)
                 .telemetry_sender(telemetry_sender.clone())
-                .fallback_recovery_seconds(options.fallback_recovery_seconds);
+                .fallback_recovery_seconds(options.fallback_recovery_seconds)
+                .read_only(options.read_only)
+                .read_your_writes_timeout(
+                    options
+                        .read_your_writes_timeout_ms
+                        .map(Duration::from_millis),
+                )
+                .feature_flags(feature_flags.clone())
+                .controller_health(fallback_controller_health.clone())
+                .max_prepared_statements(options.max_prepared_statements)
+                .audit_log(audit_log.clone())
+                .client_addr(s.peer_addr().ok());
             let telemetry_sender = telemetry_sender.clone();
 
             // Initialize the reader layer for the adapter.
@@ -968,9 +1424,21 @@ where
                             Ok(Default::default())
                         };
 
+                        // Default to the upstream's strict mode setting, so that values too long
+                        // for their column are handled the same way they would be upstream
+                        let strict_mode = match &mut upstream {
+                            Some(upstream) => {
+                                upstream.strict_mode().await.unwrap_or_else(|error| {
+                                    warn!(%error, "Failed to load strict mode from upstream");
+                                    true
+                                })
+                            }
+                            None => true,
+                        };
+
                         match schema_search_path_res {
                             Ok(ssp) => {
-                                let noria = NoriaConnector::new_with_local_reads(
+                                let mut noria = NoriaConnector::new_with_local_reads(
                                     rh.clone(),
                                     auto_increments.clone(),
                                     query_cache.clone(),
@@ -982,6 +1450,7 @@ where
                                 )
                                 .instrument(debug_span!("Building noria connector"))
                                 .await;
+                                noria.set_strict_mode(strict_mode);
 
                                 let backend = backend_builder.clone().build(
                                     noria,
@@ -1062,6 +1531,21 @@ where
     }
 }
 
+/// Apply the configured TCP socket options to a newly accepted client connection.
+fn configure_client_socket(
+    stream: &net::TcpStream,
+    nodelay: bool,
+    keepalive: Option<Duration>,
+) -> io::Result<()> {
+    if nodelay {
+        stream.set_nodelay(true)?;
+    }
+    if let Some(time) = keepalive {
+        SockRef::from(stream).set_tcp_keepalive(&TcpKeepalive::new().with_time(time))?;
+    }
+    Ok(())
+}
+
 async fn check_server_version_compatibility(rh: &mut ReadySetHandle) -> anyhow::Result<()> {
     let server_version = rh.version().await?;
     debug!(server_version);
@@ -1253,4 +1737,35 @@ mod tests {
         assert_eq!(opts.max_processing_minutes, 15);
         assert_eq!(opts.migration_task_interval, 20000);
     }
+
+    #[test]
+    fn tcp_option_parsing() {
+        let opts = Options::parse_from(vec![
+            "noria-mysql",
+            "--deployment",
+            "test",
+            "--allow-unauthenticated-connections",
+            "--tcp-nodelay",
+            "--tcp-keepalive-secs",
+            "30",
+        ]);
+
+        assert!(opts.tcp_nodelay);
+        assert_eq!(opts.tcp_keepalive_secs, Some(30));
+    }
+
+    #[tokio::test]
+    async fn configure_client_socket_sets_options() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let _client = net::TcpStream::connect(addr).await.unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+
+        configure_client_socket(&stream, true, Some(Duration::from_secs(30))).unwrap();
+
+        assert!(stream.nodelay().unwrap());
+        let sock = SockRef::from(&stream);
+        assert!(sock.keepalive().unwrap());
+        assert_eq!(sock.keepalive_time().unwrap(), Duration::from_secs(30));
+    }
 }
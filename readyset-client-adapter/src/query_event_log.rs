@@ -0,0 +1,221 @@
+//! Persisting raw query execution events to a file for offline analysis.
+//!
+//! When the adapter is started with `--query-event-log <path>`, every [`QueryExecutionEvent`]
+//! received by the query logger is appended to the file at that path as a single line of JSON.
+//! Once writing an event would take the file past the configured maximum size, the file is
+//! rotated: it's renamed to `<path>.1` (replacing any previously rotated file), and a new, empty
+//! file is started at `<path>`.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{SecondsFormat, Utc};
+use readyset::query::QueryId;
+use readyset_client_metrics::{EventType, QueryDestination, QueryExecutionEvent, SqlQueryType};
+use readyset_sql_passes::anonymize::anonymize_query_literals;
+use serde::Serialize;
+use tracing::warn;
+
+/// A single line in the query event log
+#[derive(Debug, Serialize)]
+struct QueryEventLogEntry {
+    /// The time at which the event was written, in RFC 3339 format
+    timestamp: String,
+    event_type: EventType,
+    query_type: SqlQueryType,
+    /// The query the event is for, with all literals anonymized
+    query: Option<String>,
+    query_id: Option<QueryId>,
+    destination: Option<QueryDestination>,
+    num_keys: Option<u64>,
+    cache_misses: Option<u64>,
+    rows_examined: Option<u64>,
+    parse_duration_us: Option<u64>,
+    upstream_duration_us: Option<u64>,
+    readyset_duration_us: Option<u64>,
+    /// The error returned by ReadySet, if any
+    readyset_error: Option<String>,
+}
+
+impl From<&QueryExecutionEvent> for QueryEventLogEntry {
+    fn from(event: &QueryExecutionEvent) -> Self {
+        let micros = |d: std::time::Duration| d.as_micros() as u64;
+        Self {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true),
+            event_type: event.event,
+            query_type: event.sql_type,
+            query: event.query.as_ref().map(|query| {
+                let mut query = (**query).clone();
+                anonymize_query_literals(&mut query);
+                query.to_string()
+            }),
+            query_id: event.query_id,
+            destination: event.destination,
+            num_keys: event.num_keys,
+            cache_misses: event.cache_misses,
+            rows_examined: event.rows_examined,
+            parse_duration_us: event.parse_duration.map(micros),
+            upstream_duration_us: event.upstream_duration.map(micros),
+            readyset_duration_us: event.readyset_duration.map(micros),
+            readyset_error: event.noria_error.as_ref().map(|e| e.to_string()),
+        }
+    }
+}
+
+/// An append-only file of newline-delimited JSON query execution events, rotated by size
+pub(crate) struct QueryEventLog {
+    path: PathBuf,
+    file: File,
+    /// The current size of `file`, in bytes
+    size: u64,
+    /// The size, in bytes, past which the file is rotated
+    max_size: u64,
+}
+
+impl QueryEventLog {
+    /// Open the query event log at the given path for appending, creating it if it doesn't exist.
+    /// The file will be rotated once it would grow past `max_size` bytes.
+    pub(crate) fn open<P: AsRef<Path>>(path: P, max_size: u64) -> io::Result<Self> {
+        let path = path.as_ref().to_owned();
+        let file = Self::open_file(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            file,
+            size,
+            max_size,
+        })
+    }
+
+    fn open_file(path: &Path) -> io::Result<File> {
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+
+    /// Returns the path that the log is moved to when it's rotated
+    fn rotated_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".1");
+        path.into()
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        fs::rename(&self.path, self.rotated_path())?;
+        self.file = Self::open_file(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    fn write_entry(&mut self, event: &QueryExecutionEvent) -> io::Result<()> {
+        let mut line = serde_json::to_vec(&QueryEventLogEntry::from(event))?;
+        line.push(b'\n');
+
+        // Never rotate an empty file, so that a single event larger than the maximum size still
+        // gets written
+        if self.size != 0 && self.size + line.len() as u64 > self.max_size {
+            self.rotate()?;
+        }
+
+        self.file.write_all(&line)?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    /// Append a line for the given event to the log, rotating it first if necessary.
+    ///
+    /// Failures to write to the log are logged, but otherwise ignored.
+    pub(crate) fn record(&mut self, event: &QueryExecutionEvent) {
+        if let Err(error) = self.write_entry(event) {
+            warn!(%error, path = %self.path.display(), "Failed to write to query event log");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use nom_sql::{parse_query, Dialect};
+    use readyset::internal::IndexType;
+    use readyset_client_metrics::QueryTags;
+
+    use super::*;
+
+    fn event() -> QueryExecutionEvent {
+        QueryExecutionEvent {
+            event: EventType::Query,
+            sql_type: SqlQueryType::Read,
+            query: Some(Arc::new(
+                parse_query(Dialect::MySQL, "SELECT a FROM t WHERE b = 'secret'").unwrap(),
+            )),
+            query_id: None,
+            num_keys: Some(1),
+            index_type: Some(IndexType::HashMap),
+            key_width: Some(1),
+            parse_duration: Some(Duration::from_micros(10)),
+            upstream_duration: None,
+            readyset_duration: Some(Duration::from_micros(250)),
+            noria_error: None,
+            destination: Some(QueryDestination::Readyset),
+            cache_misses: Some(1),
+            rows_examined: Some(3),
+            unavailable_shards: None,
+            rows_affected: None,
+            truncated_columns: Vec::new(),
+            tags: QueryTags::default(),
+        }
+    }
+
+    fn lines(path: &Path) -> Vec<serde_json::Value> {
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn events_are_written_as_ndjson() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.log");
+        let mut log = QueryEventLog::open(&path, u64::MAX).unwrap();
+        log.record(&event());
+        log.record(&event());
+
+        let lines = lines(&path);
+        assert_eq!(lines.len(), 2);
+        let entry = &lines[0];
+        assert_eq!(entry["event_type"], "Query");
+        assert_eq!(entry["query_type"], "Read");
+        assert_eq!(entry["destination"], "Readyset");
+        assert_eq!(entry["cache_misses"], 1);
+        assert_eq!(entry["rows_examined"], 3);
+        assert_eq!(entry["parse_duration_us"], 10);
+        assert_eq!(entry["readyset_duration_us"], 250);
+        assert!(entry["upstream_duration_us"].is_null());
+        let query = entry["query"].as_str().unwrap();
+        assert!(query.contains("<anonymized>"), "{query}");
+        assert!(!query.contains("secret"), "{query}");
+    }
+
+    #[test]
+    fn rotates_at_size_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.log");
+        let mut log = QueryEventLog::open(&path, u64::MAX).unwrap();
+        log.record(&event());
+        let line_len = fs::metadata(&path).unwrap().len();
+
+        // Room for two events, but not three
+        let mut log = QueryEventLog::open(&path, line_len * 5 / 2).unwrap();
+        log.record(&event());
+        assert_eq!(lines(&path).len(), 2);
+        assert!(!log.rotated_path().exists());
+
+        log.record(&event());
+        assert_eq!(lines(&log.rotated_path()).len(), 2);
+        assert_eq!(lines(&path).len(), 1);
+    }
+}
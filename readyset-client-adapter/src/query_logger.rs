@@ -1,11 +1,13 @@
 use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
-use metrics::{register_counter, register_histogram, Counter, Histogram, SharedString};
+use metrics::{register_counter, register_histogram, Counter, Histogram, Label, SharedString};
 use nom_sql::SqlQuery;
+use readyset::internal::IndexType;
 use readyset::query::QueryId;
 use readyset_client_metrics::{
-    recorded, DatabaseType, EventType, QueryExecutionEvent, SqlQueryType,
+    recorded, DatabaseType, EventType, QueryExecutionEvent, QueryTags, SqlQueryType,
+    TagValueLimiter,
 };
 use readyset_sql_passes::anonymize::anonymize_literals;
 use tokio::select;
@@ -13,9 +15,13 @@ use tokio::sync::broadcast;
 use tokio::sync::mpsc::UnboundedReceiver;
 use tracing::{info, info_span};
 
+use crate::query_event_log::QueryEventLog;
+
 pub(crate) struct QueryLogger {
     per_id_metrics: BTreeMap<QueryId, QueryMetrics>,
     per_query_metrics: HashMap<Arc<SqlQuery>, QueryMetrics>,
+    /// Bounds the values of the tags used as labels on the histograms
+    tag_limiter: TagValueLimiter,
 }
 
 struct QueryMetrics {
@@ -24,7 +30,25 @@ struct QueryMetrics {
     num_keys: Counter,
     cache_misses: Counter,
     cache_keys_missed: Counter,
-    histograms: BTreeMap<(EventType, SqlQueryType), QueryHistograms>,
+    rows_examined: Counter,
+    histograms: BTreeMap<HistogramKind, QueryHistograms>,
+}
+
+/// The labels that distinguish the histograms recorded for a single query: the event type, the
+/// query type, the tags attached to the query, and the type and width of the index the query was
+/// looked up in, if it was run against ReadySet
+type HistogramKind = (
+    EventType,
+    SqlQueryType,
+    QueryTags,
+    Option<(IndexType, usize)>,
+);
+
+fn index_type_label(index_type: IndexType) -> SharedString {
+    match index_type {
+        IndexType::HashMap => SharedString::const_str("hash_map"),
+        IndexType::BTreeMap => SharedString::const_str("btree_map"),
+    }
 }
 
 #[derive(Default)]
@@ -35,62 +59,73 @@ struct QueryHistograms {
 }
 
 impl QueryMetrics {
-    fn parse_histogram(&mut self, kind: (EventType, SqlQueryType)) -> &mut Histogram {
+    fn parse_histogram(&mut self, kind: HistogramKind) -> &mut Histogram {
+        let (event_type, query_type, tags) = (kind.0, kind.1, kind.2.labels());
         self.histograms
             .entry(kind)
             .or_default()
             .parse_time
             .get_or_insert_with(|| {
                 let mut labels = vec![
-                    ("query", self.query.clone()),
-                    ("event_type", SharedString::from(kind.0)),
-                    ("query_type", SharedString::from(kind.1)),
+                    Label::new("query", self.query.clone()),
+                    Label::new("event_type", SharedString::from(event_type)),
+                    Label::new("query_type", SharedString::from(query_type)),
                 ];
+                labels.extend(tags);
 
                 if let Some(id) = &self.query_id {
-                    labels.push(("query_id", id.clone()));
+                    labels.push(Label::new("query_id", id.clone()));
                 }
 
                 register_histogram!(recorded::QUERY_LOG_PARSE_TIME, &labels)
             })
     }
 
-    fn readyset_histogram(&mut self, kind: (EventType, SqlQueryType)) -> &mut Histogram {
+    fn readyset_histogram(&mut self, kind: HistogramKind) -> &mut Histogram {
+        let (event_type, query_type, tags, index) = (kind.0, kind.1, kind.2.labels(), kind.3);
         self.histograms
             .entry(kind)
             .or_default()
             .readyset_exe_time
             .get_or_insert_with(|| {
                 let mut labels = vec![
-                    ("query", self.query.clone()),
-                    ("event_type", SharedString::from(kind.0)),
-                    ("query_type", SharedString::from(kind.1)),
-                    ("database_type", SharedString::from(DatabaseType::ReadySet)),
+                    Label::new("query", self.query.clone()),
+                    Label::new("event_type", SharedString::from(event_type)),
+                    Label::new("query_type", SharedString::from(query_type)),
+                    Label::new("database_type", SharedString::from(DatabaseType::ReadySet)),
                 ];
+                labels.extend(tags);
+
+                if let Some((index_type, key_width)) = index {
+                    labels.push(Label::new("index_type", index_type_label(index_type)));
+                    labels.push(Label::new("key_width", key_width.to_string()));
+                }
 
                 if let Some(id) = &self.query_id {
-                    labels.push(("query_id", id.clone()));
+                    labels.push(Label::new("query_id", id.clone()));
                 }
 
                 register_histogram!(recorded::QUERY_LOG_EXECUTION_TIME, &labels)
             })
     }
 
-    fn upstream_histogram(&mut self, kind: (EventType, SqlQueryType)) -> &mut Histogram {
+    fn upstream_histogram(&mut self, kind: HistogramKind) -> &mut Histogram {
+        let (event_type, query_type, tags) = (kind.0, kind.1, kind.2.labels());
         self.histograms
             .entry(kind)
             .or_default()
             .upstream_exe_time
             .get_or_insert_with(|| {
                 let mut labels = vec![
-                    ("query", self.query.clone()),
-                    ("event_type", SharedString::from(kind.0)),
-                    ("query_type", SharedString::from(kind.1)),
-                    ("database_type", SharedString::from(DatabaseType::MySql)),
+                    Label::new("query", self.query.clone()),
+                    Label::new("event_type", SharedString::from(event_type)),
+                    Label::new("query_type", SharedString::from(query_type)),
+                    Label::new("database_type", SharedString::from(DatabaseType::MySql)),
                 ];
+                labels.extend(tags);
 
                 if let Some(id) = &self.query_id {
-                    labels.push(("query_id", id.clone()));
+                    labels.push(Label::new("query_id", id.clone()));
                 }
 
                 register_histogram!(recorded::QUERY_LOG_EXECUTION_TIME, &labels)
@@ -135,6 +170,11 @@ impl QueryLogger {
                     "query" => query_string.clone(),
                     "query_id" => query_id.clone(),
                 ),
+                rows_examined: register_counter!(
+                    recorded::QUERY_LOG_TOTAL_ROWS_EXAMINED,
+                    "query" => query_string.clone(),
+                    "query_id" => query_id.clone(),
+                ),
                 query: query_string,
                 query_id: Some(query_id),
                 histograms: BTreeMap::new(),
@@ -161,6 +201,10 @@ impl QueryLogger {
                         readyset_client_metrics::recorded::QUERY_LOG_TOTAL_CACHE_MISSES,
                         "query" => query_string.clone(),
                     ),
+                    rows_examined: register_counter!(
+                        readyset_client_metrics::recorded::QUERY_LOG_TOTAL_ROWS_EXAMINED,
+                        "query" => query_string.clone(),
+                    ),
                     query: query_string,
                     query_id: None,
                     histograms: BTreeMap::new(),
@@ -169,8 +213,13 @@ impl QueryLogger {
     }
 
     /// Async task that logs query stats.
+    ///
+    /// If `record_metrics` is true, each event is recorded in the per-query metrics, and if
+    /// `event_log` is set, each event is also written to that log.
     pub(crate) async fn run(
         mut receiver: UnboundedReceiver<QueryExecutionEvent>,
+        record_metrics: bool,
+        mut event_log: Option<QueryEventLog>,
         mut shutdown_recv: broadcast::Receiver<()>,
     ) {
         let _span = info_span!("query-logger");
@@ -178,6 +227,7 @@ impl QueryLogger {
         let mut logger = QueryLogger {
             per_query_metrics: HashMap::new(),
             per_id_metrics: BTreeMap::new(),
+            tag_limiter: TagValueLimiter::default(),
         };
 
         loop {
@@ -191,11 +241,20 @@ impl QueryLogger {
                         }
                     };
 
+                    if let Some(event_log) = &mut event_log {
+                        event_log.record(&event);
+                    }
+
+                    if !record_metrics {
+                        continue;
+                    }
+
                     let query = match event.query {
                         Some(query) => query,
                         None => continue,
                     };
 
+                    let tags = logger.tag_limiter.limit(&event.tags);
                     let metrics = if let Some(id) = event.query_id {
                         logger.metrics_for_id(id, query)
                     } else {
@@ -213,21 +272,31 @@ impl QueryLogger {
                         }
                     }
 
+                    if let Some(rows_examined) = event.rows_examined {
+                        metrics.rows_examined.increment(rows_examined);
+                    }
+
                     if let Some(duration) = event.parse_duration {
                         metrics
-                            .parse_histogram((event.event, event.sql_type))
+                            .parse_histogram((event.event, event.sql_type, tags.clone(), None))
                             .record(duration);
                     }
 
                     if let Some(duration) = event.readyset_duration {
+                        let index = event.index_type.zip(event.key_width);
                         metrics
-                            .readyset_histogram((event.event, event.sql_type))
+                            .readyset_histogram((
+                                event.event,
+                                event.sql_type,
+                                tags.clone(),
+                                index,
+                            ))
                             .record(duration);
                     }
 
                     if let Some(duration) = event.upstream_duration {
                         metrics
-                            .upstream_histogram((event.event, event.sql_type))
+                            .upstream_histogram((event.event, event.sql_type, tags.clone(), None))
                             .record(duration);
                     }
                 }
@@ -239,3 +308,56 @@ impl QueryLogger {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use metrics_util::debugging::DebuggingRecorder;
+    use nom_sql::{parse_query, Dialect};
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    fn tagged_event(query: &str) -> QueryExecutionEvent {
+        let mut event = QueryExecutionEvent::new(EventType::Query);
+        event.sql_type = SqlQueryType::Read;
+        event.query = Some(Arc::new(parse_query(Dialect::MySQL, query).unwrap()));
+        event.readyset_duration = Some(Duration::from_micros(250));
+        event.tags = QueryTags::from_leading_comment(query);
+        event
+    }
+
+    #[tokio::test]
+    async fn tagged_query_metrics_carry_tags() {
+        let recorder = DebuggingRecorder::per_thread();
+        let snapshotter = recorder.snapshotter();
+        recorder.install().unwrap();
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let (_shutdown_send, shutdown_recv) = broadcast::channel(1);
+        sender
+            .send(tagged_event("/* service=billing */ SELECT a FROM t"))
+            .unwrap();
+        sender
+            .send(tagged_event("/* 1st=x */ SELECT a FROM t"))
+            .unwrap();
+        drop(sender);
+        QueryLogger::run(receiver, true, None, shutdown_recv).await;
+
+        let mut tags = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter(|(key, ..)| key.key().name() == recorded::QUERY_LOG_EXECUTION_TIME)
+            .map(|(key, ..)| {
+                key.key()
+                    .labels()
+                    .find(|l| l.key() == "service")
+                    .map(|l| l.value().to_owned())
+            })
+            .collect::<Vec<_>>();
+        tags.sort();
+        assert_eq!(tags, vec![None, Some("billing".to_owned())]);
+    }
+}
@@ -5,12 +5,16 @@ use std::time::{Duration, Instant};
 
 use metrics::SharedString;
 use nom_sql::SqlQuery;
+use readyset::internal::IndexType;
 use readyset::query::QueryId;
 use readyset::ReadySetError;
 use serde::Serialize;
 
+mod query_tags;
 pub mod recorded;
 
+pub use query_tags::{QueryTags, TagValueLimiter};
+
 #[derive(Debug, Serialize, Clone)]
 /// Event logging for the execution of a single query in the adapter. Durations
 /// logged should be mirrored by an update to `QueryExecutionTimerHandle`.
@@ -27,6 +31,14 @@ pub struct QueryExecutionEvent {
     /// The number of keys that were read
     pub num_keys: Option<u64>,
 
+    /// The type of index that was used to look up the keys in ReadySet, if the query was run on
+    /// ReadySet. A [`IndexType::BTreeMap`] index means the lookup was a range scan.
+    pub index_type: Option<IndexType>,
+
+    /// The number of columns in the key that was used to look up the query in ReadySet, if the
+    /// query was run on ReadySet
+    pub key_width: Option<usize>,
+
     /// How long the request spent in parsing.
     pub parse_duration: Option<Duration>,
 
@@ -44,6 +56,24 @@ pub struct QueryExecutionEvent {
 
     /// Number of cache misses which occurred as part of a query
     pub cache_misses: Option<u64>,
+
+    /// Number of rows read from ReadySet's caches to answer the query, before any filtering,
+    /// aggregation, or limits were applied. A rough measure of how much work the query did.
+    pub rows_examined: Option<u64>,
+
+    /// Number of shards of the cache that couldn't be read from, if the query returned partial
+    /// results
+    pub unavailable_shards: Option<u64>,
+
+    /// Number of rows affected by an `UPDATE` or `DELETE` statement executed against ReadySet
+    pub rows_affected: Option<u64>,
+
+    /// Columns whose values were truncated to fit when written by a statement executed against
+    /// ReadySet, because they were too long and strict mode was disabled
+    pub truncated_columns: Vec<String>,
+
+    /// Tags attached to the query by a leading comment, used as additional metric labels
+    pub tags: QueryTags,
 }
 
 #[derive(Debug, PartialEq, Eq, Serialize, Clone, Copy, Default)]
@@ -161,7 +191,14 @@ impl QueryExecutionEvent {
             noria_error: None,
             destination: None,
             cache_misses: None,
+            rows_examined: None,
+            unavailable_shards: None,
+            rows_affected: None,
+            truncated_columns: Vec::new(),
             num_keys: None,
+            index_type: None,
+            key_width: None,
+            tags: QueryTags::default(),
         }
     }
 
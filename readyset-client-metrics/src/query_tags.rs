@@ -0,0 +1,222 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use metrics::Label;
+use serde::Serialize;
+
+/// The maximum number of tags that will be extracted from a single query. Tags are used as metric
+/// labels, so both the number of tags and the length of their values are bounded to keep the
+/// number of distinct label sets under control.
+const MAX_TAGS: usize = 4;
+
+/// The maximum length, in bytes, of a tag key or value
+const MAX_TAG_LEN: usize = 64;
+
+/// The maximum number of distinct values a single tag key can take on in metric labels
+const MAX_TAG_VALUES: usize = 32;
+
+/// The value that replaces a tag's value in metric labels once its key has taken on
+/// [`MAX_TAG_VALUES`] other values
+const OTHER_TAG_VALUE: &str = "other";
+
+/// Label names already used by the query metrics, which can't be overridden by a tag
+const RESERVED_KEYS: &[&str] = &[
+    "query",
+    "query_id",
+    "event_type",
+    "query_type",
+    "database_type",
+    "index_type",
+    "key_width",
+];
+
+/// A set of `key=value` tags attached to a query by the application in a leading comment, such as
+/// `/* service=billing, team=payments */ SELECT ...`.
+///
+/// Tags are extracted by [`QueryTags::from_leading_comment`], and are attached to the query's
+/// tracing span and used as additional labels on the query's metrics.
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QueryTags(Vec<(String, String)>);
+
+impl QueryTags {
+    /// Extract the tags from the comment at the start of `query`, if any.
+    ///
+    /// Only a `/* ... */` comment that appears before any other (non-whitespace) text in the query
+    /// is considered. Within that comment, tags are separated by commas or whitespace, and each tag
+    /// must be of the form `key=value`. Keys must be valid metric label names (an ASCII letter or
+    /// `_`, followed by ASCII letters, digits or `_`), and values may consist only of alphanumeric
+    /// characters and any of `_`, `-`, `.` or `:`. Anything that doesn't look like a tag, or whose
+    /// key clashes with one of the labels already used by the query metrics, is ignored.
+    pub fn from_leading_comment(query: &str) -> Self {
+        let comment = match query
+            .trim_start()
+            .strip_prefix("/*")
+            .and_then(|rest| rest.split_once("*/"))
+        {
+            Some((comment, _)) => comment,
+            None => return Self::default(),
+        };
+
+        let is_valid_key = |s: &str| {
+            s.len() <= MAX_TAG_LEN
+                && s.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && !RESERVED_KEYS.contains(&s)
+        };
+        let is_valid_value = |s: &str| {
+            !s.is_empty()
+                && s.len() <= MAX_TAG_LEN
+                && s.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'))
+        };
+
+        let mut tags = comment
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter_map(|tag| tag.split_once('='))
+            .filter(|&(key, value)| is_valid_key(key) && is_valid_value(value))
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .take(MAX_TAGS)
+            .collect::<Vec<_>>();
+        // Sort the tags so that the same set of tags always produces the same set of labels
+        tags.sort();
+        tags.dedup_by(|(k1, _), (k2, _)| k1 == k2);
+
+        Self(tags)
+    }
+
+    /// Returns true if no tags were attached to the query
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the `(key, value)` pairs of these tags
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns these tags as a list of metric labels
+    pub fn labels(&self) -> Vec<Label> {
+        self.0
+            .iter()
+            .map(|(k, v)| Label::new(k.clone(), v.clone()))
+            .collect()
+    }
+}
+
+/// Bounds the number of distinct values each tag key can take on in metric labels, so that
+/// applications can't create an unbounded number of metric series by tagging queries with
+/// arbitrary values.
+///
+/// The first [`MAX_TAG_VALUES`] values seen for a key are kept as they are, and any others are
+/// replaced with [`OTHER_TAG_VALUE`].
+#[derive(Debug, Default)]
+pub struct TagValueLimiter {
+    values: HashMap<String, HashSet<String>>,
+}
+
+impl TagValueLimiter {
+    /// Returns `tags` with any values past the limit for their key replaced
+    pub fn limit(&mut self, tags: &QueryTags) -> QueryTags {
+        QueryTags(
+            tags.0
+                .iter()
+                .map(|(key, value)| {
+                    let values = self.values.entry(key.clone()).or_default();
+                    if values.contains(value) || values.len() < MAX_TAG_VALUES {
+                        values.insert(value.clone());
+                        (key.clone(), value.clone())
+                    } else {
+                        (key.clone(), OTHER_TAG_VALUE.to_owned())
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for QueryTags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (k, v)) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}={}", k, v)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_comment() {
+        assert!(QueryTags::from_leading_comment("SELECT * FROM t").is_empty());
+    }
+
+    #[test]
+    fn comment_not_at_start() {
+        assert!(
+            QueryTags::from_leading_comment("SELECT * FROM t /* service=billing */").is_empty()
+        );
+    }
+
+    #[test]
+    fn extracts_tags() {
+        let tags = QueryTags::from_leading_comment(
+            "  /* service=billing, team=payments */ SELECT * FROM t WHERE id = ?",
+        );
+        assert_eq!(
+            tags.iter().collect::<Vec<_>>(),
+            vec![("service", "billing"), ("team", "payments")]
+        );
+        assert_eq!(tags.to_string(), "service=billing,team=payments");
+    }
+
+    #[test]
+    fn ignores_malformed_tags() {
+        let tags = QueryTags::from_leading_comment(
+            "/* just a comment, service=billing, b@d=x, 1st=x, a-b=x, =empty, query=x, \
+             key_width=1, drop=table;-- */ SELECT 1",
+        );
+        assert_eq!(
+            tags.iter().collect::<Vec<_>>(),
+            vec![("service", "billing")]
+        );
+    }
+
+    #[test]
+    fn number_of_tags_is_bounded() {
+        let tags = QueryTags::from_leading_comment("/* a=1 b=2 c=3 d=4 e=5 f=6 */ SELECT 1");
+        assert_eq!(tags.iter().count(), MAX_TAGS);
+    }
+
+    #[test]
+    fn number_of_values_per_key_is_bounded() {
+        let mut limiter = TagValueLimiter::default();
+        let limited = (0..MAX_TAG_VALUES + 2)
+            .map(|i| {
+                let tags = QueryTags::from_leading_comment(&format!("/* user=u{i} */ SELECT 1"));
+                limiter.limit(&tags).to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(limited[0], "user=u0");
+        assert_eq!(
+            limited[MAX_TAG_VALUES - 1],
+            format!("user=u{}", MAX_TAG_VALUES - 1)
+        );
+        assert_eq!(limited[MAX_TAG_VALUES], "user=other");
+        assert_eq!(limited[MAX_TAG_VALUES + 1], "user=other");
+
+        // Values already seen are still allowed, and other keys have their own limit
+        let tags = QueryTags::from_leading_comment("/* user=u0 service=billing */ SELECT 1");
+        assert_eq!(limiter.limit(&tags).to_string(), "service=billing,user=u0");
+    }
+
+    #[test]
+    fn tagged_query_metric_labels_carry_tags() {
+        let tags = QueryTags::from_leading_comment("/* service=billing */ SELECT 1");
+        assert_eq!(tags.labels(), vec![Label::new("service", "billing")]);
+    }
+}
@@ -45,6 +45,14 @@ pub const QUERY_LOG_TOTAL_CACHE_MISSES: &str = "query-log.total_cache_misses";
 /// | query | The query text being executed. |
 pub const QUERY_LOG_QUERY_CACHE_MISSED: &str = "query-log.query_cache_missed";
 
+/// Counter: The number of rows read from ReadySet's caches to answer queries, before any
+/// filtering, aggregation, or limits were applied.
+///
+/// | Tag | Description |
+/// | --- | ----------- |
+/// | query | The query text being executed. |
+pub const QUERY_LOG_TOTAL_ROWS_EXAMINED: &str = "query-log.total_rows_examined";
+
 /// Counter: The number of successful queries (dry runs/real) processed by the migration handler.
 pub const MIGRATION_HANDLER_SUCCESSES: &str = "migration-handler.successes";
 
@@ -57,8 +65,20 @@ pub const MIGRATION_HANDLER_FAILURES: &str = "migration-handler.failures";
 /// status in the query status cache. Requires optimization of locking.
 pub const MIGRATION_HANDLER_ALLOWED: &str = "migration-handler.allowed";
 
+/// Counter: The number of mismatches found between the results of ReadySet and the upstream
+/// database when validating queries with `--validate-queries`.
+///
+/// | Tag | Description |
+/// | --- | ----------- |
+/// | query | The query text, with all literals anonymized. |
+pub const QUERY_VALIDATION_MISMATCH: &str = "readyset_query_validation_mismatch_total";
+
 /// Counter: The number of HTTP requests received at the noria-client.
 pub const ADAPTER_EXTERNAL_REQUESTS: &str = "noria-client.external_requests";
 
 /// Gauge: The number of currently connected SQL clients
 pub const CONNECTED_CLIENTS: &str = "noria-client.connected_clients";
+
+/// Gauge: The number of statements currently prepared against ReadySet, summed across all
+/// connections
+pub const PREPARED_STATEMENT_CACHE_SIZE: &str = "noria-client.prepared_statement_cache_size";
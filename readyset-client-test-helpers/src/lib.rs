@@ -12,6 +12,7 @@ use readyset::consensus::{Authority, LocalAuthorityStore};
 use readyset::ViewCreateRequest;
 use readyset_adapter::backend::noria_connector::{NoriaConnector, ReadBehavior};
 use readyset_adapter::backend::{BackendBuilder, MigrationMode};
+use readyset_adapter::preload::preload_queries;
 use readyset_adapter::query_status_cache::QueryStatusCache;
 use readyset_adapter::{Backend, QueryHandler, UpstreamConfig, UpstreamDatabase};
 use readyset_server::{Builder, Handle, LocalAuthority, ReadySetHandle};
@@ -59,11 +60,14 @@ pub struct TestBuilder {
     fallback: bool,
     fallback_url: Option<String>,
     partial: bool,
+    sharding: Option<usize>,
+    allow_partial_results: bool,
     wait_for_backend: bool,
     read_behavior: ReadBehavior,
     migration_mode: MigrationMode,
     recreate_database: bool,
     query_status_cache: Option<&'static QueryStatusCache>,
+    preload_queries: Option<String>,
 }
 
 impl Default for TestBuilder {
@@ -79,11 +83,14 @@ impl TestBuilder {
             fallback: false,
             fallback_url: None,
             partial: true,
+            sharding: None,
+            allow_partial_results: false,
             wait_for_backend: true,
             read_behavior: ReadBehavior::Blocking,
             migration_mode: MigrationMode::InRequestPath,
             recreate_database: true,
             query_status_cache: None,
+            preload_queries: None,
         }
     }
 
@@ -103,6 +110,19 @@ impl TestBuilder {
         self
     }
 
+    /// Shard the dataflow graph of the server into the given number of shards
+    pub fn sharding(mut self, sharding: Option<usize>) -> Self {
+        self.sharding = sharding;
+        self
+    }
+
+    /// Allow reads to return partial results if some shards of a cache are unavailable, as with
+    /// the adapter's `--allow-partial-results` option
+    pub fn allow_partial_results(mut self, allow_partial_results: bool) -> Self {
+        self.allow_partial_results = allow_partial_results;
+        self
+    }
+
     pub fn read_behavior(mut self, read_behavior: ReadBehavior) -> Self {
         self.read_behavior = read_behavior;
         self
@@ -123,6 +143,13 @@ impl TestBuilder {
         self
     }
 
+    /// Create caches for the given semicolon-separated queries before accepting any connections,
+    /// as with the adapter's `--preload-queries` option
+    pub fn preload_queries<S: Into<String>>(mut self, queries: S) -> Self {
+        self.preload_queries = Some(queries.into());
+        self
+    }
+
     pub async fn build<A>(self) -> (A::ConnectionOpts, Handle)
     where
         A: Adapter + 'static,
@@ -156,6 +183,7 @@ impl TestBuilder {
         if !self.partial {
             builder.disable_partial();
         }
+        builder.set_sharding(self.sharding);
 
         if let Some(f) = &fallback_url {
             builder.set_replication_url(f.clone());
@@ -167,12 +195,39 @@ impl TestBuilder {
 
         let auto_increments: Arc<RwLock<HashMap<Relation, AtomicUsize>>> = Arc::default();
         let query_cache: Arc<RwLock<HashMap<ViewCreateRequest, Relation>>> = Arc::default();
+
+        if let Some(queries) = &self.preload_queries {
+            let schema_search_path = if let Some(f) = &fallback_url {
+                A::make_upstream(f.clone())
+                    .await
+                    .schema_search_path()
+                    .await
+                    .unwrap()
+            } else {
+                Default::default()
+            };
+
+            let mut rh = ReadySetHandle::new(authority.clone()).await;
+            let server_supports_pagination = rh.supports_pagination().await.unwrap();
+            let mut noria = NoriaConnector::new(
+                rh,
+                auto_increments.clone(),
+                query_cache.clone(),
+                self.read_behavior,
+                A::EXPR_DIALECT,
+                schema_search_path,
+                server_supports_pagination,
+            )
+            .await;
+            preload_queries(&mut noria, query_status_cache, A::DIALECT, queries).await;
+        }
+
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let addr = listener.local_addr().unwrap();
 
         tokio::spawn(async move {
             loop {
-                let (s, _) = listener.accept().await.unwrap();
+                let (s, client_addr) = listener.accept().await.unwrap();
                 let query_cache = query_cache.clone();
                 let backend_builder = self.backend_builder.clone();
                 let auto_increments = auto_increments.clone();
@@ -192,6 +247,7 @@ impl TestBuilder {
                 };
 
                 let mut rh = ReadySetHandle::new(authority).await;
+                rh.set_allow_partial_results(self.allow_partial_results);
                 let server_supports_pagination = rh.supports_pagination().await.unwrap();
                 let noria = NoriaConnector::new(
                     rh,
@@ -207,6 +263,7 @@ impl TestBuilder {
                 let backend = backend_builder
                     .dialect(A::DIALECT)
                     .migration_mode(self.migration_mode)
+                    .client_addr(Some(client_addr))
                     .build(noria, upstream, query_status_cache);
 
                 tokio::spawn(A::run_backend(backend, s));
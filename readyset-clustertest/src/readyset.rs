@@ -91,6 +91,70 @@ async fn new_leader_worker_set() {
     deployment.teardown().await.unwrap();
 }
 
+#[clustertest]
+async fn evict_worker() {
+    let mut deployment = DeploymentBuilder::new("ct_evict_worker")
+        .with_servers(2, ServerParams::default())
+        .start()
+        .await
+        .unwrap();
+    let lh = deployment.leader_handle();
+
+    lh.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id int, val int);
+         CREATE CACHE q FROM SELECT id, sum(val) FROM t WHERE id = ? GROUP BY id;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = lh.table("t").await.unwrap();
+    t.insert_many(vec![
+        vec![DfValue::from(1), DfValue::from(1)],
+        vec![DfValue::from(1), DfValue::from(2)],
+    ])
+    .await
+    .unwrap();
+
+    // The worker holding the base table can't be evicted, since its data would be lost, but the
+    // other one can
+    let mut evicted = None;
+    for worker in lh.workers().await.unwrap() {
+        match lh.evict_worker(worker.clone()).await {
+            Ok(()) => {
+                evicted = Some(worker);
+                break;
+            }
+            Err(e) => assert!(e.caused_by_cannot_evict_worker(), "{}", e),
+        }
+    }
+    let evicted = evicted.expect("One of the workers should be evictable");
+
+    let workers = lh.workers().await.unwrap();
+    assert_eq!(workers.len(), 1);
+    assert!(!workers.contains(&evicted));
+
+    // All of the domains should now be on the remaining worker
+    let info = lh.get_info().await.unwrap();
+    assert!(info.keys().all(|w| *w == workers[0]));
+
+    // The query should have been migrated onto the remaining worker
+    let mut q = lh.view("q").await.unwrap();
+    eventually! {
+        let res = q.lookup(&[1.into()], true).await.unwrap().into_vec();
+        res == vec![vec![DfValue::from(1), DfValue::from(Decimal::from_i32(3))]]
+    }
+
+    // The last worker in the cluster can't be evicted
+    let err = lh.evict_worker(workers[0].clone()).await.unwrap_err();
+    assert!(err.caused_by_cannot_evict_worker());
+
+    deployment.teardown().await.unwrap();
+}
+
 #[clustertest]
 async fn balance_base_table_domains() {
     let mut deployment = DeploymentBuilder::new("ct_balance_base_table_domains")
@@ -377,6 +441,90 @@ async fn replicated_readers_with_unions() {
     deployment.teardown().await.unwrap();
 }
 
+#[clustertest]
+async fn set_reader_replicas() {
+    let mut deployment = DeploymentBuilder::new("ct_set_reader_replicas")
+        .with_servers(1, ServerParams::default())
+        .start()
+        .await
+        .unwrap();
+    let lh = deployment.leader_handle();
+
+    lh.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id int, val int);
+         CREATE CACHE q FROM SELECT id, sum(val) FROM t WHERE id = ? GROUP BY id;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = lh.table("t").await.unwrap();
+    t.insert_many(vec![
+        vec![DfValue::from(1), DfValue::from(1)],
+        vec![DfValue::from(1), DfValue::from(2)],
+    ])
+    .await
+    .unwrap();
+
+    // With only one worker, we can only run one replica of the reader
+    lh.set_reader_replicas("q".into(), 3).await.unwrap();
+    assert!(lh.view_with_replica("q", 1).await.is_err());
+
+    // Once enough workers join the cluster, the controller should add the remaining replicas
+    deployment
+        .start_server(ServerParams::default(), true)
+        .await
+        .unwrap();
+    deployment
+        .start_server(ServerParams::default(), true)
+        .await
+        .unwrap();
+
+    let lh = deployment.leader_handle();
+    eventually! {
+        lh.view_with_replica("q", 2).await.is_ok()
+    }
+    let mut views = vec![];
+    for replica in 0..3 {
+        views.push(lh.view_with_replica("q", replica).await.unwrap());
+    }
+    assert!(lh.view_with_replica("q", 3).await.is_err());
+
+    // Each replica should be scheduled onto a different worker
+    let addrs = views
+        .iter()
+        .map(|v| v.shard_addrs().to_vec())
+        .collect::<HashSet<_>>();
+    assert_eq!(addrs.len(), 3);
+
+    for view in &mut views {
+        eventually! {
+            let res = view.lookup(&[1.into()], true).await.unwrap().into_vec();
+            res == vec![vec![DfValue::from(1), DfValue::from(Decimal::from_i32(3))]]
+        }
+    }
+
+    // Dropping the query forgets its desired replicas, so a new query with the same name only
+    // gets a single replica
+    lh.remove_query(&"q".into()).await.unwrap();
+    lh.extend_recipe(
+        ChangeList::from_str(
+            "CREATE CACHE q FROM SELECT id, sum(val) FROM t WHERE id = ? GROUP BY id;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert!(lh.view_with_replica("q", 0).await.is_ok());
+    assert!(lh.view_with_replica("q", 1).await.is_err());
+
+    deployment.teardown().await.unwrap();
+}
+
 #[clustertest]
 async fn no_readers_worker_doesnt_get_readers() {
     let mut deployment = DeploymentBuilder::new("ct_no_readers_worker_doesnt_get_readers")
@@ -0,0 +1,56 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use readyset_data::DfValue;
+
+/// A global allocator that counts the number of allocations made, so we can report how many
+/// allocations constructing strings of different lengths requires
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Returns the number of allocations made by constructing `n` [`DfValue`]s from `s`
+fn allocations_for(s: &str, n: usize) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..n {
+        black_box(DfValue::from(black_box(s)));
+    }
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn text(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Text");
+
+    // Strings right around the boundary between TinyText and Text
+    for len in [8, 14, 15, 24] {
+        let s = "a".repeat(len);
+        println!(
+            "DfValue::from({len}-byte string): {} allocations per 1000 values",
+            allocations_for(&s, 1000)
+        );
+
+        group.bench_with_input(BenchmarkId::new("DfValue::from", len), &s, |b, s| {
+            b.iter(|| DfValue::from(black_box(s.as_str())))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, text);
+criterion_main!(benches);
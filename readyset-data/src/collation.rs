@@ -45,6 +45,24 @@ pub enum Collation {
 }
 
 impl Collation {
+    /// Returns the collation with the given name, as it would be written in a `COLLATE` clause, or
+    /// [`None`] if that collation isn't supported.
+    ///
+    /// Case-insensitive MySQL collations (those whose names end in `_ci`, such as
+    /// `utf8mb4_general_ci`) are represented as [`Collation::Citext`], and binary or
+    /// case-sensitive collations as [`Collation::Utf8`]. Note that only case-insensitivity is
+    /// supported - accent-insensitive collations compare accented characters as distinct.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        if name.ends_with("_ci") {
+            Some(Collation::Citext)
+        } else if name.ends_with("_bin") || name.ends_with("_cs") || name == "binary" {
+            Some(Collation::Utf8)
+        } else {
+            None
+        }
+    }
+
     /// Normalize the given string according to this collation.
     ///
     /// It will always be the case that two normalized strings compare in the same way as
@@ -90,6 +108,24 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn collation_from_name() {
+        assert_eq!(
+            Collation::from_name("utf8mb4_general_ci"),
+            Some(Collation::Citext)
+        );
+        assert_eq!(
+            Collation::from_name("UTF8_GENERAL_CI"),
+            Some(Collation::Citext)
+        );
+        assert_eq!(Collation::from_name("utf8mb4_bin"), Some(Collation::Utf8));
+        assert_eq!(
+            Collation::from_name("utf8mb4_0900_as_cs"),
+            Some(Collation::Utf8)
+        );
+        assert_eq!(Collation::from_name("en_US"), None);
+    }
+
     #[test]
     #[allow(clippy::assertions_on_constants)]
     fn no_more_than_16_collations() {
@@ -6,7 +6,7 @@ use std::convert::{TryFrom, TryInto};
 use std::error::Error;
 use std::hash::{Hash, Hasher};
 use std::net::IpAddr;
-use std::ops::{Add, Div, Mul, Sub};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Shl, Shr, Sub};
 use std::sync::Arc;
 use std::{fmt, str};
 
@@ -283,6 +283,41 @@ impl DfValue {
         matches!(*self, DfValue::Array(_))
     }
 
+    /// Returns `true` if this value represents the same value as `other`, regardless of how either
+    /// value is represented.
+    ///
+    /// Unlike [`PartialEq::eq`], which only considers values of compatible variants to be equal,
+    /// this compares integer, real, and numeric values by their numeric value - so, for example,
+    /// `Int(5)`, `Double(5.0)`, and `Numeric(5.0)` are all considered equal. Values of any other
+    /// type are compared using [`PartialEq::eq`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use readyset_data::DfValue;
+    ///
+    /// assert!(DfValue::Int(5).value_eq(&DfValue::Double(5.0)));
+    /// assert!(!DfValue::Int(5).value_eq(&DfValue::Double(5.5)));
+    /// assert!(!DfValue::Int(5).value_eq(&DfValue::from("5")));
+    /// ```
+    pub fn value_eq(&self, other: &DfValue) -> bool {
+        let is_numeric =
+            |v: &DfValue| v.is_integer() || v.is_real() || matches!(v, DfValue::Numeric(_));
+        if !is_numeric(self) || !is_numeric(other) || (self.is_integer() && other.is_integer()) {
+            return self == other;
+        }
+
+        match (Decimal::try_from(self), Decimal::try_from(other)) {
+            (Ok(a), Ok(b)) => a == b,
+            // Reals that can't be represented as a decimal (infinities and NaN) can only ever be
+            // equal to other reals
+            _ => match (f64::try_from(self), f64::try_from(other)) {
+                (Ok(a), Ok(b)) => a.to_bits() == b.to_bits(),
+                _ => false,
+            },
+        }
+    }
+
     /// Returns `true` if this value is truthy (is not 0, 0.0, '', or NULL).
     ///
     /// # Examples
@@ -317,6 +352,41 @@ impl DfValue {
         }
     }
 
+    /// Interpret this value as the result of a boolean predicate, as when filtering rows.
+    ///
+    /// Numeric values are true if they're nonzero, and `NULL` is never true. Unlike
+    /// [`is_truthy`](Self::is_truthy), returns a [`ReadySetError::DfValueConversionError`] for
+    /// all other (non-numeric) values, rather than guessing at their truthiness.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use readyset_data::DfValue;
+    ///
+    /// assert!(!DfValue::Int(0).as_bool().unwrap());
+    /// assert!(DfValue::Int(1).as_bool().unwrap());
+    /// assert!(!DfValue::None.as_bool().unwrap());
+    /// assert!(DfValue::from("abc").as_bool().is_err());
+    /// ```
+    pub fn as_bool(&self) -> ReadySetResult<bool> {
+        match *self {
+            DfValue::None => Ok(false),
+            DfValue::Int(x) => Ok(x != 0),
+            DfValue::UnsignedInt(x) => Ok(x != 0),
+            DfValue::Float(f) => Ok(f != 0.0),
+            DfValue::Double(f) => Ok(f != 0.0),
+            DfValue::Numeric(ref d) => Ok(!d.is_zero()),
+            _ => Err(ReadySetError::DfValueConversionError {
+                src_type: match self.sql_type() {
+                    Some(ty) => ty.to_string(),
+                    None => "Null".to_string(),
+                },
+                target_type: "Bool".to_string(),
+                details: "Only numeric values can be interpreted as booleans".to_string(),
+            }),
+        }
+    }
+
     /// Checks if the given DfValue::Double or DfValue::Float is equal to another DfValue::Double
     /// or DfValue::Float (respectively) under an acceptable error margin. If None is supplied,
     /// we use f32::EPSILON or f64::EPSILON, accordingly.
@@ -560,6 +630,14 @@ impl DfValue {
                 .unwrap_or(DfValue::Int(0));
         } else if col_ty.is_array() && col_ty.innermost_array_type().is_enum() {
             *self = self.coerce_to(col_ty, &DfType::Unknown)?;
+        } else if let (Some((s, collation)), Some(col_collation)) =
+            (self.as_str_and_collation(), col_ty.collation())
+        {
+            // Text values are stored with the collation of the column they're in, so that they're
+            // compared and hashed consistently
+            if collation != col_collation {
+                *self = DfValue::from_str_and_collation(s, col_collation);
+            }
         }
 
         Ok(())
@@ -629,6 +707,37 @@ impl DfValue {
         }
     }
 
+    /// Attempt to interpret this value as a timestamp without time zone.
+    ///
+    /// [`DfValue::TimestampTz`] values are returned as their local date and time, and string values
+    /// are parsed using any of the timestamp (or date) formats accepted when coercing text to a
+    /// timestamp type. All other values, and strings which can't be parsed as a timestamp, return
+    /// a [`ReadySetError::DfValueConversionError`].
+    ///
+    /// This is used when coercing text to [`DfType::Timestamp`] or [`DfType::DateTime`].
+    pub fn try_into_timestamp(&self) -> ReadySetResult<NaiveDateTime> {
+        let conversion_err = |details: String| ReadySetError::DfValueConversionError {
+            src_type: match self.sql_type() {
+                Some(ty) => ty.to_string(),
+                None => "Null".to_string(),
+            },
+            target_type: "NaiveDateTime".to_string(),
+            details,
+        };
+
+        match self {
+            DfValue::TimestampTz(ts) => Ok(ts.to_chrono().naive_local()),
+            DfValue::Text(_) | DfValue::TinyText(_) => {
+                let s = <&str>::try_from(self)?;
+                s.trim()
+                    .parse::<TimestampTz>()
+                    .map(|ts| ts.to_chrono().naive_local())
+                    .map_err(|e| conversion_err(e.to_string()))
+            }
+            _ => Err(conversion_err("".to_string())),
+        }
+    }
+
     /// Returns Some(&self) if self is not [`DfValue::None`]
     ///
     /// # Examples
@@ -665,6 +774,30 @@ impl DfValue {
         }
     }
 
+    /// Compares `self` and `other` for equality using SQL's three-valued logic, returning `None`
+    /// (unknown) if either value is NULL.
+    ///
+    /// This should be used when evaluating SQL predicates - unlike the [`PartialEq`] impl for
+    /// [`DfValue`], which treats NULL as equal to itself so that NULLs can be stored in and looked
+    /// up from indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use readyset_data::DfValue;
+    ///
+    /// assert_eq!(DfValue::from(1).sql_eq(&DfValue::from(1)), Some(true));
+    /// assert_eq!(DfValue::from(1).sql_eq(&DfValue::from(2)), Some(false));
+    /// assert_eq!(DfValue::None.sql_eq(&DfValue::None), None);
+    /// ```
+    pub fn sql_eq(&self, other: &DfValue) -> Option<bool> {
+        if self.is_none() || other.is_none() {
+            None
+        } else {
+            Some(self == other)
+        }
+    }
+
     /// Attempts to convert self to a str and parse as JSON, returning a [`serde_json::Value`]
     /// wrapped in [`ReadySetResult`].
     ///
@@ -692,6 +825,8 @@ impl DfValue {
     }
 }
 
+/// Note that unlike SQL equality (see [`DfValue::sql_eq`]), NULL is equal to itself, so that
+/// NULLs can be stored in and looked up from indices
 impl PartialEq for DfValue {
     fn eq(&self, other: &DfValue) -> bool {
         match (self, other) {
@@ -735,18 +870,8 @@ impl PartialEq for DfValue {
             }
             (&DfValue::Int(a), &DfValue::Int(b)) => a == b,
             (&DfValue::UnsignedInt(a), &DfValue::UnsignedInt(b)) => a == b,
-            (&DfValue::UnsignedInt(..), &DfValue::Int(..))
-            | (&DfValue::Int(..), &DfValue::UnsignedInt(..)) => {
-                // this unwrap should be safe because no error path in try_from for i128 (&i128) on
-                // Int and UnsignedInt
-                #[allow(clippy::unwrap_used)]
-                let a: i128 = <i128>::try_from(self).unwrap();
-                // this unwrap should be safe because no error path in try_from for i128 (&i128) on
-                // Int and UnsignedInt
-                #[allow(clippy::unwrap_used)]
-                let b: i128 = <i128>::try_from(other).unwrap();
-                a == b
-            }
+            (&DfValue::UnsignedInt(a), &DfValue::Int(b)) => i128::from(a) == i128::from(b),
+            (&DfValue::Int(a), &DfValue::UnsignedInt(b)) => i128::from(a) == i128::from(b),
             (&DfValue::Float(fa), &DfValue::Float(fb)) => {
                 // We need to compare the *bit patterns* of the floats so that our Hash matches our
                 // Eq
@@ -811,6 +936,8 @@ impl PartialOrd for DfValue {
     }
 }
 
+/// NULL sorts before all other values. As with [`PartialEq`], this ordering is used for indices,
+/// and shouldn't be used to evaluate SQL comparisons involving NULL
 impl Ord for DfValue {
     fn cmp(&self, other: &DfValue) -> Ordering {
         match (self, other) {
@@ -854,18 +981,8 @@ impl Ord for DfValue {
             ) => other.cmp(self).reverse(),
             (&DfValue::Int(a), &DfValue::Int(b)) => a.cmp(&b),
             (&DfValue::UnsignedInt(a), &DfValue::UnsignedInt(b)) => a.cmp(&b),
-            (&DfValue::UnsignedInt(..), &DfValue::Int(..))
-            | (&DfValue::Int(..), &DfValue::UnsignedInt(..)) => {
-                // this unwrap should be safe because no error path in try_from for i128 (&i128) on
-                // Int and UnsignedInt
-                #[allow(clippy::unwrap_used)]
-                let a: i128 = <i128>::try_from(self).unwrap();
-                // this unwrap should be safe because no error path in try_from for i128 (&i128 on
-                // Int and UnsignedInt
-                #[allow(clippy::unwrap_used)]
-                let b: i128 = <i128>::try_from(other).unwrap();
-                a.cmp(&b)
-            }
+            (&DfValue::UnsignedInt(a), &DfValue::Int(b)) => i128::from(a).cmp(&i128::from(b)),
+            (&DfValue::Int(a), &DfValue::UnsignedInt(b)) => i128::from(a).cmp(&i128::from(b)),
             (&DfValue::Float(fa), &DfValue::Float(fb)) => fa.total_cmp(&fb),
             (&DfValue::Double(fa), &DfValue::Double(fb)) => fa.total_cmp(&fb),
             (&DfValue::Numeric(ref da), &DfValue::Numeric(ref db)) => da.cmp(db),
@@ -898,53 +1015,18 @@ impl Ord for DfValue {
             (&DfValue::Time(ref ta), &DfValue::Time(ref tb)) => ta.cmp(tb),
 
             // Convert ints to f32 and cmp against Float.
-            (&DfValue::Int(..), &DfValue::Float(b, ..))
-            | (&DfValue::UnsignedInt(..), &DfValue::Float(b, ..)) => {
-                // this unwrap should be safe because no error path in try_from for i128 (&i128) on
-                // Int and UnsignedInt
-                #[allow(clippy::unwrap_used)]
-                let a = <i128>::try_from(self).unwrap();
-
-                (a as f32).total_cmp(&b)
-            }
+            (&DfValue::Int(a), &DfValue::Float(b, ..)) => (a as f32).total_cmp(&b),
+            (&DfValue::UnsignedInt(a), &DfValue::Float(b, ..)) => (a as f32).total_cmp(&b),
             // Convert ints to double and cmp against Real.
-            (&DfValue::Int(..), &DfValue::Double(b, ..))
-            | (&DfValue::UnsignedInt(..), &DfValue::Double(b, ..)) => {
-                // this unwrap should be safe because no error path in try_from for i128 (&i128) on
-                // Int and UnsignedInt
-                #[allow(clippy::unwrap_used)]
-                let a: i128 = <i128>::try_from(self).unwrap();
-
-                (a as f64).total_cmp(&b)
-            }
+            (&DfValue::Int(a), &DfValue::Double(b, ..)) => (a as f64).total_cmp(&b),
+            (&DfValue::UnsignedInt(a), &DfValue::Double(b, ..)) => (a as f64).total_cmp(&b),
             // Convert ints to f32 and cmp against Float.
-            (&DfValue::Int(..), &DfValue::Numeric(ref b))
-            | (&DfValue::UnsignedInt(..), &DfValue::Numeric(ref b)) => {
-                // this unwrap should be safe because no error path in try_from for i128 (&i128) on
-                // Int and UnsignedInt
-                #[allow(clippy::unwrap_used)]
-                let a: i128 = <i128>::try_from(self).unwrap();
-
-                Decimal::from(a).cmp(b)
-            }
-            (&DfValue::Float(a, ..), &DfValue::Int(..))
-            | (&DfValue::Float(a, ..), &DfValue::UnsignedInt(..)) => {
-                // this unwrap should be safe because no error path in try_from for i128 (&i128)  on
-                // Int and UnsignedInt
-                #[allow(clippy::unwrap_used)]
-                let b: i128 = <i128>::try_from(other).unwrap();
-
-                a.total_cmp(&(b as f32))
-            }
-            (&DfValue::Double(a, ..), &DfValue::Int(..))
-            | (&DfValue::Double(a, ..), &DfValue::UnsignedInt(..)) => {
-                // this unwrap should be safe because no error path in try_from for i128 (&i128) on
-                // Int and UnsignedInt
-                #[allow(clippy::unwrap_used)]
-                let b: i128 = <i128>::try_from(other).unwrap();
-
-                a.total_cmp(&(b as f64))
-            }
+            (&DfValue::Int(a), &DfValue::Numeric(ref b)) => Decimal::from(a).cmp(b),
+            (&DfValue::UnsignedInt(a), &DfValue::Numeric(ref b)) => Decimal::from(a).cmp(b),
+            (&DfValue::Float(a, ..), &DfValue::Int(b)) => a.total_cmp(&(b as f32)),
+            (&DfValue::Float(a, ..), &DfValue::UnsignedInt(b)) => a.total_cmp(&(b as f32)),
+            (&DfValue::Double(a, ..), &DfValue::Int(b)) => a.total_cmp(&(b as f64)),
+            (&DfValue::Double(a, ..), &DfValue::UnsignedInt(b)) => a.total_cmp(&(b as f64)),
             (&DfValue::Numeric(_), &DfValue::Int(..))
             | (&DfValue::Numeric(_), &DfValue::UnsignedInt(..)) => other.cmp(self).reverse(),
             (&DfValue::ByteArray(ref array_a), &DfValue::ByteArray(ref array_b)) => {
@@ -1464,7 +1546,7 @@ impl TryFrom<&'_ DfValue> for f64 {
             DfValue::Double(f) => Ok(f),
             DfValue::Numeric(ref d) => d.to_f64().ok_or(Self::Error::DfValueConversionError {
                 src_type: "DfValue".to_string(),
-                target_type: "f32".to_string(),
+                target_type: "f64".to_string(),
                 details: "".to_string(),
             }),
             DfValue::UnsignedInt(i) => Ok(i as f64),
@@ -1822,8 +1904,7 @@ macro_rules! arithmetic_operation (
             (first @ &DfValue::Float(..), second @ &DfValue::Int(..)) |
             (first @ &DfValue::Float(..), second @ &DfValue::UnsignedInt(..)) |
             (first @ &DfValue::Float(..), second @ &DfValue::Float(..)) |
-            (first @ &DfValue::Float(..), second @ &DfValue::Double(..)) |
-            (first @ &DfValue::Float(..), second @ &DfValue::Numeric(..)) => {
+            (first @ &DfValue::Float(..), second @ &DfValue::Double(..)) => {
                 let a: f32 = f32::try_from(first)?;
                 let b: f32 = f32::try_from(second)?;
                 DfValue::try_from(a $op b)?
@@ -1834,64 +1915,32 @@ macro_rules! arithmetic_operation (
             (first @ &DfValue::Double(..), second @ &DfValue::Int(..)) |
             (first @ &DfValue::Double(..), second @ &DfValue::UnsignedInt(..)) |
             (first @ &DfValue::Double(..), second @ &DfValue::Double(..)) |
-            (first @ &DfValue::Double(..), second @ &DfValue::Float(..)) |
-            (first @ &DfValue::Double(..), second @ &DfValue::Numeric(..)) => {
+            (first @ &DfValue::Double(..), second @ &DfValue::Float(..)) => {
                 let a: f64 = f64::try_from(first)?;
                 let b: f64 = f64::try_from(second)?;
                 DfValue::try_from(a $op b)?
             }
 
+            // Numeric values are never lowered to floats, since that would lose precision - instead
+            // the other operand is promoted to a Decimal
             (first @ &DfValue::Int(..), second @ &DfValue::Numeric(..)) |
             (first @ &DfValue::UnsignedInt(..), second @ &DfValue::Numeric(..)) |
+            (first @ &DfValue::Float(..), second @ &DfValue::Numeric(..)) |
+            (first @ &DfValue::Double(..), second @ &DfValue::Numeric(..)) |
             (first @ &DfValue::Numeric(..), second @ &DfValue::Int(..)) |
             (first @ &DfValue::Numeric(..), second @ &DfValue::UnsignedInt(..)) |
+            (first @ &DfValue::Numeric(..), second @ &DfValue::Float(..)) |
+            (first @ &DfValue::Numeric(..), second @ &DfValue::Double(..)) |
             (first @ &DfValue::Numeric(..), second @ &DfValue::Numeric(..)) => {
-                let a: Decimal = Decimal::try_from(first)
-                    .map_err(|e| ReadySetError::DfValueConversionError {
-                        src_type: "DfValue".to_string(),
-                        target_type: "Decimal".to_string(),
-                        details: e.to_string(),
-                    })?;
-                let b: Decimal = Decimal::try_from(second)
-                    .map_err(|e| ReadySetError::DfValueConversionError {
-                        src_type: "DfValue".to_string(),
-                        target_type: "Decimal".to_string(),
-                        details: e.to_string(),
-                    })?;
-                DfValue::from(a.$checked_op(b))
-            }
-            (first @ &DfValue::Numeric(..), second @ &DfValue::Float(..)) => {
-                let a: Decimal = Decimal::try_from(first)
-                    .map_err(|e| ReadySetError::DfValueConversionError {
-                        src_type: "DfValue".to_string(),
-                        target_type: "Decimal".to_string(),
-                        details: e.to_string(),
-                    })?;
-                let b: Decimal = f32::try_from(second).and_then(|f| Decimal::from_f32(f)
-                    .ok_or(ReadySetError::DfValueConversionError {
-                        src_type: "DfValue".to_string(),
-                        target_type: "Decimal".to_string(),
-                        details: "".to_string(),
-                    }))?;
-                DfValue::from(a.$checked_op(b))
-            }
-            (first @ &DfValue::Numeric(..), second @ &DfValue::Double(..)) => {
-                let a: Decimal = Decimal::try_from(first)
-                    .map_err(|e| ReadySetError::DfValueConversionError {
-                        src_type: "DfValue".to_string(),
-                        target_type: "Decimal".to_string(),
-                        details: e.to_string(),
-                    })?;
-                let b: Decimal = f64::try_from(second).and_then(|f| Decimal::from_f64(f)
-                    .ok_or(ReadySetError::DfValueConversionError {
-                        src_type: "DfValue".to_string(),
-                        target_type: "Decimal".to_string(),
-                        details: "".to_string(),
-                    }))?;
-                DfValue::from(a.$checked_op(b))
+                // Floats which can't be represented as a Decimal (NaN, infinities, and values
+                // beyond the range of Decimal) give NULL, just like the Decimal operation
+                // overflowing does
+                match (Decimal::try_from(first), Decimal::try_from(second)) {
+                    (Ok(a), Ok(b)) => DfValue::from(a.$checked_op(b)),
+                    _ => DfValue::None,
+                }
             }
 
-
             (first, second) => return Err(invalid_err!(
                 "can't {} a {:?} and {:?}",
                 stringify!($op),
@@ -1934,6 +1983,112 @@ impl<'a, 'b> Div<&'b DfValue> for &'a DfValue {
     }
 }
 
+impl<'a, 'b> Rem<&'b DfValue> for &'a DfValue {
+    type Output = ReadySetResult<DfValue>;
+
+    fn rem(self, other: &'b DfValue) -> Self::Output {
+        // Like MySQL, the remainder of a division by zero is NULL (rather than NaN, for floats)
+        let zero_divisor = match *other {
+            DfValue::Int(i) => i == 0,
+            DfValue::UnsignedInt(i) => i == 0,
+            DfValue::Float(f) => f == 0.0,
+            DfValue::Double(f) => f == 0.0,
+            DfValue::Numeric(ref d) => d.is_zero(),
+            _ => false,
+        };
+        if zero_divisor {
+            return Ok(DfValue::None);
+        }
+
+        Ok(arithmetic_operation!(%, checked_rem, self, other))
+    }
+}
+
+/// Returns the given integral [`DfValue`] as a [`u64`] for use as an operand to a bitwise operator,
+/// following MySQL's semantics of evaluating bitwise operations on unsigned 64-bit integers
+fn bitwise_operand(value: &DfValue, op: &str) -> ReadySetResult<u64> {
+    match *value {
+        DfValue::Int(i) => Ok(i as u64),
+        DfValue::UnsignedInt(i) => Ok(i),
+        _ => Err(invalid_err!(
+            "can't {} a {:?}",
+            op,
+            DfValueKind::from(value)
+        )),
+    }
+}
+
+/// Evaluates the bitwise operation `f` on the given operands, which must both be integral (or NULL)
+fn bitwise_operation<F>(
+    first: &DfValue,
+    second: &DfValue,
+    op: &str,
+    f: F,
+) -> ReadySetResult<DfValue>
+where
+    F: FnOnce(u64, u64) -> u64,
+{
+    if first.is_none() || second.is_none() {
+        return Ok(DfValue::None);
+    }
+
+    Ok(DfValue::UnsignedInt(f(
+        bitwise_operand(first, op)?,
+        bitwise_operand(second, op)?,
+    )))
+}
+
+impl<'a, 'b> BitAnd<&'b DfValue> for &'a DfValue {
+    type Output = ReadySetResult<DfValue>;
+
+    fn bitand(self, other: &'b DfValue) -> Self::Output {
+        bitwise_operation(self, other, "&", |a, b| a & b)
+    }
+}
+
+impl<'a, 'b> BitOr<&'b DfValue> for &'a DfValue {
+    type Output = ReadySetResult<DfValue>;
+
+    fn bitor(self, other: &'b DfValue) -> Self::Output {
+        bitwise_operation(self, other, "|", |a, b| a | b)
+    }
+}
+
+impl<'a, 'b> BitXor<&'b DfValue> for &'a DfValue {
+    type Output = ReadySetResult<DfValue>;
+
+    fn bitxor(self, other: &'b DfValue) -> Self::Output {
+        bitwise_operation(self, other, "^", |a, b| a ^ b)
+    }
+}
+
+impl<'a, 'b> Shl<&'b DfValue> for &'a DfValue {
+    type Output = ReadySetResult<DfValue>;
+
+    fn shl(self, other: &'b DfValue) -> Self::Output {
+        // Shifting by 64 or more bits (including by a negative amount) yields 0, like in MySQL
+        bitwise_operation(self, other, "<<", |a, b| {
+            u32::try_from(b)
+                .ok()
+                .and_then(|b| a.checked_shl(b))
+                .unwrap_or(0)
+        })
+    }
+}
+
+impl<'a, 'b> Shr<&'b DfValue> for &'a DfValue {
+    type Output = ReadySetResult<DfValue>;
+
+    fn shr(self, other: &'b DfValue) -> Self::Output {
+        bitwise_operation(self, other, ">>", |a, b| {
+            u32::try_from(b)
+                .ok()
+                .and_then(|b| a.checked_shr(b))
+                .unwrap_or(0)
+        })
+    }
+}
+
 impl Arbitrary for DfValue {
     type Parameters = Option<DfValueKind>;
     type Strategy = proptest::strategy::BoxedStrategy<DfValue>;
@@ -2176,6 +2331,11 @@ mod tests {
             Value::Time(false, 1, 1, 1, 1, 1),
             Value::try_from(DfValue::try_from(Value::Time(false, 1, 1, 1, 1, 1)).unwrap()).unwrap()
         );
+        assert_eq!(
+            Value::Time(true, 34, 12, 59, 59, 999_999),
+            Value::try_from(DfValue::try_from(Value::Time(true, 34, 12, 59, 59, 999_999)).unwrap())
+                .unwrap()
+        );
     }
 
     #[test]
@@ -2243,14 +2403,171 @@ mod tests {
         assert_eq!(a_dt.unwrap(), DfValue::TimestampTz(ts.into()));
 
         // Test Value::Time.
-        // noria_data::DfValue has no `Time` representation.
         let a = Value::Time(true, 0, 0, 0, 0, 0);
         let a_dt = DfValue::try_from(a);
         assert!(a_dt.is_ok());
         assert_eq!(
             a_dt.unwrap(),
             DfValue::Time(MySqlTime::from_microseconds(0))
-        )
+        );
+
+        // Negative times with sub-second precision, spanning more than a day
+        let a = Value::Time(true, 1, 2, 3, 4, 5);
+        let a_dt = DfValue::try_from(a);
+        assert!(a_dt.is_ok());
+        assert_eq!(
+            a_dt.unwrap(),
+            DfValue::Time(MySqlTime::from_hmsus(false, 26, 3, 4, 5))
+        );
+    }
+
+    #[test]
+    fn time_ordering() {
+        let neg_day = DfValue::Time(MySqlTime::from_hmsus(false, 24, 0, 0, 0));
+        let neg_micro = DfValue::Time(MySqlTime::from_microseconds(-1));
+        let zero = DfValue::Time(MySqlTime::from_microseconds(0));
+        let micro = DfValue::Time(MySqlTime::from_microseconds(1));
+        let day = DfValue::Time(MySqlTime::from_hmsus(true, 24, 0, 0, 0));
+        let max = DfValue::Time(MySqlTime::max_value());
+
+        let mut values = vec![
+            max.clone(),
+            zero.clone(),
+            day.clone(),
+            neg_micro.clone(),
+            micro.clone(),
+            neg_day.clone(),
+        ];
+        values.sort();
+        assert_eq!(values, vec![neg_day, neg_micro, zero, micro, day, max]);
+    }
+
+    #[test]
+    fn try_into_timestamp_from_text() {
+        let expected = NaiveDate::from_ymd(2024, 1, 1).and_hms(0, 0, 0);
+        for input in [
+            "2024-01-01 00:00:00",
+            "2024-01-01 00:00:00.000",
+            "2024-01-01T00:00:00",
+            "2024/01/01 00:00:00",
+            "20240101000000",
+            "2024-01-01",
+            " 2024-01-01 00:00:00 ",
+        ] {
+            assert_eq!(
+                DfValue::from(input).try_into_timestamp().unwrap(),
+                expected,
+                "input = {:?}",
+                input
+            );
+        }
+
+        assert_eq!(
+            DfValue::from("2024-01-01 12:34:56.789")
+                .try_into_timestamp()
+                .unwrap(),
+            NaiveDate::from_ymd(2024, 1, 1).and_hms_milli(12, 34, 56, 789)
+        );
+    }
+
+    #[test]
+    fn try_into_timestamp_passes_through_timestamps() {
+        let ts = NaiveDate::from_ymd(2024, 1, 1).and_hms(1, 2, 3);
+        assert_eq!(DfValue::from(ts).try_into_timestamp().unwrap(), ts);
+    }
+
+    #[test]
+    fn try_into_timestamp_rejects_malformed() {
+        for input in [
+            "",
+            "not a timestamp",
+            "2024-13-01 00:00:00",
+            "2024-01-01 25:00:00",
+        ] {
+            let res = DfValue::from(input).try_into_timestamp();
+            assert!(
+                matches!(res, Err(ReadySetError::DfValueConversionError { .. })),
+                "input = {:?}, res = {:?}",
+                input,
+                res
+            );
+        }
+
+        assert!(DfValue::Int(1).try_into_timestamp().is_err());
+        assert!(DfValue::None.try_into_timestamp().is_err());
+    }
+
+    #[test]
+    fn value_eq_ignores_numeric_representation() {
+        let five = [
+            DfValue::Int(5),
+            DfValue::UnsignedInt(5),
+            DfValue::Float(5.0),
+            DfValue::Double(5.0),
+            DfValue::from(Decimal::new(50, 1)),
+        ];
+        for a in &five {
+            for b in &five {
+                assert!(a.value_eq(b), "{:?} should be value_eq to {:?}", a, b);
+            }
+        }
+
+        // `eq` is sensitive to the variant, `value_eq` isn't
+        assert_ne!(DfValue::Int(5), DfValue::Double(5.0));
+        assert!(DfValue::Int(5).value_eq(&DfValue::Double(5.0)));
+
+        assert!(!DfValue::Int(5).value_eq(&DfValue::Double(5.1)));
+        assert!(!DfValue::Int(5).value_eq(&DfValue::from(Decimal::new(51, 1))));
+        assert!(!DfValue::Int(5).value_eq(&DfValue::from("5")));
+        assert!(!DfValue::Int(5).value_eq(&DfValue::None));
+        assert!(DfValue::Double(f64::INFINITY).value_eq(&DfValue::Float(f32::INFINITY)));
+        assert!(!DfValue::Double(f64::INFINITY).value_eq(&DfValue::Int(5)));
+        assert!(DfValue::from("a").value_eq(&DfValue::from("a")));
+    }
+
+    #[test]
+    fn as_bool() {
+        assert!(!DfValue::Int(0).as_bool().unwrap());
+        assert!(DfValue::Int(1).as_bool().unwrap());
+        assert!(DfValue::Int(-1).as_bool().unwrap());
+        assert!(!DfValue::UnsignedInt(0).as_bool().unwrap());
+        assert!(DfValue::UnsignedInt(2).as_bool().unwrap());
+        assert!(!DfValue::Float(0.0).as_bool().unwrap());
+        assert!(DfValue::Float(0.5).as_bool().unwrap());
+        assert!(!DfValue::Double(0.0).as_bool().unwrap());
+        assert!(DfValue::Double(-1.5).as_bool().unwrap());
+        assert!(!DfValue::from(Decimal::new(0, 1)).as_bool().unwrap());
+        assert!(DfValue::from(Decimal::new(1, 1)).as_bool().unwrap());
+        assert!(!DfValue::None.as_bool().unwrap());
+        assert!(DfValue::from("1").as_bool().is_err());
+        assert!(DfValue::ByteArray(Arc::new(vec![1])).as_bool().is_err());
+    }
+
+    #[test]
+    fn from_bool() {
+        let hash = |dt: &DfValue| {
+            use std::collections::hash_map::DefaultHasher;
+            use std::hash::{Hash, Hasher};
+            let mut s = DefaultHasher::new();
+            dt.hash(&mut s);
+            s.finish()
+        };
+
+        // Booleans keep comparing equal to their integer representations
+        assert_eq!(DfValue::from(true), DfValue::Int(1));
+        assert_eq!(DfValue::from(true), DfValue::UnsignedInt(1));
+        assert_eq!(DfValue::from(false), DfValue::Int(0));
+        assert_eq!(DfValue::from(false), DfValue::UnsignedInt(0));
+        assert_ne!(DfValue::from(true), DfValue::Int(0));
+        assert_eq!(hash(&DfValue::from(true)), hash(&DfValue::Int(1)));
+        assert_eq!(hash(&DfValue::from(false)), hash(&DfValue::Int(0)));
+        assert_eq!(
+            DfValue::try_from(&Literal::Boolean(true)).unwrap(),
+            DfValue::Int(1)
+        );
+
+        assert!(DfValue::from(true).as_bool().unwrap());
+        assert!(!DfValue::from(false).as_bool().unwrap());
     }
 
     #[test]
@@ -2294,6 +2611,24 @@ mod tests {
         assert_eq!(original, converted);
     }
 
+    #[test]
+    fn non_finite_floats_are_errors() {
+        DfValue::try_from(f64::NAN).unwrap_err();
+        DfValue::try_from(f64::INFINITY).unwrap_err();
+        DfValue::try_from(f64::NEG_INFINITY).unwrap_err();
+        DfValue::try_from(f32::NAN).unwrap_err();
+        DfValue::try_from(f32::INFINITY).unwrap_err();
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn double_near_integer_boundary() {
+        let data_type = DfValue::try_from(1.9999999995_f64).unwrap();
+        assert_eq!(data_type, DfValue::Double(1.9999999995));
+        assert_eq!(f64::try_from(&data_type).unwrap(), 1.9999999995);
+        assert_eq!(data_type.to_string(), "1.9999999995");
+    }
+
     #[test]
     #[allow(clippy::float_cmp)]
     fn real_to_numeric() {
@@ -2407,6 +2742,99 @@ mod tests {
         assert_eq!((&DfValue::Int(4) / &DfValue::from(2)).unwrap(), 2.into());
     }
 
+    #[test]
+    fn sql_eq_null() {
+        assert_eq!(DfValue::None.sql_eq(&DfValue::None), None);
+        assert_eq!(DfValue::Int(1).sql_eq(&DfValue::None), None);
+        assert_eq!(DfValue::None.sql_eq(&DfValue::Int(1)), None);
+        assert_eq!(DfValue::Int(1).sql_eq(&DfValue::Int(1)), Some(true));
+        assert_eq!(
+            DfValue::Int(1).sql_eq(&DfValue::UnsignedInt(2)),
+            Some(false)
+        );
+        // NULLs are still equal to each other for the purposes of indexing
+        assert_eq!(DfValue::None, DfValue::None);
+    }
+
+    #[test]
+    fn rem_data_types() {
+        assert_arithmetic!(%, 7, 2, 1);
+        assert_arithmetic!(%, -7, 2, -1);
+        assert_arithmetic!(%, 7_u64, 2_u64, 1_u64);
+        assert_arithmetic!(%, 7.5_f32, 2, 1.5_f32);
+        assert_arithmetic!(%, 7.5_f64, 2, 1.5_f64);
+        assert_arithmetic!(%, Decimal::new(75, 1), 2, Decimal::new(15, 1));
+        assert_arithmetic!(%, 7, 2.5_f64, 2.0_f64);
+        assert_arithmetic!(%, 7, Decimal::new(25, 1), Decimal::new(20, 1));
+        assert_arithmetic!(%, 1, 0, None::<i64>);
+        assert_arithmetic!(%, 1_u64, 0_u64, None::<u64>);
+        assert_arithmetic!(%, 1.5_f32, 0.0_f32, None::<f32>);
+        assert_arithmetic!(%, 1.5_f64, 0, None::<f64>);
+        assert_arithmetic!(%, Decimal::ONE, Decimal::ZERO, None::<Decimal>);
+        assert_eq!((&DfValue::Int(5) % &DfValue::from(3)).unwrap(), 2.into());
+        assert_eq!((&DfValue::None % &DfValue::Int(3)).unwrap(), DfValue::None);
+    }
+
+    #[test]
+    fn bitwise_data_types() {
+        assert_arithmetic!(&, 0b1100, 0b1010, 0b1000_u64);
+        assert_arithmetic!(|, 0b1100, 0b1010, 0b1110_u64);
+        assert_arithmetic!(^, 0b1100, 0b1010, 0b0110_u64);
+        assert_arithmetic!(<<, 1, 4, 16_u64);
+        assert_arithmetic!(>>, 16_u64, 4, 1_u64);
+        assert_arithmetic!(<<, 1, 64, 0_u64);
+        assert_arithmetic!(>>, 1, 64, 0_u64);
+        assert_arithmetic!(<<, 1, -1, 0_u64);
+        // Like MySQL, bitwise operations are evaluated on unsigned 64-bit integers
+        assert_arithmetic!(&, -1, 0xff_u64, 0xff_u64);
+        assert_arithmetic!(|, -2, 1, u64::MAX);
+        assert_eq!((&DfValue::None & &DfValue::Int(3)).unwrap(), DfValue::None);
+        assert_eq!((&DfValue::Int(3) | &DfValue::None).unwrap(), DfValue::None);
+
+        (&DfValue::Double(1.5) & &DfValue::Int(1)).unwrap_err();
+        (&DfValue::Int(1) | &DfValue::from(Decimal::ONE)).unwrap_err();
+        (&DfValue::from("a") ^ &DfValue::Int(1)).unwrap_err();
+        (&DfValue::Int(1) << &DfValue::Float(1.0)).unwrap_err();
+    }
+
+    #[test]
+    fn numeric_arithmetic_does_not_drift() {
+        let tenth = DfValue::from(Decimal::new(1, 1));
+        let mut sum = DfValue::from(Decimal::ZERO);
+        for _ in 0..10 {
+            sum = (&sum + &tenth).unwrap();
+        }
+        assert_eq!(sum, DfValue::from(Decimal::ONE));
+
+        let a = DfValue::from("123456789012345678.0123456789".parse::<Decimal>().unwrap());
+        let b = DfValue::from("0.0000000001".parse::<Decimal>().unwrap());
+        let res = (&a + &b).unwrap();
+        assert_eq!(
+            res,
+            DfValue::from("123456789012345678.0123456790".parse::<Decimal>().unwrap())
+        );
+        assert_eq!(res.to_string(), "123456789012345678.0123456790");
+
+        // Mixing a numeric with a float promotes the float, rather than lowering the numeric
+        assert!(matches!(
+            (&DfValue::Double(0.5) + &a).unwrap(),
+            DfValue::Numeric(_)
+        ));
+        assert!(matches!(
+            (&DfValue::Float(0.5) * &a).unwrap(),
+            DfValue::Numeric(_)
+        ));
+
+        // Floats that can't be represented as a decimal give NULL, like overflows do
+        assert_eq!((&DfValue::Double(f64::NAN) + &a).unwrap(), DfValue::None);
+        assert_eq!(
+            (&a * &DfValue::Double(f64::INFINITY)).unwrap(),
+            DfValue::None
+        );
+        assert_eq!((&DfValue::Double(1e300) - &a).unwrap(), DfValue::None);
+        assert_eq!((&a / &DfValue::Float(f32::MAX)).unwrap(), DfValue::None);
+    }
+
     #[test]
     fn invalid_arithmetic_returns_error() {
         (&DfValue::from(0) + &DfValue::from("abc")).unwrap_err();
@@ -2476,6 +2904,8 @@ mod tests {
             FixedOffset::west(18_000)
                 .from_utc_datetime(&NaiveDateTime::from_timestamp(1, 42_000_000)),
         );
+        let mysql_time = DfValue::Time(MySqlTime::from_hmsus(false, 30, 1, 2, 345_678));
+        let mysql_time2 = DfValue::Time(MySqlTime::from_hmsus(true, 30, 1, 2, 345_678));
         let shrt = DfValue::Int(5);
         let shrt6 = DfValue::Int(6);
         let ushrt = DfValue::UnsignedInt(5);
@@ -2497,6 +2927,7 @@ mod tests {
         assert_eq!(f(&double_from_real), f(&double_from_real));
         assert_eq!(f(&time), f(&time));
         assert_eq!(f(&timestamp_tz), f(&timestamp_tz));
+        assert_eq!(f(&mysql_time), f(&mysql_time));
         assert_eq!(f(&bytes), f(&bytes));
         assert_eq!(f(&bits), f(&bits));
 
@@ -2588,6 +3019,12 @@ mod tests {
         assert_ne!(f(&double_from_real), f(&ushrt));
         assert_ne!(f(&numeric), f(&ushrt));
 
+        assert_ne!(f(&mysql_time), f(&mysql_time2));
+        assert_ne!(f(&mysql_time), f(&txt1));
+        assert_ne!(f(&mysql_time), f(&time));
+        assert_ne!(f(&mysql_time), f(&shrt));
+        assert_ne!(f(&mysql_time), f(&double));
+
         assert_ne!(f(&time), f(&time2));
         assert_ne!(f(&time), f(&txt1));
         assert_ne!(f(&time), f(&txt2));
@@ -2773,6 +3210,31 @@ mod tests {
         _data_type_conversion_test_eq_i128(&ubigint_u64_max);
     }
 
+    #[test]
+    fn unsigned_bigint_max_to_i64_is_an_error() {
+        let res = i64::try_from(&DfValue::UnsignedInt(u64::MAX));
+        assert!(matches!(
+            res,
+            Err(ReadySetError::DfValueConversionError { ref target_type, .. })
+                if target_type == "i64"
+        ));
+    }
+
+    #[test]
+    fn mixed_sign_integers_compare_as_i128() {
+        assert_eq!(
+            DfValue::UnsignedInt(u64::MAX),
+            DfValue::UnsignedInt(u64::MAX)
+        );
+        assert_ne!(DfValue::UnsignedInt(u64::MAX), DfValue::Int(-1));
+        assert_eq!(DfValue::UnsignedInt(5), DfValue::Int(5));
+        assert!(DfValue::UnsignedInt(u64::MAX) > DfValue::Int(i64::MAX));
+        assert!(DfValue::Int(i64::MIN) < DfValue::UnsignedInt(0));
+        assert!(DfValue::Float(-1.0) < DfValue::UnsignedInt(0));
+        assert!(DfValue::Double(1e20) > DfValue::UnsignedInt(u64::MAX));
+        assert!(DfValue::Int(-1) < DfValue::from(Decimal::from(0)));
+    }
+
     #[proptest]
     fn data_type_string_conversion_roundtrip(s: String) {
         assert_eq!(
@@ -3108,6 +3570,33 @@ mod tests {
         assert_eq!(numeric2.cmp(&int1), Ordering::Less);
     }
 
+    #[test]
+    fn coerce_for_table_op_applies_column_collation() {
+        let hash = |dt: &DfValue| {
+            use std::collections::hash_map::DefaultHasher;
+            use std::hash::{Hash, Hasher};
+            let mut s = DefaultHasher::new();
+            dt.hash(&mut s);
+            s.finish()
+        };
+
+        let col_ty = DfType::Text(Collation::Citext);
+        let mut val = DfValue::from("ABC");
+        val.maybe_coerce_for_table_op(&col_ty).unwrap();
+        assert_eq!(val.collation(), Some(Collation::Citext));
+
+        let key = DfValue::from("abc")
+            .coerce_to(&col_ty, &DfType::Unknown)
+            .unwrap();
+        assert_eq!(val, key);
+        assert_eq!(hash(&val), hash(&key));
+
+        let mut val = DfValue::from("ABC");
+        val.maybe_coerce_for_table_op(&DfType::VarChar(10, Collation::default()))
+            .unwrap();
+        assert_ne!(val, DfValue::from("abc"));
+    }
+
     #[test]
     fn array_sql_type() {
         let arr = DfValue::from(vec![DfValue::None, DfValue::from(1)]);
@@ -3153,6 +3642,34 @@ mod tests {
             assert_eq!(result, expected);
         }
 
+        #[test]
+        fn parse_timestamps_in_alternate_formats() {
+            let subsecond_digits = Dialect::DEFAULT_MYSQL.default_subsecond_digits();
+            let expected = DfValue::from(NaiveDate::from_ymd(2024, 1, 1).and_hms(12, 34, 56));
+            for input in [
+                "2024-01-01T12:34:56",
+                "2024/01/01 12:34:56",
+                "20240101123456",
+            ] {
+                for ty in [
+                    DfType::Timestamp { subsecond_digits },
+                    DfType::DateTime { subsecond_digits },
+                ] {
+                    assert_eq!(
+                        DfValue::from(input)
+                            .coerce_to(&ty, &DfType::Unknown)
+                            .unwrap(),
+                        expected,
+                        "input = {input:?}, ty = {ty:?}"
+                    );
+                }
+            }
+
+            assert!(DfValue::from("2024-01-01 25:00:00")
+                .coerce_to(&DfType::Timestamp { subsecond_digits }, &DfType::Unknown)
+                .is_err());
+        }
+
         #[proptest]
         fn parse_times(#[strategy(arbitrary_naive_time())] nt: NaiveTime) {
             let subsecond_digits = Dialect::DEFAULT_MYSQL.default_subsecond_digits();
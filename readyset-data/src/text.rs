@@ -11,6 +11,16 @@ use readyset_errors::{ReadySetError, ReadySetResult};
 
 use crate::{Array, Collation, DfType, DfValue};
 
+/// The maximum length, in bytes, of a string that can be stored inline in a [`TinyText`].
+///
+/// This is the largest width that keeps [`DfValue`] 16 bytes long: the enum's discriminant and the
+/// [`LenAndCollation`] byte take up one byte each, leaving 14 bytes for the string itself. Making
+/// this any larger (even by a single byte) would grow every `DfValue` to 24 bytes, which costs far
+/// more memory across materialized state than the allocations it would save for strings of that
+/// length.
+///
+/// Strings up to this length are deserialized as [`TinyText`], and longer strings as [`Text`], so
+/// changing this does not change the serialized representation of either.
 pub(crate) const TINYTEXT_WIDTH: usize = 14;
 
 /// A nibble of [`Collation`], and a nibble of length (since length can never be greater than
@@ -334,6 +344,17 @@ pub(crate) trait TextCoerce: Sized + Clone + Into<DfValue> {
     /// Print the DfValue name for error reporting
     fn type_name() -> String;
 
+    /// Convert this value to a [`DfValue`] with the given collation, reusing the existing value
+    /// (rather than allocating a new string) if it already has that collation
+    fn with_collation(&self, str: &str, collation: Collation) -> DfValue {
+        let val: DfValue = self.clone().into();
+        if val.collation() == Some(collation) {
+            val
+        } else {
+            DfValue::from_str_and_collation(str, collation)
+        }
+    }
+
     /// A convenience constructor for a coerction error from this type
     fn coerce_err<D: ToString>(ty: &DfType, deets: D) -> ReadySetError {
         ReadySetError::DfValueConversionError {
@@ -372,31 +393,34 @@ pub(crate) trait TextCoerce: Sized + Clone + Into<DfValue> {
                 Ok(DfValue::from_str_and_collation(self.try_str()?, collation))
             }
 
-            DfType::VarChar(l, ..) if l as usize >= str.len() => {
+            DfType::VarChar(l, collation) if l as usize >= str.len() => {
                 // VarChar, but length is sufficient to store current string
-                Ok(self.clone().into())
+                Ok(self.with_collation(str, collation))
             }
 
-            DfType::Char(l, ..) if l as usize == str.len() => {
+            DfType::Char(l, collation, _) if l as usize == str.len() => {
                 // Char, but length is same as current string
-                Ok(self.clone().into())
+                Ok(self.with_collation(str, collation))
             }
 
-            DfType::Char(l, ..) if l as usize > str.len() => {
+            DfType::Char(l, collation, _) if l as usize > str.len() => {
                 // Char, but length is greater than the current string, have to pad with whitespace
                 let mut new_string = String::with_capacity(l as usize);
                 new_string += str;
                 new_string.extend(std::iter::repeat(' ').take(l as usize - str.len()));
-                Ok(DfValue::from(new_string))
+                Ok(DfValue::from_str_and_collation(&new_string, collation))
             }
 
-            DfType::VarChar(l, ..) | DfType::Char(l, ..) => {
+            DfType::VarChar(l, collation) | DfType::Char(l, collation, _) => {
                 // String is too long, so have to truncate and allocate a new one
                 // TODO: can we do something smarter, like keep a len field, and clone the existing
                 // Arc?
                 // TODO: avoiding the extra String allocation here would be *nice*, but it's
                 // annoying
-                Ok(DfValue::from(str.chars().take(l as _).collect::<String>()))
+                Ok(DfValue::from_str_and_collation(
+                    &str.chars().take(l as _).collect::<String>(),
+                    collation,
+                ))
             }
 
             DfType::Blob => Ok(DfValue::ByteArray(str.as_bytes().to_vec().into())),
@@ -426,10 +450,14 @@ pub(crate) trait TextCoerce: Sized + Clone + Into<DfValue> {
                 ))
             }
 
-            DfType::Timestamp { .. }
-            | DfType::DateTime { .. }
-            | DfType::TimestampTz { .. }
-            | DfType::Date => str
+            DfType::Timestamp { .. } | DfType::DateTime { .. } => {
+                Into::<DfValue>::into(self.clone())
+                    .try_into_timestamp()
+                    .map_err(|e| Self::coerce_err(to_ty, e))
+                    .map(DfValue::from)
+            }
+
+            DfType::TimestampTz { .. } | DfType::Date => str
                 .trim()
                 .parse::<crate::TimestampTz>()
                 .map_err(|e| Self::coerce_err(to_ty, e))
@@ -620,6 +648,42 @@ mod tests {
         assert_eq!(tt.as_str(), s);
     }
 
+    #[test]
+    fn tiny_text_width_boundary() {
+        let at_width = "a".repeat(TINYTEXT_WIDTH);
+        let tt = TinyText::try_from(at_width.as_str()).unwrap();
+        assert_eq!(tt.as_str(), at_width);
+        assert_eq!(tt.len() as usize, TINYTEXT_WIDTH);
+        assert!(matches!(
+            DfValue::from(at_width.as_str()),
+            DfValue::TinyText(_)
+        ));
+
+        let over_width = "a".repeat(TINYTEXT_WIDTH + 1);
+        assert!(TinyText::try_from(over_width.as_str()).is_err());
+        assert!(TinyText::from_slice(over_width.as_bytes()).is_err());
+        assert!(matches!(
+            DfValue::from(over_width.as_str()),
+            DfValue::Text(_)
+        ));
+    }
+
+    #[test]
+    fn tiny_text_width_boundary_serde_round_trip() {
+        for len in [TINYTEXT_WIDTH - 1, TINYTEXT_WIDTH, TINYTEXT_WIDTH + 1] {
+            let s = "a".repeat(len);
+            let value = DfValue::from_str_and_collation(&s, Collation::Citext);
+            let serialized = bincode::serialize(&value).unwrap();
+            let deserialized: DfValue = bincode::deserialize(&serialized).unwrap();
+            assert_eq!(<&str>::try_from(&deserialized).unwrap(), s);
+            assert_eq!(deserialized.collation(), Some(Collation::Citext));
+            assert_eq!(
+                matches!(deserialized, DfValue::TinyText(_)),
+                len <= TINYTEXT_WIDTH
+            );
+        }
+    }
+
     #[proptest]
     fn text_str_round_trip(s: String) {
         let t: Text = s.as_str().into();
@@ -647,6 +711,25 @@ mod tests {
         TinyText::from_slice(&s).expect("ok");
     }
 
+    #[test]
+    fn char_coercion_reuses_value_with_same_collation() {
+        let text = DfValue::from("abcdefghijklmnopqrstuvwxyz");
+        let ptr = <&str>::try_from(&text).unwrap().as_ptr();
+        for ty in [
+            DfType::VarChar(30, Collation::default()),
+            DfType::Char(26, Collation::default(), Dialect::DEFAULT_MYSQL),
+        ] {
+            let coerced = text.coerce_to(&ty, &DfType::Unknown).unwrap();
+            assert_eq!(coerced, text);
+            assert_eq!(<&str>::try_from(&coerced).unwrap().as_ptr(), ptr);
+        }
+
+        let coerced = text
+            .coerce_to(&DfType::VarChar(30, Collation::Citext), &DfType::Unknown)
+            .unwrap();
+        assert_eq!(coerced.collation(), Some(Collation::Citext));
+    }
+
     #[test]
     fn text_coercion() {
         // TEXT to TEXT coercions
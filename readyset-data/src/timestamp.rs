@@ -24,6 +24,14 @@ pub const TIMESTAMP_TZ_FORMAT: &str = "%Y-%m-%d %H:%M:%S%:z";
 /// The format for dates when parsed as text
 pub const DATE_FORMAT: &str = "%Y-%m-%d";
 
+/// Alternative formats for timestamps without time zone, which are tried in order when parsing
+/// text that doesn't match [`TIMESTAMP_PARSE_FORMAT`]
+const ALTERNATE_TIMESTAMP_PARSE_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y/%m/%d %H:%M:%S%.f",
+    "%Y%m%d%H%M%S",
+];
+
 /// An optimized storage for date and datetime SQL formats. The possible inner data
 /// may be:
 ///
@@ -74,9 +82,13 @@ impl TimestampTz {
         self.extra[2] & TimestampTz::TIMEZONE_FLAG != 0
     }
 
-    /// Returns true if should be displayed as date only
+    /// Returns true if this timestamp represents a value of a SQL `DATE` column, and so should be
+    /// displayed as a date only, without a time component.
+    ///
+    /// The date-only marker does not affect comparison or hashing: a date compares equal to a
+    /// timestamp at midnight on the same day.
     #[inline(always)]
-    fn has_date_only(&self) -> bool {
+    pub fn has_date_only(&self) -> bool {
         self.extra[2] & TimestampTz::DATE_FLAG != 0
     }
 
@@ -309,6 +321,11 @@ impl TimestampTz {
                 dt.into()
             } else if let Ok(dt) = NaiveDateTime::parse_from_str(ts, TIMESTAMP_PARSE_FORMAT) {
                 dt.into()
+            } else if let Some(dt) = ALTERNATE_TIMESTAMP_PARSE_FORMATS
+                .iter()
+                .find_map(|fmt| NaiveDateTime::parse_from_str(ts, fmt).ok())
+            {
+                dt.into()
             } else {
                 NaiveDate::parse_from_str(ts, DATE_FORMAT)?.into()
             },
@@ -471,6 +488,30 @@ mod tests {
     use super::*;
     use crate::{Collation, DfType};
 
+    #[test]
+    fn date_coercion_drops_time_component() {
+        // MySQL sends DATE values as a DATETIME at midnight
+        let midnight =
+            DfValue::try_from(mysql_common::value::Value::Date(2024, 1, 1, 0, 0, 0, 0)).unwrap();
+        assert_eq!(midnight.to_string(), "2024-01-01 00:00:00");
+
+        let date = midnight.coerce_to(&DfType::Date, &DfType::Unknown).unwrap();
+        assert!(matches!(&date, DfValue::TimestampTz(ts) if ts.has_date_only()));
+        assert_eq!(date.to_string(), "2024-01-01");
+        assert_eq!(
+            date.coerce_to(&DfType::DEFAULT_TEXT, &DfType::Unknown)
+                .unwrap(),
+            DfValue::from("2024-01-01")
+        );
+
+        // The date-only marker doesn't affect comparisons
+        let literal = DfValue::from("2024-01-01")
+            .coerce_to(&DfType::Date, &DfType::DEFAULT_TEXT)
+            .unwrap();
+        assert_eq!(date, literal);
+        assert_eq!(date, midnight);
+    }
+
     #[test]
     fn timestamp_coercion() {
         let ts =
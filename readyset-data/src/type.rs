@@ -2,7 +2,7 @@ use std::fmt;
 
 use enum_kinds::EnumKind;
 use itertools::Itertools;
-use nom_sql::{EnumVariants, Relation, SqlIdentifier, SqlType};
+use nom_sql::{ColumnSpecification, EnumVariants, Relation, SqlIdentifier, SqlType};
 use readyset_errors::{unsupported_err, ReadySetResult};
 use serde::{Deserialize, Serialize};
 
@@ -300,6 +300,27 @@ impl DfType {
                 .ok_or_else(|| unsupported_err!("Unsupported type: {id}"))?,
         })
     }
+
+    /// Converts the type of the column described by `spec` to a [`DfType`], as with
+    /// [`DfType::from_sql_type`], additionally taking into account the collation declared for
+    /// the column, if any.
+    ///
+    /// Collations that aren't supported (see [`Collation::from_name`]) are ignored, and the
+    /// column is given the default collation for its type.
+    pub fn from_column_spec<R>(
+        spec: &ColumnSpecification,
+        dialect: Dialect,
+        resolve_custom_type: R,
+    ) -> ReadySetResult<Self>
+    where
+        R: Fn(Relation) -> Option<DfType>,
+    {
+        let ty = Self::from_sql_type(&spec.sql_type, dialect, resolve_custom_type)?;
+        Ok(match spec.collation().and_then(Collation::from_name) {
+            Some(collation) => ty.with_collation(collation),
+            None => ty,
+        })
+    }
 }
 
 impl DfType {
@@ -369,6 +390,28 @@ impl DfType {
         }
     }
 
+    /// Returns the collation of this type if it is a string type, otherwise [`None`].
+    #[inline]
+    pub fn collation(&self) -> Option<Collation> {
+        match *self {
+            Self::Text(collation) | Self::Char(_, collation, _) | Self::VarChar(_, collation) => {
+                Some(collation)
+            }
+            _ => None,
+        }
+    }
+
+    /// If this is a string type, returns the same type with the given collation, otherwise
+    /// returns this type unchanged.
+    pub fn with_collation(self, collation: Collation) -> Self {
+        match self {
+            Self::Text(_) => Self::Text(collation),
+            Self::Char(len, _, dialect) => Self::Char(len, collation, dialect),
+            Self::VarChar(len, _) => Self::VarChar(len, collation),
+            ty => ty,
+        }
+    }
+
     /// Converts the type to an [`Option`] where [`DfType::Unknown`] becomes [`None`].
     #[inline]
     pub fn try_into_known(self) -> Option<Self> {
@@ -441,6 +484,12 @@ impl DfType {
         )
     }
 
+    /// Returns `true` if this is any integer, floating-point, or fixed-point numeric type.
+    #[inline]
+    pub fn is_numeric(&self) -> bool {
+        self.is_any_int() || self.is_any_float() || matches!(self, Self::Numeric { .. })
+    }
+
     /// Returns `true` if this is any `text` type
     #[inline]
     pub fn is_any_text(&self) -> bool {
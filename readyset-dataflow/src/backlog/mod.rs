@@ -1,7 +1,9 @@
 use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::ops::Bound;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use ahash::RandomState;
 use common::SizeOf;
@@ -156,6 +158,7 @@ fn new_inner(
         post_lookup: post_processing,
         receiver,
         eviction_epoch: 0,
+        last_read: Default::default(),
     };
 
     (r, w)
@@ -333,6 +336,33 @@ impl WriteHandle {
         bytes_to_be_freed
     }
 
+    /// Evict keys from state until it uses no more than `max_bytes`, returning the number of bytes
+    /// freed. Unlike [`evict_bytes`](Self::evict_bytes), this always evicts at least one key if
+    /// the state is over the limit, so it's guaranteed to get under the limit eventually.
+    pub(crate) fn evict_to_size(&mut self, max_bytes: usize) -> u64 {
+        let mut freed = 0;
+        while self.mem_size > max_bytes {
+            // Keys evicted by the previous iteration are only removed from `len` once published
+            self.swap();
+            let len = self.len();
+            if len == 0 {
+                break;
+            }
+
+            let avg_key_size = (self.mem_size + len - 1) / len;
+            let keys_to_evict = ((self.mem_size - max_bytes) / avg_key_size).max(1);
+            // Add half a key to the ratio so that the number of keys the map computes from it
+            // doesn't get rounded down below `keys_to_evict`
+            let evicted = self.handle.evict((keys_to_evict as f64 + 0.5) / len as f64);
+            if evicted == 0 {
+                break;
+            }
+            self.mem_size = self.mem_size.saturating_sub(evicted as usize);
+            freed += evicted;
+        }
+        freed
+    }
+
     pub(crate) fn mark_hole(&mut self, key: &KeyComparison) -> ReadySetResult<()> {
         if let Some(len) = key.len() {
             invariant_eq!(len, self.index.len());
@@ -438,6 +468,9 @@ pub struct SingleReadHandle {
     receiver: ReaderUpdatedNotifier,
     /// Caches the eviction epoch of the associated [`WriteHandle`]
     eviction_epoch: usize,
+    /// The time of the last read from the reader, in milliseconds since the unix epoch, or 0 if
+    /// it has never been read from. Shared between all clones of the handle.
+    last_read: Arc<AtomicU64>,
 }
 
 impl Clone for SingleReadHandle {
@@ -449,6 +482,7 @@ impl Clone for SingleReadHandle {
             post_lookup: self.post_lookup.clone(),
             receiver: self.receiver.resubscribe(),
             eviction_epoch: self.eviction_epoch,
+            last_read: Arc::clone(&self.last_read),
         }
     }
 }
@@ -464,6 +498,24 @@ impl std::fmt::Debug for SingleReadHandle {
 }
 
 impl SingleReadHandle {
+    /// Record that a client has just read from the reader
+    pub fn mark_read(&self) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        self.last_read
+            .store(now.as_millis() as u64, AtomicOrdering::Relaxed);
+    }
+
+    /// Returns the time of the last read from the reader, by a client using any handle to it, or
+    /// `None` if it has never been read from
+    pub fn last_read(&self) -> Option<SystemTime> {
+        match self.last_read.load(AtomicOrdering::Relaxed) {
+            0 => None,
+            millis => Some(UNIX_EPOCH + Duration::from_millis(millis)),
+        }
+    }
+
     /// Trigger a replay of a missing key from a partially materialized view.
     pub fn trigger<I>(&self, keys: I) -> bool
     where
@@ -731,6 +783,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn evict_to_size_evicts_least_recently_used() {
+        let (r, mut w) = new_partial(
+            1,
+            Index::hash_map(vec![0]),
+            |_: &mut dyn Iterator<Item = KeyComparison>| true,
+            EvictionKind::LRU,
+            ReaderProcessing::default(),
+        );
+        for i in 0..20 {
+            let key = vec1![DfValue::from(i)];
+            w.mark_filled(key.into()).unwrap();
+            w.add(vec![Record::Positive(vec![DfValue::from(i)])]);
+        }
+        w.swap();
+
+        // Read the last few keys we inserted, then the first few, so the keys in the middle are
+        // the coldest
+        let hot = [17, 18, 19, 0, 1, 2];
+        for i in hot {
+            r.get(&[DfValue::from(i)]).unwrap();
+        }
+
+        let max_bytes = w.deep_size_of() as usize / 2;
+        assert!(w.evict_to_size(max_bytes) > 0);
+        w.swap();
+        assert!(w.deep_size_of() as usize <= max_bytes);
+
+        for i in hot {
+            r.get(&[DfValue::from(i)]).unwrap();
+        }
+        assert!(r.get(&[DfValue::from(3)]).err().unwrap().is_miss());
+    }
+
     mod mark_filled {
         use super::*;
 
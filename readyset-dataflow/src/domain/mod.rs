@@ -25,6 +25,7 @@ use petgraph::graph::NodeIndex;
 use readyset::internal::Index;
 use readyset::replication::ReplicationOffset;
 use readyset::{channel, internal, KeyComparison, KeyCount, ReaderAddress, ReadySetError};
+use readyset_data::DfType;
 use readyset_errors::{internal, internal_err, ReadySetResult};
 use serde::{Deserialize, Serialize};
 use timekeeper::{RealTime, SimpleTracker, ThreadTime, Timer, TimerSet};
@@ -432,6 +433,7 @@ impl DomainBuilder {
 
             eviction_kind: self.config.eviction_kind,
             remapped_keys: Default::default(),
+            base_rows_affected: 0,
         }
     }
 }
@@ -633,6 +635,10 @@ pub struct Domain {
 
     metrics: domain_metrics::DomainMetrics,
     eviction_kind: crate::EvictionKind,
+
+    /// The number of existing rows matched by writes to base nodes while handling the current
+    /// packet, reported back to the client in the packet's ack
+    base_rows_affected: u64,
 }
 
 impl Domain {
@@ -1064,9 +1070,7 @@ impl Domain {
             self.process_times.start(me);
             self.process_ptimes.start(me);
             let mut m = Some(m);
-            let NodeProcessingResult {
-                misses, captured, ..
-            } = n.process(
+            let res = n.process(
                 &mut m,
                 None,
                 None,
@@ -1079,10 +1083,19 @@ impl Domain {
                     shard: self.shard,
                     replica: self.replica,
                 },
-            )?;
-            assert_eq!(captured.len(), 0);
+            );
             self.process_ptimes.stop();
             self.process_times.stop();
+            // Base nodes can reject writes (eg with `DuplicateKey`) without any state having been
+            // changed, so stop the timers before returning the error
+            let NodeProcessingResult {
+                misses,
+                captured,
+                rows_affected,
+                ..
+            } = res?;
+            assert_eq!(captured.len(), 0);
+            self.base_rows_affected += rows_affected;
 
             if m.is_none() {
                 // no need to deal with our children if we're not sending them anything
@@ -1373,6 +1386,27 @@ impl Domain {
                     .set_column_type(column, new_type)?;
                 Ok(None)
             }
+            DomainRequest::SetReaderMaxBytes { node, max_bytes } => {
+                trace!(%node, %max_bytes, "Setting reader max bytes");
+                if self
+                    .reader_write_handles
+                    .get(node)
+                    .map_or(false, |state| !state.is_partial())
+                {
+                    unsupported!("max_mem can only be set on partially materialized caches");
+                }
+                self.nodes
+                    .get(node)
+                    .ok_or_else(|| ReadySetError::NoSuchNode(node.id()))?
+                    .borrow_mut()
+                    .as_mut_reader()
+                    .ok_or(ReadySetError::InvalidNodeType {
+                        node_index: node.id(),
+                        expected_type: NodeType::Reader,
+                    })?
+                    .set_max_bytes(max_bytes);
+                Ok(None)
+            }
             DomainRequest::AddEgressTx {
                 egress_node,
                 ingress_node: (ingress_node_global, ingress_node_local),
@@ -1608,7 +1642,13 @@ impl Domain {
                                     })
                                 }
                             },
-                            self.eviction_kind,
+                            // Readers with a memory limit always evict their least recently used
+                            // keys when they exceed it
+                            if r.max_bytes().is_some() {
+                                crate::EvictionKind::LRU
+                            } else {
+                                self.eviction_kind
+                            },
                             r.reader_processing().clone(),
                         );
 
@@ -3983,6 +4023,77 @@ impl Domain {
         })
     }
 
+    /// Look up all the rows in the state of the base node `node` whose primary key is equal to
+    /// `key`, to respond to a [`PacketPayload::Lookup`] sent by a base table handle.
+    ///
+    /// Values for any columns that have since been dropped from the base table are omitted from
+    /// the returned rows.
+    pub fn lookup_base_rows(
+        &self,
+        node: LocalNodeIndex,
+        key: Vec<DfValue>,
+    ) -> ReadySetResult<Vec<Vec<DfValue>>> {
+        let n = self
+            .nodes
+            .get(node)
+            .ok_or_else(|| ReadySetError::NoSuchNode(node.id()))?
+            .borrow();
+        let base = n.get_base().ok_or(ReadySetError::InvalidNodeType {
+            node_index: node.id(),
+            expected_type: NodeType::Base,
+        })?;
+        let columns = base
+            .primary_key()
+            .ok_or_else(|| ReadySetError::NoPrimaryKey {
+                table: n.name().name.to_string(),
+            })?;
+        if columns.len() != key.len() {
+            return Err(ReadySetError::WrongKeyColumnCount(columns.len(), key.len()));
+        }
+
+        // Base table state is keyed on the types of the columns, so make sure the key matches
+        let key = columns
+            .iter()
+            .zip(key)
+            .map(|(&col, value)| {
+                let ty = n
+                    .columns()
+                    .get(col)
+                    .ok_or_else(|| internal_err!("Primary key column {col} out of bounds"))?
+                    .ty();
+                value.coerce_to(ty, &DfType::Unknown)
+            })
+            .collect::<ReadySetResult<Vec<_>>>()?;
+
+        let state = self
+            .state
+            .get(node)
+            .ok_or_else(|| internal_err!("Base node {} has no state", node))?;
+        let records = match state.lookup(columns, &PointKey::from(key)) {
+            LookupResult::Some(records) => records,
+            LookupResult::Missing => internal!("Base node state can't be partial"),
+        };
+
+        let dropped = base.get_dropped();
+        Ok(records
+            .into_iter()
+            .map(|r| {
+                r.into_owned()
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, _)| !dropped.contains_key(*i))
+                    .map(|(_, v)| v)
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Return the number of existing rows matched by writes to base nodes while handling the last
+    /// packet, and reset it to zero
+    pub fn take_base_rows_affected(&mut self) -> u64 {
+        mem::take(&mut self.base_rows_affected)
+    }
+
     /// Handle a single message for this domain
     #[failpoint("handle-packet")]
     pub fn handle_packet(
@@ -3994,6 +4105,8 @@ impl Domain {
             self.wait_time.stop();
         }
 
+        self.base_rows_affected = 0;
+
         self.handle(packet, executor)?;
         // After we handle an external packet, the domain may have accumulated a bunch of packets to
         // itself we need to process them all next;
@@ -4002,6 +4115,8 @@ impl Domain {
             self.handle(message, executor)?;
         }
 
+        self.evict_oversized_readers()?;
+
         if self.aggressively_update_state_sizes {
             self.update_state_sizes();
         }
@@ -4013,6 +4128,38 @@ impl Domain {
         Ok(())
     }
 
+    /// Evict the least recently used keys from any partial readers whose state has grown past
+    /// their configured maximum size (see
+    /// [`Reader::max_bytes`](crate::node::special::Reader::max_bytes)), until they're back under it
+    fn evict_oversized_readers(&mut self) -> ReadySetResult<()> {
+        for (node, state) in self.reader_write_handles.iter_mut() {
+            if !state.is_partial() {
+                continue;
+            }
+            #[allow(clippy::indexing_slicing)] // reader_write_handles keys are all in self.nodes
+            let max_bytes = match self.nodes[node]
+                .borrow()
+                .as_reader()
+                .and_then(|r| r.max_bytes())
+            {
+                Some(max_bytes) => max_bytes,
+                None => continue,
+            };
+            if state.deep_size_of() as usize <= max_bytes {
+                continue;
+            }
+
+            let start = time::Instant::now();
+            let freed = state.evict_to_size(max_bytes);
+            state.swap();
+            state.notify_readers_of_eviction()?;
+            debug!(%freed, ?node, max_bytes, "evicted from reader over its memory limit");
+            self.state_size.fetch_sub(freed as usize, Ordering::AcqRel);
+            self.metrics.rec_eviction_time(start.elapsed(), freed);
+        }
+        Ok(())
+    }
+
     /// Handle an expired timeout from `next_poll_duration`
     pub fn handle_timeout(&mut self) -> ReadySetResult<()> {
         if self.wait_time.is_running() {
@@ -56,8 +56,8 @@ pub type Readers = Arc<Mutex<ReaderMap>>;
 pub type DomainConfig = domain::Config;
 
 pub use dataflow_expression::{
-    BinaryOperator, BuiltinFunction, Expr, LowerContext, PostLookup, PostLookupAggregate,
-    PostLookupAggregateFunction, PostLookupAggregates, ReaderProcessing,
+    BinaryOperator, BuiltinFunction, ConstantColumn, Expr, LowerContext, PostLookup,
+    PostLookupAggregate, PostLookupAggregateFunction, PostLookupAggregates, ReaderProcessing,
 };
 pub use dataflow_state::{DurabilityMode, PersistenceParameters};
 
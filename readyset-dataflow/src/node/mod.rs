@@ -59,7 +59,7 @@ impl Column {
     {
         Ok(Self::new(
             spec.column.name,
-            DfType::from_sql_type(&spec.sql_type, dialect, resolve_type)?,
+            DfType::from_column_spec(&spec, dialect, resolve_type)?,
             spec.column.table,
         ))
     }
@@ -26,6 +26,9 @@ pub(crate) struct NodeProcessingResult {
 
     /// Keys for replays captured during processing
     pub(crate) captured: HashSet<KeyComparison>,
+
+    /// The number of existing rows matched by the writes to a base node
+    pub(crate) rows_affected: u64,
 }
 
 /// A helper struct that combines unique misses for the same columns in the same node
@@ -155,6 +158,7 @@ impl Node {
                             records: mut rs,
                             replication_offset,
                             set_snapshot_mode,
+                            rows_affected,
                         } = b.process(addr, &self.columns, data, &*env.state, snapshot_mode)?;
 
                         if let (Some(SetSnapshotMode::EnterSnapshotMode), Some(s)) = (
@@ -188,6 +192,11 @@ impl Node {
                             data: rs,
                             trace,
                         }));
+
+                        return Ok(NodeProcessingResult {
+                            rows_affected,
+                            ..Default::default()
+                        });
                     }
                     Some(ref p) => {
                         // TODO: replays?
@@ -418,6 +427,7 @@ impl Node {
                     misses,
                     lookups,
                     captured,
+                    ..Default::default()
                 });
             }
             NodeType::Dropped => {
@@ -863,6 +873,7 @@ pub mod bench {
             ],
             lookups: vec![],
             captured: HashSet::new(),
+            rows_affected: 0,
         };
 
         c.bench_function("unique_misses", |b| {
@@ -43,6 +43,10 @@ pub struct BaseWrite {
 
     /// Optionally enter or exit the snapshot mode for this table
     pub set_snapshot_mode: Option<SetSnapshotMode>,
+
+    /// The number of existing rows matched by the `Update`, `DeleteByKey` and `DeleteRow`
+    /// operations in this batch
+    pub rows_affected: u64,
 }
 
 impl From<Records> for BaseWrite {
@@ -51,6 +55,7 @@ impl From<Records> for BaseWrite {
             records,
             replication_offset: None,
             set_snapshot_mode: None,
+            rows_affected: 0,
         }
     }
 }
@@ -189,7 +194,7 @@ impl Default for Base {
 
 fn key_val(i: usize, col: usize, r: &TableOperation) -> Option<&DfValue> {
     match *r {
-        TableOperation::Insert(ref row) => Some(&row[col]),
+        TableOperation::Insert(ref row) | TableOperation::InsertUnique(ref row) => Some(&row[col]),
         TableOperation::DeleteByKey { ref key } => Some(&key[i]),
         TableOperation::DeleteRow { ref row } => Some(&row[col]),
         TableOperation::Update { ref key, .. } => Some(&key[i]),
@@ -216,11 +221,52 @@ fn key_of<'a>(key_cols: &'a [usize], r: &'a TableOperation) -> impl Iterator<Ite
 ///
 /// Note that the actual type-specific logic is implemented as a [`DfValue`] method, so as to keep
 /// type logic out of the base node code.
-fn apply_table_op_coercions(op: &mut TableOperation, columns: &[Column]) -> ReadySetResult<()> {
-    if let TableOperation::Insert(vals) = op {
-        for (val, col) in vals.iter_mut().zip(columns) {
-            val.maybe_coerce_for_table_op(col.ty())?;
+fn apply_table_op_coercions(
+    op: &mut TableOperation,
+    columns: &[Column],
+    primary_key: Option<&[usize]>,
+) -> ReadySetResult<()> {
+    let coerce_key = |key: &mut Vec<DfValue>| -> ReadySetResult<()> {
+        if let Some(pk) = primary_key {
+            for (val, col) in key.iter_mut().zip(pk) {
+                if let Some(col) = columns.get(*col) {
+                    val.maybe_coerce_for_table_op(col.ty())?;
+                }
+            }
+        }
+        Ok(())
+    };
+
+    match op {
+        TableOperation::Insert(row)
+        | TableOperation::InsertUnique(row)
+        | TableOperation::DeleteRow { row } => {
+            for (val, col) in row.iter_mut().zip(columns) {
+                val.maybe_coerce_for_table_op(col.ty())?;
+            }
         }
+        TableOperation::InsertOrUpdate { row, update } => {
+            for (val, col) in row.iter_mut().zip(columns) {
+                val.maybe_coerce_for_table_op(col.ty())?;
+            }
+            for (modification, col) in update.iter_mut().zip(columns) {
+                if let Modification::Set(val) = modification {
+                    val.maybe_coerce_for_table_op(col.ty())?;
+                }
+            }
+        }
+        TableOperation::Update { update, key } => {
+            for (modification, col) in update.iter_mut().zip(columns) {
+                if let Modification::Set(val) = modification {
+                    val.maybe_coerce_for_table_op(col.ty())?;
+                }
+            }
+            coerce_key(key)?;
+        }
+        TableOperation::DeleteByKey { key } => coerce_key(key)?,
+        TableOperation::Truncate
+        | TableOperation::SetReplicationOffset(_)
+        | TableOperation::SetSnapshotMode(_) => {}
     }
 
     Ok(())
@@ -242,7 +288,8 @@ impl Base {
         let mut records = Vec::with_capacity(operations.len());
         for op in operations {
             match op {
-                TableOperation::Insert(mut row) => {
+                // Without a key, no two rows can be duplicates of each other
+                TableOperation::Insert(mut row) | TableOperation::InsertUnique(mut row) => {
                     self.fix(&mut row);
                     records.push(Record::Positive(row));
                 }
@@ -270,6 +317,7 @@ impl Base {
             records: records.into(),
             replication_offset,
             set_snapshot_mode,
+            rows_affected: 0,
         })
     }
 
@@ -285,7 +333,7 @@ impl Base {
     ) -> ReadySetResult<BaseWrite> {
         trace!(node = %our_index, base_ops = ?ops);
         for op in ops.iter_mut() {
-            apply_table_op_coercions(op, columns)?;
+            apply_table_op_coercions(op, columns, self.primary_key.as_deref())?;
         }
 
         let key_cols = match &self.primary_key {
@@ -369,6 +417,7 @@ impl Base {
             Inserted(Cow<'a, [DfValue]>),
         }
         let mut touched_keys: HashMap<Vec<DfValue>, TouchedKey> = HashMap::new();
+        let mut rows_affected = 0;
 
         for (key, ops) in &ops {
             // It is not enough to check the persisted value for the key, as it may have been
@@ -403,14 +452,34 @@ impl Base {
             let mut value = stored_value.clone();
 
             for op in ops {
+                if value.is_some()
+                    && matches!(
+                        op,
+                        TableOperation::Update { .. } | TableOperation::DeleteByKey { .. }
+                    )
+                {
+                    rows_affected += 1;
+                }
+
                 match op {
-                    TableOperation::Insert(row) if value.is_none() => value = Some(Cow::Owned(row)),
+                    TableOperation::Insert(row) | TableOperation::InsertUnique(row)
+                        if value.is_none() =>
+                    {
+                        value = Some(Cow::Owned(row))
+                    }
                     TableOperation::Insert(_) => {
                         failed_log.failed_insert();
                     }
+                    TableOperation::InsertUnique(_) => {
+                        // Fail the whole batch before any of it is materialized
+                        return Err(ReadySetError::DuplicateKey {
+                            key: key.iter().join("-"),
+                        });
+                    }
                     TableOperation::DeleteRow { row } if value == Some(Cow::Borrowed(&row)) => {
                         // Delete the row, but only if it fully matches the current row
                         value = None;
+                        rows_affected += 1;
                     }
                     TableOperation::DeleteRow { row } => {
                         failed_log.failed_delete(row, value.as_deref());
@@ -485,6 +554,7 @@ impl Base {
             records: results.into(),
             replication_offset,
             set_snapshot_mode,
+            rows_affected,
         })
     }
 
@@ -752,6 +822,72 @@ mod tests {
             test_lots_of_changes_in_same_batch(MaterializedNodeState::Persistent(state));
         }
 
+        #[test]
+        fn insert_unique_duplicate_key() {
+            let mut b = Base::new().with_primary_key([0]);
+            let ni = LocalNodeIndex::make(0u32);
+
+            let mut state = MaterializedNodeState::Memory(MemoryState::default());
+            state.add_key(Index::hash_map(vec![0]), None);
+            let mut states = StateMap::new();
+            states.insert(ni, state);
+
+            let mut rs = b
+                .process(
+                    ni,
+                    &[],
+                    vec![TableOperation::InsertUnique(vec![1.into(), 2.into()])],
+                    &states,
+                    SnapshotMode::SnapshotModeDisabled,
+                )
+                .unwrap()
+                .records;
+            crate::node::materialize(&mut rs, None, None, states.get_mut(ni));
+
+            // A row with the same key as a row that already exists
+            let err = b
+                .process(
+                    ni,
+                    &[],
+                    vec![
+                        TableOperation::InsertUnique(vec![2.into(), 2.into()]),
+                        TableOperation::InsertUnique(vec![1.into(), 3.into()]),
+                    ],
+                    &states,
+                    SnapshotMode::SnapshotModeDisabled,
+                )
+                .unwrap_err();
+            assert!(err.is_duplicate_key(), "err = {err}");
+
+            // Two rows with the same key in the same batch
+            let err = b
+                .process(
+                    ni,
+                    &[],
+                    vec![
+                        TableOperation::InsertUnique(vec![2.into(), 2.into()]),
+                        TableOperation::InsertUnique(vec![2.into(), 3.into()]),
+                    ],
+                    &states,
+                    SnapshotMode::SnapshotModeDisabled,
+                )
+                .unwrap_err();
+            assert!(err.is_duplicate_key(), "err = {err}");
+
+            // Plain inserts with a duplicate key are ignored rather than failing
+            let rs = b
+                .process(
+                    ni,
+                    &[],
+                    vec![TableOperation::Insert(vec![1.into(), 3.into()])],
+                    &states,
+                    SnapshotMode::SnapshotModeDisabled,
+                )
+                .unwrap()
+                .records;
+            assert!(rs.is_empty());
+        }
+
         #[test]
         fn delete_row_unkeyed() {
             let mut b = Base::new();
@@ -789,6 +925,7 @@ mod tests {
                     .into(),
                     replication_offset: None,
                     set_snapshot_mode: None,
+                    rows_affected: 0,
                 }
             )
         }
@@ -835,6 +972,7 @@ mod tests {
                     .into(),
                     replication_offset: None,
                     set_snapshot_mode: None,
+                    rows_affected: 1,
                 }
             )
         }
@@ -889,6 +1027,7 @@ mod tests {
                     .into(),
                     replication_offset: None,
                     set_snapshot_mode: None,
+                    rows_affected: 2,
                 }
             )
         }
@@ -937,7 +1076,8 @@ mod tests {
                     ]
                     .into(),
                     replication_offset: None,
-                    set_snapshot_mode: None
+                    set_snapshot_mode: None,
+                    rows_affected: 0,
                 }
             );
         }
@@ -25,6 +25,11 @@ pub struct Reader {
     ///
     /// The data is stored in this manner instead of in a Hashmap to support ordered iteration.
     placeholder_map: Vec<(ViewPlaceholder, KeyColumnIdx)>,
+
+    /// The maximum number of bytes this reader's state may grow to before the domain evicts its
+    /// least recently used keys to bring it back under the limit
+    #[serde(default)]
+    max_bytes: Option<usize>,
 }
 
 impl Clone for Reader {
@@ -34,6 +39,7 @@ impl Clone for Reader {
             reader_processing: self.reader_processing.clone(),
             index: self.index.clone(),
             placeholder_map: self.placeholder_map.clone(),
+            max_bytes: self.max_bytes,
         }
     }
 }
@@ -45,6 +51,7 @@ impl Reader {
             reader_processing,
             index: None,
             placeholder_map: Default::default(),
+            max_bytes: None,
         }
     }
 
@@ -60,6 +67,7 @@ impl Reader {
             reader_processing: self.reader_processing.clone(),
             index: self.index.clone(),
             placeholder_map: self.placeholder_map.clone(),
+            max_bytes: self.max_bytes,
         }
     }
 
@@ -93,6 +101,17 @@ impl Reader {
         }
     }
 
+    /// Returns the maximum number of bytes this reader's state may grow to, if it has one
+    pub fn max_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
+    /// Limit this reader's state to the given number of bytes, past which its least recently used
+    /// keys will be evicted
+    pub fn set_max_bytes(&mut self, max_bytes: usize) {
+        self.max_bytes = Some(max_bytes);
+    }
+
     /// Sets the placeholder to column mapping if it is not already set.
     ///
     /// We do not currently support multiple mappings from placeholders to key columns. That would
@@ -54,7 +54,7 @@ impl Ingredient for Filter {
     ) -> ReadySetResult<ProcessingResult> {
         let mut results = Vec::new();
         for r in rs {
-            if self.expression.eval(r.rec())?.is_truthy() {
+            if passes_filter(&self.expression.eval(r.rec())?) {
                 results.push(r);
             }
         }
@@ -97,7 +97,7 @@ impl Ingredient for Filter {
         match self.lookup(*self.src, columns, key, nodes, states, mode)? {
             IngredientLookupResult::Records(rs) => {
                 let f = self.expression.clone();
-                let filter = move |r: &[DfValue]| Ok(f.eval(r)?.is_truthy());
+                let filter = move |r: &[DfValue]| Ok(passes_filter(&f.eval(r)?));
                 Ok(IngredientLookupResult::Records(
                     Box::new(rs.filter_map(move |r| {
                         match r {
@@ -131,6 +131,15 @@ impl Ingredient for Filter {
     }
 }
 
+/// Returns true if a row for which the filter expression evaluated to `value` should be emitted.
+///
+/// Numeric results and `NULL` are interpreted with [`DfValue::as_bool`], so `NULL` never passes the
+/// filter. Non-numeric results (such as for `WHERE text_col`) can't be interpreted as booleans
+/// directly, so those fall back to the value's [truthiness](DfValue::is_truthy).
+fn passes_filter(value: &DfValue) -> bool {
+    value.as_bool().unwrap_or_else(|_| value.is_truthy())
+}
+
 #[cfg(test)]
 mod tests {
     use dataflow_expression::utils::{column_with_type, make_literal};
@@ -314,4 +323,25 @@ mod tests {
         left = vec![2.into(), 3.into()];
         assert_eq!(g.narrow_one_row(left, false), Records::default());
     }
+
+    #[test]
+    fn it_filters_by_column_value() {
+        let mut g = setup(false, Some(column_with_type(0, DfType::Int)));
+
+        let left: Vec<DfValue> = vec![2.into(), "a".try_into().unwrap()];
+        assert_eq!(g.narrow_one_row(left.clone(), false), vec![left].into());
+        let left: Vec<DfValue> = vec![0.into(), "a".try_into().unwrap()];
+        assert!(g.narrow_one_row(left, false).is_empty());
+        let left: Vec<DfValue> = vec![DfValue::None, "a".try_into().unwrap()];
+        assert!(g.narrow_one_row(left, false).is_empty());
+    }
+
+    #[test]
+    fn non_numeric_results_use_truthiness() {
+        assert!(passes_filter(&DfValue::from("a")));
+        assert!(!passes_filter(&DfValue::from("")));
+        assert!(passes_filter(&DfValue::Int(-1)));
+        assert!(!passes_filter(&DfValue::Double(0.0)));
+        assert!(!passes_filter(&DfValue::None));
+    }
 }
@@ -4,8 +4,9 @@ use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 
 pub use nom_sql::{BinaryOperator, Literal, SqlType};
-use readyset_data::{Collation, DfType};
-use readyset_errors::{invariant, ReadySetResult};
+use readyset_data::dialect::SqlEngine;
+use readyset_data::{Collation, DfType, Dialect};
+use readyset_errors::{invariant, ReadySetError, ReadySetResult};
 use serde::{Deserialize, Serialize};
 
 use crate::ops::grouped::{GroupedOperation, GroupedOperator};
@@ -25,32 +26,58 @@ pub enum Aggregation {
 }
 
 impl Aggregation {
+    /// Returns the type of the result of this aggregation over a column of type `over_col_ty`,
+    /// following the conventions of the given SQL dialect.
+    pub fn output_type(&self, over_col_ty: &DfType, dialect: Dialect) -> DfType {
+        match (self, dialect.engine()) {
+            (Aggregation::Count { .. }, _) => DfType::BigInt,
+            // The SUM() and AVG() functions return a DECIMAL value for exact-value arguments
+            // (integer or DECIMAL), and a DOUBLE value for approximate-value arguments (FLOAT or
+            // DOUBLE).
+            (Aggregation::Sum | Aggregation::Avg, SqlEngine::MySQL) => {
+                if over_col_ty.is_any_float() {
+                    DfType::Double
+                } else {
+                    DfType::DEFAULT_NUMERIC
+                }
+            }
+            // sum() returns bigint for smallint or int arguments, numeric for bigint arguments,
+            // and otherwise the same as the argument data type
+            (Aggregation::Sum, SqlEngine::PostgreSQL) => match over_col_ty {
+                DfType::SmallInt | DfType::Int | DfType::UnsignedSmallInt => DfType::BigInt,
+                DfType::Float | DfType::Double => over_col_ty.clone(),
+                _ => DfType::DEFAULT_NUMERIC,
+            },
+            // avg() returns numeric for any integer-type argument, and double precision for a
+            // floating-point argument
+            (Aggregation::Avg, SqlEngine::PostgreSQL) => {
+                if over_col_ty.is_any_float() {
+                    DfType::Double
+                } else {
+                    DfType::DEFAULT_NUMERIC
+                }
+            }
+            (Aggregation::GroupConcat { .. }, _) => {
+                DfType::Text(/* TODO */ Collation::default())
+            }
+        }
+    }
+
     /// Construct a new `Aggregator` that performs this operation.
     ///
     /// The aggregation will aggregate the value in column number `over` from its inputs (i.e.,
     /// from the `src` node in the graph), and use the columns in the `group_by` array as a group
-    /// identifier.
+    /// identifier. The type of the result is determined from the type of the `over` column and
+    /// the SQL dialect, per [`Aggregation::output_type`].
     pub fn over(
         self,
         src: NodeIndex,
         over: usize,
         group_by: &[usize],
         over_col_ty: &DfType,
+        dialect: Dialect,
     ) -> ReadySetResult<GroupedOperator<Aggregator>> {
-        let out_ty = match &self {
-            Aggregation::Count { .. } => DfType::BigInt,
-            // The SUM() and AVG() functions return a DECIMAL value for exact-value arguments
-            // (integer or DECIMAL), and a DOUBLE value for approximate-value arguments (FLOAT or
-            // DOUBLE).
-            Aggregation::Sum | Aggregation::Avg => {
-                if over_col_ty.is_any_float() {
-                    DfType::Double
-                } else {
-                    DfType::DEFAULT_NUMERIC
-                }
-            }
-            Aggregation::GroupConcat { .. } => DfType::Text(/* TODO */ Collation::default()),
-        };
+        let out_ty = self.output_type(over_col_ty, dialect);
 
         Ok(GroupedOperator::new(
             src,
@@ -143,9 +170,63 @@ impl Aggregator {
         hasher.finish()
     }
 
+    /// Add the value in `diff` to (or subtract it from) `curr`, the running total of a `SUM`.
+    ///
+    /// The total is accumulated in the aggregation's output type: integer totals (`BIGINT`) are
+    /// added as `i128`s so that the accumulation itself can't overflow, and integer values being
+    /// summed into a decimal total are widened to a decimal first. If the total exceeds the range
+    /// of the output type, this returns an error rather than silently wrapping or producing NULL.
+    fn apply_sum(&self, curr: DfValue, diff: NumericalDiff) -> ReadySetResult<DfValue> {
+        let out_of_range = |ty: &str| ReadySetError::ValueOutOfRange {
+            ty: ty.into(),
+            expression: self.description(true),
+        };
+
+        match &self.out_ty {
+            DfType::BigInt => {
+                let curr = i128::try_from(&curr)?;
+                let value = i128::try_from(&diff.value)?;
+                // Can't overflow, since both values fit in 64 bits
+                let sum = if diff.positive {
+                    curr + value
+                } else {
+                    curr - value
+                };
+                i64::try_from(sum)
+                    .map(DfValue::from)
+                    .map_err(|_| out_of_range("BIGINT"))
+            }
+            out_ty => {
+                let (curr, value) = if matches!(out_ty, DfType::Numeric { .. }) {
+                    (
+                        curr.coerce_to(out_ty, &DfType::Unknown)?,
+                        diff.value.coerce_to(out_ty, &DfType::Unknown)?,
+                    )
+                } else {
+                    (curr, diff.value)
+                };
+
+                let sum = if diff.positive {
+                    (&curr + &value)?
+                } else {
+                    (&curr - &value)?
+                };
+
+                // Arithmetic on DfValues returns NULL on overflow, and neither operand is NULL
+                // here
+                if sum.is_none() && !curr.is_none() {
+                    return Err(out_of_range("DECIMAL"));
+                }
+
+                Ok(sum)
+            }
+        }
+    }
+
     fn new_data(&self) -> ReadySetResult<DfValue> {
         match &self.out_ty {
             DfType::BigInt => Ok(DfValue::Int(Default::default())),
+            DfType::Float => Ok(DfValue::Float(Default::default())),
             DfType::Double => Ok(DfValue::Double(Default::default())),
             DfType::Numeric { .. } => Ok(DfValue::Numeric(Default::default())),
             DfType::Text { .. } => Ok(DfValue::from("" /* TODO(grfn): Use collation here */)),
@@ -196,14 +277,6 @@ impl GroupedOperation for Aggregator {
             }
         };
 
-        let apply_sum = |curr: DfValue, diff: Self::Diff| -> ReadySetResult<DfValue> {
-            if diff.positive {
-                &curr + &diff.value
-            } else {
-                &curr - &diff.value
-            }
-        };
-
         let apply_avg = |_curr, diff: Self::Diff| -> ReadySetResult<DfValue> {
             self.count_sum_map
                 .borrow_mut()
@@ -223,7 +296,7 @@ impl GroupedOperation for Aggregator {
 
                 match self.op {
                     Aggregation::Count { .. } => apply_count(curr?, diff),
-                    Aggregation::Sum => apply_sum(curr?, diff),
+                    Aggregation::Sum => self.apply_sum(curr?, diff),
                     Aggregation::Avg => apply_avg(curr?, diff),
                     Aggregation::GroupConcat { separator: _ } => internal!(
                         "GroupConcats are separate from the other aggregations in the dataflow."
@@ -231,9 +304,9 @@ impl GroupedOperation for Aggregator {
                 }
             };
 
-        diffs
-            .fold(Ok(current.cloned().unwrap_or(self.new_data()?)), apply_diff)
-            .map(Some)
+        let result = diffs.fold(Ok(current.cloned().unwrap_or(self.new_data()?)), apply_diff)?;
+
+        Ok(Some(result))
     }
 
     fn description(&self, detailed: bool) -> String {
@@ -304,7 +377,13 @@ mod tests {
             "identity",
             &["x", "ys"],
             aggregation
-                .over(s.as_global(), 1, &[0], &DfType::Double)
+                .over(
+                    s.as_global(),
+                    1,
+                    &[0],
+                    &DfType::Double,
+                    Dialect::DEFAULT_MYSQL,
+                )
                 .unwrap(),
             mat,
         );
@@ -318,7 +397,13 @@ mod tests {
             "identity",
             &["x", "z", "ys"],
             aggregation
-                .over(s.as_global(), 1, &[0, 2], &DfType::Double)
+                .over(
+                    s.as_global(),
+                    1,
+                    &[0, 2],
+                    &DfType::Double,
+                    Dialect::DEFAULT_MYSQL,
+                )
                 .unwrap(),
             mat,
         );
@@ -330,17 +415,17 @@ mod tests {
         let src = 0.into();
 
         let c = Aggregation::Count
-            .over(src, 1, &[0, 2], &DfType::Unknown)
+            .over(src, 1, &[0, 2], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
             .unwrap();
         assert_eq!(c.description(true), "|*| γ[0, 2]");
 
         let s = Aggregation::Sum
-            .over(src, 1, &[2, 0], &DfType::Unknown)
+            .over(src, 1, &[2, 0], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
             .unwrap();
         assert_eq!(s.description(true), "𝛴(1) γ[2, 0]");
 
         let a = Aggregation::Avg
-            .over(src, 1, &[2, 0], &DfType::Unknown)
+            .over(src, 1, &[2, 0], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
             .unwrap();
         assert_eq!(a.description(true), "Avg(1) γ[2, 0]");
     }
@@ -1118,4 +1203,120 @@ mod tests {
             .into()
         );
     }
+
+    #[test]
+    fn sum_widens_instead_of_overflowing() {
+        let mut g = ops::test::MockGraph::new();
+        let s = g.add_base("source", &["x", "y"]);
+        g.set_op(
+            "identity",
+            &["x", "ys"],
+            Aggregation::Sum
+                .over(
+                    s.as_global(),
+                    1,
+                    &[0],
+                    &DfType::UnsignedBigInt,
+                    Dialect::DEFAULT_MYSQL,
+                )
+                .unwrap(),
+            true,
+        );
+
+        g.narrow_one_row(vec![1.into(), DfValue::from(u64::MAX)], true);
+        let rs = g.narrow_one_row(vec![1.into(), DfValue::from(u64::MAX)], true);
+        let sum = rs
+            .into_iter()
+            .find_map(|r| match r {
+                Record::Positive(r) => Some(r[1].clone()),
+                Record::Negative(_) => None,
+            })
+            .unwrap();
+        assert!(matches!(sum, DfValue::Numeric(_)));
+        assert_eq!(sum.to_string(), "36893488147419103230");
+
+        let rs = g.narrow_one_row(vec![1.into(), DfValue::from(i64::MIN)], true);
+        let sum = rs
+            .into_iter()
+            .find_map(|r| match r {
+                Record::Positive(r) => Some(r[1].clone()),
+                Record::Negative(_) => None,
+            })
+            .unwrap();
+        assert_eq!(sum.to_string(), "27670116110564327422");
+    }
+
+    #[test]
+    fn output_types() {
+        let mysql = Dialect::DEFAULT_MYSQL;
+        let postgres = Dialect::DEFAULT_POSTGRESQL;
+
+        for dialect in [mysql, postgres] {
+            assert_eq!(
+                Aggregation::Count.output_type(&DfType::Int, dialect),
+                DfType::BigInt
+            );
+            assert_eq!(
+                Aggregation::Avg.output_type(&DfType::Int, dialect),
+                DfType::DEFAULT_NUMERIC
+            );
+            assert_eq!(
+                Aggregation::Avg.output_type(&DfType::Double, dialect),
+                DfType::Double
+            );
+        }
+
+        assert_eq!(
+            Aggregation::Sum.output_type(&DfType::Int, mysql),
+            DfType::DEFAULT_NUMERIC
+        );
+        assert_eq!(
+            Aggregation::Sum.output_type(&DfType::Float, mysql),
+            DfType::Double
+        );
+
+        assert_eq!(
+            Aggregation::Sum.output_type(&DfType::Int, postgres),
+            DfType::BigInt
+        );
+        assert_eq!(
+            Aggregation::Sum.output_type(&DfType::BigInt, postgres),
+            DfType::DEFAULT_NUMERIC
+        );
+        assert_eq!(
+            Aggregation::Sum.output_type(&DfType::Float, postgres),
+            DfType::Float
+        );
+    }
+
+    #[test]
+    fn postgres_sum_of_ints_is_bigint() {
+        let mut g = ops::test::MockGraph::new();
+        let s = g.add_base("source", &["x", "y"]);
+        g.set_op(
+            "identity",
+            &["x", "ys"],
+            Aggregation::Sum
+                .over(
+                    s.as_global(),
+                    1,
+                    &[0],
+                    &DfType::Int,
+                    Dialect::DEFAULT_POSTGRESQL,
+                )
+                .unwrap(),
+            true,
+        );
+
+        g.narrow_one_row(vec![1.into(), 2.into()], true);
+        let rs = g.narrow_one_row(vec![1.into(), 3.into()], true);
+        let sum = rs
+            .into_iter()
+            .find_map(|r| match r {
+                Record::Positive(r) => Some(r[1].clone()),
+                Record::Negative(_) => None,
+            })
+            .unwrap();
+        assert_eq!(sum, DfValue::Int(5));
+    }
 }
@@ -194,6 +194,12 @@ impl Join {
             .collect()
     }
 
+    /// Returns the parent whose columns can be NULL in the output of the join, because a row from
+    /// the other parent may have no matching row in it - the right parent of a left join.
+    pub fn nullable_parent(&self) -> Option<NodeIndex> {
+        (self.kind == JoinType::Left).then(|| self.right.as_global())
+    }
+
     fn resolve_col(&self, col: usize) -> (Option<usize>, Option<usize>) {
         let (side, pcol) = self.emit[col];
 
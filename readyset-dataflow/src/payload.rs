@@ -228,6 +228,13 @@ pub enum DomainRequest {
         new_type: DfType,
     },
 
+    /// Limit the size of the state of an existing reader node, past which its keys will be evicted
+    /// (see [`Reader::max_bytes`](crate::node::special::Reader::max_bytes)).
+    SetReaderMaxBytes {
+        node: LocalNodeIndex,
+        max_bytes: usize,
+    },
+
     /// Add a new node to this domain below the given parents.
     AddNode {
         node: Node,
@@ -78,6 +78,21 @@ pub enum ReadySetError {
     #[error("No query known by id {id}")]
     NoQueryForId { id: String },
 
+    /// The adapter is running in read-only mode, and received a statement which would write data
+    #[error("Cannot execute {statement} statement: ReadySet is running in read-only mode")]
+    ReadOnly {
+        /// The type of the statement that was rejected
+        statement: String,
+    },
+
+    /// A write statement executed against ReadySet didn't affect any rows, and the adapter is
+    /// configured to treat writes that don't affect any rows as errors
+    #[error("{statement} statement did not affect any rows")]
+    NoRowsAffected {
+        /// The type of the statement that didn't affect any rows
+        statement: String,
+    },
+
     /// The adapter will return this error on any set statement that is not
     /// explicitly allowed.
     #[error("Set statement disallowed: {}", Sensitive(statement))]
@@ -184,6 +199,17 @@ pub enum ReadySetError {
     #[error("wrong number of key columns used: expected {0}, got {1}")]
     WrongKeyColumnCount(usize, usize),
 
+    /// A row was deleted or updated by key in a table that has no primary key. Rows can only be
+    /// removed from such tables by specifying the full contents of the row.
+    #[error(
+        "Table '{table}' has no primary key, so rows can only be deleted by specifying the values \
+         of all of their columns"
+    )]
+    NoPrimaryKey {
+        /// The name of the table
+        table: String,
+    },
+
     /// A table operation was passed an incorrect packet data type.
     #[error("wrong packet data type")]
     WrongPacketDataType,
@@ -233,6 +259,19 @@ pub enum ReadySetError {
         schema: Option<String>,
     },
 
+    /// A query referenced a table which isn't being replicated from the upstream database, so
+    /// can't be cached.
+    #[error(
+        "Table '{}{}' is not replicated by ReadySet; add it to --replication-tables to cache \
+         queries that reference it",
+        schema.as_ref().map(|s| format!("{}.", s)).unwrap_or_default(),
+        name
+    )]
+    TableNotReplicated {
+        name: String,
+        schema: Option<String>,
+    },
+
     /// A view is not yet available.
     #[error("view not yet available")]
     ViewNotYetAvailable,
@@ -285,6 +324,82 @@ pub enum ReadySetError {
         statement_id: u32,
     },
 
+    /// A new cache couldn't be created because the materialized state for existing caches already
+    /// exceeds the configured memory budget.
+    #[error(
+        "Memory budget exceeded: caches are using {used} bytes, but the budget is {budget} bytes"
+    )]
+    MaterializedMemoryBudgetExceeded {
+        /// The number of bytes of materialized state currently in use
+        used: usize,
+        /// The configured memory budget, in bytes
+        budget: usize,
+    },
+
+    /// The number of parameters supplied when executing a prepared statement didn't match the
+    /// number of placeholders in the statement.
+    #[error("Wrong number of parameters for prepared statement: expected {expected}, got {got}")]
+    WrongParameterCount {
+        /// The number of placeholders in the prepared statement
+        expected: usize,
+        /// The number of parameters that were supplied
+        got: usize,
+    },
+
+    /// The statement was cancelled by a `KILL QUERY` statement issued from another connection
+    /// before it finished executing.
+    #[error("Query execution was interrupted")]
+    QueryCancelled,
+
+    /// The statement didn't finish executing within the statement timeout set for the session
+    /// (with `SET statement_timeout` or `SET max_execution_time`)
+    #[error("Query execution was interrupted, maximum statement execution time exceeded")]
+    StatementTimeout,
+
+    /// A `WAIT FOR REPLICATION` statement gave up waiting for ReadySet to replicate all writes
+    /// committed to the upstream database
+    #[error("Timed out after {timeout_secs}s waiting for ReadySet to catch up with replication")]
+    ReplicationWaitTimeout {
+        /// How long the statement waited before giving up, in seconds
+        timeout_secs: u64,
+    },
+
+    /// The result of an arithmetic operation, such as accumulating a `SUM`, is out of the range of
+    /// values representable by its type
+    #[error("{ty} value is out of range in '{expression}'")]
+    ValueOutOfRange {
+        /// The type of the value which overflowed
+        ty: String,
+        /// A description of the expression whose value overflowed
+        expression: String,
+    },
+
+    /// An insert would have created a row with the same primary key as a row that already exists
+    /// in the table, or as another row in the same insert
+    #[error("Duplicate entry '{}' for key 'PRIMARY'", Sensitive(key))]
+    DuplicateKey {
+        /// The duplicated primary key, with the values of compound keys separated by `-`
+        key: String,
+    },
+
+    /// Looking up a user in the adapter's authentication backend failed
+    #[error("Failed to look up user in the authentication backend: {0}")]
+    AuthBackendFailure(String),
+
+    /// A `KILL QUERY` statement referenced a connection that doesn't exist
+    #[error("Unknown connection id: {id}")]
+    UnknownConnection {
+        /// The connection id given in the statement
+        id: u64,
+    },
+
+    /// A `KILL QUERY` statement referenced a connection authenticated as a different user
+    #[error("You are not owner of thread {id}")]
+    KillDenied {
+        /// The connection id given in the statement
+        id: u64,
+    },
+
     /// An internal invariant has been violated.
     ///
     /// This is produced by the [`internal!`] and [`invariant!`] macros, as an alternative to
@@ -348,6 +463,32 @@ pub enum ReadySetError {
     #[error("Column {0} not found in table or view")]
     NoSuchColumn(String),
 
+    /// A value was too long to be written to a column with a maximum length
+    #[error("Data too long for column '{column}' (maximum length is {max_length})")]
+    DataTooLong {
+        /// The name of the column the value was being written to
+        column: String,
+        /// The maximum length of values in the column
+        max_length: u16,
+    },
+
+    /// Two columns with incompatible types were compared (for example in a join condition)
+    /// without an explicit cast
+    #[error(
+        "Cannot compare column {left} of type {left_type} with column {right} of type \
+         {right_type} without an explicit cast"
+    )]
+    ColumnTypeMismatch {
+        /// The column on the left-hand side of the comparison
+        left: String,
+        /// The type of the column on the left-hand side of the comparison
+        left_type: String,
+        /// The column on the right-hand side of the comparison
+        right: String,
+        /// The type of the column on the right-hand side of the comparison
+        right_type: String,
+    },
+
     /// Conversion to or from a [`DfValue`](crate::DfValue) failed.
     #[error("DfValue conversion error: Failed to convert value of type {src_type} to the type {target_type}: {details}")]
     DfValueConversionError {
@@ -445,6 +586,15 @@ pub enum ReadySetError {
         uri: Url,
     },
 
+    /// A request to manually evict a worker from the cluster was refused.
+    #[error("Cannot evict worker at {uri}: {reason}")]
+    CannotEvictWorker {
+        /// The URI of the worker that was to be evicted.
+        uri: Url,
+        /// The reason the worker could not be evicted.
+        reason: String,
+    },
+
     /// Making a HTTP request failed.
     #[error("HTTP request failed: {0}")]
     HttpRequestFailed(String),
@@ -535,6 +685,16 @@ pub enum ReadySetError {
     #[error("Error during replication: {0}")]
     ReplicationFailed(String),
 
+    /// The schema of a base table in ReadySet does not match the definition of the same table in
+    /// the upstream database, so replication can't safely be started
+    #[error("Schema for table {table} does not match upstream: {}", mismatches.join("; "))]
+    UpstreamSchemaMismatch {
+        /// The name of the table whose schema differs
+        table: String,
+        /// A description of each difference between the two schemas
+        mismatches: Vec<String>,
+    },
+
     /// There are no available Workers to assign domains to.
     #[error("Could not find healthy worker to place domain {domain_index}.{shard}")]
     NoAvailableWorkers {
@@ -672,6 +832,99 @@ impl ReadySetError {
         self.any_cause(|e| e.is_table_not_found())
     }
 
+    /// Returns `true` if self is [`TableNotReplicated`].
+    pub fn is_table_not_replicated(&self) -> bool {
+        matches!(self, Self::TableNotReplicated { .. })
+    }
+
+    /// Returns `true` if self either *is* [`TableNotReplicated`], or was *caused by*
+    /// [`TableNotReplicated`].
+    pub fn caused_by_table_not_replicated(&self) -> bool {
+        self.any_cause(|e| e.is_table_not_replicated())
+    }
+
+    /// Returns `true` if self is [`MaterializedMemoryBudgetExceeded`].
+    pub fn is_materialized_memory_budget_exceeded(&self) -> bool {
+        matches!(self, Self::MaterializedMemoryBudgetExceeded { .. })
+    }
+
+    /// Returns `true` if self either *is* [`MaterializedMemoryBudgetExceeded`], or was *caused
+    /// by* [`MaterializedMemoryBudgetExceeded`].
+    pub fn caused_by_materialized_memory_budget_exceeded(&self) -> bool {
+        self.any_cause(|e| e.is_materialized_memory_budget_exceeded())
+    }
+
+    /// Returns `true` if self is [`InvalidNodeType`].
+    pub fn is_invalid_node_type(&self) -> bool {
+        matches!(self, Self::InvalidNodeType { .. })
+    }
+
+    /// Returns `true` if self either *is* [`InvalidNodeType`], or was *caused by*
+    /// [`InvalidNodeType`].
+    pub fn caused_by_invalid_node_type(&self) -> bool {
+        self.any_cause(|e| e.is_invalid_node_type())
+    }
+
+    /// Returns `true` if self is [`QueryCancelled`].
+    pub fn is_query_cancelled(&self) -> bool {
+        matches!(self, Self::QueryCancelled)
+    }
+
+    /// Returns `true` if self either *is* [`QueryCancelled`], or was *caused by*
+    /// [`QueryCancelled`].
+    pub fn caused_by_query_cancelled(&self) -> bool {
+        self.any_cause(|e| e.is_query_cancelled())
+    }
+
+    /// Returns `true` if self is [`StatementTimeout`].
+    pub fn is_statement_timeout(&self) -> bool {
+        matches!(self, Self::StatementTimeout)
+    }
+
+    /// Returns `true` if self is [`ValueOutOfRange`].
+    pub fn is_value_out_of_range(&self) -> bool {
+        matches!(self, Self::ValueOutOfRange { .. })
+    }
+
+    /// Returns `true` if self either *is* [`ValueOutOfRange`], or was *caused by*
+    /// [`ValueOutOfRange`].
+    pub fn caused_by_value_out_of_range(&self) -> bool {
+        self.any_cause(|e| e.is_value_out_of_range())
+    }
+
+    /// Returns `true` if self is [`DuplicateKey`].
+    pub fn is_duplicate_key(&self) -> bool {
+        matches!(self, Self::DuplicateKey { .. })
+    }
+
+    /// Returns `true` if self either *is* [`DuplicateKey`], or was *caused by*
+    /// [`DuplicateKey`].
+    pub fn caused_by_duplicate_key(&self) -> bool {
+        self.any_cause(|e| e.is_duplicate_key())
+    }
+
+    /// Returns `true` if self is [`NoPrimaryKey`].
+    pub fn is_no_primary_key(&self) -> bool {
+        matches!(self, Self::NoPrimaryKey { .. })
+    }
+
+    /// Returns `true` if self either *is* [`NoPrimaryKey`], or was *caused by*
+    /// [`NoPrimaryKey`].
+    pub fn caused_by_no_primary_key(&self) -> bool {
+        self.any_cause(|e| e.is_no_primary_key())
+    }
+
+    /// Returns `true` if self is [`CannotEvictWorker`].
+    pub fn is_cannot_evict_worker(&self) -> bool {
+        matches!(self, Self::CannotEvictWorker { .. })
+    }
+
+    /// Returns `true` if self either *is* [`CannotEvictWorker`], or was *caused by*
+    /// [`CannotEvictWorker`].
+    pub fn caused_by_cannot_evict_worker(&self) -> bool {
+        self.any_cause(|e| e.is_cannot_evict_worker())
+    }
+
     /// Returns `true` if the error could have been caused by a networking problem.
     pub fn is_networking_related(&self) -> bool {
         self.any_cause(|e| {
@@ -697,6 +950,17 @@ impl ReadySetError {
         self.any_cause(|e| matches!(e, Self::ViewDestroyed))
     }
 
+    /// Returns `true` if the error is expected to be resolved by retrying the same request after a
+    /// short delay - for example, because a domain is briefly unavailable during a migration.
+    pub fn is_transient(&self) -> bool {
+        self.any_cause(|e| {
+            matches!(
+                e,
+                Self::ViewNotYetAvailable | Self::ReaderNotFound | Self::ServiceUnavailable
+            )
+        })
+    }
+
     /// Returns `true` if the error is [`InvalidQuery`].
     pub fn is_invalid_query(&self) -> bool {
         matches!(self, Self::InvalidQuery(..))
@@ -346,6 +346,7 @@ mod tests {
                 returned_cols: None,
                 default_row: None,
                 aggregates: None,
+                constant_cols: vec![],
             })
         }
 
@@ -239,6 +239,10 @@ pub enum MirNodeInner {
         default_row: Option<Vec<DfValue>>,
         /// Aggregates to perform in the reader on result sets for keys after performing the lookup
         aggregates: Option<PostLookupAggregates<Column>>,
+        /// Columns with the same value in every row, which are inserted into rows after they're
+        /// looked up rather than being stored in the reader. Each is the index of the column in
+        /// the returned rows, along with its name and value.
+        constant_cols: Vec<(usize, SqlIdentifier, DfValue)>,
     },
 }
 
@@ -254,6 +258,7 @@ impl MirNodeInner {
             returned_cols: None,
             default_row: None,
             aggregates: None,
+            constant_cols: vec![],
         }
     }
 
@@ -209,6 +209,7 @@ impl GraphViz for MirNodeInner {
                 limit,
                 returned_cols,
                 aggregates,
+                constant_cols,
                 ..
             } => {
                 let key_cols = keys.iter().map(|k| &k.0).join(", ");
@@ -233,6 +234,17 @@ impl GraphViz for MirNodeInner {
                     write!(f, "\\nreturn: {}", returned_cols.iter().join(", "))?;
                 }
 
+                if !constant_cols.is_empty() {
+                    write!(
+                        f,
+                        "\\nconstants: {}",
+                        constant_cols
+                            .iter()
+                            .map(|(idx, name, value)| format!("{idx}: {name} = {value}"))
+                            .join(", ")
+                    )?;
+                }
+
                 if let Some(aggregates) = aggregates {
                     write!(
                         f,
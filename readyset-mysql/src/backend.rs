@@ -2,6 +2,7 @@ use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use futures_util::StreamExt;
@@ -11,17 +12,18 @@ use mysql_async::consts::StatusFlags;
 use mysql_common::bigdecimal03::ToPrimitive;
 use mysql_srv::{
     CachedSchema, Column, ColumnFlags, ColumnType, InitWriter, MsqlSrvError, MySqlShim,
-    QueryResultWriter, RowWriter, StatementMetaWriter,
+    PasswordHash, QueryResultWriter, RowWriter, StatementMetaWriter,
 };
+use readyset_adapter::auth::AuthBackend;
 use readyset_adapter::backend::noria_connector::MetaVariable;
 use readyset_adapter::backend::{
-    noria_connector, QueryResult, SinglePrepareResult, UpstreamPrepare,
+    noria_connector, QueryResult, SinglePrepareResult, TimestampFormat, UpstreamPrepare,
 };
 use readyset_data::{DfType, DfValue, DfValueKind};
 use readyset_errors::{internal, internal_err, ReadySetError};
 use streaming_iterator::StreamingIterator;
 use tokio::io::{self, AsyncWrite};
-use tracing::{error, trace};
+use tracing::{error, trace, warn};
 use upstream::StatementMeta;
 
 use crate::constants::DEFAULT_CHARACTER_SET;
@@ -251,6 +253,12 @@ impl Backend {
     pub fn new(noria: readyset_adapter::Backend<MySqlUpstream, MySqlQueryHandler>) -> Self {
         Backend { noria }
     }
+
+    /// Returns the number of warnings to report to the client about the results of the last query,
+    /// which it can then retrieve with `SHOW WARNINGS`
+    fn warning_count(&self) -> u16 {
+        u16::from(self.last_query_warning().is_some())
+    }
 }
 
 impl Deref for Backend {
@@ -330,6 +338,7 @@ macro_rules! handle_error {
 async fn handle_readyset_result<'a, W>(
     result: noria_connector::QueryResult<'a>,
     writer: QueryResultWriter<'_, W>,
+    warnings: u16,
 ) -> io::Result<()>
 where
     W: AsyncWrite + Unpin,
@@ -339,13 +348,30 @@ where
         noria_connector::QueryResult::Insert {
             num_rows_inserted,
             first_inserted_id,
-        } => write_query_results(Ok((num_rows_inserted, first_inserted_id)), writer, None).await,
+        } => {
+            write_query_results(
+                Ok((num_rows_inserted, first_inserted_id)),
+                writer.set_warnings(warnings),
+                None,
+            )
+            .await
+        }
         noria_connector::QueryResult::Update {
             num_rows_updated,
             last_inserted_id,
-        } => write_query_results(Ok((num_rows_updated, last_inserted_id)), writer, None).await,
+        } => {
+            write_query_results(
+                Ok((num_rows_updated, last_inserted_id)),
+                writer.set_warnings(warnings),
+                None,
+            )
+            .await
+        }
         noria_connector::QueryResult::Delete { num_rows_deleted } => {
-            writer.completed(num_rows_deleted, 0, None).await
+            writer
+                .set_warnings(warnings)
+                .completed(num_rows_deleted, 0, None)
+                .await
         }
         noria_connector::QueryResult::Meta(vars) => write_meta_table(vars, writer).await,
         noria_connector::QueryResult::MetaVariables(vars) => {
@@ -385,7 +411,7 @@ where
                 }
                 rw.end_row().await?;
             }
-            rw.finish().await
+            rw.set_warnings(warnings).finish().await
         }
     }
 }
@@ -469,7 +495,7 @@ where
     W: AsyncWrite + Unpin,
 {
     match result {
-        Ok(QueryResult::Noria(result)) => handle_readyset_result(result, writer).await,
+        Ok(QueryResult::Noria(result)) => handle_readyset_result(result, writer, 0).await,
         Ok(QueryResult::Upstream(result)) => handle_upstream_result(result, writer).await,
         Err(error) => handle_error!(error, writer),
     }
@@ -584,8 +610,16 @@ where
             }
         };
 
-        match self.execute(id, &value_params).await {
-            Ok(QueryResult::Noria(noria_connector::QueryResult::Select { mut rows, schema })) => {
+        // The cached schema for a statement records the types of its columns, which change with
+        // the session's timestamp format, so results are only written using the cache while the
+        // format is left as the default
+        let use_schema_cache = self.timestamp_format() == TimestampFormat::Native;
+        let result = match self.execute(id, &value_params).await {
+            Ok(QueryResult::Noria(result)) => result,
+            execute_result => return handle_query_result(execute_result, results).await,
+        };
+        match result {
+            noria_connector::QueryResult::Select { mut rows, schema } if use_schema_cache => {
                 let CachedSchema {
                     mysql_schema,
                     column_types,
@@ -619,6 +653,9 @@ where
                         })
                     }
                 };
+                // Now that we're done with the schema, which borrows from `self`, we can look up
+                // any warnings about the results
+                let warnings = self.warning_count();
 
                 let mut rw = results
                     .start_with_cache(mysql_schema, preencoded_schema.clone())
@@ -646,9 +683,15 @@ where
                     }
                     rw.end_row().await?;
                 }
-                rw.finish().await
+                rw.set_warnings(warnings).finish().await
+            }
+            result => {
+                // Take ownership of the results, which borrow from `self`, so that we can look up
+                // any warnings about them
+                let result = result.into_owned();
+                let warnings = self.warning_count();
+                handle_readyset_result(result, results, warnings).await
             }
-            execute_result => handle_query_result(execute_result, results).await,
         }
     }
 
@@ -665,15 +708,36 @@ where
         }
     }
 
-    async fn on_close(&mut self, _: u32) {}
+    async fn on_close(&mut self, statement_id: u32) {
+        self.remove_statement(statement_id)
+    }
 
     async fn on_query(&mut self, query: &str, results: QueryResultWriter<'_, W>) -> io::Result<()> {
-        let query_result = self.query(query).await;
-        handle_query_result(query_result, results).await
+        // Take ownership of any results from ReadySet, which borrow from `self`, so that we can
+        // look up any warnings about them
+        let result = match self.query(query).await {
+            Ok(QueryResult::Noria(result)) => result.into_owned(),
+            query_result => return handle_query_result(query_result, results).await,
+        };
+        let warnings = self.warning_count();
+        handle_readyset_result(result, results, warnings).await
     }
 
-    fn password_for_username(&self, username: &str) -> Option<Vec<u8>> {
-        self.users.get(username).cloned().map(String::into_bytes)
+    async fn password_hash_for_username(&mut self, username: &str) -> Option<PasswordHash> {
+        // Clone the auth backend so that we don't hold a reference to `self` across the await
+        // point
+        let auth_backend = Arc::clone(&self.auth_backend);
+        match auth_backend.password_hash_for_user(username).await {
+            Ok(password_hash) => password_hash,
+            Err(error) => {
+                warn!(%error, %username, "Failed to look up user");
+                None
+            }
+        }
+    }
+
+    fn on_authenticated(&mut self, username: &str) {
+        self.set_authenticated_user(username)
     }
 
     fn require_authentication(&self) -> bool {
@@ -683,6 +747,10 @@ where
     fn version(&self) -> String {
         self.noria.version()
     }
+
+    fn connection_id(&self) -> u32 {
+        self.noria.connection_id()
+    }
 }
 
 async fn handle_column_write_err<W: AsyncWrite + Unpin>(
@@ -41,6 +41,21 @@ impl Error {
         }
          */
         match self {
+            Self::ReadySet(ReadySetError::ReadOnly { .. }) => {
+                mysql_srv::ErrorKind::ER_OPTION_PREVENTS_STATEMENT
+            }
+            Self::ReadySet(ReadySetError::DuplicateKey { .. }) => {
+                mysql_srv::ErrorKind::ER_DUP_ENTRY
+            }
+            Self::ReadySet(ReadySetError::QueryCancelled | ReadySetError::StatementTimeout) => {
+                mysql_srv::ErrorKind::ER_QUERY_INTERRUPTED
+            }
+            Self::ReadySet(ReadySetError::UnknownConnection { .. }) => {
+                mysql_srv::ErrorKind::ER_NO_SUCH_THREAD
+            }
+            Self::ReadySet(ReadySetError::KillDenied { .. }) => {
+                mysql_srv::ErrorKind::ER_KILL_DENIED_ERROR
+            }
             Self::MySql(mysql_async::Error::Server(e)) => e.code.into(),
             Self::MySql(_) => {
                 // TODO(peter): We need to translate these to appropriate
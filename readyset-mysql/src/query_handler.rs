@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 use std::collections::HashSet;
 use std::str::FromStr;
+use std::time::Duration;
 
 use lazy_static::lazy_static;
 use nom_sql::{Column, Expr, FieldDefinitionExpr, Literal, SqlIdentifier, SqlQuery, VariableScope};
@@ -512,7 +513,6 @@ lazy_static! {
         "max_connections",
         "max_delayed_threads",
         "max_error_count",
-        "max_execution_time",
         "max_heap_table_size",
         "max_insert_delayed_threads",
         "max_join_size",
@@ -890,7 +890,17 @@ impl QueryHandler for MySqlQueryHandler {
                     );
                 }
 
-                SetBehavior::proxy_if(set.variables.iter().all(|(variable, value)| {
+                // max_execution_time is in milliseconds, with 0 meaning no timeout
+                let statement_timeout = |value: &Expr| {
+                    match value {
+                        Expr::Literal(Literal::UnsignedInteger(i)) => Some(*i),
+                        Expr::Literal(Literal::Integer(i)) => u64::try_from(*i).ok(),
+                        _ => None,
+                    }
+                    .map(|millis| (millis > 0).then(|| Duration::from_millis(millis)))
+                };
+
+                let behavior = SetBehavior::proxy_if(set.variables.iter().all(|(variable, value)| {
                     if variable.scope == VariableScope::User {
                         return false;
                     }
@@ -923,9 +933,40 @@ impl QueryHandler for MySqlQueryHandler {
                                 false
                             }
                         }
+                        "max_execution_time" => statement_timeout(value).is_some(),
                         p => ALLOWED_PARAMETERS_ANY_VALUE.contains(p),
                     }
-                }))
+                }));
+
+                // If we're setting the sql_mode (to something we support), we also need to know
+                // whether strict mode is being turned on or off
+                let sql_modes = set
+                    .variables
+                    .iter()
+                    .find_map(|(variable, value)| match value {
+                        Expr::Literal(Literal::String(s))
+                            if variable.name.eq_ignore_ascii_case("sql_mode") =>
+                        {
+                            raw_sql_modes_to_list(s).ok()
+                        }
+                        _ => None,
+                    });
+                let timeout = set.variables.iter().find_map(|(variable, value)| {
+                    if variable.name.eq_ignore_ascii_case("max_execution_time") {
+                        statement_timeout(value)
+                    } else {
+                        None
+                    }
+                });
+                match (behavior, sql_modes, timeout) {
+                    (Proxy, Some(sql_modes), None) => SetStrictMode(sql_modes.iter().any(|m| {
+                        matches!(m, SqlMode::StrictAllTables | SqlMode::StrictTransTables)
+                    })),
+                    (Proxy, None, Some(timeout)) => SetStatementTimeout(timeout),
+                    // We can only keep track of one of these per SET statement
+                    (Proxy, Some(_), Some(_)) => Unsupported,
+                    (behavior, ..) => behavior,
+                }
             }
             nom_sql::SetStatement::Names(names) => SetBehavior::proxy_if(
                 names.collation.is_none()
@@ -956,7 +997,25 @@ mod tests {
         });
         assert_eq!(
             MySqlQueryHandler::handle_set_statement(&stmt),
-            SetBehavior::Proxy
+            SetBehavior::SetStrictMode(true)
+        );
+    }
+
+    #[test]
+    fn non_strict_sql_mode() {
+        let m = "NO_ZERO_DATE,ONLY_FULL_GROUP_BY,NO_ZERO_IN_DATE";
+        let stmt = SetStatement::Variable(SetVariables {
+            variables: vec![(
+                Variable {
+                    scope: VariableScope::Session,
+                    name: "sql_mode".into(),
+                },
+                Expr::Literal(Literal::from(m)),
+            )],
+        });
+        assert_eq!(
+            MySqlQueryHandler::handle_set_statement(&stmt),
+            SetBehavior::SetStrictMode(false)
         );
     }
 
@@ -978,6 +1037,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_max_execution_time() {
+        let set_max_execution_time = |value| {
+            SetStatement::Variable(SetVariables {
+                variables: vec![(
+                    Variable {
+                        scope: VariableScope::Session,
+                        name: "max_execution_time".into(),
+                    },
+                    Expr::Literal(value),
+                )],
+            })
+        };
+
+        assert_eq!(
+            MySqlQueryHandler::handle_set_statement(&set_max_execution_time(
+                Literal::UnsignedInteger(1500)
+            )),
+            SetBehavior::SetStatementTimeout(Some(Duration::from_millis(1500)))
+        );
+        assert_eq!(
+            MySqlQueryHandler::handle_set_statement(&set_max_execution_time(
+                Literal::UnsignedInteger(0)
+            )),
+            SetBehavior::SetStatementTimeout(None)
+        );
+        assert_eq!(
+            MySqlQueryHandler::handle_set_statement(&set_max_execution_time(Literal::from("soon"))),
+            SetBehavior::Unsupported
+        );
+
+        let set_with = |variable: &str, value| {
+            SetStatement::Variable(SetVariables {
+                variables: vec![
+                    (
+                        Variable {
+                            scope: VariableScope::Session,
+                            name: "max_execution_time".into(),
+                        },
+                        Expr::Literal(Literal::UnsignedInteger(1500)),
+                    ),
+                    (
+                        Variable {
+                            scope: VariableScope::Session,
+                            name: variable.into(),
+                        },
+                        Expr::Literal(value),
+                    ),
+                ],
+            })
+        };
+        assert_eq!(
+            MySqlQueryHandler::handle_set_statement(&set_with(
+                "time_zone",
+                Literal::from("+00:00")
+            )),
+            SetBehavior::SetStatementTimeout(Some(Duration::from_millis(1500)))
+        );
+        // The rest of the variables in the statement are still checked
+        assert_eq!(
+            MySqlQueryHandler::handle_set_statement(&set_with(
+                "time_zone",
+                Literal::from("+01:00")
+            )),
+            SetBehavior::Unsupported
+        );
+        assert_eq!(
+            MySqlQueryHandler::handle_set_statement(&set_with(
+                "sql_mode",
+                Literal::from("NO_ZERO_DATE,STRICT_ALL_TABLES,ONLY_FULL_GROUP_BY,NO_ZERO_IN_DATE")
+            )),
+            SetBehavior::Unsupported
+        );
+    }
+
     #[test]
     fn all_required_sql_modes_are_allowed() {
         for mode in REQUIRED_SQL_MODES {
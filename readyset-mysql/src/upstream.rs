@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::convert::TryInto;
 #[cfg(feature = "fallback_cache")]
 use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -17,11 +18,14 @@ use mysql_async::{
 };
 use nom_sql::SqlIdentifier;
 use pin_project::pin_project;
+use readyset::replication::ReplicationOffset;
 use readyset::ColumnSchema;
 use readyset_adapter::fallback_cache::FallbackCache;
 #[cfg(feature = "fallback_cache")]
 use readyset_adapter::fallback_cache::FallbackCacheApi;
-use readyset_adapter::upstream_database::{NoriaCompare, UpstreamDestination};
+use readyset_adapter::upstream_database::{
+    NoriaCompare, UpstreamCancelHandle, UpstreamDestination,
+};
 use readyset_adapter::{UpstreamConfig, UpstreamDatabase, UpstreamPrepare};
 use readyset_client_metrics::QueryDestination;
 use readyset_data::DfValue;
@@ -312,6 +316,25 @@ impl MySqlUpstream {
     }
 }
 
+#[async_trait]
+/// Cancels the query running on a connection to an upstream MySQL database, by running `KILL
+/// QUERY` for that connection over a new connection
+struct MySqlCancelHandle {
+    opts: Opts,
+    connection_id: u32,
+}
+
+#[async_trait]
+impl UpstreamCancelHandle for MySqlCancelHandle {
+    async fn cancel(&self) -> Result<(), anyhow::Error> {
+        let mut conn = Conn::new(self.opts.clone()).await?;
+        conn.query_drop(format!("KILL QUERY {}", self.connection_id))
+            .await?;
+        conn.disconnect().await?;
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl UpstreamDatabase for MySqlUpstream {
     type QueryResult<'a> = QueryResult<'a>;
@@ -587,6 +610,13 @@ impl UpstreamDatabase for MySqlUpstream {
         })
     }
 
+    fn cancel_handle(&self) -> Box<dyn UpstreamCancelHandle> {
+        Box::new(MySqlCancelHandle {
+            opts: self.conn.opts().clone(),
+            connection_id: self.conn.id(),
+        })
+    }
+
     async fn schema_dump(&mut self) -> Result<Vec<u8>, anyhow::Error> {
         let tables: Vec<String> = self.conn.query_iter("SHOW TABLES").await?.collect().await?;
         let mut dump = String::with_capacity(tables.len());
@@ -607,6 +637,71 @@ impl UpstreamDatabase for MySqlUpstream {
     async fn schema_search_path(&mut self) -> Result<Vec<SqlIdentifier>, Self::Error> {
         Ok(self.database().into_iter().map(|s| s.into()).collect())
     }
+
+    async fn strict_mode(&mut self) -> Result<bool, Self::Error> {
+        let sql_mode: Option<String> = self.conn.query_first("SELECT @@SESSION.sql_mode").await?;
+        Ok(sql_mode.map_or(false, |sql_mode| {
+            sql_mode.split(',').any(|mode| {
+                let mode = mode.trim();
+                mode.eq_ignore_ascii_case("STRICT_TRANS_TABLES")
+                    || mode.eq_ignore_ascii_case("STRICT_ALL_TABLES")
+            })
+        }))
+    }
+
+    async fn query_rows<S>(&mut self, query: S) -> Result<Vec<Vec<DfValue>>, Self::Error>
+    where
+        S: AsRef<str> + Send + Sync,
+    {
+        // Use the binary protocol, so that values come back typed rather than as bytes
+        let rows: Vec<Row> = self.conn.exec(query.as_ref(), ()).await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                row.unwrap()
+                    .into_iter()
+                    .map(DfValue::try_from)
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    async fn execute_rows(
+        &mut self,
+        statement_id: u32,
+        params: &[DfValue],
+    ) -> Result<Vec<Vec<DfValue>>, Self::Error> {
+        let params = dt_to_value_params(params)?;
+        let statement = self
+            .prepared_statements
+            .get(&statement_id)
+            .ok_or(Error::ReadySet(ReadySetError::PreparedStatementMissing {
+                statement_id,
+            }))?;
+        let rows: Vec<Row> = self.conn.exec(statement, params).await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                row.unwrap()
+                    .into_iter()
+                    .map(DfValue::try_from)
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    async fn replication_offset(&mut self) -> Result<Option<ReplicationOffset>, Self::Error> {
+        // `SHOW MASTER STATUS` returns no rows if binary logging is disabled
+        let status: Option<Row> = self.conn.query_first("SHOW MASTER STATUS").await?;
+        let (file, position): (String, u32) =
+            match status.and_then(|row| Some((row.get(0)?, row.get(1)?))) {
+                Some(status) => status,
+                None => return Ok(None),
+            };
+        Ok(Some(ReplicationOffset::from_str(&format!(
+            "{file}:{position}"
+        ))?))
+    }
 }
 
 #[cfg(test)]
@@ -1,11 +1,20 @@
+use std::str::FromStr;
+use std::time::Duration;
+
 use launchpad::hash::hash;
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
 use mysql_async::prelude::*;
 use readyset::query::QueryId;
+use readyset::replication::ReplicationOffset;
+use readyset::status::{ReadySetStatus, SnapshotStatus};
 use readyset_adapter::backend::UnsupportedSetMode;
+use readyset_adapter::controller_health::ControllerHealth;
+use readyset_adapter::query_status_cache::QueryStatusCache;
 use readyset_adapter::BackendBuilder;
+use readyset_client_metrics::recorded::QUERY_VALIDATION_MISMATCH;
 use readyset_client_metrics::QueryDestination;
 use readyset_client_test_helpers::mysql_helpers::{last_query_info, MySQLAdapter};
-use readyset_client_test_helpers::{self, sleep, TestBuilder};
+use readyset_client_test_helpers::{self, sleep, Adapter, TestBuilder};
 use readyset_server::Handle;
 use serial_test::serial;
 
@@ -600,6 +609,99 @@ async fn proxy_mode_should_allow_commands() {
     );
 }
 
+async fn assert_read_only_error(conn: &mut mysql_async::Conn, write: &str) {
+    match conn.query_drop(write).await.unwrap_err() {
+        mysql_async::Error::Server(e) => {
+            // ER_OPTION_PREVENTS_STATEMENT
+            assert_eq!(e.code, 1290, "{write}");
+            assert!(e.message.contains("read-only"), "{}", e.message);
+        }
+        e => panic!("Unexpected error for {write}: {e}"),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn read_only_rejects_proxied_writes() {
+    let (opts, _handle) = setup_with(
+        BackendBuilder::new()
+            .require_authentication(false)
+            .unsupported_set_mode(UnsupportedSetMode::Proxy)
+            .read_only(true),
+    )
+    .await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t (x int primary key)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    // REPLACE can't be parsed by ReadySet, so would otherwise be proxied upstream
+    assert_read_only_error(&mut conn, "REPLACE INTO t (x) VALUES (1)").await;
+    assert!(conn.prep("REPLACE INTO t (x) VALUES (?)").await.is_err());
+
+    // Put the connection into proxy mode, so that every statement would go upstream
+    conn.query_drop("SET @@SESSION.SQL_MODE = 'ANSI_QUOTES';")
+        .await
+        .unwrap();
+    assert_read_only_error(&mut conn, "INSERT INTO \"t\" (\"x\") VALUES (1)").await;
+    assert_read_only_error(&mut conn, "/* comment */ REPLACE INTO t (x) VALUES (1)").await;
+    assert!(conn.prep("INSERT INTO t (x) VALUES (?)").await.is_err());
+
+    // Reads are still proxied upstream, which never saw any of the writes
+    let rows: Vec<i32> = conn.query("SELECT \"x\" FROM \"t\"").await.unwrap();
+    assert!(rows.is_empty());
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Upstream
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn explain_readyset_and_upstream() {
+    let (opts, _handle) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+
+    conn.query_drop("CREATE TABLE t (x int, y int)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let res: mysql_async::Row = conn
+        .query_first("EXPLAIN READYSET SELECT x FROM t WHERE y = ?")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        res.columns().as_ref().first().unwrap().name_str(),
+        "READYSET PLAN"
+    );
+    let plan = res.get::<String, _>(0).unwrap();
+    assert!(plan.contains("relations:"), "{plan}");
+    assert!(plan.contains("params:"), "{plan}");
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+
+    // Plain EXPLAIN should return the upstream database's own plan
+    let res: mysql_async::Row = conn
+        .query_first("EXPLAIN SELECT x FROM t WHERE y = 1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(res
+        .columns()
+        .as_ref()
+        .iter()
+        .any(|col| col.name_str() == "select_type"));
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Upstream
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[serial]
 #[ignore = "ENG-2141: flaky test"]
@@ -729,3 +831,442 @@ async fn switch_database_with_use() {
     conn.query_drop("SELECT b FROM t").await.unwrap();
     conn.query_drop("SELECT c FROM t2").await.unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn wait_for_replication() {
+    let (opts, _handle) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    let mut upstream = mysql_async::Conn::from_url(MySQLAdapter::url())
+        .await
+        .unwrap();
+
+    conn.query_drop("CREATE TABLE Cats (id int, PRIMARY KEY(id))")
+        .await
+        .unwrap();
+    conn.query_drop("INSERT INTO Cats (id) VALUES (1), (2), (3)")
+        .await
+        .unwrap();
+
+    // Everything written before the WAIT has been committed upstream, so once it returns ReadySet
+    // must be at least as far along as the upstream was before it was issued
+    let (file, position): (String, u32) = upstream
+        .query_first::<mysql_async::Row, _>("SHOW MASTER STATUS")
+        .await
+        .unwrap()
+        .map(|row| (row.get(0).unwrap(), row.get(1).unwrap()))
+        .unwrap();
+    let target = ReplicationOffset::from_str(&format!("{file}:{position}")).unwrap();
+
+    let rows: Vec<mysql_async::Row> = conn.query("WAIT FOR REPLICATION").await.unwrap();
+    let status = ReadySetStatus::try_from(rows).unwrap();
+    assert_eq!(status.snapshot_status, SnapshotStatus::Completed);
+    assert!(status.replication_offset.unwrap() >= target);
+
+    let rows: Vec<mysql_async::Row> = conn.query("SHOW READYSET STATUS").await.unwrap();
+    let status = ReadySetStatus::try_from(rows).unwrap();
+    assert!(status.replication_offset.unwrap() >= target);
+
+    // The table (and its rows) have been replicated, so we can now cache queries against it
+    conn.query_drop("CREATE CACHE FROM SELECT Cats.id FROM Cats WHERE Cats.id = ?")
+        .await
+        .unwrap();
+    let row: Option<(i32,)> = conn
+        .query_first("SELECT Cats.id FROM Cats WHERE Cats.id = 2")
+        .await
+        .unwrap();
+    assert_eq!(row, Some((2,)));
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+}
+
+#[cfg(feature = "failure_injection")]
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn wait_for_replication_blocks_until_caught_up() {
+    use readyset::failpoints;
+
+    let (opts, mut handle) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    let mut upstream = mysql_async::Conn::from_url(MySQLAdapter::url())
+        .await
+        .unwrap();
+
+    conn.query_drop("CREATE TABLE Cats (id int, PRIMARY KEY(id))")
+        .await
+        .unwrap();
+    conn.query_drop("WAIT FOR REPLICATION").await.unwrap();
+
+    handle
+        .set_failpoint(failpoints::PAUSE_REPLICATION, "return")
+        .await;
+    conn.query_drop("INSERT INTO Cats (id) VALUES (1)")
+        .await
+        .unwrap();
+    let (file, position): (String, u32) = upstream
+        .query_first::<mysql_async::Row, _>("SHOW MASTER STATUS")
+        .await
+        .unwrap()
+        .map(|row| (row.get(0).unwrap(), row.get(1).unwrap()))
+        .unwrap();
+    let target = ReplicationOffset::from_str(&format!("{file}:{position}")).unwrap();
+
+    // ReadySet can't catch up to the insert while replication is paused, so the wait must not
+    // return until it's resumed
+    let mut wait = tokio::spawn(async move {
+        let rows: Vec<mysql_async::Row> = conn.query("WAIT FOR REPLICATION").await.unwrap();
+        (conn, ReadySetStatus::try_from(rows).unwrap())
+    });
+    assert!(tokio::time::timeout(Duration::from_secs(2), &mut wait)
+        .await
+        .is_err());
+
+    handle
+        .set_failpoint(failpoints::PAUSE_REPLICATION, "off")
+        .await;
+    let (mut conn, status) = wait.await.unwrap();
+    assert!(status.replication_offset.unwrap() >= target);
+
+    conn.query_drop("CREATE CACHE FROM SELECT Cats.id FROM Cats WHERE Cats.id = ?")
+        .await
+        .unwrap();
+    let row: Option<(i32,)> = conn
+        .query_first("SELECT Cats.id FROM Cats WHERE Cats.id = 1")
+        .await
+        .unwrap();
+    assert_eq!(row, Some((1,)));
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn read_your_writes() {
+    let (opts, _handle) = setup_with(
+        BackendBuilder::new()
+            .require_authentication(false)
+            .read_your_writes_timeout(Some(Duration::from_secs(30))),
+    )
+    .await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+
+    conn.query_drop("CREATE TABLE Cats (id int, name text, PRIMARY KEY(id))")
+        .await
+        .unwrap();
+    conn.query_drop("INSERT INTO Cats (id, name) VALUES (1, 'Bob')")
+        .await
+        .unwrap();
+    conn.query_drop("WAIT FOR REPLICATION").await.unwrap();
+    conn.query_drop("CREATE CACHE FROM SELECT Cats.name FROM Cats WHERE Cats.id = ?")
+        .await
+        .unwrap();
+
+    // A read made immediately after a write in the same session must reflect that write, and
+    // should still be served from ReadySet
+    for (id, name) in [(1, "Bob"), (2, "Jane"), (3, "Mittens")] {
+        if id != 1 {
+            conn.query_drop(format!(
+                "INSERT INTO Cats (id, name) VALUES ({id}, '{name}')"
+            ))
+            .await
+            .unwrap();
+        }
+        let row: Option<(String,)> = conn
+            .query_first(format!("SELECT Cats.name FROM Cats WHERE Cats.id = {id}"))
+            .await
+            .unwrap();
+        assert_eq!(row, Some((name.to_owned(),)));
+        assert_eq!(
+            last_query_info(&mut conn).await.destination,
+            QueryDestination::Readyset
+        );
+    }
+
+    // Updates are visible too
+    conn.query_drop("UPDATE Cats SET name = 'Robert' WHERE id = 1")
+        .await
+        .unwrap();
+    let row: Option<(String,)> = conn
+        .query_first("SELECT Cats.name FROM Cats WHERE Cats.id = 1")
+        .await
+        .unwrap();
+    assert_eq!(row, Some(("Robert".to_owned(),)));
+}
+
+/// Reads from `Cats` both ad-hoc and with the prepared `select`, asserting that both reads go to
+/// `destination`
+async fn assert_cats_destination(
+    conn: &mut mysql_async::Conn,
+    select: &mysql_async::Statement,
+    destination: QueryDestination,
+) {
+    let row: Option<(i32,)> = conn
+        .query_first("SELECT Cats.id FROM Cats WHERE Cats.id = 1")
+        .await
+        .unwrap();
+    assert_eq!(row, Some((1,)));
+    assert_eq!(last_query_info(conn).await.destination, destination);
+
+    let row: Option<(i32,)> = conn.exec_first(select, (1,)).await.unwrap();
+    assert_eq!(row, Some((1,)));
+    assert_eq!(last_query_info(conn).await.destination, destination);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn fallback_on_controller_loss() {
+    let controller_health = ControllerHealth::new(Duration::ZERO);
+    let (opts, _handle) = setup_with(
+        BackendBuilder::new()
+            .require_authentication(false)
+            .controller_health(Some(controller_health.clone())),
+    )
+    .await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+
+    conn.query_drop("CREATE TABLE Cats (id int, PRIMARY KEY(id))")
+        .await
+        .unwrap();
+    conn.query_drop("INSERT INTO Cats (id) VALUES (1)")
+        .await
+        .unwrap();
+    conn.query_drop("WAIT FOR REPLICATION").await.unwrap();
+    conn.query_drop("CREATE CACHE FROM SELECT Cats.id FROM Cats WHERE Cats.id = ?")
+        .await
+        .unwrap();
+    let select = conn
+        .prep("SELECT Cats.id FROM Cats WHERE Cats.id = ?")
+        .await
+        .unwrap();
+
+    assert_cats_destination(&mut conn, &select, QueryDestination::Readyset).await;
+
+    // Simulate the controller becoming unreachable
+    controller_health.record_check(false);
+    assert!(controller_health.is_lost());
+    assert_cats_destination(&mut conn, &select, QueryDestination::Upstream).await;
+
+    // Once the controller is back, we go back to serving reads from the cache
+    controller_health.record_check(true);
+    assert_cats_destination(&mut conn, &select, QueryDestination::Readyset).await;
+}
+
+/// Runs a query joining `Cats` and `Names` (which fails to migrate, since it compares columns of
+/// incompatible types), asserting that it goes to `destination`
+async fn assert_cat_names_destination(conn: &mut mysql_async::Conn, destination: QueryDestination) {
+    let rows: Vec<i32> = conn
+        .query("SELECT Cats.id FROM Cats JOIN Names ON Cats.id = Names.name")
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![1]);
+    assert_eq!(last_query_info(conn).await.destination, destination);
+}
+
+/// The backoff after the first failed attempt to migrate a query in [`failed_migrations_back_off`]
+const MIGRATION_BACKOFF: Duration = Duration::from_millis(200);
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn failed_migrations_back_off() {
+    let query_status_cache: &'static _ = Box::leak(Box::new(
+        QueryStatusCache::new().initial_migration_backoff(MIGRATION_BACKOFF),
+    ));
+    let (opts, _handle) = TestBuilder::new(BackendBuilder::new().require_authentication(false))
+        .fallback(true)
+        .query_status_cache(query_status_cache)
+        .build::<MySQLAdapter>()
+        .await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+
+    conn.query_drop("CREATE TABLE Cats (id int, PRIMARY KEY(id))")
+        .await
+        .unwrap();
+    conn.query_drop("CREATE TABLE Names (name varchar(255))")
+        .await
+        .unwrap();
+    conn.query_drop("INSERT INTO Cats (id) VALUES (1)")
+        .await
+        .unwrap();
+    conn.query_drop("INSERT INTO Names (name) VALUES ('1')")
+        .await
+        .unwrap();
+    conn.query_drop("WAIT FOR REPLICATION").await.unwrap();
+
+    // The first attempt fails to migrate the query, so it's run against upstream instead
+    assert_cat_names_destination(&mut conn, QueryDestination::ReadysetThenUpstream).await;
+
+    // Until the backoff elapses, we don't try to migrate the query again
+    assert_cat_names_destination(&mut conn, QueryDestination::Upstream).await;
+
+    tokio::time::sleep(MIGRATION_BACKOFF).await;
+    assert_cat_names_destination(&mut conn, QueryDestination::ReadysetThenUpstream).await;
+
+    // The backoff doubles after the second failure
+    tokio::time::sleep(MIGRATION_BACKOFF).await;
+    assert_cat_names_destination(&mut conn, QueryDestination::Upstream).await;
+
+    // A statement prepared during the backoff is only prepared against upstream...
+    let stmt = conn
+        .prep("SELECT Cats.id FROM Cats JOIN Names ON Cats.id = Names.name")
+        .await
+        .unwrap();
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Upstream
+    );
+
+    // ...but tries to migrate the query again when it's executed after the backoff has elapsed
+    tokio::time::sleep(MIGRATION_BACKOFF).await;
+    let rows: Vec<i32> = conn.exec(&stmt, ()).await.unwrap();
+    assert_eq!(rows, vec![1]);
+    let info = last_query_info(&mut conn).await;
+    assert_eq!(info.destination, QueryDestination::Upstream);
+    assert!(!info.noria_error.is_empty());
+
+    // That attempt failed too, so executing the statement again doesn't retry the migration
+    let rows: Vec<i32> = conn.exec(&stmt, ()).await.unwrap();
+    assert_eq!(rows, vec![1]);
+    let info = last_query_info(&mut conn).await;
+    assert_eq!(info.destination, QueryDestination::Upstream);
+    assert!(info.noria_error.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn preload_queries() {
+    MySQLAdapter::recreate_database().await;
+    let mut upstream = mysql_async::Conn::from_url(MySQLAdapter::url())
+        .await
+        .unwrap();
+    upstream
+        .query_drop("CREATE TABLE Cats (id int, name text, PRIMARY KEY(id))")
+        .await
+        .unwrap();
+    upstream
+        .query_drop("INSERT INTO Cats (id, name) VALUES (1, 'Bob'), (2, 'Jane')")
+        .await
+        .unwrap();
+
+    let (opts, mut handle) = TestBuilder::default()
+        .fallback(true)
+        .recreate_database(false)
+        .preload_queries(
+            "-- Caches to create on startup
+             CREATE CACHE cat_name FROM SELECT name FROM Cats WHERE id = ?;
+             SELECT id FROM Cats WHERE name = ?;
+             this is not a query;
+             SELECT id FROM Cats WHERE name = 'semi;colon' AND id = ?;
+             SELECT * FROM nonexistent_table;",
+        )
+        .build::<MySQLAdapter>()
+        .await;
+
+    // The caches should exist before any clients have connected, and the queries that couldn't be
+    // parsed or cached shouldn't have prevented the others from being created
+    let views = handle.views().await.unwrap();
+    assert_eq!(views.len(), 3);
+    assert!(views.keys().any(|view| view.name == "cat_name"));
+
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    let row: Option<(String,)> = conn
+        .query_first("SELECT name FROM Cats WHERE id = 2")
+        .await
+        .unwrap();
+    assert_eq!(row, Some(("Jane".to_owned(),)));
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn kill_proxied_query() {
+    let (opts, _handle) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts.clone()).await.unwrap();
+    let mut killer = mysql_async::Conn::new(opts).await.unwrap();
+    let id = conn.id();
+
+    let query = tokio::spawn(async move {
+        let res = conn.query_drop("SELECT SLEEP(60)").await;
+        (conn, res)
+    });
+    // Give the query time to start running against the upstream database
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    killer.query_drop(format!("KILL QUERY {id}")).await.unwrap();
+
+    let (mut conn, res) = tokio::time::timeout(Duration::from_secs(10), query)
+        .await
+        .expect("Killed query should abort promptly")
+        .unwrap();
+    let err = res.unwrap_err();
+    assert!(
+        matches!(err, mysql_async::Error::Server(ref e) if e.code == 1317),
+        "err = {err:?}"
+    );
+
+    // The connection can still be used once the upstream has stopped running the query
+    let row: Option<(i32,)> =
+        tokio::time::timeout(Duration::from_secs(10), conn.query_first("SELECT 1"))
+            .await
+            .expect("Upstream query should have been cancelled")
+            .unwrap();
+    assert_eq!(row, Some((1,)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn validate_prepared_statement_results() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    recorder.install().unwrap();
+
+    let (opts, _handle) = setup_with(
+        BackendBuilder::new()
+            .require_authentication(false)
+            .validate_queries(true, false),
+    )
+    .await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    let mut upstream = mysql_async::Conn::from_url(MySQLAdapter::url())
+        .await
+        .unwrap();
+
+    conn.query_drop("CREATE TABLE t (x int)").await.unwrap();
+    conn.query_drop("INSERT INTO t (x) VALUES (1)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    // Write a row upstream without writing it to the binlog, so that ReadySet never sees it and
+    // its results no longer match the upstream's
+    upstream
+        .query_drop("SET SESSION sql_log_bin = 0")
+        .await
+        .unwrap();
+    upstream
+        .query_drop("INSERT INTO t (x) VALUES (1)")
+        .await
+        .unwrap();
+
+    let prepared = conn.prep("SELECT x FROM t WHERE x = ?").await.unwrap();
+    let rows: Vec<i32> = conn.exec(prepared, (1,)).await.unwrap();
+    assert_eq!(rows, vec![1]);
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+
+    let mismatches = snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .filter(|(key, ..)| key.key().name() == QUERY_VALIDATION_MISMATCH)
+        .map(|(.., value)| value)
+        .collect::<Vec<_>>();
+    assert_eq!(mismatches, vec![DebugValue::Counter(1)]);
+}
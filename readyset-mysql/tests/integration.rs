@@ -5,17 +5,20 @@ use std::time::Duration;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use mysql_async::prelude::Queryable;
 use readyset::status::ReadySetStatus;
-use readyset_adapter::backend::noria_connector::ReadBehavior;
-use readyset_adapter::backend::{MigrationMode, QueryInfo};
+use readyset::ViewRequest;
+use readyset_adapter::backend::noria_connector::{NoriaConnector, ReadBehavior};
+use readyset_adapter::backend::{MigrationMode, QueryInfo, ZeroRowWriteMode};
+use readyset_adapter::feature_flags::{FeatureFlags, READ_ONLY};
 use readyset_adapter::proxied_queries_reporter::ProxiedQueriesReporter;
 use readyset_adapter::query_status_cache::{MigrationStyle, QueryStatusCache};
-use readyset_adapter::BackendBuilder;
+use readyset_adapter::{AuditLog, BackendBuilder};
 use readyset_client_metrics::QueryDestination;
 use readyset_client_test_helpers::mysql_helpers::{last_query_info, MySQLAdapter};
 use readyset_client_test_helpers::{sleep, TestBuilder};
 use readyset_errors::ReadySetError;
 use readyset_server::Handle;
 use readyset_telemetry_reporter::{TelemetryEvent, TelemetryInitializer, TelemetryReporter};
+use readyset_version::{COMMIT_ID, RELEASE_VERSION};
 
 async fn setup() -> (mysql_async::Opts, Handle) {
     readyset_tracing::init_test_logging();
@@ -580,7 +583,8 @@ async fn update_other_column() {
 #[tokio::test(flavor = "multi_thread")]
 #[ignore]
 async fn update_no_changes() {
-    // ignored because we currently *always* return 1 row(s) affected.
+    // ignored because we report the number of rows matched by the update, rather than the number
+    // of rows it changed
     let (opts, _handle) = setup().await;
     let mut conn = mysql_async::Conn::new(opts).await.unwrap();
     conn.query_drop("CREATE TABLE Cats (id int PRIMARY KEY, name VARCHAR(255), PRIMARY KEY(id))")
@@ -838,6 +842,94 @@ async fn prepared_select() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn prepared_select_param_metadata() {
+    let (opts, _handle) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE test (id int primary key, x int not null, y int)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let stmt = conn
+        .prep("SELECT test.id AS a, test.x AS b FROM test WHERE id = ? AND x = ? AND y = ?")
+        .await
+        .unwrap();
+    let params = stmt
+        .params()
+        .iter()
+        .map(|col| {
+            (
+                col.name_str().into_owned(),
+                col.flags()
+                    .contains(mysql_async::consts::ColumnFlags::NOT_NULL_FLAG),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        params,
+        vec![
+            ("id".to_owned(), true),
+            ("x".to_owned(), true),
+            ("y".to_owned(), false)
+        ]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn prepared_select_column_nullability() {
+    let (opts, _handle) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t1 (id int, x int not null, PRIMARY KEY (id))")
+        .await
+        .unwrap();
+    conn.query_drop("CREATE TABLE t2 (id int primary key, y int not null)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let not_null = |cols: &[mysql_async::Column]| {
+        cols.iter()
+            .map(|col| {
+                (
+                    col.name_str().into_owned(),
+                    col.flags()
+                        .contains(mysql_async::consts::ColumnFlags::NOT_NULL_FLAG),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // A primary key declared as a table constraint makes its columns non-nullable
+    let stmt = conn
+        .prep("SELECT t1.id, t1.x FROM t1 WHERE id = ?")
+        .await
+        .unwrap();
+    assert_eq!(not_null(stmt.params()), vec![("id".to_owned(), true)]);
+    assert_eq!(
+        not_null(stmt.columns()),
+        vec![("id".to_owned(), true), ("x".to_owned(), true)]
+    );
+
+    // Columns from the right-hand side of a LEFT JOIN can be NULL, even if they're declared NOT
+    // NULL
+    let stmt = conn
+        .prep(
+            "SELECT t1.id, t2.id AS t2_id, t2.y FROM t1 LEFT JOIN t2 ON t1.id = t2.id \
+             WHERE t1.x = ?",
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        not_null(stmt.columns()),
+        vec![
+            ("id".to_owned(), true),
+            ("t2_id".to_owned(), false),
+            ("y".to_owned(), false)
+        ]
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn create_view() {
     let (opts, _handle) = setup().await;
@@ -1405,6 +1497,107 @@ async fn write_timestamps() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn set_timestamp_format() {
+    let (opts, _handle) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop(
+        "CREATE TABLE posts (id int primary key, created_at TIMESTAMP, price DECIMAL(10, 2))",
+    )
+    .await
+    .unwrap();
+    conn.query_drop(
+        "INSERT INTO posts (id, created_at, price) VALUES (1, '2020-01-23 17:08:24', 19.99)",
+    )
+    .await
+    .unwrap();
+    sleep().await;
+
+    let query = "SELECT created_at FROM posts WHERE id = 1";
+    let native: NaiveDateTime = conn.query_first(query).await.unwrap().unwrap();
+    assert_eq!(native, NaiveDate::from_ymd(2020, 1, 23).and_hms(17, 08, 24));
+
+    conn.query_drop("SET readyset_timestamp_format = 'epoch_ms'")
+        .await
+        .unwrap();
+    let epoch_ms: i64 = conn.query_first(query).await.unwrap().unwrap();
+    assert_eq!(epoch_ms, 1579799304000);
+    let epoch_ms: i64 = conn
+        .exec_first("SELECT created_at FROM posts WHERE id = ?", (1,))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(epoch_ms, 1579799304000);
+
+    conn.query_drop("SET readyset_timestamp_format = 'rfc3339'")
+        .await
+        .unwrap();
+    let rfc3339: String = conn.query_first(query).await.unwrap().unwrap();
+    assert_eq!(rfc3339, "2020-01-23T17:08:24+00:00");
+    let price: String = conn
+        .query_first("SELECT price FROM posts WHERE id = 1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(price, "19.99");
+
+    conn.query_drop("SET readyset_timestamp_format = 'native'")
+        .await
+        .unwrap();
+    let native: NaiveDateTime = conn.query_first(query).await.unwrap().unwrap();
+    assert_eq!(native, NaiveDate::from_ymd(2020, 1, 23).and_hms(17, 08, 24));
+
+    conn.query_drop("SET readyset_timestamp_format = 'yesterday'")
+        .await
+        .unwrap_err();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn last_insert_id() {
+    let (opts, _handle) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts.clone()).await.unwrap();
+    conn.query_drop("CREATE TABLE t (id int AUTO_INCREMENT PRIMARY KEY, x int)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let id: u64 = conn
+        .query_first("SELECT LAST_INSERT_ID()")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(id, 0);
+
+    conn.query_drop("INSERT INTO t (x) VALUES (10), (20)")
+        .await
+        .unwrap();
+    let id: u64 = conn
+        .query_first("SELECT LAST_INSERT_ID()")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(id, 1);
+
+    conn.query_drop("INSERT INTO t (x) VALUES (30)")
+        .await
+        .unwrap();
+    let id: u64 = conn
+        .query_first("SELECT last_insert_id() AS id")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(id, 3);
+
+    // The last insert id is per-session
+    let mut other_conn = mysql_async::Conn::new(opts).await.unwrap();
+    let id: u64 = other_conn
+        .query_first("SELECT LAST_INSERT_ID()")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(id, 0);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn round_trip_time_type() {
     let (opts, _handle) = setup().await;
@@ -1520,6 +1713,40 @@ async fn insert_quoted_string() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn insert_stored_generated_column() {
+    let (opts, _handle) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop(
+        "CREATE TABLE Cats (id int PRIMARY KEY, a int, b int, \
+         total int GENERATED ALWAYS AS (a + b) STORED)",
+    )
+    .await
+    .unwrap();
+    sleep().await;
+
+    conn.query_drop("INSERT INTO Cats (id, a, b) VALUES (1, 2, 3)")
+        .await
+        .unwrap();
+    // Stored values (eg from replication) are kept as-is
+    conn.query_drop("INSERT INTO Cats (id, a, b, total) VALUES (2, 4, 5, 9)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let rows: Vec<(i32, i32, i32, i32)> = conn
+        .query("SELECT id, a, b, total FROM Cats WHERE Cats.id = 1")
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![(1, 2, 3, 5)]);
+
+    let rows: Vec<(i32, i32, i32, i32)> = conn
+        .query("SELECT id, a, b, total FROM Cats WHERE Cats.id = 2")
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![(2, 4, 5, 9)]);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn json_column_insert_read() {
     let (opts, _handle) = setup().await;
@@ -1639,9 +1866,25 @@ async fn show_readyset_status() {
 async fn show_readyset_version() {
     let (opts, _handle) = setup().await;
     let mut conn = mysql_async::Conn::new(opts).await.unwrap();
-    conn.query_drop("SHOW READYSET VERSION;")
-        .await
-        .expect("should be OK");
+    let rows: Vec<(String, String)> = conn.query("SHOW READYSET VERSION;").await.unwrap();
+    assert!(rows.contains(&("commit id".to_string(), COMMIT_ID.to_string())));
+    assert!(rows.contains(&("release version".to_string(), RELEASE_VERSION.to_string())));
+    assert!(rows.iter().any(|(name, _)| name == "platform"));
+    let schema_version = |rows: &[(String, String)]| -> usize {
+        rows.iter()
+            .find(|(name, _)| name == "schema version")
+            .unwrap()
+            .1
+            .parse()
+            .unwrap()
+    };
+    let old_schema_version = schema_version(&rows);
+
+    conn.query_drop("CREATE TABLE t (x int)").await.unwrap();
+    sleep().await;
+
+    let rows: Vec<(String, String)> = conn.query("SHOW READYSET VERSION;").await.unwrap();
+    assert_eq!(schema_version(&rows), old_schema_version + 1);
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -1720,6 +1963,37 @@ async fn test_show_proxied_queries_telemetry() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_connection_lifecycle_telemetry() {
+    readyset_tracing::init_test_logging();
+    let (mut reporter, opts, _handle) = setup_telemetry().await;
+
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t (x int)").await.unwrap();
+    conn.query_drop("SELECT x FROM t").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    conn.disconnect().await.unwrap();
+    sleep().await;
+
+    reporter.run_timeout(Duration::from_millis(500)).await;
+
+    assert_eq!(
+        1,
+        reporter
+            .check_event(TelemetryEvent::ConnectionOpened)
+            .await
+            .len()
+    );
+    let closed = reporter.check_event(TelemetryEvent::ConnectionClosed).await;
+    assert_eq!(closed.len(), 1);
+    let duration = closed[0].connection_duration_ms.unwrap();
+    assert!(
+        (100..60_000).contains(&duration),
+        "implausible connection duration: {duration}ms"
+    );
+    assert!(closed[0].query_count.unwrap() >= 2);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_show_caches_queries_telemetry() {
     readyset_tracing::init_test_logging();
@@ -1814,3 +2088,580 @@ async fn test_proxied_queries_telemetry() {
     // with its initial value
     assert_eq!(telemetry.migration_status, Some("pending".to_string()));
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn read_only_rejects_writes() {
+    readyset_tracing::init_test_logging();
+    let backend = BackendBuilder::new()
+        .require_authentication(false)
+        .read_only(true);
+    let (opts, _handle) = TestBuilder::new(backend).build::<MySQLAdapter>().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t (id int, PRIMARY KEY(id))")
+        .await
+        .unwrap();
+    sleep().await;
+
+    for write in [
+        "INSERT INTO t (id) VALUES (1)",
+        "UPDATE t SET id = 2 WHERE id = 1",
+        "DELETE FROM t WHERE id = 1",
+    ] {
+        match conn.query_drop(write).await.unwrap_err() {
+            mysql_async::Error::Server(e) => {
+                // ER_OPTION_PREVENTS_STATEMENT
+                assert_eq!(e.code, 1290);
+                assert!(e.message.contains("read-only"), "{}", e.message);
+            }
+            e => panic!("Unexpected error: {}", e),
+        }
+    }
+
+    let rows: Vec<i32> = conn
+        .query("SELECT t.id FROM t WHERE t.id = 1")
+        .await
+        .unwrap();
+    assert!(rows.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn max_prepared_statements_evicts_least_recently_used() {
+    readyset_tracing::init_test_logging();
+    let backend = BackendBuilder::new()
+        .require_authentication(false)
+        .max_prepared_statements(Some(2));
+    let (opts, _handle) = TestBuilder::new(backend).build::<MySQLAdapter>().await;
+    // Disable the client's statement cache, so that preparing a statement again actually sends
+    // another prepare to the adapter
+    let opts = mysql_async::OptsBuilder::from_opts(opts).stmt_cache_size(0);
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t (id int primary key, x int, y int)")
+        .await
+        .unwrap();
+    conn.query_drop("INSERT INTO t (id, x, y) VALUES (1, 2, 3)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let by_id = conn.prep("SELECT t.x FROM t WHERE t.id = ?").await.unwrap();
+    let by_x = conn.prep("SELECT t.id FROM t WHERE t.x = ?").await.unwrap();
+    let res: Option<i32> = conn.exec_first(&by_id, (1,)).await.unwrap();
+    assert_eq!(res, Some(2));
+
+    // `by_x` is now the least recently used statement, so preparing a third statement evicts it
+    // from ReadySet - but executing it again transparently prepares it against ReadySet again
+    // (evicting `by_id` in turn)
+    let by_y = conn.prep("SELECT t.id FROM t WHERE t.y = ?").await.unwrap();
+    let res: Option<i32> = conn.exec_first(&by_x, (2,)).await.unwrap();
+    assert_eq!(res, Some(1));
+    let res: Option<i32> = conn.exec_first(&by_y, (3,)).await.unwrap();
+    assert_eq!(res, Some(1));
+    let res: Option<i32> = conn.exec_first(&by_id, (1,)).await.unwrap();
+    assert_eq!(res, Some(2));
+
+    // Closed statements are deallocated, and can no longer be executed
+    conn.close(by_id.clone()).await.unwrap();
+    conn.exec_first::<i32, _, _>(&by_id, (1,))
+        .await
+        .unwrap_err();
+    let res: Option<i32> = conn.exec_first(&by_y, (3,)).await.unwrap();
+    assert_eq!(res, Some(1));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn read_only_feature_flag() {
+    readyset_tracing::init_test_logging();
+    let feature_flags = FeatureFlags::new();
+    let backend = BackendBuilder::new()
+        .require_authentication(false)
+        .feature_flags(feature_flags.clone());
+    let (opts, mut handle) = TestBuilder::new(backend).build::<MySQLAdapter>().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t (id int, PRIMARY KEY(id))")
+        .await
+        .unwrap();
+    sleep().await;
+
+    conn.query_drop("INSERT INTO t (id) VALUES (1)")
+        .await
+        .unwrap();
+
+    handle
+        .set_feature_flag(READ_ONLY.to_owned(), true)
+        .await
+        .unwrap();
+
+    // The flag doesn't take effect until the adapter refreshes its feature flags
+    conn.query_drop("INSERT INTO t (id) VALUES (2)")
+        .await
+        .unwrap();
+
+    feature_flags.refresh(&mut handle).await.unwrap();
+    match conn
+        .query_drop("INSERT INTO t (id) VALUES (3)")
+        .await
+        .unwrap_err()
+    {
+        mysql_async::Error::Server(e) => {
+            // ER_OPTION_PREVENTS_STATEMENT
+            assert_eq!(e.code, 1290);
+            assert!(e.message.contains("read-only"), "{}", e.message);
+        }
+        e => panic!("Unexpected error: {}", e),
+    }
+
+    handle
+        .set_feature_flag(READ_ONLY.to_owned(), false)
+        .await
+        .unwrap();
+    feature_flags.refresh(&mut handle).await.unwrap();
+    conn.query_drop("INSERT INTO t (id) VALUES (3)")
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn create_cache_is_audit_logged() {
+    readyset_tracing::init_test_logging();
+    let audit_log_path = std::env::temp_dir().join(format!(
+        "readyset-audit-create-cache-{}.log",
+        std::process::id()
+    ));
+    let backend = BackendBuilder::new()
+        .require_authentication(false)
+        .audit_log(Some(AuditLog::open(&audit_log_path).unwrap()));
+    let (opts, _handle) = TestBuilder::new(backend)
+        .migration_mode(MigrationMode::OutOfBand)
+        .build::<MySQLAdapter>()
+        .await;
+    let opts = mysql_async::OptsBuilder::from_opts(opts).user(Some("alice"));
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t (id int, name text, PRIMARY KEY(id))")
+        .await
+        .unwrap();
+    sleep().await;
+
+    conn.query_drop("CREATE CACHE FROM SELECT t.id FROM t WHERE t.name = 'hunter2'")
+        .await
+        .unwrap();
+    // Non-DDL statements aren't logged
+    conn.query_drop("INSERT INTO t (id, name) VALUES (1, 'hunter2')")
+        .await
+        .unwrap();
+
+    let log = std::fs::read_to_string(&audit_log_path).unwrap();
+    std::fs::remove_file(&audit_log_path).unwrap();
+    let lines = log.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2, "{log}");
+    assert!(lines[0].contains("CREATE TABLE"), "{}", lines[0]);
+
+    let entry = lines[1];
+    assert!(entry.contains("\"timestamp\":"), "{entry}");
+    assert!(entry.contains("\"client_addr\":\"127.0.0.1:"), "{entry}");
+    assert!(entry.contains("\"user\":\"alice\""), "{entry}");
+    assert!(entry.contains("\"statement\":\"CREATE CACHE"), "{entry}");
+    // Literals in the statement are redacted
+    assert!(!entry.contains("hunter2"), "{entry}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn primary_key_lookup_reads_base_table() {
+    let (opts, mut handle) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE Cats (id int, name VARCHAR(255), PRIMARY KEY(id))")
+        .await
+        .unwrap();
+    conn.query_drop("INSERT INTO Cats (id, name) VALUES (1, 'Bob'), (2, 'Jane')")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let num_views = handle.views().await.unwrap().len();
+
+    let rows: Vec<(i32, String)> = conn
+        .query("SELECT * FROM Cats WHERE Cats.id = 2")
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![(2, "Jane".to_owned())]);
+
+    let rows: Vec<(i32, String)> = conn.query("SELECT * FROM Cats WHERE id = 3").await.unwrap();
+    assert!(rows.is_empty());
+
+    // The lookups should have been served from the base table, without creating any views
+    assert_eq!(handle.views().await.unwrap().len(), num_views);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn prepared_primary_key_lookup_reads_base_table() {
+    let (opts, _handle) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE Cats (id int, name VARCHAR(255), PRIMARY KEY(id))")
+        .await
+        .unwrap();
+    conn.query_drop("INSERT INTO Cats (id, name) VALUES (1, 'Bob'), (2, 'Jane')")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let stmt = conn.prep("SELECT * FROM Cats WHERE id = ?").await.unwrap();
+    let rows: Vec<(i32, String)> = conn.exec(&stmt, (2,)).await.unwrap();
+    assert_eq!(rows, vec![(2, "Jane".to_owned())]);
+
+    let rows: Vec<(i32, String)> = conn.exec(&stmt, (3,)).await.unwrap();
+    assert!(rows.is_empty());
+
+    // Writes should be visible to the prepared lookup
+    conn.query_drop("INSERT INTO Cats (id, name) VALUES (3, 'Tom')")
+        .await
+        .unwrap();
+    sleep().await;
+    let rows: Vec<(i32, String)> = conn.exec(&stmt, (3,)).await.unwrap();
+    assert_eq!(rows, vec![(3, "Tom".to_owned())]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn kill_query() {
+    let (opts, _handle) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts.clone()).await.unwrap();
+    let other = mysql_async::Conn::new(opts).await.unwrap();
+    assert_ne!(conn.id(), other.id());
+
+    // Killing a connection that isn't running a query does nothing
+    conn.query_drop(format!("KILL QUERY {}", other.id()))
+        .await
+        .unwrap();
+
+    let err = conn.query_drop("KILL QUERY 4294967296").await.unwrap_err();
+    assert!(
+        matches!(err, mysql_async::Error::Server(ref e) if e.code == 1094),
+        "err = {err:?}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn view_node_index() {
+    let (opts, mut handle) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t (id int, PRIMARY KEY(id))")
+        .await
+        .unwrap();
+    conn.query_drop("CREATE CACHE q FROM SELECT id FROM t WHERE id = ?")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let mut noria = NoriaConnector::new(
+        (*handle).clone(),
+        Default::default(),
+        Default::default(),
+        ReadBehavior::Blocking,
+        readyset_data::Dialect::DEFAULT_MYSQL,
+        vec![],
+        false,
+    )
+    .await;
+    let node = noria.view_node_index("q").await.unwrap();
+
+    let reader = handle
+        .view_builder(ViewRequest {
+            name: "q".into(),
+            filter: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(node, reader.node);
+    // The reader is a separate node from the node it's materializing
+    assert_ne!(Some(&node), handle.views().await.unwrap().get(&"q".into()));
+
+    assert!(noria.view_node_index("nonexistent").await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn insert_duplicate_primary_key() {
+    let (opts, _handle) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t (id int, val int, PRIMARY KEY(id))")
+        .await
+        .unwrap();
+    sleep().await;
+
+    conn.query_drop("INSERT INTO t (id, val) VALUES (1, 1)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    for insert in [
+        "INSERT INTO t (id, val) VALUES (1, 2)",
+        "INSERT INTO t (id, val) VALUES (2, 2), (2, 3)",
+    ] {
+        match conn.query_drop(insert).await.unwrap_err() {
+            mysql_async::Error::Server(e) => {
+                // ER_DUP_ENTRY
+                assert_eq!(e.code, 1062);
+                assert!(e.message.contains("Duplicate entry"), "{}", e.message);
+            }
+            e => panic!("Unexpected error: {}", e),
+        }
+    }
+    sleep().await;
+
+    let rows: Vec<(i32, i32)> = conn
+        .query("SELECT t.id, t.val FROM t WHERE t.id = 1")
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![(1, 1)]);
+    let rows: Vec<(i32, i32)> = conn
+        .query("SELECT t.id, t.val FROM t WHERE t.id = 2")
+        .await
+        .unwrap();
+    assert!(rows.is_empty());
+}
+
+#[cfg(feature = "failure_injection")]
+mod partial_results {
+    use readyset::failpoints;
+    use serial_test::serial;
+
+    use super::*;
+
+    const QUERY: &str = "SELECT id FROM t WHERE id IN (1, 2, 3, 4, 5, 6, 7, 8, 9, 10)";
+    const WARNING: &str = "Results are incomplete: 1 shard(s) of the cache were unavailable";
+
+    /// Starts a server with two shards, and creates and fills a cache for [`QUERY`] in it
+    async fn setup(allow_partial_results: bool) -> (mysql_async::Conn, Handle) {
+        let (opts, handle) = TestBuilder::default()
+            .sharding(Some(2))
+            .allow_partial_results(allow_partial_results)
+            .build::<MySQLAdapter>()
+            .await;
+        let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+        conn.query_drop("CREATE TABLE t (id int PRIMARY KEY)")
+            .await
+            .unwrap();
+        conn.query_drop(
+            "INSERT INTO t (id) VALUES (1), (2), (3), (4), (5), (6), (7), (8), (9), (10)",
+        )
+        .await
+        .unwrap();
+        sleep().await;
+
+        // Read from every shard while they're all available, to migrate and fill the cache
+        let rows: Vec<i32> = conn.query(QUERY).await.unwrap();
+        assert_eq!(rows.len(), 10);
+        (conn, handle)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[serial]
+    async fn unavailable_shard_returns_partial_results_with_warning() {
+        let (mut conn, mut handle) = setup(true).await;
+        assert_eq!(conn.get_warnings(), 0);
+
+        handle
+            .set_failpoint(failpoints::UNAVAILABLE_READER_SHARDS, "return")
+            .await;
+
+        let rows: Vec<i32> = conn.query(QUERY).await.unwrap();
+        assert!(!rows.is_empty() && rows.len() < 10);
+        assert_eq!(conn.get_warnings(), 1);
+        let warnings: Vec<(String, u32, String)> = conn.query("SHOW WARNINGS").await.unwrap();
+        assert_eq!(
+            warnings,
+            vec![("Warning".to_owned(), 1105, WARNING.to_owned())]
+        );
+
+        let prepared_rows: Vec<i32> = conn.exec(QUERY, ()).await.unwrap();
+        assert_eq!(prepared_rows.len(), rows.len());
+        assert_eq!(conn.get_warnings(), 1);
+
+        handle
+            .set_failpoint(failpoints::UNAVAILABLE_READER_SHARDS, "off")
+            .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[serial]
+    async fn unavailable_shard_fails_read_by_default() {
+        let (mut conn, mut handle) = setup(false).await;
+
+        handle
+            .set_failpoint(failpoints::UNAVAILABLE_READER_SHARDS, "return")
+            .await;
+
+        conn.query::<i32, _>(QUERY).await.unwrap_err();
+
+        handle
+            .set_failpoint(failpoints::UNAVAILABLE_READER_SHARDS, "off")
+            .await;
+    }
+}
+
+#[cfg(feature = "failure_injection")]
+mod statement_timeout {
+    use readyset::failpoints;
+    use serial_test::serial;
+
+    use super::*;
+
+    const QUERY: &str = "SELECT id FROM t WHERE id = 1";
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[serial]
+    async fn slow_cache_reads_time_out() {
+        let (opts, mut handle) = setup().await;
+        let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+        conn.query_drop("CREATE TABLE t (id int PRIMARY KEY)")
+            .await
+            .unwrap();
+        conn.query_drop("INSERT INTO t (id) VALUES (1)")
+            .await
+            .unwrap();
+        sleep().await;
+
+        // Migrate and fill the cache before making reads from it slow
+        let rows: Vec<i32> = conn.query(QUERY).await.unwrap();
+        assert_eq!(rows, vec![1]);
+
+        conn.query_drop("SET max_execution_time = 100")
+            .await
+            .unwrap();
+        handle
+            .set_failpoint(failpoints::SLOW_CACHE_READS, "return(5000)")
+            .await;
+
+        let err = conn.query::<i32, _>(QUERY).await.unwrap_err();
+        assert!(
+            matches!(err, mysql_async::Error::Server(ref e) if e.code == 1317),
+            "{err}"
+        );
+        let err = conn.exec::<i32, _, _>(QUERY, ()).await.unwrap_err();
+        assert!(
+            matches!(err, mysql_async::Error::Server(ref e) if e.code == 1317),
+            "{err}"
+        );
+
+        handle
+            .set_failpoint(failpoints::SLOW_CACHE_READS, "off")
+            .await;
+
+        // Fast statements still succeed with the timeout set
+        let rows: Vec<i32> = conn.query(QUERY).await.unwrap();
+        assert_eq!(rows, vec![1]);
+        conn.query_drop("INSERT INTO t (id) VALUES (2)")
+            .await
+            .unwrap();
+    }
+}
+
+mod zero_row_writes {
+    use super::*;
+
+    async fn setup(zero_row_write_mode: ZeroRowWriteMode) -> (mysql_async::Conn, Handle) {
+        let backend = BackendBuilder::new()
+            .require_authentication(false)
+            .zero_row_write_mode(zero_row_write_mode);
+        let (opts, handle) = TestBuilder::new(backend).build::<MySQLAdapter>().await;
+        let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+        conn.query_drop("CREATE TABLE Cats (id int PRIMARY KEY, name VARCHAR(255))")
+            .await
+            .unwrap();
+        conn.query_drop("INSERT INTO Cats (id, name) VALUES (1, \"Bob\")")
+            .await
+            .unwrap();
+        sleep().await;
+        (conn, handle)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn succeed_by_default() {
+        let (mut conn, _handle) = setup(ZeroRowWriteMode::default()).await;
+
+        let deleted = conn
+            .query_iter("DELETE FROM Cats WHERE Cats.id = 2")
+            .await
+            .unwrap();
+        assert_eq!(deleted.affected_rows(), 0);
+        drop(deleted);
+        assert_eq!(conn.get_warnings(), 0);
+
+        let updated = conn
+            .exec_iter(
+                "UPDATE Cats SET Cats.name = ? WHERE Cats.id = ?",
+                ("Jane", 2),
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.affected_rows(), 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn warn() {
+        let (mut conn, _handle) = setup(ZeroRowWriteMode::Warn).await;
+
+        let deleted = conn
+            .query_iter("DELETE FROM Cats WHERE Cats.id = 2")
+            .await
+            .unwrap();
+        assert_eq!(deleted.affected_rows(), 0);
+        drop(deleted);
+        assert_eq!(conn.get_warnings(), 1);
+        let warnings: Vec<(String, u32, String)> = conn.query("SHOW WARNINGS").await.unwrap();
+        assert_eq!(
+            warnings,
+            vec![(
+                "Warning".to_owned(),
+                1105,
+                "Statement did not affect any rows".to_owned()
+            )]
+        );
+
+        let updated = conn
+            .exec_iter(
+                "UPDATE Cats SET Cats.name = ? WHERE Cats.id = ?",
+                ("Jane", 2),
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.affected_rows(), 0);
+        drop(updated);
+        assert_eq!(conn.get_warnings(), 1);
+
+        let updated = conn
+            .exec_iter(
+                "UPDATE Cats SET Cats.name = ? WHERE Cats.id = ?",
+                ("Jane", 1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.affected_rows(), 1);
+        drop(updated);
+        assert_eq!(conn.get_warnings(), 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn error() {
+        let (mut conn, _handle) = setup(ZeroRowWriteMode::Error).await;
+
+        let err = conn
+            .query_drop("DELETE FROM Cats WHERE Cats.id = 2")
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("DELETE statement did not affect any rows"),
+            "{err}"
+        );
+        conn.exec_drop(
+            "UPDATE Cats SET Cats.name = ? WHERE Cats.id = ?",
+            ("Jane", 2),
+        )
+        .await
+        .unwrap_err();
+
+        let deleted = conn
+            .query_iter("DELETE FROM Cats WHERE Cats.id = 1")
+            .await
+            .unwrap();
+        assert_eq!(deleted.affected_rows(), 1);
+    }
+}
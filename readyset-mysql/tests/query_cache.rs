@@ -1,6 +1,8 @@
 use mysql_async::prelude::*;
 use mysql_async::{Conn, Result, Row, Statement};
-use readyset_adapter::backend::{MigrationMode, QueryInfo, UnsupportedSetMode};
+use readyset_adapter::backend::{
+    MigrationMode, QueryInfo, UnsupportedQueryMode, UnsupportedSetMode,
+};
 use readyset_adapter::query_status_cache::QueryStatusCache;
 use readyset_adapter::BackendBuilder;
 use readyset_client_metrics::QueryDestination;
@@ -654,3 +656,84 @@ async fn drop_all_caches() {
     assert_eq!(query_status_cache.allow_list().len(), 0);
     assert_eq!(query_status_cache.deny_list().len(), 0);
 }
+
+async fn setup_with_unsupported_query_mode(
+    query_status_cache: &'static QueryStatusCache,
+    mode: UnsupportedQueryMode,
+) -> (mysql_async::Opts, Handle) {
+    TestBuilder::new(
+        BackendBuilder::default()
+            .require_authentication(false)
+            .unsupported_query_mode(mode),
+    )
+    .fallback(true)
+    .query_status_cache(query_status_cache)
+    .migration_mode(MigrationMode::InRequestPath)
+    .build::<MySQLAdapter>()
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn unsupported_query_mode_error() {
+    let query_status_cache: &'static _ = Box::leak(Box::new(QueryStatusCache::new()));
+    let (opts, _handle) =
+        setup_with_unsupported_query_mode(query_status_cache, UnsupportedQueryMode::Error).await;
+    let mut conn = Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t (a INT, b INT)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    // Both the first execution, which discovers that the query is unsupported, and subsequent
+    // executions of the now-denied query should return an error rather than being proxied
+    for _ in 0..2 {
+        let res: Result<Vec<Row>> = conn.query("SELECT * FROM t WHERE a = NOW()").await;
+        assert!(res.is_err());
+        assert_eq!(query_status_cache.deny_list().len(), 1);
+    }
+
+    // The same goes for preparing the denied query, and for queries ReadySet can't parse
+    let res: Result<Vec<Row>> = conn.exec("SELECT * FROM t WHERE a = NOW()", ()).await;
+    assert!(res.is_err());
+    let res: Result<Vec<Row>> = conn.query("SELECT * FROM t WHERE a SOUNDS LIKE 'x'").await;
+    assert!(res.is_err());
+
+    // Supported queries are unaffected
+    let res: Result<Vec<Row>> = conn.query("SELECT * FROM t").await;
+    assert!(res.is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn unsupported_query_mode_proxy() {
+    let query_status_cache: &'static _ = Box::leak(Box::new(QueryStatusCache::new()));
+    let (opts, _handle) =
+        setup_with_unsupported_query_mode(query_status_cache, UnsupportedQueryMode::Proxy).await;
+    let mut conn = Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t (a INT, b INT)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let res: Result<Vec<Row>> = conn.query("SELECT * FROM t WHERE a = NOW()").await;
+    assert!(res.is_ok());
+    assert_eq!(query_status_cache.deny_list().len(), 1);
+
+    let res: Result<Vec<Row>> = conn.query("SELECT * FROM t WHERE a = NOW()").await;
+    assert!(res.is_ok());
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Upstream
+    );
+
+    let res: Result<Vec<Row>> = conn.exec("SELECT * FROM t WHERE a = NOW()", ()).await;
+    assert!(res.is_ok());
+
+    let res: Result<Vec<Row>> = conn.query("SELECT * FROM t WHERE a SOUNDS LIKE 'x'").await;
+    assert!(res.is_ok());
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Upstream
+    );
+}
@@ -5,8 +5,10 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use eui48::MacAddressFormat;
 use psql_srv as ps;
+use readyset_adapter::auth::AuthBackend;
 use readyset_adapter::backend as cl;
 use readyset_data::DfValue;
+use tracing::warn;
 
 use crate::error::Error;
 use crate::query_handler::PostgreSqlQueryHandler;
@@ -82,15 +84,32 @@ impl ps::Backend for Backend {
         self.execute(statement_id, &params).await?.try_into()
     }
 
-    async fn on_close(&mut self, _statement_id: u32) -> Result<(), ps::Error> {
+    async fn on_close(&mut self, statement_id: u32) -> Result<(), ps::Error> {
+        self.0.remove_statement(statement_id);
         Ok(())
     }
 
+    fn warnings(&self) -> Vec<String> {
+        self.0
+            .last_query_warning()
+            .map(str::to_owned)
+            .into_iter()
+            .collect()
+    }
+
     async fn on_auth(&mut self, credentials: ps::Credentials) -> Result<(), ps::Error> {
         match credentials {
             ps::Credentials::Cleartext { user, password } => {
-                if self.users.get(&user) == Some(&password) {
-                    return Ok(());
+                // Clone the auth backend so that we don't hold a reference to `self` across the
+                // await point
+                let auth_backend = Arc::clone(&self.auth_backend);
+                match auth_backend.authenticate(&user, &password).await {
+                    Ok(true) => {
+                        self.set_authenticated_user(&user);
+                        return Ok(());
+                    }
+                    Ok(false) => {}
+                    Err(error) => warn!(%error, %user, "Failed to look up user"),
                 }
                 return Err(ps::Error::AuthenticationFailure(user));
             }
@@ -27,6 +27,12 @@ impl From<Error> for ps::Error {
                 ps::Error::MissingPreparedStatement(statement_id.to_string())
             }
             ReadySet(ReadySetError::Unsupported(s)) => ps::Error::Unsupported(s),
+            ReadySet(e @ ReadySetError::DuplicateKey { .. }) => {
+                ps::Error::UniqueViolation(e.to_string())
+            }
+            ReadySet(e @ ReadySetError::StatementTimeout) => {
+                ps::Error::QueryCanceled(e.to_string())
+            }
             ReadySet(e) => ps::Error::Unknown(e.to_string()),
             PostgreSql(e) => e.into(),
         }
@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 use lazy_static::lazy_static;
 use nom_sql::{
@@ -79,7 +80,6 @@ lazy_static! {
             "idle_in_transaction_session_timeout",
             "lock_timeout",
             "session_replication_role",
-            "statement_timeout",
             "temp_tablespaces",
             "transaction_deferrable",
             "transaction_isolation",
@@ -336,6 +336,39 @@ lazy_static! {
         ]);
 }
 
+/// Parse the value of a `SET statement_timeout` statement, which is either a number of
+/// milliseconds or a string containing a number with an optional unit. Returns `None` if the value
+/// can't be parsed, and `Some(None)` if the value disables the timeout.
+fn parse_statement_timeout(value: &SetPostgresParameterValue) -> Option<Option<Duration>> {
+    let millis = match value {
+        SetPostgresParameterValue::Default => 0,
+        SetPostgresParameterValue::Value(PostgresParameterValue::Single(
+            PostgresParameterValueInner::Literal(lit),
+        )) => match lit {
+            Literal::UnsignedInteger(i) => *i,
+            Literal::Integer(i) => u64::try_from(*i).ok()?,
+            Literal::String(s) => {
+                let s = s.trim();
+                let unit_start = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+                let n: u64 = s[..unit_start].parse().ok()?;
+                let multiplier = match s[unit_start..].trim() {
+                    "" | "ms" => 1,
+                    "s" => 1000,
+                    "min" => 60 * 1000,
+                    "h" => 60 * 60 * 1000,
+                    "d" => 24 * 60 * 60 * 1000,
+                    _ => return None,
+                };
+                n.checked_mul(multiplier)?
+            }
+            _ => return None,
+        },
+        SetPostgresParameterValue::Value(_) => return None,
+    };
+
+    Some((millis > 0).then(|| Duration::from_millis(millis)))
+}
+
 /// PostgreSQL flavor of [`QueryHandler`].
 pub struct PostgreSqlQueryHandler;
 
@@ -390,6 +423,10 @@ impl QueryHandler for PostgreSqlQueryHandler {
 
                     SetBehavior::SetSearchPath(search_path)
                 }
+                "statement_timeout" => match parse_statement_timeout(value) {
+                    Some(timeout) => SetBehavior::SetStatementTimeout(timeout),
+                    None => SetBehavior::Unsupported,
+                },
                 _ => {
                     if let Some(allowed_value) = ALLOWED_PARAMETERS_WITH_VALUE.get(name.as_str()) {
                         SetBehavior::proxy_if(allowed_value.set_value_is_allowed(value))
@@ -453,6 +490,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn statement_timeout() {
+        let sets_timeout = |stmt, timeout| {
+            assert_eq!(
+                PostgreSqlQueryHandler::handle_set_statement(&parse_set_statement(stmt)),
+                SetBehavior::SetStatementTimeout(timeout),
+            )
+        };
+
+        sets_timeout(
+            "SET statement_timeout = 250",
+            Some(Duration::from_millis(250)),
+        );
+        sets_timeout("SET statement_timeout = '3s'", Some(Duration::from_secs(3)));
+        sets_timeout(
+            "SET statement_timeout TO '2 min'",
+            Some(Duration::from_secs(120)),
+        );
+        sets_timeout("SET statement_timeout = 0", None);
+        sets_timeout("SET statement_timeout TO DEFAULT", None);
+
+        assert_eq!(
+            PostgreSqlQueryHandler::handle_set_statement(&parse_set_statement(
+                "SET statement_timeout = '3 fortnights'"
+            )),
+            SetBehavior::Unsupported,
+        );
+    }
+
     mod search_path {
         use super::*;
 
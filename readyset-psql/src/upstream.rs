@@ -7,12 +7,16 @@ use async_trait::async_trait;
 use futures::TryStreamExt;
 use nom_sql::SqlIdentifier;
 use pgsql::config::Host;
-use pgsql::types::Type;
-use pgsql::{GenericResult, Row, SimpleQueryMessage};
+use pgsql::types::{ToSql, Type};
+use pgsql::{CancelToken, GenericResult, Row, SimpleQueryMessage};
+use postgres_native_tls::MakeTlsConnector;
 use psql_srv::Column;
+use readyset::replication::ReplicationOffset;
 use readyset::ColumnSchema;
 use readyset_adapter::fallback_cache::FallbackCache;
-use readyset_adapter::upstream_database::{NoriaCompare, UpstreamDestination};
+use readyset_adapter::upstream_database::{
+    NoriaCompare, UpstreamCancelHandle, UpstreamDestination,
+};
 use readyset_adapter::{UpstreamConfig, UpstreamDatabase, UpstreamPrepare};
 use readyset_data::DfValue;
 use readyset_errors::{unsupported, ReadySetError};
@@ -28,6 +32,9 @@ use crate::Error;
 pub struct PostgreSqlUpstream {
     /// This is the underlying (regular) PostgreSQL client
     client: pgsql::Client,
+    /// The TLS connector used to connect to the upstream database, which is also needed to send
+    /// it cancel requests
+    tls: MakeTlsConnector,
     /// A tokio task that handles the connection, required by `tokio_postgres` to operate
     _connection_handle: tokio::task::JoinHandle<Result<(), pgsql::Error>>,
     /// Map from prepared statement IDs to prepared statements
@@ -43,6 +50,20 @@ pub struct PostgreSqlUpstream {
     version: String,
 }
 
+/// Cancels the query running on a connection to an upstream PostgreSQL database, by sending a
+/// cancel request for that connection
+struct PostgreSqlCancelHandle {
+    token: CancelToken,
+    tls: MakeTlsConnector,
+}
+
+#[async_trait]
+impl UpstreamCancelHandle for PostgreSqlCancelHandle {
+    async fn cancel(&self) -> Result<(), anyhow::Error> {
+        Ok(self.token.cancel_query(self.tls.clone()).await?)
+    }
+}
+
 #[derive(Debug)]
 pub enum QueryResult {
     Read { data: Vec<Row> },
@@ -135,14 +156,17 @@ impl UpstreamDatabase for PostgreSqlUpstream {
             }
             builder.build().unwrap() // Never returns an error
         };
-        let tls = postgres_native_tls::MakeTlsConnector::new(connector);
+        let tls = MakeTlsConnector::new(connector);
         let span = info_span!(
             "Connecting to PostgreSQL upstream",
             host = ?pg_config.get_hosts(),
             port = ?pg_config.get_ports()
         );
         span.in_scope(|| info!("Establishing connection"));
-        let (client, connection) = pg_config.connect(tls).instrument(span.clone()).await?;
+        let (client, connection) = pg_config
+            .connect(tls.clone())
+            .instrument(span.clone())
+            .await?;
         let version = connection.parameter("server_version").ok_or_else(|| {
             ReadySetError::Internal("Upstream database failed to send server version".to_string())
         })?;
@@ -152,6 +176,7 @@ impl UpstreamDatabase for PostgreSqlUpstream {
 
         Ok(Self {
             client,
+            tls,
             _connection_handle,
             prepared_statements: Default::default(),
             statement_id_counter: 0,
@@ -279,6 +304,13 @@ impl UpstreamDatabase for PostgreSqlUpstream {
         Ok(QueryResult::Command)
     }
 
+    fn cancel_handle(&self) -> Box<dyn UpstreamCancelHandle> {
+        Box::new(PostgreSqlCancelHandle {
+            token: self.client.cancel_token(),
+            tls: self.tls.clone(),
+        })
+    }
+
     async fn schema_dump(&mut self) -> Result<Vec<u8>, anyhow::Error> {
         let config = pgsql::Config::from_str(self.url())?;
         let mut pg_dump = Command::new("pg_dump");
@@ -328,6 +360,54 @@ impl UpstreamDatabase for PostgreSqlUpstream {
             })
             .collect())
     }
+
+    async fn query_rows<S>(&mut self, query: S) -> Result<Vec<Vec<DfValue>>, Self::Error>
+    where
+        S: AsRef<str> + Send + Sync,
+    {
+        let rows = self.client.query(query.as_ref(), &[]).await?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (0..row.len())
+                    .map(|i| row.try_get::<_, DfValue>(i))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    async fn execute_rows(
+        &mut self,
+        statement_id: u32,
+        params: &[DfValue],
+    ) -> Result<Vec<Vec<DfValue>>, Self::Error> {
+        let statement = self
+            .prepared_statements
+            .get(&statement_id)
+            .ok_or(ReadySetError::PreparedStatementMissing { statement_id })?;
+        let params = params
+            .iter()
+            .map(|p| p as &(dyn ToSql + Sync))
+            .collect::<Vec<_>>();
+        let rows = self.client.query(statement, &params).await?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (0..row.len())
+                    .map(|i| row.try_get::<_, DfValue>(i))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    async fn replication_offset(&mut self) -> Result<Option<ReplicationOffset>, Self::Error> {
+        let lsn = self
+            .client
+            .query_one("SELECT pg_current_wal_lsn()::text", &[])
+            .await?
+            .get::<_, String>(0);
+        Ok(Some(ReplicationOffset::from_str(&format!("wal[{lsn}]"))?))
+    }
 }
 
 #[cfg(test)]
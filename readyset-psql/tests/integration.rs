@@ -1037,6 +1037,40 @@ async fn write_timestamps() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn set_timestamp_format_is_unsupported() {
+    let (opts, _handle) = setup().await;
+    let conn = connect(opts).await;
+    conn.simple_query("CREATE TABLE posts (id int primary key, created_at TIMESTAMP)")
+        .await
+        .unwrap();
+    conn.simple_query("INSERT INTO posts (id, created_at) VALUES (1, '2020-01-23 17:08:24')")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let stmt = conn
+        .prepare("SELECT created_at FROM posts WHERE id = $1")
+        .await
+        .unwrap();
+
+    let err = conn
+        .simple_query("SET readyset_timestamp_format = 'epoch_ms'")
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("readyset_timestamp_format"),
+        "unexpected error: {err}"
+    );
+
+    // Statements prepared before the attempted change still decode as timestamps
+    let row = conn.query_one(&stmt, &[&1]).await.unwrap();
+    assert_eq!(
+        row.get::<usize, NaiveDateTime>(0),
+        NaiveDate::from_ymd(2020, 1, 23).and_hms(17, 08, 24)
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn delete_case_insensitive() {
     let (opts, _handle) = setup().await;
@@ -1309,3 +1343,103 @@ async fn same_query_different_search_path() {
         2
     );
 }
+
+#[cfg(feature = "failure_injection")]
+mod partial_results {
+    use std::task::Poll;
+
+    use futures::ready;
+    use readyset::failpoints;
+    use serial_test::serial;
+    use tokio::sync::mpsc;
+    use tokio_postgres::{AsyncMessage, Client, NoTls};
+
+    use super::*;
+
+    const QUERY: &str = "SELECT id FROM t WHERE id IN (1, 2, 3, 4, 5, 6, 7, 8, 9, 10)";
+
+    /// Starts a server with two shards, and connects to it with a client that forwards the
+    /// messages of any notices it receives to the returned channel
+    async fn setup(
+        allow_partial_results: bool,
+    ) -> (Client, mpsc::UnboundedReceiver<String>, Handle) {
+        let (config, handle) = TestBuilder::default()
+            .sharding(Some(2))
+            .allow_partial_results(allow_partial_results)
+            .build::<PostgreSQLAdapter>()
+            .await;
+        let (client, mut connection) = config.connect(NoTls).await.unwrap();
+        let (notices_tx, notices_rx) = mpsc::unbounded_channel();
+        tokio::spawn(futures::future::poll_fn(move |cx| {
+            while let Some(message) = ready!(connection.poll_message(cx)) {
+                if let Ok(AsyncMessage::Notice(notice)) = message {
+                    let _ = notices_tx.send(notice.message().to_owned());
+                }
+            }
+            Poll::Ready(())
+        }));
+
+        client
+            .simple_query("CREATE TABLE t (id int PRIMARY KEY)")
+            .await
+            .unwrap();
+        client
+            .simple_query(
+                "INSERT INTO t (id) VALUES (1), (2), (3), (4), (5), (6), (7), (8), (9), (10)",
+            )
+            .await
+            .unwrap();
+        sleep().await;
+
+        // Read from every shard while they're all available, to migrate and fill the cache
+        assert_eq!(client.query(QUERY, &[]).await.unwrap().len(), 10);
+        (client, notices_rx, handle)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[serial]
+    async fn unavailable_shard_returns_partial_results_with_warning() {
+        let (client, mut notices, mut handle) = setup(true).await;
+        assert!(notices.try_recv().is_err());
+
+        handle
+            .set_failpoint(failpoints::UNAVAILABLE_READER_SHARDS, "return")
+            .await;
+
+        let rows = client.query(QUERY, &[]).await.unwrap();
+        assert!(!rows.is_empty() && rows.len() < 10);
+        assert_eq!(
+            notices.try_recv().unwrap(),
+            "Results are incomplete: 1 shard(s) of the cache were unavailable"
+        );
+
+        let messages = client.simple_query(QUERY).await.unwrap();
+        let num_rows = messages
+            .iter()
+            .filter(|m| matches!(m, SimpleQueryMessage::Row(_)))
+            .count();
+        assert_eq!(num_rows, rows.len());
+        assert!(notices.try_recv().is_ok());
+
+        handle
+            .set_failpoint(failpoints::UNAVAILABLE_READER_SHARDS, "off")
+            .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[serial]
+    async fn unavailable_shard_fails_read_by_default() {
+        let (client, mut notices, mut handle) = setup(false).await;
+
+        handle
+            .set_failpoint(failpoints::UNAVAILABLE_READER_SHARDS, "return")
+            .await;
+
+        client.query(QUERY, &[]).await.unwrap_err();
+        assert!(notices.try_recv().is_err());
+
+        handle
+            .set_failpoint(failpoints::UNAVAILABLE_READER_SHARDS, "off")
+            .await;
+    }
+}
@@ -13,7 +13,7 @@ use readyset_telemetry_reporter::TelemetrySender;
 
 use crate::controller::replication::ReplicationStrategy;
 use crate::handle::Handle;
-use crate::{Config, FrontierStrategy, ReuseConfigType, VolumeId};
+use crate::{Config, FrontierStrategy, IdentifierCase, ReuseConfigType, VolumeId};
 
 /// Used to construct a worker.
 #[derive(Clone)]
@@ -78,6 +78,11 @@ impl Builder {
         builder.set_allow_topk(opts.enable_experimental_topk_support);
         builder.set_allow_paginate(opts.enable_experimental_paginate_support);
         builder.set_allow_mixed_comparisons(opts.enable_experimental_mixed_comparisons);
+        builder.set_max_view_key_columns(opts.max_view_key_columns);
+        if opts.case_insensitive_identifiers {
+            builder.set_identifier_case(IdentifierCase::Insensitive);
+        }
+        builder.set_materialized_memory_budget(opts.materialized_memory_budget);
 
         builder.set_replication_strategy(opts.domain_replication_options.into());
 
@@ -188,6 +193,22 @@ impl Builder {
         self.config.mir_config.allow_mixed_comparisons = allow_mixed_comparisons;
     }
 
+    /// Set the value of [`controller::sql::Config::max_view_key_columns`]
+    pub fn set_max_view_key_columns(&mut self, max_view_key_columns: Option<usize>) {
+        self.config.mir_config.max_view_key_columns = max_view_key_columns;
+    }
+
+    /// Set the value of [`controller::sql::Config::identifier_case`]
+    pub fn set_identifier_case(&mut self, identifier_case: IdentifierCase) {
+        self.config.mir_config.identifier_case = identifier_case;
+    }
+
+    /// Set the maximum total size, in bytes, of materialized state for caches, beyond which new
+    /// caches will be rejected. `None` means there is no limit.
+    pub fn set_materialized_memory_budget(&mut self, budget: Option<usize>) {
+        self.config.materialized_memory_budget = budget;
+    }
+
     /// Set the value of [`DomainConfig::aggressively_update_state_sizes`][0]. See the documentation
     /// of that field for more information
     ///
@@ -64,6 +64,34 @@ impl DomainHandle {
         self.shards.cells().iter().any(|s| s == worker)
     }
 
+    /// Stop every replica of every shard of this domain on the workers they're assigned to.
+    ///
+    /// Replicas assigned to workers that have failed are skipped, since they're no longer running,
+    /// as are replicas that were never actually started on their worker.
+    pub(super) async fn kill(
+        &self,
+        workers: &HashMap<WorkerIdentifier, Worker>,
+    ) -> ReadySetResult<()> {
+        for (shard, replicas) in self.shards.rows().enumerate() {
+            for (replica, addr) in replicas.iter().enumerate() {
+                if let Some(worker) = workers.get(addr) {
+                    let replica_address = ReplicaAddress {
+                        domain_index: self.idx,
+                        shard,
+                        replica,
+                    };
+                    worker
+                        .rpc::<()>(WorkerRequestKind::KillDomain(replica_address))
+                        .await
+                        .map_err(|e| {
+                            rpc_err_no_downcast(format!("killing domain {}", replica_address), e)
+                        })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub(super) async fn send_to_healthy_shard_replica<R>(
         &self,
         shard: usize,
@@ -7,23 +7,28 @@
     clippy::unreachable
 )]
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use database_utils::UpstreamConfig;
 use failpoint_macros::failpoint;
 use hyper::Method;
 use launchpad::futures::abort_on_panic;
+use nom_sql::Relation;
+use parking_lot::Mutex;
 use readyset::consensus::Authority;
 use readyset::internal::ReplicaAddress;
+use readyset::recipe::changelist::{Change, ChangeList};
 use readyset::recipe::ExtendRecipeSpec;
 use readyset::replication::ReplicationOffset;
 use readyset::status::{ReadySetStatus, SnapshotStatus};
-use readyset::WorkerDescriptor;
-use readyset_errors::{ReadySetError, ReadySetResult};
+use readyset::{LeaderInfo, NodeRestriction, ViewMetadata, ViewRequest, WorkerDescriptor};
+use readyset_errors::{unsupported_err, ReadySetError, ReadySetResult};
 use readyset_telemetry_reporter::TelemetrySender;
 use readyset_version::RELEASE_VERSION;
+use replicators::ResnapshotRequests;
 use reqwest::Url;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::Notify;
@@ -50,13 +55,28 @@ pub struct Leader {
 
     quorum: usize,
     controller_uri: Url,
+    /// The leadership term in which this controller became the leader
+    term: u64,
 
     /// The amount of time to wait for a worker request to complete.
     worker_request_timeout: Duration,
+    /// If set, the maximum total size in bytes of materialized state before new caches are
+    /// rejected
+    materialized_memory_budget: Option<usize>,
     /// Configuration for the replicator
     pub(super) replicator_config: UpstreamConfig,
     /// A handle to the replicator task
     pub(super) replicator_task: Option<tokio::task::JoinHandle<()>>,
+    /// Tables that have been requested to be re-snapshot by the replicator
+    resnapshot_requests: ResnapshotRequests,
+    /// The last time each view was looked up by a client, since this controller became the leader.
+    ///
+    /// This is kept separately from the rest of the [view metadata][view_metadata] so that looking
+    /// up a view doesn't require writing to the authority. Reads from views are tracked by the
+    /// workers hosting their readers, and combined with these lookups when metadata is requested.
+    ///
+    /// [view_metadata]: DfState::view_metadata
+    view_accesses: Mutex<HashMap<Relation, DateTime<Utc>>>,
     /// A client to the current authority.
     pub(super) authority: Arc<Authority>,
 }
@@ -109,6 +129,7 @@ impl Leader {
         let authority = Arc::clone(&self.authority);
         let replicator_restart_timeout = self.replicator_config.replicator_restart_timeout;
         let config = self.replicator_config.clone();
+        let resnapshot_requests = self.resnapshot_requests.clone();
 
         // The replication task ideally won't panic, but if it does and we arent replicating, that
         // will mean the data we return, will be more and more stale, and the transaction logs on
@@ -124,11 +145,15 @@ impl Leader {
                     config.clone(),
                     Some(ready_notification.clone()),
                     telemetry_sender.clone(),
+                    resnapshot_requests.clone(),
                 )
                 .await
                 {
                     // Unrecoverable errors, propagate the error the controller and kill the loop.
-                    Err(err @ ReadySetError::RecipeInvariantViolated(_)) => {
+                    Err(
+                        err @ (ReadySetError::RecipeInvariantViolated(_)
+                        | ReadySetError::UpstreamSchemaMismatch { .. }),
+                    ) => {
                         if let Err(e) = replication_error.send(err) {
                             error!(error = %e, "Could not notify controller of critical error. The system may be in an invalid state");
                         }
@@ -223,6 +248,12 @@ impl Leader {
                 (&Method::GET | &Method::POST, "/controller_uri") => {
                     return_serialized!(self.controller_uri);
                 }
+                (&Method::GET | &Method::POST, "/leader_info") => {
+                    return_serialized!(LeaderInfo {
+                        controller_uri: self.controller_uri.clone(),
+                        term: self.term,
+                    });
+                }
                 (&Method::GET, "/workers") | (&Method::POST, "/workers") => {
                     let ds = futures::executor::block_on(self.dataflow_state_handle.read());
                     return_serialized!(&ds.workers.keys().collect::<Vec<_>>())
@@ -290,6 +321,12 @@ impl Leader {
                     check_quorum!(ds);
                     return_serialized!(ds.view_statuses(queries, dialect))
                 }
+                (&Method::POST, "/explain_query") => {
+                    let (query, dialect) = bincode::deserialize(&body)?;
+                    let ds = futures::executor::block_on(self.dataflow_state_handle.read());
+                    check_quorum!(ds);
+                    return_serialized!(ds.explain_query(query, dialect)?)
+                }
                 (&Method::GET | &Method::POST, "/instances") => {
                     let ds = futures::executor::block_on(self.dataflow_state_handle.read());
                     check_quorum!(ds);
@@ -310,6 +347,11 @@ impl Leader {
                         .map(|w| w.0)
                         .collect::<Vec<_>>());
                 }
+                (&Method::GET | &Method::POST, "/orphaned_domains") => {
+                    let ds = futures::executor::block_on(self.dataflow_state_handle.read());
+                    check_quorum!(ds);
+                    return_serialized!(ds.orphaned_domains())
+                }
                 (&Method::GET, "/nodes") => {
                     let ds = futures::executor::block_on(self.dataflow_state_handle.read());
                     check_quorum!(ds);
@@ -371,10 +413,44 @@ impl Leader {
                 (&Method::POST, "/view_builder") => {
                     // NOTE(eta): same as above applies
                     require_leader_ready()?;
-                    let body = bincode::deserialize(&body)?;
+                    let view_req: ViewRequest = bincode::deserialize(&body)?;
                     let ds = futures::executor::block_on(self.dataflow_state_handle.read());
                     check_quorum!(ds);
-                    let ret = ds.view_builder(body);
+                    let name = ds
+                        .recipe
+                        .resolve_alias(&view_req.name)
+                        .unwrap_or(&view_req.name)
+                        .clone();
+                    let ret = ds.view_builder(view_req);
+                    if let Ok(Some(_)) = ret {
+                        self.view_accesses.lock().insert(name, Utc::now());
+                    }
+                    return_serialized!(ret);
+                }
+                (&Method::POST, "/view_metadata") => {
+                    let ds = futures::executor::block_on(self.dataflow_state_handle.read());
+                    check_quorum!(ds);
+                    let reads = futures::executor::block_on(ds.view_reads())?;
+                    let view_accesses = self.view_accesses.lock();
+                    let ret = ds
+                        .view_metadata
+                        .iter()
+                        .map(|(name, metadata)| {
+                            // A view was last accessed either when it was last read from, or when
+                            // it was last looked up by a client, whichever is later
+                            let last_accessed_at = reads
+                                .get(name)
+                                .into_iter()
+                                .chain(view_accesses.get(name))
+                                .max()
+                                .copied();
+                            let metadata = ViewMetadata {
+                                last_accessed_at,
+                                ..metadata.clone()
+                            };
+                            (name.clone(), metadata)
+                        })
+                        .collect::<BTreeMap<_, _>>();
                     return_serialized!(ret);
                 }
                 (&Method::POST, "/get_info") => {
@@ -402,6 +478,95 @@ impl Leader {
                     })?;
                     return_serialized!(res);
                 }
+                (&Method::POST, "/materialization_progress") => {
+                    let view: Relation = bincode::deserialize(&body)?;
+                    // this method can't be `async` since `Leader` isn't Send because `Graph`
+                    // isn't Send :(
+                    let res = futures::executor::block_on(async move {
+                        let ds = self.dataflow_state_handle.read().await;
+                        check_quorum!(ds);
+                        ds.materialization_progress(&view).await
+                    })?;
+                    return_serialized!(res);
+                }
+                (&Method::GET, "/key_distribution") => {
+                    let view = query
+                        .as_deref()
+                        .and_then(|query| {
+                            querystring::querify(query)
+                                .into_iter()
+                                .find(|(k, _)| *k == "view")
+                                .map(|(_, view)| match view.split_once('.') {
+                                    Some((schema, name)) => Relation {
+                                        schema: Some(schema.into()),
+                                        name: name.into(),
+                                    },
+                                    None => Relation::from(view),
+                                })
+                        })
+                        .ok_or_else(|| {
+                            ReadySetError::BadRequest("Missing `view` parameter".into())
+                        })?;
+                    let res = futures::executor::block_on(async move {
+                        let ds = self.dataflow_state_handle.read().await;
+                        check_quorum!(ds);
+                        ds.key_distribution(&view).await
+                    })?;
+                    return_serialized!(res);
+                }
+                (&Method::POST, "/key_distribution") => {
+                    let view: Relation = bincode::deserialize(&body)?;
+                    let res = futures::executor::block_on(async move {
+                        let ds = self.dataflow_state_handle.read().await;
+                        check_quorum!(ds);
+                        ds.key_distribution(&view).await
+                    })?;
+                    return_serialized!(res);
+                }
+                (&Method::GET, "/queries_for_table") => {
+                    let table = query
+                        .as_deref()
+                        .and_then(|query| {
+                            querystring::querify(query)
+                                .into_iter()
+                                .find(|(k, _)| *k == "table")
+                                .map(|(_, table)| match table.split_once('.') {
+                                    Some((schema, name)) => Relation {
+                                        schema: Some(schema.into()),
+                                        name: name.into(),
+                                    },
+                                    None => Relation::from(table),
+                                })
+                        })
+                        .ok_or_else(|| {
+                            ReadySetError::BadRequest("Missing `table` parameter".into())
+                        })?;
+                    let ds = futures::executor::block_on(self.dataflow_state_handle.read());
+                    check_quorum!(ds);
+                    return_serialized!(ds.queries_for_table(&table)?);
+                }
+                (&Method::POST, "/queries_for_table") => {
+                    let table: Relation = bincode::deserialize(&body)?;
+                    let ds = futures::executor::block_on(self.dataflow_state_handle.read());
+                    check_quorum!(ds);
+                    return_serialized!(ds.queries_for_table(&table)?);
+                }
+                (&Method::POST, "/schema_version") => {
+                    let ds = futures::executor::block_on(self.dataflow_state_handle.read());
+                    check_quorum!(ds);
+                    return_serialized!(ds.recipe.schema_version());
+                }
+                (&Method::POST, "/feature_flags") => {
+                    let ds = futures::executor::block_on(self.dataflow_state_handle.read());
+                    check_quorum!(ds);
+                    return_serialized!(ds.feature_flags);
+                }
+                (&Method::GET, "/node_restrictions")
+                | (&Method::POST, "/get_node_restrictions") => {
+                    let ds = futures::executor::block_on(self.dataflow_state_handle.read());
+                    check_quorum!(ds);
+                    return_serialized!(ds.node_restrictions());
+                }
                 (&Method::POST, "/node_sizes") => {
                     let res = futures::executor::block_on(async move {
                         let ds = self.dataflow_state_handle.read().await;
@@ -413,6 +578,14 @@ impl Leader {
                     return_serialized!(leader_ready);
                 }
                 (&Method::POST, "/status") => {
+                    // Reporting the replication offset is best-effort, since we still want to be
+                    // able to report the snapshot status if the workers can't be reached
+                    let replication_offset = futures::executor::block_on(async move {
+                        let ds = self.dataflow_state_handle.read().await;
+                        ds.replication_offsets().await
+                    })
+                    .ok()
+                    .and_then(|offsets| offsets.max_offset().ok().flatten().cloned());
                     let status = ReadySetStatus {
                         // Use whether the leader is ready or not as a proxy for if we have
                         // completed snapshotting.
@@ -421,6 +594,7 @@ impl Leader {
                         } else {
                             SnapshotStatus::InProgress
                         },
+                        replication_offset,
                     };
                     return_serialized!(status);
                 }
@@ -470,12 +644,38 @@ impl Leader {
                 let ret = futures::executor::block_on(async move {
                     let mut writer = self.dataflow_state_handle.write().await;
                     check_quorum!(writer.as_ref());
+                    check_materialized_memory_budget(
+                        writer.as_ref(),
+                        self.materialized_memory_budget,
+                        body.changes.changes.iter(),
+                    )
+                    .await?;
                     let r = writer.as_mut().extend_recipe(body, false).await?;
                     self.dataflow_state_handle.commit(writer, authority).await?;
                     Ok(r)
                 })?;
                 return_serialized!(ret);
             }
+            (Method::POST, "/extend_recipe_batch") => {
+                require_leader_ready()?;
+                let changelists: Vec<ChangeList> = bincode::deserialize(&body)?;
+                let ret = futures::executor::block_on(async move {
+                    let mut writer = self.dataflow_state_handle.write().await;
+                    check_quorum!(writer.as_ref());
+                    check_materialized_memory_budget(
+                        writer.as_ref(),
+                        self.materialized_memory_budget,
+                        changelists
+                            .iter()
+                            .flat_map(|changelist| &changelist.changes),
+                    )
+                    .await?;
+                    let r = writer.as_mut().extend_recipe_batch(changelists).await?;
+                    self.dataflow_state_handle.commit(writer, authority).await?;
+                    Ok(r)
+                })?;
+                return_serialized!(ret);
+            }
             (Method::POST, "/remove_query") => {
                 require_leader_ready()?;
                 let query_name = bincode::deserialize(&body)?;
@@ -509,6 +709,89 @@ impl Leader {
                 })?;
                 return_serialized!(ret);
             }
+            (Method::POST, "/resnapshot_table") => {
+                require_leader_ready()?;
+                let table: Relation = bincode::deserialize(&body)?;
+                if self.replicator_config.upstream_db_url.is_none() {
+                    return Err(unsupported_err!(
+                        "Cannot resnapshot table {table} without an upstream database"
+                    ));
+                }
+                let table = futures::executor::block_on(async move {
+                    let ds = self.dataflow_state_handle.read().await;
+                    check_quorum!(ds);
+                    // Resolve the table by its canonical name, so that it matches the tables the
+                    // replicator knows about
+                    ds.table_builder(&table)?
+                        .map(|builder| builder.table_name)
+                        .ok_or_else(|| ReadySetError::TableNotFound {
+                            name: table.name.clone().into(),
+                            schema: table.schema.clone().map(Into::into),
+                        })
+                })?;
+                info!(%table, "Requesting resnapshot of table");
+                self.resnapshot_requests.request(table);
+                return_serialized!(());
+            }
+            (Method::POST, "/evict_worker") => {
+                require_leader_ready()?;
+                let worker = bincode::deserialize(&body)?;
+                let ret = futures::executor::block_on(async move {
+                    {
+                        let reader = self.dataflow_state_handle.read().await;
+                        check_quorum!(reader);
+                    }
+                    self.evict_worker(worker, authority).await
+                })?;
+                return_serialized!(ret);
+            }
+            (Method::POST, "/replicate_readers") => {
+                require_leader_ready()?;
+                let queries: Vec<Relation> = bincode::deserialize(&body)?;
+                let ret = futures::executor::block_on(async move {
+                    let mut writer = self.dataflow_state_handle.write().await;
+                    check_quorum!(writer.as_ref());
+                    writer.as_mut().replicate_readers(&queries).await?;
+                    self.dataflow_state_handle.commit(writer, authority).await?;
+                    Ok(())
+                })?;
+                return_serialized!(ret);
+            }
+            (Method::POST, "/set_reader_replicas") => {
+                require_leader_ready()?;
+                let (query, replicas): (Relation, usize) = bincode::deserialize(&body)?;
+                let ret = futures::executor::block_on(async move {
+                    let mut writer = self.dataflow_state_handle.write().await;
+                    check_quorum!(writer.as_ref());
+                    writer.as_mut().set_reader_replicas(query, replicas).await?;
+                    self.dataflow_state_handle.commit(writer, authority).await?;
+                    Ok(())
+                })?;
+                return_serialized!(ret);
+            }
+            (Method::POST, "/set_feature_flag") => {
+                require_leader_ready()?;
+                let (name, enabled): (String, bool) = bincode::deserialize(&body)?;
+                let ret = futures::executor::block_on(async move {
+                    let mut writer = self.dataflow_state_handle.write().await;
+                    check_quorum!(writer.as_ref());
+                    writer.as_mut().set_feature_flag(name, enabled);
+                    self.dataflow_state_handle.commit(writer, authority).await?;
+                    Ok(())
+                })?;
+                return_serialized!(ret);
+            }
+            (Method::POST, "/node_restrictions") => {
+                let restrictions: Vec<NodeRestriction> = bincode::deserialize(&body)?;
+                let ret = futures::executor::block_on(async move {
+                    let mut writer = self.dataflow_state_handle.write().await;
+                    check_quorum!(writer.as_ref());
+                    writer.as_mut().set_node_restrictions(restrictions);
+                    self.dataflow_state_handle.commit(writer, authority).await?;
+                    Ok(())
+                })?;
+                return_serialized!(ret);
+            }
             (Method::POST, "/remove_node") => {
                 require_leader_ready()?;
                 let body = bincode::deserialize(&body)?;
@@ -614,6 +897,12 @@ impl Leader {
             info!("Finished restoring graph configuration");
         }
 
+        // New workers may allow us to run more replicas of readers that were previously capped by
+        // the number of workers in the cluster
+        if ds.workers.len() >= self.quorum && !self.pending_recovery {
+            ds.reconcile_reader_replicas().await?;
+        }
+
         self.dataflow_state_handle
             .commit(writer, &self.authority)
             .await
@@ -624,31 +913,58 @@ impl Leader {
         failed: Vec<WorkerIdentifier>,
     ) -> ReadySetResult<()> {
         let mut writer = self.dataflow_state_handle.write().await;
-        let ds = writer.as_mut();
-
-        // first, translate from the affected workers to affected data-flow nodes
-        let mut affected_nodes = HashMap::new();
-        for wi in failed {
+        for wi in &failed {
             warn!(worker = %wi, "handling failure of worker");
-            let mut domain_nodes_on_worker = ds.nodes_on_worker(Some(&wi));
-            for (domain_index, node_indices) in domain_nodes_on_worker.drain() {
-                ds.domains.remove(&domain_index);
-                ds.materializations.remove_nodes(&node_indices);
-                affected_nodes
-                    .entry(domain_index)
-                    .or_insert_with(|| HashSet::new())
-                    .extend(node_indices);
-            }
-            ds.workers.remove(&wi);
         }
-
-        ds.recover(&affected_nodes).await?;
+        writer.as_mut().remove_workers(failed).await?;
 
         self.dataflow_state_handle
             .commit(writer, &self.authority)
             .await
     }
 
+    /// Manually evict the given worker from the cluster, reassigning all of its domains to the
+    /// remaining workers as if it had failed.
+    ///
+    /// Refuses to evict the worker if it's the only worker in the cluster, or if it holds the only
+    /// copy of any base table state, since that data would be lost.
+    pub(super) async fn evict_worker(
+        &self,
+        worker: WorkerIdentifier,
+        authority: &Arc<Authority>,
+    ) -> ReadySetResult<()> {
+        let mut writer = self.dataflow_state_handle.write().await;
+        let ds = writer.as_mut();
+
+        let cannot_evict = |reason: &str| ReadySetError::CannotEvictWorker {
+            uri: worker.clone(),
+            reason: reason.to_owned(),
+        };
+        if !ds.workers.contains_key(&worker) {
+            return Err(cannot_evict("worker is not registered with the controller"));
+        }
+        if ds.workers.len() <= 1 {
+            return Err(cannot_evict("worker is the last worker in the cluster"));
+        }
+        if ds.worker_has_unique_base_state(&worker) {
+            return Err(cannot_evict(
+                "worker holds the only copy of base table state",
+            ));
+        }
+
+        warn!(%worker, "manually evicting worker");
+        // The worker is still running, so make sure it stops running the domains we're about to
+        // reassign
+        if let Some(w) = ds.workers.get(&worker) {
+            if let Err(error) = w.rpc::<()>(WorkerRequestKind::ClearDomains).await {
+                warn!(%worker, %error, "Could not clear domains on evicted worker");
+            }
+        }
+        ds.remove_workers(vec![worker]).await?;
+
+        self.dataflow_state_handle.commit(writer, authority).await
+    }
+
     /// Construct `Leader` with a specified listening interface
     pub(super) fn new(
         state: ControllerState,
@@ -673,17 +989,46 @@ impl Leader {
             pending_recovery,
 
             quorum: state.config.quorum,
+            materialized_memory_budget: state.config.materialized_memory_budget,
 
             controller_uri,
+            term: state.leader_term,
 
             replicator_config,
             replicator_task: None,
+            resnapshot_requests: ResnapshotRequests::new(),
+            view_accesses: Default::default(),
             authority,
             worker_request_timeout,
         }
     }
 }
 
+/// Returns [`ReadySetError::MaterializedMemoryBudgetExceeded`] if `changes` create any caches while
+/// the memory used by materialized state in `ds` is already over `budget`.
+async fn check_materialized_memory_budget<'a, I>(
+    ds: &DfState,
+    budget: Option<usize>,
+    mut changes: I,
+) -> ReadySetResult<()>
+where
+    I: Iterator<Item = &'a Change>,
+{
+    let budget = match budget {
+        Some(budget) => budget,
+        None => return Ok(()),
+    };
+
+    if changes.any(|change| matches!(change, Change::CreateCache(_))) {
+        let used = ds.materialized_memory_usage().await?;
+        if used > budget {
+            return Err(ReadySetError::MaterializedMemoryBudgetExceeded { used, budget });
+        }
+    }
+
+    Ok(())
+}
+
 /// Helper method to distinguish if the given [`ControllerRequest`] actually
 /// requires modifying the dataflow graph state.
 pub(super) fn request_type(req: &ControllerRequest) -> ControllerRequestType {
@@ -691,10 +1036,16 @@ pub(super) fn request_type(req: &ControllerRequest) -> ControllerRequestType {
         (&Method::GET, "/flush_partial")
         | (&Method::GET | &Method::POST, "/controller_uri")
         | (&Method::POST, "/extend_recipe")
+        | (&Method::POST, "/extend_recipe_batch")
         | (&Method::POST, "/remove_query")
         | (&Method::POST, "/remove_all_queries")
         | (&Method::POST, "/set_replication_offset")
         | (&Method::POST, "/replicate_readers")
+        | (&Method::POST, "/set_reader_replicas")
+        | (&Method::POST, "/set_feature_flag")
+        | (&Method::POST, "/node_restrictions")
+        | (&Method::POST, "/resnapshot_table")
+        | (&Method::POST, "/evict_worker")
         | (&Method::POST, "/remove_node") => ControllerRequestType::Write,
         (&Method::POST, "/dry_run") => ControllerRequestType::DryRun,
         _ => ControllerRequestType::Read,
@@ -611,6 +611,11 @@ impl Materializations {
                 for (mi, indices) in add {
                     replay_obligations.entry(mi).or_default().extend(indices);
                 }
+            } else if graph[ni]
+                .as_reader()
+                .map_or(false, |r| r.max_bytes().is_some())
+            {
+                unsupported!("max_mem can only be set on partially materialized caches");
             } else if !graph[ni].is_base() && !self.config.allow_full_materialization {
                 unsupported!("Creation of fully materialized query is forbidden");
             } else {
@@ -38,13 +38,18 @@ use array2::Array2;
 use dataflow::node::Column;
 use dataflow::prelude::*;
 use dataflow::{node, DomainRequest, ReaderProcessing};
+use failpoint_macros::set_failpoint;
 use metrics::{counter, histogram};
 use nom_sql::Relation;
+#[cfg(feature = "failure_injection")]
+use readyset::failpoints;
+use readyset::internal::MaterializationStatus;
 use readyset::metrics::recorded;
 use readyset::{KeyColumnIdx, ReadySetError, ViewPlaceholder};
 use readyset_data::{DfType, Dialect};
-use tracing::{debug, debug_span, error, info, info_span, instrument, trace};
+use tracing::{debug, debug_span, error, info, info_span, instrument, trace, warn};
 
+use crate::controller::domain_handle::DomainHandle;
 use crate::controller::migrate::materialization::InvalidEdge;
 use crate::controller::migrate::node_changes::{MigrationNodeChanges, NodeChanges};
 use crate::controller::migrate::scheduling::Scheduler;
@@ -176,9 +181,10 @@ pub struct MigrationPlan<'df> {
 impl<'df> MigrationPlan<'df> {
     /// Apply the migration plan to the provided `Leader`.
     ///
-    /// If the plan fails, the `Leader`'s state is left unchanged; however, no attempt
-    /// is made to roll back any destructive changes that may have occurred before the plan failed
-    /// to apply.
+    /// If the plan fails, the `Leader`'s state is left unchanged, and any domains placed or nodes
+    /// added to existing domains by the plan are removed again (see
+    /// [`DomainMigrationPlan::apply`]); however, no attempt is made to roll back any other
+    /// destructive changes that may have occurred before the plan failed to apply.
     #[instrument(level = "info", name = "apply", skip(self))]
     pub async fn apply(self) -> ReadySetResult<()> {
         let MigrationPlan {
@@ -279,19 +285,112 @@ impl DomainMigrationPlan {
 
     /// Apply all stored changes using the given controller object, placing new domains and sending
     /// messages added since the last time this method was called.
+    ///
+    /// If any step fails, every domain placed by this call is killed and removed from `mainline`,
+    /// and any nodes that were added to pre-existing domains are removed from them again, so that
+    /// a failed migration doesn't leave orphaned domains or nodes running on the workers.
     pub async fn apply(&mut self, mainline: &mut DfState) -> ReadySetResult<()> {
+        let mut placed = Vec::with_capacity(self.place.len());
+        let mut added_nodes = HashMap::new();
+        let res = self
+            .apply_inner(mainline, &mut placed, &mut added_nodes)
+            .await;
+
+        if let Err(error) = &res {
+            warn!(
+                %error,
+                placed_domains = placed.len(),
+                "Migration plan failed to apply, rolling back"
+            );
+            Self::roll_back(mainline, placed, added_nodes).await;
+        }
+
+        res
+    }
+
+    async fn apply_inner(
+        &mut self,
+        mainline: &mut DfState,
+        placed: &mut Vec<DomainHandle>,
+        added_nodes: &mut HashMap<DomainIndex, Vec<LocalNodeIndex>>,
+    ) -> ReadySetResult<()> {
         for place in self.place.drain(..) {
+            // Record the domain before placing it, so that we also roll back any replicas that were
+            // started before placing the rest of the domain failed
+            placed.push(DomainHandle::new(
+                place.idx,
+                place.shard_replica_workers.clone(),
+            ));
             let d = mainline
                 .place_domain(place.idx, place.shard_replica_workers, place.nodes)
                 .await?;
             mainline.domains.insert(place.idx, d);
         }
+
+        set_failpoint!(failpoints::MIGRATION_PLAN_APPLY, |_| Err(
+            ReadySetError::Internal("Injected failure applying migration plan".to_owned())
+        ));
+
         for req in std::mem::take(&mut self.stored) {
+            let added_node = match &req.req {
+                DomainRequest::AddNode { node, .. }
+                    if !placed.iter().any(|d| d.index() == req.domain) =>
+                {
+                    Some((req.domain, node.local_addr()))
+                }
+                _ => None,
+            };
+
             req.apply(mainline).await?;
+
+            if let Some((domain, node)) = added_node {
+                added_nodes
+                    .entry(domain)
+                    .or_insert_with(Vec::new)
+                    .push(node);
+            }
         }
         Ok(())
     }
 
+    /// Undo the changes made by a failed call to [`DomainMigrationPlan::apply`], by removing the
+    /// given nodes from the (pre-existing) domains they were added to, and killing the given newly
+    /// placed domains.
+    ///
+    /// This is best-effort: any errors are logged rather than returned, since the migration has
+    /// already failed.
+    async fn roll_back(
+        mainline: &mut DfState,
+        placed: Vec<DomainHandle>,
+        added_nodes: HashMap<DomainIndex, Vec<LocalNodeIndex>>,
+    ) {
+        for (domain, nodes) in added_nodes {
+            if let Some(dh) = mainline.domains.get(&domain) {
+                if let Err(error) = dh
+                    .send_to_healthy::<()>(DomainRequest::RemoveNodes { nodes }, &mainline.workers)
+                    .await
+                {
+                    error!(
+                        %error,
+                        domain = %domain.index(),
+                        "Failed to remove nodes while rolling back migration"
+                    );
+                }
+            }
+        }
+
+        for dh in placed {
+            mainline.domains.remove(&dh.index());
+            if let Err(error) = dh.kill(&mainline.workers).await {
+                error!(
+                    %error,
+                    domain = %dh.index().index(),
+                    "Failed to kill domain while rolling back migration"
+                );
+            }
+        }
+    }
+
     /// Enqueue a message to be sent to all replicas of a specific shard of a domain on plan
     /// application.
     ///
@@ -431,6 +530,27 @@ fn inform_col_changes(
     Ok(())
 }
 
+/// Add messages to the dmp to inform existing readers of their new size limits
+fn inform_reader_max_bytes(
+    dmp: &mut DomainMigrationPlan,
+    reader_max_bytes: &[(NodeIndex, usize)],
+    ingredients: &Graph,
+) -> ReadySetResult<()> {
+    for &(ni, max_bytes) in reader_max_bytes {
+        // Can't have a NodeIndex that does not exist in ingredients
+        #[allow(clippy::indexing_slicing)]
+        let n = &ingredients[ni];
+        dmp.add_message(
+            n.domain(),
+            DomainRequest::SetReaderMaxBytes {
+                node: n.local_addr(),
+                max_bytes,
+            },
+        )?;
+    }
+    Ok(())
+}
+
 /// A `Migration` encapsulates a number of changes to the dataflow graph.
 ///
 /// Only one `Migration` can be in effect at any point in time. No changes are made to the running
@@ -440,6 +560,8 @@ pub struct Migration<'df> {
     pub(in crate::controller) changes: MigrationNodeChanges,
     pub(super) columns: Vec<(NodeIndex, ColumnChange)>,
     pub(super) readers: HashMap<NodeIndex, NodeIndex>,
+    /// New size limits for readers that existed before this migration
+    pub(super) reader_max_bytes: Vec<(NodeIndex, usize)>,
     pub(super) worker: Option<WorkerIdentifier>,
     pub(super) dialect: Dialect,
 
@@ -453,6 +575,7 @@ impl<'df> Migration<'df> {
             changes: Default::default(),
             columns: Default::default(),
             readers: Default::default(),
+            reader_max_bytes: Default::default(),
             worker: None,
             dialect,
             start: Instant::now(),
@@ -721,6 +844,58 @@ impl<'df> Migration<'df> {
         r.set_mapping(placeholder_map);
     }
 
+    /// Limit the size of the state of the reader for `n` to `max_bytes`, past which the reader's
+    /// least recently used keys will be evicted.
+    ///
+    /// If the reader already existed before this migration, the domain it lives in will be told
+    /// about the new limit when the migration is committed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n` has no reader, or if its reader already exists and is fully
+    /// materialized, since keys can't be evicted from full state
+    pub(crate) fn set_reader_max_bytes(
+        &mut self,
+        n: NodeIndex,
+        max_bytes: usize,
+    ) -> ReadySetResult<()> {
+        let (ri, existing) = match self.readers.get(&n) {
+            Some(&ri) => (ri, false),
+            None => {
+                let ingredients = &self.dataflow_state.ingredients;
+                #[allow(clippy::indexing_slicing)] // just came from ingredients
+                let ri = ingredients
+                    .neighbors_directed(n, petgraph::EdgeDirection::Outgoing)
+                    .find(|&c| ingredients[c].is_reader())
+                    .ok_or_else(|| internal_err!("Node {} has no reader", n.index()))?;
+                (ri, true)
+            }
+        };
+
+        #[allow(clippy::indexing_slicing)] // ri came from self.ingredients
+        let reader_node = &mut self.dataflow_state.ingredients[ri];
+        if existing
+            && !matches!(
+                self.dataflow_state
+                    .materializations
+                    .get_status(ri, reader_node),
+                MaterializationStatus::Partial { .. }
+            )
+        {
+            unsupported!("max_mem can only be set on partially materialized caches");
+        }
+
+        #[allow(clippy::unwrap_used)] // we know it's a reader - we just checked above
+        reader_node
+            .as_mut_reader()
+            .unwrap()
+            .set_max_bytes(max_bytes);
+        if existing {
+            self.reader_max_bytes.push((ri, max_bytes));
+        }
+        Ok(())
+    }
+
     /// Build a `MigrationPlan` for this migration, and apply it if the planning stage succeeds.
     pub(super) async fn commit(self, dry_run: bool) -> ReadySetResult<()> {
         let start = self.start;
@@ -765,6 +940,7 @@ impl<'df> Migration<'df> {
         let mut added = 0;
         let mut dropped = 0;
         let columns = self.columns;
+        let reader_max_bytes = self.reader_max_bytes;
         let worker = self.worker;
         for change in self.changes.into_iter() {
             match change {
@@ -782,6 +958,7 @@ impl<'df> Migration<'df> {
         // We have successfully made a valid graph! Now we can inform the dmp of all the
         // changes
         inform_col_changes(&mut dmp, &columns, &dataflow_state.ingredients)?;
+        inform_reader_max_bytes(&mut dmp, &reader_max_bytes, &dataflow_state.ingredients)?;
 
         debug!(
             added_nodes = added,
@@ -36,7 +36,7 @@ use crate::controller::{DomainPlacementRestriction, NodeRestrictionKey, Worker,
 /// Verifies that the worker `worker` meets the domain placement restrictions of all dataflow nodes
 /// that will be placed in a new domain on the worker.  If the set of restrictions in this domain
 /// are too stringent, no worker may be able to satisfy the domain placement.
-fn worker_meets_restrictions(
+pub(in crate::controller) fn worker_meets_restrictions(
     worker: &Worker,
     restrictions: &[&DomainPlacementRestriction],
 ) -> bool {
@@ -45,6 +45,16 @@ fn worker_meets_restrictions(
         .all(|r| r.worker_volume == worker.domain_scheduling_config.volume_id)
 }
 
+/// Returns whether the scheduling configuration of `worker` allows it to run a domain that does (or
+/// doesn't) contain reader nodes.
+fn worker_can_run_domain(worker: &Worker, is_reader_domain: bool) -> bool {
+    match worker.domain_scheduling_config.reader_nodes {
+        NodeTypeSchedulingRestriction::None => true,
+        NodeTypeSchedulingRestriction::OnlyWithNodeType => is_reader_domain,
+        NodeTypeSchedulingRestriction::NeverWithNodeType => !is_reader_domain,
+    }
+}
+
 /// Statistics about the domains scheduled onto a worker
 #[derive(Default, Clone, Copy)]
 struct WorkerStats {
@@ -109,6 +119,40 @@ impl<'state> Scheduler<'state> {
         })
     }
 
+    /// Determine the number of times a domain with the given list of `nodes` should be replicated.
+    ///
+    /// If any of the readers in the domain belong to a query with a [desired number of reader
+    /// replicas][reader_replicas], that number is used (capped at the number of workers that could
+    /// run the domain, since no two replicas of the same shard can run on the same worker).
+    /// Otherwise, the cluster-wide [`ReplicationStrategy`] decides.
+    ///
+    /// [reader_replicas]: DfState::reader_replicas
+    /// [`ReplicationStrategy`]: crate::ReplicationStrategy
+    ///
+    /// # Invariants
+    ///
+    /// * All the nodes in `nodes` must exist in `self.dataflow_state.ingredients`
+    #[allow(clippy::indexing_slicing)] // documented invariant
+    pub(crate) fn num_replicas(&self, nodes: &[NodeIndex]) -> usize {
+        let is_base_table_domain = nodes
+            .iter()
+            .any(|n| self.dataflow_state.ingredients[*n].is_base());
+        match self.dataflow_state.desired_reader_replicas(nodes) {
+            Some(replicas) if !is_base_table_domain => {
+                let num_workers = self
+                    .valid_workers
+                    .iter()
+                    .filter(|(_, worker)| worker_can_run_domain(worker, true))
+                    .count();
+                replicas.min(num_workers).max(1)
+            }
+            _ => self
+                .dataflow_state
+                .replication_strategy
+                .replicate_domain(&self.dataflow_state.ingredients, nodes),
+        }
+    }
+
     /// Decide which workers the shards of the given `domain` (with the given list of `nodes`)
     /// should run on
     ///
@@ -130,10 +174,7 @@ impl<'state> Scheduler<'state> {
             .sharded_by()
             .shards()
             .unwrap_or(1);
-        let num_replicas = self
-            .dataflow_state
-            .replication_strategy
-            .replicate_domain(&self.dataflow_state.ingredients, nodes);
+        let num_replicas = self.num_replicas(nodes);
 
         let is_reader_domain = nodes
             .iter()
@@ -147,13 +188,10 @@ impl<'state> Scheduler<'state> {
             invariant_eq!(num_replicas, 1);
         }
 
-        let workers = self.valid_workers.iter().filter(|(_, worker)| {
-            match worker.domain_scheduling_config.reader_nodes {
-                NodeTypeSchedulingRestriction::None => true,
-                NodeTypeSchedulingRestriction::OnlyWithNodeType => is_reader_domain,
-                NodeTypeSchedulingRestriction::NeverWithNodeType => !is_reader_domain,
-            }
-        });
+        let workers = self
+            .valid_workers
+            .iter()
+            .filter(|(_, worker)| worker_can_run_domain(worker, is_reader_domain));
 
         let mut res = Vec::with_capacity(num_shards);
         for shard in 0..num_shards {
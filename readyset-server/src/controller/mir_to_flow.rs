@@ -15,7 +15,7 @@ use dataflow::ops::grouped::concat::GroupConcat;
 use dataflow::ops::join::{Join, JoinType};
 use dataflow::ops::latest::Latest;
 use dataflow::ops::project::Project;
-use dataflow::{node, ops, Expr as DfExpr, PostLookupAggregates, ReaderProcessing};
+use dataflow::{node, ops, ConstantColumn, Expr as DfExpr, PostLookupAggregates, ReaderProcessing};
 use itertools::Itertools;
 use mir::graph::MirGraph;
 use mir::node::node_inner::MirNodeInner;
@@ -225,6 +225,7 @@ pub(super) fn mir_node_to_flow_parts(
                     ref returned_cols,
                     ref default_row,
                     ref aggregates,
+                    ref constant_cols,
                     ..
                 } => {
                     invariant_eq!(ancestors.len(), 1);
@@ -237,6 +238,7 @@ pub(super) fn mir_node_to_flow_parts(
                         returned_cols,
                         default_row.clone(),
                         aggregates,
+                        constant_cols,
                     )?;
                     materialize_leaf_node(
                         graph,
@@ -589,6 +591,7 @@ fn make_grouped_node(
                 over_col_indx,
                 group_col_indx.as_slice(),
                 over_col_ty,
+                mig.dialect,
             )?;
             let agg_col = make_agg_col(grouped.output_col_type().or_ref(over_col_ty).clone());
             cols.push(agg_col);
@@ -1049,7 +1052,7 @@ fn make_distinct_node(
         // remaining occurances of the set.
         //
         // We use 0 as a placeholder value
-        Aggregation::Count.over(parent_na, 0, &group_by_indx, &DfType::Unknown)?,
+        Aggregation::Count.over(parent_na, 0, &group_by_indx, &DfType::Unknown, mig.dialect)?,
     );
     Ok(FlowNode::New(na))
 }
@@ -1135,6 +1138,7 @@ fn make_reader_processing(
     returned_cols: &Option<Vec<Column>>,
     default_row: Option<Vec<DfValue>>,
     aggregates: &Option<PostLookupAggregates<Column>>,
+    constant_cols: &[(usize, SqlIdentifier, DfValue)],
 ) -> ReadySetResult<ReaderProcessing> {
     let order_by = if let Some(order) = order_by.as_ref() {
         Some(
@@ -1166,7 +1170,24 @@ fn make_reader_processing(
         .map(|aggs| aggs.map_columns(|col| graph.column_id_for_column(*parent, &col)))
         .transpose()?;
 
-    ReaderProcessing::new(order_by, limit, returned_cols, default_row, aggregates)
+    let constant_columns = constant_cols
+        .iter()
+        .map(|(index, name, value)| ConstantColumn {
+            index: *index,
+            name: name.clone(),
+            ty: value.infer_dataflow_type(),
+            value: value.clone(),
+        })
+        .collect();
+
+    ReaderProcessing::new(
+        order_by,
+        limit,
+        returned_cols,
+        default_row,
+        aggregates,
+        constant_columns,
+    )
 }
 
 fn materialize_leaf_node(
@@ -81,6 +81,10 @@ pub struct NodeRestrictionKey {
 pub(crate) struct ControllerState {
     pub(crate) config: Config,
     pub(crate) dataflow_state: DfState,
+    /// The leadership term of the controller that most recently acquired leadership. Incremented
+    /// every time a controller wins a leader election.
+    #[serde(default)]
+    pub(crate) leader_term: u64,
 }
 
 // We implement [`Debug`] manually so that we can skip the [`DfState`] field.
@@ -90,6 +94,7 @@ impl Debug for ControllerState {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ControllerState")
             .field("config", &self.config)
+            .field("leader_term", &self.leader_term)
             .field(
                 "schema_replication_offset",
                 &self.dataflow_state.schema_replication_offset(),
@@ -749,6 +754,7 @@ impl AuthorityLeaderElectionState {
                                 Ok(ControllerState {
                                     config: self.config.clone(),
                                     dataflow_state,
+                                    leader_term: 1,
                                 })
                             },
                             Some(mut state) => {
@@ -764,6 +770,7 @@ impl AuthorityLeaderElectionState {
                                 state.dataflow_state.domain_config = self.config.domain_config.clone();
                                 state.dataflow_state.replication_strategy = self.config.replication_strategy;
                                 state.config = self.config.clone();
+                                state.leader_term += 1;
                                 Ok(state)
                             }
                         }
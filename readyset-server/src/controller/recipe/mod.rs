@@ -1,6 +1,7 @@
 use std::str;
 use std::vec::Vec;
 
+use common::IndexType;
 use nom_sql::{
     CacheInner, CreateCacheStatement, CreateTableStatement, CreateViewStatement, Relation,
     SqlQuery, SqlType,
@@ -65,10 +66,13 @@ impl Recipe {
                 name,
                 statement,
                 always,
+                max_mem,
             } => SqlQuery::CreateCache(CreateCacheStatement {
                 name: Some(name.clone()),
                 inner: CacheInner::Statement(Box::new(statement.clone())),
                 always: *always,
+                index_type: None,
+                max_mem: *max_mem,
             }),
         });
         if expr.is_none() {
@@ -173,6 +177,33 @@ impl Recipe {
         &mut self,
         mig: &mut Migration<'_>,
         changelist: ChangeList,
+    ) -> ReadySetResult<()> {
+        self.activate_batch(mig, vec![changelist])
+    }
+
+    /// Applies each of the given lists of changes in order, as part of the same [`Migration`].
+    ///
+    /// The schema version is only upgraded once, after all the changes have been applied.
+    pub(crate) fn activate_batch(
+        &mut self,
+        mig: &mut Migration<'_>,
+        changelists: Vec<ChangeList>,
+    ) -> ReadySetResult<()> {
+        for changelist in changelists {
+            self.apply_changes(mig, changelist)?;
+        }
+
+        // We upgrade schema version *after* applying changes, so that the initial
+        // queries get correctly tagged with version 0.
+        self.inc.upgrade_version();
+
+        Ok(())
+    }
+
+    fn apply_changes(
+        &mut self,
+        mig: &mut Migration<'_>,
+        changelist: ChangeList,
     ) -> ReadySetResult<()> {
         debug!(
             num_queries = self.registry.len(),
@@ -183,6 +214,7 @@ impl Recipe {
             changes,
             schema_search_path,
             dialect,
+            ..
         } = changelist;
 
         for change in changes {
@@ -273,6 +305,7 @@ impl Recipe {
                             name: name.clone(),
                             statement: statement.clone(),
                             always: ccqs.always,
+                            max_mem: ccqs.max_mem,
                         };
                         let aliased = self.registry.add_query(expression)?;
                         debug!(
@@ -286,16 +319,38 @@ impl Recipe {
                         )?;
                         if !aliased {
                             // The expression is already present, and we successfully added
-                            // a new alias for it.
+                            // a new alias for it. The alias shares the existing cache's reader, so
+                            // apply any memory limit to that reader.
+                            if let Some(max_mem) = ccqs.max_mem {
+                                let leaf = self
+                                    .registry
+                                    .resolve_alias(name)
+                                    .and_then(|name| self.inc.get_query_address(name))
+                                    .ok_or_else(|| internal_err!("Query {name} has no leaf"))?;
+                                mig.set_reader_max_bytes(leaf, max_mem as usize)?;
+                                self.registry.set_cache_max_mem(name, max_mem);
+                            }
                             continue;
                         }
                     }
 
-                    let name = self.inc.add_query(ccqs.name, statement.clone(), mig)?;
+                    let name = self.inc.add_query_with_index_type(
+                        ccqs.name,
+                        statement.clone(),
+                        ccqs.index_type.map(IndexType::from),
+                        mig,
+                    )?;
+                    if let Some(max_mem) = ccqs.max_mem {
+                        let leaf = self.inc.get_query_address(&name).ok_or_else(|| {
+                            internal_err!("Query {name} has no leaf after being added")
+                        })?;
+                        mig.set_reader_max_bytes(leaf, max_mem as usize)?;
+                    }
                     self.registry.add_query(RecipeExpr::Cache {
                         name: name.clone(),
                         statement,
                         always: ccqs.always,
+                        max_mem: ccqs.max_mem,
                     })?;
                     self.registry
                         .insert_invalidating_tables(name.clone(), invalidating_tables)?;
@@ -334,6 +389,14 @@ impl Recipe {
                     self.registry.add_custom_type(name.clone());
                     self.inc.add_custom_type(name, ty)?;
                 }
+                Change::AddNonReplicatedRelation(mut name) => {
+                    if let Some(first_schema) = schema_search_path.first() {
+                        if name.schema.is_none() {
+                            name.schema = Some(first_schema.clone())
+                        }
+                    }
+                    self.inc.add_non_replicated_relation(name);
+                }
                 Change::Drop {
                     mut name,
                     if_exists,
@@ -425,10 +488,6 @@ impl Recipe {
             }
         }
 
-        // We upgrade schema version *after* applying changes, so that the initial
-        // queries get correctly tagged with version 0.
-        self.inc.upgrade_version();
-
         Ok(())
     }
 
@@ -437,6 +496,12 @@ impl Recipe {
         &self.inc
     }
 
+    /// Returns the current schema version of this recipe, which is incremented every time a set
+    /// of changes is applied to it.
+    pub(crate) fn schema_version(&self) -> usize {
+        self.inc.schema_version()
+    }
+
     fn drop_and_recreate_table(
         &mut self,
         table: &Relation,
@@ -680,4 +745,15 @@ impl Recipe {
                 .rewrite(query.statement, &query.schema_search_path, dialect, None)?;
         Ok(self.registry.contains(&statement))
     }
+
+    /// Returns a human-readable description of the plan that would be used to cache the given
+    /// query, without adding it to the recipe.
+    pub(crate) fn explain_query(
+        &self,
+        query: ViewCreateRequest,
+        dialect: Dialect,
+    ) -> ReadySetResult<String> {
+        self.inc
+            .explain_query(query.statement, &query.schema_search_path, dialect)
+    }
 }
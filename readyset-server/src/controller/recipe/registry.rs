@@ -27,6 +27,10 @@ pub(super) enum RecipeExpr {
         name: Relation,
         statement: SelectStatement,
         always: bool,
+        /// The maximum number of bytes the cache's reader may use, if requested with a `max_mem`
+        /// annotation
+        #[serde(default)]
+        max_mem: Option<u64>,
     },
 }
 
@@ -210,6 +214,18 @@ impl ExprRegistry {
         Ok(true)
     }
 
+    /// Records `max_mem` as the memory limit for the cache with the given name (or alias), if it
+    /// exists
+    pub(super) fn set_cache_max_mem(&mut self, name_or_alias: &Relation, max_mem: u64) {
+        if let Some(RecipeExpr::Cache { max_mem: m, .. }) = self
+            .aliases
+            .get(name_or_alias)
+            .and_then(|query_id| self.expressions.get_mut(query_id))
+        {
+            *m = Some(max_mem);
+        }
+    }
+
     /// Retrieves the [`RecipeExpr`] associated with the given name or alias.
     /// If no query is found, returns `None`.
     pub(super) fn get(&self, alias: &Relation) -> Option<&RecipeExpr> {
@@ -420,6 +436,7 @@ mod tests {
                 statement: parse_select_statement(Dialect::MySQL, "SELECT * FROM test_table;")
                     .unwrap(),
                 always: false,
+                max_mem: None,
             };
 
             assert_eq!(cached_query.name(), &query_name);
@@ -451,6 +468,7 @@ mod tests {
                 statement: parse_select_statement(Dialect::MySQL, "SELECT * FROM test_table;")
                     .unwrap(),
                 always: false,
+                max_mem: None,
             };
 
             let cached_query_table_refs = cached_query.table_references();
@@ -494,6 +512,7 @@ mod tests {
                     name: "test_query".into(),
                     statement: statement.clone(),
                     always: false,
+                    max_mem: None,
                 })
                 .unwrap();
             registry
@@ -501,6 +520,7 @@ mod tests {
                     name: "test_query_alias".into(),
                     statement,
                     always: false,
+                    max_mem: None,
                 })
                 .unwrap();
 
@@ -511,6 +531,7 @@ mod tests {
                     name: "test_query".into(),
                     statement: statement.clone(),
                     always: false,
+                    max_mem: None,
                 })
                 .unwrap();
             registry
@@ -518,6 +539,7 @@ mod tests {
                     name: "test_query_alias".into(),
                     statement,
                     always: false,
+                    max_mem: None,
                 })
                 .unwrap();
 
@@ -558,6 +580,7 @@ mod tests {
                 )
                 .unwrap(),
                 always: false,
+                max_mem: None,
             };
 
             assert!(registry.add_query(expr.clone()).unwrap());
@@ -575,6 +598,7 @@ mod tests {
                 statement: parse_select_statement(Dialect::MySQL, "SELECT * FROM test_table;")
                     .unwrap(),
                 always: false,
+                max_mem: None,
             };
             assert!(!registry.add_query(expr).unwrap());
 
@@ -585,7 +609,26 @@ mod tests {
                     name: "test_query".into(),
                     statement: parse_select_statement(Dialect::MySQL, "SELECT * FROM test_table;")
                         .unwrap(),
-                    always: false
+                    always: false,
+                    max_mem: None
+                }
+            );
+        }
+
+        #[test]
+        fn set_cache_max_mem_through_alias() {
+            let mut registry = setup();
+
+            registry.set_cache_max_mem(&"test_query_alias".into(), 1024);
+
+            assert_eq!(
+                *registry.get(&"test_query".into()).unwrap(),
+                RecipeExpr::Cache {
+                    name: "test_query".into(),
+                    statement: parse_select_statement(Dialect::MySQL, "SELECT * FROM test_table;")
+                        .unwrap(),
+                    always: false,
+                    max_mem: Some(1024)
                 }
             );
         }
@@ -728,7 +771,8 @@ mod tests {
                     name: "test_query".into(),
                     statement: parse_select_statement(Dialect::MySQL, "SELECT * FROM test_table")
                         .unwrap(),
-                    always: false
+                    always: false,
+                    max_mem: None
                 }
             );
             assert!(registry.get(&"test_query_alias".into()).is_none())
@@ -774,6 +818,7 @@ mod tests {
                     name: "test".into(),
                     statement: stmt.clone(),
                     always: false,
+                    max_mem: None,
                 })
                 .unwrap();
             assert!(registry.contains(&stmt))
@@ -804,6 +849,7 @@ mod tests {
                     statement: parse_select_statement(Dialect::MySQL, "SELECT * FROM test_table")
                         .unwrap(),
                     always: false,
+                    max_mem: None,
                 })
                 .unwrap();
 
@@ -890,7 +936,8 @@ mod tests {
                 .add_query(RecipeExpr::Cache {
                     name: "foo".into(),
                     statement: query.clone(),
-                    always: false
+                    always: false,
+                    max_mem: None
                 })
                 .unwrap());
 
@@ -1,5 +1,5 @@
 use dataflow::prelude::*;
-use nom_sql::Column;
+use nom_sql::{Column, ColumnConstraint, TableKey};
 use readyset::{ColumnBase, ColumnSchema};
 use readyset_data::DfType;
 use tracing::trace;
@@ -46,6 +46,20 @@ fn trace_column_type_on_path<'g>(path: Path, graph: &'g Graph) -> ReadySetResult
     }
 }
 
+/// Returns true if the column traced along `path` comes from the nullable side of a left join
+/// somewhere along the path, in which case it can be NULL even if its base column can't
+fn is_outer_join_nullable(path: Path, graph: &Graph) -> bool {
+    path.windows(2).any(|w| {
+        let (child, _) = &w[0];
+        let (parent, cols) = &w[1];
+        cols.iter().any(Option::is_some)
+            && matches!(
+                graph[*child].as_internal(),
+                Some(NodeOperator::Join(join)) if join.nullable_parent() == Some(*parent)
+            )
+    })
+}
+
 fn get_base_for_column(
     path: Path,
     graph: &Graph,
@@ -59,16 +73,24 @@ fn get_base_for_column(
         if source_node.is_base() {
             if let Some(Schema::Table(ref schema)) = recipe.schema_for(source_node.name()) {
                 let col_index = cols.first().unwrap().unwrap();
+                let field = &schema.fields[col_index];
+                let mut constraints = field.constraints.clone();
+                // Primary keys declared as a table constraint, rather than on the column itself,
+                // still apply to the column
+                let in_table_primary_key = schema.keys.iter().flatten().any(|key| {
+                    matches!(key, TableKey::PrimaryKey { columns, .. }
+                        if columns.iter().any(|c| c.name == field.column.name))
+                });
+                if in_table_primary_key && !constraints.contains(&ColumnConstraint::PrimaryKey) {
+                    constraints.push(ColumnConstraint::PrimaryKey);
+                }
+
                 #[allow(clippy::unwrap_used)] // occurs after implied table rewrite
                 return Ok(Some(ColumnBase {
-                    column: schema.fields[col_index].column.name.clone(),
-                    table: schema.fields[col_index]
-                        .column
-                        .table
-                        .as_ref()
-                        .unwrap()
-                        .clone(),
-                    constraints: schema.fields[col_index].constraints.clone(),
+                    column: field.column.name.clone(),
+                    table: field.column.table.as_ref().unwrap().clone(),
+                    constraints,
+                    outer_join_nullable: is_outer_join_nullable(path, graph),
                 }));
             }
         }
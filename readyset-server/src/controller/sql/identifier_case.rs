@@ -0,0 +1,375 @@
+//! Resolution of the names of tables and columns referenced within queries against their spelling
+//! in the schema, for upstream databases which compare those names without regard to case.
+
+use std::collections::HashMap;
+
+use nom_sql::analysis::visit_mut::{walk_join_clause, VisitorMut};
+use nom_sql::{
+    Column, CreateTableStatement, CreateViewStatement, JoinClause, JoinRightSide, Relation,
+    SelectSpecification, SelectStatement, SqlIdentifier, TableExpr,
+};
+use serde::{Deserialize, Serialize};
+
+/// How the names of tables and columns referenced within a query are compared with each other
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IdentifierCase {
+    /// Names only refer to the same table or column if they're spelled identically
+    #[default]
+    Sensitive,
+    /// Names refer to the same table or column regardless of their case, as with MySQL servers
+    /// running with `lower_case_table_names` set
+    Insensitive,
+}
+
+impl IdentifierCase {
+    fn fold_identifier(self, ident: &SqlIdentifier) -> SqlIdentifier {
+        match self {
+            IdentifierCase::Sensitive => ident.clone(),
+            IdentifierCase::Insensitive => ident.to_lowercase().into(),
+        }
+    }
+
+    fn fold_relation(self, relation: &Relation) -> Relation {
+        Relation {
+            schema: relation.schema.as_ref().map(|s| self.fold_identifier(s)),
+            name: self.fold_identifier(&relation.name),
+        }
+    }
+}
+
+/// The spelling in the schema of the names of all the tables and views we know about, and of their
+/// columns, indexed by those names as folded according to an [`IdentifierCase`].
+pub(crate) struct SchemaSpellings {
+    identifier_case: IdentifierCase,
+    /// Map from folded relation names, both with and without their schema, to the spelling of
+    /// that relation in the schema
+    relations: HashMap<Relation, Relation>,
+    /// Map from folded relation names, both with and without their schema, to the spelling of the
+    /// columns of that relation, indexed by their folded names
+    columns: HashMap<Relation, HashMap<SqlIdentifier, SqlIdentifier>>,
+}
+
+impl SchemaSpellings {
+    pub(crate) fn new(
+        identifier_case: IdentifierCase,
+        base_schemas: &HashMap<Relation, CreateTableStatement>,
+        view_schemas: &HashMap<Relation, Vec<SqlIdentifier>>,
+    ) -> Self {
+        let mut spellings = Self {
+            identifier_case,
+            relations: HashMap::new(),
+            columns: HashMap::new(),
+        };
+
+        for (relation, stmt) in base_schemas {
+            spellings.add(relation, stmt.fields.iter().map(|field| &field.column.name));
+        }
+        for (relation, columns) in view_schemas {
+            spellings.add(relation, columns.iter());
+        }
+
+        spellings
+    }
+
+    fn add<'a, I>(&mut self, relation: &Relation, columns: I)
+    where
+        I: Iterator<Item = &'a SqlIdentifier> + Clone,
+    {
+        let identifier_case = self.identifier_case;
+        let unqualified = Relation {
+            schema: None,
+            name: relation.name.clone(),
+        };
+        for relation in [relation.clone(), unqualified] {
+            let folded = identifier_case.fold_relation(&relation);
+            let column_spellings = self.columns.entry(folded.clone()).or_default();
+            for column in columns.clone() {
+                column_spellings
+                    .entry(identifier_case.fold_identifier(column))
+                    .or_insert_with(|| column.clone());
+            }
+            self.relations.entry(folded).or_insert(relation);
+        }
+    }
+
+    /// Returns the spelling in the schema of the given table or view, if we know about it
+    fn relation(&self, relation: &Relation) -> Option<&Relation> {
+        self.relations
+            .get(&self.identifier_case.fold_relation(relation))
+    }
+
+    /// Returns the spelling in the schema of the given column of the given table or view, if we
+    /// know about it
+    fn column(&self, relation: &Relation, column: &SqlIdentifier) -> Option<&SqlIdentifier> {
+        self.columns
+            .get(&self.identifier_case.fold_relation(relation))?
+            .get(&self.identifier_case.fold_identifier(column))
+    }
+}
+
+/// Statements whose references to tables and columns can be respelled to match the schema
+pub(crate) trait NormalizeIdentifierCase: Sized {
+    /// Rewrite all the references to tables and columns in `self` to use the spelling of those
+    /// names in `schema`, so that they can be resolved against the schema (and against each other)
+    /// by exact comparison when rewriting the statement and building the [`QueryGraph`] and MIR
+    /// for it.
+    ///
+    /// Tables and views are replaced with their spelling in the schema, and table names in column
+    /// references with the spelling of the table (or alias) in the FROM or JOIN clause that they
+    /// refer to. Column names are replaced with their spelling in the schema, or if the column
+    /// isn't in the schema (such as a column of a subquery), with the first spelling of the same
+    /// column found in the query.
+    ///
+    /// [`QueryGraph`]: super::query_graph::QueryGraph
+    fn normalize_identifier_case(self, schema: &SchemaSpellings) -> Self;
+}
+
+impl NormalizeIdentifierCase for SelectStatement {
+    fn normalize_identifier_case(mut self, schema: &SchemaSpellings) -> Self {
+        struct TableCollector<'a> {
+            schema: &'a SchemaSpellings,
+            /// Map from the folded names of the tables and aliases in the query to their spelling
+            /// in the query
+            tables: HashMap<Relation, Relation>,
+            /// Map from the folded names of the tables and aliases in the query to the table or
+            /// view in the schema that they refer to
+            relations: HashMap<Relation, Relation>,
+        }
+
+        impl TableCollector<'_> {
+            fn add(&mut self, table: Relation, relation: Option<&Relation>) {
+                let folded = self.schema.identifier_case.fold_relation(&table);
+                if let Some(relation) = relation {
+                    self.relations
+                        .entry(folded.clone())
+                        .or_insert_with(|| relation.clone());
+                }
+                self.tables.entry(folded).or_insert(table);
+            }
+        }
+
+        impl<'ast> VisitorMut<'ast> for TableCollector<'_> {
+            type Error = !;
+
+            fn visit_table_expr(
+                &mut self,
+                table_expr: &'ast mut TableExpr,
+            ) -> Result<(), Self::Error> {
+                let schema = self.schema;
+                let relation = schema.relation(&table_expr.table);
+                if let Some(relation) = relation {
+                    table_expr.table = relation.clone();
+                }
+                self.add(table_expr.table.clone(), relation);
+                // Columns can refer to schema-qualified tables by their name alone
+                if table_expr.table.schema.is_some() {
+                    self.add(table_expr.table.name.clone().into(), relation);
+                }
+                if let Some(alias) = &table_expr.alias {
+                    self.add(alias.clone().into(), relation);
+                }
+                Ok(())
+            }
+
+            fn visit_join_clause(&mut self, join: &'ast mut JoinClause) -> Result<(), Self::Error> {
+                if let JoinRightSide::NestedSelect(_, alias) = &join.right {
+                    self.add(alias.clone().into(), None);
+                }
+                walk_join_clause(self, join)
+            }
+        }
+
+        struct ColumnNormalizer<'a> {
+            schema: &'a SchemaSpellings,
+            tables: HashMap<Relation, Relation>,
+            relations: HashMap<Relation, Relation>,
+            columns: HashMap<(Option<Relation>, SqlIdentifier), SqlIdentifier>,
+        }
+
+        impl<'ast> VisitorMut<'ast> for ColumnNormalizer<'_> {
+            type Error = !;
+
+            fn visit_column(&mut self, column: &'ast mut Column) -> Result<(), Self::Error> {
+                let schema = self.schema;
+                let identifier_case = schema.identifier_case;
+                let folded_table = column
+                    .table
+                    .as_ref()
+                    .map(|table| identifier_case.fold_relation(table));
+                if let Some(table) = folded_table.as_ref().and_then(|t| self.tables.get(t)) {
+                    column.table = Some(table.clone());
+                }
+
+                let spelling = match &folded_table {
+                    Some(table) => self
+                        .relations
+                        .get(table)
+                        .and_then(|relation| schema.column(relation, &column.name)),
+                    None => self
+                        .relations
+                        .values()
+                        .find_map(|relation| schema.column(relation, &column.name)),
+                };
+                column.name = self
+                    .columns
+                    .entry((
+                        column.table.clone(),
+                        identifier_case.fold_identifier(&column.name),
+                    ))
+                    .or_insert_with(|| spelling.unwrap_or(&column.name).clone())
+                    .clone();
+                Ok(())
+            }
+        }
+
+        let mut collector = TableCollector {
+            schema,
+            tables: HashMap::new(),
+            relations: HashMap::new(),
+        };
+        let _ = collector.visit_select_statement(&mut self);
+        let mut normalizer = ColumnNormalizer {
+            schema,
+            tables: collector.tables,
+            relations: collector.relations,
+            columns: HashMap::new(),
+        };
+        let _ = normalizer.visit_select_statement(&mut self);
+
+        self
+    }
+}
+
+impl NormalizeIdentifierCase for CreateViewStatement {
+    fn normalize_identifier_case(mut self, schema: &SchemaSpellings) -> Self {
+        self.definition = Box::new(match *self.definition {
+            SelectSpecification::Simple(stmt) => {
+                SelectSpecification::Simple(stmt.normalize_identifier_case(schema))
+            }
+            SelectSpecification::Compound(mut compound) => {
+                compound.selects = compound
+                    .selects
+                    .into_iter()
+                    .map(|(op, stmt)| (op, stmt.normalize_identifier_case(schema)))
+                    .collect();
+                SelectSpecification::Compound(compound)
+            }
+        });
+        self
+    }
+}
+
+impl NormalizeIdentifierCase for CreateTableStatement {
+    /// Tables define the spelling of their own names, so there's nothing to normalize
+    fn normalize_identifier_case(self, _schema: &SchemaSpellings) -> Self {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::{parse_create_table, parse_create_view, parse_query, Dialect, SqlQuery};
+
+    use super::*;
+    use crate::controller::sql::query_graph::to_query_graph;
+
+    fn parse_select(sql: &str) -> SelectStatement {
+        match parse_query(Dialect::MySQL, sql).unwrap() {
+            SqlQuery::Select(stmt) => stmt,
+            q => panic!("Expected select, got {q:?}"),
+        }
+    }
+
+    fn no_schema(identifier_case: IdentifierCase) -> SchemaSpellings {
+        SchemaSpellings::new(identifier_case, &HashMap::new(), &HashMap::new())
+    }
+
+    #[test]
+    fn case_sensitive_identifiers() {
+        let query = parse_select("SELECT Users.id FROM users WHERE users.name = ?");
+
+        let normalized = query
+            .clone()
+            .normalize_identifier_case(&no_schema(IdentifierCase::Sensitive));
+        assert_eq!(normalized, query);
+        // `Users` isn't a table the query reads from, so the parameter can't be resolved
+        let qg = to_query_graph(&normalized).unwrap();
+        assert!(qg.relations[&Relation::from("users")]
+            .columns
+            .iter()
+            .all(|c| c.name != "id"));
+    }
+
+    #[test]
+    fn case_insensitive_identifiers() {
+        let query = parse_select(
+            "SELECT Users.id, USERS.Name FROM users WHERE users.NAME = ? AND users.ID > 5",
+        );
+
+        let normalized = query.normalize_identifier_case(&no_schema(IdentifierCase::Insensitive));
+        assert_eq!(
+            normalized,
+            parse_select(
+                "SELECT users.id, users.Name FROM users WHERE users.Name = ? AND users.id > 5"
+            )
+        );
+
+        let qg = to_query_graph(&normalized).unwrap();
+        assert_eq!(qg.relations.len(), 1);
+        let rel = &qg.relations[&Relation::from("users")];
+        assert_eq!(
+            rel.columns,
+            vec![Column::from("users.id"), Column::from("users.Name")]
+        );
+        assert_eq!(rel.parameters.len(), 1);
+        assert_eq!(rel.parameters[0].col, Column::from("users.Name"));
+    }
+
+    #[test]
+    fn case_insensitive_identifiers_use_schema_spelling() {
+        let table = parse_create_table(
+            Dialect::MySQL,
+            "CREATE TABLE db.Users (ID int, Name text, Email text)",
+        )
+        .unwrap();
+        let schema = SchemaSpellings::new(
+            IdentifierCase::Insensitive,
+            &HashMap::from([(table.table.clone(), table)]),
+            &HashMap::from([(
+                Relation {
+                    schema: Some("db".into()),
+                    name: "Recent".into(),
+                },
+                vec!["UserID".into()],
+            )]),
+        );
+
+        let normalized = parse_select(
+            "SELECT u.id, email FROM users AS u JOIN DB.recent ON U.ID = recent.userid \
+             WHERE u.NAME = ?",
+        )
+        .normalize_identifier_case(&schema);
+        assert_eq!(
+            normalized,
+            parse_select(
+                "SELECT u.ID, Email FROM Users AS u JOIN db.Recent ON u.ID = Recent.UserID \
+                 WHERE u.Name = ?"
+            )
+        );
+
+        let view = parse_create_view(
+            Dialect::MySQL,
+            "CREATE VIEW names AS SELECT users.name FROM users",
+        )
+        .unwrap()
+        .normalize_identifier_case(&schema);
+        assert_eq!(
+            view,
+            parse_create_view(
+                Dialect::MySQL,
+                "CREATE VIEW names AS SELECT Users.Name FROM Users"
+            )
+            .unwrap()
+        );
+    }
+}
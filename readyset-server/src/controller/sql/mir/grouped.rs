@@ -8,7 +8,6 @@ use nom_sql::FunctionExpr::*;
 use nom_sql::{self, Expr, FieldDefinitionExpr, Relation, SelectStatement, SqlIdentifier};
 use petgraph::graph::NodeIndex;
 use readyset_errors::{unsupported, ReadySetError};
-use readyset_sql_passes::is_aggregate;
 
 use crate::controller::sql::mir::join::make_joins_for_aggregates;
 use crate::controller::sql::mir::SqlToMirConverter;
@@ -54,8 +53,10 @@ pub(super) fn make_predicates_above_grouped<'a>(
 /// expressions reference expressions rather than columns directly, we need to project them out
 /// before the grouped nodes.
 ///
-/// This does that projection, and returns a mapping from the expressions themselves to the names of
-/// the columns they have been projected to
+/// This does that projection for each of the query graph's [`aggregate_arguments`], and returns a
+/// mapping from the expressions themselves to the names of the columns they have been projected to
+///
+/// [`aggregate_arguments`]: QueryGraph::aggregate_arguments
 pub(super) fn make_expressions_above_grouped(
     mir_converter: &mut SqlToMirConverter,
     query_name: &Relation,
@@ -64,14 +65,9 @@ pub(super) fn make_expressions_above_grouped(
     prev_node: &mut NodeIndex,
 ) -> HashMap<Expr, SqlIdentifier> {
     let exprs: Vec<_> = qg
-        .aggregates
+        .aggregate_arguments
         .iter()
-        .map(|(f, _)| f)
-        .filter(|&f| is_aggregate(f))
-        .flat_map(|f| f.arguments())
-        // We don't need to do any work for bare column expressions
-        .filter(|arg| !matches!(arg, Expr::Column(_)))
-        .map(|expr| (SqlIdentifier::from(expr.to_string()), expr.clone()))
+        .map(|ec| (ec.name.clone(), ec.expression.clone()))
         .collect();
 
     if !exprs.is_empty() {
@@ -1,10 +1,10 @@
 use std::collections::{HashMap, HashSet};
 
-use nom_sql::Relation;
+use nom_sql::{Expr, Relation};
 use petgraph::graph::NodeIndex;
 use readyset_errors::{internal, internal_err, invariant};
 
-use super::JoinKind;
+use super::{value_columns_needed_for_predicates, JoinKind};
 use crate::controller::sql::mir::SqlToMirConverter;
 use crate::controller::sql::query_graph::{QueryGraph, QueryGraphEdge};
 use crate::ReadySetResult;
@@ -34,21 +34,36 @@ impl JoinChain {
 // If a predicate's parent tables haven't been used by any previous predicate,
 // a new join chain is started for the current predicate. And we assume that
 // a future predicate will bring these chains together.
-pub(super) fn make_joins(
+//
+// Global predicates which only reference columns of tables in the query (such as
+// `t1.x = 1 OR t2.y = 2`) are applied as filters directly after the join which brings all of those
+// tables together, rather than after all the joins in the query. Returns the last node of each
+// join chain step (either the join node, or the last filter applied after it), along with the
+// global predicates which were applied.
+pub(super) fn make_joins<'a>(
     mir_converter: &mut SqlToMirConverter,
     query_name: &Relation,
     name: Relation,
-    qg: &QueryGraph,
+    qg: &'a QueryGraph,
     node_for_rel: &HashMap<&Relation, NodeIndex>,
     correlated_nodes: &HashSet<NodeIndex>,
-) -> ReadySetResult<Vec<NodeIndex>> {
+) -> ReadySetResult<(Vec<NodeIndex>, Vec<&'a Expr>)> {
     let mut join_nodes: Vec<NodeIndex> = Vec::new();
     let mut join_chains = Vec::new();
+    let mut pending_predicates = qg
+        .global_predicates
+        .iter()
+        .filter_map(|pred| Some((pred, global_predicate_tables(qg, pred)?)))
+        .collect::<Vec<_>>();
+    let mut applied_predicates = Vec::new();
 
     for jref in qg.join_order.iter() {
         let (mut join_kind, jps) = match &qg.edges[&(jref.src.clone(), jref.dst.clone())] {
             QueryGraphEdge::Join { on } => (JoinKind::Inner, on),
             QueryGraphEdge::LeftJoin { on } => (JoinKind::Left, on),
+            // The right-hand side of a semi-join is always distinct, so an inner join against it
+            // never duplicates rows on the left
+            QueryGraphEdge::SemiJoin { on } => (JoinKind::Inner, on),
         };
 
         let (left_chain, right_chain) =
@@ -78,13 +93,52 @@ pub(super) fn make_joins(
         )?;
 
         // merge node chains
-        let new_chain = left_chain.merge_chain(right_chain, jn);
+        let mut new_chain = left_chain.merge_chain(right_chain, jn);
+
+        // apply any global predicates that only needed the tables in this chain
+        let (ready, pending): (Vec<_>, Vec<_>) = pending_predicates
+            .into_iter()
+            .partition(|(_, tables)| tables.iter().all(|t| new_chain.has_table(t)));
+        pending_predicates = pending;
+        for (pred, _) in ready {
+            new_chain.last_node = mir_converter.make_predicate_nodes(
+                query_name,
+                mir_converter.generate_label(&name),
+                new_chain.last_node,
+                pred,
+            )?;
+            applied_predicates.push(pred);
+        }
+
+        join_nodes.push(new_chain.last_node);
         join_chains.push(new_chain);
+    }
 
-        join_nodes.push(jn);
+    Ok((join_nodes, applied_predicates))
+}
+
+/// Returns the set of tables referenced by the given global predicate, if it can be applied as
+/// soon as those tables have been joined together.
+///
+/// Predicates which reference columns that aren't available until after the joins (such as
+/// projected expressions or literals), or which reference fewer than two tables, return `None`,
+/// and are applied after all the joins in the query instead.
+fn global_predicate_tables<'a>(
+    qg: &'a QueryGraph,
+    pred: &'a Expr,
+) -> Option<HashSet<&'a Relation>> {
+    if !value_columns_needed_for_predicates(&qg.columns, std::slice::from_ref(pred)).is_empty() {
+        return None;
     }
 
-    Ok(join_nodes)
+    let mut tables = HashSet::new();
+    for col in pred.referred_columns() {
+        let table = col.table.as_ref()?;
+        let (table, _) = qg.relations.get_key_value(table)?;
+        tables.insert(table);
+    }
+
+    (tables.len() > 1).then_some(tables)
 }
 
 /// Make cartesian (cross) joins for the given list of nodes, returning a list of join nodes created
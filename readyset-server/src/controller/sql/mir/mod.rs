@@ -29,12 +29,15 @@ use readyset_sql_passes::is_correlated;
 use tracing::{debug, trace};
 
 use super::query_graph::{extract_limit_offset, JoinPredicate};
+use crate::controller::sql::identifier_case::IdentifierCase;
 use crate::controller::sql::mir::grouped::{
     make_expressions_above_grouped, make_grouped, make_predicates_above_grouped,
     post_lookup_aggregates,
 };
 use crate::controller::sql::mir::join::{make_cross_joins, make_joins};
-use crate::controller::sql::query_graph::{to_query_graph, OutputColumn, Pagination, QueryGraph};
+use crate::controller::sql::query_graph::{
+    to_query_graph, ExprColumn, LiteralColumn, OutputColumn, Pagination, QueryGraph,
+};
 use crate::controller::sql::query_signature::Signature;
 use crate::ReadySetResult;
 
@@ -110,6 +113,52 @@ fn default_row_for_select(st: &SelectStatement) -> Option<Vec<DfValue>> {
     )
 }
 
+/// Returns the columns returned by the given query, in the order they're returned. Columns that
+/// are only projected so that the results can be ordered by them are
+/// [hidden][QueryGraph::hidden_columns], and aren't returned.
+fn returned_columns(qg: &QueryGraph) -> Vec<Column> {
+    qg.output_columns()
+        .iter()
+        .map(|oc| match oc {
+            OutputColumn::Data { alias, column }
+                if column.table.is_some() && *alias == column.name =>
+            {
+                Column::from(column)
+            }
+            OutputColumn::Data { alias, .. } => Column::named(alias.clone()),
+            OutputColumn::Literal(LiteralColumn { name, .. })
+            | OutputColumn::Expr(ExprColumn { name, .. }) => Column::named(name.clone()),
+        })
+        .filter(|col| col.name != "bogokey")
+        .collect()
+}
+
+/// Returns the columns to order the results of lookups into the leaf of the given query by
+fn leaf_order_by(st: &SelectStatement) -> ReadySetResult<Option<Vec<(Column, OrderType)>>> {
+    st.order
+        .as_ref()
+        .map(|order| {
+            order
+                .order_by
+                .iter()
+                .cloned()
+                .map(|(expr, ot)| {
+                    Ok((
+                        match expr {
+                            FieldReference::Expr(Expr::Column(col)) => Column::from(col),
+                            FieldReference::Expr(expr) => Column::named(expr.to_string()),
+                            FieldReference::Numeric(_) => {
+                                internal!("Numeric field references should have been removed")
+                            }
+                        },
+                        ot.unwrap_or(OrderType::OrderAscending),
+                    ))
+                })
+                .collect::<ReadySetResult<_>>()
+        })
+        .transpose()
+}
+
 /// Kinds of joins in MIR
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JoinKind {
@@ -142,6 +191,15 @@ pub(crate) struct Config {
     /// Enable support for mixing equality and range comparisons in a query. Support for mixed
     /// comparisons is currently unfinished, so these queries may return incorrect results.
     pub(crate) allow_mixed_comparisons: bool,
+
+    /// The maximum number of columns allowed in the lookup key of a view. Queries parametrized on
+    /// more columns than this are rejected with a [`ReadySetError::Unsupported`], causing the
+    /// adapter to send them to fallback. Defaults to `None`, meaning no limit.
+    pub(crate) max_view_key_columns: Option<usize>,
+
+    /// How the names of tables and columns referenced within queries are compared with each
+    /// other. Defaults to [`IdentifierCase::Sensitive`].
+    pub(crate) identifier_case: IdentifierCase,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -156,6 +214,9 @@ pub(super) struct SqlToMirConverter {
     /// of views or cached queries) in the MIR Supergraph.
     pub(in crate::controller::sql) relations: HashMap<Relation, NodeIndex>,
     pub(in crate::controller::sql) schema_version: usize,
+    /// Tables which exist in the upstream database but were filtered out of replication
+    #[serde(default)]
+    pub(in crate::controller::sql) non_replicated_relations: HashSet<Relation>,
 }
 
 impl SqlToMirConverter {
@@ -175,6 +236,19 @@ impl SqlToMirConverter {
         self.relations.get(relation).copied()
     }
 
+    /// Returns true if the given relation is a table which was filtered out of replication. If
+    /// the relation isn't qualified with a schema, a table with the same name in any schema
+    /// matches.
+    fn is_non_replicated(&self, relation: &Relation) -> bool {
+        match &relation.schema {
+            Some(_) => self.non_replicated_relations.contains(relation),
+            None => self
+                .non_replicated_relations
+                .iter()
+                .any(|r| r.name == relation.name),
+        }
+    }
+
     /// Generates a label based on the number of nodes in the MIR graph.
     /// Useful to generate label for new nodes.
     ///
@@ -211,6 +285,17 @@ impl SqlToMirConverter {
                 subquery_leaves.as_slice(),
                 union::DuplicateMode::UnionAll,
             )?,
+            CompoundSelectOperator::DistinctUnion => {
+                // Deduplicate the result of the union by grouping on all of its columns
+                let union = self.make_union_node(
+                    query_name,
+                    format!("{}_union_all", query_name).into(),
+                    subquery_leaves.as_slice(),
+                    union::DuplicateMode::UnionAll,
+                )?;
+                let group_by = self.mir_graph.columns(union);
+                self.make_distinct_node(query_name, name, union, group_by)
+            }
             _ => internal!(),
         };
 
@@ -851,6 +936,16 @@ impl SqlToMirConverter {
         let mut on = Vec::new();
 
         for jp in join_predicates {
+            if !jp.is_equi() {
+                unsupported!(
+                    "Join predicate {} {} {} requires a nested-loop join, which is not yet \
+                     supported",
+                    jp.left,
+                    jp.op,
+                    jp.right
+                );
+            }
+
             let mut l_col = match jp.left {
                 Expr::Column(ref f) => Column::from(f),
                 _ => unsupported!("no multi-level joins yet"),
@@ -1133,6 +1228,7 @@ impl SqlToMirConverter {
                     &qg,
                     HashMap::new(),
                     false,
+                    None,
                 )?;
 
                 // -> π[lit: 0, lit: 0]
@@ -1188,10 +1284,10 @@ impl SqlToMirConverter {
                 self.make_join_node(
                     query_name,
                     format!("{}_join", name).into(),
-                    &[JoinPredicate {
-                        left: Expr::Column("__exists_join_key".into()),
-                        right: Expr::Column("__count_grp".into()),
-                    }],
+                    &[JoinPredicate::equal(
+                        Expr::Column("__exists_join_key".into()),
+                        Expr::Column("__count_grp".into()),
+                    )],
                     left_literal_join_key_proj,
                     gt_0_filter,
                     if is_correlated(subquery) {
@@ -1303,6 +1399,9 @@ impl SqlToMirConverter {
 
     /// Adds all the MIR nodes corresponding to the given query,
     /// and returns the index of its leaf node.
+    ///
+    /// If `index_type` is provided, it overrides the index type inferred from the query's
+    /// parameters for the leaf node.
     #[allow(clippy::cognitive_complexity)]
     pub(super) fn named_query_to_mir(
         &mut self,
@@ -1311,6 +1410,7 @@ impl SqlToMirConverter {
         qg: &QueryGraph,
         anon_queries: HashMap<Relation, NodeIndex>,
         has_leaf: bool,
+        index_type: Option<IndexType>,
     ) -> Result<NodeIndex, ReadySetError> {
         // TODO(fran): We are not modifying the execution of this method with the implementation
         //  of petgraph, which causes us to create nodes that could now easily be reused:
@@ -1325,7 +1425,18 @@ impl SqlToMirConverter {
 
             // Convert the query parameters to an ordered list of columns that will comprise the
             // lookup key if a leaf node is attached.
-            let view_key = qg.view_key(self.config())?;
+            let mut view_key = qg.view_key(self.config())?;
+            if let Some(index_type) = index_type {
+                // Lookups for range parameters can only be satisfied by a BTreeMap index, but any
+                // query can be satisfied by one
+                if index_type < view_key.index_type {
+                    unsupported!(
+                        "Cannot use a {:?} index for a query with range parameters",
+                        index_type
+                    );
+                }
+                view_key.index_type = index_type;
+            }
 
             // 0. Base nodes (always reused)
             let mut base_nodes: Vec<NodeIndex> = Vec::new();
@@ -1340,6 +1451,7 @@ impl SqlToMirConverter {
                         subgraph,
                         HashMap::new(),
                         false,
+                        None,
                     )?;
                     if is_correlated(subquery) {
                         correlated_relations.insert(subquery_leaf);
@@ -1349,9 +1461,16 @@ impl SqlToMirConverter {
                     match self.get_relation(rel) {
                         Some(node_idx) => node_idx,
                         None => anon_queries.get(rel).copied().ok_or_else(|| {
-                            ReadySetError::TableNotFound {
-                                name: rel.to_string(),
-                                schema: None,
+                            if self.is_non_replicated(rel) {
+                                ReadySetError::TableNotReplicated {
+                                    name: rel.name.to_string(),
+                                    schema: rel.schema.as_ref().map(|s| s.to_string()),
+                                }
+                            } else {
+                                ReadySetError::TableNotFound {
+                                    name: rel.to_string(),
+                                    schema: None,
+                                }
                             }
                         })?,
                     }
@@ -1382,7 +1501,7 @@ impl SqlToMirConverter {
                 node_for_rel.insert(*rel, alias_table_node);
             }
 
-            let join_nodes = make_joins(
+            let (join_nodes, join_predicates) = make_joins(
                 self,
                 query_name,
                 format!("q_{:x}", qg.signature().hash).into(),
@@ -1437,7 +1556,12 @@ impl SqlToMirConverter {
                     .relations
                     .get(*rel)
                     .ok_or_else(|| internal_err!("couldn't find {:?} in qg relations", rel))?;
-                for pred in qgn.predicates.iter().chain(&qg.global_predicates) {
+                for pred in qgn
+                    .predicates
+                    .iter()
+                    .chain(&qg.global_predicates)
+                    .filter(|p| !join_predicates.contains(p))
+                {
                     for col in pred.referred_columns() {
                         column_to_predicates
                             .entry(col.clone())
@@ -1504,9 +1628,9 @@ impl SqlToMirConverter {
                 prev_node = projected;
             }
 
-            // 7. Global predicates
+            // 7. Global predicates (other than those already applied directly after a join)
             for (i, ref p) in qg.global_predicates.iter().enumerate() {
-                if created_predicates.contains(p) {
+                if created_predicates.contains(p) || join_predicates.contains(p) {
                     continue;
                 }
 
@@ -1723,6 +1847,53 @@ impl SqlToMirConverter {
                 final_node = distinct_node;
             }
 
+            // Literals which are returned from the query as-is have the same value in every row, so
+            // rather than storing them in the reader they're added to rows after they're looked up
+            let (returned_cols, order_by) = if has_leaf {
+                (returned_columns(qg), leaf_order_by(&st)?)
+            } else {
+                (vec![], None)
+            };
+            let aggregates = if has_leaf && view_key.index_type != IndexType::HashMap {
+                post_lookup_aggregates(qg, &st, query_name)?
+            } else {
+                None
+            };
+            let constant_cols: Vec<(usize, SqlIdentifier, DfValue)> =
+                if has_leaf && aggregates.is_none() {
+                    returned_cols
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, col)| {
+                            col.table.is_none()
+                                && !order_by
+                                    .iter()
+                                    .flatten()
+                                    .any(|(order_col, _)| order_col.name == col.name)
+                        })
+                        .filter_map(|(i, col)| {
+                            let (name, value) = projected_literals
+                                .iter()
+                                .find(|(name, _)| *name == col.name)?;
+                            Some((i, name.clone(), value.clone()))
+                        })
+                        .collect()
+                } else {
+                    vec![]
+                };
+            projected_literals.retain(|(name, _)| {
+                !constant_cols
+                    .iter()
+                    .any(|(_, constant_name, _)| constant_name == name)
+            });
+            let default_row = default_row_for_select(&st).map(|row| {
+                row.into_iter()
+                    .enumerate()
+                    .filter(|(i, _)| !constant_cols.iter().any(|(idx, _, _)| idx == i))
+                    .map(|(_, val)| val)
+                    .collect()
+            });
+
             let leaf_project_node = self.make_project_node(
                 query_name,
                 if has_leaf {
@@ -1739,28 +1910,12 @@ impl SqlToMirConverter {
             if has_leaf {
                 // We are supposed to add a `Leaf` node keyed on the query parameters. For purely
                 // internal views (e.g., subqueries), this is not set.
-                let mut returned_cols = st
-                    .fields
-                    .iter()
-                    .map(|expression| -> ReadySetResult<_> {
-                        match expression {
-                            FieldDefinitionExpr::All | FieldDefinitionExpr::AllInTable(_) => {
-                                internal!("All expression should have been desugared at this point")
-                            }
-                            FieldDefinitionExpr::Expr {
-                                alias: Some(alias), ..
-                            } => Ok(Column::named(alias.clone())),
-                            FieldDefinitionExpr::Expr {
-                                expr: Expr::Column(c),
-                                ..
-                            } => Ok(Column::from(c)),
-                            FieldDefinitionExpr::Expr { expr, .. } => {
-                                Ok(Column::named(expr.to_string()))
-                            }
-                        }
-                    })
-                    .collect::<Result<Vec<_>, _>>()?;
-                returned_cols.retain(|e| e.name != "bogokey");
+                let returned_cols = returned_cols
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, _)| !constant_cols.iter().any(|(idx, _, _)| idx == i))
+                    .map(|(_, col)| col)
+                    .collect::<Vec<_>>();
 
                 // After we have all of our returned columns figured out, find out how they are
                 // projected by this projection, so we can then add another projection that returns
@@ -1794,54 +1949,27 @@ impl SqlToMirConverter {
                     vec![],
                 );
 
-                let aggregates = if view_key.index_type != IndexType::HashMap {
-                    post_lookup_aggregates(qg, &st, query_name)?
-                } else {
-                    None
-                };
-
-                let leaf_node = self.add_query_node(query_name.clone(), MirNode::new(
+                let leaf_node = self.add_query_node(
                     query_name.clone(),
-                    MirNodeInner::Leaf {
-                        keys: view_key
-                            .columns
-                            .into_iter()
-                            .map(|(col, placeholder)| (col, placeholder))
-                            .collect(),
-                        index_type: view_key.index_type,
-                        order_by: st
-                            .order
-                            .as_ref()
-                            .map(|order| {
-                                order
-                                    .order_by
-                                    .iter()
-                                    .cloned()
-                                    .map(|(expr, ot)| {
-                                        Ok((
-                                            match expr {
-                                                FieldReference::Expr(Expr::Column(
-                                                                         col,
-                                                                     )) => Column::from(col),
-                                                FieldReference::Expr(expr) => {
-                                                    Column::named(expr.to_string())
-                                                }
-                                                FieldReference::Numeric(_) => internal!(
-                                                    "Numeric field references should have been removed"
-                                                ),
-                                            },
-                                            ot.unwrap_or(OrderType::OrderAscending),
-                                        ))
-                                    })
-                                    .collect::<ReadySetResult<_>>()
-                            })
-                            .transpose()?,
-                        limit: qg.pagination.as_ref().map(|p| p.limit),
-                        returned_cols: Some(returned_cols),
-                        default_row: default_row_for_select(&st),
-                        aggregates,
-                    },
-                ), &[leaf_project_reorder_node]);
+                    MirNode::new(
+                        query_name.clone(),
+                        MirNodeInner::Leaf {
+                            keys: view_key
+                                .columns
+                                .into_iter()
+                                .map(|(col, placeholder)| (col, placeholder))
+                                .collect(),
+                            index_type: view_key.index_type,
+                            order_by,
+                            limit: qg.pagination.as_ref().map(|p| p.limit),
+                            returned_cols: Some(returned_cols),
+                            default_row,
+                            aggregates,
+                            constant_cols,
+                        },
+                    ),
+                    &[leaf_project_reorder_node],
+                );
 
                 self.relations.insert(query_name.clone(), leaf_node);
 
@@ -1860,4 +1988,9 @@ impl SqlToMirConverter {
     pub(super) fn upgrade_version(&mut self) {
         self.schema_version += 1;
     }
+
+    /// Returns the current schema version of the MIR nodes.
+    pub(super) fn schema_version(&self) -> usize {
+        self.schema_version
+    }
 }
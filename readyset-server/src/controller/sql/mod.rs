@@ -4,6 +4,7 @@ use std::vec::Vec;
 
 use ::mir::visualize::GraphViz;
 use ::serde::{Deserialize, Serialize};
+use common::IndexType;
 use nom_sql::{
     CompoundSelectOperator, CompoundSelectStatement, CreateTableStatement, CreateViewStatement,
     FieldDefinitionExpr, Relation, SelectSpecification, SelectStatement, SqlIdentifier, TableExpr,
@@ -16,15 +17,19 @@ use readyset_sql_passes::alias_removal::TableAliasRewrite;
 use readyset_sql_passes::{AliasRemoval, Rewrite, RewriteContext};
 use tracing::{debug, trace};
 
+pub use self::identifier_case::IdentifierCase;
+use self::identifier_case::{NormalizeIdentifierCase, SchemaSpellings};
 use self::mir::SqlToMirConverter;
-use self::query_graph::{to_query_graph, QueryGraph};
+use self::query_graph::{to_query_graph, QueryGraph, QueryGraphSet};
 use crate::controller::mir_to_flow::{mir_node_to_flow_parts, mir_query_to_flow_parts};
 use crate::controller::Migration;
 use crate::ReuseConfigType;
 
+mod identifier_case;
 pub(crate) mod mir;
 mod query_graph;
 mod query_signature;
+mod type_check;
 
 /// Configuration for converting SQL to dataflow
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -110,6 +115,10 @@ impl SqlIncorporator {
     /// Rewrite the given SQL statement to normalize, validate, and desugar it, based on the stored
     /// relations in `self`.
     ///
+    /// If identifiers are configured to be [case-insensitive](IdentifierCase::Insensitive), the
+    /// names of tables and columns referenced by the statement are first respelled to match the
+    /// schema, so that the rewrite passes (and everything after them) can resolve them exactly.
+    ///
     /// Can optionally provide a mutable reference to a list of names of non-existent tables which,
     /// if created, should invalidate the query
     // TODO(grfn): This should really be happening as part of the `add_<whatever>` methods (it was,
@@ -124,8 +133,17 @@ impl SqlIncorporator {
         invalidating_tables: Option<&mut Vec<Relation>>,
     ) -> ReadySetResult<S>
     where
-        S: Rewrite,
+        S: Rewrite + NormalizeIdentifierCase,
     {
+        let stmt = match self.mir_converter.config.identifier_case {
+            IdentifierCase::Sensitive => stmt,
+            identifier_case => stmt.normalize_identifier_case(&SchemaSpellings::new(
+                identifier_case,
+                &self.base_schemas,
+                &self.view_schemas,
+            )),
+        };
+
         stmt.rewrite(&mut RewriteContext {
             view_schemas: &self.view_schemas,
             base_schemas: &self.base_schemas,
@@ -143,6 +161,18 @@ impl SqlIncorporator {
         })
     }
 
+    /// Returns a human-readable description of the [query graph](QueryGraph) that would be built
+    /// for the given query, without making any changes to the graph.
+    pub(crate) fn explain_query(
+        &self,
+        stmt: SelectStatement,
+        search_path: &[SqlIdentifier],
+        dialect: Dialect,
+    ) -> ReadySetResult<String> {
+        let stmt = self.rewrite(stmt, search_path, dialect, None)?;
+        Ok(to_query_graph(&stmt)?.describe())
+    }
+
     /// Add a new table, specified by the given `CREATE TABLE` statement, to the graph, using the
     /// given `mig` to track changes.
     pub(crate) fn add_table(
@@ -151,10 +181,17 @@ impl SqlIncorporator {
         mig: &mut Migration<'_>,
     ) -> ReadySetResult<()> {
         let (name, dataflow_idx) = self.add_base_via_mir(statement, mig)?;
+        self.mir_converter.non_replicated_relations.remove(&name);
         self.leaf_addresses.insert(name, dataflow_idx);
         Ok(())
     }
 
+    /// Record that the given table exists in the upstream database but is not being replicated,
+    /// so that queries which reference it can be rejected with a descriptive error
+    pub(crate) fn add_non_replicated_relation(&mut self, name: Relation) {
+        self.mir_converter.non_replicated_relations.insert(name);
+    }
+
     /// Add a new SQL VIEW, specified by the given `CREATE VIEW` statement, to the graph, using the
     /// given `mig` to track changes.
     pub(crate) fn add_view(
@@ -168,7 +205,7 @@ impl SqlIncorporator {
                 self.add_compound_query(name.clone(), query, /* is_leaf = */ true, mig)?
             }
             SelectSpecification::Simple(query) => {
-                self.add_select_query(name.clone(), query, /* is_leaf = */ true, mig)?
+                self.add_select_query(name.clone(), query, /* is_leaf = */ true, None, mig)?
             }
         };
         self.mir_to_dataflow(name, mir_leaf, mig)?;
@@ -184,10 +221,28 @@ impl SqlIncorporator {
         name: Option<Relation>,
         stmt: SelectStatement,
         mig: &mut Migration<'_>,
+    ) -> ReadySetResult<Relation> {
+        self.add_query_with_index_type(name, stmt, None, mig)
+    }
+
+    /// Add a new query to the graph, as with [`add_query`](Self::add_query), but using the given
+    /// `index_type` (if provided) for the query's reader instead of the one that would otherwise
+    /// be inferred from its parameters.
+    pub(crate) fn add_query_with_index_type(
+        &mut self,
+        name: Option<Relation>,
+        stmt: SelectStatement,
+        index_type: Option<IndexType>,
+        mig: &mut Migration<'_>,
     ) -> ReadySetResult<Relation> {
         let name = name.unwrap_or_else(|| format!("q_{}", self.num_queries).into());
-        let mir_query =
-            self.add_select_query(name.clone(), stmt, /* is_leaf = */ true, mig)?;
+        let mir_query = self.add_select_query(
+            name.clone(),
+            stmt,
+            /* is_leaf = */ true,
+            index_type,
+            mig,
+        )?;
 
         let leaf = self.mir_to_dataflow(name.clone(), mir_query, mig)?;
         self.leaf_addresses.insert(name.clone(), leaf);
@@ -324,7 +379,6 @@ impl SqlIncorporator {
     }
 
     /// Retrieves the flow node associated with a given query's leaf view.
-    #[allow(unused)]
     pub(super) fn get_query_address(&self, name: &Relation) -> Option<NodeIndex> {
         match self.leaf_addresses.get(name) {
             None => self.mir_converter.get_leaf(name),
@@ -374,16 +428,29 @@ impl SqlIncorporator {
         is_leaf: bool,
         mig: &mut Migration<'_>,
     ) -> Result<NodeIndex, ReadySetError> {
+        let on_err = |e| ReadySetError::SelectQueryCreationFailed {
+            qname: query_name.to_string(),
+            source: Box::new(e),
+        };
+
+        let qgs = QueryGraphSet::new(&query).map_err(on_err)?;
+        type_check::check_union_types(&qgs, &self.base_schemas).map_err(on_err)?;
+
         let mut subqueries = Vec::new();
         for (_, stmt) in query.selects.into_iter() {
-            let subquery_leaf = self.add_select_query(query_name.clone(), stmt, false, mig)?;
+            let subquery_leaf =
+                self.add_select_query(query_name.clone(), stmt, false, None, mig)?;
             subqueries.push(subquery_leaf);
         }
 
         let mir_leaf = self.mir_converter.compound_query_to_mir(
             &query_name,
             subqueries,
-            CompoundSelectOperator::Union,
+            if qgs.union_all {
+                CompoundSelectOperator::Union
+            } else {
+                CompoundSelectOperator::DistinctUnion
+            },
             &query.order,
             &query.limit,
             &query.offset,
@@ -394,12 +461,16 @@ impl SqlIncorporator {
     }
 
     /// Add a new SelectStatement to the given migration, returning information about the dataflow
-    /// and MIR nodes that were added
+    /// and MIR nodes that were added.
+    ///
+    /// If `index_type` is provided, it's used as the index type for the leaf of the query instead
+    /// of the one inferred from the query's parameters.
     fn add_select_query(
         &mut self,
         query_name: Relation,
         mut stmt: SelectStatement,
         is_leaf: bool,
+        index_type: Option<IndexType>,
         mig: &mut Migration<'_>,
     ) -> Result<NodeIndex, ReadySetError> {
         let on_err = |e| ReadySetError::SelectQueryCreationFailed {
@@ -435,6 +506,7 @@ impl SqlIncorporator {
                         )
                         .map_err(on_err)?,
                         false,
+                        None,
                         mig,
                     )?;
                     anon_queries.insert(to_view, subquery_leaf);
@@ -445,7 +517,7 @@ impl SqlIncorporator {
                     ..
                 } => {
                     let subquery_leaf = self
-                        .add_select_query(query_name.clone(), *for_statement, false, mig)
+                        .add_select_query(query_name.clone(), *for_statement, false, None, mig)
                         .map_err(on_err)?;
                     anon_queries.insert(to_view, subquery_leaf);
                 }
@@ -456,9 +528,10 @@ impl SqlIncorporator {
         trace!(rewritten_query = %stmt);
 
         let qg = to_query_graph(&stmt).map_err(on_err)?;
+        type_check::check_comparison_types(&qg, &self.base_schemas).map_err(on_err)?;
         let mir_leaf = self
             .mir_converter
-            .named_query_to_mir(&query_name, stmt, &qg, anon_queries, is_leaf)
+            .named_query_to_mir(&query_name, stmt, &qg, anon_queries, is_leaf, index_type)
             .map_err(on_err)?;
 
         Ok(mir_leaf)
@@ -511,12 +584,22 @@ impl SqlIncorporator {
     pub(super) fn upgrade_version(&mut self) {
         self.mir_converter.upgrade_version();
     }
+
+    /// Returns the current schema version of the
+    /// internal [`SqlToMirConverter`].
+    pub(crate) fn schema_version(&self) -> usize {
+        self.mir_converter.schema_version()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use common::IndexType;
     use dataflow::prelude::*;
-    use nom_sql::{parse_create_table, parse_select_statement, Column, Dialect, Relation};
+    use nom_sql::{
+        parse_create_table, parse_query, parse_select_statement, CacheInner, Column, Dialect,
+        Relation, SqlQuery,
+    };
     use readyset_data::{Collation, DfType, Dialect as DataDialect};
 
     use super::SqlIncorporator;
@@ -818,6 +901,71 @@ mod tests {
         .await;
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn index_type_annotation_overrides_inferred_index_type() {
+        let mut g = integration_utils::start_simple_unsharded(
+            "index_type_annotation_overrides_inferred_index_type",
+        )
+        .await;
+        g.migrate(|mig| {
+            let mut inc = SqlIncorporator::default();
+            inc.add_table(
+                inc.rewrite(
+                    parse_create_table(
+                        Dialect::MySQL,
+                        "CREATE TABLE users (id int, name varchar(40));",
+                    )
+                    .unwrap(),
+                    &[],
+                    DataDialect::DEFAULT_MYSQL,
+                    None,
+                )
+                .unwrap(),
+                mig,
+            )
+            .unwrap();
+
+            let mut add_cache = |query: &str| {
+                let ccqs = match parse_query(Dialect::MySQL, query).unwrap() {
+                    SqlQuery::CreateCache(ccqs) => ccqs,
+                    q => panic!("Unexpected query: {q}"),
+                };
+                let stmt = match ccqs.inner {
+                    CacheInner::Statement(stmt) => *stmt,
+                    CacheInner::Id(id) => panic!("Unexpected query id: {id}"),
+                };
+                inc.add_query_with_index_type(
+                    ccqs.name,
+                    inc.rewrite(stmt, &[], DataDialect::DEFAULT_MYSQL, None)
+                        .unwrap(),
+                    ccqs.index_type.map(IndexType::from),
+                    mig,
+                )
+                .unwrap()
+            };
+
+            // Equality-only parameters get a HashMap index by default...
+            let inferred =
+                add_cache("CREATE CACHE q1 FROM SELECT id, name FROM users WHERE id = ?");
+            // ...but the annotation forces a BTreeMap index
+            let forced = add_cache(
+                "CREATE CACHE q2 FROM SELECT /* readyset: index=btree */ name, id FROM users \
+                 WHERE id = ?",
+            );
+
+            let index_type = |name| {
+                get_reader(&inc, mig, &name)
+                    .as_reader()
+                    .unwrap()
+                    .index_type()
+                    .unwrap()
+            };
+            assert_eq!(index_type(inferred), IndexType::HashMap);
+            assert_eq!(index_type(forced), IndexType::BTreeMap);
+        })
+        .await;
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn it_incorporates_simple_join() {
         // set up graph
@@ -3753,4 +3901,194 @@ mod tests {
         })
         .await;
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn query_over_unreplicated_table() {
+        let mut g =
+            integration_utils::start_simple_unsharded("query_over_unreplicated_table").await;
+        g.migrate(|mig| {
+            let mut inc = SqlIncorporator::default();
+            inc.add_table(
+                inc.rewrite(
+                    parse_create_table(Dialect::MySQL, "CREATE TABLE users (id int, name text);")
+                        .unwrap(),
+                    &[],
+                    DataDialect::DEFAULT_MYSQL,
+                    None,
+                )
+                .unwrap(),
+                mig,
+            )
+            .unwrap();
+
+            let query = parse_select_statement(
+                Dialect::MySQL,
+                "SELECT users.name, orders.total FROM users \
+                 JOIN orders ON users.id = orders.user_id",
+            )
+            .unwrap();
+
+            // Tables we've never heard of don't get the hint about replication
+            let err = inc
+                .add_query(
+                    None,
+                    inc.rewrite(query.clone(), &[], DataDialect::DEFAULT_MYSQL, None)
+                        .unwrap(),
+                    mig,
+                )
+                .unwrap_err();
+            assert!(!err.caused_by_table_not_replicated(), "{err}");
+            assert!(!err.to_string().contains("--replication-tables"), "{err}");
+
+            inc.add_non_replicated_relation(Relation {
+                schema: Some("public".into()),
+                name: "orders".into(),
+            });
+            let err = inc
+                .add_query(
+                    None,
+                    inc.rewrite(query, &[], DataDialect::DEFAULT_MYSQL, None)
+                        .unwrap(),
+                    mig,
+                )
+                .unwrap_err();
+            assert!(err.caused_by_table_not_replicated(), "{err}");
+            assert!(err.to_string().contains("orders"), "{err}");
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn case_insensitive_identifiers_resolve_against_schema() {
+        let mut g = integration_utils::start_simple_unsharded(
+            "case_insensitive_identifiers_resolve_against_schema",
+        )
+        .await;
+        g.migrate(|mig| {
+            let mut inc = SqlIncorporator::default();
+            inc.set_mir_config(super::mir::Config {
+                identifier_case: super::IdentifierCase::Insensitive,
+                ..Default::default()
+            });
+            inc.add_table(
+                inc.rewrite(
+                    parse_create_table(Dialect::MySQL, "CREATE TABLE Users (ID int, Name text);")
+                        .unwrap(),
+                    &[],
+                    DataDialect::DEFAULT_MYSQL,
+                    None,
+                )
+                .unwrap(),
+                mig,
+            )
+            .unwrap();
+
+            let query = inc
+                .rewrite(
+                    parse_select_statement(
+                        Dialect::MySQL,
+                        "SELECT users.id FROM users WHERE users.NAME = ?",
+                    )
+                    .unwrap(),
+                    &[],
+                    DataDialect::DEFAULT_MYSQL,
+                    None,
+                )
+                .unwrap();
+            let name = inc.add_query(None, query, mig).unwrap();
+            assert_eq!(inc.get_view_schema(&name).unwrap(), vec!["ID".to_owned()]);
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn join_with_mismatched_types() {
+        let mut g = integration_utils::start_simple_unsharded("join_with_mismatched_types").await;
+        g.migrate(|mig| {
+            let mut inc = SqlIncorporator::default();
+            for table in [
+                "CREATE TABLE users (id int, name text);",
+                "CREATE TABLE orders (user_id bigint, user_name int);",
+            ] {
+                inc.add_table(
+                    inc.rewrite(
+                        parse_create_table(Dialect::MySQL, table).unwrap(),
+                        &[],
+                        DataDialect::DEFAULT_MYSQL,
+                        None,
+                    )
+                    .unwrap(),
+                    mig,
+                )
+                .unwrap();
+            }
+
+            let err = inc
+                .add_query(
+                    None,
+                    inc.rewrite(
+                        parse_select_statement(
+                            Dialect::MySQL,
+                            "SELECT users.id FROM users \
+                             JOIN orders ON users.name = orders.user_name",
+                        )
+                        .unwrap(),
+                        &[],
+                        DataDialect::DEFAULT_MYSQL,
+                        None,
+                    )
+                    .unwrap(),
+                    mig,
+                )
+                .unwrap_err();
+            let msg = err.to_string();
+            assert!(msg.contains("without an explicit cast"), "{msg}");
+            assert!(msg.contains("`name`"), "{msg}");
+            assert!(msg.contains("TEXT"), "{msg}");
+            assert!(msg.contains("`user_name`"), "{msg}");
+            assert!(msg.contains("INT"), "{msg}");
+
+            // Equality comparisons in global predicates are checked too
+            let err = inc
+                .add_query(
+                    None,
+                    inc.rewrite(
+                        parse_select_statement(
+                            Dialect::MySQL,
+                            "SELECT users.id FROM users \
+                             JOIN orders ON users.id = orders.user_id \
+                             WHERE users.name = orders.user_name OR users.id = 1",
+                        )
+                        .unwrap(),
+                        &[],
+                        DataDialect::DEFAULT_MYSQL,
+                        None,
+                    )
+                    .unwrap(),
+                    mig,
+                )
+                .unwrap_err();
+            let msg = err.to_string();
+            assert!(msg.contains("without an explicit cast"), "{msg}");
+
+            // Joining columns of different but compatible types is fine
+            inc.add_query(
+                None,
+                inc.rewrite(
+                    parse_select_statement(
+                        Dialect::MySQL,
+                        "SELECT users.name FROM users JOIN orders ON users.id = orders.user_id",
+                    )
+                    .unwrap(),
+                    &[],
+                    DataDialect::DEFAULT_MYSQL,
+                    None,
+                )
+                .unwrap(),
+                mig,
+            )
+            .unwrap();
+        })
+        .await;
+    }
 }
@@ -10,16 +10,17 @@ use common::IndexType;
 use nom_sql::analysis::visit_mut::{walk_expr, VisitorMut};
 use nom_sql::analysis::ReferredColumns;
 use nom_sql::{
-    BinaryOperator, Column, Expr, FieldDefinitionExpr, FieldReference, FunctionExpr, InValue,
-    ItemPlaceholder, JoinConstraint, JoinOperator, JoinRightSide, Literal, OrderType, Relation,
-    SelectStatement, SqlIdentifier, UnaryOperator,
+    BinaryOperator, Column, CompoundSelectOperator, CompoundSelectStatement, Expr,
+    FieldDefinitionExpr, FieldReference, FunctionExpr, InValue, ItemPlaceholder, JoinConstraint,
+    JoinOperator, JoinRightSide, Literal, OrderType, Relation, SelectStatement, SqlIdentifier,
+    UnaryOperator,
 };
 use readyset::{PlaceholderIdx, ViewPlaceholder};
 use readyset_errors::{
     internal, invalid_err, invariant, invariant_eq, no_table_for_col, unsupported, unsupported_err,
     ReadySetResult,
 };
-use readyset_sql_passes::{is_aggregate, is_predicate, map_aggregates, LogicalOp};
+use readyset_sql_passes::{is_aggregate, is_correlated, is_predicate, map_aggregates, LogicalOp};
 use serde::{Deserialize, Serialize};
 
 use super::mir::{self, PAGE_NUMBER_COL};
@@ -166,11 +167,37 @@ pub struct JoinRef {
     pub dst: Relation,
 }
 
-/// An equality predicate on two expressions, used as the key for a join
+/// A comparison predicate on two expressions, used as the key for a join
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct JoinPredicate {
     pub left: Expr,
     pub right: Expr,
+    /// The comparison between `left` and `right`. Equi-joins (on [`BinaryOperator::Equal`]) can
+    /// be executed as hash joins, but any other comparison requires a nested-loop join.
+    pub op: BinaryOperator,
+}
+
+impl JoinPredicate {
+    /// Construct a new equality join predicate between `left` and `right`
+    pub fn equal(left: Expr, right: Expr) -> Self {
+        Self {
+            left,
+            right,
+            op: BinaryOperator::Equal,
+        }
+    }
+
+    /// Returns true if this is an equi-join predicate
+    pub fn is_equi(&self) -> bool {
+        self.op == BinaryOperator::Equal
+    }
+
+    /// Swap the left and right sides of this predicate, flipping the comparison operator so that
+    /// the predicate keeps the same meaning
+    fn swap_sides(&mut self) {
+        mem::swap(&mut self.left, &mut self.right);
+        self.op = self.op.flip_comparison().unwrap_or_else(|op| op);
+    }
 }
 
 /// An individual column on which a query is parameterized
@@ -193,10 +220,62 @@ pub struct QueryGraphNode {
     pub subgraph: Option<(Box<QueryGraph>, SelectStatement)>,
 }
 
+impl QueryGraphNode {
+    /// Returns true if `self` and `other` describe the same relation, ignoring the order of
+    /// predicates, columns, and parameters. See [`QueryGraph::structurally_equivalent`].
+    fn structurally_equivalent(&self, other: &QueryGraphNode) -> bool {
+        self.relation == other.relation
+            && same_elements(&self.predicates, &other.predicates)
+            && same_elements(&self.columns, &other.columns)
+            && same_elements(&self.parameters, &other.parameters)
+            && match (&self.subgraph, &other.subgraph) {
+                (Some((qg, _)), Some((other_qg, _))) => qg.structurally_equivalent(other_qg),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum QueryGraphEdge {
-    Join { on: Vec<JoinPredicate> },
-    LeftJoin { on: Vec<JoinPredicate> },
+    Join {
+        on: Vec<JoinPredicate>,
+    },
+    LeftJoin {
+        on: Vec<JoinPredicate>,
+    },
+    /// A semi-join against a subquery, from a `<column> IN (<subquery>)` condition in the WHERE
+    /// clause.
+    ///
+    /// The subquery on the right-hand side of the edge projects distinct rows, so the semi-join is
+    /// planned as an inner join against it.
+    SemiJoin {
+        on: Vec<JoinPredicate>,
+    },
+}
+
+impl QueryGraphEdge {
+    /// Returns the predicates this edge joins on
+    pub fn on(&self) -> &[JoinPredicate] {
+        match self {
+            QueryGraphEdge::Join { on }
+            | QueryGraphEdge::LeftJoin { on }
+            | QueryGraphEdge::SemiJoin { on } => on,
+        }
+    }
+
+    /// Returns true if `self` and `other` are the same kind of join on the same set of predicates,
+    /// in any order
+    fn structurally_equivalent(&self, other: &QueryGraphEdge) -> bool {
+        match (self, other) {
+            (QueryGraphEdge::Join { on }, QueryGraphEdge::Join { on: other_on })
+            | (QueryGraphEdge::LeftJoin { on }, QueryGraphEdge::LeftJoin { on: other_on })
+            | (QueryGraphEdge::SemiJoin { on }, QueryGraphEdge::SemiJoin { on: other_on }) => {
+                same_elements(on, other_on)
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
@@ -233,11 +312,28 @@ pub struct QueryGraph {
     /// If a single aggregate is projected as multiple aliases, only one will appear in this map,
     /// but both will appear in `self.columns` as [`OutputColumn::Data`] referencing that alias
     pub aggregates: HashMap<FunctionExpr, SqlIdentifier>,
+    /// Expressions (other than bare column references) used as the arguments to aggregates, such
+    /// as `price * qty` in `SUM(price * qty)`.
+    ///
+    /// These are projected as computed columns before the aggregates themselves, so that each
+    /// aggregate can consume its argument as a column. They don't appear in `self.columns`, since
+    /// they aren't part of the result set of the query.
+    #[serde(default)]
+    pub aggregate_arguments: Vec<ExprColumn>,
     /// Set of columns that appear in the GROUP BY clause
     pub group_by: HashSet<Column>,
     /// Final set of projected columns in this query; may include literals in addition to the
     /// columns reflected in individual relations' `QueryGraphNode` structures.
+    ///
+    /// The columns selected by the query come first, in the order they appear in the `SELECT`
+    /// list, followed by the last [`hidden_columns`][Self::hidden_columns] columns.
     pub columns: Vec<OutputColumn>,
+    /// The number of columns at the end of [`columns`][Self::columns] which aren't selected by the
+    /// query, but are projected so that the results can be ordered by them (such as a column that
+    /// appears in the `ORDER BY` clause but not in the `SELECT` list). These aren't part of the
+    /// result set of the query. See [`QueryGraph::output_columns`].
+    #[serde(default)]
+    pub hidden_columns: usize,
     /// Establishes an order for join predicates. Each join predicate can be identified by
     /// its (src, dst) pair
     pub join_order: Vec<JoinRef>,
@@ -247,6 +343,18 @@ pub struct QueryGraph {
     pub having_predicates: Vec<Expr>,
     /// The pagination (order, limit, offset) for the query, if any
     pub pagination: Option<Pagination>,
+    /// True if the WHERE clause of the query contains a conjunct that is always false, meaning the
+    /// query can never return any rows
+    #[serde(default)]
+    pub always_empty: bool,
+    /// True if the query was a `SELECT DISTINCT`.
+    ///
+    /// This doesn't change the shape of the rest of the graph; instead, when lowering to MIR a
+    /// distinct node grouping by all the projected columns is added on top of the final projection
+    /// (or, for queries which need post-lookup aggregation, the reader groups by all projected
+    /// columns).
+    #[serde(default)]
+    pub distinct: bool,
 }
 
 impl QueryGraph {
@@ -264,6 +372,194 @@ impl QueryGraph {
             .collect()
     }
 
+    /// Returns the columns in the result set of this query, in the order they appear in the
+    /// `SELECT` list - omitting any [hidden columns][Self::hidden_columns] that are only projected
+    /// internally.
+    pub fn output_columns(&self) -> &[OutputColumn] {
+        &self.columns[..self.columns.len().saturating_sub(self.hidden_columns)]
+    }
+
+    /// Returns a compact, human-readable description of this query graph, listing its relations
+    /// (with their parameters and predicates), joins, projected columns, grouping, aggregates,
+    /// pagination and distinctness, one item per line.
+    ///
+    /// This is intended for debugging and for tests; unlike the [`Debug`] impl, the output is
+    /// deterministic and omits empty sections.
+    pub fn describe(&self) -> String {
+        fn list<T: ToString>(items: impl IntoIterator<Item = T>) -> String {
+            items
+                .into_iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+
+        fn describe_join(on: &[JoinPredicate]) -> String {
+            list(
+                on.iter()
+                    .map(|p| format!("{} {} {}", p.left, p.op, p.right)),
+            )
+        }
+
+        let mut lines = Vec::new();
+
+        let mut relations = self.relations.values().collect::<Vec<_>>();
+        relations.sort_by(|a, b| a.relation.cmp(&b.relation));
+        lines.push("relations:".to_owned());
+        for rel in relations {
+            let mut line = format!("  {}", rel.relation);
+            if rel.subgraph.is_some() {
+                line.push_str(" (subquery)");
+            }
+            if !rel.parameters.is_empty() {
+                line.push_str(&format!(
+                    " params: [{}]",
+                    list(rel.parameters.iter().map(|p| format!(
+                        "{} {} ?{}",
+                        p.col,
+                        p.op,
+                        p.placeholder_idx.map(|i| i.to_string()).unwrap_or_default()
+                    )))
+                ));
+            }
+            if !rel.predicates.is_empty() {
+                line.push_str(&format!(" predicates: [{}]", list(&rel.predicates)));
+            }
+            lines.push(line);
+        }
+
+        if !self.join_order.is_empty() {
+            lines.push("joins:".to_owned());
+            for JoinRef { src, dst } in &self.join_order {
+                let (kind, on) = match self.edges.get(&(src.clone(), dst.clone())) {
+                    Some(QueryGraphEdge::Join { on }) => ("JOIN", on.as_slice()),
+                    Some(QueryGraphEdge::LeftJoin { on }) => ("LEFT JOIN", on.as_slice()),
+                    Some(QueryGraphEdge::SemiJoin { on }) => ("SEMI JOIN", on.as_slice()),
+                    None => ("JOIN", [].as_slice()),
+                };
+                lines.push(format!("  {src} {kind} {dst} ON {}", describe_join(on)));
+            }
+        }
+
+        if !self.global_predicates.is_empty() {
+            lines.push(format!(
+                "global predicates: {}",
+                list(&self.global_predicates)
+            ));
+        }
+
+        let num_output_columns = self.output_columns().len();
+        lines.push(format!(
+            "columns: {}",
+            list(self.columns.iter().enumerate().map(|(i, c)| {
+                let column = match c {
+                    OutputColumn::Data { alias, column } => format!("{column} AS `{alias}`"),
+                    OutputColumn::Literal(lit) => format!("{} AS `{}`", lit.value, lit.name),
+                    OutputColumn::Expr(expr) => format!("{} AS `{}`", expr.expression, expr.name),
+                };
+                if i < num_output_columns {
+                    column
+                } else {
+                    format!("{column} (hidden)")
+                }
+            }))
+        ));
+
+        if !self.group_by.is_empty() {
+            let mut group_by = self.group_by.iter().collect::<Vec<_>>();
+            group_by.sort();
+            lines.push(format!("group by: {}", list(group_by)));
+        }
+
+        if !self.aggregates.is_empty() {
+            let mut aggregates = self
+                .aggregates
+                .iter()
+                .map(|(f, alias)| format!("{f} AS `{alias}`"))
+                .collect::<Vec<_>>();
+            aggregates.sort();
+            lines.push(format!("aggregates: {}", list(aggregates)));
+        }
+
+        if !self.aggregate_arguments.is_empty() {
+            lines.push(format!(
+                "aggregate arguments: {}",
+                list(
+                    self.aggregate_arguments
+                        .iter()
+                        .map(|ec| format!("{} AS `{}`", ec.expression, ec.name))
+                )
+            ));
+        }
+
+        if !self.having_predicates.is_empty() {
+            lines.push(format!("having: {}", list(&self.having_predicates)));
+        }
+
+        if let Some(pagination) = &self.pagination {
+            let mut line = "pagination:".to_owned();
+            if let Some(order) = &pagination.order {
+                line.push_str(&format!(
+                    " ORDER BY {}",
+                    list(order.iter().map(|(expr, ord)| format!("{expr} {ord}")))
+                ));
+            }
+            line.push_str(&format!(" LIMIT {}", pagination.limit));
+            if pagination.offset.is_some() {
+                line.push_str(" OFFSET ?");
+            }
+            lines.push(line);
+        }
+
+        if self.always_empty {
+            lines.push("always empty".to_owned());
+        }
+
+        if self.distinct {
+            lines.push("distinct".to_owned());
+        }
+
+        lines.join("\n")
+    }
+
+    /// Returns true if `self` and `other` are structurally equivalent - that is, if they have the
+    /// same relations, edges, aggregates, grouping, projected columns, join order, predicates,
+    /// pagination and distinctness, up to the order of elements whose order doesn't affect the
+    /// query's semantics (such as the conjuncts of a `WHERE` clause).
+    ///
+    /// This is a deterministic alternative to comparing the [`Hash`] of two query graphs, which
+    /// doesn't suffer from the possibility of hash collisions. Unlike the [`PartialEq`] impl,
+    /// predicates written in a different order are considered equivalent.
+    pub fn structurally_equivalent(&self, other: &QueryGraph) -> bool {
+        self.relations.len() == other.relations.len()
+            && self.relations.iter().all(|(rel, node)| {
+                other
+                    .relations
+                    .get(rel)
+                    .map_or(false, |other_node| node.structurally_equivalent(other_node))
+            })
+            && self.edges.len() == other.edges.len()
+            && self.edges.iter().all(|(rels, edge)| {
+                other
+                    .edges
+                    .get(rels)
+                    .map_or(false, |other_edge| edge.structurally_equivalent(other_edge))
+            })
+            && self.aggregates == other.aggregates
+            && self.aggregate_arguments == other.aggregate_arguments
+            && self.group_by == other.group_by
+            // The projected columns and join order are semantically meaningful, so they have to
+            // match exactly
+            && self.columns == other.columns
+            && self.hidden_columns == other.hidden_columns
+            && self.join_order == other.join_order
+            && same_elements(&self.global_predicates, &other.global_predicates)
+            && same_elements(&self.having_predicates, &other.having_predicates)
+            && self.pagination == other.pagination
+            && self.always_empty == other.always_empty
+            && self.distinct == other.distinct
+    }
+
     /// Construct a representation of the lookup key of a view for this query graph, based on the
     /// parameters in this query and the page number if this query is parametrized on an offset key.
     pub(crate) fn view_key(&self, config: &mir::Config) -> ReadySetResult<ViewKey> {
@@ -356,6 +652,17 @@ impl QueryGraph {
                 last_op = Some(param.op);
             }
 
+            if let Some(max) = config.max_view_key_columns {
+                if columns.len() > max {
+                    unsupported!(
+                        "Query is parametrized on {} columns, but at most {} key columns are \
+                         allowed",
+                        columns.len(),
+                        max
+                    );
+                }
+            }
+
             if let Some(offset) = offset {
                 if index_type == Some(IndexType::BTreeMap) {
                     unsupported!("ReadySet does not support Pagination and range queries")
@@ -394,14 +701,31 @@ impl Hash for QueryGraph {
         let mut aggregates = self.aggregates.iter().collect::<Vec<_>>();
         aggregates.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
         aggregates.hash(state);
+        self.aggregate_arguments.hash(state);
 
         // columns and join_order are Vecs, so already ordered
         self.columns.hash(state);
+        self.hidden_columns.hash(state);
         self.join_order.hash(state);
         self.global_predicates.hash(state);
         self.having_predicates.hash(state);
         self.pagination.hash(state);
+        self.always_empty.hash(state);
+        self.distinct.hash(state);
+    }
+}
+
+/// Returns true if `a` and `b` contain the same elements the same number of times, in any order
+fn same_elements<T: Hash + Eq>(a: &[T], b: &[T]) -> bool {
+    fn counts<T: Hash + Eq>(xs: &[T]) -> HashMap<&T, usize> {
+        let mut counts = HashMap::new();
+        for x in xs {
+            *counts.entry(x).or_insert(0) += 1;
+        }
+        counts
     }
+
+    a.len() == b.len() && counts(a) == counts(b)
 }
 
 /// Splits top level conjunctions into multiple predicates
@@ -429,17 +753,53 @@ where
     new_ces
 }
 
+/// Returns the truth value of the given literal when used as a condition, or `None` if it can't be
+/// determined statically
+fn literal_truthiness(lit: &Literal) -> Option<bool> {
+    match lit {
+        // A NULL condition never matches any rows
+        Literal::Null => Some(false),
+        Literal::Boolean(b) => Some(*b),
+        Literal::Integer(i) => Some(*i != 0),
+        Literal::UnsignedInteger(i) => Some(*i != 0),
+        _ => None,
+    }
+}
+
+/// Removes any conjuncts which are (constant-folded) literals that are always true from the given
+/// list of conjuncts.
+///
+/// Returns `false` if any of the conjuncts is a literal that is always false, meaning the whole
+/// conjunction is always false.
+fn remove_constant_conjuncts(conjuncts: &mut Vec<Expr>) -> bool {
+    let mut always_false = false;
+    conjuncts.retain(|ce| match ce {
+        Expr::Literal(lit) => match literal_truthiness(lit) {
+            Some(true) => false,
+            Some(false) => {
+                always_false = true;
+                false
+            }
+            None => true,
+        },
+        _ => true,
+    });
+    !always_false
+}
+
 // 1. Extract any predicates with placeholder parameters. We push these down to the edge
 //    nodes, since we cannot instantiate the parameters inside the data flow graph (except for
 //    non-materialized nodes).
 // 2. Extract local predicates
 // 3. Extract join predicates
-// 4. Collect remaining predicates as global predicates
+// 4. Extract `IN` conditions against subqueries, which become semi-joins
+// 5. Collect remaining predicates as global predicates
 fn classify_conditionals(
     ce: &Expr,
     inner_join_rels: &HashSet<Relation>,
     local: &mut HashMap<Relation, Vec<Expr>>,
     join: &mut Vec<JoinPredicate>,
+    semi_joins: &mut Vec<InSubquery>,
     global: &mut Vec<Expr>,
     params: &mut Vec<Parameter>,
 ) -> ReadySetResult<()> {
@@ -448,9 +808,9 @@ fn classify_conditionals(
     //       because these expressions are meaningless in the Soup context.
     //    b) we don't support OR expressions with join predicates because they are weird and
     //       too hard.
-    //    c) we don't support OR expressions between different tables (e.g table1.x = 1 OR
-    //       table2.y= 42). this is a global predicate according to finkelstein algorithm
-    //       and we don't support these yet.
+    //    c) OR expressions between different tables (e.g table1.x = 1 OR table2.y= 42) are
+    //       global predicates according to the finkelstein algorithm, and are evaluated as a filter
+    //       directly after the join which brings together all the tables they reference
 
     match ce {
         Expr::BinaryOp { op, lhs, rhs } => {
@@ -464,6 +824,7 @@ fn classify_conditionals(
                 //     remain a local predicate) or over several (so it must be a global predicate)
                 let mut new_params = Vec::new();
                 let mut new_join = Vec::new();
+                let mut new_semi_joins = Vec::new();
                 let mut new_local = HashMap::new();
                 let mut new_global = Vec::new();
 
@@ -472,6 +833,7 @@ fn classify_conditionals(
                     inner_join_rels,
                     &mut new_local,
                     &mut new_join,
+                    &mut new_semi_joins,
                     &mut new_global,
                     &mut new_params,
                 )?;
@@ -480,6 +842,7 @@ fn classify_conditionals(
                     inner_join_rels,
                     &mut new_local,
                     &mut new_join,
+                    &mut new_semi_joins,
                     &mut new_global,
                     &mut new_params,
                 )?;
@@ -517,6 +880,11 @@ fn classify_conditionals(
                         if !new_join.is_empty() {
                             unsupported!("can't handle OR expressions between JOIN predicates")
                         }
+                        if !new_semi_joins.is_empty() {
+                            unsupported!(
+                                "can't handle OR expressions with IN conditions against subqueries"
+                            )
+                        }
                         if !new_params.is_empty() {
                             unsupported!(
                                 "can't handle OR expressions between query parameter predicates"
@@ -547,6 +915,7 @@ fn classify_conditionals(
                 }
 
                 join.extend(new_join);
+                semi_joins.extend(new_semi_joins);
                 params.extend(new_params);
             } else if is_predicate(op) {
                 // atomic selection predicate
@@ -567,18 +936,18 @@ fn classify_conditionals(
                                 // different --> inner join predicate appearing in the WHERE clause
                                 if *op == BinaryOperator::Equal {
                                     // equi-join between two tables
-                                    let mut jp = JoinPredicate {
-                                        left: (**lhs).clone(),
-                                        right: (**rhs).clone(),
-                                    };
+                                    let mut jp =
+                                        JoinPredicate::equal((**lhs).clone(), (**rhs).clone());
                                     if let Ordering::Less =
                                         rf.table.as_ref().cmp(&lf.table.as_ref())
                                     {
-                                        mem::swap(&mut jp.left, &mut jp.right);
+                                        jp.swap_sides();
                                     }
                                     join.push(jp);
                                 } else {
-                                    // non-equi-join?
+                                    // non-equi-join. Tables compared this way in the WHERE clause
+                                    // are cross-joined, so this can be evaluated as a filter
+                                    // after that join
                                     global.push(ce.clone());
                                 }
                             }
@@ -671,6 +1040,29 @@ fn classify_conditionals(
                 }
             }
         }
+        Expr::In {
+            lhs,
+            rhs: InValue::Subquery(subquery),
+            negated,
+        } => {
+            if *negated {
+                unsupported!("NOT IN conditions against subqueries are not supported");
+            }
+            let column = match lhs.as_ref() {
+                Expr::Column(column) if column.table.is_some() => column.clone(),
+                _ => unsupported!(
+                    "Only columns are supported on the left-hand side of IN conditions against \
+                     subqueries (got {lhs})"
+                ),
+            };
+            if is_correlated(subquery) {
+                unsupported!("Correlated subqueries in IN conditions are not supported");
+            }
+            semi_joins.push(InSubquery {
+                column,
+                subquery: (**subquery).clone(),
+            });
+        }
         Expr::UnaryOp {
             op: UnaryOperator::Not,
             ..
@@ -682,8 +1074,44 @@ fn classify_conditionals(
             // local predicate in disguise
             global.push(ce.clone())
         }
-        Expr::Between { .. } => {
-            internal!("Between should have been removed earlier")
+        Expr::Between {
+            operand,
+            min,
+            max,
+            negated,
+        } => {
+            // Split the BETWEEN into its two comparisons and classify those instead, so that a
+            // BETWEEN with placeholder bounds becomes a pair of `>=` and `<=` parameters on the
+            // same column (which `view_key` maps to a single range lookup), and a BETWEEN with
+            // one literal bound and one placeholder bound becomes a local predicate plus a
+            // parameter.
+            let (lower_op, upper_op, conjunction) = if *negated {
+                (
+                    BinaryOperator::Less,
+                    BinaryOperator::Greater,
+                    BinaryOperator::Or,
+                )
+            } else {
+                (
+                    BinaryOperator::GreaterOrEqual,
+                    BinaryOperator::LessOrEqual,
+                    BinaryOperator::And,
+                )
+            };
+            let split = Expr::BinaryOp {
+                lhs: Box::new(Expr::BinaryOp {
+                    lhs: operand.clone(),
+                    op: lower_op,
+                    rhs: min.clone(),
+                }),
+                op: conjunction,
+                rhs: Box::new(Expr::BinaryOp {
+                    lhs: operand.clone(),
+                    op: upper_op,
+                    rhs: max.clone(),
+                }),
+            };
+            classify_conditionals(&split, inner_join_rels, local, join, global, params)?;
         }
         expr => {
             // don't expect to see a base here: we ought to exit when classifying its
@@ -697,18 +1125,26 @@ fn classify_conditionals(
     Ok(())
 }
 
+/// An `<column> IN (<subquery>)` condition in the WHERE clause of a query, which is represented in
+/// the query graph as a [semi-join](QueryGraphEdge::SemiJoin) from the relation of `column` to the
+/// subquery
+#[derive(Debug)]
+struct InSubquery {
+    /// The column in the outer query compared against the results of the subquery
+    column: Column,
+    /// The (uncorrelated) subquery itself
+    subquery: SelectStatement,
+}
+
 /// Convert the given `Expr`, which should be a set of AND-ed together direct
 /// comparison predicates, into a list of predicate expressions
 fn collect_join_predicates(cond: Expr, out: &mut Vec<JoinPredicate>) -> ReadySetResult<()> {
     match cond {
-        Expr::BinaryOp {
-            op: BinaryOperator::Equal,
-            lhs,
-            rhs,
-        } => {
+        Expr::BinaryOp { op, lhs, rhs } if op == BinaryOperator::Equal || op.is_comparison() => {
             out.push(JoinPredicate {
                 left: *lhs,
                 right: *rhs,
+                op,
             });
             Ok(())
         }
@@ -781,6 +1217,55 @@ fn extract_having_aggregates(
     having_predicates
 }
 
+/// Register `expr` as a computed column in the output of `qg`, returning the expression that
+/// evaluates to that column's value once all aggregates in the query have been computed.
+///
+/// Any aggregates within `expr` are added to `qg.aggregates` and replaced by references to their
+/// result columns. If `qg` already projects the same expression, no new column is added.
+fn add_computed_column(qg: &mut QueryGraph, expr: &Expr) -> Expr {
+    let name: SqlIdentifier = expr.to_string().into();
+    let mut expression = expr.clone();
+    for (agg, agg_name) in map_aggregates(&mut expression) {
+        qg.aggregates.entry(agg).or_insert(agg_name);
+    }
+
+    if !qg
+        .columns
+        .iter()
+        .any(|oc| matches!(oc, OutputColumn::Expr(ec) if ec.expression == expression))
+    {
+        qg.columns.push(OutputColumn::Expr(ExprColumn {
+            name,
+            table: None,
+            expression: expression.clone(),
+        }));
+    }
+
+    expression
+}
+
+/// Returns computed columns for each distinct expression (other than a bare column reference)
+/// which is used as an argument to one of the given aggregates, named after the expression itself.
+///
+/// The columns are sorted by name, so that the result doesn't depend on the iteration order of
+/// `aggregates`.
+fn aggregate_arguments(aggregates: &HashMap<FunctionExpr, SqlIdentifier>) -> Vec<ExprColumn> {
+    let mut columns = aggregates
+        .keys()
+        .filter(|f| is_aggregate(f))
+        .flat_map(|f| f.arguments())
+        .filter(|arg| !matches!(arg, Expr::Column(_)))
+        .map(|expr| ExprColumn {
+            name: expr.to_string().into(),
+            table: None,
+            expression: expr.clone(),
+        })
+        .collect::<Vec<_>>();
+    columns.sort_by(|a, b| a.name.cmp(&b.name));
+    columns.dedup();
+    columns
+}
+
 /// Convert limit and offset fields to an optional constant numeric limit and optional placeholder
 /// for the offset
 pub(crate) fn extract_limit_offset(
@@ -900,11 +1385,17 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
         inner_join_rels.insert(rel);
     }
     for jc in &st.join {
+        // Everything to the left of a RIGHT JOIN is on its nullable side, while the relation on its
+        // right is preserved just like the relations in an inner join
+        let right_is_inner = jc.operator.is_inner_join() || jc.operator == JoinOperator::RightJoin;
+        if jc.operator == JoinOperator::RightJoin {
+            inner_join_rels.clear();
+        }
         match &jc.right {
             JoinRightSide::Table(table_expr) => {
                 if !qg.relations.contains_key(&table_expr.table) {
                     let name = table_expr.table.clone();
-                    if jc.operator.is_inner_join() {
+                    if right_is_inner {
                         inner_join_rels.insert(name.clone());
                     }
                     qg.relations
@@ -913,7 +1404,7 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
             }
             JoinRightSide::NestedSelect(subquery, alias) => {
                 let rel: Relation = alias.clone().into();
-                if jc.operator.is_inner_join() {
+                if right_is_inner {
                     inner_join_rels.insert(rel.clone());
                 }
                 if let Entry::Vacant(e) = qg.relations.entry(rel.clone()) {
@@ -937,9 +1428,16 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
     };
 
     // 2a. Explicit joins
-    // The table specified in the query is available for USING joins.
+    // The table specified in the query is available for joins with no join condition.
     // TODO(DAN): why is prev_table tables.last()?
     let prev_table = st.tables.last().cloned();
+    // The joins in the order they're written in the query, used as the join order if the query
+    // asks for it with STRAIGHT_JOIN
+    let mut textual_join_order = Vec::new();
+    let straight_join = st
+        .join
+        .iter()
+        .any(|jc| jc.operator == JoinOperator::StraightJoin);
     for jc in &st.join {
         let rhs_relation = match &jc.right {
             JoinRightSide::Table(table) => table.table.clone(),
@@ -950,7 +1448,7 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
         let left_table;
         let right_table;
 
-        let join_preds = match &jc.constraint {
+        let mut join_preds = match &jc.constraint {
             JoinConstraint::On(cond) => {
                 use nom_sql::analysis::ReferredTables;
 
@@ -998,26 +1496,17 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
                     if *l.table.as_ref().ok_or_else(|| no_table_for_col())? == right_table
                         && *r.table.as_ref().ok_or_else(|| no_table_for_col())? == left_table
                     {
-                        mem::swap(&mut pred.left, &mut pred.right);
+                        pred.swap_sides();
                     }
                 }
 
                 join_preds
             }
-            JoinConstraint::Using(cols) => {
-                invariant_eq!(cols.len(), 1);
-                #[allow(clippy::unwrap_used)] // cols.len() == 1
-                let col = cols.iter().next().unwrap();
-
-                // prev_table must exist because we error on st.tables.is_empty()
-                #[allow(clippy::unwrap_used)]
-                left_table = prev_table.as_ref().unwrap().table.clone();
-                right_table = rhs_relation.clone();
-
-                vec![JoinPredicate {
-                    left: col_expr(&left_table, &col.name),
-                    right: col_expr(&right_table, &col.name),
-                }]
+            JoinConstraint::Using(_) => {
+                internal!("USING joins should have been rewritten by resolve_natural_joins")
+            }
+            JoinConstraint::Natural => {
+                internal!("NATURAL joins should have been rewritten by resolve_natural_joins")
             }
             JoinConstraint::Empty => {
                 // prev_table must exist because we error on st.tables.is_empty()
@@ -1029,36 +1518,71 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
             }
         };
 
+        // `a RIGHT JOIN b` is the same as `b LEFT JOIN a`, so swap the tables (and the sides of the
+        // join predicates along with them, so that the left side of each predicate still refers to
+        // the left table) and add a left join edge
+        let (left_table, right_table) = if jc.operator == JoinOperator::RightJoin {
+            join_preds.iter_mut().for_each(JoinPredicate::swap_sides);
+            (right_table, left_table)
+        } else {
+            (left_table, right_table)
+        };
+
         // add edge for join
         // FIXME(eta): inefficient cloning!
         if let std::collections::hash_map::Entry::Vacant(e) =
             qg.edges.entry((left_table.clone(), right_table.clone()))
         {
             e.insert(match jc.operator {
-                JoinOperator::LeftJoin | JoinOperator::LeftOuterJoin => {
+                JoinOperator::LeftJoin | JoinOperator::LeftOuterJoin | JoinOperator::RightJoin => {
                     QueryGraphEdge::LeftJoin { on: join_preds }
                 }
-                JoinOperator::Join | JoinOperator::InnerJoin => {
+                JoinOperator::Join | JoinOperator::InnerJoin | JoinOperator::StraightJoin => {
                     QueryGraphEdge::Join { on: join_preds }
                 }
-                _ => unsupported!("join operator not supported"),
+                op @ JoinOperator::CrossJoin => unsupported!("{op} is not supported"),
+            });
+            textual_join_order.push(JoinRef {
+                src: left_table,
+                dst: right_table,
             });
         }
     }
 
+    // The order of joins between comma-separated tables in the FROM clause (or of repeated joins
+    // between the same pair of tables) isn't written in the query, so it can't be pinned
+    if straight_join && (st.tables.len() > 1 || textual_join_order.len() != st.join.len()) {
+        unsupported!(
+            "STRAIGHT_JOIN is only supported when every table is joined with an explicit JOIN \
+             clause, at most once"
+        );
+    }
+
     if let Some(ref cond) = st.where_clause {
         let mut local_predicates = HashMap::new();
         let mut global_predicates = Vec::new();
         let mut query_parameters = Vec::new();
+        let mut in_subqueries = Vec::new();
+
+        // Conditions which have been constant-folded to a literal either don't filter anything (so
+        // we can drop them) or filter out everything (so we can mark the query as always empty).
+        // We still classify the rest of the predicates in the latter case, so that the query keeps
+        // the same parameters.
+        let mut conjuncts = split_conjunctions(iter::once(cond));
+        qg.always_empty = !remove_constant_conjuncts(&mut conjuncts);
+
         // Let's classify the predicates we have in the query
-        classify_conditionals(
-            cond,
-            &inner_join_rels,
-            &mut local_predicates,
-            &mut join_predicates,
-            &mut global_predicates,
-            &mut query_parameters,
-        )?;
+        for conjunct in &conjuncts {
+            classify_conditionals(
+                conjunct,
+                &inner_join_rels,
+                &mut local_predicates,
+                &mut join_predicates,
+                &mut in_subqueries,
+                &mut global_predicates,
+                &mut query_parameters,
+            )?;
+        }
 
         for (_, ces) in local_predicates.iter_mut() {
             *ces = split_conjunctions(ces.iter());
@@ -1118,7 +1642,47 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
             }
         }
 
-        // 3. Add any columns that are query parameters, and which therefore must appear in the leaf
+        // 3. Add a semi-join for each IN condition against a subquery. The subquery becomes a new
+        //    relation which projects the distinct values of its (single) column, so that an inner
+        //    join against it doesn't duplicate any rows in the outer query
+        for (i, InSubquery { column, subquery }) in in_subqueries.into_iter().enumerate() {
+            let subquery_column: SqlIdentifier = match subquery.fields.as_slice() {
+                [FieldDefinitionExpr::Expr { expr, alias }] => {
+                    alias.clone().unwrap_or_else(|| match expr {
+                        Expr::Column(c) => c.name.clone(),
+                        expr => expr.to_string().into(),
+                    })
+                }
+                _ => unsupported!("Subqueries in IN conditions must project exactly one column"),
+            };
+            let table = column.table.clone().ok_or_else(no_table_for_col)?;
+            if !qg.relations.contains_key(&table) {
+                internal!(
+                    "IN condition on relation {} that is not in query graph",
+                    table
+                );
+            }
+
+            let rel = Relation::from(SqlIdentifier::from(format!("__in_subquery_{i}")));
+            let subquery = SelectStatement {
+                distinct: true,
+                ..subquery
+            };
+            let mut node = new_node(rel.clone(), vec![], st)?;
+            node.subgraph = Some((Box::new(to_query_graph(&subquery)?), subquery));
+            qg.relations.insert(rel.clone(), node);
+            qg.edges.insert(
+                (table, rel.clone()),
+                QueryGraphEdge::SemiJoin {
+                    on: vec![JoinPredicate::equal(
+                        Expr::Column(column),
+                        col_expr(&rel, &subquery_column),
+                    )],
+                },
+            );
+        }
+
+        // 4. Add any columns that are query parameters, and which therefore must appear in the leaf
         //    node for this query. Such columns will be carried all the way through the operators
         //    implementing the query (unlike in a traditional query plan, where the predicates on
         //    parameters might be evaluated sooner).
@@ -1146,8 +1710,13 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
             }
         }
 
-        // 4. Add global predicates
-        qg.global_predicates = global_predicates;
+        // 5. Add global predicates. If the query is always empty, none of them matter, and we
+        //    replace them with a single always-false predicate to filter out all rows
+        qg.global_predicates = if qg.always_empty {
+            vec![Expr::Literal(Literal::Boolean(false))]
+        } else {
+            global_predicates
+        };
     }
 
     // Add HAVING predicates and aggregates. Note that unlike below for selected columns, we don't
@@ -1213,6 +1782,10 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
         }
     }
 
+    // Any columns projected after this point are only needed internally, rather than being part of
+    // the result set
+    let num_selected_columns = qg.columns.len();
+
     if let Some(group_by_clause) = &st.group_by {
         qg.group_by.extend(
             group_by_clause
@@ -1231,17 +1804,24 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
         );
     }
 
+    let mut order_by = vec![];
     if let Some(ref order) = st.order {
         // For each column in the `ORDER BY` clause, check if it needs to be projected
-        order
-            .order_by
-            .iter()
-            .for_each(|(ord_expr, _)| match ord_expr {
-                FieldReference::Expr(Expr::Column(Column { table: None, .. })) => {
+        for (ord_expr, ot) in &order.order_by {
+            let ord_expr = match ord_expr {
+                FieldReference::Numeric(_) => {
+                    internal!("Numeric field references should have been removed")
+                }
+                FieldReference::Expr(expr) => expr,
+            };
+            let ot = ot.unwrap_or(OrderType::OrderAscending);
+            match ord_expr {
+                Expr::Column(Column { table: None, .. }) => {
                     // This is a reference to a projected column, otherwise the table value
                     // would be assigned in the `rewrite_selection` pass
+                    order_by.push((ord_expr.clone(), ot));
                 }
-                FieldReference::Expr(Expr::Column(col @ Column { table: Some(_), .. })) => {
+                Expr::Column(col @ Column { table: Some(_), .. }) => {
                     // This is a reference to a column in a table, we need to project it if it is
                     // not yet projected in order to be able to execute `ORDER
                     // BY` post lookup.
@@ -1256,8 +1836,9 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
                             column: col.clone(),
                         })
                     }
+                    order_by.push((ord_expr.clone(), ot));
                 }
-                FieldReference::Expr(Expr::Call(func)) if is_aggregate(func) => {
+                Expr::Call(func) if is_aggregate(func) => {
                     // This is an aggregate expression that we need to add to the list of
                     // aggregates. We *don't* add it to the list of projected columns here, since
                     // we don't necessarily need it projected in the result set of the query, and
@@ -1266,46 +1847,28 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
                     qg.aggregates
                         .entry(func.clone())
                         .or_insert_with(|| func.to_string().into());
+                    order_by.push((ord_expr.clone(), ot));
                 }
-                FieldReference::Expr(expr) => {
-                    // This is an expression that we need to add to the list of projected columns
-                    qg.columns.push(OutputColumn::Expr(ExprColumn {
-                        name: expr.to_string().into(),
-                        table: None,
-                        expression: expr.clone(),
-                    }));
+                expr => {
+                    // This is an arbitrary expression (such as `a + b`), which we project as a
+                    // computed column. Pagination then orders by the same expression the computed
+                    // column evaluates, with any aggregates already replaced by their results.
+                    order_by.push((add_computed_column(&mut qg, expr), ot));
                 }
-                // Numeric field references have already been projected, by definition
-                FieldReference::Numeric(_) => {}
-            })
+            }
+        }
     }
 
+    qg.hidden_columns = qg.columns.len() - num_selected_columns;
+
+    // Now that all the aggregates in the query have been found, register the expressions they're
+    // computed over as the columns those aggregates consume
+    qg.aggregate_arguments = aggregate_arguments(&qg.aggregates);
+
     // Extract pagination parameters
     if let Some((limit, offset)) = extract_limit_offset(&st.limit, &st.offset)? {
         qg.pagination = Some(Pagination {
-            order: st
-                .order
-                .as_ref()
-                .map(|o| {
-                    o.order_by
-                        .iter()
-                        .cloned()
-                        .map(|(field, ot)| {
-                            Ok((
-                                match field {
-                                    FieldReference::Numeric(_) => {
-                                        internal!(
-                                            "Numeric field references should have been removed"
-                                        )
-                                    }
-                                    FieldReference::Expr(expr) => expr,
-                                },
-                                ot.unwrap_or(OrderType::OrderAscending),
-                            ))
-                        })
-                        .collect::<ReadySetResult<_>>()
-                })
-                .transpose()?,
+            order: st.order.is_some().then_some(order_by),
             limit,
             offset,
         })
@@ -1313,27 +1876,98 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
 
     // create initial join order
     {
+        // STRAIGHT_JOIN pins the explicit joins to the order they're written in the query. Any
+        // other joins (from the WHERE clause) come after them, in the usual order.
+        if straight_join {
+            qg.join_order = textual_join_order;
+        }
+
         let mut sorted_edges: Vec<(&(Relation, Relation), &QueryGraphEdge)> =
             qg.edges.iter().collect();
         // Sort the edges to ensure deterministic join order.
         sorted_edges.sort_by(|&(a, _), &(b, _)| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
 
-        qg.join_order
-            .extend(sorted_edges.iter().map(|((src, dst), _)| JoinRef {
+        let remaining_edges = sorted_edges
+            .iter()
+            .map(|((src, dst), _)| JoinRef {
                 src: src.clone(),
                 dst: dst.clone(),
-            }));
+            })
+            .filter(|jref| !qg.join_order.contains(jref))
+            .collect::<Vec<_>>();
+        qg.join_order.extend(remaining_edges);
     }
 
+    qg.distinct = st.distinct;
+
     Ok(qg)
 }
 
+/// The query graphs for each of the arms of a compound (`UNION`) query
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryGraphSet {
+    /// One query graph per arm of the compound query, in the order they appear in the query
+    pub graphs: Vec<QueryGraph>,
+    /// True if the arms are combined with `UNION ALL`, meaning duplicate rows are kept. If false,
+    /// the arms are combined with `UNION` (or `UNION DISTINCT`), and duplicate rows must be
+    /// removed from the result.
+    pub union_all: bool,
+}
+
+impl QueryGraphSet {
+    /// Build a [`QueryGraphSet`] for the given compound query, with one query graph per arm.
+    ///
+    /// All the arms of the query must project the same number of columns, and must all be combined
+    /// with the same kind of `UNION`.
+    pub fn new(st: &CompoundSelectStatement) -> ReadySetResult<Self> {
+        let mut union_all = None;
+        for op in st.selects.iter().filter_map(|(op, _)| op.as_ref()) {
+            let all = match op {
+                CompoundSelectOperator::Union => true,
+                CompoundSelectOperator::DistinctUnion => false,
+                CompoundSelectOperator::Intersect | CompoundSelectOperator::Except => {
+                    unsupported!("{op} is not supported")
+                }
+            };
+            if *union_all.get_or_insert(all) != all {
+                unsupported!("Mixing UNION and UNION ALL in the same query is not supported");
+            }
+        }
+
+        let graphs = st
+            .selects
+            .iter()
+            .map(|(_, stmt)| to_query_graph(stmt))
+            .collect::<ReadySetResult<Vec<_>>>()?;
+
+        if let Some((first, rest)) = graphs.split_first() {
+            if let Some(qg) = rest
+                .iter()
+                .find(|qg| qg.output_columns().len() != first.output_columns().len())
+            {
+                return Err(invalid_err!(
+                    "The SELECT statements in a UNION must project the same number of columns \
+                     (found {} and {})",
+                    first.output_columns().len(),
+                    qg.output_columns().len()
+                ));
+            }
+        }
+
+        Ok(QueryGraphSet {
+            graphs,
+            union_all: union_all.unwrap_or(true),
+        })
+    }
+}
+
 #[allow(clippy::unwrap_used)]
 #[allow(clippy::panic)]
 #[cfg(test)]
 mod tests {
     use assert_unordered::assert_eq_unordered;
-    use nom_sql::{parse_query, Dialect, FunctionExpr, SqlQuery};
+    use nom_sql::{parse_expr, parse_query, Dialect, FunctionExpr, SqlQuery, TableExpr};
+    use readyset_sql_passes::ScalarOptimizeExpressions;
 
     use super::*;
 
@@ -1349,6 +1983,21 @@ mod tests {
         to_query_graph(&query).unwrap()
     }
 
+    /// Like [`make_query_graph`], but constant-folds the query's expressions first, as the rewrite
+    /// passes do before a query graph is built for a query
+    fn make_folded_query_graph(sql: &str) -> QueryGraph {
+        let query = match parse_query(Dialect::MySQL, sql).unwrap() {
+            SqlQuery::Select(stmt) => stmt,
+            q => panic!(
+                "Unexpected query type; expected SelectStatement but got {:?}",
+                q
+            ),
+        };
+
+        to_query_graph(&query.scalar_optimize_expressions(readyset_data::Dialect::DEFAULT_MYSQL))
+            .unwrap()
+    }
+
     #[test]
     fn aggregates() {
         let qg = make_query_graph("SELECT max(t1.x) FROM t1 JOIN t2 ON t1.id = t2.id");
@@ -1365,6 +2014,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn aggregate_over_expression() {
+        let qg = make_query_graph("SELECT sum(t.a * t.b) AS total FROM t");
+        let arg = Expr::BinaryOp {
+            lhs: Box::new(Expr::Column("t.a".into())),
+            op: BinaryOperator::Multiply,
+            rhs: Box::new(Expr::Column("t.b".into())),
+        };
+        assert_eq!(
+            qg.aggregate_arguments,
+            vec![ExprColumn {
+                name: arg.to_string().into(),
+                table: None,
+                expression: arg.clone(),
+            }]
+        );
+        assert_eq!(
+            qg.aggregates,
+            HashMap::from([(
+                FunctionExpr::Sum {
+                    expr: Box::new(arg),
+                    distinct: false
+                },
+                "total".into()
+            )])
+        );
+        assert_eq!(
+            qg.columns,
+            vec![OutputColumn::Data {
+                alias: "total".into(),
+                column: Column::from("total"),
+            }]
+        );
+    }
+
     #[test]
     fn aggregates_with_alias() {
         let qg = make_query_graph("SELECT max(t1.x) AS max_x FROM t1 JOIN t2 ON t1.id = t2.id");
@@ -1525,27 +2209,453 @@ mod tests {
         );
     }
 
-    mod view_key {
-        use super::*;
+    #[test]
+    fn order_by_arithmetic_expression() {
+        let qg = make_query_graph("SELECT t.a FROM t ORDER BY t.a + t.b DESC LIMIT 3");
 
-        #[test]
-        fn bogokey_key() {
-            let qg = make_query_graph("SELECT t.x FROM t");
-            let key = qg.view_key(&Default::default()).unwrap();
+        let order_expr = Expr::BinaryOp {
+            lhs: Box::new(Expr::Column("t.a".into())),
+            op: BinaryOperator::Add,
+            rhs: Box::new(Expr::Column("t.b".into())),
+        };
+        assert_eq!(
+            qg.columns,
+            vec![
+                OutputColumn::Data {
+                    alias: "a".into(),
+                    column: Column::from("t.a")
+                },
+                OutputColumn::Expr(ExprColumn {
+                    name: order_expr.to_string().into(),
+                    table: None,
+                    expression: order_expr.clone(),
+                })
+            ]
+        );
 
-            assert_eq!(key.index_type, IndexType::HashMap);
-            assert_eq!(
-                key.columns,
-                vec![(mir::Column::named("bogokey"), ViewPlaceholder::Generated)]
-            );
-        }
+        let pagination = qg.pagination.unwrap();
+        assert_eq!(
+            pagination.order,
+            Some(vec![(order_expr, OrderType::OrderDescending)])
+        );
+        assert_eq!(pagination.limit, 3);
+    }
 
-        #[test]
-        fn one_to_one_equal_key() {
-            let qg = make_query_graph("SELECT t.x FROM t WHERE t.x = $1");
-            let key = qg.view_key(&Default::default()).unwrap();
+    #[test]
+    fn order_by_projected_expression_is_not_duplicated() {
+        let qg = make_query_graph("SELECT t.a + t.b FROM t ORDER BY t.a + t.b LIMIT 3");
+        assert_eq!(qg.columns.len(), 1);
+    }
 
-            assert_eq!(key.index_type, IndexType::HashMap);
+    #[test]
+    fn always_true_conjuncts_are_removed() {
+        let qg = make_folded_query_graph("SELECT t.x FROM t WHERE 1 = 1 AND t.x = ?");
+
+        assert!(!qg.always_empty);
+        assert!(qg.global_predicates.is_empty());
+        assert_eq!(
+            qg.parameters()
+                .into_iter()
+                .map(|p| p.col.clone())
+                .collect::<Vec<_>>(),
+            vec![Column::from("t.x")]
+        );
+        let rel = qg.relations.get(&Relation::from("t")).unwrap();
+        assert!(rel.predicates.is_empty());
+    }
+
+    #[test]
+    fn always_false_predicate_marks_query_empty() {
+        let qg = make_folded_query_graph("SELECT t.x FROM t WHERE 1 = 0");
+
+        assert!(qg.always_empty);
+        assert_eq!(
+            qg.global_predicates,
+            vec![Expr::Literal(Literal::Boolean(false))]
+        );
+    }
+
+    #[test]
+    fn always_false_conjunct_keeps_parameters() {
+        let qg = make_folded_query_graph("SELECT t.x FROM t WHERE t.x = ? AND 1 = 0");
+
+        assert!(qg.always_empty);
+        assert_eq!(
+            qg.parameters()
+                .into_iter()
+                .map(|p| p.col.clone())
+                .collect::<Vec<_>>(),
+            vec![Column::from("t.x")]
+        );
+    }
+
+    #[test]
+    fn straight_join_uses_textual_join_order() {
+        let join_ref = |src: &str, dst: &str| JoinRef {
+            src: src.into(),
+            dst: dst.into(),
+        };
+
+        let qg = make_query_graph(
+            "SELECT t1.x FROM t1 JOIN t3 ON t1.id = t3.id JOIN t2 ON t1.id = t2.id",
+        );
+        assert_eq!(
+            qg.join_order,
+            vec![join_ref("t1", "t2"), join_ref("t1", "t3")]
+        );
+
+        let qg = make_query_graph(
+            "SELECT t1.x FROM t1 STRAIGHT_JOIN t3 ON t1.id = t3.id \
+             STRAIGHT_JOIN t2 ON t1.id = t2.id",
+        );
+        assert_eq!(
+            qg.join_order,
+            vec![join_ref("t1", "t3"), join_ref("t1", "t2")]
+        );
+        assert!(matches!(
+            qg.edges.get(&("t1".into(), "t3".into())),
+            Some(QueryGraphEdge::Join { .. })
+        ));
+    }
+
+    #[test]
+    fn or_across_tables_is_global_predicate() {
+        let qg = make_query_graph(
+            "SELECT t1.x FROM t1 JOIN t2 ON t1.id = t2.id JOIN t3 ON t1.id = t3.id \
+             WHERE t1.x = 1 OR t2.y = 2",
+        );
+
+        let pred = parse_expr(Dialect::MySQL, "t1.x = 1 OR t2.y = 2").unwrap();
+        assert_eq!(qg.global_predicates, vec![pred.clone()]);
+        assert!(qg.relations.values().all(|rel| rel.predicates.is_empty()));
+    }
+
+    #[test]
+    fn between_placeholders_are_range_parameters() {
+        let qg = make_query_graph("SELECT t.x FROM t WHERE t.x BETWEEN $1 AND $2");
+        let rel = qg.relations.get(&Relation::from("t")).unwrap();
+        assert!(rel.predicates.is_empty());
+        assert_eq!(
+            rel.parameters,
+            vec![
+                Parameter {
+                    col: Column::from("t.x"),
+                    op: BinaryOperator::GreaterOrEqual,
+                    placeholder_idx: Some(1),
+                },
+                Parameter {
+                    col: Column::from("t.x"),
+                    op: BinaryOperator::LessOrEqual,
+                    placeholder_idx: Some(2),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn between_literal_and_placeholder() {
+        let qg = make_query_graph("SELECT t.x FROM t WHERE t.x BETWEEN 5 AND $1");
+        let rel = qg.relations.get(&Relation::from("t")).unwrap();
+        assert_eq!(
+            rel.predicates,
+            vec![parse_expr(Dialect::MySQL, "t.x >= 5").unwrap()]
+        );
+        assert_eq!(
+            rel.parameters,
+            vec![Parameter {
+                col: Column::from("t.x"),
+                op: BinaryOperator::LessOrEqual,
+                placeholder_idx: Some(1),
+            }]
+        );
+    }
+
+    #[test]
+    fn uncorrelated_exists() {
+        let qg =
+            make_query_graph("SELECT t1.x FROM t1 WHERE EXISTS (SELECT 1 FROM t2 WHERE t2.x = 5)");
+
+        assert_eq!(qg.global_predicates.len(), 1);
+        let subquery = match &qg.global_predicates[0] {
+            Expr::Exists(subquery) => subquery,
+            pred => panic!("Expected EXISTS predicate, got {pred}"),
+        };
+        assert_eq!(subquery.tables, vec![TableExpr::from(Relation::from("t2"))]);
+        assert!(!readyset_sql_passes::is_correlated(subquery));
+        assert!(qg.relations.values().all(|rel| rel.predicates.is_empty()));
+    }
+
+    #[test]
+    fn non_equi_join() {
+        let qg = make_query_graph("SELECT t1.x FROM t1 JOIN t2 ON t1.a < t2.b");
+        let expected = JoinPredicate {
+            left: Expr::Column("t1.a".into()),
+            right: Expr::Column("t2.b".into()),
+            op: BinaryOperator::Less,
+        };
+        match qg.edges.get(&("t1".into(), "t2".into())) {
+            Some(QueryGraphEdge::Join { on }) => {
+                assert_eq!(on, &vec![expected.clone()]);
+                assert!(!on[0].is_equi());
+            }
+            e => panic!("Expected join edge, got {e:?}"),
+        }
+
+        // Operands given in the opposite order to the tables are swapped, flipping the operator
+        let qg = make_query_graph("SELECT t1.x FROM t1 JOIN t2 ON t2.b > t1.a");
+        assert_eq!(
+            qg.edges.get(&("t1".into(), "t2".into())),
+            Some(&QueryGraphEdge::Join { on: vec![expected] })
+        );
+
+        let qg = make_query_graph("SELECT t1.x FROM t1 JOIN t2 ON t1.a = t2.b");
+        match qg.edges.get(&("t1".into(), "t2".into())) {
+            Some(QueryGraphEdge::Join { on }) => assert!(on[0].is_equi()),
+            e => panic!("Expected join edge, got {e:?}"),
+        }
+    }
+
+    #[test]
+    fn straight_join_with_implicit_join() {
+        let query = match parse_query(
+            Dialect::MySQL,
+            "SELECT t1.x FROM t1, t3 STRAIGHT_JOIN t2 ON t1.id = t2.id WHERE t1.id = t3.id",
+        )
+        .unwrap()
+        {
+            SqlQuery::Select(stmt) => stmt,
+            _ => unreachable!(),
+        };
+        let err = to_query_graph(&query).unwrap_err();
+        assert!(err.is_unsupported(), "{err}");
+        assert!(err.to_string().contains("STRAIGHT_JOIN"), "{err}");
+    }
+
+    #[test]
+    fn unsupported_join_operator() {
+        let query = match parse_query(Dialect::MySQL, "SELECT t1.x FROM t1 CROSS JOIN t2").unwrap()
+        {
+            SqlQuery::Select(stmt) => stmt,
+            _ => unreachable!(),
+        };
+        let err = to_query_graph(&query).unwrap_err();
+        assert!(
+            err.to_string().contains("CROSS JOIN is not supported"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn right_join_is_swapped_left_join() {
+        let qg = make_query_graph("SELECT t1.x FROM t1 RIGHT JOIN t2 ON t1.a = t2.b");
+        assert_eq!(
+            qg.edges,
+            HashMap::from([(
+                ("t2".into(), "t1".into()),
+                QueryGraphEdge::LeftJoin {
+                    on: vec![JoinPredicate::equal(
+                        Expr::Column("t2.b".into()),
+                        Expr::Column("t1.a".into())
+                    )]
+                }
+            )])
+        );
+        assert_eq!(
+            qg.join_order,
+            vec![JoinRef {
+                src: "t2".into(),
+                dst: "t1".into()
+            }]
+        );
+
+        let left_join_qg = make_query_graph("SELECT t1.x FROM t2 LEFT JOIN t1 ON t2.b = t1.a");
+        assert_eq!(qg.edges, left_join_qg.edges);
+    }
+
+    #[test]
+    fn right_join_swaps_non_equi_predicates() {
+        let qg = make_query_graph("SELECT t1.x FROM t1 RIGHT JOIN t2 ON t1.a < t2.b");
+        match qg.edges.get(&("t2".into(), "t1".into())) {
+            Some(QueryGraphEdge::LeftJoin { on }) => assert_eq!(
+                on,
+                &vec![JoinPredicate {
+                    left: Expr::Column("t2.b".into()),
+                    right: Expr::Column("t1.a".into()),
+                    op: BinaryOperator::Greater,
+                }]
+            ),
+            e => panic!("Expected a left join edge, got {e:?}"),
+        }
+    }
+
+    #[test]
+    fn right_join_nullable_side_is_outer() {
+        let qg = make_query_graph(
+            "SELECT t1.x FROM t1 RIGHT JOIN t2 ON t1.a = t2.a JOIN t3 ON t2.c = t3.c \
+             WHERE t1.b = t3.b",
+        );
+        // t1 is on the nullable side of the RIGHT JOIN, so comparing it with t3 in the WHERE
+        // clause isn't an inner join between the two
+        assert!(!qg.edges.contains_key(&("t1".into(), "t3".into())));
+        assert!(!qg.edges.contains_key(&("t3".into(), "t1".into())));
+        assert_eq!(
+            qg.global_predicates,
+            vec![Expr::BinaryOp {
+                lhs: Box::new(Expr::Column("t1.b".into())),
+                op: BinaryOperator::Equal,
+                rhs: Box::new(Expr::Column("t3.b".into())),
+            }]
+        );
+
+        // While t2, on the preserved side, can still be inner joined with t3
+        let qg = make_query_graph(
+            "SELECT t1.x FROM t1 RIGHT JOIN t2 ON t1.a = t2.a JOIN t3 ON t2.c = t3.c \
+             WHERE t2.b = t3.b",
+        );
+        assert!(qg.global_predicates.is_empty());
+    }
+
+    mod structurally_equivalent {
+        use super::*;
+
+        fn equivalent(q1: &str, q2: &str) -> bool {
+            let qg1 = make_query_graph(q1);
+            let qg2 = make_query_graph(q2);
+            let res = qg1.structurally_equivalent(&qg2);
+            assert_eq!(
+                res,
+                qg2.structurally_equivalent(&qg1),
+                "structural equivalence should be symmetric"
+            );
+            res
+        }
+
+        #[test]
+        fn identical_queries() {
+            let q = "SELECT t1.x, count(*) FROM t1 JOIN t2 ON t1.id = t2.id \
+                     WHERE t1.y = ? AND t2.z > 3 GROUP BY t1.x";
+            assert!(equivalent(q, q));
+        }
+
+        #[test]
+        fn reordered_predicates() {
+            assert!(equivalent(
+                "SELECT t.x FROM t WHERE t.y = 1 AND t.z = 2",
+                "SELECT t.x FROM t WHERE t.z = 2 AND t.y = 1"
+            ));
+            assert!(equivalent(
+                "SELECT t.x FROM t WHERE t.y = $1 AND t.z = $2",
+                "SELECT t.x FROM t WHERE t.z = $2 AND t.y = $1"
+            ));
+        }
+
+        #[test]
+        fn reordered_join_conditions() {
+            assert!(equivalent(
+                "SELECT t1.x FROM t1 JOIN t2 ON t1.a = t2.a AND t1.b = t2.b",
+                "SELECT t1.x FROM t1 JOIN t2 ON t1.b = t2.b AND t1.a = t2.a"
+            ));
+        }
+
+        #[test]
+        fn different_literal() {
+            assert!(!equivalent(
+                "SELECT t.x FROM t WHERE t.y = 1",
+                "SELECT t.x FROM t WHERE t.y = 2"
+            ));
+        }
+
+        #[test]
+        fn different_operator() {
+            assert!(!equivalent(
+                "SELECT t.x FROM t WHERE t.y = ?",
+                "SELECT t.x FROM t WHERE t.y > ?"
+            ));
+        }
+
+        #[test]
+        fn different_projection_order() {
+            assert!(!equivalent(
+                "SELECT t.x, t.y FROM t",
+                "SELECT t.y, t.x FROM t"
+            ));
+        }
+
+        #[test]
+        fn different_join_type() {
+            assert!(!equivalent(
+                "SELECT t1.x FROM t1 JOIN t2 ON t1.id = t2.id",
+                "SELECT t1.x FROM t1 LEFT JOIN t2 ON t1.id = t2.id"
+            ));
+        }
+
+        #[test]
+        fn duplicated_predicate() {
+            assert!(!equivalent(
+                "SELECT t.x FROM t WHERE t.y = 1 AND t.z = 1",
+                "SELECT t.x FROM t WHERE t.y = 1 AND t.y = 1"
+            ));
+        }
+
+        #[test]
+        fn different_pagination() {
+            assert!(!equivalent(
+                "SELECT t.x FROM t ORDER BY t.x LIMIT 3",
+                "SELECT t.x FROM t ORDER BY t.x LIMIT 4"
+            ));
+        }
+    }
+
+    mod view_key {
+        use super::*;
+
+        #[test]
+        fn bogokey_key() {
+            let qg = make_query_graph("SELECT t.x FROM t");
+            let key = qg.view_key(&Default::default()).unwrap();
+
+            assert_eq!(key.index_type, IndexType::HashMap);
+            assert_eq!(
+                key.columns,
+                vec![(mir::Column::named("bogokey"), ViewPlaceholder::Generated)]
+            );
+        }
+
+        #[test]
+        fn too_many_key_columns() {
+            let qg = make_query_graph("SELECT t.x FROM t WHERE t.x = $1 AND t.y = $2 AND t.z = $3");
+            let err = qg
+                .view_key(&mir::Config {
+                    max_view_key_columns: Some(2),
+                    ..Default::default()
+                })
+                .unwrap_err();
+
+            assert!(
+                err.is_unsupported(),
+                "Expected Unsupported error, got {err}"
+            );
+        }
+
+        #[test]
+        fn key_columns_within_limit() {
+            let qg = make_query_graph("SELECT t.x FROM t WHERE t.x = $1 AND t.y = $2");
+            let key = qg
+                .view_key(&mir::Config {
+                    max_view_key_columns: Some(2),
+                    ..Default::default()
+                })
+                .unwrap();
+
+            assert_eq!(key.columns.len(), 2);
+        }
+
+        #[test]
+        fn one_to_one_equal_key() {
+            let qg = make_query_graph("SELECT t.x FROM t WHERE t.x = $1");
+            let key = qg.view_key(&Default::default()).unwrap();
+
+            assert_eq!(key.index_type, IndexType::HashMap);
             assert_eq!(
                 key.columns,
                 vec![(
@@ -1651,6 +2761,41 @@ mod tests {
             );
         }
 
+        #[test]
+        fn between_expression_keys() {
+            let qg = make_query_graph("SELECT t.x FROM t WHERE t.x BETWEEN $1 AND $2");
+            let key = qg.view_key(&Default::default()).unwrap();
+
+            assert_eq!(key.index_type, IndexType::BTreeMap);
+            assert_eq!(
+                key.columns,
+                vec![(
+                    mir::Column::new(Some("t"), "x"),
+                    ViewPlaceholder::Between(1, 2)
+                )]
+            );
+        }
+
+        #[test]
+        fn aggregate_with_range_key() {
+            // Aggregates over range lookups are re-aggregated at the reader (see
+            // `post_lookup_aggregates`), so this should pick a range index rather than erroring
+            let qg = make_query_graph(
+                "SELECT t.category, count(*) FROM t WHERE t.ts > $1 GROUP BY t.category",
+            );
+            assert!(!qg.aggregates.is_empty());
+            let key = qg.view_key(&Default::default()).unwrap();
+
+            assert_eq!(key.index_type, IndexType::BTreeMap);
+            assert_eq!(
+                key.columns,
+                vec![(
+                    mir::Column::new(Some("t"), "ts"),
+                    ViewPlaceholder::OneToOne(1)
+                )]
+            );
+        }
+
         #[test]
         fn between_keys_reversed() {
             let qg = make_query_graph("SELECT t.x FROM t WHERE t.x <= $1 AND t.x >= $2");
@@ -1813,4 +2958,207 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn order_by_unselected_column_is_hidden() {
+        let qg = make_query_graph("SELECT t.b, t.a FROM t ORDER BY t.c");
+
+        assert_eq!(qg.hidden_columns, 1);
+        assert_eq!(
+            qg.output_columns(),
+            &[
+                OutputColumn::Data {
+                    alias: "b".into(),
+                    column: Column::from("t.b"),
+                },
+                OutputColumn::Data {
+                    alias: "a".into(),
+                    column: Column::from("t.a"),
+                },
+            ]
+        );
+        assert_eq!(
+            qg.columns.last(),
+            Some(&OutputColumn::Data {
+                alias: "c".into(),
+                column: Column::from("t.c"),
+            })
+        );
+        assert!(
+            qg.describe()
+                .contains("columns: `t`.`b` AS `b`, `t`.`a` AS `a`, `t`.`c` AS `c` (hidden)"),
+            "{}",
+            qg.describe()
+        );
+    }
+
+    #[test]
+    fn order_by_selected_column_is_not_hidden() {
+        let qg = make_query_graph("SELECT t.b, t.a FROM t ORDER BY t.a");
+        assert_eq!(qg.hidden_columns, 0);
+        assert_eq!(qg.output_columns(), qg.columns.as_slice());
+    }
+
+    #[test]
+    fn describe_join_query() {
+        let qg = make_query_graph(
+            "SELECT t1.a, t2.b FROM t1 JOIN t2 ON t1.id = t2.t1_id WHERE t1.c = ? \
+             ORDER BY t1.a LIMIT 10",
+        );
+        let description = qg.describe();
+
+        assert!(
+            description.contains("relations:\n  `t1` params: [`t1`.`c` = ?]\n  `t2`"),
+            "{description}"
+        );
+        assert!(
+            description.contains("`t1` JOIN `t2` ON `t1`.`id` = `t2`.`t1_id`"),
+            "{description}"
+        );
+        assert!(
+            description.contains("columns: `t1`.`a` AS `a`, `t2`.`b` AS `b`"),
+            "{description}"
+        );
+        assert!(
+            description.contains("pagination: ORDER BY `t1`.`a` ASC LIMIT 10"),
+            "{description}"
+        );
+    }
+
+    mod in_subquery {
+        use super::*;
+
+        #[test]
+        fn uncorrelated() {
+            let qg = make_query_graph(
+                "SELECT users.id FROM users WHERE users.id IN (SELECT perms.user_id FROM perms)",
+            );
+
+            let rel = Relation::from("__in_subquery_0");
+            let (subgraph, subquery) = qg.relations[&rel].subgraph.as_ref().unwrap();
+            assert!(subquery.distinct);
+            assert!(subgraph.distinct);
+            assert!(subgraph.relations.contains_key(&Relation::from("perms")));
+
+            assert_eq!(
+                qg.edges[&(Relation::from("users"), rel.clone())],
+                QueryGraphEdge::SemiJoin {
+                    on: vec![JoinPredicate::equal(
+                        Expr::Column("users.id".into()),
+                        Expr::Column(Column {
+                            name: "user_id".into(),
+                            table: Some(rel.clone()),
+                        }),
+                    )]
+                }
+            );
+            assert_eq!(
+                qg.join_order,
+                vec![JoinRef {
+                    src: "users".into(),
+                    dst: rel,
+                }]
+            );
+            assert!(qg.global_predicates.is_empty());
+        }
+
+        #[test]
+        fn with_other_conditions() {
+            let qg = make_query_graph(
+                "SELECT users.id FROM users WHERE users.name = 'a' \
+                 AND users.id IN (SELECT perms.user_id AS uid FROM perms)",
+            );
+
+            assert_eq!(qg.relations[&Relation::from("users")].predicates.len(), 1);
+            assert_eq!(
+                qg.edges[&(Relation::from("users"), Relation::from("__in_subquery_0"))].on()[0]
+                    .right,
+                Expr::Column("__in_subquery_0.uid".into())
+            );
+        }
+
+        #[test]
+        fn correlated() {
+            let query = parse_select(
+                "SELECT users.id FROM users \
+                 WHERE users.id IN (SELECT perms.user_id FROM perms WHERE perms.org = users.org)",
+            );
+            assert!(to_query_graph(&query).unwrap_err().is_unsupported());
+        }
+
+        #[test]
+        fn negated() {
+            let query = parse_select(
+                "SELECT users.id FROM users WHERE users.id NOT IN (SELECT perms.user_id FROM perms)",
+            );
+            assert!(to_query_graph(&query).unwrap_err().is_unsupported());
+        }
+
+        #[test]
+        fn in_or() {
+            let query = parse_select(
+                "SELECT users.id FROM users \
+                 WHERE users.id = 1 OR users.id IN (SELECT perms.user_id FROM perms)",
+            );
+            assert!(to_query_graph(&query).unwrap_err().is_unsupported());
+        }
+
+        #[test]
+        fn multiple_columns() {
+            let query = parse_select(
+                "SELECT users.id FROM users \
+                 WHERE users.id IN (SELECT perms.user_id, perms.org FROM perms)",
+            );
+            assert!(to_query_graph(&query).unwrap_err().is_unsupported());
+        }
+    }
+
+    mod query_graph_set {
+        use readyset_errors::ReadySetError;
+
+        use super::*;
+
+        fn make_query_graph_set(sql: &str) -> ReadySetResult<QueryGraphSet> {
+            match parse_query(Dialect::MySQL, sql).unwrap() {
+                SqlQuery::CompoundSelect(stmt) => QueryGraphSet::new(&stmt),
+                q => panic!("Expected compound select, got {q:?}"),
+            }
+        }
+
+        #[test]
+        fn union() {
+            let qgs = make_query_graph_set(
+                "SELECT t1.a FROM t1 WHERE t1.b = ? UNION SELECT t2.a FROM t2 WHERE t2.b = ?",
+            )
+            .unwrap();
+            assert_eq!(qgs.graphs.len(), 2);
+            assert!(!qgs.union_all);
+            assert!(qgs.graphs[0].relations.contains_key(&Relation::from("t1")));
+            assert!(qgs.graphs[1].relations.contains_key(&Relation::from("t2")));
+        }
+
+        #[test]
+        fn union_all() {
+            let qgs =
+                make_query_graph_set("SELECT t1.a FROM t1 UNION ALL SELECT t2.a FROM t2").unwrap();
+            assert_eq!(qgs.graphs.len(), 2);
+            assert!(qgs.union_all);
+        }
+
+        #[test]
+        fn mismatched_column_counts() {
+            let err = make_query_graph_set("SELECT t1.a, t1.b FROM t1 UNION SELECT t2.a FROM t2")
+                .unwrap_err();
+            assert!(matches!(err, ReadySetError::InvalidQuery(_)), "{err:?}");
+        }
+
+        #[test]
+        fn mixed_union_kinds() {
+            let err = make_query_graph_set(
+                "SELECT t1.a FROM t1 UNION SELECT t2.a FROM t2 UNION ALL SELECT t3.a FROM t3",
+            )
+            .unwrap_err();
+            assert!(err.is_unsupported(), "{err:?}");
+        }
+    }
 }
@@ -4,7 +4,7 @@ use std::hash::{Hash, Hasher};
 use nom_sql::analysis::ReferredColumns;
 use nom_sql::Column;
 
-use crate::controller::sql::query_graph::{OutputColumn, QueryGraph, QueryGraphEdge};
+use crate::controller::sql::query_graph::{OutputColumn, QueryGraph};
 
 pub trait Signature {
     fn signature(&self) -> QuerySignature;
@@ -74,14 +74,11 @@ impl Signature for QueryGraph {
             .for_each(&mut record_column);
 
         for e in self.edges.values() {
-            match e {
-                QueryGraphEdge::Join { on } | QueryGraphEdge::LeftJoin { on } => {
-                    on.iter()
-                        .flat_map(|p| vec![&p.left, &p.right])
-                        .flat_map(|p| p.referred_columns())
-                        .for_each(&mut record_column);
-                }
-            }
+            e.on()
+                .iter()
+                .flat_map(|p| vec![&p.left, &p.right])
+                .flat_map(|p| p.referred_columns())
+                .for_each(&mut record_column);
         }
 
         self.group_by.iter().for_each(&mut record_column);
@@ -201,4 +198,26 @@ mod tests {
 
         assert_ne!(h1.finish(), h2.finish());
     }
+
+    #[test]
+    fn distinct_hashes_are_inequal() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let without_distinct = parse_select("SELECT a.id FROM a WHERE a.id = ?").unwrap();
+        let with_distinct = parse_select("SELECT DISTINCT a.id FROM a WHERE a.id = ?").unwrap();
+
+        let without_distinct_qg = to_query_graph(&without_distinct).unwrap();
+        let with_distinct_qg = to_query_graph(&with_distinct).unwrap();
+
+        assert!(!without_distinct_qg.distinct);
+        assert!(with_distinct_qg.distinct);
+        assert!(!without_distinct_qg.structurally_equivalent(&with_distinct_qg));
+
+        let mut h1 = DefaultHasher::new();
+        let mut h2 = DefaultHasher::new();
+        without_distinct_qg.hash(&mut h1);
+        with_distinct_qg.hash(&mut h2);
+
+        assert_ne!(h1.finish(), h2.finish());
+    }
 }
@@ -0,0 +1,176 @@
+//! Checking that the columns compared (or combined with `UNION`) by a query have compatible types,
+//! so that we don't plan a query which would silently return wrong results due to implicit
+//! coercion.
+
+use std::collections::HashMap;
+
+use nom_sql::{BinaryOperator, Column, CreateTableStatement, Expr, Relation, SqlType};
+use readyset_errors::{ReadySetError, ReadySetResult};
+
+use super::query_graph::{OutputColumn, QueryGraph, QueryGraphSet};
+
+/// Broad categories of types whose values can be compared with each other without an explicit
+/// cast
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TypeCategory {
+    Numeric,
+    Text,
+    Temporal,
+}
+
+impl TypeCategory {
+    /// Returns the category of the given type, or `None` if we don't know enough about the type
+    /// to say which other types it can be compared with
+    fn of(ty: &SqlType) -> Option<Self> {
+        use SqlType::*;
+
+        match ty {
+            Bool
+            | TinyInt(_)
+            | UnsignedTinyInt(_)
+            | SmallInt(_)
+            | UnsignedSmallInt(_)
+            | Int(_)
+            | UnsignedInt(_)
+            | BigInt(_)
+            | UnsignedBigInt(_)
+            | Serial
+            | BigSerial
+            | Float
+            | Double
+            | Real
+            | Numeric(_)
+            | Decimal(_, _) => Some(Self::Numeric),
+            Char(_) | VarChar(_) | TinyText | MediumText | LongText | Text | Citext | Enum(_) => {
+                Some(Self::Text)
+            }
+            Date | DateTime(_) | Time | Timestamp | TimestampTz => Some(Self::Temporal),
+            _ => None,
+        }
+    }
+}
+
+/// Look up the type of `column` in the schema of the base table it refers to, if it refers to a
+/// base table at all
+fn column_type<'a>(
+    column: &Column,
+    base_schemas: &'a HashMap<Relation, CreateTableStatement>,
+) -> Option<&'a SqlType> {
+    base_schemas
+        .get(column.table.as_ref()?)?
+        .fields
+        .iter()
+        .find(|f| f.column.name == column.name)
+        .map(|f| &f.sql_type)
+}
+
+/// Returns an error if `left` and `right` are both columns in base tables whose types are known to
+/// be incompatible
+fn check_columns(
+    left: &Expr,
+    right: &Expr,
+    base_schemas: &HashMap<Relation, CreateTableStatement>,
+) -> ReadySetResult<()> {
+    let (Expr::Column(left), Expr::Column(right)) = (left, right) else {
+        return Ok(());
+    };
+    let (Some(left_type), Some(right_type)) = (
+        column_type(left, base_schemas),
+        column_type(right, base_schemas),
+    ) else {
+        return Ok(());
+    };
+
+    match (TypeCategory::of(left_type), TypeCategory::of(right_type)) {
+        (Some(l), Some(r)) if l != r => Err(ReadySetError::ColumnTypeMismatch {
+            left: left.to_string(),
+            left_type: left_type.to_string(),
+            right: right.to_string(),
+            right_type: right_type.to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn check_predicate(
+    expr: &Expr,
+    base_schemas: &HashMap<Relation, CreateTableStatement>,
+) -> ReadySetResult<()> {
+    match expr {
+        Expr::BinaryOp { lhs, op, rhs }
+            if op.is_comparison()
+                || matches!(op, BinaryOperator::Equal | BinaryOperator::NotEqual) =>
+        {
+            check_columns(lhs, rhs, base_schemas)
+        }
+        Expr::BinaryOp { lhs, rhs, .. } => {
+            check_predicate(lhs, base_schemas)?;
+            check_predicate(rhs, base_schemas)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Check that every join predicate and every comparison between two columns in the given query
+/// graph (and the graphs of any of its subqueries) compares columns of compatible types, returning
+/// [`ReadySetError::ColumnTypeMismatch`] if not.
+///
+/// Only columns in base tables are checked, since those are the only columns whose types we know
+/// before the query is lowered. Comparisons involving an explicit `CAST` are never rejected.
+pub(super) fn check_comparison_types(
+    qg: &QueryGraph,
+    base_schemas: &HashMap<Relation, CreateTableStatement>,
+) -> ReadySetResult<()> {
+    for edge in qg.edges.values() {
+        for pred in edge.on() {
+            check_columns(&pred.left, &pred.right, base_schemas)?;
+        }
+    }
+
+    for node in qg.relations.values() {
+        for pred in &node.predicates {
+            check_predicate(pred, base_schemas)?;
+        }
+        if let Some((subgraph, _)) = &node.subgraph {
+            check_comparison_types(subgraph, base_schemas)?;
+        }
+    }
+
+    for pred in qg.global_predicates.iter().chain(&qg.having_predicates) {
+        check_predicate(pred, base_schemas)?;
+    }
+
+    Ok(())
+}
+
+/// Check that each column projected by every arm of the given compound query has a type compatible
+/// with the corresponding column projected by the first arm, returning
+/// [`ReadySetError::ColumnTypeMismatch`] if not.
+///
+/// As with [`check_comparison_types`], only columns in base tables are checked.
+pub(super) fn check_union_types(
+    qgs: &QueryGraphSet,
+    base_schemas: &HashMap<Relation, CreateTableStatement>,
+) -> ReadySetResult<()> {
+    let Some((first, rest)) = qgs.graphs.split_first() else {
+        return Ok(());
+    };
+
+    for qg in rest {
+        for (left, right) in first.columns.iter().zip(&qg.columns) {
+            if let (
+                OutputColumn::Data { column: left, .. },
+                OutputColumn::Data { column: right, .. },
+            ) = (left, right)
+            {
+                check_columns(
+                    &Expr::Column(left.clone()),
+                    &Expr::Column(right.clone()),
+                    base_schemas,
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -14,17 +14,19 @@
 
 use std::borrow::Cow;
 use std::cell;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::net::SocketAddr;
 use std::ops::Deref;
 use std::sync::Arc;
 use std::time::Instant;
 
 use array2::Array2;
+use chrono::{DateTime, Utc};
 use common::IndexPair;
 use dataflow::prelude::{ChannelCoordinator, DomainIndex, DomainNodes, Graph, NodeIndex};
 use dataflow::{
-    DomainBuilder, DomainConfig, DomainRequest, NodeMap, Packet, PersistenceParameters, Sharding,
+    ConstantColumn, DomainBuilder, DomainConfig, DomainRequest, NodeMap, Packet,
+    PersistenceParameters, Sharding,
 };
 use futures::stream::{self, StreamExt, TryStreamExt};
 use futures::{FutureExt, TryStream};
@@ -33,7 +35,7 @@ use metrics::{gauge, histogram};
 use nom_sql::{
     CacheInner, CreateCacheStatement, Relation, SelectStatement, SqlIdentifier, SqlQuery,
 };
-use petgraph::visit::Bfs;
+use petgraph::visit::{Bfs, Reversed};
 use readyset::builders::{TableBuilder, ViewBuilder};
 use readyset::consensus::{Authority, AuthorityControl};
 use readyset::debug::info::GraphInfo;
@@ -43,22 +45,26 @@ use readyset::metrics::recorded;
 use readyset::recipe::changelist::{Change, ChangeList};
 use readyset::recipe::ExtendRecipeSpec;
 use readyset::replication::{ReplicationOffset, ReplicationOffsets};
+use readyset::status::{KeyDistribution, MaterializationProgress};
 use readyset::{
-    NodeSize, ReadySetError, ReadySetResult, ViewCreateRequest, ViewFilter, ViewRequest, ViewSchema,
+    ColumnSchema, KeyCount, NodeRestriction, NodeSize, ReadySetError, ReadySetResult,
+    ViewCreateRequest, ViewFilter, ViewMetadata, ViewRequest, ViewSchema,
 };
 use readyset_data::Dialect;
-use readyset_errors::{internal, internal_err, invariant_eq, NodeType};
+use readyset_errors::{
+    bad_request_err, internal, internal_err, invariant_eq, unsupported, NodeType,
+};
 use regex::Regex;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use tokio::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard};
-use tracing::{debug, error, instrument, trace, warn};
+use tracing::{debug, error, info, instrument, trace, warn};
 
 use super::migrate::DomainSettings;
 use super::replication::ReplicationStrategy;
 use crate::controller::domain_handle::DomainHandle;
 use crate::controller::migrate::materialization::Materializations;
-use crate::controller::migrate::scheduling::Scheduler;
+use crate::controller::migrate::scheduling::{worker_meets_restrictions, Scheduler};
 use crate::controller::migrate::{routing, DomainMigrationPlan, Migration};
 use crate::controller::recipe::{Recipe, Schema};
 use crate::controller::{
@@ -108,6 +114,30 @@ pub struct DfState {
     /// Placement restrictions for nodes and the domains they are placed into.
     #[serde(with = "serde_with::rust::hashmap_as_tuple_list")]
     pub(super) node_restrictions: HashMap<NodeRestrictionKey, DomainPlacementRestriction>,
+    /// Node placement restrictions set by [`DfState::set_node_restrictions`] which the domains
+    /// already running those nodes may not honor yet. Those domains are re-placed at the start of
+    /// the next migration, by [`DfState::reconcile_node_restrictions`].
+    #[serde(default)]
+    pub(super) pending_node_restrictions: HashSet<NodeRestrictionKey>,
+    /// The desired number of replicas of the reader for each query that has one set, overriding
+    /// the [`ReplicationStrategy`] for the domains containing those readers.
+    ///
+    /// The controller reconciles the actual number of replicas towards this whenever it's set and
+    /// whenever workers join the cluster.
+    #[serde(default, with = "serde_with::rust::hashmap_as_tuple_list")]
+    pub(super) reader_replicas: HashMap<Relation, usize>,
+    /// Per-deployment feature flags, keyed by name, which adapters read from the controller to
+    /// enable or disable opt-in behavior without needing to be restarted.
+    #[serde(default)]
+    pub(super) feature_flags: BTreeMap<String, bool>,
+    /// Bookkeeping information about every view in the recipe, keyed by the name of the view.
+    ///
+    /// Entries are added when views are created and removed when they're dropped, by
+    /// [`DfState::update_view_metadata`]. The last access time of each view is tracked separately
+    /// by the [`Leader`](super::inner::Leader) and the workers (see [`DfState::view_reads`]), so
+    /// it's never set here.
+    #[serde(default, with = "serde_with::rust::hashmap_as_tuple_list")]
+    pub(super) view_metadata: HashMap<Relation, ViewMetadata>,
 
     #[serde(skip)]
     pub(super) domains: HashMap<DomainIndex, DomainHandle>,
@@ -121,6 +151,13 @@ pub struct DfState {
     pub(super) read_addrs: HashMap<WorkerIdentifier, SocketAddr>,
     #[serde(skip)]
     pub(super) workers: HashMap<WorkerIdentifier, Worker>,
+    /// The number of replicas that reconciling [reader replicas][reader_replicas] last tried to
+    /// run each domain with, so that a domain isn't rescheduled again until that number changes.
+    /// Cleared for a domain whenever it's recovered.
+    ///
+    /// [reader_replicas]: DfState::reader_replicas
+    #[serde(skip)]
+    pub(super) reader_replica_targets: HashMap<DomainIndex, usize>,
 
     /// State between migrations
     #[serde(with = "serde_with::rust::hashmap_as_tuple_list")]
@@ -161,10 +198,15 @@ impl DfState {
             recipe,
             schema_replication_offset,
             node_restrictions,
+            pending_node_restrictions: Default::default(),
+            reader_replicas: Default::default(),
+            feature_flags: Default::default(),
+            view_metadata: Default::default(),
             domains: Default::default(),
             domain_nodes: Default::default(),
             channel_coordinator,
             read_addrs: Default::default(),
+            reader_replica_targets: Default::default(),
             workers: Default::default(),
             remap: Default::default(),
             keep_prior_recipes,
@@ -288,6 +330,16 @@ impl DfState {
             .collect()
     }
 
+    /// Returns a human-readable description of the plan that would be used to cache the given
+    /// query, without creating a view for it.
+    pub(super) fn explain_query(
+        &self,
+        query: ViewCreateRequest,
+        dialect: Dialect,
+    ) -> ReadySetResult<String> {
+        self.recipe.explain_query(query, dialect)
+    }
+
     pub(super) fn find_reader_for(
         &self,
         node: NodeIndex,
@@ -382,6 +434,11 @@ impl DfState {
             .map(|idx| columns.get(*idx).map(|c| c.name().into()))
             .collect::<Option<Vec<_>>>()
             .ok_or_else(|| internal_err!("Schema expects valid column indices"))?;
+        let columns = with_constant_columns(
+            columns,
+            &reader.reader_processing().post_processing.constant_columns,
+            |constant| constant.name.clone(),
+        );
 
         let key_mapping = Vec::from(reader.mapping());
 
@@ -417,6 +474,7 @@ impl DfState {
             schema,
             replica_shard_addrs: Array2::from_rows(replicas),
             key_mapping,
+            index_type: reader.index_type(),
             view_request_timeout: self.domain_config.view_request_timeout,
         }))
     }
@@ -455,7 +513,21 @@ impl DfState {
             .map(|idx| schema::column_schema(&self.ingredients, view_ni, &self.recipe, *idx))
             .collect::<Result<Vec<_>, ReadySetError>>()?
             .into_iter()
-            .collect::<Option<Vec<_>>>();
+            .collect::<Option<Vec<_>>>()
+            .map(|schema| {
+                with_constant_columns(
+                    schema,
+                    &reader.reader_processing().post_processing.constant_columns,
+                    |constant| ColumnSchema {
+                        column: nom_sql::Column {
+                            name: constant.name.clone(),
+                            table: Some(n.name().clone()),
+                        },
+                        column_type: constant.ty.clone(),
+                        base: None,
+                    },
+                )
+            });
 
         match (projected_schema, returned_schema) {
             (None, _) => Ok(None),
@@ -489,24 +561,24 @@ impl DfState {
 
         trace!(%base, "creating table");
 
-        let mut key = node
+        let primary_key = node
             .get_base()
             .ok_or_else(|| ReadySetError::InvalidNodeType {
                 node_index: node.local_addr().id(),
                 expected_type: NodeType::Base,
             })?
             .primary_key()
-            .map(|k| k.to_owned())
-            .unwrap_or_default();
-
-        let mut is_primary = false;
-        if key.is_empty() {
-            if let Sharding::ByColumn(col, _) = node.sharded_by() {
-                key = vec![col];
-            }
-        } else {
-            is_primary = true;
-        }
+            .filter(|k| !k.is_empty());
+
+        // Tables without a primary key only support inserting and deleting whole rows, which the
+        // `Table` enforces by returning `ReadySetError::NoPrimaryKey` for any operations by key.
+        // If such a table is sharded, its sharding column is used as the key purely to route
+        // writes to the right shard.
+        let (key, is_primary) = match (primary_key, node.sharded_by()) {
+            (Some(key), _) => (key.to_owned(), true),
+            (None, Sharding::ByColumn(col, _)) => (vec![col], false),
+            (None, _) => (vec![], false),
+        };
 
         let domain =
             self.domains
@@ -662,6 +734,292 @@ impl DfState {
             })
     }
 
+    /// Returns the indices of all running domains which have no nodes left in them, either because
+    /// they were never assigned any or because all of their nodes have since been removed, in
+    /// ascending order
+    pub(super) fn orphaned_domains(&self) -> Vec<DomainIndex> {
+        let mut orphaned = self
+            .domains
+            .keys()
+            .filter(|di| {
+                self.domain_nodes
+                    .get(di)
+                    .iter()
+                    .flat_map(|nodes| nodes.values())
+                    .all(|ni| self.ingredients[*ni].is_dropped())
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        orphaned.sort_unstable();
+        orphaned
+    }
+
+    /// Returns true if the given worker holds the only copy of some shard of a domain containing a
+    /// base table, meaning the data in that base table would be lost if the worker were removed
+    pub(super) fn worker_has_unique_base_state(&self, worker: &WorkerIdentifier) -> bool {
+        self.domains
+            .values()
+            .filter(|dh| {
+                self.domain_nodes
+                    .get(&dh.index())
+                    .iter()
+                    .any(|nodes| nodes.values().any(|ni| self.ingredients[*ni].is_base()))
+            })
+            .any(|dh| {
+                dh.shards()
+                    .any(|replicas| replicas.iter().all(|w| w == worker))
+            })
+    }
+
+    /// Returns the desired number of replicas for a domain containing the given nodes, if any of
+    /// them are readers for a query with a desired number of [reader replicas][reader_replicas].
+    ///
+    /// [reader_replicas]: DfState::reader_replicas
+    pub(super) fn desired_reader_replicas(&self, nodes: &[NodeIndex]) -> Option<usize> {
+        nodes
+            .iter()
+            .filter_map(|ni| self.ingredients.node_weight(*ni))
+            .filter(|node| node.is_reader())
+            .filter_map(|node| self.reader_replicas.get(node.name()).copied())
+            .max()
+    }
+
+    /// Set the desired number of replicas of the reader for the query with the given name, then
+    /// reconcile the replicas of the query's reader domain towards it.
+    pub(super) async fn set_reader_replicas(
+        &mut self,
+        query: Relation,
+        replicas: usize,
+    ) -> ReadySetResult<()> {
+        if replicas == 0 {
+            return Err(bad_request_err(
+                "Queries must have at least one reader replica",
+            ));
+        }
+        if !self.views().contains_key(&query) {
+            return Err(ReadySetError::ViewNotFound(query.to_string()));
+        }
+
+        info!(%query, %replicas, "Setting desired number of reader replicas");
+        self.reader_replicas.insert(query.clone(), replicas);
+        self.replicate_readers(&[query]).await
+    }
+
+    /// Enable or disable the [feature flag][feature_flags] with the given name.
+    ///
+    /// [feature_flags]: DfState::feature_flags
+    pub(super) fn set_feature_flag(&mut self, name: String, enabled: bool) {
+        info!(%name, %enabled, "Setting feature flag");
+        self.feature_flags.insert(name, enabled);
+    }
+
+    /// Returns the placement restrictions for all dataflow nodes, ordered by node name and shard.
+    pub(super) fn node_restrictions(&self) -> Vec<NodeRestriction> {
+        let mut restrictions = self
+            .node_restrictions
+            .iter()
+            .map(|(key, restriction)| NodeRestriction {
+                node_name: key.node_name.clone(),
+                shard: key.shard,
+                worker_volume: restriction.worker_volume.clone(),
+            })
+            .collect::<Vec<_>>();
+        restrictions.sort_by(|r1, r2| {
+            r1.node_name
+                .cmp(&r2.node_name)
+                .then_with(|| r1.shard.cmp(&r2.shard))
+        });
+        restrictions
+    }
+
+    /// Set the placement restrictions for the dataflow node shards in `restrictions`, replacing
+    /// any existing restrictions for those node shards and leaving all other restrictions
+    /// unchanged.
+    ///
+    /// Domains which are already running any of those node shards on a worker that doesn't meet
+    /// the new restrictions are re-placed at the start of the next migration (see
+    /// [`DfState::reconcile_node_restrictions`]).
+    pub(super) fn set_node_restrictions(&mut self, restrictions: Vec<NodeRestriction>) {
+        info!(
+            num_restrictions = restrictions.len(),
+            "Setting node restrictions"
+        );
+        for restriction in restrictions {
+            let key = NodeRestrictionKey {
+                node_name: restriction.node_name,
+                shard: restriction.shard,
+            };
+            self.node_restrictions.insert(
+                key.clone(),
+                DomainPlacementRestriction {
+                    worker_volume: restriction.worker_volume,
+                },
+            );
+            self.pending_node_restrictions.insert(key);
+        }
+    }
+
+    /// Re-place every domain with a shard running on a worker that doesn't meet the [pending node
+    /// restrictions][pending] of that shard's nodes onto workers that do, then clear the pending
+    /// restrictions.
+    ///
+    /// [pending]: DfState::pending_node_restrictions
+    pub(super) async fn reconcile_node_restrictions(&mut self) -> ReadySetResult<()> {
+        if self.pending_node_restrictions.is_empty() {
+            return Ok(());
+        }
+
+        let mut affected_nodes = HashMap::new();
+        for (domain_index, dh) in &self.domains {
+            let nodes = match self.domain_nodes.get(domain_index) {
+                Some(nodes) => nodes.values().copied().collect::<HashSet<_>>(),
+                None => continue,
+            };
+            let misplaced = dh.shards().enumerate().any(|(shard, replicas)| {
+                let restrictions = nodes
+                    .iter()
+                    .filter_map(|ni| {
+                        #[allow(clippy::indexing_slicing)] // came from self.domain_nodes
+                        let key = NodeRestrictionKey {
+                            node_name: self.ingredients[*ni].name().clone(),
+                            shard,
+                        };
+                        if self.pending_node_restrictions.contains(&key) {
+                            self.node_restrictions.get(&key)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                !restrictions.is_empty()
+                    && replicas.iter().any(|wi| {
+                        self.workers.get(wi).map_or(false, |worker| {
+                            !worker_meets_restrictions(worker, &restrictions)
+                        })
+                    })
+            });
+            if misplaced {
+                affected_nodes.insert(*domain_index, nodes);
+            }
+        }
+
+        for (domain_index, node_indices) in &affected_nodes {
+            info!(
+                domain = %domain_index.index(),
+                "Re-placing domain to honor node restrictions"
+            );
+            if let Some(dh) = self.domains.remove(domain_index) {
+                dh.kill(&self.workers).await?;
+            }
+            self.materializations.remove_nodes(node_indices);
+        }
+
+        if !affected_nodes.is_empty() {
+            self.recover(&affected_nodes).await?;
+        }
+        self.pending_node_restrictions.clear();
+        Ok(())
+    }
+
+    /// Replicate the readers of the given queries, by rescheduling each of their reader domains
+    /// whose number of replicas differs from the number it should be running with (see
+    /// [`Scheduler::num_replicas`]).
+    ///
+    /// Only the domains containing the readers of the given queries are rescheduled, and only if
+    /// the number of replicas they would be scheduled with has changed since the last time they
+    /// were replicated, so domains that can't be scheduled with as many replicas as they should
+    /// have aren't killed and recovered every time this is called.
+    pub(super) async fn replicate_readers(&mut self, queries: &[Relation]) -> ReadySetResult<()> {
+        let views = self.views();
+        let domains = queries
+            .iter()
+            .filter_map(|query| {
+                let reader = self.find_reader_for(*views.get(query)?, query, &None)?;
+                #[allow(clippy::indexing_slicing)] // `find_reader_for` returns valid indices
+                Some(self.ingredients[reader].domain())
+            })
+            .collect::<HashSet<_>>();
+        if domains.is_empty() {
+            return Ok(());
+        }
+
+        let mut affected_nodes = HashMap::new();
+        let mut targets = HashMap::new();
+        {
+            let scheduler = Scheduler::new(self, &None)?;
+            for domain_index in domains {
+                let (dh, nodes) = match (
+                    self.domains.get(&domain_index),
+                    self.domain_nodes.get(&domain_index),
+                ) {
+                    (Some(dh), Some(nodes)) => (dh, nodes.values().copied().collect::<Vec<_>>()),
+                    _ => continue,
+                };
+                let target = scheduler.num_replicas(&nodes);
+                if target != dh.num_replicas()
+                    && self.reader_replica_targets.get(&domain_index) != Some(&target)
+                {
+                    affected_nodes.insert(domain_index, nodes.into_iter().collect::<HashSet<_>>());
+                    targets.insert(domain_index, target);
+                }
+            }
+        }
+
+        if affected_nodes.is_empty() {
+            return Ok(());
+        }
+
+        for (domain_index, node_indices) in &affected_nodes {
+            info!(
+                domain = %domain_index.index(),
+                replicas = ?targets.get(domain_index),
+                "Replicating reader domain"
+            );
+            if let Some(dh) = self.domains.remove(domain_index) {
+                dh.kill(&self.workers).await?;
+            }
+            self.materializations.remove_nodes(node_indices);
+        }
+
+        self.recover(&affected_nodes).await?;
+        self.reader_replica_targets.extend(targets);
+        Ok(())
+    }
+
+    /// [Replicate the readers][replicate_readers] of every query with a desired number of [reader
+    /// replicas][reader_replicas], if there are now enough workers to run them.
+    ///
+    /// [replicate_readers]: DfState::replicate_readers
+    /// [reader_replicas]: DfState::reader_replicas
+    pub(super) async fn reconcile_reader_replicas(&mut self) -> ReadySetResult<()> {
+        let queries = self.reader_replicas.keys().cloned().collect::<Vec<_>>();
+        self.replicate_readers(&queries).await
+    }
+
+    /// Remove the given workers from the cluster, and recover all the domains that were running
+    /// on them onto the remaining workers
+    pub(super) async fn remove_workers(
+        &mut self,
+        workers: Vec<WorkerIdentifier>,
+    ) -> ReadySetResult<()> {
+        // first, translate from the affected workers to affected data-flow nodes
+        let mut affected_nodes = HashMap::new();
+        for wi in workers {
+            let mut domain_nodes_on_worker = self.nodes_on_worker(Some(&wi));
+            for (domain_index, node_indices) in domain_nodes_on_worker.drain() {
+                self.domains.remove(&domain_index);
+                self.materializations.remove_nodes(&node_indices);
+                affected_nodes
+                    .entry(domain_index)
+                    .or_insert_with(HashSet::new)
+                    .extend(node_indices);
+            }
+            self.workers.remove(&wi);
+        }
+
+        self.recover(&affected_nodes).await
+    }
+
     /// Issue all of `requests` to their corresponding domains asynchronously, and return a stream
     /// of the results, consisting of shard, then replica, then result (potentially in a different
     /// order)
@@ -788,6 +1146,124 @@ impl DfState {
             .collect()
     }
 
+    /// Returns the names of all the queries whose views are derived (directly or indirectly) from
+    /// the base table with the given name, in order.
+    pub(super) fn queries_for_table(&self, name: &Relation) -> ReadySetResult<Vec<Relation>> {
+        let table_not_found = || ReadySetError::TableNotFound {
+            name: name.name.clone().into(),
+            schema: name.schema.clone().map(Into::into),
+        };
+        let ni = self
+            .recipe
+            .node_addr_for(name)
+            .map_err(|_| table_not_found())?;
+        #[allow(clippy::indexing_slicing)] // node_addr_for returns valid node indices
+        if !self.ingredients[ni].is_base() {
+            return Err(table_not_found());
+        }
+
+        let mut queries = BTreeSet::new();
+        let mut bfs = Bfs::new(&self.ingredients, ni);
+        while let Some(descendant) = bfs.next(&self.ingredients) {
+            #[allow(clippy::indexing_slicing)] // just came from self.ingredients
+            let node = &self.ingredients[descendant];
+            if node.is_reader() {
+                queries.insert(node.name().clone());
+            }
+        }
+
+        Ok(queries.into_iter().collect())
+    }
+
+    /// Returns the progress of materializing the view with the given name, measured as the number
+    /// of base tables the view is derived from which have finished snapshotting.
+    pub(super) async fn materialization_progress(
+        &self,
+        name: &Relation,
+    ) -> ReadySetResult<MaterializationProgress> {
+        let ni = match self.recipe.node_addr_for(name) {
+            Ok(ni) => ni,
+            Err(_) => *self
+                .views()
+                .get(name)
+                .ok_or_else(|| ReadySetError::ViewNotFound(name.to_string()))?,
+        };
+
+        let mut tables = HashSet::new();
+        let graph = Reversed(&self.ingredients);
+        let mut bfs = Bfs::new(graph, ni);
+        while let Some(ancestor) = bfs.next(graph) {
+            #[allow(clippy::indexing_slicing)] // just came from self.ingredients
+            let node = &self.ingredients[ancestor];
+            if node.is_base() {
+                tables.insert(node.name().clone());
+            }
+        }
+
+        let snapshotting_tables = self.snapshotting_tables().await?;
+        Ok(MaterializationProgress {
+            snapshotted_tables: tables.difference(&snapshotting_tables).count(),
+            total_tables: tables.len(),
+        })
+    }
+
+    /// Returns the number of keys materialized in each shard of the reader for the view with the
+    /// given name.
+    pub(super) async fn key_distribution(
+        &self,
+        name: &Relation,
+    ) -> ReadySetResult<KeyDistribution> {
+        let view_not_found = || ReadySetError::ViewNotFound(name.to_string());
+        let ni = match self.recipe.node_addr_for(name) {
+            Ok(ni) => ni,
+            Err(_) => *self.views().get(name).ok_or_else(view_not_found)?,
+        };
+        let name = self.recipe.resolve_alias(name).unwrap_or(name);
+        let reader_node = self
+            .find_reader_for(ni, name, &None)
+            .ok_or_else(view_not_found)?;
+        #[allow(clippy::indexing_slicing)] // `find_reader_for` returns valid indices
+        let domain = self.ingredients[reader_node].domain();
+
+        let shard_sizes = self
+            .query_domains::<_, Vec<(NodeIndex, NodeSize)>>([(
+                domain,
+                DomainRequest::RequestNodeSizes,
+            )])
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .flat_map(|(_, shards)| shards);
+
+        let shard_key_counts = shard_sizes
+            .map(|replicas| -> ReadySetResult<usize> {
+                // Every replica of a shard holds the same keys, so we only need to look at one
+                let size = replicas
+                    .into_iter()
+                    .next()
+                    .into_iter()
+                    .flatten()
+                    .find(|(ni, _)| *ni == reader_node)
+                    .map(|(_, size)| size);
+                match size {
+                    Some(NodeSize {
+                        key_count: KeyCount::ExactKeyCount(n),
+                        ..
+                    }) => Ok(n),
+                    Some(NodeSize { key_count, .. }) => {
+                        internal!(
+                            "Reader {reader_node:?} reported an inexact key count: {key_count:?}"
+                        )
+                    }
+                    // The reader doesn't have any state in this shard yet
+                    None => Ok(0),
+                }
+            })
+            .collect::<ReadySetResult<Vec<_>>>()?;
+
+        Ok(KeyDistribution::new(shard_key_counts))
+    }
+
     /// Return a map of node indices to key counts.
     #[allow(dead_code)]
     pub(super) async fn node_sizes(&self) -> ReadySetResult<HashMap<NodeIndex, NodeSize>> {
@@ -815,10 +1291,43 @@ impl DfState {
         Ok(res)
     }
 
+    /// Returns the last time each view was read from by a client, across all workers, for all
+    /// views which have been read from since the workers hosting their readers started
+    pub(super) async fn view_reads(&self) -> ReadySetResult<HashMap<Relation, DateTime<Utc>>> {
+        let mut res: HashMap<Relation, DateTime<Utc>> = HashMap::new();
+        for worker in self.workers.values() {
+            let reads: HashMap<Relation, DateTime<Utc>> =
+                worker.rpc(WorkerRequestKind::ViewAccesses).await?;
+            for (name, last_read) in reads {
+                res.entry(name)
+                    .and_modify(|t| *t = (*t).max(last_read))
+                    .or_insert(last_read);
+            }
+        }
+        Ok(res)
+    }
+
+    /// Returns the total size, in bytes, of the materialized state of all non-base nodes in the
+    /// graph.
+    pub(super) async fn materialized_memory_usage(&self) -> ReadySetResult<usize> {
+        Ok(self
+            .node_sizes()
+            .await?
+            .into_iter()
+            .filter(|(ni, _)| {
+                self.ingredients
+                    .node_weight(*ni)
+                    .map_or(false, |n| !n.is_base())
+            })
+            .map(|(_, size)| usize::from(size.bytes))
+            .sum())
+    }
+
     // ** Modify operations **
 
     /// Perform a new query schema migration.
-    #[instrument(level = "info", name = "migrate", skip(self, f, dialect))]
+    ///
+    /// This is [`try_migrate`](Self::try_migrate) for migrations that can't fail to build.
     pub(crate) async fn migrate<F, T>(
         &mut self,
         dry_run: bool,
@@ -828,14 +1337,41 @@ impl DfState {
     where
         F: FnOnce(&mut Migration<'_>) -> T,
     {
+        self.try_migrate(dry_run, dialect, |m| Ok(f(m))).await
+    }
+
+    /// Perform a new query schema migration, but only commit it if `f` succeeds.
+    ///
+    /// If `f` returns an error the migration is dropped without being planned or applied, so that
+    /// no domains are placed for a partially-built migration. If the migration fails to apply,
+    /// [`DomainMigrationPlan::apply`] removes any domains and nodes it had already placed.
+    ///
+    /// In both cases, the in-memory graph may still contain the nodes added by the migration, so
+    /// callers must discard `self` if this returns an error.
+    #[instrument(level = "info", name = "migrate", skip(self, f, dialect))]
+    pub(crate) async fn try_migrate<F, T>(
+        &mut self,
+        dry_run: bool,
+        dialect: Dialect,
+        f: F,
+    ) -> ReadySetResult<T>
+    where
+        F: FnOnce(&mut Migration<'_>) -> ReadySetResult<T>,
+    {
+        if !dry_run {
+            self.reconcile_node_restrictions().await?;
+        }
+
         debug!("starting migration");
         gauge!(recorded::CONTROLLER_MIGRATION_IN_PROGRESS, 1.0);
         let mut m = Migration::new(self, dialect);
-        let r = f(&mut m);
-        m.commit(dry_run).await?;
-        debug!("finished migration");
+        let res = match f(&mut m) {
+            Ok(r) => m.commit(dry_run).await.map(|_| r),
+            Err(e) => Err(e),
+        };
+        debug!(succeeded = res.is_ok(), "finished migration");
         gauge!(recorded::CONTROLLER_MIGRATION_IN_PROGRESS, 0.0);
-        Ok(r)
+        res
     }
 
     /// Controls the persistence mode, and parameters related to persistence.
@@ -1126,18 +1662,52 @@ impl DfState {
         changelist: ChangeList,
         dry_run: bool,
     ) -> Result<(), ReadySetError> {
+        self.apply_recipes(vec![changelist], dry_run).await
+    }
+
+    /// Applies all of the given changelists in a single migration.
+    ///
+    /// All the changelists must use the same SQL dialect. If they were all made by the same user,
+    /// that user is recorded as the creator of any views they create.
+    async fn apply_recipes(
+        &mut self,
+        changelists: Vec<ChangeList>,
+        dry_run: bool,
+    ) -> Result<(), ReadySetError> {
+        let dialect = match changelists.first() {
+            Some(changelist) => changelist.dialect,
+            None => return Ok(()),
+        };
+        if changelists.iter().any(|c| c.dialect != dialect) {
+            unsupported!("All changelists applied in a single migration must use the same dialect");
+        }
+        let user = if changelists.iter().all(|c| c.user == changelists[0].user) {
+            changelists[0].user.clone()
+        } else {
+            None
+        };
+
         // I hate this, but there's no way around for now, as migrations
         // are super entangled with the recipe and the graph.
         let mut new = self.recipe.clone();
 
         let r = self
-            .migrate(dry_run, changelist.dialect, |mig| {
-                new.activate(mig, changelist)
-            })
-            .await?;
+            .try_migrate(dry_run, dialect, |mig| new.activate_batch(mig, changelists))
+            .await;
 
         match r {
-            Ok(_) => self.recipe = new,
+            Ok(_) => {
+                self.recipe = new;
+                self.update_view_metadata(user);
+                // Forget the desired reader replicas of any queries that were dropped, so they
+                // aren't applied to a new query that's later created with the same name
+                let views = self.views();
+                self.reader_replicas
+                    .retain(|query, _| views.contains_key(query));
+                let domains = &self.domains;
+                self.reader_replica_targets
+                    .retain(|domain_index, _| domains.contains_key(domain_index));
+            }
             Err(ref e) => {
                 tracing::
                     warn!(error = %e, "failed to apply recipe. Will retry periodically up to max_processing_mintues.");
@@ -1147,6 +1717,27 @@ impl DfState {
         r
     }
 
+    /// Bring [`view_metadata`][view_metadata] up to date with the views in the recipe, recording
+    /// any views which don't have metadata yet as having been just created by `user`, and
+    /// forgetting the metadata for any views which have been dropped.
+    ///
+    /// [view_metadata]: DfState::view_metadata
+    fn update_view_metadata(&mut self, user: Option<String>) {
+        let views = self.recipe.cache_names().cloned().collect::<HashSet<_>>();
+        self.view_metadata.retain(|name, _| views.contains(name));
+
+        let now = Utc::now();
+        for view in views {
+            self.view_metadata
+                .entry(view)
+                .or_insert_with(|| ViewMetadata {
+                    created_at: now,
+                    created_by: user.clone(),
+                    last_accessed_at: None,
+                });
+        }
+    }
+
     pub(super) async fn extend_recipe(
         &mut self,
         recipe_spec: ExtendRecipeSpec<'_>,
@@ -1175,6 +1766,14 @@ impl DfState {
         }
     }
 
+    /// Extends the recipe with all of the given changelists at once, in a single migration.
+    pub(super) async fn extend_recipe_batch(
+        &mut self,
+        changelists: Vec<ChangeList>,
+    ) -> ReadySetResult<()> {
+        self.apply_recipes(changelists, false).await
+    }
+
     pub(super) async fn remove_query(&mut self, query_name: &Relation) -> ReadySetResult<()> {
         let name = match self.recipe.resolve_alias(query_name) {
             None => return Ok(()),
@@ -1233,6 +1832,8 @@ impl DfState {
         &mut self,
         domain_nodes: &HashMap<DomainIndex, HashSet<NodeIndex>>,
     ) -> ReadySetResult<()> {
+        self.reader_replica_targets
+            .retain(|domain_index, _| !domain_nodes.contains_key(domain_index));
         let mut dmp = DomainMigrationPlan::new(self);
         let domain_nodes = domain_nodes
             .iter()
@@ -1294,6 +1895,19 @@ impl DfState {
     }
 }
 
+/// Insert an element built from each of the given constant columns of a reader into `cols` (which
+/// describes the columns stored in that reader) at the index of that constant column, to describe
+/// the columns in the rows returned from that reader
+fn with_constant_columns<T, F>(mut cols: Vec<T>, constants: &[ConstantColumn], f: F) -> Vec<T>
+where
+    F: Fn(&ConstantColumn) -> T,
+{
+    for constant in constants {
+        cols.insert(constant.index.min(cols.len()), f(constant));
+    }
+    cols
+}
+
 /// This structure acts as a wrapper for a [`DfStateReader`] in order to guarantee
 /// thread-safe access (read and writes) to ReadySet's dataflow state.
 ///
@@ -12,7 +12,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use std::{iter, thread};
 
-use chrono::NaiveDate;
+use chrono::{NaiveDate, Utc};
 use common::Index;
 use dataflow::node::special::Base;
 use dataflow::ops::grouped::aggregate::Aggregation;
@@ -27,13 +27,14 @@ use dataflow::{
 };
 use futures::StreamExt;
 use itertools::Itertools;
+use launchpad::eventually;
 use nom_sql::{parse_query, OrderType, Relation, SqlQuery};
 use readyset::consensus::{Authority, LocalAuthority, LocalAuthorityStore};
 use readyset::consistency::Timestamp;
-use readyset::internal::LocalNodeIndex;
+use readyset::internal::{IndexType, LocalNodeIndex};
 use readyset::recipe::changelist::ChangeList;
 use readyset::{KeyComparison, Modification, SchemaType, ViewPlaceholder, ViewQuery};
-use readyset_data::{DfType, DfValue, Dialect};
+use readyset_data::{Collation, DfType, DfValue, Dialect};
 use readyset_errors::ReadySetError::{MigrationPlanFailed, RpcFailed, SelectQueryCreationFailed};
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
@@ -638,6 +639,48 @@ async fn it_works_w_mat() {
     assert!(res.iter().any(|r| *r == vec![id.clone(), 6.into()]));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn lookups_report_rows_examined() {
+    let mut g = start_simple_unsharded("lookups_report_rows_examined").await;
+    let a = g
+        .migrate(|mig| {
+            let a = mig.add_base("a", make_columns(&["a", "b"]), Base::default());
+            mig.maintain_anonymous(a, &Index::hash_map(vec![0]));
+            a
+        })
+        .await;
+
+    let mut cq = g.view("a").await.unwrap();
+    let mut muta = g.table_by_index(a).await.unwrap();
+
+    muta.insert(vec![DfValue::from(1), DfValue::from(1)])
+        .await
+        .unwrap();
+    for b in 0..20 {
+        muta.insert(vec![DfValue::from(2), DfValue::from(b)])
+            .await
+            .unwrap();
+    }
+    sleep().await;
+
+    let mut rows_examined = Vec::new();
+    for key in [1, 2] {
+        let res = cq
+            .raw_lookup(ViewQuery::from((
+                vec![KeyComparison::Equal(vec1![DfValue::from(key)])],
+                true,
+                None,
+            )))
+            .await
+            .unwrap();
+        rows_examined.push(res.total_stats().unwrap().rows_examined);
+    }
+
+    // A lookup that has to read many rows is reported as examining more of them than a point
+    // lookup does
+    assert_eq!(rows_examined, vec![1, 20]);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn it_works_w_partial_mat() {
     // set up graph
@@ -798,6 +841,39 @@ async fn it_works_deletion() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn table_lookup() {
+    let mut g = start_simple_unsharded("table_lookup").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t1 (id int PRIMARY KEY, x int, y int);",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    let mut t = g.table("t1").await.unwrap();
+
+    t.insert_many(vec![
+        vec![DfValue::from(1), DfValue::from(2), DfValue::from(3)],
+        vec![DfValue::from(2), DfValue::from(4), DfValue::from(6)],
+    ])
+    .await
+    .unwrap();
+
+    // Lookups are processed by the base table's domain in order with writes, so they see the
+    // results of any writes that have already been acknowledged
+    assert_eq!(
+        t.lookup(vec![DfValue::from(2)]).await.unwrap(),
+        vec![vec![DfValue::from(2), DfValue::from(4), DfValue::from(6)]]
+    );
+    assert!(t.lookup(vec![DfValue::from(3)]).await.unwrap().is_empty());
+    t.lookup(vec![DfValue::from(1), DfValue::from(2)])
+        .await
+        .unwrap_err();
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn delete_row() {
     let mut g = start_simple_unsharded("delete_row").await;
@@ -835,6 +911,133 @@ async fn delete_row() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn resnapshot_table_requires_upstream() {
+    let mut g = start_simple_unsharded("resnapshot_table_requires_upstream").await;
+    g.extend_recipe(
+        ChangeList::from_str("CREATE TABLE t1 (x int);", Dialect::DEFAULT_MYSQL).unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let err = g.resnapshot_table("t1".into()).await.unwrap_err();
+    assert!(err.caused_by_unsupported(), "{err}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn view_metadata_records_creation() {
+    let mut g = start_simple_unsharded("view_metadata_records_creation").await;
+    let before = Utc::now();
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t1 (x int);
+             CREATE CACHE q FROM SELECT * FROM t1;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap()
+        .with_user("alice".to_owned()),
+    )
+    .await
+    .unwrap();
+    let after = Utc::now();
+
+    let metadata = g.view_metadata().await.unwrap();
+    assert_eq!(
+        metadata.keys().collect::<Vec<_>>(),
+        vec![&Relation::from("q")]
+    );
+    let q = &metadata[&Relation::from("q")];
+    assert!(q.created_at >= before && q.created_at <= after, "{q:?}");
+    assert_eq!(q.created_by.as_deref(), Some("alice"));
+    assert_eq!(q.last_accessed_at, None);
+
+    let mut view = g.view("q").await.unwrap();
+    let q = &g.view_metadata().await.unwrap()[&Relation::from("q")];
+    let looked_up_at = q.last_accessed_at.unwrap();
+    assert!(looked_up_at >= q.created_at, "{q:?}");
+
+    // Reading from the view directly, without looking it up again, also counts as an access
+    sleep().await;
+    let before_read = Utc::now();
+    view.lookup(&[0.into()], true).await.unwrap();
+    let q = &g.view_metadata().await.unwrap()[&Relation::from("q")];
+    assert!(q.last_accessed_at.unwrap() > looked_up_at, "{q:?}");
+    assert!(
+        q.last_accessed_at.unwrap() >= before_read - chrono::Duration::milliseconds(1),
+        "{q:?}"
+    );
+
+    g.remove_query(&"q".into()).await.unwrap();
+    assert!(g.view_metadata().await.unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn orphaned_domains_after_removing_query() {
+    let mut g = start_simple_unsharded("orphaned_domains_after_removing_query").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t1 (x int);
+             CREATE CACHE q FROM SELECT * FROM t1 WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert!(g.orphaned_domains().await.unwrap().is_empty());
+
+    // Removing the query removes all the nodes in the reader's domain, but leaves the domain
+    // itself running
+    g.remove_query(&"q".into()).await.unwrap();
+    assert!(!g.orphaned_domains().await.unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn keyless_table_rejects_keyed_operations() {
+    let mut g = start_simple_unsharded("keyless_table_rejects_keyed_operations").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t1 (x int, y int);
+             CREATE CACHE all_rows FROM SELECT * FROM t1;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    let mut t = g.table("t1").await.unwrap();
+    let mut all_rows = g.view("all_rows").await.unwrap();
+
+    t.insert_many(vec![
+        vec![DfValue::from(1), DfValue::from(2)],
+        vec![DfValue::from(3), DfValue::from(4)],
+    ])
+    .await
+    .unwrap();
+
+    let err = t.delete(vec![DfValue::from(1)]).await.unwrap_err();
+    assert!(err.caused_by_no_primary_key(), "{err}");
+
+    let err = t
+        .update(
+            vec![DfValue::from(1)],
+            vec![(1, Modification::Set(DfValue::from(5)))],
+        )
+        .await
+        .unwrap_err();
+    assert!(err.caused_by_no_primary_key(), "{err}");
+
+    // Deleting whole rows still works
+    t.delete_row(vec![DfValue::from(1), DfValue::from(2)])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        all_rows.lookup(&[0.into()], true).await.unwrap().into_vec(),
+        vec![vec![DfValue::from(3), DfValue::from(4)]]
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn it_works_with_sql_recipe() {
     let mut g = start_simple_unsharded("it_works_with_sql_recipe").await;
@@ -1279,6 +1482,38 @@ async fn it_recovers_persisted_bases() {
     drop(g);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn leader_term_increments_on_new_leadership() {
+    let authority_store = Arc::new(LocalAuthorityStore::new());
+    let authority = Arc::new(Authority::from(LocalAuthority::new_with_store(
+        authority_store.clone(),
+    )));
+
+    let first_term = {
+        let mut g = Builder::for_tests().start(authority.clone()).await.unwrap();
+        g.backend_ready().await;
+
+        let info = g.leader_info().await.unwrap();
+        assert_eq!(info.controller_uri, g.controller_uri().await.unwrap());
+
+        g.shutdown();
+        g.wait_done().await;
+        if let Authority::LocalAuthority(l) = authority.as_ref() {
+            l.delete_ephemeral();
+        }
+        info.term
+    };
+
+    let authority = Arc::new(Authority::from(LocalAuthority::new_with_store(
+        authority_store,
+    )));
+    let mut g = Builder::for_tests().start(authority).await.unwrap();
+    g.backend_ready().await;
+
+    let info = g.leader_info().await.unwrap();
+    assert_eq!(info.term, first_term + 1);
+}
+
 // TODO(ENG-860): Flaky test.
 #[tokio::test(flavor = "multi_thread")]
 async fn it_recovers_persisted_bases_with_volume_id() {
@@ -1429,14 +1664,14 @@ async fn mutator_churn() {
                 "votecount",
                 make_columns(&["id", "votes"]),
                 Aggregation::Count
-                    .over(vote, 0, &[1], &DfType::Unknown)
+                    .over(vote, 0, &[1], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
 
             mig.maintain_anonymous_with_reader_processing(
                 vc,
                 &Index::hash_map(vec![0]),
-                ReaderProcessing::new(None, None, Some(vec![0, 1]), None, None).unwrap(),
+                ReaderProcessing::new(None, None, Some(vec![0, 1]), None, None, vec![]).unwrap(),
             );
             vote
         })
@@ -1898,7 +2133,7 @@ async fn votes() {
                 "vc",
                 make_columns(&["id", "votes"]),
                 Aggregation::Count
-                    .over(vote, 0, &[1], &DfType::Unknown)
+                    .over(vote, 0, &[1], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
             mig.maintain_anonymous(vc, &Index::hash_map(vec![0]));
@@ -2539,13 +2774,13 @@ async fn cascading_replays_with_sharding() {
             // aggregate over the join. this will force a shard merger to be inserted because the
             // group-by column ("f2") isn't the same as the join's output sharding column ("f1"/"u")
             let a = Aggregation::Count
-                .over(j, 0, &[2], &DfType::Unknown)
+                .over(j, 0, &[2], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                 .unwrap();
             let end = mig.add_ingredient("end", make_columns(&["u", "c"]), a);
             mig.maintain_anonymous_with_reader_processing(
                 end,
                 &Index::hash_map(vec![0]),
-                ReaderProcessing::new(None, None, Some(vec![0, 1]), None, None).unwrap(),
+                ReaderProcessing::new(None, None, Some(vec![0, 1]), None, None, vec![]).unwrap(),
             );
             (j, end)
         })
@@ -2673,13 +2908,13 @@ async fn full_aggregation_with_bogokey() {
                 "agg",
                 make_columns(&["bogo", "count"]),
                 Aggregation::Count
-                    .over(bogo, 0, &[1], &DfType::Unknown)
+                    .over(bogo, 0, &[1], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
             mig.maintain_anonymous_with_reader_processing(
                 agg,
                 &Index::hash_map(vec![0]),
-                ReaderProcessing::new(None, None, Some(vec![0, 1]), None, None).unwrap(),
+                ReaderProcessing::new(None, None, Some(vec![0, 1]), None, None, vec![]).unwrap(),
             );
             agg
         })
@@ -2800,7 +3035,7 @@ async fn materialization_frontier() {
                 "votecount",
                 make_columns(&["id", "votes"]),
                 Aggregation::Count
-                    .over(vote, 0, &[1], &DfType::Unknown)
+                    .over(vote, 0, &[1], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
             mig.mark_shallow(vc);
@@ -3110,7 +3345,7 @@ async fn do_full_vote_migration(sharded: bool, old_puts_after: bool) {
                 "votecount",
                 make_columns(&["id", "votes"]),
                 Aggregation::Count
-                    .over(vote, 0, &[1], &DfType::Unknown)
+                    .over(vote, 0, &[1], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
 
@@ -3167,7 +3402,7 @@ async fn do_full_vote_migration(sharded: bool, old_puts_after: bool) {
                 "rsum",
                 make_columns(&["id", "total"]),
                 Aggregation::Sum
-                    .over(rating, 2, &[1], &DfType::Unknown)
+                    .over(rating, 2, &[1], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
 
@@ -3259,14 +3494,14 @@ async fn live_writes() {
                 "votecount",
                 make_columns(&["id", "votes"]),
                 Aggregation::Count
-                    .over(vote, 0, &[1], &DfType::Unknown)
+                    .over(vote, 0, &[1], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
 
             mig.maintain_anonymous_with_reader_processing(
                 vc,
                 &Index::hash_map(vec![0]),
-                ReaderProcessing::new(None, None, Some(vec![0, 1]), None, None).unwrap(),
+                ReaderProcessing::new(None, None, Some(vec![0, 1]), None, None, vec![]).unwrap(),
             );
             (vote, vc)
         })
@@ -3298,13 +3533,13 @@ async fn live_writes() {
                 "votecount2",
                 make_columns(&["id", "votes"]),
                 Aggregation::Sum
-                    .over(vc, 1, &[0], &DfType::Unknown)
+                    .over(vc, 1, &[0], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
             mig.maintain_anonymous_with_reader_processing(
                 vc2,
                 &Index::hash_map(vec![0]),
-                ReaderProcessing::new(None, None, Some(vec![0, 1]), None, None).unwrap(),
+                ReaderProcessing::new(None, None, Some(vec![0, 1]), None, None, vec![]).unwrap(),
             );
             vc2
         })
@@ -4076,6 +4311,7 @@ async fn between_parametrized() {
 
     let mut q = g.view("q").await.unwrap();
     assert_eq!(q.key_map(), &[(ViewPlaceholder::Between(1, 2), 0)]);
+    assert_eq!(q.index_type(), Some(IndexType::BTreeMap));
 
     let expected: Vec<Vec<DfValue>> = (3..6).map(|i| vec![DfValue::from(i)]).collect();
     let rows = q
@@ -4091,6 +4327,26 @@ async fn between_parametrized() {
     assert_eq!(rows, expected);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn equality_view_index_type() {
+    let mut g = start_simple_unsharded("equality_view_index_type").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE things (id INT, bigness INT);
+             CREATE CACHE q FROM SELECT bigness FROM things WHERE id = $1;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let q = g.view("q").await.unwrap();
+    assert_eq!(q.key_map(), &[(ViewPlaceholder::OneToOne(1), 0)]);
+    assert_eq!(q.index_type(), Some(IndexType::HashMap));
+}
+
 // TODO(grfn): This doesn't work because top-level disjunction between
 // parameters doesn't work, and the query gets rewritten to:
 //   SELECT bigness FROM things WHERE bigness < ? OR bigness > ?
@@ -4186,6 +4442,59 @@ async fn topk_updates() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn topk_without_order_by_primary_key() {
+    let mut g = start_simple_unsharded("topk_without_order_by_primary_key").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE posts (id INTEGER PRIMARY KEY, number INTEGER);
+
+         CREATE CACHE some_posts FROM
+         SELECT number FROM posts LIMIT 10;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut posts = g.table("posts").await.unwrap();
+    let mut some_posts = g.view("some_posts").await.unwrap();
+
+    // Insert in the opposite order to the primary key, so that we'd get the wrong rows if we
+    // returned whatever was written first
+    posts
+        .insert_many((1..=20).rev().map(|i| vec![i.into(), (i * 10).into()]))
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    let expected = (1..=10)
+        .map(|i| vec![DfValue::from(i * 10)])
+        .collect::<Vec<_>>();
+    for _ in 0..5 {
+        let res = some_posts.lookup(&[0.into()], true).await.unwrap();
+        let mut rows: Vec<Vec<DfValue>> = res.into();
+        rows.sort();
+        assert_eq!(rows, expected);
+    }
+
+    posts.delete(vec![1.into()]).await.unwrap();
+
+    sleep().await;
+
+    let res = some_posts.lookup(&[0.into()], true).await.unwrap();
+    let mut rows: Vec<Vec<DfValue>> = res.into();
+    rows.sort();
+    assert_eq!(
+        rows,
+        (2..=11)
+            .map(|i| vec![DfValue::from(i * 10)])
+            .collect::<Vec<_>>()
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn simple_pagination() {
     let mut g = start_simple_unsharded("simple_pagination").await;
@@ -5078,6 +5387,7 @@ async fn post_read_ilike() {
                     None,
                     None,
                     None,
+                    vec![],
                 )
                 .unwrap(),
             );
@@ -5208,6 +5518,54 @@ async fn aggregate_expression() {
     assert_eq!(get_col!(q, res, "max_num"), &DfValue::from(100));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn case_insensitive_collation() {
+    let mut g = start_simple_unsharded("case_insensitive_collation").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id INT PRIMARY KEY, name TEXT COLLATE utf8mb4_general_ci);
+         CREATE CACHE q FROM SELECT id, name FROM t WHERE name = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    let mut q = g.view("q").await.unwrap();
+
+    t.insert(vec![DfValue::from(1), DfValue::from("ABC")])
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    let key_type = q
+        .schema()
+        .unwrap()
+        .col_types([1], SchemaType::ProjectedSchema)
+        .unwrap()[0]
+        .clone();
+    assert_eq!(key_type, DfType::Text(Collation::Citext));
+
+    let res = q
+        .lookup(
+            &[DfValue::from("abc")
+                .coerce_to(&key_type, &DfType::Unknown)
+                .unwrap()],
+            true,
+        )
+        .await
+        .unwrap()
+        .into_vec();
+
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0][0], DfValue::from(1));
+    assert_eq!(res[0][1], DfValue::from("ABC"));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn aggregate_missing_columns() {
     let mut g = start_simple_unsharded("aggregate_missing_columns").await;
@@ -5288,20 +5646,20 @@ async fn post_join_filter() {
 }
 
 #[tokio::test(flavor = "multi_thread")]
-/// Tests the case where two tables have the same column name and those columns are
-/// used in a post-join filter.
-async fn duplicate_column_names() {
-    let mut g = start_simple_unsharded("duplicate_column_names").await;
+async fn or_across_joined_tables() {
+    let mut g = start_simple_unsharded("or_across_joined_tables").await;
 
     g.extend_recipe(
         ChangeList::from_str(
-            "CREATE TABLE t1 (id int, val int);
-         CREATE TABLE t2 (id int, val int);
+            "CREATE TABLE t1 (id int, x int);
+         CREATE TABLE t2 (id int, y int);
+         CREATE TABLE t3 (id int, z int);
          CREATE CACHE q FROM
-            SELECT t1.id AS id_1, t1.val AS val_1, t2.val AS val_2
+            SELECT t1.id AS id, t3.z AS z
             FROM t1
             JOIN t2 ON t1.id = t2.id
-            WHERE t1.val >= t2.val",
+            LEFT JOIN t3 ON t1.id = t3.id
+            WHERE t1.x = 1 OR t2.y = 2",
             Dialect::DEFAULT_MYSQL,
         )
         .unwrap(),
@@ -5311,22 +5669,28 @@ async fn duplicate_column_names() {
 
     let mut t1 = g.table("t1").await.unwrap();
     let mut t2 = g.table("t2").await.unwrap();
+    let mut t3 = g.table("t3").await.unwrap();
     let mut q = g.view("q").await.unwrap();
 
     t1.insert_many(vec![
         vec![DfValue::from(1), DfValue::from(1)],
-        vec![DfValue::from(2), DfValue::from(2)],
-        vec![DfValue::from(3), DfValue::from(3)],
-        vec![DfValue::from(4), DfValue::from(4)],
+        vec![DfValue::from(2), DfValue::from(0)],
+        vec![DfValue::from(3), DfValue::from(0)],
     ])
     .await
     .unwrap();
 
     t2.insert_many(vec![
-        vec![DfValue::from(1), DfValue::from(1)],
-        vec![DfValue::from(2), DfValue::from(1)],
-        vec![DfValue::from(2), DfValue::from(5)],
-        vec![DfValue::from(3), DfValue::from(5)],
+        vec![DfValue::from(1), DfValue::from(0)],
+        vec![DfValue::from(2), DfValue::from(2)],
+        vec![DfValue::from(3), DfValue::from(0)],
+    ])
+    .await
+    .unwrap();
+
+    t3.insert_many(vec![
+        vec![DfValue::from(1), DfValue::from(10)],
+        vec![DfValue::from(3), DfValue::from(30)],
     ])
     .await
     .unwrap();
@@ -5338,21 +5702,76 @@ async fn duplicate_column_names() {
 
     assert_eq!(
         res,
-        vec![
-            vec![1.into(), 1.into(), 1.into()],
-            vec![2.into(), 2.into(), 1.into()]
-        ]
+        vec![vec![1.into(), 10.into()], vec![2.into(), DfValue::None],]
     );
 }
+
 #[tokio::test(flavor = "multi_thread")]
-async fn filter_on_expression() {
-    let mut g = start_simple_unsharded("filter_on_expression").await;
+/// Tests the case where two tables have the same column name and those columns are
+/// used in a post-join filter.
+async fn duplicate_column_names() {
+    let mut g = start_simple_unsharded("duplicate_column_names").await;
 
     g.extend_recipe(
         ChangeList::from_str(
-            "CREATE TABLE users (id int, birthday date);
-         CREATE CACHE friday_babies FROM SELECT id FROM users WHERE dayofweek(birthday) = 6;",
-            Dialect::DEFAULT_MYSQL,
+            "CREATE TABLE t1 (id int, val int);
+         CREATE TABLE t2 (id int, val int);
+         CREATE CACHE q FROM
+            SELECT t1.id AS id_1, t1.val AS val_1, t2.val AS val_2
+            FROM t1
+            JOIN t2 ON t1.id = t2.id
+            WHERE t1.val >= t2.val",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t1 = g.table("t1").await.unwrap();
+    let mut t2 = g.table("t2").await.unwrap();
+    let mut q = g.view("q").await.unwrap();
+
+    t1.insert_many(vec![
+        vec![DfValue::from(1), DfValue::from(1)],
+        vec![DfValue::from(2), DfValue::from(2)],
+        vec![DfValue::from(3), DfValue::from(3)],
+        vec![DfValue::from(4), DfValue::from(4)],
+    ])
+    .await
+    .unwrap();
+
+    t2.insert_many(vec![
+        vec![DfValue::from(1), DfValue::from(1)],
+        vec![DfValue::from(2), DfValue::from(1)],
+        vec![DfValue::from(2), DfValue::from(5)],
+        vec![DfValue::from(3), DfValue::from(5)],
+    ])
+    .await
+    .unwrap();
+
+    sleep().await;
+
+    let mut res: Vec<_> = q.lookup(&[0.into()], true).await.unwrap().into();
+    res.sort();
+
+    assert_eq!(
+        res,
+        vec![
+            vec![1.into(), 1.into(), 1.into()],
+            vec![2.into(), 2.into(), 1.into()]
+        ]
+    );
+}
+#[tokio::test(flavor = "multi_thread")]
+async fn filter_on_expression() {
+    let mut g = start_simple_unsharded("filter_on_expression").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE users (id int, birthday date);
+         CREATE CACHE friday_babies FROM SELECT id FROM users WHERE dayofweek(birthday) = 6;",
+            Dialect::DEFAULT_MYSQL,
         )
         .unwrap(),
     )
@@ -5512,6 +5931,51 @@ async fn left_join_null() {
     assert_eq!(num_res, 2);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn null_key_lookup_matches_nothing() {
+    let mut g = start_simple_unsharded("null_key_lookup_matches_nothing").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id int, x int);
+             CREATE CACHE q FROM SELECT id, x FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    let mut q = g.view("q").await.unwrap();
+
+    t.insert_many(vec![
+        vec![DfValue::from(1), DfValue::None],
+        vec![DfValue::from(2), DfValue::from(5)],
+    ])
+    .await
+    .unwrap();
+
+    sleep().await;
+
+    // `x = NULL` is never true, even for rows where `x` is NULL
+    let res = q.lookup(&[DfValue::None], true).await.unwrap().into_vec();
+    assert!(res.is_empty(), "{res:?}");
+
+    let res = q
+        .multi_lookup(
+            vec![
+                KeyComparison::Equal(vec1![DfValue::None]),
+                KeyComparison::Equal(vec1![DfValue::from(5)]),
+            ],
+            true,
+        )
+        .await
+        .unwrap()
+        .into_vec();
+    assert_eq!(res, vec![vec![DfValue::from(2), DfValue::from(5)]]);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn overlapping_indices() {
     let mut g = start_simple_unsharded("overlapping_indices").await;
@@ -5641,6 +6105,122 @@ async fn join_simple_cte() {
     assert_eq!(get_col!(view, res[0], "name"), &DfValue::from("four"));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn join_chained_ctes() {
+    let mut g = start_simple_unsharded("join_chained_ctes").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t1 (id int, value int);
+         CREATE TABLE t2 (value int, name text);
+         CREATE CACHE with_ctes FROM
+         WITH max_val AS (SELECT max(value) as value FROM t1),
+              big_max_val AS (SELECT max_val.value FROM max_val WHERE max_val.value > 3)
+         SELECT t2.name FROM t2
+         JOIN max_val ON max_val.value = t2.value
+         JOIN big_max_val ON big_max_val.value = t2.value;
+         CREATE CACHE with_subqueries FROM
+         SELECT t2.name FROM t2
+         JOIN (SELECT max(value) as value FROM t1) max_val ON max_val.value = t2.value
+         JOIN (SELECT max_val.value
+               FROM (SELECT max(value) as value FROM t1) max_val
+               WHERE max_val.value > 3) big_max_val
+           ON big_max_val.value = t2.value;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t1 = g.table("t1").await.unwrap();
+    let mut t2 = g.table("t2").await.unwrap();
+
+    t1.insert_many(vec![
+        vec![DfValue::from(1i32), DfValue::from(2i32)],
+        vec![DfValue::from(1i32), DfValue::from(4i32)],
+    ])
+    .await
+    .unwrap();
+
+    t2.insert_many(vec![
+        vec![DfValue::from(2i32), DfValue::from("two")],
+        vec![DfValue::from(4i32), DfValue::from("four")],
+    ])
+    .await
+    .unwrap();
+
+    sleep().await;
+
+    let mut with_ctes = g.view("with_ctes").await.unwrap();
+    let res = with_ctes
+        .lookup(&[0i32.into()], true)
+        .await
+        .unwrap()
+        .into_vec();
+    assert_eq!(res.len(), 1);
+    assert_eq!(get_col!(with_ctes, res[0], "name"), &DfValue::from("four"));
+
+    let mut with_subqueries = g.view("with_subqueries").await.unwrap();
+    let subquery_res = with_subqueries
+        .lookup(&[0i32.into()], true)
+        .await
+        .unwrap()
+        .into_vec();
+    assert_eq!(res, subquery_res);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn in_subquery() {
+    let mut g = start_simple_unsharded("in_subquery").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE users (id int, name text);
+         CREATE TABLE perms (user_id int, perm text);
+         CREATE CACHE permitted FROM
+         SELECT users.name FROM users
+         WHERE users.id IN (SELECT perms.user_id FROM perms WHERE perms.perm = 'admin');",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut users = g.table("users").await.unwrap();
+    let mut perms = g.table("perms").await.unwrap();
+
+    users
+        .insert_many(vec![
+            vec![DfValue::from(1i32), DfValue::from("alice")],
+            vec![DfValue::from(2i32), DfValue::from("bob")],
+        ])
+        .await
+        .unwrap();
+
+    // Duplicate rows in the subquery shouldn't duplicate rows in the result
+    perms
+        .insert_many(vec![
+            vec![DfValue::from(1i32), DfValue::from("admin")],
+            vec![DfValue::from(1i32), DfValue::from("admin")],
+            vec![DfValue::from(2i32), DfValue::from("read")],
+        ])
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    let mut permitted = g.view("permitted").await.unwrap();
+    let res = permitted
+        .lookup(&[0i32.into()], true)
+        .await
+        .unwrap()
+        .into_vec();
+    assert_eq!(res.len(), 1);
+    assert_eq!(get_col!(permitted, res[0], "name"), &DfValue::from("alice"));
+}
+
 // multiple_aggregate_sum tests multiple aggregators of the same type, in this case sum(),
 // operating over different columns from the same table.
 #[tokio::test(flavor = "multi_thread")]
@@ -6158,6 +6738,114 @@ async fn col_beginning_with_literal() {
     // TODO Verify that q1 returns correct values.
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn constant_projection() {
+    let mut g = start_simple_unsharded("constant_projection").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id INT, value INT);
+             CREATE CACHE q FROM SELECT 'const' AS c, t.value FROM t WHERE t.id = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut mutator = g.table("t").await.unwrap();
+    mutator
+        .insert_many(vec![
+            vec![DfValue::from(1), DfValue::from(10)],
+            vec![DfValue::from(1), DfValue::from(11)],
+            vec![DfValue::from(2), DfValue::from(20)],
+        ])
+        .await
+        .unwrap();
+    sleep().await;
+
+    let mut q = g.view("q").await.unwrap();
+    assert_eq!(q.columns(), &["c", "value"]);
+
+    let mut res = q.lookup(&[1.into()], true).await.unwrap().into_vec();
+    res.sort();
+    assert_eq!(
+        res,
+        vec![
+            vec![DfValue::from("const"), DfValue::from(10)],
+            vec![DfValue::from("const"), DfValue::from(11)],
+        ]
+    );
+    assert_eq!(
+        q.lookup(&[2.into()], true).await.unwrap().into_vec(),
+        vec![vec![DfValue::from("const"), DfValue::from(20)]]
+    );
+
+    // The constant is returned, but never stored in the reader
+    let schema = q.schema().unwrap();
+    assert_eq!(
+        schema
+            .schema(SchemaType::ReturnedSchema)
+            .iter()
+            .map(|cs| cs.column.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["c", "value"]
+    );
+    assert!(schema
+        .schema(SchemaType::ProjectedSchema)
+        .iter()
+        .all(|cs| cs.column.name != "c"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn order_by_unselected_column() {
+    let mut g = start_simple_unsharded("order_by_unselected_column").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (a INT, b INT, c INT, k INT);
+             CREATE CACHE q FROM SELECT t.b, t.a FROM t WHERE t.k = ? ORDER BY t.c;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut mutator = g.table("t").await.unwrap();
+    mutator
+        .insert_many(vec![
+            vec![1.into(), 10.into(), 3.into(), 1.into()],
+            vec![2.into(), 20.into(), 1.into(), 1.into()],
+            vec![3.into(), 30.into(), 2.into(), 1.into()],
+        ])
+        .await
+        .unwrap();
+    sleep().await;
+
+    // Only the selected columns are returned, in the order they were selected - not the column
+    // the results are ordered by
+    let mut q = g.view("q").await.unwrap();
+    assert_eq!(q.columns(), &["b", "a"]);
+    assert_eq!(
+        q.schema()
+            .unwrap()
+            .schema(SchemaType::ReturnedSchema)
+            .iter()
+            .map(|cs| cs.column.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["b", "a"]
+    );
+
+    assert_eq!(
+        q.lookup(&[1.into()], true).await.unwrap().into_vec(),
+        vec![
+            vec![DfValue::from(20), DfValue::from(2)],
+            vec![DfValue::from(30), DfValue::from(3)],
+            vec![DfValue::from(10), DfValue::from(1)],
+        ]
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn simple_enum() {
     let mut g = start_simple_unsharded("simple_enum").await;
@@ -6893,6 +7581,51 @@ async fn distinct_select_with_join() {
     assert_eq!(res, vec![(10, 20)]);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn natural_join() {
+    let mut g = start_simple_unsharded("natural_join").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t1 (id int, x int, y int);
+             CREATE TABLE t2 (y int, id int, z int);
+             CREATE CACHE q FROM SELECT t1.x, t2.z FROM t1 NATURAL JOIN t2 WHERE t1.x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t1 = g.table("t1").await.unwrap();
+    let mut t2 = g.table("t2").await.unwrap();
+    let mut q = g.view("q").await.unwrap();
+
+    t1.insert_many(vec![
+        vec![1.into(), 10.into(), 100.into()],
+        vec![2.into(), 10.into(), 200.into()],
+    ])
+    .await
+    .unwrap();
+    t2.insert_many(vec![
+        // Matches on both id and y
+        vec![100.into(), 1.into(), 1000.into()],
+        // Only matches on id
+        vec![300.into(), 2.into(), 2000.into()],
+    ])
+    .await
+    .unwrap();
+
+    sleep().await;
+
+    let rows = q.lookup(&[10.into()], true).await.unwrap();
+    let res = rows
+        .into_iter()
+        .map(|r| (get_col!(q, r, "x", i32), get_col!(q, r, "z", i32)))
+        .collect::<Vec<(i32, i32)>>();
+
+    assert_eq!(res, vec![(10, 1000)]);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[ignore = "Ignoring sharded tests"]
 async fn distinct_select_with_join_sharded() {
@@ -7239,6 +7972,110 @@ async fn assign_nonreader_domains_to_nonreader_workers() {
     assert!(matches!(result, Ok(_)));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn node_restrictions_are_honored_by_placement() {
+    readyset_tracing::init_test_logging();
+    let authority_store = Arc::new(LocalAuthorityStore::new());
+    let w1_authority = Arc::new(Authority::from(LocalAuthority::new_with_store(
+        authority_store.clone(),
+    )));
+    let w2_authority = Arc::new(Authority::from(LocalAuthority::new_with_store(
+        authority_store,
+    )));
+    let cluster_name = "node_restrictions_are_honored_by_placement";
+
+    let mut builder = Builder::for_tests();
+    builder.set_sharding(None);
+    builder.set_persistence(get_persistence_params(cluster_name));
+    builder.set_volume_id("v1".into());
+    let mut w1 = builder.start_local_custom(w1_authority).await.unwrap();
+
+    let mut builder = Builder::for_tests();
+    builder.set_sharding(None);
+    builder.set_persistence(get_persistence_params(cluster_name));
+    builder.set_volume_id("v2".into());
+    let _w2 = builder.start(w2_authority).await.unwrap();
+
+    sleep().await;
+    w1.backend_ready().await;
+
+    let restriction = |node_name: &str, volume: &str| readyset::NodeRestriction {
+        node_name: node_name.into(),
+        shard: 0,
+        worker_volume: Some(volume.into()),
+    };
+    w1.set_node_restrictions(vec![restriction("t1", "v1"), restriction("t2", "v2")])
+        .await
+        .unwrap();
+    assert_eq!(
+        w1.node_restrictions().await.unwrap(),
+        vec![restriction("t1", "v1"), restriction("t2", "v2")]
+    );
+
+    w1.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t1 (id int, PRIMARY KEY(id));
+             CREATE TABLE t2 (id int, PRIMARY KEY(id));",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // Returns the worker running the domain of the base table with the given name
+    let controller_url = w1.get_address().clone();
+    let workers = w1.workers().await.unwrap();
+    let worker_for_table = |table: &'static str| {
+        let controller_url = controller_url.clone();
+        let workers = workers.clone();
+        async move {
+            for worker in workers {
+                let url = controller_url.join(&format!("nodes?w={worker}")).unwrap();
+                let body = reqwest::get(url).await.unwrap().bytes().await.unwrap();
+                let nodes = bincode::deserialize::<
+                    Vec<(dataflow::prelude::NodeIndex, Relation, String)>,
+                >(&body)
+                .unwrap();
+                if nodes
+                    .iter()
+                    .any(|(_, name, _)| *name == Relation::from(table))
+                {
+                    return worker;
+                }
+            }
+            panic!("Table {table} isn't running on any worker");
+        }
+    };
+
+    let v1_worker = worker_for_table("t1").await;
+    let v2_worker = worker_for_table("t2").await;
+    assert_ne!(v1_worker, v2_worker);
+
+    // Setting a restriction merges it into the existing restrictions
+    w1.set_node_restrictions(vec![restriction("t1", "v2")])
+        .await
+        .unwrap();
+    assert_eq!(
+        w1.node_restrictions().await.unwrap(),
+        vec![restriction("t1", "v2"), restriction("t2", "v2")]
+    );
+
+    // Running domains are only moved by the next migration
+    assert_eq!(worker_for_table("t1").await, v1_worker);
+    w1.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t3 (id int, PRIMARY KEY(id));",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(worker_for_table("t1").await, v2_worker);
+    assert_eq!(worker_for_table("t2").await, v2_worker);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn join_straddled_columns() {
     let mut g = start_simple_unsharded("join_straddled_columns").await;
@@ -9255,3 +10092,266 @@ async fn multiple_schemas_explicit() {
         vec![vec![DfValue::from("schema_1"), DfValue::from("schema_2")]]
     );
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn materialized_memory_budget() {
+    readyset_tracing::init_test_logging();
+    let mut builder = Builder::for_tests();
+    builder.set_sharding(None);
+    builder.set_persistence(get_persistence_params("materialized_memory_budget"));
+    builder.set_materialized_memory_budget(Some(1));
+    let mut g = builder.start_local().await.unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id INT PRIMARY KEY, name TEXT);
+             CREATE CACHE q1 FROM SELECT name FROM t WHERE id = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    t.insert(vec![DfValue::from(1), DfValue::from("a")])
+        .await
+        .unwrap();
+    sleep().await;
+
+    // Filling the reader for q1 puts the caches over the (tiny) budget
+    let mut q1 = g.view("q1").await.unwrap();
+    let res = q1.lookup(&[1.into()], true).await.unwrap().into_vec();
+    assert_eq!(res, vec![vec![DfValue::from("a")]]);
+
+    let err = g
+        .extend_recipe(
+            ChangeList::from_str(
+                "CREATE CACHE q2 FROM SELECT id FROM t WHERE name = ?;",
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.caused_by_materialized_memory_budget_exceeded(), "{err}");
+    assert!(g.view("q2").await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn queries_for_table() {
+    let mut builder = Builder::for_tests();
+    builder.set_sharding(None);
+    builder.set_persistence(get_persistence_params("queries_for_table"));
+    let mut g = builder.start_local().await.unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t1 (id INT PRIMARY KEY, name TEXT);
+             CREATE TABLE t2 (id INT PRIMARY KEY, value INT);
+             CREATE CACHE q1 FROM SELECT name FROM t1 WHERE id = ?;
+             CREATE CACHE q2 FROM SELECT id FROM t1 WHERE name = ?;
+             CREATE CACHE q3 FROM SELECT value FROM t2 WHERE id = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        g.queries_for_table("t1".into()).await.unwrap(),
+        vec![Relation::from("q1"), Relation::from("q2")]
+    );
+    assert_eq!(
+        g.queries_for_table("t2".into()).await.unwrap(),
+        vec![Relation::from("q3")]
+    );
+    assert!(g
+        .queries_for_table("q1".into())
+        .await
+        .unwrap_err()
+        .caused_by_table_not_found());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn materialization_progress() {
+    let mut builder = Builder::for_tests();
+    builder.set_sharding(None);
+    builder.set_persistence(get_persistence_params("materialization_progress"));
+    let mut g = builder.start_local().await.unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t1 (id INT PRIMARY KEY, name TEXT);
+             CREATE TABLE t2 (id INT PRIMARY KEY, value INT);
+             CREATE CACHE q1 FROM SELECT t1.name, t2.value FROM t1 JOIN t2 ON t1.id = t2.id \
+                WHERE t1.id = ?;
+             CREATE CACHE q2 FROM SELECT value FROM t2 WHERE id = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t1 = g.table("t1").await.unwrap();
+    t1.set_snapshot_mode(true).await.unwrap();
+    sleep().await;
+
+    let progress = g.materialization_progress("q1".into()).await.unwrap();
+    assert_eq!(progress.total_tables, 2);
+    assert_eq!(progress.snapshotted_tables, 1);
+    assert!(progress.fraction() < 1.0);
+    assert!(!progress.is_snapshot_complete());
+
+    let progress = g.materialization_progress("q2".into()).await.unwrap();
+    assert!(progress.is_snapshot_complete());
+
+    t1.set_snapshot_mode(false).await.unwrap();
+    sleep().await;
+
+    let progress = g.materialization_progress("q1".into()).await.unwrap();
+    assert_eq!(progress.fraction(), 1.0);
+    assert!(progress.is_snapshot_complete());
+
+    assert!(g
+        .materialization_progress("nonexistent".into())
+        .await
+        .unwrap_err()
+        .caused_by_view_not_found());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "Ignoring sharded tests"]
+async fn key_distribution() {
+    let mut g = start_simple("key_distribution").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id INT PRIMARY KEY, k INT);
+             CREATE CACHE q FROM SELECT id FROM t WHERE k = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // Deliberately pick keys which all hash to the same shard
+    let keys = (0..)
+        .map(DfValue::from)
+        .filter(|k| readyset::shard_by(k, DEFAULT_SHARDING) == 0)
+        .take(10)
+        .collect::<Vec<_>>();
+
+    let mut t = g.table("t").await.unwrap();
+    t.insert_many(
+        keys.iter()
+            .enumerate()
+            .map(|(id, k)| vec![DfValue::from(id as i32), k.clone()]),
+    )
+    .await
+    .unwrap();
+    sleep().await;
+
+    let mut q = g.view("q").await.unwrap();
+    for k in &keys {
+        q.lookup(&[k.clone()], true).await.unwrap();
+    }
+
+    // Every key is in the first shard, and none are in any of the others
+    let mut expected_counts = vec![0; DEFAULT_SHARDING];
+    expected_counts[0] = keys.len();
+
+    eventually! {
+        let distribution = g.key_distribution("q".into()).await.unwrap();
+        distribution.shard_key_counts == expected_counts
+            && distribution.skew == DEFAULT_SHARDING as f64
+    }
+
+    assert!(g
+        .key_distribution("nonexistent".into())
+        .await
+        .unwrap_err()
+        .caused_by_view_not_found());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn numeric_round_trip() {
+    let mut g = start_simple_unsharded("numeric_round_trip").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id INT, d DECIMAL(30, 10));
+             CREATE CACHE q FROM SELECT t.d, t.d + t.d AS dd FROM t WHERE t.id = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let value = "123456789012345678.0123456789".parse::<Decimal>().unwrap();
+    let mut t = g.table("t").await.unwrap();
+    t.insert(vec![DfValue::from(1), DfValue::from(value)])
+        .await
+        .unwrap();
+    sleep().await;
+
+    let mut q = g.view("q").await.unwrap();
+    let res = q.lookup(&[1.into()], true).await.unwrap().into_vec();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0][0], DfValue::from(value));
+    assert_eq!(res[0][0].to_string(), "123456789012345678.0123456789");
+    assert_eq!(
+        res[0][1],
+        DfValue::from("246913578024691356.0246913578".parse::<Decimal>().unwrap())
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn extend_recipe_batch() {
+    let mut g = start_simple_unsharded("extend_recipe_batch").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id INT, value INT);",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let schema_version = g.schema_version().await.unwrap();
+
+    let changelists = (0..50)
+        .map(|i| {
+            ChangeList::from_str(
+                format!("CREATE CACHE q{i} FROM SELECT value FROM t WHERE id = {i};"),
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap()
+        })
+        .collect();
+    g.extend_recipe_batch(changelists).await.unwrap();
+
+    // All the queries were added as part of a single migration
+    assert_eq!(g.schema_version().await.unwrap(), schema_version + 1);
+
+    let mut t = g.table("t").await.unwrap();
+    t.insert(vec![DfValue::from(7), DfValue::from(70)])
+        .await
+        .unwrap();
+    sleep().await;
+
+    for i in 0..50 {
+        assert!(g.view(format!("q{i}")).await.is_ok());
+    }
+    let mut q7 = g.view("q7").await.unwrap();
+    assert_eq!(
+        q7.lookup(&[0.into()], true).await.unwrap().into_vec(),
+        vec![vec![DfValue::from(70)]]
+    );
+}
@@ -339,3 +339,79 @@ async fn test_metrics_client_impl() {
     let metrics_dump = &metrics[0].metrics;
     assert!(get_external_requests_count(metrics_dump) < second_count);
 }
+
+#[cfg(feature = "failure_injection")]
+rusty_fork_test! {
+    #[test]
+    fn failed_migration_leaves_no_orphaned_domains() {
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(failed_migration_leaves_no_orphaned_domains_impl());
+    }
+}
+
+#[cfg(feature = "failure_injection")]
+async fn failed_migration_leaves_no_orphaned_domains_impl() {
+    use readyset::failpoints;
+
+    register_metric_recorder();
+    let mut g = start_simple_unsharded("failed_migration_leaves_no_orphaned_domains").await;
+    let mut metrics_client = initialize_metrics(&mut g).await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (a int PRIMARY KEY, b int)",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let metrics = metrics_client.get_metrics().await.unwrap();
+    let metrics_dump = &metrics[0].metrics;
+    let running_domains = get_metric!(metrics_dump, recorded::WORKER_RUNNING_DOMAINS);
+    assert!(matches!(running_domains, Some(DumpedMetricValue::Gauge(v)) if v > 0.0));
+
+    // Fail the migration after the domains for the new query have been placed
+    g.set_failpoint(failpoints::MIGRATION_PLAN_APPLY, "return")
+        .await;
+    let create_view = ChangeList::from_str(
+        "CREATE VIEW q AS SELECT a, b FROM t WHERE a = ?",
+        Dialect::DEFAULT_MYSQL,
+    )
+    .unwrap();
+    g.extend_recipe(create_view.clone()).await.unwrap_err();
+
+    // The domains placed by the failed migration should have been killed
+    let metrics = metrics_client.get_metrics().await.unwrap();
+    let metrics_dump = &metrics[0].metrics;
+    assert_eq!(
+        get_metric!(metrics_dump, recorded::WORKER_RUNNING_DOMAINS),
+        running_domains
+    );
+    assert!(g.view("q").await.is_err());
+
+    // Once the failure is gone the same migration should succeed
+    g.set_failpoint(failpoints::MIGRATION_PLAN_APPLY, "off")
+        .await;
+    g.extend_recipe(create_view).await.unwrap();
+    g.table("t")
+        .await
+        .unwrap()
+        .insert(vec![DfValue::from(1), DfValue::from(2)])
+        .await
+        .unwrap();
+    sleep().await;
+
+    let mut q = g.view("q").await.unwrap();
+    assert_eq!(
+        q.lookup(&[DfValue::from(1)], true)
+            .await
+            .unwrap()
+            .into_vec(),
+        vec![vec![DfValue::from(1), DfValue::from(2)]]
+    );
+}
@@ -441,6 +441,7 @@ use controller::migrate::materialization;
 pub use controller::migrate::materialization::FrontierStrategy;
 pub use controller::replication::{ReplicationOptions, ReplicationStrategy};
 use controller::sql;
+pub use controller::sql::IdentifierCase;
 use database_utils::UpstreamConfig;
 pub use dataflow::{DurabilityMode, PersistenceParameters};
 pub use petgraph::graph::NodeIndex;
@@ -499,6 +500,10 @@ pub struct Config {
     /// The duration to wait before canceling a task waiting on a worker request. Worker requests
     /// are typically issued as part of migrations.
     pub(crate) worker_request_timeout: Duration,
+    /// If set, new caches will be rejected once the total size, in bytes, of all materialized
+    /// state (excluding base tables) exceeds this value.
+    #[serde(default)]
+    pub(crate) materialized_memory_budget: Option<usize>,
 }
 
 impl Default for Config {
@@ -528,6 +533,7 @@ impl Default for Config {
             replication_strategy: Default::default(),
             upquery_timeout: Duration::from_millis(5000),
             worker_request_timeout: Duration::from_millis(1800000),
+            materialized_memory_budget: None,
         }
     }
 }
@@ -613,6 +619,23 @@ pub struct WorkerOptions {
     #[clap(long, env = "EXPERIMENTAL_MIXED_COMPARISONS_SUPPORT", hide = true)]
     pub enable_experimental_mixed_comparisons: bool,
 
+    /// Maximum number of columns allowed in the lookup key of a cached query. Queries
+    /// parametrized on more columns than this will not be cached. If not specified, there is no
+    /// limit.
+    #[clap(long, env = "MAX_VIEW_KEY_COLUMNS")]
+    pub max_view_key_columns: Option<usize>,
+
+    /// Compare the names of tables and columns referenced within queries without regard to case,
+    /// for upstream MySQL databases running with `lower_case_table_names` set
+    #[clap(long, env = "CASE_INSENSITIVE_IDENTIFIERS")]
+    pub case_insensitive_identifiers: bool,
+
+    /// Maximum total size, in bytes, of materialized state for caches. Once the memory used by
+    /// caches exceeds this budget, requests to create new caches will be rejected. If not
+    /// specified, there is no limit.
+    #[clap(long, env = "MATERIALIZED_MEMORY_BUDGET")]
+    pub materialized_memory_budget: Option<usize>,
+
     /// Directory in which to store replicated table data. If not specified, defaults to the
     /// current working directory.
     #[clap(long, env = "DB_DIR")]
@@ -7,6 +7,7 @@ use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use dataflow::{DomainBuilder, DomainRequest, Packet, Readers};
 use futures::stream::FuturesUnordered;
 use futures::FutureExt;
@@ -15,6 +16,7 @@ use futures_util::sink::SinkExt;
 use futures_util::stream::StreamExt;
 use launchpad::select;
 use metrics::{counter, gauge, histogram};
+use nom_sql::Relation;
 use readyset::internal::ReplicaAddress;
 use readyset::metrics::recorded;
 use readyset::{channel, ReadySetError};
@@ -59,6 +61,9 @@ pub enum WorkerRequestKind {
     /// Clear domains.
     ClearDomains,
 
+    /// Stop running a single domain replica on this worker, if it's running here.
+    KillDomain(ReplicaAddress),
+
     /// A set of domains has been started elsewhere in the distributed system.
     ///
     /// The message contains information on how the domain can be reached, in order that
@@ -86,6 +91,11 @@ pub enum WorkerRequestKind {
         /// The limit in bytes
         limit: Option<usize>,
     },
+
+    /// Return the last time each view with a reader on this worker was read from, as a map from
+    /// the name of the view to the time of the last read. Views which haven't been read from are
+    /// omitted.
+    ViewAccesses,
 }
 
 /// A request to a running ReadySet worker, containing a request kind and a completion channel.
@@ -246,6 +256,16 @@ impl Worker {
                 while let Some(res) = self.domain_wait_queue.next().await {
                     handle_domain_future_completion(res);
                 }
+                gauge!(recorded::WORKER_RUNNING_DOMAINS, 0.0);
+
+                Ok(None)
+            }
+            WorkerRequestKind::KillDomain(replica_address) => {
+                // Dropping the domain's handle sends it the abort signal
+                if self.domains.remove(&replica_address).is_some() {
+                    info!(%replica_address, "controller requested that this worker kills a domain");
+                }
+                gauge!(recorded::WORKER_RUNNING_DOMAINS, self.domains.len() as f64);
 
                 Ok(None)
             }
@@ -332,6 +352,7 @@ impl Worker {
                 );
 
                 self.domain_wait_queue.push(jh);
+                gauge!(recorded::WORKER_RUNNING_DOMAINS, self.domains.len() as f64);
 
                 span.in_scope(|| debug!(%bind_actual, %bind_external, "domain booted",));
                 let resp = RunDomainResponse {
@@ -377,6 +398,20 @@ impl Worker {
                 self.memory_limit = limit;
                 Ok(None)
             }
+            WorkerRequestKind::ViewAccesses => {
+                let mut accesses: HashMap<Relation, DateTime<Utc>> = HashMap::new();
+                for (address, reader) in self.readers.lock().unwrap().iter() {
+                    if let Some(last_read) = reader.last_read() {
+                        // Keep the latest read across all shards of the reader
+                        let last_read = DateTime::<Utc>::from(last_read);
+                        accesses
+                            .entry(address.name.clone())
+                            .and_modify(|t| *t = (*t).max(last_read))
+                            .or_insert(last_read);
+                    }
+                }
+                Ok(Some(bincode::serialize(&accesses)?))
+            }
         }
     }
 
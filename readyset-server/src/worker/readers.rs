@@ -21,7 +21,7 @@ use readyset::consistency::Timestamp;
 #[cfg(feature = "failure_injection")]
 use readyset::failpoints;
 use readyset::metrics::recorded;
-use readyset::results::ResultIterator;
+use readyset::results::{ResultIterator, SharedResults};
 use readyset::{
     KeyComparison, LookupResult, ReadQuery, ReadReply, ReadReplyStats, ReaderAddress, Tagged,
     ViewQuery,
@@ -198,11 +198,33 @@ impl ReadRequestHandler {
             };
         }
 
+        set_failpoint!(
+            failpoints::UNAVAILABLE_READER_SHARDS,
+            target.shard > 0,
+            |_| {
+                reply_with_error!(ReadySetError::Internal(
+                    "Injected failure reading from reader shard".to_owned()
+                ))
+            }
+        );
+
         let reader = get_reader_from_cache(&target, &mut self.readers_cache, &self.global_readers);
         let reader = match reader {
             Ok(r) => r,
             Err(e) => reply_with_error!(e),
         };
+        reader.mark_read();
+
+        // The reader's index treats NULL as equal to itself, but in SQL NULL isn't equal to
+        // anything (see `DfValue::sql_eq`), so equality lookups for keys containing NULL can never
+        // match any rows
+        let key_comparisons = key_comparisons
+            .into_iter()
+            .filter(|k| {
+                !k.equal()
+                    .map_or(false, |key| key.iter().any(DfValue::is_none))
+            })
+            .collect::<Vec<_>>();
 
         let consistency_miss = !has_sufficient_timestamp(reader, &timestamp);
 
@@ -221,6 +243,7 @@ impl ReadRequestHandler {
                 // immediately
                 self.hit_ctr.increment(1);
 
+                let stats = lookup_stats(&hit);
                 let results = ResultIterator::new(hit, &reader.post_lookup, limit, offset, filter);
 
                 let results = if raw_result {
@@ -229,10 +252,7 @@ impl ReadRequestHandler {
                     ServerReadReplyBatch::serialize(results)
                 };
 
-                reply_with_ok!(LookupResult::Results(
-                    vec![results],
-                    ReadReplyStats::default()
-                ));
+                reply_with_ok!(LookupResult::Results(vec![results], stats));
             }
         };
 
@@ -467,6 +487,14 @@ impl std::fmt::Debug for BlockingRead {
     }
 }
 
+/// Build the [`ReadReplyStats`] for a lookup that hit on all keys
+fn lookup_stats(hit: &SharedResults) -> ReadReplyStats {
+    ReadReplyStats {
+        rows_examined: hit.iter().map(|rows| rows.len() as u64).sum(),
+        ..Default::default()
+    }
+}
+
 impl BlockingRead {
     /// Check if we have the results for this blocking read.
     pub fn check(&mut self, reader_cache: &mut ReaderMap) -> Poll<Reply> {
@@ -487,6 +515,7 @@ impl BlockingRead {
             Err(_) => return Poll::Ready(Err(ReadySetError::ServerShuttingDown)),
             Ok(hit) => {
                 // We hit on all keys, and there is no consistency miss, can return results
+                let stats = lookup_stats(&hit);
                 let results = ResultIterator::new(
                     hit,
                     &reader.post_lookup,
@@ -503,10 +532,7 @@ impl BlockingRead {
 
                 return Poll::Ready(Ok(Tagged {
                     tag: self.tag,
-                    v: ReadReply::Normal(Ok(LookupResult::Results(
-                        vec![results],
-                        ReadReplyStats::default(),
-                    ))),
+                    v: ReadReply::Normal(Ok(LookupResult::Results(vec![results], stats))),
                 }));
             }
         };
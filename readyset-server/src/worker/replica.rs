@@ -2,7 +2,7 @@ use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::collections::{HashSet, VecDeque};
 use std::net::SocketAddr;
 use std::sync::{atomic, Arc};
-use std::time;
+use std::{mem, time};
 
 use ahash::AHashMap;
 use anyhow::{self, Context as AnyhowContext};
@@ -15,7 +15,7 @@ use futures_util::stream::StreamExt;
 use futures_util::FutureExt;
 use readyset::channel::{self, CONNECTION_FROM_BASE};
 use readyset::internal::ReplicaAddress;
-use readyset::{KeyComparison, PacketData, PacketPayload, Tagged};
+use readyset::{KeyComparison, PacketData, PacketPayload, TableReply, Tagged};
 use strawpoll::Strawpoll;
 use time::Duration;
 use tokio::io::{AsyncReadExt, BufReader, BufStream, BufWriter};
@@ -161,7 +161,7 @@ impl Replica {
                 let input: PacketData = v;
                 // Peek at its type.
                 match input.data {
-                    PacketPayload::Input(_) => Box::new(Packet::Input {
+                    PacketPayload::Input(_) | PacketPayload::Lookup(_) => Box::new(Packet::Input {
                         inner: input,
                         src: SourceChannelIdentifier { token, tag },
                     }),
@@ -388,11 +388,29 @@ impl Replica {
                     },
                     Some(mut packets) => {
                         while let Some(mut packet) = packets.pop_front() {
+                            if let Packet::Input {
+                                inner: PacketData { dst, data: PacketPayload::Lookup(key), .. },
+                                src: SourceChannelIdentifier { token, tag },
+                            } = &mut *packet {
+                                // Lookups into the state of a base table are answered directly,
+                                // rather than being processed like writes
+                                let reply = match span.in_scope(|| domain.lookup_base_rows(*dst, mem::take(key))) {
+                                    Ok(rows) => TableReply::Rows(rows),
+                                    Err(e) => TableReply::Error(e),
+                                };
+                                if let Some((_, conn)) = connections.iter_mut().find(|(t, _)| *t == *token) {
+                                    conn.send(Tagged { tag: *tag, v: reply }).await?;
+                                }
+                                continue;
+                            }
+
                             let ack = match &mut *packet {
-                                Packet::Timestamp { src: SourceChannelIdentifier { token, tag }, .. } |
-                                Packet::Input { src: SourceChannelIdentifier { token, tag }, .. } => {
+                                Packet::Timestamp { src: SourceChannelIdentifier { token, tag }, .. } => {
                                     // After processing we need to ack timestamp and input messages from base
-                                    connections.iter_mut().find(|(t, _)| *t == *token).map(|(_, conn)| (*tag, conn))
+                                    connections.iter_mut().find(|(t, _)| *t == *token).map(|(_, conn)| (*tag, conn, false))
+                                }
+                                Packet::Input { src: SourceChannelIdentifier { token, tag }, .. } => {
+                                    connections.iter_mut().find(|(t, _)| *t == *token).map(|(_, conn)| (*tag, conn, true))
                                 }
                                 Packet::RequestReaderReplay { node, cols, keys } => {
                                     // We want to batch multiple reader replay requests into a single call while
@@ -405,10 +423,28 @@ impl Replica {
                                 _ => None,
                             };
 
-                            span.in_scope(|| domain.handle_packet(packet, out))?;
-
-                            if let Some((tag, conn)) = ack {
-                                conn.send(Tagged { tag, v: () }).await?;
+                            let res = span.in_scope(|| domain.handle_packet(packet, out));
+
+                            match (res, ack) {
+                                (Ok(()), Some((tag, conn, is_input))) => {
+                                    // Writes are acked with the number of existing rows they
+                                    // matched, so clients can report affected rows without
+                                    // looking them up first
+                                    let v = if is_input {
+                                        TableReply::RowsAffected(domain.take_base_rows_affected())
+                                    } else {
+                                        TableReply::Ack
+                                    };
+                                    conn.send(Tagged { tag, v }).await?;
+                                }
+                                (Ok(()), None) => {}
+                                // Writes rejected by a base table because they'd duplicate a
+                                // primary key are reported back to the client that made them,
+                                // rather than being treated as a failure of the domain
+                                (Err(e), Some((tag, conn, _))) if e.is_duplicate_key() => {
+                                    conn.send(Tagged { tag, v: TableReply::Error(e) }).await?;
+                                }
+                                (Err(e), _) => return Err(e.into()),
                             }
                         }
                     },
@@ -7,7 +7,7 @@ use std::collections::HashMap;
 use nom_sql::analysis::visit_mut::VisitorMut;
 use nom_sql::{
     CreateTableOption, CreateTableStatement, CreateViewStatement, Literal, SelectStatement,
-    SqlIdentifier,
+    SqlIdentifier, SqlQuery,
 };
 
 pub trait Anonymize {
@@ -74,6 +74,12 @@ pub fn anonymize_literals(query: &mut SelectStatement) {
         .unwrap();
 }
 
+/// Replaces every instance of `Literal` in the given query with `Literal::String("<anonymized>")`
+pub fn anonymize_query_literals(query: &mut SqlQuery) {
+    #[allow(clippy::unwrap_used)] // error is !, which can never be returned
+    AnonymizeLiteralsVisitor.visit_sql_query(query).unwrap();
+}
+
 pub struct Anonymizer {
     /// A map of symbols to anonymized symbols
     anonymizations: HashMap<SqlIdentifier, SqlIdentifier>,
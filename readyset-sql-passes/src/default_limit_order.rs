@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use nom_sql::analysis::contains_aggregate;
+use nom_sql::{
+    Column, CreateTableStatement, Expr, FieldDefinitionExpr, FieldReference, OrderClause,
+    OrderType, Relation, SelectStatement, SqlQuery, TableKey,
+};
+use readyset_errors::ReadySetResult;
+
+pub trait DefaultLimitOrder: Sized {
+    /// Add an ORDER BY clause to any query with a LIMIT (or OFFSET) but no ORDER BY, so that the
+    /// rows the query returns are the same no matter what order they were written to the tables in
+    /// or are stored in by the dataflow graph.
+    ///
+    /// The rows are ordered ascending by, in order of preference:
+    ///
+    /// 1. The columns in the GROUP BY clause, if the query has one, since they uniquely identify
+    ///    each row returned by the query
+    /// 2. The primary key of the table, if the query reads from a single base table (with no joins
+    ///    or DISTINCT) that has one
+    /// 3. All of the non-literal expressions in the SELECT list, in the order they're projected
+    ///
+    /// Queries with aggregates but no GROUP BY only ever return one row, so they're left alone.
+    ///
+    /// This pass must be run after the expand_implied_tables and remove_numeric_field_references
+    /// passes, since it copies column references from the GROUP BY clause and SELECT list into the
+    /// ORDER BY clause, and after the key_def_coalescing pass has been run on all the tables, since
+    /// it only looks for primary keys in the `keys` of the table.
+    fn add_default_limit_order(
+        self,
+        base_schemas: &HashMap<Relation, CreateTableStatement>,
+    ) -> ReadySetResult<Self>;
+}
+
+/// Returns the primary key columns of the single table `stmt` reads from, qualified with the name
+/// that table is referred to by in `stmt`, if there is such a table
+fn single_table_primary_key(
+    stmt: &SelectStatement,
+    base_schemas: &HashMap<Relation, CreateTableStatement>,
+) -> Option<Vec<Column>> {
+    if stmt.distinct || !stmt.join.is_empty() {
+        return None;
+    }
+    let table_expr = match stmt.tables.as_slice() {
+        [table_expr] => table_expr,
+        _ => return None,
+    };
+    let table = base_schemas.get(&table_expr.table)?;
+    let columns = table.keys.iter().flatten().find_map(|key| match key {
+        TableKey::PrimaryKey { columns, .. } => Some(columns),
+        _ => None,
+    })?;
+
+    let table_name = match &table_expr.alias {
+        Some(alias) => Relation::from(alias.clone()),
+        None => table_expr.table.clone(),
+    };
+    Some(
+        columns
+            .iter()
+            .map(|col| Column {
+                name: col.name.clone(),
+                table: Some(table_name.clone()),
+            })
+            .collect(),
+    )
+}
+
+impl DefaultLimitOrder for SelectStatement {
+    fn add_default_limit_order(
+        mut self,
+        base_schemas: &HashMap<Relation, CreateTableStatement>,
+    ) -> ReadySetResult<Self> {
+        if self.order.is_some() || (self.limit.is_none() && self.offset.is_none()) {
+            return Ok(self);
+        }
+
+        let order_by: Vec<FieldReference> = if let Some(group_by) = &self.group_by {
+            group_by.fields.clone()
+        } else if self.fields.iter().any(|field| {
+            matches!(field, FieldDefinitionExpr::Expr { expr, .. } if contains_aggregate(expr))
+        }) {
+            return Ok(self);
+        } else if let Some(columns) = single_table_primary_key(&self, base_schemas) {
+            columns
+                .into_iter()
+                .map(|col| FieldReference::Expr(Expr::Column(col)))
+                .collect()
+        } else {
+            self.fields
+                .iter()
+                .filter_map(|field| match field {
+                    FieldDefinitionExpr::Expr { expr, .. } if !matches!(expr, Expr::Literal(_)) => {
+                        Some(FieldReference::Expr(expr.clone()))
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+
+        if !order_by.is_empty() {
+            self.order = Some(OrderClause {
+                order_by: order_by
+                    .into_iter()
+                    .map(|field| (field, Some(OrderType::OrderAscending)))
+                    .collect(),
+            });
+        }
+
+        Ok(self)
+    }
+}
+
+impl DefaultLimitOrder for SqlQuery {
+    fn add_default_limit_order(
+        self,
+        base_schemas: &HashMap<Relation, CreateTableStatement>,
+    ) -> ReadySetResult<Self> {
+        match self {
+            SqlQuery::Select(stmt) => Ok(SqlQuery::Select(
+                stmt.add_default_limit_order(base_schemas)?,
+            )),
+            _ => Ok(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::{parse_query, ColumnSpecification, Dialect, SqlType};
+
+    use super::*;
+    use crate::util::parse_select_statement;
+
+    fn base_schemas() -> HashMap<Relation, CreateTableStatement> {
+        let column = |name: &str| ColumnSpecification {
+            column: Column {
+                name: name.into(),
+                table: Some("t".into()),
+            },
+            sql_type: SqlType::Int(None),
+            constraints: vec![],
+            comment: None,
+        };
+
+        let mut base_schemas = HashMap::new();
+        base_schemas.insert(
+            "t".into(),
+            CreateTableStatement {
+                table: "t".into(),
+                fields: vec![column("id"), column("x"), column("y")],
+                keys: Some(vec![TableKey::PrimaryKey {
+                    index_name: None,
+                    constraint_name: None,
+                    columns: vec![column("id").column],
+                }]),
+                if_not_exists: false,
+                options: vec![],
+            },
+        );
+        base_schemas.insert(
+            "keyless".into(),
+            CreateTableStatement {
+                table: "keyless".into(),
+                fields: vec![column("x"), column("y")],
+                keys: None,
+                if_not_exists: false,
+                options: vec![],
+            },
+        );
+        base_schemas
+    }
+
+    fn rewrites_to(input: &str, expected: &str) {
+        let input = parse_query(Dialect::MySQL, input).unwrap();
+        let expected = parse_query(Dialect::MySQL, expected).unwrap();
+        let result = input.add_default_limit_order(&base_schemas()).unwrap();
+        assert_eq!(result, expected, "{result} != {expected}");
+    }
+
+    fn does_not_change(input: &str) {
+        rewrites_to(input, input)
+    }
+
+    #[test]
+    fn orders_by_primary_key() {
+        rewrites_to(
+            "SELECT t.x FROM t WHERE t.y = ? LIMIT 10",
+            "SELECT t.x FROM t WHERE t.y = ? ORDER BY t.id ASC LIMIT 10",
+        );
+    }
+
+    #[test]
+    fn orders_by_primary_key_with_alias() {
+        rewrites_to(
+            "SELECT p.x FROM t AS p LIMIT 10 OFFSET 5",
+            "SELECT p.x FROM t AS p ORDER BY p.id ASC LIMIT 10 OFFSET 5",
+        );
+    }
+
+    #[test]
+    fn orders_by_group_by() {
+        rewrites_to(
+            "SELECT t.x, count(t.y) FROM t GROUP BY t.x LIMIT 10",
+            "SELECT t.x, count(t.y) FROM t GROUP BY t.x ORDER BY t.x ASC LIMIT 10",
+        );
+    }
+
+    #[test]
+    fn orders_by_projected_columns_without_primary_key() {
+        rewrites_to(
+            "SELECT keyless.x, 1, keyless.y FROM keyless LIMIT 10",
+            "SELECT keyless.x, 1, keyless.y FROM keyless \
+             ORDER BY keyless.x ASC, keyless.y ASC LIMIT 10",
+        );
+    }
+
+    #[test]
+    fn orders_by_projected_columns_with_join() {
+        rewrites_to(
+            "SELECT t.x, keyless.y FROM t JOIN keyless ON t.x = keyless.x LIMIT 10",
+            "SELECT t.x, keyless.y FROM t JOIN keyless ON t.x = keyless.x \
+             ORDER BY t.x ASC, keyless.y ASC LIMIT 10",
+        );
+    }
+
+    #[test]
+    fn orders_distinct_by_projected_columns() {
+        rewrites_to(
+            "SELECT DISTINCT t.x FROM t LIMIT 10",
+            "SELECT DISTINCT t.x FROM t ORDER BY t.x ASC LIMIT 10",
+        );
+    }
+
+    #[test]
+    fn leaves_existing_order_alone() {
+        does_not_change("SELECT t.x FROM t ORDER BY t.y DESC LIMIT 10");
+    }
+
+    #[test]
+    fn leaves_queries_without_limit_alone() {
+        does_not_change("SELECT t.x FROM t");
+    }
+
+    #[test]
+    fn leaves_ungrouped_aggregates_alone() {
+        does_not_change("SELECT count(t.x) FROM t LIMIT 10");
+    }
+
+    #[test]
+    fn select_statement() {
+        let stmt = parse_select_statement("SELECT t.x FROM t LIMIT 1");
+        let result = stmt.add_default_limit_order(&base_schemas()).unwrap();
+        assert_eq!(
+            result.order,
+            Some(OrderClause {
+                order_by: vec![(
+                    FieldReference::Expr(Expr::Column("t.id".into())),
+                    Some(OrderType::OrderAscending)
+                )]
+            })
+        );
+    }
+}
@@ -183,7 +183,7 @@ fn check_select_statement<'a>(
                     unsupported!("Self-joins using the same column are unsupported")
                 }
             }
-            JoinConstraint::Empty => {}
+            JoinConstraint::Natural | JoinConstraint::Empty => {}
         }
     }
 
@@ -0,0 +1,88 @@
+use nom_sql::analysis::visit::{self, Visitor};
+use nom_sql::{CommonTableExpr, SelectStatement, SqlQuery};
+use readyset_errors::{unsupported, ReadySetError, ReadySetResult};
+
+pub trait DetectRecursiveCtes: Sized {
+    /// Detect and return an unsupported error for any recursive common table expressions (those
+    /// declared with `WITH RECURSIVE`) anywhere in the query, since recursion can't be represented
+    /// in the (acyclic) dataflow graph.
+    fn detect_recursive_ctes(self) -> ReadySetResult<Self>;
+}
+
+struct DetectRecursiveCtesVisitor;
+
+impl<'ast> Visitor<'ast> for DetectRecursiveCtesVisitor {
+    type Error = ReadySetError;
+
+    fn visit_common_table_expr(&mut self, cte: &'ast CommonTableExpr) -> Result<(), Self::Error> {
+        if cte.recursive {
+            unsupported!(
+                "Recursive common table expressions are not supported (found recursive CTE `{}`)",
+                cte.name
+            );
+        }
+        visit::walk_common_table_expr(self, cte)
+    }
+}
+
+impl DetectRecursiveCtes for SelectStatement {
+    fn detect_recursive_ctes(self) -> ReadySetResult<Self> {
+        DetectRecursiveCtesVisitor.visit_select_statement(&self)?;
+        Ok(self)
+    }
+}
+
+impl DetectRecursiveCtes for SqlQuery {
+    fn detect_recursive_ctes(self) -> ReadySetResult<Self> {
+        match &self {
+            SqlQuery::Select(stmt) => {
+                DetectRecursiveCtesVisitor.visit_select_statement(stmt)?;
+            }
+            SqlQuery::CompoundSelect(stmt) => {
+                for (_, stmt) in &stmt.selects {
+                    DetectRecursiveCtesVisitor.visit_select_statement(stmt)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::{parse_query, Dialect};
+
+    use super::*;
+
+    fn detect(query_str: &str) -> ReadySetResult<SqlQuery> {
+        parse_query(Dialect::MySQL, query_str)
+            .unwrap()
+            .detect_recursive_ctes()
+    }
+
+    #[test]
+    fn recursive_cte() {
+        let err = detect("WITH RECURSIVE r AS (SELECT r.x FROM r) SELECT x FROM r").unwrap_err();
+        assert!(err.is_unsupported(), "{err:?}");
+        assert!(err
+            .to_string()
+            .contains("Recursive common table expressions"));
+    }
+
+    #[test]
+    fn recursive_cte_in_subquery() {
+        let err = detect(
+            "SELECT t.x FROM t \
+             JOIN (WITH RECURSIVE r AS (SELECT r.x FROM r) SELECT x FROM r) sq ON t.x = sq.x",
+        )
+        .unwrap_err();
+        assert!(err.is_unsupported(), "{err:?}");
+    }
+
+    #[test]
+    fn non_recursive_cte() {
+        detect("WITH max_val AS (SELECT max(value) AS value FROM t1) SELECT value FROM max_val")
+            .unwrap();
+    }
+}
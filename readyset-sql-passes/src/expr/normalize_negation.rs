@@ -38,6 +38,7 @@ fn negate_expr(expr: &mut Expr) -> bool {
                 | BinaryOperator::Subtract
                 | BinaryOperator::Multiply
                 | BinaryOperator::Divide
+                | BinaryOperator::IntDivide
                 | BinaryOperator::DoublePipe
                 | BinaryOperator::Arrow1
                 | BinaryOperator::Arrow2
@@ -4,13 +4,17 @@ pub mod alias_removal;
 pub mod anonymize;
 mod count_star_rewrite;
 mod create_table_columns;
+mod default_limit_order;
 mod detect_problematic_self_joins;
+mod detect_recursive_ctes;
 pub mod expr;
 mod implied_tables;
 mod key_def_coalescing;
 mod normalize_topk_with_aggregate;
 mod order_limit_removal;
+mod propagate_ctes;
 mod remove_numeric_field_references;
+mod resolve_natural_joins;
 mod resolve_schemas;
 mod rewrite_between;
 mod star_expansion;
@@ -30,13 +34,17 @@ use readyset_errors::ReadySetResult;
 pub use crate::alias_removal::AliasRemoval;
 pub use crate::count_star_rewrite::CountStarRewrite;
 pub use crate::create_table_columns::CreateTableColumns;
+pub use crate::default_limit_order::DefaultLimitOrder;
 pub use crate::detect_problematic_self_joins::DetectProblematicSelfJoins;
+pub use crate::detect_recursive_ctes::DetectRecursiveCtes;
 pub use crate::expr::ScalarOptimizeExpressions;
 pub use crate::implied_tables::ImpliedTableExpansion;
 pub use crate::key_def_coalescing::KeyDefinitionCoalescing;
 pub use crate::normalize_topk_with_aggregate::NormalizeTopKWithAggregate;
 pub use crate::order_limit_removal::OrderLimitRemoval;
+pub use crate::propagate_ctes::PropagateCtes;
 pub use crate::remove_numeric_field_references::RemoveNumericFieldReferences;
+pub use crate::resolve_natural_joins::ResolveNaturalJoins;
 pub use crate::resolve_schemas::ResolveSchemas;
 pub use crate::rewrite_between::RewriteBetween;
 pub use crate::star_expansion::StarExpansion;
@@ -117,7 +125,9 @@ impl Rewrite for CreateTableStatement {
 
 impl Rewrite for SelectStatement {
     fn rewrite(self, context: &mut RewriteContext) -> ReadySetResult<Self> {
-        self.rewrite_between()
+        self.detect_recursive_ctes()?
+            .propagate_ctes()
+            .rewrite_between()
             .scalar_optimize_expressions(context.dialect)
             .strip_post_filters()
             .resolve_schemas(
@@ -126,13 +136,15 @@ impl Rewrite for SelectStatement {
                 context.search_path,
                 context.invalidating_tables.as_deref_mut(),
             )
+            .resolve_natural_joins(context.view_schemas)?
             .expand_stars(context.view_schemas)?
             .expand_implied_tables(context.view_schemas)?
             .normalize_topk_with_aggregate()?
             .rewrite_count_star(context.view_schemas)?
             .detect_problematic_self_joins()?
             .remove_numeric_field_references()?
-            .order_limit_removal(context.base_schemas)
+            .order_limit_removal(context.base_schemas)?
+            .add_default_limit_order(context.base_schemas)
     }
 }
 
@@ -0,0 +1,177 @@
+use std::collections::HashSet;
+use std::mem;
+
+use nom_sql::analysis::visit::Visitor;
+use nom_sql::analysis::visit_mut::{self, VisitorMut};
+use nom_sql::{Relation, SelectStatement, SqlIdentifier, SqlQuery};
+
+pub trait PropagateCtes: Sized {
+    /// Copy each common table expression into the `WITH` clause of every CTE declared after it in
+    /// the same `WITH` clause which refers to it, so that CTEs which refer to earlier CTEs can be
+    /// planned on their own.
+    ///
+    /// Each CTE ends up being planned as a separate query (see
+    /// [`TableAliasRewrite::Cte`](crate::alias_removal::TableAliasRewrite::Cte)), so without this a
+    /// reference from one CTE to another would be resolved as a reference to a (likely
+    /// nonexistent) table.
+    #[must_use]
+    fn propagate_ctes(self) -> Self;
+}
+
+/// Collects the names of all the unqualified tables referenced anywhere within a statement
+#[derive(Default)]
+struct ReferencedTables<'ast>(HashSet<&'ast SqlIdentifier>);
+
+impl<'ast> Visitor<'ast> for ReferencedTables<'ast> {
+    type Error = !;
+
+    fn visit_table(&mut self, table: &'ast Relation) -> Result<(), Self::Error> {
+        if table.schema.is_none() {
+            self.0.insert(&table.name);
+        }
+        Ok(())
+    }
+}
+
+struct PropagateCtesVisitor;
+
+impl PropagateCtesVisitor {
+    /// Propagate CTEs within `select_statement`, the first `num_copied` of whose CTEs are copies
+    /// which have already been propagated into, and so are left alone
+    fn propagate(
+        &mut self,
+        select_statement: &mut SelectStatement,
+        num_copied: usize,
+    ) -> Result<(), !> {
+        let mut ctes = mem::take(&mut select_statement.ctes);
+        visit_mut::walk_select_statement(self, select_statement)?;
+
+        for i in num_copied..ctes.len() {
+            let (earlier, rest) = ctes.split_at_mut(i);
+            let cte = &mut rest[0];
+
+            let mut referenced = ReferencedTables::default();
+            let Ok(_) = referenced.visit_select_statement(&cte.statement);
+            let copies = earlier
+                .iter()
+                .filter(|earlier_cte| {
+                    referenced.0.contains(&earlier_cte.name)
+                        && !cte
+                            .statement
+                            .ctes
+                            .iter()
+                            .any(|own_cte| own_cte.name == earlier_cte.name)
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let num_copies = copies.len();
+            cte.statement.ctes.splice(0..0, copies);
+            self.propagate(&mut cte.statement, num_copies)?;
+        }
+
+        select_statement.ctes = ctes;
+        Ok(())
+    }
+}
+
+impl<'ast> VisitorMut<'ast> for PropagateCtesVisitor {
+    type Error = !;
+
+    fn visit_select_statement(
+        &mut self,
+        select_statement: &'ast mut SelectStatement,
+    ) -> Result<(), Self::Error> {
+        self.propagate(select_statement, 0)
+    }
+}
+
+impl PropagateCtes for SelectStatement {
+    fn propagate_ctes(mut self) -> Self {
+        let Ok(_) = PropagateCtesVisitor.visit_select_statement(&mut self);
+        self
+    }
+}
+
+impl PropagateCtes for SqlQuery {
+    fn propagate_ctes(self) -> Self {
+        match self {
+            SqlQuery::Select(stmt) => SqlQuery::Select(stmt.propagate_ctes()),
+            _ => self,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse_select_statement;
+
+    fn propagates_to(input: &str, expected: &str) {
+        let result = parse_select_statement(input).propagate_ctes();
+        let expected = parse_select_statement(expected);
+        assert_eq!(result, expected, "\n{result}\n!= {expected}");
+    }
+
+    #[test]
+    fn single_cte() {
+        propagates_to(
+            "WITH a AS (SELECT t1.x FROM t1) SELECT a.x FROM a",
+            "WITH a AS (SELECT t1.x FROM t1) SELECT a.x FROM a",
+        );
+    }
+
+    #[test]
+    fn chained_ctes() {
+        propagates_to(
+            "WITH a AS (SELECT t1.x FROM t1), b AS (SELECT a.x FROM a)
+             SELECT t2.y FROM t2 JOIN b ON b.x = t2.x",
+            "WITH a AS (SELECT t1.x FROM t1),
+                  b AS (WITH a AS (SELECT t1.x FROM t1) SELECT a.x FROM a)
+             SELECT t2.y FROM t2 JOIN b ON b.x = t2.x",
+        );
+    }
+
+    #[test]
+    fn only_referenced_ctes() {
+        propagates_to(
+            "WITH a AS (SELECT t1.x FROM t1),
+                  b AS (SELECT t2.y FROM t2),
+                  c AS (SELECT a.x FROM a)
+             SELECT b.y FROM b JOIN c ON b.y = c.x",
+            "WITH a AS (SELECT t1.x FROM t1),
+                  b AS (SELECT t2.y FROM t2),
+                  c AS (WITH a AS (SELECT t1.x FROM t1) SELECT a.x FROM a)
+             SELECT b.y FROM b JOIN c ON b.y = c.x",
+        );
+    }
+
+    #[test]
+    fn transitively_chained_ctes() {
+        propagates_to(
+            "WITH a AS (SELECT t1.x FROM t1),
+                  b AS (SELECT a.x FROM a),
+                  c AS (SELECT b.x FROM b)
+             SELECT c.x FROM c",
+            "WITH a AS (SELECT t1.x FROM t1),
+                  b AS (WITH a AS (SELECT t1.x FROM t1) SELECT a.x FROM a),
+                  c AS (WITH b AS (WITH a AS (SELECT t1.x FROM t1) SELECT a.x FROM a)
+                        SELECT b.x FROM b)
+             SELECT c.x FROM c",
+        );
+    }
+
+    #[test]
+    fn cte_with_own_ctes() {
+        propagates_to(
+            "WITH a AS (SELECT t1.x FROM t1),
+                  b AS (WITH c AS (SELECT a.x FROM a) SELECT c.x FROM c)
+             SELECT b.x FROM b",
+            "WITH a AS (SELECT t1.x FROM t1),
+                  b AS (WITH a AS (SELECT t1.x FROM t1),
+                             c AS (WITH a AS (SELECT t1.x FROM t1) SELECT a.x FROM a)
+                        SELECT c.x FROM c)
+             SELECT b.x FROM b",
+        );
+    }
+}
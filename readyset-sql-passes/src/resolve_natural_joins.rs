@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+
+use nom_sql::analysis::visit_mut::{self, VisitorMut};
+use nom_sql::{
+    BinaryOperator, Column, Expr, FieldDefinitionExpr, JoinConstraint, JoinRightSide, Relation,
+    SelectStatement, SqlIdentifier, SqlQuery, TableExpr,
+};
+use readyset_errors::{unsupported, ReadySetError, ReadySetResult};
+
+use crate::util;
+
+pub trait ResolveNaturalJoins: Sized {
+    /// Rewrite all `NATURAL` joins in the query into joins with an explicit `ON` condition that
+    /// compares every column which has the same name on both sides of the join, given a map from
+    /// tables to the lists of columns in those tables.
+    ///
+    /// `USING` joins are rewritten the same way, comparing each of the named columns on the
+    /// right-hand side of the join with the first table to the left of the join that has a column
+    /// with that name.
+    ///
+    /// Must be run after schemas have been resolved, and before stars have been expanded
+    fn resolve_natural_joins(
+        self,
+        table_columns: &HashMap<Relation, Vec<SqlIdentifier>>,
+    ) -> ReadySetResult<Self>;
+}
+
+struct ResolveNaturalJoinsVisitor<'schema> {
+    table_columns: &'schema HashMap<Relation, Vec<SqlIdentifier>>,
+}
+
+impl<'ast, 'schema> VisitorMut<'ast> for ResolveNaturalJoinsVisitor<'schema> {
+    type Error = ReadySetError;
+
+    fn visit_select_statement(
+        &mut self,
+        select_statement: &'ast mut SelectStatement,
+    ) -> Result<(), Self::Error> {
+        visit_mut::walk_select_statement(self, select_statement)?;
+
+        if !select_statement.join.iter().any(|jc| {
+            matches!(
+                jc.constraint,
+                JoinConstraint::Natural | JoinConstraint::Using(_)
+            )
+        }) {
+            return Ok(());
+        }
+
+        if select_statement
+            .fields
+            .iter()
+            .any(|f| matches!(f, FieldDefinitionExpr::All))
+        {
+            unsupported!("SELECT * is not supported in queries with NATURAL or USING joins");
+        }
+
+        let subquery_schemas =
+            util::subquery_schemas(&select_statement.ctes, &select_statement.join);
+
+        let columns_for_table = |table_expr: &TableExpr| -> ReadySetResult<(Relation, Vec<_>)> {
+            let table = &table_expr.table;
+            let columns = if table.schema.is_none() {
+                // Can only reference subqueries with tables that don't have a schema
+                subquery_schemas.get(&table.name).cloned()
+            } else {
+                None
+            }
+            .or_else(|| self.table_columns.get(table).map(|fs| fs.iter().collect()))
+            .ok_or_else(|| ReadySetError::TableNotFound {
+                name: table.name.clone().into(),
+                schema: table.schema.clone().map(Into::into),
+            })?;
+
+            let relation = table_expr
+                .alias
+                .clone()
+                .map(Relation::from)
+                .unwrap_or_else(|| table.clone());
+
+            Ok((relation, columns.into_iter().cloned().collect()))
+        };
+
+        // The left-hand side of a NATURAL join is the result of everything to its left, so we
+        // accumulate the columns of every relation we've seen so far, in order
+        let mut left_columns = select_statement
+            .tables
+            .iter()
+            .map(columns_for_table)
+            .collect::<ReadySetResult<Vec<_>>>()?;
+
+        let mut constraints = Vec::with_capacity(select_statement.join.len());
+        for jc in &select_statement.join {
+            let right_columns = match &jc.right {
+                JoinRightSide::Table(table) => vec![columns_for_table(table)?],
+                JoinRightSide::Tables(tables) => tables
+                    .iter()
+                    .map(columns_for_table)
+                    .collect::<ReadySetResult<_>>()?,
+                JoinRightSide::NestedSelect(_, alias) => vec![(
+                    alias.clone().into(),
+                    subquery_schemas
+                        .get(alias)
+                        .into_iter()
+                        .flatten()
+                        .map(|c| (*c).clone())
+                        .collect(),
+                )],
+            };
+
+            let common_columns: Option<Vec<(&Relation, &SqlIdentifier)>> = match &jc.constraint {
+                JoinConstraint::Natural => {
+                    let (_, right_columns) = match right_columns.as_slice() {
+                        [right] => right,
+                        _ => {
+                            unsupported!("NATURAL joins against multiple tables are not supported")
+                        }
+                    };
+
+                    // Only compare against the first occurrence of each column on the left
+                    let mut common_columns: Vec<(&Relation, &SqlIdentifier)> = vec![];
+                    for (left_relation, cols) in &left_columns {
+                        for col in cols {
+                            if right_columns.contains(col)
+                                && !common_columns.iter().any(|(_, c)| *c == col)
+                            {
+                                common_columns.push((left_relation, col));
+                            }
+                        }
+                    }
+                    Some(common_columns)
+                }
+                JoinConstraint::Using(cols) => {
+                    let (_, right_columns) = match right_columns.as_slice() {
+                        [right] => right,
+                        _ => unsupported!("USING joins against multiple tables are not supported"),
+                    };
+
+                    // Each column is compared against the first table on the left which has a
+                    // column with that name
+                    Some(
+                        cols.iter()
+                            .map(|col| {
+                                let left_relation = left_columns
+                                    .iter()
+                                    .find(|(_, cols)| cols.contains(&col.name))
+                                    .map(|(left_relation, _)| left_relation)
+                                    .ok_or_else(|| {
+                                        ReadySetError::NoSuchColumn(col.name.to_string())
+                                    })?;
+                                if !right_columns.contains(&col.name) {
+                                    return Err(ReadySetError::NoSuchColumn(col.name.to_string()));
+                                }
+                                Ok((left_relation, &col.name))
+                            })
+                            .collect::<ReadySetResult<_>>()?,
+                    )
+                }
+                JoinConstraint::On(_) | JoinConstraint::Empty => None,
+            };
+
+            constraints.push(common_columns.map(|common_columns| {
+                // Both NATURAL and USING joins have been checked to have exactly one relation on
+                // the right
+                #[allow(clippy::indexing_slicing)]
+                let right_relation = &right_columns[0].0;
+                let conds = common_columns
+                    .into_iter()
+                    .map(|(left_relation, col)| Expr::BinaryOp {
+                        lhs: Box::new(Expr::Column(Column {
+                            name: col.clone(),
+                            table: Some(left_relation.clone()),
+                        })),
+                        op: BinaryOperator::Equal,
+                        rhs: Box::new(Expr::Column(Column {
+                            name: col.clone(),
+                            table: Some(right_relation.clone()),
+                        })),
+                    })
+                    .reduce(|lhs, rhs| Expr::BinaryOp {
+                        lhs: Box::new(lhs),
+                        op: BinaryOperator::And,
+                        rhs: Box::new(rhs),
+                    });
+
+                // A NATURAL join with no columns in common is a cartesian product
+                conds.map_or(JoinConstraint::Empty, JoinConstraint::On)
+            }));
+
+            left_columns.extend(right_columns);
+        }
+
+        for (jc, constraint) in select_statement.join.iter_mut().zip(constraints) {
+            if let Some(constraint) = constraint {
+                jc.constraint = constraint;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ResolveNaturalJoins for SelectStatement {
+    fn resolve_natural_joins(
+        mut self,
+        table_columns: &HashMap<Relation, Vec<SqlIdentifier>>,
+    ) -> ReadySetResult<Self> {
+        let mut visitor = ResolveNaturalJoinsVisitor { table_columns };
+        visitor.visit_select_statement(&mut self)?;
+        Ok(self)
+    }
+}
+
+impl ResolveNaturalJoins for SqlQuery {
+    fn resolve_natural_joins(
+        self,
+        table_columns: &HashMap<Relation, Vec<SqlIdentifier>>,
+    ) -> ReadySetResult<Self> {
+        Ok(match self {
+            SqlQuery::Select(sq) => SqlQuery::Select(sq.resolve_natural_joins(table_columns)?),
+            _ => self,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::hashmap;
+    use nom_sql::{parse_query, Dialect};
+
+    use super::ResolveNaturalJoins;
+
+    macro_rules! resolves_natural_joins {
+        ($source: expr, $expected: expr, schema: {$($schema:tt)*}) => {{
+            let q = parse_query(Dialect::MySQL, $source).unwrap();
+            let expected = parse_query(Dialect::MySQL, $expected).unwrap();
+            let schema = hashmap!($($schema)*);
+            let res = q.resolve_natural_joins(&schema).unwrap();
+            assert_eq!(res, expected, "{} != {}", res, expected);
+        }};
+    }
+
+    #[test]
+    fn single_common_column() {
+        resolves_natural_joins!(
+            "SELECT t1.a, t2.c FROM t1 NATURAL JOIN t2",
+            "SELECT t1.a, t2.c FROM t1 JOIN t2 ON t1.b = t2.b",
+            schema: {
+                "t1".into() => vec!["a".into(), "b".into()],
+                "t2".into() => vec!["b".into(), "c".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn multiple_common_columns() {
+        resolves_natural_joins!(
+            "SELECT t1.a FROM t1 NATURAL LEFT JOIN t2",
+            "SELECT t1.a FROM t1 LEFT JOIN t2 ON t1.b = t2.b AND t1.c = t2.c",
+            schema: {
+                "t1".into() => vec!["a".into(), "b".into(), "c".into()],
+                "t2".into() => vec!["c".into(), "b".into(), "d".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn no_common_columns() {
+        resolves_natural_joins!(
+            "SELECT t1.a FROM t1 NATURAL JOIN t2",
+            "SELECT t1.a FROM t1 JOIN t2",
+            schema: {
+                "t1".into() => vec!["a".into()],
+                "t2".into() => vec!["b".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn chained_with_previous_joins() {
+        resolves_natural_joins!(
+            "SELECT t1.a FROM t1 JOIN t2 ON t1.a = t2.a NATURAL JOIN t3",
+            "SELECT t1.a FROM t1 JOIN t2 ON t1.a = t2.a JOIN t3 ON t1.a = t3.a AND t2.b = t3.b",
+            schema: {
+                "t1".into() => vec!["a".into()],
+                "t2".into() => vec!["a".into(), "b".into()],
+                "t3".into() => vec!["b".into(), "a".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn aliased_tables() {
+        resolves_natural_joins!(
+            "SELECT x.a FROM t1 AS x NATURAL JOIN t2 AS y",
+            "SELECT x.a FROM t1 AS x JOIN t2 AS y ON x.b = y.b",
+            schema: {
+                "t1".into() => vec!["a".into(), "b".into()],
+                "t2".into() => vec!["b".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn subquery() {
+        resolves_natural_joins!(
+            "SELECT t1.a FROM t1 NATURAL JOIN (SELECT t2.b FROM t2) AS sq",
+            "SELECT t1.a FROM t1 JOIN (SELECT t2.b FROM t2) AS sq ON t1.b = sq.b",
+            schema: {
+                "t1".into() => vec!["a".into(), "b".into()],
+                "t2".into() => vec!["b".into(), "c".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn using() {
+        resolves_natural_joins!(
+            "SELECT t1.a FROM t1 JOIN t2 USING (b, c)",
+            "SELECT t1.a FROM t1 JOIN t2 ON t1.b = t2.b AND t1.c = t2.c",
+            schema: {
+                "t1".into() => vec!["a".into(), "b".into(), "c".into()],
+                "t2".into() => vec!["b".into(), "c".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn using_column_from_earlier_table() {
+        resolves_natural_joins!(
+            "SELECT t1.a FROM t1 JOIN t2 ON t1.a = t2.a JOIN t3 USING (b)",
+            "SELECT t1.a FROM t1 JOIN t2 ON t1.a = t2.a JOIN t3 ON t2.b = t3.b",
+            schema: {
+                "t1".into() => vec!["a".into()],
+                "t2".into() => vec!["a".into(), "b".into()],
+                "t3".into() => vec!["b".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn using_column_from_first_table() {
+        resolves_natural_joins!(
+            "SELECT t1.a FROM t1 JOIN t2 ON t1.a = t2.a JOIN t3 USING (b)",
+            "SELECT t1.a FROM t1 JOIN t2 ON t1.a = t2.a JOIN t3 ON t1.b = t3.b",
+            schema: {
+                "t1".into() => vec!["a".into(), "b".into()],
+                "t2".into() => vec!["a".into(), "b".into()],
+                "t3".into() => vec!["b".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn using_missing_column() {
+        let q = parse_query(Dialect::MySQL, "SELECT t1.a FROM t1 JOIN t2 USING (b)").unwrap();
+        let schema = hashmap! {
+            "t1".into() => vec!["a".into(), "b".into()],
+            "t2".into() => vec!["a".into()],
+        };
+        assert!(q.resolve_natural_joins(&schema).is_err());
+    }
+
+    #[test]
+    fn star_unsupported() {
+        let q = parse_query(Dialect::MySQL, "SELECT * FROM t1 NATURAL JOIN t2").unwrap();
+        let schema = hashmap! {
+            "t1".into() => vec!["a".into()],
+            "t2".into() => vec!["a".into()],
+        };
+        assert!(q.resolve_natural_joins(&schema).is_err());
+    }
+}
@@ -91,8 +91,8 @@ impl<'ast, 'schema> VisitorMut<'ast> for ResolveSchemaVisitor<'schema> {
         &mut self,
         cte: &'ast mut nom_sql::CommonTableExpr,
     ) -> Result<(), Self::Error> {
-        // Walk first, since the alias for the CTE is not visible inside the CTE itself (TODO:
-        // except in the case of `WITH RECURSIVE`, which we don't even parse yet).
+        // Walk first, since the alias for the CTE is not visible inside the CTE itself (except in
+        // the case of `WITH RECURSIVE`, which we don't support).
         visit_mut::walk_common_table_expr(self, cte)?;
         self.insert_alias(cte.name.clone());
         Ok(())
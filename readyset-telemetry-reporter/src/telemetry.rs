@@ -56,6 +56,12 @@ pub enum TelemetryEvent {
 
     /// A new query was run that is proxied (not cached)
     ProxiedQuery,
+
+    /// A client opened a connection to the adapter
+    ConnectionOpened,
+
+    /// A client connection to the adapter was closed
+    ConnectionClosed,
 }
 
 #[derive(Clone, Copy, Default, Serialize)]
@@ -94,6 +100,8 @@ pub struct Telemetry {
     pub schema: Option<String>,
     pub proxied_query: Option<String>,
     pub migration_status: Option<String>,
+    pub connection_duration_ms: Option<u64>,
+    pub query_count: Option<u64>,
 }
 
 impl TelemetryBuilder {
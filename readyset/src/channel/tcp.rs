@@ -20,6 +20,7 @@ use socket2::{Domain, Protocol, SockAddr, Socket, Type};
 use thiserror::Error;
 use tokio::io::{AsyncRead, AsyncWrite};
 
+use crate::table::TableReply;
 use crate::Tagged;
 
 #[derive(Debug, Error)]
@@ -163,9 +164,9 @@ pub enum RecvError {
 
 #[pin_project(project = DualTcpStreamProj)]
 pub enum DualTcpStream<S, T, T2, D> {
-    Passthrough(#[pin] AsyncBincodeStream<S, T, Tagged<()>, D>),
+    Passthrough(#[pin] AsyncBincodeStream<S, T, Tagged<TableReply>, D>),
     Upgrade(
-        #[pin] AsyncBincodeStream<S, T2, Tagged<()>, D>,
+        #[pin] AsyncBincodeStream<S, T2, Tagged<TableReply>, D>,
         Box<dyn FnMut(T2) -> T + Send + Sync>,
     ),
 }
@@ -178,7 +179,7 @@ impl<S, T, T2> From<S> for DualTcpStream<S, T, T2, AsyncDestination> {
 
 impl<S, T, T2> DualTcpStream<S, T, T2, AsyncDestination> {
     pub fn upgrade<F: 'static + FnMut(T2) -> T + Send + Sync>(stream: S, f: F) -> Self {
-        let s: AsyncBincodeStream<S, T2, Tagged<()>, AsyncDestination> =
+        let s: AsyncBincodeStream<S, T2, Tagged<TableReply>, AsyncDestination> =
             AsyncBincodeStream::from(stream).for_async();
         DualTcpStream::Upgrade(s, Box::new(f))
     }
@@ -191,11 +192,13 @@ impl<S, T, T2> DualTcpStream<S, T, T2, AsyncDestination> {
     }
 }
 
-impl<S, T, T2, D> Sink<Tagged<()>> for DualTcpStream<S, T, T2, D>
+impl<S, T, T2, D> Sink<Tagged<TableReply>> for DualTcpStream<S, T, T2, D>
 where
     S: AsyncWrite,
-    AsyncBincodeStream<S, T, Tagged<()>, D>: Sink<Tagged<()>, Error = bincode::Error>,
-    AsyncBincodeStream<S, T2, Tagged<()>, D>: Sink<Tagged<()>, Error = bincode::Error>,
+    AsyncBincodeStream<S, T, Tagged<TableReply>, D>:
+        Sink<Tagged<TableReply>, Error = bincode::Error>,
+    AsyncBincodeStream<S, T2, Tagged<TableReply>, D>:
+        Sink<Tagged<TableReply>, Error = bincode::Error>,
 {
     type Error = bincode::Error;
 
@@ -206,7 +209,7 @@ where
         }
     }
 
-    fn start_send(self: Pin<&mut Self>, item: Tagged<()>) -> Result<(), Self::Error> {
+    fn start_send(self: Pin<&mut Self>, item: Tagged<TableReply>) -> Result<(), Self::Error> {
         match self.project() {
             DualTcpStreamProj::Passthrough(abs) => abs.start_send(item),
             DualTcpStreamProj::Upgrade(abs, _) => abs.start_send(item),
@@ -233,8 +236,8 @@ where
     T: DeserializeOwned,
     T2: DeserializeOwned,
     S: AsyncRead,
-    AsyncBincodeStream<S, T, Tagged<()>, D>: Stream<Item = Result<T, bincode::Error>>,
-    AsyncBincodeStream<S, T2, Tagged<()>, D>: Stream<Item = Result<T2, bincode::Error>>,
+    AsyncBincodeStream<S, T, Tagged<TableReply>, D>: Stream<Item = Result<T, bincode::Error>>,
+    AsyncBincodeStream<S, T2, Tagged<TableReply>, D>: Stream<Item = Result<T2, bincode::Error>>,
 {
     type Item = Result<T, bincode::Error>;
 
@@ -24,13 +24,14 @@ use url::Url;
 use crate::consensus::{Authority, AuthorityControl};
 use crate::debug::info::GraphInfo;
 use crate::debug::stats;
+use crate::internal::DomainIndex;
 use crate::metrics::MetricsDump;
 use crate::recipe::changelist::ChangeList;
 use crate::recipe::ExtendRecipeSpec;
 use crate::replication::ReplicationOffsets;
-use crate::status::ReadySetStatus;
+use crate::status::{KeyDistribution, MaterializationProgress, ReadySetStatus};
 use crate::table::{Table, TableBuilder, TableRpc};
-use crate::view::{View, ViewBuilder, ViewRpc};
+use crate::view::{View, ViewBuilder, ViewMetadata, ViewRpc};
 use crate::{NodeSize, ReplicationOffset, ViewCreateRequest, ViewFilter, ViewRequest};
 
 mod rpc;
@@ -46,6 +47,31 @@ pub struct ControllerDescriptor {
     pub nonce: u64,
 }
 
+/// Information about the current leader controller, as returned by
+/// [`ReadySetHandle::leader_info`].
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct LeaderInfo {
+    /// The URI of the current leader controller
+    pub controller_uri: Url,
+    /// The leadership term of the current leader. This is incremented every time a controller
+    /// acquires leadership, so a change in term indicates that the leader has changed (or
+    /// restarted) since it was last observed.
+    pub term: u64,
+}
+
+/// A restriction on which workers a single shard of a dataflow node may be placed on, as
+/// returned by [`ReadySetHandle::node_restrictions`] and set by
+/// [`ReadySetHandle::set_node_restrictions`].
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct NodeRestriction {
+    /// The name of the dataflow node
+    pub node_name: Relation,
+    /// The shard of the node the restriction applies to
+    pub shard: usize,
+    /// If set, the shard may only be placed on a worker with this volume id
+    pub worker_volume: Option<String>,
+}
+
 struct Controller {
     authority: Arc<Authority>,
     client: hyper::Client<hyper::client::HttpConnector>,
@@ -197,6 +223,7 @@ pub struct ReadySetHandle {
     tracer: tracing::Dispatch,
     request_timeout: Option<Duration>,
     migration_timeout: Option<Duration>,
+    allow_partial_results: bool,
 }
 
 impl Clone for ReadySetHandle {
@@ -208,6 +235,7 @@ impl Clone for ReadySetHandle {
             tracer: self.tracer.clone(),
             request_timeout: self.request_timeout,
             migration_timeout: self.migration_timeout,
+            allow_partial_results: self.allow_partial_results,
         }
     }
 }
@@ -250,9 +278,18 @@ impl ReadySetHandle {
             tracer,
             request_timeout,
             migration_timeout,
+            allow_partial_results: false,
         }
     }
 
+    /// Configure whether views obtained from this handle return partial results, rather than
+    /// failing, if some of their shards are unavailable.
+    ///
+    /// See [`View::set_allow_partial_results`] for more information.
+    pub fn set_allow_partial_results(&mut self, allow_partial_results: bool) {
+        self.allow_partial_results = allow_partial_results;
+    }
+
     /// Check that the `ReadySetHandle` can accept another request.
     ///
     /// Note that this method _must_ return `Poll::Ready` before any other methods that return
@@ -360,6 +397,17 @@ impl ReadySetHandle {
             .await
     }
 
+    /// Returns a human-readable description of the plan ReadySet would use to cache the given
+    /// query, without creating a view for it.
+    pub async fn explain_query(
+        &mut self,
+        query: ViewCreateRequest,
+        dialect: dataflow_expression::Dialect,
+    ) -> ReadySetResult<String> {
+        self.rpc("explain_query", (query, dialect), self.request_timeout)
+            .await
+    }
+
     /// Obtain a `View` that allows you to query the given external view.
     ///
     /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
@@ -449,7 +497,9 @@ impl ReadySetHandle {
                 None
             };
             let view_builder = self.view_builder(view_request).await?;
-            view_builder.build(replica, views)
+            let mut view = view_builder.build(replica, views)?;
+            view.set_allow_partial_results(self.allow_partial_results);
+            Ok(view)
         }
     }
 
@@ -551,6 +601,17 @@ impl ReadySetHandle {
         self.rpc("extend_recipe", request, self.migration_timeout)
     }
 
+    /// Extend the existing recipe with all of the given lists of changes, applying them in a single
+    /// migration.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn extend_recipe_batch(
+        &mut self,
+        changelists: Vec<ChangeList>,
+    ) -> impl Future<Output = ReadySetResult<()>> + '_ {
+        self.rpc("extend_recipe_batch", changelists, self.migration_timeout)
+    }
+
     /// Extend the existing recipe with the given set of queries and don't require leader ready.
     ///
     /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
@@ -675,6 +736,128 @@ impl ReadySetHandle {
         self.rpc("healthy_workers", (), self.request_timeout)
     }
 
+    /// Manually evict the worker with the given URI from the cluster, reassigning all of its
+    /// domains to the remaining workers as if it had failed.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn evict_worker(&mut self, worker: Url) -> impl Future<Output = ReadySetResult<()>> + '_ {
+        self.rpc("evict_worker", worker, self.migration_timeout)
+    }
+
+    /// Reschedule the reader domains of the queries with the given names with as many replicas as
+    /// they should have (either their desired number of [reader replicas][set_reader_replicas],
+    /// or the number given by the cluster's replication strategy), if there are enough workers to
+    /// run them.
+    ///
+    /// [set_reader_replicas]: Self::set_reader_replicas
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn replicate_readers(
+        &mut self,
+        queries: Vec<Relation>,
+    ) -> impl Future<Output = ReadySetResult<()>> + '_ {
+        self.rpc("replicate_readers", queries, self.migration_timeout)
+    }
+
+    /// Set the desired number of replicas of the reader for the query with the given name.
+    ///
+    /// The controller will maintain that many replicas of the query's reader, as long as there
+    /// are enough workers in the cluster to run them, adding more replicas as workers join.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn set_reader_replicas(
+        &mut self,
+        query: Relation,
+        replicas: usize,
+    ) -> impl Future<Output = ReadySetResult<()>> + '_ {
+        self.rpc(
+            "set_reader_replicas",
+            (query, replicas),
+            self.migration_timeout,
+        )
+    }
+
+    /// Returns the current value of all the feature flags set for this deployment, keyed by
+    /// name.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn feature_flags(
+        &mut self,
+    ) -> impl Future<Output = ReadySetResult<BTreeMap<String, bool>>> + '_ {
+        self.rpc("feature_flags", (), self.request_timeout)
+    }
+
+    /// Enable or disable the feature flag with the given name for this deployment.
+    ///
+    /// Adapters pick up the new value of the flag the next time they refresh their feature flags.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn set_feature_flag(
+        &mut self,
+        name: String,
+        enabled: bool,
+    ) -> impl Future<Output = ReadySetResult<()>> + '_ {
+        self.rpc("set_feature_flag", (name, enabled), self.request_timeout)
+    }
+
+    /// Get the placement restrictions for all dataflow nodes, ordered by node name and shard.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn node_restrictions(
+        &mut self,
+    ) -> impl Future<Output = ReadySetResult<Vec<NodeRestriction>>> + '_ {
+        self.rpc("get_node_restrictions", (), self.request_timeout)
+    }
+
+    /// Set the placement restrictions for the dataflow node shards in the given set of
+    /// restrictions, replacing any existing restrictions for those node shards. Restrictions for
+    /// all other node shards are left unchanged.
+    ///
+    /// Domains that are already running one of those node shards on a worker that doesn't meet
+    /// its new restriction are moved onto a worker that does at the start of the next migration.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn set_node_restrictions(
+        &mut self,
+        restrictions: Vec<NodeRestriction>,
+    ) -> impl Future<Output = ReadySetResult<()>> + '_ {
+        self.rpc("node_restrictions", restrictions, self.request_timeout)
+    }
+
+    /// Returns bookkeeping information about every view in the graph - when it was created, by
+    /// whom, and when it was last looked up - keyed by the name of the view.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn view_metadata(
+        &mut self,
+    ) -> impl Future<Output = ReadySetResult<BTreeMap<Relation, ViewMetadata>>> + '_ {
+        self.rpc("view_metadata", (), self.request_timeout)
+    }
+
+    /// Returns the indices of all domains which no longer contain any (non-removed) nodes, but are
+    /// still running.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn orphaned_domains(
+        &mut self,
+    ) -> impl Future<Output = ReadySetResult<Vec<DomainIndex>>> + '_ {
+        self.rpc("orphaned_domains", (), self.request_timeout)
+    }
+
+    /// Discard the contents of the base table with the given name and snapshot it again from the
+    /// upstream database, without affecting the replication of any other tables.
+    ///
+    /// This returns as soon as the resnapshot has been requested; the replicator then restarts
+    /// replication, and the table's contents are replaced once the snapshot finishes.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn resnapshot_table(
+        &mut self,
+        table: Relation,
+    ) -> impl Future<Output = ReadySetResult<()>> + '_ {
+        self.rpc("resnapshot_table", table, self.request_timeout)
+    }
+
     /// Get the url of the current noria controller.
     ///
     /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
@@ -682,6 +865,13 @@ impl ReadySetHandle {
         self.rpc("controller_uri", (), self.request_timeout)
     }
 
+    /// Get the url and leadership term of the current noria controller.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn leader_info(&mut self) -> impl Future<Output = ReadySetResult<LeaderInfo>> + '_ {
+        self.rpc("leader_info", (), self.request_timeout)
+    }
+
     /// Get a set of all replication offsets for the entire system.
     ///
     /// See [the documentation for PersistentState](::readyset_dataflow::state::persistent_state)
@@ -699,6 +889,31 @@ impl ReadySetHandle {
         self.rpc("snapshotting_tables", (), self.request_timeout)
     }
 
+    /// Returns the progress of materializing the view with the given name, based on how many of
+    /// the base tables it is derived from have finished snapshotting.
+    ///
+    /// Progress is only tracked per table, and doesn't account for the view catching up with the
+    /// snapshotted rows; see [`MaterializationProgress`].
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn materialization_progress(
+        &mut self,
+        view: Relation,
+    ) -> impl Future<Output = ReadySetResult<MaterializationProgress>> + '_ {
+        self.rpc("materialization_progress", view, self.request_timeout)
+    }
+
+    /// Returns the number of keys materialized in each shard of the reader for the view with the
+    /// given name, along with a measure of how unevenly the keys are distributed across shards.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn key_distribution(
+        &mut self,
+        view: Relation,
+    ) -> impl Future<Output = ReadySetResult<KeyDistribution>> + '_ {
+        self.rpc("key_distribution", view, self.request_timeout)
+    }
+
     /// Return a map of node indices to key counts.
     pub fn node_sizes(
         &mut self,
@@ -706,6 +921,16 @@ impl ReadySetHandle {
         self.rpc("node_sizes", (), self.request_timeout)
     }
 
+    /// Returns the names of all the queries whose views are derived from the given base table.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn queries_for_table(
+        &mut self,
+        table: Relation,
+    ) -> impl Future<Output = ReadySetResult<Vec<Relation>>> + '_ {
+        self.rpc("queries_for_table", table, self.request_timeout)
+    }
+
     /// Return whether the leader is ready or not.
     pub fn leader_ready(&mut self) -> impl Future<Output = ReadySetResult<bool>> + '_ {
         self.rpc("leader_ready", (), self.request_timeout)
@@ -726,6 +951,11 @@ impl ReadySetHandle {
         self.rpc("version", (), self.request_timeout)
     }
 
+    /// Returns the current schema version of the leader's recipe
+    pub fn schema_version(&mut self) -> impl Future<Output = ReadySetResult<usize>> + '_ {
+        self.rpc("schema_version", (), self.request_timeout)
+    }
+
     /// Returns the amount of actually allocated memory
     pub fn allocated_bytes(&mut self) -> impl Future<Output = ReadySetResult<Option<usize>>> + '_ {
         self.rpc("allocated_bytes", (), self.request_timeout)
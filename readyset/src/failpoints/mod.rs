@@ -10,5 +10,17 @@
 pub const AUTHORITY: &str = "authority";
 /// Injects an error in crate::worker::readers::listen()
 pub const READ_QUERY: &str = "read-query";
+/// Makes reads from all but the first shard of sharded readers fail, as if those shards were
+/// unavailable
+pub const UNAVAILABLE_READER_SHARDS: &str = "unavailable-reader-shards";
+/// Delays every read from a cache made by the adapter by the number of milliseconds given to the
+/// `return` action, eg `return(1000)`
+pub const SLOW_CACHE_READS: &str = "slow-cache-reads";
 /// Imitates traffic being dropped from upstream
 pub const UPSTREAM: &str = "upstream";
+/// Stops the replicator from applying any more replication actions while enabled with the
+/// `return` action, until the failpoint is turned off again
+pub const PAUSE_REPLICATION: &str = "pause-replication";
+/// Injects an error while applying a migration plan, after any new domains have been placed but
+/// before any messages have been sent to them
+pub const MIGRATION_PLAN_APPLY: &str = "migration-plan-apply";
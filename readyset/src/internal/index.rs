@@ -38,6 +38,15 @@ impl PartialOrd for IndexType {
     }
 }
 
+impl From<nom_sql::IndexType> for IndexType {
+    fn from(index_type: nom_sql::IndexType) -> Self {
+        match index_type {
+            nom_sql::IndexType::BTree => Self::BTreeMap,
+            nom_sql::IndexType::Hash => Self::HashMap,
+        }
+    }
+}
+
 impl IndexType {
     /// Return the [`IndexType`] that is best able to satisfy lookups via the given operator, if any
     pub fn for_operator(operator: BinaryOperator) -> Option<Self> {
@@ -276,7 +276,8 @@ pub use readyset_errors::{ReadySetError, ReadySetResult};
 use serde::{Deserialize, Serialize};
 use tokio::task_local;
 pub use view::{
-    ColumnBase, ColumnSchema, KeyColumnIdx, PlaceholderIdx, ViewPlaceholder, ViewSchema,
+    ColumnBase, ColumnSchema, KeyColumnIdx, PlaceholderIdx, ViewMetadata, ViewPlaceholder,
+    ViewSchema,
 };
 
 pub use crate::consensus::ZookeeperAuthority;
@@ -357,10 +358,10 @@ impl<T> From<T> for Tagged<T> {
 use url::Url;
 
 pub use crate::consensus::WorkerDescriptor;
-pub use crate::controller::{ControllerDescriptor, ReadySetHandle};
+pub use crate::controller::{ControllerDescriptor, LeaderInfo, NodeRestriction, ReadySetHandle};
 pub use crate::table::{Modification, Operation, Table, TableOperation, TableRequest};
 #[doc(hidden)]
-pub use crate::table::{PacketData, PacketPayload, PacketTrace};
+pub use crate::table::{PacketData, PacketPayload, PacketTrace, TableReply};
 pub use crate::view::View;
 #[doc(hidden)]
 pub use crate::view::{
@@ -493,6 +494,12 @@ impl AddAssign for KeyCount {
     }
 }
 
+impl From<NodeMaterializedSize> for usize {
+    fn from(size: NodeMaterializedSize) -> Self {
+        size.0
+    }
+}
+
 impl AddAssign for NodeMaterializedSize {
     /// Adds the node size for the rhs node size to ourselves.
     fn add_assign(&mut self, rhs: Self) {
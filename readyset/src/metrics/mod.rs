@@ -307,6 +307,9 @@ pub mod recorded {
     /// Counter: The number of controller HTTP requests received by the readyset-server.
     pub const SERVER_CONTROLLER_REQUESTS: &str = "server.controller_requests";
 
+    /// Gauge: The number of domain replicas currently running on a worker.
+    pub const WORKER_RUNNING_DOMAINS: &str = "worker.running_domains";
+
     /// Counter: The number of lookup requests to a base table nodes state.
     ///
     ///
@@ -35,8 +35,8 @@ use dataflow_expression::Dialect;
 use nom_locate::LocatedSpan;
 use nom_sql::{
     AlterTableStatement, CacheInner, CreateCacheStatement, CreateTableStatement,
-    CreateViewStatement, DropTableStatement, DropViewStatement, Relation, SelectStatement,
-    SqlIdentifier, SqlQuery,
+    CreateViewStatement, DropTableStatement, DropViewStatement, IndexType, Relation,
+    SelectStatement, SqlIdentifier, SqlQuery,
 };
 use readyset_data::DfType;
 use readyset_errors::{unsupported, ReadySetError, ReadySetResult};
@@ -56,6 +56,11 @@ pub struct ChangeList {
 
     /// The SQL dialect to use for all types and expressions in queries added by this ChangeList
     pub dialect: Dialect,
+
+    /// The name of the user who made these changes, if known. This is recorded as the creator of
+    /// any views created by this ChangeList.
+    #[serde(default)]
+    pub user: Option<String>,
 }
 
 /// Types that can be converted directly into a list of [`Change`]s. Used to type-overload
@@ -123,6 +128,7 @@ impl ChangeList {
             changes: vec![],
             schema_search_path: vec![],
             dialect,
+            user: None,
         }
     }
 
@@ -217,6 +223,7 @@ impl ChangeList {
             changes,
             schema_search_path: vec![],
             dialect,
+            user: None,
         })
     }
 
@@ -234,6 +241,7 @@ impl ChangeList {
             changes: changes.into_changes(),
             schema_search_path: vec![],
             dialect,
+            user: None,
         }
     }
 
@@ -250,6 +258,14 @@ impl ChangeList {
         }
     }
 
+    /// Construct a new `ChangeList` from `self`, but made by the user with the given name
+    pub fn with_user(self, user: String) -> Self {
+        Self {
+            user: Some(user),
+            ..self
+        }
+    }
+
     /// Return a mutable reference to the changes in this `ChangeList`
     pub fn changes_mut(&mut self) -> &mut Vec<Change> {
         &mut self.changes
@@ -305,6 +321,10 @@ pub enum Change {
         /// A specification for the change to make to the type
         change: AlterTypeChange,
     },
+    /// Record that a table in the upstream database is not being replicated, because it was
+    /// filtered out of replication (eg with `--replication-tables`). Queries that reference the
+    /// table will be rejected with an error saying so.
+    AddNonReplicatedRelation(Relation),
     /// The removal of a [`RecipeExpr`].
     Drop {
         /// The name of the relation to remove.
@@ -316,8 +336,16 @@ pub enum Change {
 
 impl Change {
     /// Creates a new [`Change::CreateCache`] from the given `name` and
-    /// [`SelectStatement`].
-    pub fn create_cache<N>(name: N, statement: SelectStatement, always: bool) -> Self
+    /// [`SelectStatement`]. If `index_type` is provided, it overrides the type of index that
+    /// would otherwise be inferred for the cache's reader. If `max_mem` is provided, the cache's
+    /// reader will evict its least recently used keys to keep its size under that many bytes.
+    pub fn create_cache<N>(
+        name: N,
+        statement: SelectStatement,
+        always: bool,
+        index_type: Option<IndexType>,
+        max_mem: Option<u64>,
+    ) -> Self
     where
         N: Into<Relation>,
     {
@@ -325,6 +353,8 @@ impl Change {
             name: Some(name.into()),
             inner: CacheInner::Statement(Box::new(statement)),
             always,
+            index_type,
+            max_mem,
         })
     }
 
@@ -5,6 +5,7 @@ use std::cmp::{min_by_key, Ordering};
 use std::collections::HashMap;
 use std::fmt;
 use std::hash::Hash;
+use std::str::FromStr;
 
 use nom_sql::Relation;
 use readyset_errors::{ReadySetError, ReadySetResult};
@@ -51,6 +52,42 @@ impl fmt::Display for ReplicationOffset {
     }
 }
 
+impl FromStr for ReplicationOffset {
+    type Err = ReadySetError;
+
+    /// Parse a [`ReplicationOffset`] from the format produced by its [`Display`](fmt::Display)
+    /// implementation - either a MySQL binlog position such as `binlog.000003:1234`, or a
+    /// PostgreSQL LSN such as `wal[0/16B6C50]`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || ReadySetError::ReplicationFailed(format!("Invalid replication offset {s}"));
+
+        if let Some(lsn) = s.strip_prefix("wal[").and_then(|s| s.strip_suffix(']')) {
+            let (hi, lo) = lsn.split_once('/').ok_or_else(invalid)?;
+            let hi = u32::from_str_radix(hi, 16).map_err(|_| invalid())?;
+            let lo = u32::from_str_radix(lo, 16).map_err(|_| invalid())?;
+            return Ok(ReplicationOffset {
+                offset: ((hi as u128) << 32) | lo as u128,
+                replication_log_name: String::new(),
+            });
+        }
+
+        let (binlog_file, position) = s.rsplit_once(':').ok_or_else(invalid)?;
+        let (basename, suffix) = binlog_file.rsplit_once('.').ok_or_else(invalid)?;
+        let suffix_len = suffix.len() as u128;
+        if basename.is_empty() || suffix_len > 17 {
+            return Err(invalid());
+        }
+        let suffix = suffix.parse::<u128>().map_err(|_| invalid())?;
+        let position = position.parse::<u32>().map_err(|_| invalid())?;
+
+        Ok(ReplicationOffset {
+            offset: (suffix_len << 123) + (suffix << 64) + (position as u128),
+            replication_log_name: basename.to_owned(),
+        })
+    }
+}
+
 impl PartialOrd for ReplicationOffset {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         if other.replication_log_name != self.replication_log_name {
@@ -345,4 +382,30 @@ mod tests {
             assert!(res.is_none());
         }
     }
+
+    mod from_str {
+        use super::*;
+
+        #[test]
+        fn binlog_round_trip() {
+            let offset = ReplicationOffset::from_str("binlog.000003:1234").unwrap();
+            assert_eq!(offset.replication_log_name, "binlog");
+            assert_eq!(offset.to_string(), "binlog.000003:1234");
+        }
+
+        #[test]
+        fn wal_round_trip() {
+            let offset = ReplicationOffset::from_str("wal[1/16B6C50]").unwrap();
+            assert_eq!(offset.replication_log_name, "");
+            assert_eq!(offset.offset, 0x1_016B_6C50);
+            assert_eq!(offset.to_string(), "wal[1/16B6C50]");
+        }
+
+        #[test]
+        fn invalid() {
+            assert!(ReplicationOffset::from_str("binlog").is_err());
+            assert!(ReplicationOffset::from_str("binlog.abc:12").is_err());
+            assert!(ReplicationOffset::from_str("wal[16B6C50]").is_err());
+        }
+    }
 }
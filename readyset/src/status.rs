@@ -9,13 +9,17 @@
 //! that can be passed to various SQL clients.
 use std::convert::TryFrom;
 use std::fmt::{self, Display};
+use std::str::FromStr;
 
 use mysql_common::row::Row;
 use readyset_errors::{internal, ReadySetError};
 use serde::{Deserialize, Serialize};
 
+use crate::replication::ReplicationOffset;
+
 // Consts for variable names.
 const SNAPSHOT_STATUS_VARIABLE: &str = "Snapshot Status";
+const REPLICATION_OFFSET_VARIABLE: &str = "Replication Offset";
 
 /// ReadySetStatus holds information regarding the status of ReadySet, similar to
 /// [`SHOW STATUS`](https://dev.mysql.com/doc/refman/8.0/en/show-status.html) in MySQL.
@@ -25,7 +29,10 @@ const SNAPSHOT_STATUS_VARIABLE: &str = "Snapshot Status";
 pub struct ReadySetStatus {
     /// The snapshot status of the current leader.
     pub snapshot_status: SnapshotStatus,
-    //TODO: Include binlog position and other fields helpful for evaluating a ReadySet cluster.
+    /// The offset in the upstream database's replication log that ReadySet has replicated up to,
+    /// if all tables have finished snapshotting.
+    pub replication_offset: Option<ReplicationOffset>,
+    //TODO: Include other fields helpful for evaluating a ReadySet cluster.
 }
 
 impl TryFrom<Vec<(String, String)>> for ReadySetStatus {
@@ -33,10 +40,14 @@ impl TryFrom<Vec<(String, String)>> for ReadySetStatus {
     fn try_from(vars: Vec<(String, String)>) -> Result<Self, Self::Error> {
         let mut res = ReadySetStatus {
             snapshot_status: SnapshotStatus::InProgress,
+            replication_offset: None,
         };
         for v in vars {
             match (v.0.as_str(), v.1) {
                 (SNAPSHOT_STATUS_VARIABLE, v) => res.snapshot_status = SnapshotStatus::try_from(v)?,
+                (REPLICATION_OFFSET_VARIABLE, v) => {
+                    res.replication_offset = Some(ReplicationOffset::from_str(&v)?)
+                }
                 (_, _) => {
                     internal!("Invalid ReadySetStatus variable")
                 }
@@ -49,10 +60,14 @@ impl TryFrom<Vec<(String, String)>> for ReadySetStatus {
 
 impl From<ReadySetStatus> for Vec<(String, String)> {
     fn from(status: ReadySetStatus) -> Vec<(String, String)> {
-        vec![(
+        let mut res = vec![(
             SNAPSHOT_STATUS_VARIABLE.to_string(),
             status.snapshot_status.to_string(),
-        )]
+        )];
+        if let Some(offset) = status.replication_offset {
+            res.push((REPLICATION_OFFSET_VARIABLE.to_string(), offset.to_string()));
+        }
+        res
     }
 }
 
@@ -108,6 +123,78 @@ impl TryFrom<String> for SnapshotStatus {
     }
 }
 
+/// The progress of materializing a view while the base tables it is derived from are being
+/// snapshotted.
+///
+/// Progress is counted in whole base tables, not rows: a table only counts towards
+/// [`snapshotted_tables`](Self::snapshotted_tables) once its entire snapshot has been written, so
+/// a view derived from a single large table will report no progress at all until that table is
+/// done. Progress being complete also only means that the snapshot has been written to the base
+/// tables - the view itself may still be processing the snapshotted rows.
+///
+/// Returned via the /materialization_progress RPC.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub struct MaterializationProgress {
+    /// The number of base tables the view is derived from which have finished snapshotting
+    pub snapshotted_tables: usize,
+    /// The total number of base tables the view is derived from
+    pub total_tables: usize,
+}
+
+impl MaterializationProgress {
+    /// Returns the fraction of the view's base tables which have finished snapshotting, between
+    /// 0.0 and 1.0
+    pub fn fraction(&self) -> f64 {
+        if self.total_tables == 0 {
+            1.0
+        } else {
+            self.snapshotted_tables as f64 / self.total_tables as f64
+        }
+    }
+
+    /// Returns true if all of the view's base tables have finished snapshotting.
+    ///
+    /// This doesn't mean the view has caught up with the snapshotted rows yet; see the
+    /// [type-level documentation](Self).
+    pub fn is_snapshot_complete(&self) -> bool {
+        self.snapshotted_tables >= self.total_tables
+    }
+}
+
+/// The distribution of keys across the shards of a view's reader, used to detect uneven (skewed)
+/// distributions of keys which can make lookups into a sharded view uneven.
+///
+/// Returned via the /key_distribution RPC.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct KeyDistribution {
+    /// The number of keys materialized in each shard of the view's reader, indexed by shard
+    pub shard_key_counts: Vec<usize>,
+    /// The ratio of the number of keys in the largest shard to the mean number of keys per shard.
+    ///
+    /// This is 1.0 if keys are perfectly evenly distributed (or if the reader has no keys at
+    /// all), and equal to the number of shards if all keys are in a single shard.
+    pub skew: f64,
+}
+
+impl KeyDistribution {
+    /// Construct a new [`KeyDistribution`] from the number of keys in each shard of a reader,
+    /// computing the skew of the distribution
+    pub fn new(shard_key_counts: Vec<usize>) -> Self {
+        let total: usize = shard_key_counts.iter().sum();
+        let max = shard_key_counts.iter().copied().max().unwrap_or(0);
+        let skew = if total == 0 {
+            1.0
+        } else {
+            max as f64 * shard_key_counts.len() as f64 / total as f64
+        };
+
+        Self {
+            shard_key_counts,
+            skew,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,10 +203,47 @@ mod tests {
     fn readyset_status_round_trip() {
         let original = ReadySetStatus {
             snapshot_status: SnapshotStatus::Completed,
+            replication_offset: Some(ReplicationOffset {
+                offset: 0x1_016B_6C50,
+                replication_log_name: String::new(),
+            }),
         };
         let intermediate: Vec<(String, String)> = original.clone().into();
         let round_tripped = ReadySetStatus::try_from(intermediate).unwrap();
 
         assert_eq!(original, round_tripped);
     }
+
+    #[test]
+    fn materialization_progress_fraction() {
+        let in_progress = MaterializationProgress {
+            snapshotted_tables: 1,
+            total_tables: 4,
+        };
+        assert_eq!(in_progress.fraction(), 0.25);
+        assert!(!in_progress.is_snapshot_complete());
+
+        let complete = MaterializationProgress {
+            snapshotted_tables: 4,
+            total_tables: 4,
+        };
+        assert_eq!(complete.fraction(), 1.0);
+        assert!(complete.is_snapshot_complete());
+
+        let no_tables = MaterializationProgress {
+            snapshotted_tables: 0,
+            total_tables: 0,
+        };
+        assert_eq!(no_tables.fraction(), 1.0);
+        assert!(no_tables.is_snapshot_complete());
+    }
+
+    #[test]
+    fn key_distribution_skew() {
+        assert_eq!(KeyDistribution::new(vec![5, 5, 5, 5]).skew, 1.0);
+        assert_eq!(KeyDistribution::new(vec![8, 0, 0, 0]).skew, 4.0);
+        assert_eq!(KeyDistribution::new(vec![3, 1]).skew, 1.5);
+        assert_eq!(KeyDistribution::new(vec![0, 0]).skew, 1.0);
+        assert_eq!(KeyDistribution::new(vec![]).skew, 1.0);
+    }
 }
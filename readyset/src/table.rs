@@ -17,9 +17,7 @@ use itertools::Either;
 use nom_sql::{CreateTableStatement, Relation, SqlIdentifier};
 use petgraph::graph::NodeIndex;
 use readyset_data::DfValue;
-use readyset_errors::{
-    internal, internal_err, rpc_err, table_err, unsupported, ReadySetError, ReadySetResult,
-};
+use readyset_errors::{internal, internal_err, rpc_err, table_err, ReadySetError, ReadySetResult};
 use serde::{Deserialize, Serialize};
 use tokio::io::AsyncWriteExt;
 use tokio_tower::multiplex;
@@ -72,6 +70,13 @@ where
 pub enum TableOperation {
     /// Insert the contained row.
     Insert(Vec<DfValue>),
+    /// Insert the contained row, failing the whole batch of operations with
+    /// [`ReadySetError::DuplicateKey`] if the base table already has a row with the same primary
+    /// key, or if another operation in the batch inserts one.
+    ///
+    /// Unlike [`TableOperation::Insert`], which silently ignores rows with a duplicate primary
+    /// key, this is used for inserts made by clients rather than replicated from upstream.
+    InsertUnique(Vec<DfValue>),
     /// Delete a row with the contained key.
     DeleteByKey {
         /// The key.
@@ -119,7 +124,7 @@ impl TableOperation {
     #[doc(hidden)]
     pub fn row(&self) -> Option<&[DfValue]> {
         match *self {
-            TableOperation::Insert(ref r) => Some(r),
+            TableOperation::Insert(ref r) | TableOperation::InsertUnique(ref r) => Some(r),
             TableOperation::InsertOrUpdate { ref row, .. } => Some(row),
             _ => None,
         }
@@ -134,7 +139,7 @@ impl TableOperation {
     pub fn shards(&self, key_col: usize, num_shards: usize) -> impl Iterator<Item = usize> {
         #[allow(clippy::indexing_slicing)]
         let key = match self {
-            TableOperation::Insert(row) => Some(&row[key_col]),
+            TableOperation::Insert(row) | TableOperation::InsertUnique(row) => Some(&row[key_col]),
             TableOperation::DeleteByKey { key } => Some(&key[0]),
             TableOperation::DeleteRow { row } => Some(&row[key_col]),
             TableOperation::Update { key, .. } => Some(&key[0]),
@@ -159,8 +164,12 @@ impl From<Vec<DfValue>> for TableOperation {
     }
 }
 
-type Transport =
-    AsyncBincodeStream<tokio::net::TcpStream, Tagged<()>, Tagged<PacketData>, AsyncDestination>;
+type Transport = AsyncBincodeStream<
+    tokio::net::TcpStream,
+    Tagged<TableReply>,
+    Tagged<PacketData>,
+    AsyncDestination,
+>;
 
 #[derive(Debug)]
 struct Endpoint {
@@ -263,6 +272,22 @@ pub enum PacketPayload {
     Input(Vec<TableOperation>),
     /// A new timestamp to update the base table.
     Timestamp(consistency::Timestamp),
+    /// A lookup of the rows in the base table's state with the given primary key.
+    Lookup(Vec<DfValue>),
+}
+
+/// The reply sent by a base table's domain once it has handled a [`PacketData`].
+#[derive(Debug, Serialize, Deserialize)]
+pub enum TableReply {
+    /// The packet was processed successfully
+    Ack,
+    /// The write in a [`PacketPayload::Input`] was processed successfully, matching the given
+    /// number of existing rows with its updates and deletes
+    RowsAffected(u64),
+    /// The rows found for a [`PacketPayload::Lookup`]
+    Rows(Vec<Vec<DfValue>>),
+    /// The packet could not be processed
+    Error(ReadySetError),
 }
 
 impl fmt::Debug for PacketData {
@@ -390,7 +415,7 @@ impl Table {
     fn input(
         &mut self,
         mut i: PacketData,
-    ) -> impl Future<Output = Result<Tagged<()>, ReadySetError>> + Send {
+    ) -> impl Future<Output = Result<Tagged<u64>, ReadySetError>> + Send {
         let span = if crate::trace_next_op() {
             Some(tracing::trace_span!(
                 "table-request",
@@ -407,8 +432,19 @@ impl Table {
                 .try_into()
                 .map_err(|_| ReadySetError::WrongPacketDataType)?;
             for op in ops {
+                if matches!(
+                    op,
+                    TableOperation::DeleteByKey { .. }
+                        | TableOperation::Update { .. }
+                        | TableOperation::InsertOrUpdate { .. }
+                ) {
+                    self.check_has_primary_key()?;
+                }
+
                 match op {
-                    TableOperation::Insert(ref row) | TableOperation::DeleteRow { ref row } => {
+                    TableOperation::Insert(ref row)
+                    | TableOperation::InsertUnique(ref row)
+                    | TableOperation::DeleteRow { ref row } => {
                         if row.len() != ncols {
                             return Err(ReadySetError::WrongColumnCount(ncols, row.len()));
                         }
@@ -473,7 +509,10 @@ impl Table {
                 let _guard = span.as_ref().map(tracing::Span::enter);
                 tracing::trace!("submit request");
                 future::Either::Left(future::Either::Right(
-                    table_rpc.call(request).map_err(rpc_err!("Table::input")),
+                    table_rpc
+                        .call(request)
+                        .map_err(rpc_err!("Table::input"))
+                        .and_then(|reply| future::ready(check_reply(reply.v).map(Tagged::from))),
                 ))
             }
             _ => {
@@ -558,8 +597,10 @@ impl Table {
 
                 future::Either::Right(
                     wait_for
-                        .try_for_each(|_| async { Ok(()) })
                         .map_err(rpc_err!("Table::input"))
+                        .try_fold(0, |rows_affected, reply| {
+                            future::ready(check_reply(reply.v).map(|n| rows_affected + n))
+                        })
                         .map_ok(Tagged::from),
                 )
             }
@@ -571,7 +612,7 @@ impl Table {
     fn timestamp(
         &mut self,
         t: PacketData,
-    ) -> impl Future<Output = Result<Tagged<()>, ReadySetError>> + Send {
+    ) -> impl Future<Output = Result<Tagged<u64>, ReadySetError>> + Send {
         let nshards = self.shards.len();
         match self.shards.first_mut() {
             Some(table_rpc) if nshards == 1 => {
@@ -579,7 +620,8 @@ impl Table {
                 future::Either::Left(
                     table_rpc
                         .call(request)
-                        .map_err(rpc_err!("Table::timestamp")),
+                        .map_err(rpc_err!("Table::timestamp"))
+                        .map_ok(|_| Tagged::from(0)),
                 )
             }
             _ => {
@@ -605,13 +647,23 @@ impl Table {
                     wait_for
                         .try_for_each(|_| async { Ok(()) })
                         .map_err(rpc_err!("Table::timestamp"))
-                        .map_ok(Tagged::from),
+                        .map_ok(|()| Tagged::from(0)),
                 ))
             }
         }
     }
 }
 
+/// Returns the error carried by a [`TableReply`] to a write, if any, or else the number of rows
+/// the write affected
+fn check_reply(reply: TableReply) -> ReadySetResult<u64> {
+    match reply {
+        TableReply::Error(e) => Err(e),
+        TableReply::RowsAffected(n) => Ok(n),
+        TableReply::Ack | TableReply::Rows(_) => Ok(0),
+    }
+}
+
 /// A request to the table service.
 pub enum TableRequest {
     /// A set of operations to apply on the table.
@@ -622,9 +674,9 @@ pub enum TableRequest {
 
 impl Service<TableRequest> for Table {
     type Error = ReadySetError;
-    type Response = <TableRpc as Service<Tagged<PacketData>>>::Response;
+    type Response = Tagged<u64>;
 
-    type Future = impl Future<Output = Result<Tagged<()>, ReadySetError>> + Send;
+    type Future = impl Future<Output = Result<Tagged<u64>, ReadySetError>> + Send;
 
     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         for s in &mut self.shards {
@@ -688,6 +740,7 @@ impl Table {
             // get a handle to the underlying data vector
             let r = match *r {
                 TableOperation::Insert(ref mut row)
+                | TableOperation::InsertUnique(ref mut row)
                 | TableOperation::InsertOrUpdate { ref mut row, .. } => row,
                 _ => unimplemented!("we need to shift the update/delete cols!"),
             };
@@ -810,7 +863,18 @@ impl Table {
         Ok(self.call(r).await?.v)
     }
 
-    async fn quick_n_dirty_with_timeout(&mut self, r: TableRequest) -> ReadySetResult<()> {
+    /// Returns [`ReadySetError::NoPrimaryKey`] if this table has no primary key, and so can't have
+    /// rows deleted or updated by key
+    fn check_has_primary_key(&self) -> ReadySetResult<()> {
+        if self.key.is_empty() || !self.key_is_primary {
+            return Err(ReadySetError::NoPrimaryKey {
+                table: self.table_name.name.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    async fn quick_n_dirty_with_timeout(&mut self, r: TableRequest) -> ReadySetResult<u64> {
         tokio::time::timeout(self.request_timeout, self.quick_n_dirty(r))
             .await
             .map_err(|_| internal_err!("Timeout during table request"))?
@@ -825,6 +889,7 @@ impl Table {
             u.into(),
         )]))
         .await
+        .map(|_| ())
     }
 
     /// Insert multiple rows of data into this base table.
@@ -839,6 +904,7 @@ impl Table {
                 .collect::<Vec<_>>(),
         ))
         .await
+        .map(|_| ())
     }
 
     /// Perform multiple operation on this base table.
@@ -851,10 +917,12 @@ impl Table {
             i.into_iter().map(Into::into).collect::<Vec<_>>(),
         ))
         .await
+        .map(|_| ())
     }
 
-    /// Delete the row with the given key from this base table.
-    pub async fn delete<I>(&mut self, key: I) -> ReadySetResult<()>
+    /// Delete the row with the given key from this base table, returning the number of rows that
+    /// were deleted.
+    pub async fn delete<I>(&mut self, key: I) -> ReadySetResult<u64>
     where
         I: Into<Vec<DfValue>>,
     {
@@ -874,19 +942,20 @@ impl Table {
             TableOperation::DeleteRow { row: row.into() },
         ]))
         .await
+        .map(|_| ())
     }
 
     /// Update the row with the given key in this base table.
     ///
     /// `u` is a set of column-modification pairs, where for each pair `(i, m)`, the modification
     /// `m` will be applied to column `i` of the record with key `key`.
-    pub async fn update<V>(&mut self, key: Vec<DfValue>, u: V) -> ReadySetResult<()>
+    ///
+    /// Returns the number of rows that were updated.
+    pub async fn update<V>(&mut self, key: Vec<DfValue>, u: V) -> ReadySetResult<u64>
     where
         V: IntoIterator<Item = (usize, Modification)>,
     {
-        if self.key.is_empty() || !self.key_is_primary {
-            unsupported!("update operations can only be applied to base nodes with key columns")
-        }
+        self.check_has_primary_key()?;
 
         let mut update = vec![Modification::None; self.columns.len()];
         for (coli, m) in u {
@@ -919,9 +988,7 @@ impl Table {
     where
         V: IntoIterator<Item = (usize, Modification)>,
     {
-        if self.key.is_empty() || !self.key_is_primary {
-            unsupported!("update operations can only be applied to base nodes with key columns")
-        }
+        self.check_has_primary_key()?;
 
         let mut set = vec![Modification::None; self.columns.len()];
         for (coli, m) in update {
@@ -943,6 +1010,76 @@ impl Table {
             },
         ]))
         .await
+        .map(|_| ())
+    }
+
+    /// Look up all the rows in this base table whose primary key is equal to `key`, reading them
+    /// directly from the base table's state rather than from a view.
+    pub async fn lookup(&mut self, key: Vec<DfValue>) -> ReadySetResult<Vec<Vec<DfValue>>> {
+        self.check_has_primary_key()?;
+        if key.len() != self.key.len() {
+            return Err(table_err(
+                self.table_name.clone(),
+                ReadySetError::WrongKeyColumnCount(self.key.len(), key.len()),
+            ));
+        }
+
+        let nshards = self.shards.len();
+        let shard = match key.as_slice() {
+            _ if nshards == 1 => 0,
+            [k] => crate::shard_by(k, nshards),
+            _ => internal!("base sharded by complex key"),
+        };
+        let request = Tagged::from(PacketData {
+            dst: self.node,
+            data: PacketPayload::Lookup(key),
+            trace: None,
+        });
+
+        let table_rpc = self
+            .shards
+            .get_mut(shard)
+            .ok_or_else(|| internal_err!("Shard {shard} out of bounds"))?;
+        let reply = tokio::time::timeout(self.request_timeout, async move {
+            future::poll_fn(|cx| table_rpc.poll_ready(cx))
+                .await
+                .map_err(rpc_err!("Table::lookup"))?;
+            table_rpc
+                .call(request)
+                .await
+                .map_err(rpc_err!("Table::lookup"))
+        })
+        .await
+        .map_err(|_| internal_err!("Timeout during table request"))?
+        .map_err(|e| table_err(self.table_name.clone(), e))?;
+
+        match reply.v {
+            TableReply::Rows(rows) => Ok(rows),
+            TableReply::Error(e) => Err(table_err(self.table_name.clone(), e)),
+            TableReply::Ack | TableReply::RowsAffected(_) => {
+                internal!("Expected rows in reply to a table lookup")
+            }
+        }
+    }
+
+    /// Insert multiple rows of data into this base table, failing with
+    /// [`ReadySetError::DuplicateKey`] without inserting any of them if any row has the same
+    /// primary key as a row that already exists in the table, or as another of the rows.
+    ///
+    /// Note that if the table is sharded, rows inserted into shards other than the one with the
+    /// duplicate key may still be inserted.
+    pub async fn insert_many_unique<I, V>(&mut self, rows: I) -> ReadySetResult<()>
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<Vec<DfValue>>,
+    {
+        self.quick_n_dirty_with_timeout(TableRequest::TableOperations(
+            rows.into_iter()
+                .map(|row| TableOperation::InsertUnique(row.into()))
+                .collect::<Vec<_>>(),
+        ))
+        .await
+        .map(|_| ())
     }
 
     /// Delete all rows from this base table
@@ -951,12 +1088,14 @@ impl Table {
             TableOperation::Truncate,
         ]))
         .await
+        .map(|_| ())
     }
 
     /// Updates the timestamp of the base table in the data flow graph.
     pub async fn update_timestamp(&mut self, t: consistency::Timestamp) -> ReadySetResult<()> {
         self.quick_n_dirty_with_timeout(TableRequest::Timestamp(t))
             .await
+            .map(|_| ())
     }
 
     /// Set the replication offset for this table to the given value.
@@ -975,6 +1114,7 @@ impl Table {
             TableOperation::SetReplicationOffset(offset),
         ]))
         .await
+        .map(|_| ())
     }
 
     /// Enable or disable snapshot mode for this table. In snapshot mode compactions are disabled
@@ -984,5 +1124,6 @@ impl Table {
             TableOperation::SetSnapshotMode(snapshot),
         ]))
         .await
+        .map(|_| ())
     }
 }
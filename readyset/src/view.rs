@@ -1,7 +1,6 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::convert::TryFrom;
-use std::fmt;
 use std::future::Future;
 use std::hash::{Hash, Hasher};
 use std::net::SocketAddr;
@@ -9,13 +8,15 @@ use std::ops::{Bound, Range, RangeBounds};
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 use std::time::Duration;
+use std::{fmt, mem};
 
 use array2::Array2;
 use async_bincode::{AsyncBincodeStream, AsyncDestination};
+use chrono::{DateTime, Utc};
 use dataflow_expression::{Dialect, Expr as DfExpr};
-use futures_util::future::TryFutureExt;
+use futures_util::future::{FutureExt, TryFutureExt};
 use futures_util::stream::futures_unordered::FuturesUnordered;
-use futures_util::stream::{StreamExt, TryStreamExt};
+use futures_util::stream::StreamExt;
 use futures_util::{future, ready};
 use launchpad::intervals::{cmp_start_end, BoundPair};
 use launchpad::redacted::Sensitive;
@@ -41,7 +42,7 @@ use tower::buffer::Buffer;
 use tower::limit::concurrency::ConcurrencyLimit;
 use tower::timeout::Timeout;
 use tower_service::Service;
-use tracing::{error, instrument};
+use tracing::{error, instrument, warn};
 use tracing_futures::Instrument;
 use vec1::Vec1;
 
@@ -49,6 +50,7 @@ pub(crate) mod results;
 
 use self::results::{ResultIterator, Results};
 use crate::consistency::Timestamp;
+use crate::internal::IndexType;
 use crate::{ReaderAddress, Tagged, Tagger};
 
 type Transport = AsyncBincodeStream<
@@ -104,6 +106,24 @@ impl ViewCreateRequest {
     }
 }
 
+/// Bookkeeping information about a view, recorded by the controller so that operators can tell
+/// which caches are no longer being used
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewMetadata {
+    /// The time at which the view was created
+    pub created_at: DateTime<Utc>,
+
+    /// The name of the user who created the view, if known
+    pub created_by: Option<String>,
+
+    /// The last time a client read from the view or looked it up from the controller, if it has
+    /// been accessed since the workers hosting its readers started (for reads) or the current
+    /// controller became the leader (for lookups).
+    ///
+    /// Read times are recorded with millisecond precision.
+    pub last_accessed_at: Option<DateTime<Utc>>,
+}
+
 /// Representation of how a key column in a view maps back to a placeholder in the original query
 #[derive(Hash, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ViewPlaceholder {
@@ -151,8 +171,13 @@ pub struct ColumnBase {
     pub column: SqlIdentifier,
     /// The name of the base table for this column
     pub table: Relation,
-    /// A list of constraints on the column
+    /// A list of constraints on the column, including the table's primary key if the column is
+    /// part of it
     pub constraints: Vec<ColumnConstraint>,
+    /// Whether the column can be NULL in the view even if the base column can't, because it comes
+    /// from the nullable side of an outer join
+    #[serde(default)]
+    pub outer_join_nullable: bool,
 }
 
 /// Combines the specification for a columns with its base name
@@ -174,6 +199,11 @@ impl ColumnSchema {
         table: Relation,
         dialect: Dialect,
     ) -> ReadySetResult<Self> {
+        let column_type = DfType::from_column_spec(
+            &spec,
+            dialect,
+            |_| None, /* Custom types not allowed for inserts via the adapter */
+        )?;
         Ok(Self {
             base: Some(ColumnBase {
                 column: spec.column.name.clone(),
@@ -181,11 +211,19 @@ impl ColumnSchema {
                 constraints: spec.constraints,
             }),
             column: spec.column,
-            column_type: DfType::from_sql_type(
-                &spec.sql_type,
-                dialect,
-                |_| None, /* Custom types not allowed for inserts via the adapter */
-            )?,
+            column_type,
+        })
+    }
+
+    /// Returns `true` if this column can never contain NULL, because its base column is declared
+    /// `NOT NULL` or is part of the primary key, and it doesn't come from the nullable side of an
+    /// outer join
+    pub fn is_not_null(&self) -> bool {
+        self.base.iter().any(|b| {
+            !b.outer_join_nullable
+                && b.constraints
+                    .iter()
+                    .any(|c| matches!(c, ColumnConstraint::NotNull | ColumnConstraint::PrimaryKey))
         })
     }
 }
@@ -846,6 +884,12 @@ impl<D> LookupResult<D> {
 pub struct ReadReplyStats {
     /// The count of cache misses which have occurred
     pub cache_misses: u64,
+    /// The number of rows read from the reader's state to answer the query, before any
+    /// post-lookup filtering, aggregation, or limits were applied
+    pub rows_examined: u64,
+    /// The number of shards of the view that couldn't be read from. If this is non-zero, the
+    /// results are incomplete.
+    pub unavailable_shards: u64,
 }
 
 impl ReadReplyStats {
@@ -854,6 +898,8 @@ impl ReadReplyStats {
     pub fn merge(&self, other: &Self) -> Self {
         Self {
             cache_misses: self.cache_misses + other.cache_misses,
+            rows_examined: self.rows_examined + other.rows_examined,
+            unavailable_shards: self.unavailable_shards + other.unavailable_shards,
         }
     }
 }
@@ -896,6 +942,11 @@ pub struct ViewBuilder {
     /// entry for each key column at the reader.
     pub key_mapping: Vec<(ViewPlaceholder, KeyColumnIdx)>,
 
+    /// The type of the index on the key columns at the reader, or `None` if the reader isn't
+    /// materialized.
+    #[serde(default)]
+    pub index_type: Option<IndexType>,
+
     /// The amount of time before a view request RPC is terminated.
     pub view_request_timeout: Duration,
 }
@@ -926,6 +977,7 @@ impl ViewBuilder {
         let columns = self.columns.clone();
         let schema = self.schema.clone();
         let key_mapping = self.key_mapping.clone();
+        let index_type = self.index_type;
 
         let mut addrs = Vec::with_capacity(shards.len());
         let mut conns = Vec::with_capacity(shards.len());
@@ -976,9 +1028,12 @@ impl ViewBuilder {
             schema,
             columns,
             key_mapping,
+            index_type,
             shard_addrs: addrs,
             shards: Vec1::try_from_vec(conns)
                 .map_err(|_| internal_err!("cannot create view '{}' without shards", self.name))?,
+            allow_partial_results: false,
+            unavailable_shards: Vec::new(),
         })
     }
 }
@@ -996,9 +1051,18 @@ pub struct View {
     /// (view_placeholder, key_column_index) pairs according to their mapping. Contains exactly
     /// one entry for each key column at the reader.
     key_mapping: Vec<(ViewPlaceholder, KeyColumnIdx)>,
+    /// The type of the index on the key columns at the reader, if it's materialized
+    index_type: Option<IndexType>,
 
     shards: Vec1<ViewRpc>,
     shard_addrs: Vec<SocketAddr>,
+
+    /// If true, reads against a sharded view return the rows from the shards that could be read
+    /// from rather than failing if some of the shards are unavailable
+    allow_partial_results: bool,
+    /// The shards that failed to become ready the last time this view was polled for readiness.
+    /// Only tracked if `allow_partial_results` is set.
+    unavailable_shards: Vec<usize>,
 }
 
 impl fmt::Debug for View {
@@ -1073,11 +1137,20 @@ impl Service<ViewQuery> for View {
     type Future = impl Future<Output = Result<Self::Response, Self::Error>> + Send;
 
     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        for s in &mut self.shards {
+        let allow_partial_results = self.allow_partial_results && self.shards.len() > 1;
+        self.unavailable_shards.clear();
+        for (shardi, s) in self.shards.iter_mut().enumerate() {
             let ni = self.node;
-            ready!(s.poll_ready(cx))
+            match ready!(s.poll_ready(cx))
                 .map_err(rpc_err!("<View as Service<ViewQuery>>::poll_ready"))
-                .map_err(|e| view_err(ni, e))?
+                .map_err(|e| view_err(ni, e))
+            {
+                Err(error) if allow_partial_results => {
+                    warn!(%error, shard = shardi, "View shard is unavailable");
+                    self.unavailable_shards.push(shardi);
+                }
+                res => res?,
+            }
         }
         Poll::Ready(Ok(()))
     }
@@ -1141,6 +1214,14 @@ impl Service<ViewQuery> for View {
             }
         }
 
+        // Shards that we'd need to read from, but which aren't available
+        let unavailable_shards = mem::take(&mut self.unavailable_shards);
+        let skipped_shards = unavailable_shards
+            .iter()
+            .filter(|&&shardi| shard_queries.get(shardi).map_or(false, |q| !q.is_empty()))
+            .count() as u64;
+        let allow_partial_results = self.allow_partial_results;
+
         let node = self.node;
         let name = self.name.clone();
         future::Either::Right(
@@ -1149,7 +1230,7 @@ impl Service<ViewQuery> for View {
                 .enumerate()
                 .zip(shard_queries.into_iter())
                 .filter_map(|((shardi, shard), shard_queries)| {
-                    if shard_queries.is_empty() {
+                    if shard_queries.is_empty() || unavailable_shards.contains(&shardi) {
                         // poll_ready reserves a sender slot which we have to release
                         // we do that by dropping the old handle and replacing it with a clone
                         // https://github.com/tokio-rs/tokio/issues/898
@@ -1198,32 +1279,70 @@ impl Service<ViewQuery> for View {
                         .map_err(move |e| view_err(ni, e))
                 })
                 .collect::<FuturesUnordered<_>>()
-                .try_collect::<Vec<LookupResult<ReadReplyBatch>>>()
-                .map_ok(move |e| {
-                    // Flatten this to a single LookupResult<Results>.
-                    e.into_iter().fold(
-                        LookupResult::Results(Vec::new(), ReadReplyStats::default()),
-                        |mut acc, x| {
-                            if let LookupResult::Results(d, _) = &mut acc {
-                                match x {
-                                    LookupResult::NonBlockingMiss => {
-                                        return LookupResult::NonBlockingMiss;
-                                    }
-                                    LookupResult::Results(u, stats) => {
-                                        d.extend(u.into_iter().map(|rows| {
-                                            Results::with_stats(rows.into(), stats.clone())
-                                        }));
-                                    }
-                                }
-                            }
-                            acc
-                        },
-                    )
+                .collect::<Vec<ReadySetResult<LookupResult<ReadReplyBatch>>>>()
+                .map(move |replies| {
+                    merge_shard_replies(replies, skipped_shards, allow_partial_results)
                 }),
         )
     }
 }
 
+/// Flatten the replies from each of the shards of a sharded view into a single
+/// [`LookupResult`].
+///
+/// Normally, if any shard fails to reply the whole read fails. If `allow_partial_results` is set,
+/// shards that fail to reply, along with the `skipped_shards` that weren't read from at all, are
+/// instead counted in the [`ReadReplyStats::unavailable_shards`] of the results, as long as at
+/// least one shard replied.
+fn merge_shard_replies(
+    replies: Vec<ReadySetResult<LookupResult<ReadReplyBatch>>>,
+    skipped_shards: u64,
+    allow_partial_results: bool,
+) -> ReadySetResult<LookupResult<Results>> {
+    let mut results = Vec::new();
+    let mut unavailable_shards = skipped_shards;
+    let mut first_error = None;
+    let mut non_blocking_miss = false;
+    for reply in replies {
+        match reply {
+            Ok(LookupResult::NonBlockingMiss) => non_blocking_miss = true,
+            Ok(LookupResult::Results(batches, stats)) => {
+                results.extend(
+                    batches
+                        .into_iter()
+                        .map(|rows| Results::with_stats(rows.into(), stats.clone())),
+                );
+            }
+            Err(error) if allow_partial_results => {
+                warn!(%error, "Failed to read from view shard");
+                unavailable_shards += 1;
+                first_error.get_or_insert(error);
+            }
+            Err(error) => return Err(error),
+        }
+    }
+
+    if non_blocking_miss {
+        return Ok(LookupResult::NonBlockingMiss);
+    }
+
+    if unavailable_shards > 0 {
+        if results.is_empty() {
+            // None of the shards could be read from, so we don't have any results to return
+            return Err(first_error.unwrap_or(ReadySetError::ViewNotYetAvailable));
+        }
+        results.push(Results::with_stats(
+            Vec::new(),
+            ReadReplyStats {
+                unavailable_shards,
+                ..Default::default()
+            },
+        ));
+    }
+
+    Ok(LookupResult::Results(results, ReadReplyStats::default()))
+}
+
 #[allow(clippy::len_without_is_empty)]
 impl View {
     /// Get the list of columns in this view.
@@ -1236,6 +1355,15 @@ impl View {
         Arc::clone(&self.columns)
     }
 
+    /// Allow reads against this view to return the rows from the shards that are available, rather
+    /// than failing, if some of the view's shards are unavailable. Partial results are indicated by
+    /// a non-zero [`ReadReplyStats::unavailable_shards`] in the stats of the returned results.
+    ///
+    /// Has no effect on views with only one shard.
+    pub fn set_allow_partial_results(&mut self, allow_partial_results: bool) {
+        self.allow_partial_results = allow_partial_results;
+    }
+
     /// Get the schema definition of this view.
     pub fn schema(&self) -> Option<&ViewSchema> {
         self.schema.as_ref()
@@ -1311,6 +1439,12 @@ impl View {
         &self.key_mapping
     }
 
+    /// Get the type of the index on the key columns at the reader node, or `None` if the reader
+    /// isn't materialized
+    pub fn index_type(&self) -> Option<IndexType> {
+        self.index_type
+    }
+
     /// Get the current keys of this view. For debugging only.
     #[instrument(level = "info", skip(self))]
     pub async fn keys(&mut self) -> ReadySetResult<Vec<Vec<DfValue>>> {
@@ -1529,4 +1663,73 @@ mod tests {
 
         eq_laws!(KeyComparison);
     }
+
+    mod merge_shard_replies {
+        use super::*;
+
+        fn replies() -> Vec<ReadySetResult<LookupResult<ReadReplyBatch>>> {
+            vec![
+                Ok(LookupResult::Results(
+                    vec![vec![vec![DfValue::from(1)], vec![DfValue::from(2)]].into()],
+                    ReadReplyStats {
+                        rows_examined: 2,
+                        ..Default::default()
+                    },
+                )),
+                Err(ReadySetError::ViewNotYetAvailable),
+            ]
+        }
+
+        fn unavailable_shards(results: &[Results]) -> u64 {
+            results
+                .iter()
+                .filter_map(|r| r.stats.as_ref())
+                .map(|stats| stats.unavailable_shards)
+                .sum()
+        }
+
+        #[test]
+        fn failed_shard_errors_by_default() {
+            merge_shard_replies(replies(), 0, false).unwrap_err();
+        }
+
+        #[test]
+        fn partial_results() {
+            let res = merge_shard_replies(replies(), 1, true).unwrap();
+            let results = match res {
+                LookupResult::Results(results, _) => results,
+                LookupResult::NonBlockingMiss => panic!("Expected results"),
+            };
+            assert_eq!(unavailable_shards(&results), 2);
+            let rows = results
+                .into_iter()
+                .flat_map(Results::into_data)
+                .collect::<Vec<_>>();
+            assert_eq!(rows, vec![vec![DfValue::from(1)], vec![DfValue::from(2)]]);
+        }
+
+        #[test]
+        fn all_shards_unavailable() {
+            merge_shard_replies(vec![Err(ReadySetError::ViewNotYetAvailable)], 0, true)
+                .unwrap_err();
+            merge_shard_replies(vec![], 2, true).unwrap_err();
+        }
+
+        #[test]
+        fn all_shards_available() {
+            let res = merge_shard_replies(
+                vec![Ok(LookupResult::Results(
+                    vec![vec![vec![DfValue::from(1)]].into()],
+                    ReadReplyStats::default(),
+                ))],
+                0,
+                true,
+            )
+            .unwrap();
+            match res {
+                LookupResult::Results(results, _) => assert_eq!(unavailable_shards(&results), 0),
+                LookupResult::NonBlockingMiss => panic!("Expected results"),
+            }
+        }
+    }
 }
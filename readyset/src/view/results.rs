@@ -1,7 +1,8 @@
 use std::cmp::Ordering;
+use std::mem;
 use std::sync::Arc;
 
-use dataflow_expression::{Expr, PostLookup, PostLookupAggregates};
+use dataflow_expression::{ConstantColumn, Expr, PostLookup, PostLookupAggregates};
 use launchpad::nonmaxusize::NonMaxUsize;
 use nom_sql::OrderType;
 use readyset_data::DfValue;
@@ -71,6 +72,12 @@ pub struct ResultIterator {
     filter: Option<Expr>,
     /// How many columns to return
     cols: usize,
+    /// Columns with the same value in every row, which are inserted into each row as it's
+    /// returned rather than being stored in the reader
+    constant_columns: Vec<ConstantColumn>,
+    /// The current row, with the values of the `constant_columns` inserted. Only used if there are
+    /// any `constant_columns`.
+    row_with_constants: Vec<DfValue>,
 }
 
 /// A ['StreamingIterator`] over rows of a noria select response
@@ -169,6 +176,7 @@ impl ResultIterator {
             returned_cols,
             aggregates,
             default_row,
+            constant_columns,
             ..
         } = post_lookup;
 
@@ -254,6 +262,8 @@ impl ResultIterator {
                     non_empty: false,
                     filter: None,
                     cols: usize::MAX,
+                    constant_columns: vec![],
+                    row_with_constants: vec![],
                 };
 
                 let mut results = temp_iter.into_vec();
@@ -268,10 +278,12 @@ impl ResultIterator {
                     results.drain(offset..);
                 }
 
-                return ResultIterator::owned(vec![Results {
+                let mut iter = ResultIterator::owned(vec![Results {
                     results,
                     stats: None,
                 }]);
+                iter.constant_columns = constant_columns.clone();
+                return iter;
             }
         };
 
@@ -288,6 +300,8 @@ impl ResultIterator {
                 .as_ref()
                 .map(|r| r.len())
                 .unwrap_or(usize::MAX),
+            constant_columns: constant_columns.clone(),
+            row_with_constants: vec![],
         }
     }
 
@@ -305,6 +319,8 @@ impl ResultIterator {
             non_empty: false,
             filter: None,
             cols: usize::MAX,
+            constant_columns: vec![],
+            row_with_constants: vec![],
         }
     }
 
@@ -314,12 +330,10 @@ impl ResultIterator {
             ResultIteratorInner::OwnedResults(OwnedResultIterator { data, .. }) => data
                 .iter()
                 .map(|r| &r.stats)
-                .fold(None, |total, cur| match cur {
-                    Some(stats) => Some(ReadReplyStats {
-                        cache_misses: stats.cache_misses
-                            + total.map(|s| s.cache_misses).unwrap_or(0),
-                    }),
-                    None => total,
+                .fold(None, |total, cur| match (total, cur) {
+                    (Some(total), Some(stats)) => Some(total.merge(stats)),
+                    (None, Some(stats)) => Some(stats.clone()),
+                    (total, None) => total,
                 }),
             _ => None,
         }
@@ -340,6 +354,47 @@ impl ResultIterator {
             break;
         }
     }
+
+    /// Returns the current row, limited to the returned columns but without any constant columns
+    fn current_row(&self) -> Option<&[DfValue]> {
+        if self.limit == Some(usize::MAX) {
+            // limit exists, and wraped around, so we are done here
+            None
+        } else {
+            self.inner
+                .get()
+                .or_else(|| self.default_row.as_ref().map(|r| &r[..]))
+                .map(|row| {
+                    // Why is there no slice truncate?
+                    if row.len() <= self.cols {
+                        row
+                    } else {
+                        &row[..self.cols]
+                    }
+                })
+        }
+    }
+
+    /// Fill `row_with_constants` with the current row, with the values of all the
+    /// `constant_columns` inserted at their indices
+    fn insert_constant_columns(&mut self) {
+        let mut out = mem::take(&mut self.row_with_constants);
+        out.clear();
+        if let Some(row) = self.current_row() {
+            let mut row = row.iter();
+            for constant in &self.constant_columns {
+                while out.len() < constant.index {
+                    match row.next() {
+                        Some(val) => out.push(val.clone()),
+                        None => break,
+                    }
+                }
+                out.push(constant.value.clone());
+            }
+            out.extend(row.cloned());
+        }
+        self.row_with_constants = out;
+    }
 }
 
 impl StreamingIterator for OwnedResultIterator {
@@ -579,25 +634,19 @@ impl StreamingIterator for ResultIterator {
         } else {
             self.non_empty = true;
         }
+
+        if !self.constant_columns.is_empty() {
+            self.insert_constant_columns();
+        }
     }
 
     #[inline(always)]
     fn get(&self) -> Option<&Self::Item> {
-        if self.limit == Some(usize::MAX) {
-            // limit exists, and wraped around, so we are done here
-            None
+        let row = self.current_row()?;
+        if self.constant_columns.is_empty() {
+            Some(row)
         } else {
-            self.inner
-                .get()
-                .or_else(|| self.default_row.as_ref().map(|r| &r[..]))
-                .map(|row| {
-                    // Why is there no slice truncate?
-                    if row.len() <= self.cols {
-                        row
-                    } else {
-                        &row[..self.cols]
-                    }
-                })
+            Some(&self.row_with_constants)
         }
     }
 }
@@ -2,8 +2,8 @@
 //! Contains helpers for determining the schemas and tables of a database for use in replication
 use std::collections::HashMap;
 
-use nom_sql::Dialect;
-use readyset::ReadySetError;
+use nom_sql::{CreateTableStatement, Dialect, Relation};
+use readyset::{ReadySetError, ReadySetResult};
 use readyset_sql_passes::anonymize::{Anonymize, Anonymizer};
 use readyset_telemetry_reporter::{TelemetryBuilder, TelemetryEvent, TelemetrySender};
 
@@ -179,9 +179,55 @@ pub fn error_is_slot_not_found(err: &ReadySetError, slot_name: &str) -> bool {
         .ends_with(&format!("replication slot \"{slot_name}\" does not exist"))
 }
 
+/// The base tables whose schema in ReadySet no longer matches the upstream, each mapped to the
+/// [`ReadySetError::UpstreamSchemaMismatch`] describing how they differ
+pub type SchemaMismatches = HashMap<Relation, ReadySetError>;
+
+/// Checks that the schema ReadySet has for a base table matches the definition of the same table
+/// in the upstream database, comparing the names and types of each column in order.
+///
+/// Returns [`ReadySetError::UpstreamSchemaMismatch`] listing every difference found if the two
+/// schemas don't match.
+pub fn check_schema_matches_upstream(
+    readyset: &CreateTableStatement,
+    upstream: &CreateTableStatement,
+) -> ReadySetResult<()> {
+    let num_columns = readyset.fields.len().max(upstream.fields.len());
+    let mismatches = (0..num_columns)
+        .filter_map(|i| match (readyset.fields.get(i), upstream.fields.get(i)) {
+            (Some(ours), Some(theirs)) if ours.column.name != theirs.column.name => Some(format!(
+                "column {i} is named `{}` in ReadySet but `{}` upstream",
+                ours.column.name, theirs.column.name
+            )),
+            (Some(ours), Some(theirs)) if ours.sql_type != theirs.sql_type => Some(format!(
+                "column `{}` has type {} in ReadySet but {} upstream",
+                ours.column.name, ours.sql_type, theirs.sql_type
+            )),
+            (Some(_), Some(_)) | (None, None) => None,
+            (Some(ours), None) => Some(format!(
+                "column `{}` does not exist upstream",
+                ours.column.name
+            )),
+            (None, Some(theirs)) => Some(format!(
+                "column `{}` does not exist in ReadySet",
+                theirs.column.name
+            )),
+        })
+        .collect::<Vec<_>>();
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(ReadySetError::UpstreamSchemaMismatch {
+            table: readyset.table.to_string(),
+            mismatches,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use nom_sql::SqlIdentifier;
+    use nom_sql::{parse_create_table, SqlIdentifier};
 
     use super::*;
 
@@ -291,4 +337,41 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn matching_schemas_pass_validation() {
+        let readyset =
+            parse_create_table(Dialect::MySQL, "CREATE TABLE t (id int, name text)").unwrap();
+        let upstream =
+            parse_create_table(Dialect::MySQL, "CREATE TABLE t (id int, name text)").unwrap();
+        check_schema_matches_upstream(&readyset, &upstream).unwrap();
+    }
+
+    #[test]
+    fn mismatched_schemas_fail_validation() {
+        let readyset = parse_create_table(
+            Dialect::MySQL,
+            "CREATE TABLE t (id int, name text, removed int)",
+        )
+        .unwrap();
+        let upstream =
+            parse_create_table(Dialect::MySQL, "CREATE TABLE t (id bigint, title text)").unwrap();
+
+        let err = check_schema_matches_upstream(&readyset, &upstream).unwrap_err();
+        match &err {
+            ReadySetError::UpstreamSchemaMismatch { table, mismatches } => {
+                assert_eq!(table, "`t`");
+                assert_eq!(
+                    mismatches,
+                    &[
+                        "column `id` has type INT in ReadySet but BIGINT upstream",
+                        "column 1 is named `name` in ReadySet but `title` upstream",
+                        "column `removed` does not exist upstream",
+                    ]
+                );
+            }
+            _ => panic!("Unexpected error: {err}"),
+        }
+        assert!(err.to_string().contains("does not match upstream"));
+    }
 }
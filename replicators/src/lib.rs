@@ -9,6 +9,7 @@ pub mod db_util;
 pub(crate) mod mysql_connector;
 pub(crate) mod noria_adapter;
 pub(crate) mod postgres_connector;
+pub(crate) mod resnapshot_requests;
 pub(crate) mod table_filter;
 
 use std::time::Duration;
@@ -16,6 +17,7 @@ use std::time::Duration;
 pub use mysql_connector::BinlogPosition;
 pub use noria_adapter::NoriaAdapter;
 pub use postgres_connector::PostgresPosition;
+pub use resnapshot_requests::ResnapshotRequests;
 
 /// Provide a simplistic human-readable estimate for how much time remains to complete an operation
 pub(crate) fn estimate_remaining_time(elapsed: Duration, progress: f64, total: f64) -> String {
@@ -15,7 +15,7 @@ use readyset::metrics::recorded;
 use readyset::recipe::ChangeList;
 use readyset::replication::ReplicationOffset;
 use readyset::{ReadySetError, ReadySetResult};
-use readyset_data::{DfValue, Dialect};
+use readyset_data::{DfType, DfValue, Dialect};
 use tracing::warn;
 
 use super::BinlogPosition;
@@ -531,9 +531,17 @@ fn binlog_val_to_noria_val(
     let buf = match val {
         mysql_common::value::Value::Bytes(b) => b,
         _ => {
-            return Ok(val
+            let val: DfValue = val
                 .try_into()
-                .map_err(|e| format!("Unable to coerce value {}", e))?)
+                .map_err(|e| format!("Unable to coerce value {}", e))?;
+            return match col_kind {
+                // DATE values are sent as a DATETIME at midnight, so mark them as date-only to
+                // avoid displaying a spurious time component
+                ColumnType::MYSQL_TYPE_DATE | ColumnType::MYSQL_TYPE_NEWDATE => Ok(val
+                    .coerce_to(&DfType::Date, &DfType::Unknown)
+                    .map_err(|e| format!("Unable to coerce value {}", e))?),
+                _ => Ok(val),
+            };
         }
     };
 
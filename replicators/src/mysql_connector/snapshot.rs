@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::convert::{TryFrom, TryInto};
 use std::error::Error;
 use std::fmt::{self, Display};
@@ -9,27 +10,31 @@ use futures::stream::FuturesUnordered;
 use futures::StreamExt;
 use itertools::Itertools;
 use metrics::register_gauge;
+use mysql::consts::ColumnType;
 use mysql::prelude::Queryable;
 use mysql::{Transaction, TxOpts};
 use mysql_async as mysql;
 use nom_sql::Relation;
 use readyset::metrics::recorded;
-use readyset::recipe::changelist::ChangeList;
+use readyset::recipe::changelist::{Change, ChangeList};
 use readyset::replication::{ReplicationOffset, ReplicationOffsets};
-use readyset::ReadySetResult;
-use readyset_data::Dialect;
+use readyset::{ReadySetError, ReadySetResult};
+use readyset_data::{DfType, Dialect};
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, info_span, warn};
 use tracing_futures::Instrument;
 
 use super::BinlogPosition;
-use crate::db_util::DatabaseSchemas;
+use crate::db_util::{check_schema_matches_upstream, DatabaseSchemas, SchemaMismatches};
 use crate::table_filter::TableFilter;
 
 const BATCH_SIZE: usize = 1000; // How many queries to buffer before pushing to ReadySet
 
 const MAX_SNAPSHOT_BATCH: usize = 8; // How many tables to snapshot at the same time
 
+/// The MySQL error code returned when a table does not exist
+const ER_NO_SUCH_TABLE: u16 = 1146;
+
 /// A list of databases MySQL uses internally, they should not be replicated
 pub const MYSQL_INTERNAL_DBS: &[&str] =
     &["mysql", "information_schema", "performance_schema", "sys"];
@@ -154,13 +159,31 @@ impl MySqlReplicator {
         // >> until the transaction ends. This principle applies not only to transactional tables,
         // >> but also to nontransactional tables.
         let all_tables = get_table_list(&mut tx, TableKind::BaseTable).await?;
-        let all_tables = all_tables
-            .into_iter()
-            .filter(|(schema, table)| {
+        let (all_tables, non_replicated_tables): (Vec<_>, Vec<_>) =
+            all_tables.into_iter().partition(|(schema, table)| {
                 self.table_filter
                     .should_be_processed(schema.as_str(), table.as_str())
-            })
-            .collect::<Vec<_>>();
+            });
+
+        // Tell ReadySet about the tables we're not replicating, so it can tell users why it can't
+        // cache queries that reference them
+        if !non_replicated_tables.is_empty() {
+            let changelist = ChangeList::from_changes(
+                non_replicated_tables
+                    .into_iter()
+                    .map(|(schema, name)| {
+                        Change::AddNonReplicatedRelation(Relation {
+                            schema: Some(schema.into()),
+                            name: name.into(),
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+                Dialect::DEFAULT_MYSQL,
+            );
+            if let Err(error) = noria.extend_recipe_no_leader_ready(changelist).await {
+                warn!(%error, "Error recording tables which are not being replicated");
+            }
+        }
 
         let all_tables_formatted = all_tables
             .iter()
@@ -404,14 +427,22 @@ impl MySqlReplicator {
     ///   the schema
     /// * `extend_recipe`: Replicate and install the recipe (`CREATE TABLE` ...; `CREATE VIEW` ...;)
     ///   in addition to the rows
+    /// * `resnapshot_tables`: Tables to snapshot again even if they already have a replication
+    ///   offset, replacing their current contents
     pub(crate) async fn snapshot_to_noria(
         mut self,
         noria: &mut readyset::ReadySetHandle,
         db_schemas: &mut DatabaseSchemas,
+        resnapshot_tables: &HashSet<Relation>,
         snapshot_report_interval_secs: u16,
     ) -> ReadySetResult<()> {
         let result = self
-            .replicate_to_noria_with_table_locks(noria, db_schemas, snapshot_report_interval_secs)
+            .replicate_to_noria_with_table_locks(
+                noria,
+                db_schemas,
+                resnapshot_tables,
+                snapshot_report_interval_secs,
+            )
             .await;
 
         // Wait for all connections to finish, not strictly necessary
@@ -419,6 +450,77 @@ impl MySqlReplicator {
         result
     }
 
+    /// Compare the schema of every base table ReadySet replicates with the definition of that
+    /// table in the upstream database, before we resume replicating from the binlog.
+    ///
+    /// Returns the tables that don't match, along with the current position of the binlog. The
+    /// differences may be explained by DDL we haven't replicated yet, which is only known once
+    /// the binlog has been replayed up to that position.
+    pub(crate) async fn table_schema_mismatches(
+        self,
+        noria: &mut readyset::ReadySetHandle,
+    ) -> ReadySetResult<(SchemaMismatches, BinlogPosition)> {
+        let result = self.table_schema_mismatches_inner(noria).await;
+
+        self.pool.disconnect().await?;
+        result
+    }
+
+    async fn table_schema_mismatches_inner(
+        &self,
+        noria: &mut readyset::ReadySetHandle,
+    ) -> ReadySetResult<(SchemaMismatches, BinlogPosition)> {
+        let mut conn = self.pool.get_conn().await?;
+        let mut mismatches = SchemaMismatches::new();
+
+        for table in noria.tables().await?.into_keys() {
+            let db = match &table.schema {
+                Some(db) if self.table_filter.should_be_processed(db, &table.name) => db.clone(),
+                _ => continue,
+            };
+
+            let readyset_schema = match noria.table(table.clone()).await?.schema() {
+                Some(schema) => schema.clone(),
+                None => continue,
+            };
+
+            let create_table =
+                match create_for_table(&mut conn, &db, &table.name, TableKind::BaseTable).await {
+                    Ok(create_table) => create_table,
+                    Err(mysql::Error::Server(err)) if err.code == ER_NO_SUCH_TABLE => {
+                        let error = ReadySetError::UpstreamSchemaMismatch {
+                            table: table.to_string(),
+                            mismatches: vec!["table does not exist upstream".to_owned()],
+                        };
+                        mismatches.insert(table, error);
+                        continue;
+                    }
+                    Err(err) => return Err(err.into()),
+                };
+
+            let upstream_schema = match nom_sql::parse_create_table(
+                nom_sql::Dialect::MySQL,
+                &create_table,
+            ) {
+                Ok(schema) => schema,
+                Err(error) => {
+                    warn!(%table, %error, "Could not parse upstream schema, skipping validation");
+                    continue;
+                }
+            };
+
+            if let Err(error) = check_schema_matches_upstream(&readyset_schema, &upstream_schema) {
+                mismatches.insert(table, error);
+            }
+        }
+
+        // Read the position only once we've seen the schemas, so that any DDL that changed them
+        // is guaranteed to come before it in the binlog
+        let pos = self.get_binlog_position().await?;
+
+        Ok((mismatches, pos))
+    }
+
     /// This is a fallback method to obtaining a database lock, that obtains table level locks
     /// instead of a global lock. The only difference between that and obtaining a global lock
     /// is that some `CREATE TABLE` or `CREATE VIEW` statements may be missed if they happen to
@@ -428,6 +530,7 @@ impl MySqlReplicator {
         &mut self,
         noria: &mut readyset::ReadySetHandle,
         db_schemas: &mut DatabaseSchemas,
+        resnapshot_tables: &HashSet<Relation>,
         snapshot_report_interval_secs: u16,
     ) -> ReadySetResult<()> {
         // NOTE: There are two ways to prevent DDL changes in MySQL:
@@ -467,17 +570,20 @@ impl MySqlReplicator {
             noria,
             table_list,
             &replication_offsets,
+            resnapshot_tables,
             snapshot_report_interval_secs,
         )
         .await
     }
 
     /// Spawns a new tokio task that replicates a given table to noria, returning
-    /// the join handle
+    /// the join handle. If `truncate` is set, any rows already in the table in noria are deleted
+    /// first.
     async fn dumper_task_for_table(
         &mut self,
         noria: &mut readyset::ReadySetHandle,
         table: Relation,
+        truncate: bool,
         snapshot_report_interval_secs: u16,
     ) -> ReadySetResult<JoinHandle<(Relation, ReplicationOffset, ReadySetResult<()>)>> {
         let span = info_span!("replicating table", %table);
@@ -495,7 +601,11 @@ impl MySqlReplicator {
         read_lock.query_drop("UNLOCK TABLES").await?;
         span.in_scope(|| info!("Read lock released"));
 
-        let table_mutator = noria.table(table.clone()).instrument(span.clone()).await?;
+        let mut table_mutator = noria.table(table.clone()).instrument(span.clone()).await?;
+        if truncate {
+            span.in_scope(|| info!("Discarding existing rows"));
+            table_mutator.truncate().instrument(span.clone()).await?;
+        }
 
         Ok(tokio::spawn(async move {
             (
@@ -514,6 +624,7 @@ impl MySqlReplicator {
         noria: &mut readyset::ReadySetHandle,
         mut table_list: Vec<Relation>,
         replication_offsets: &ReplicationOffsets,
+        resnapshot_tables: &HashSet<Relation>,
         snapshot_report_interval_secs: u16,
     ) -> ReadySetResult<()> {
         let mut replication_tasks = FuturesUnordered::new();
@@ -523,12 +634,18 @@ impl MySqlReplicator {
         // We pop front because we add the tables before the views, and the views depend on the
         // tables. TODO: do we need to fully finish tables before views?
         while let Some(table) = table_list.pop() {
-            if replication_offsets.has_table(&table) {
+            let resnapshot = resnapshot_tables.contains(&table);
+            if replication_offsets.has_table(&table) && !resnapshot {
                 info!(%table, "Replication offset already exists for table, skipping snapshot");
             } else {
                 replication_tasks.push(
-                    self.dumper_task_for_table(noria, table, snapshot_report_interval_secs)
-                        .await?,
+                    self.dumper_task_for_table(
+                        noria,
+                        table,
+                        resnapshot,
+                        snapshot_report_interval_secs,
+                    )
+                    .await?,
                 );
             }
 
@@ -564,9 +681,15 @@ impl MySqlReplicator {
                 }
                 (table, _, Err(err)) => {
                     error!(%table, error = %err, "Replication failed, retrying");
+                    let resnapshot = resnapshot_tables.contains(&table);
                     replication_tasks.push(
-                        self.dumper_task_for_table(noria, table, snapshot_report_interval_secs)
-                            .await?,
+                        self.dumper_task_for_table(
+                            noria,
+                            table,
+                            resnapshot,
+                            snapshot_report_interval_secs,
+                        )
+                        .await?,
                     );
                 }
             }
@@ -574,12 +697,18 @@ impl MySqlReplicator {
             // If still have tables to snapshot add them to the task list
             while replication_tasks.len() < MAX_SNAPSHOT_BATCH && !table_list.is_empty() {
                 let table = table_list.pop().expect("Not empty");
-                if replication_offsets.has_table(&table) {
+                let resnapshot = resnapshot_tables.contains(&table);
+                if replication_offsets.has_table(&table) && !resnapshot {
                     info!(%table, "Replication offset already exists for table, skipping snapshot");
                 } else {
                     replication_tasks.push(
-                        self.dumper_task_for_table(noria, table, snapshot_report_interval_secs)
-                            .await?,
+                        self.dumper_task_for_table(
+                            noria,
+                            table,
+                            resnapshot,
+                            snapshot_report_interval_secs,
+                        )
+                        .await?,
                     );
                 }
             }
@@ -631,7 +760,16 @@ fn mysql_row_to_noria_row(row: mysql::Row) -> ReadySetResult<Vec<readyset_data::
     let mut noria_row = Vec::with_capacity(row.len());
     for idx in 0..row.len() {
         let val = value_to_value(row.as_ref(idx).unwrap());
-        noria_row.push(readyset_data::DfValue::try_from(val)?);
+        let val = readyset_data::DfValue::try_from(val)?;
+        // DATE values are sent as a DATETIME at midnight, so mark them as date-only to avoid
+        // displaying a spurious time component
+        let val = match row.columns_ref().get(idx).map(|col| col.column_type()) {
+            Some(ColumnType::MYSQL_TYPE_DATE | ColumnType::MYSQL_TYPE_NEWDATE) => {
+                val.coerce_to(&DfType::Date, &DfType::Unknown)?
+            }
+            _ => val,
+        };
+        noria_row.push(val);
     }
     Ok(noria_row)
 }
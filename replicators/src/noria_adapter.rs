@@ -27,11 +27,12 @@ use tokio::sync::Notify;
 use tracing::{debug, error, info, info_span, trace, warn, Instrument};
 use {mysql_async as mysql, tokio_postgres as pgsql};
 
-use crate::db_util::{CreateSchema, DatabaseSchemas};
+use crate::db_util::{CreateSchema, DatabaseSchemas, SchemaMismatches};
 use crate::mysql_connector::{MySqlBinlogConnector, MySqlReplicator};
 use crate::postgres_connector::{
     PostgresReplicator, PostgresWalConnector, PUBLICATION_NAME, REPLICATION_SLOT,
 };
+use crate::resnapshot_requests::ResnapshotRequests;
 use crate::table_filter::TableFilter;
 
 const WAIT_BEFORE_RESNAPSHOT: Duration = Duration::from_secs(3);
@@ -99,6 +100,12 @@ pub struct NoriaAdapter {
     table_filter: TableFilter,
     /// If the connector can partially resnapshot a database
     supports_resnapshot: bool,
+    /// Tables requested to be re-snapshot while replicating
+    resnapshot_requests: ResnapshotRequests,
+    /// Tables whose schema didn't match the upstream when we resumed replication. DDL for one of
+    /// these tables in the log accounts for the difference; any left once we've caught up to the
+    /// upstream's position at startup fail replication.
+    schema_mismatches: SchemaMismatches,
 }
 
 impl NoriaAdapter {
@@ -108,7 +115,14 @@ impl NoriaAdapter {
         config: UpstreamConfig,
     ) -> ReadySetResult<!> {
         let noria = readyset::ReadySetHandle::new(authority).await;
-        NoriaAdapter::start(noria, config, None, telemetry_sender).await
+        NoriaAdapter::start(
+            noria,
+            config,
+            None,
+            telemetry_sender,
+            ResnapshotRequests::new(),
+        )
+        .await
     }
 
     pub async fn start(
@@ -116,6 +130,7 @@ impl NoriaAdapter {
         mut config: UpstreamConfig,
         mut notify: Option<Arc<Notify>>,
         telemetry_sender: TelemetrySender,
+        resnapshot_requests: ResnapshotRequests,
     ) -> ReadySetResult<!> {
         let mut resnapshot = false;
         let url: DatabaseURL = config
@@ -136,6 +151,7 @@ impl NoriaAdapter {
                     &mut notify,
                     resnapshot,
                     &telemetry_sender,
+                    resnapshot_requests.clone(),
                 )
                 .await
             }
@@ -149,6 +165,7 @@ impl NoriaAdapter {
                     &mut notify,
                     resnapshot,
                     &telemetry_sender,
+                    resnapshot_requests.clone(),
                 )
                 .await
             }
@@ -181,6 +198,7 @@ impl NoriaAdapter {
         ready_notify: &mut Option<Arc<Notify>>,
         resnapshot: bool,
         telemetry_sender: &TelemetrySender,
+        resnapshot_requests: ResnapshotRequests,
     ) -> ReadySetResult<!> {
         use crate::mysql_connector::BinlogPosition;
 
@@ -201,8 +219,13 @@ impl NoriaAdapter {
         )?;
 
         let mut db_schemas = DatabaseSchemas::new();
+        let mut schema_mismatches = SchemaMismatches::new();
+        let mut upstream_pos = None;
 
-        let pos = match (replication_offsets.max_offset()?, resnapshot) {
+        let pos = match (
+            replication_offsets.max_offset()?,
+            resnapshot || resnapshot_requests.has_pending(),
+        ) {
             (None, _) | (_, true) => {
                 let span = info_span!("taking database snapshot");
                 let replicator_options = mysql_options.clone();
@@ -231,10 +254,12 @@ impl NoriaAdapter {
                 );
 
                 span.in_scope(|| info!("Starting snapshot"));
+                let resnapshot_tables = resnapshot_requests.pending();
                 let snapshot_result = replicator
                     .snapshot_to_noria(
                         &mut noria,
                         &mut db_schemas,
+                        &resnapshot_tables,
                         config.snapshot_report_interval_secs,
                     )
                     .instrument(span.clone())
@@ -253,6 +278,7 @@ impl NoriaAdapter {
                 );
 
                 snapshot_result?;
+                resnapshot_requests.complete(&resnapshot_tables);
 
                 // Get updated offests, after potential replication happened
                 replication_offsets = noria.replication_offsets().await?;
@@ -290,7 +316,20 @@ impl NoriaAdapter {
 
                 pos
             }
-            (Some(pos), _) => pos.clone().into(),
+            (Some(pos), _) => {
+                // We're resuming replication from a previous run, so before we start applying
+                // binlog events check whether the tables we have still match the upstream
+                let replicator = MySqlReplicator {
+                    pool: mysql::Pool::new(mysql_options.clone()),
+                    table_filter: table_filter.clone(),
+                };
+                let (mismatches, binlog_pos) =
+                    replicator.table_schema_mismatches(&mut noria).await?;
+                schema_mismatches = mismatches;
+                upstream_pos = Some(ReplicationOffset::try_from(binlog_pos)?);
+
+                pos.clone().into()
+            }
         };
 
         // TODO: it is possible that the binlog position from noria is no longer
@@ -314,6 +353,8 @@ impl NoriaAdapter {
             table_filter,
             supports_resnapshot: true,
             dialect: Dialect::DEFAULT_MYSQL,
+            resnapshot_requests,
+            schema_mismatches,
         };
 
         let mut current_pos: ReplicationOffset = pos.try_into()?;
@@ -325,14 +366,14 @@ impl NoriaAdapter {
         // to the position of the most recent table we have, applying changes as needed.
         // Only once binlog advanced to that point, can we send a ready signal to
         // ReadySet.
-        match adapter.replication_offsets.max_offset()? {
-            Some(max) if max > &current_pos => {
+        match adapter.catch_up_offset(upstream_pos)? {
+            Some(max) if max > current_pos => {
                 info!(start = %current_pos, end = %max, "Catching up");
-                let max = max.clone();
                 adapter.main_loop(&mut current_pos, Some(max)).await?;
             }
             _ => {}
         }
+        adapter.check_schema_mismatches()?;
 
         info!("MySQL connected");
         info!(binlog_position = %current_pos);
@@ -354,6 +395,7 @@ impl NoriaAdapter {
         ready_notify: &mut Option<Arc<Notify>>,
         resnapshot: bool,
         telemetry_sender: &TelemetrySender,
+        resnapshot_requests: ResnapshotRequests,
     ) -> ReadySetResult<!> {
         let dbname = pgsql_opts.get_dbname().ok_or_else(|| {
             ReadySetError::ReplicationFailed("No database specified for replication".to_string())
@@ -398,7 +440,7 @@ impl NoriaAdapter {
 
         let replication_slot = if let Some(slot) = &connector.replication_slot {
             Some(slot.clone())
-        } else if resnapshot || pos.is_none() {
+        } else if resnapshot || resnapshot_requests.has_pending() || pos.is_none() {
             // This is not an initial connection but we need to resnapshot the latest schema,
             // therefore we create a new replication slot, just so we can get a consistent snapshot
             // with a WAL position attached. This is more robust than locking and allows us to reuse
@@ -418,6 +460,8 @@ impl NoriaAdapter {
         };
 
         let mut create_schema = CreateSchema::new(dbname.to_string(), nom_sql::Dialect::PostgreSQL);
+        let mut schema_mismatches = SchemaMismatches::new();
+        let mut upstream_pos = None;
 
         if let Some(replication_slot) = replication_slot {
             let snapshot_start = Instant::now();
@@ -435,8 +479,9 @@ impl NoriaAdapter {
             let mut replicator =
                 PostgresReplicator::new(&mut client, &mut noria, table_filter.clone()).await?;
 
+            let resnapshot_tables = resnapshot_requests.pending();
             select! {
-                snapshot_result = replicator.snapshot_to_noria(&replication_slot, &mut create_schema, snapshot_report_interval_secs).fuse() =>  {
+                snapshot_result = replicator.snapshot_to_noria(&replication_slot, &mut create_schema, &resnapshot_tables, snapshot_report_interval_secs).fuse() =>  {
                     let status = if snapshot_result.is_err() {
                         SnapshotStatusTag::Failed.value()
                     } else {
@@ -453,6 +498,7 @@ impl NoriaAdapter {
                 },
                 c = connection_handle.fuse() => c.unwrap()?,
             }
+            resnapshot_requests.complete(&resnapshot_tables);
 
             info!("Snapshot finished");
             histogram!(
@@ -472,6 +518,23 @@ impl NoriaAdapter {
                     .build(),
             );
             create_schema.send_schemas(telemetry_sender).await;
+        } else {
+            // We're resuming replication from a previous run, so before we start applying WAL
+            // events check whether the tables we have still match the upstream
+            let (mut client, connection) = pgsql_opts.connect(tls_connector).await?;
+            let connection_handle = tokio::spawn(connection);
+
+            let mut replicator =
+                PostgresReplicator::new(&mut client, &mut noria, table_filter.clone()).await?;
+
+            select! {
+                result = replicator.table_schema_mismatches().fuse() => {
+                    let (mismatches, wal_pos) = result?;
+                    schema_mismatches = mismatches;
+                    upstream_pos = Some(ReplicationOffset::from(wal_pos));
+                },
+                c = connection_handle.fuse() => c.unwrap()?,
+            }
         }
 
         connector
@@ -485,10 +548,6 @@ impl NoriaAdapter {
             .min_present_offset()?
             .expect("Minimal offset must be present after snapshot")
             .clone();
-        let max_pos = replication_offsets
-            .max_offset()?
-            .expect("Maximum offset must be present after snapshot")
-            .clone();
 
         let mut adapter = NoriaAdapter {
             noria,
@@ -499,12 +558,18 @@ impl NoriaAdapter {
             table_filter,
             supports_resnapshot: true,
             dialect: Dialect::DEFAULT_POSTGRESQL,
+            resnapshot_requests,
+            schema_mismatches,
         };
 
+        let max_pos = adapter
+            .catch_up_offset(upstream_pos)?
+            .expect("Maximum offset must be present after snapshot");
         if min_pos != max_pos {
             info!(start = %min_pos, end = %max_pos, "Catching up");
             adapter.main_loop(&mut min_pos, Some(max_pos)).await?;
         }
+        adapter.check_schema_mismatches()?;
 
         // Let waiters know that the initial snapshotting is complete.
         if let Some(notify) = ready_notify.take() {
@@ -516,6 +581,37 @@ impl NoriaAdapter {
         unreachable!("`main_loop` will never stop with an Ok status if `until = None`");
     }
 
+    /// Returns the offset replication needs to catch up to before ReadySet is ready: the maximum
+    /// offset of the schema and the tables, or the upstream's position at startup if any tables
+    /// didn't match it, since only the log up to there can account for the differences.
+    fn catch_up_offset(
+        &self,
+        upstream_pos: Option<ReplicationOffset>,
+    ) -> ReadySetResult<Option<ReplicationOffset>> {
+        let max_pos = self.replication_offsets.max_offset()?.cloned();
+        Ok(match upstream_pos {
+            Some(upstream_pos)
+                if !self.schema_mismatches.is_empty()
+                    && max_pos.as_ref().map_or(true, |max| upstream_pos > *max) =>
+            {
+                Some(upstream_pos)
+            }
+            _ => max_pos,
+        })
+    }
+
+    /// Fails with the first schema mismatch left once we've caught up to the upstream's position
+    /// at startup, since none of the DDL in the log accounted for it
+    fn check_schema_mismatches(&mut self) -> ReadySetResult<()> {
+        match self.schema_mismatches.drain().next() {
+            Some((table, err)) => {
+                error!(%table, error = %err, "Table schema does not match the upstream");
+                Err(err)
+            }
+            None => Ok(()),
+        }
+    }
+
     /// Apply a DDL string to noria with the current log position
     async fn handle_ddl_change(
         &mut self,
@@ -525,17 +621,51 @@ impl NoriaAdapter {
     ) -> ReadySetResult<()> {
         let mut changelist = ChangeList::from_changes(changes, self.dialect);
 
+        // Tables created outside the filtered scope are recorded as not being replicated
+        for change in changelist.changes_mut() {
+            if let Change::CreateTable(stmt) = change {
+                if !self
+                    .table_filter
+                    .should_be_processed(schema.as_str(), stmt.table.name.as_str())
+                {
+                    *change = Change::AddNonReplicatedRelation(Relation {
+                        schema: Some(schema.as_str().into()),
+                        name: stmt.table.name.clone(),
+                    });
+                }
+            }
+        }
+
         // Remove DDL changes outside the filtered scope
         changelist.changes_mut().retain(|change| match change {
-            Change::CreateTable(stmt) => self
-                .table_filter
-                .should_be_processed(schema.as_str(), stmt.table.name.as_str()),
             Change::AlterTable(stmt) => self
                 .table_filter
                 .should_be_processed(schema.as_str(), stmt.table.name.as_str()),
             _ => true,
         });
 
+        // Any DDL for a table that didn't match the upstream when we resumed replication accounts
+        // for the difference, either by redefining the table or by resnapshotting it below
+        if !self.schema_mismatches.is_empty() {
+            for change in changelist.changes() {
+                let table = match change {
+                    Change::CreateTable(stmt) => &stmt.table,
+                    Change::AlterTable(stmt) => &stmt.table,
+                    Change::Drop { name, .. } => name,
+                    _ => continue,
+                };
+                self.schema_mismatches.remove(&Relation {
+                    schema: Some(
+                        table
+                            .schema
+                            .clone()
+                            .unwrap_or_else(|| schema.as_str().into()),
+                    ),
+                    name: table.name.clone(),
+                });
+            }
+        }
+
         if self.supports_resnapshot && changelist.changes().any(Change::requires_resnapshot) {
             // In case we detect a DDL change that requires a full schema resnapshot exit the loop
             // with the proper status
@@ -721,12 +851,30 @@ impl NoriaAdapter {
                 return Ok(());
             }
 
-            let (action, pos) = self.connector.next_action(position, until.as_ref()).await?;
+            let (action, pos) = if until.is_some() {
+                self.connector.next_action(position, until.as_ref()).await?
+            } else {
+                select! {
+                    next = self.connector.next_action(position, None) => next?,
+                    _ = self.resnapshot_requests.requested() => {
+                        // Restart replication so that the requested tables get snapshot again.
+                        // Everything we've applied so far is already reflected in the replication
+                        // offsets, so nothing is lost by abandoning the current action.
+                        info!("Table resnapshot requested, restarting replication");
+                        return Err(ReadySetError::ResnapshotNeeded);
+                    }
+                }
+            };
             *position = pos.clone();
             debug!(%position, "Received replication action");
 
             trace!(?action);
 
+            #[cfg(feature = "failure_injection")]
+            while fail::eval(failpoints::PAUSE_REPLICATION, |_| ()).is_some() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+
             if let Err(err) = self.handle_action(action, pos, until.is_some()).await {
                 error!(error = %err, "Aborting replication task on error");
                 counter!(recorded::REPLICATOR_FAILURE, 1u64,);
@@ -405,7 +405,7 @@ impl PostgresWalConnector {
     }
 }
 
-fn parse_wal(wal: &str) -> ReadySetResult<i64> {
+pub(crate) fn parse_wal(wal: &str) -> ReadySetResult<i64> {
     // Internally, an LSN is a 64-bit integer, representing a byte position in the write-ahead log
     // stream. It is printed as two hexadecimal numbers of up to 8 digits each, separated by a
     // slash; for example, 16/B374D848
@@ -431,7 +431,7 @@ impl Connector for PostgresWalConnector {
     async fn next_action(
         &mut self,
         last_pos: &ReplicationOffset,
-        _until: Option<&ReplicationOffset>,
+        until: Option<&ReplicationOffset>,
     ) -> ReadySetResult<(ReplicationAction, ReplicationOffset)> {
         // Calling the ReadySet API is a bit expensive, therefore we try to queue as many
         // actions as possible before calling into the API.
@@ -600,8 +600,18 @@ impl Connector for PostgresWalConnector {
                         ));
                     }
                 }
-                WalEvent::WantsKeepaliveResponse => {
-                    self.send_standy_status_update(last_pos.into())?;
+                WalEvent::WantsKeepaliveResponse | WalEvent::Keepalive => {
+                    if matches!(event, WalEvent::WantsKeepaliveResponse) {
+                        self.send_standy_status_update(last_pos.into())?;
+                    }
+
+                    // Keepalives carry the position the server has sent up to, so if that's past
+                    // `until` there's nothing left to catch up on even if no changes follow
+                    if actions.is_empty()
+                        && until.map_or(false, |until| ReplicationOffset::from(cur_lsn) >= *until)
+                    {
+                        return Ok((ReplicationAction::LogPosition, cur_lsn.into()));
+                    }
                 }
                 WalEvent::Commit => {
                     if !actions.is_empty() {
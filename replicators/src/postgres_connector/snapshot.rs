@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::convert::{TryFrom, TryInto};
 use std::error::Error;
 use std::fmt::{self, Display};
@@ -20,9 +21,9 @@ use readyset_errors::{internal, internal_err, unsupported};
 use tokio_postgres as pgsql;
 use tracing::{debug, error, info, info_span, trace, warn, Instrument};
 
-use super::connector::CreatedSlot;
+use super::connector::{parse_wal, CreatedSlot};
 use super::PostgresPosition;
-use crate::db_util::CreateSchema;
+use crate::db_util::{check_schema_matches_upstream, CreateSchema, SchemaMismatches};
 use crate::table_filter::TableFilter;
 
 const BATCH_SIZE: usize = 1024; // How many queries to buffer before pushing to ReadySet
@@ -558,25 +559,112 @@ impl<'a> PostgresReplicator<'a> {
         })
     }
 
+    /// Compare the schema of every base table ReadySet replicates with the definition of that
+    /// table in the upstream database, before we resume replicating from the WAL.
+    ///
+    /// Returns the tables that don't match, along with the current position of the WAL. The
+    /// differences may be explained by DDL we haven't replicated yet, which is only known once
+    /// the WAL has been replayed up to that position.
+    pub(crate) async fn table_schema_mismatches(
+        &mut self,
+    ) -> ReadySetResult<(SchemaMismatches, PostgresPosition)> {
+        let mut mismatches = SchemaMismatches::new();
+
+        for table in self.noria.tables().await?.into_keys() {
+            let schema = match &table.schema {
+                Some(schema) if self.table_filter.should_be_processed(schema, &table.name) => {
+                    schema.clone()
+                }
+                _ => continue,
+            };
+
+            let readyset_schema = match self.noria.table(table.clone()).await?.schema() {
+                Some(schema) => schema.clone(),
+                None => continue,
+            };
+
+            let oid = self
+                .transaction
+                .query_opt(
+                    "SELECT c.oid FROM pg_catalog.pg_class c
+                     JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                     WHERE n.nspname = $1 AND c.relname = $2 AND c.relkind = 'r'",
+                    &[&schema.as_str(), &table.name.as_str()],
+                )
+                .await?
+                .map(|row| row.try_get::<_, u32>(0))
+                .transpose()?;
+            let oid = match oid {
+                Some(oid) => oid,
+                None => {
+                    let error = ReadySetError::UpstreamSchemaMismatch {
+                        table: table.to_string(),
+                        mismatches: vec!["table does not exist upstream".to_owned()],
+                    };
+                    mismatches.insert(table, error);
+                    continue;
+                }
+            };
+
+            let entry = TableEntry {
+                schema: schema.to_string(),
+                name: table.name.to_string(),
+                oid,
+            };
+            let upstream_schema = match entry
+                .get_table(&self.transaction)
+                .await
+                .and_then(TableDescription::try_into_change)
+            {
+                Ok(Change::CreateTable(schema)) => schema,
+                Ok(_) => internal!("Base tables must convert into CREATE TABLE statements"),
+                Err(error) => {
+                    warn!(%table, %error, "Could not load upstream schema, skipping validation");
+                    continue;
+                }
+            };
+
+            if let Err(error) = check_schema_matches_upstream(&readyset_schema, &upstream_schema) {
+                mismatches.insert(table, error);
+            }
+        }
+
+        // Read the position only once we've seen the schemas, so that any DDL that changed them
+        // is guaranteed to come before it in the WAL
+        let lsn: String = self
+            .transaction
+            .query_one("SELECT pg_current_wal_lsn()::text", &[])
+            .await?
+            .try_get(0)?;
+        let pos = parse_wal(&lsn)?.into();
+
+        Ok((mismatches, pos))
+    }
+
     /// Begin the replication process, starting with the recipe for the database, followed
-    /// by each table's contents
+    /// by each table's contents.
+    ///
+    /// Tables that already have a replication offset are skipped, unless they're in
+    /// `resnapshot_tables`, in which case their current contents are replaced.
     pub(crate) async fn snapshot_to_noria(
         &mut self,
         replication_slot: &CreatedSlot,
         create_schema: &mut CreateSchema,
+        resnapshot_tables: &HashSet<Relation>,
         snapshot_report_interval_secs: u16,
     ) -> ReadySetResult<()> {
         let wal_position = PostgresPosition::from(replication_slot.consistent_point).into();
         self.set_snapshot(&replication_slot.snapshot_name).await?;
 
-        let mut table_list = self.get_table_list(TableKind::RegularTable).await?;
+        let table_list = self.get_table_list(TableKind::RegularTable).await?;
         let view_list = self.get_table_list(TableKind::View).await?;
         let custom_types = self.get_custom_types().await?;
 
-        table_list.retain(|tbl| {
-            self.table_filter
-                .should_be_processed(tbl.schema.as_str(), tbl.name.as_str())
-        });
+        let (table_list, non_replicated_tables): (Vec<_>, Vec<_>) =
+            table_list.into_iter().partition(|tbl| {
+                self.table_filter
+                    .should_be_processed(tbl.schema.as_str(), tbl.name.as_str())
+            });
 
         // We don't filter the view list by schemas since a view could be in schema 1 (that may not
         // be replicated), but refer to only tables in schema 2 that are all replicated. If we try
@@ -616,6 +704,26 @@ impl<'a> PostgresReplicator<'a> {
             }
         }
 
+        // Tell ReadySet about the tables we're not replicating, so it can tell users why it can't
+        // cache queries that reference them
+        if !non_replicated_tables.is_empty() {
+            let changelist = ChangeList::from_changes(
+                non_replicated_tables
+                    .into_iter()
+                    .map(|tbl| {
+                        Change::AddNonReplicatedRelation(Relation {
+                            schema: Some(tbl.schema.into()),
+                            name: tbl.name.into(),
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+                DataDialect::DEFAULT_POSTGRESQL,
+            );
+            if let Err(error) = self.noria.extend_recipe_no_leader_ready(changelist).await {
+                warn!(%error, "Error recording tables which are not being replicated");
+            }
+        }
+
         self.drop_leftover_tables(&table_list, &view_list)
             .await
             .map_err(|e| e.context("Error while cleaning up leftover cache tables"))?;
@@ -696,9 +804,13 @@ impl<'a> PostgresReplicator<'a> {
 
         let replication_offsets = self.noria.replication_offsets().await?;
 
-        tables.drain_filter(|t| replication_offsets.has_table(&t.name)).for_each(|t|
-            info!(table = %t.name, "Replication offset already exists for table, skipping snapshot")
-        );
+        tables
+            .drain_filter(|t| {
+                replication_offsets.has_table(&t.name) && !resnapshot_tables.contains(&t.name)
+            })
+            .for_each(|t| {
+                info!(table = %t.name, "Replication offset already exists for table, skipping snapshot")
+            });
 
         // Finally copy each table into noria
         for table in &tables {
@@ -711,6 +823,11 @@ impl<'a> PostgresReplicator<'a> {
                 .instrument(span.clone())
                 .await?;
 
+            if resnapshot_tables.contains(&table.name) {
+                span.in_scope(|| info!("Discarding existing rows"));
+                noria_table.truncate().instrument(span.clone()).await?;
+            }
+
             noria_table.set_snapshot_mode(true).await?;
 
             table
@@ -39,6 +39,7 @@ pub struct WalReader {
 #[derive(Debug)]
 pub(crate) enum WalEvent {
     WantsKeepaliveResponse,
+    Keepalive,
     Commit,
     Insert {
         schema: String,
@@ -109,6 +110,7 @@ impl WalReader {
                 WalData::Keepalive { end, reply, .. } if reply == 1 => {
                     return Ok((WalEvent::WantsKeepaliveResponse, end))
                 }
+                WalData::Keepalive { end, .. } => return Ok((WalEvent::Keepalive, end)),
                 WalData::XLogData { end, data, .. } => (end, data),
                 msg => {
                     trace!(?msg, "Unhandled message");
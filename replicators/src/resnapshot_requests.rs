@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use nom_sql::Relation;
+use tokio::sync::Notify;
+
+/// A set of tables which have been requested to be re-snapshot from the upstream database, shared
+/// between the controller and the replicator.
+///
+/// When a table is requested, the replicator stops replicating and resnapshots, discarding the
+/// current contents of each requested table and copying its full state from the upstream database
+/// again. Tables that weren't requested keep their data and their replication offsets, and are
+/// caught up from the replication log as usual once the snapshot finishes.
+///
+/// Requests stay pending until a snapshot including them has finished successfully, so a request
+/// made while the replicator is restarting (or whose snapshot fails) isn't lost.
+///
+/// Cloning a [`ResnapshotRequests`] returns a handle to the same set of requests.
+#[derive(Debug, Clone, Default)]
+pub struct ResnapshotRequests {
+    tables: Arc<Mutex<HashSet<Relation>>>,
+    notify: Arc<Notify>,
+}
+
+impl ResnapshotRequests {
+    /// Create a new, empty set of resnapshot requests
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request that the given table be re-snapshot from the upstream database
+    pub fn request(&self, table: Relation) {
+        self.tables.lock().unwrap().insert(table);
+        self.notify.notify_one();
+    }
+
+    /// Returns true if any tables are waiting to be re-snapshot
+    pub fn has_pending(&self) -> bool {
+        !self.tables.lock().unwrap().is_empty()
+    }
+
+    /// Returns the set of tables currently waiting to be re-snapshot
+    pub(crate) fn pending(&self) -> HashSet<Relation> {
+        self.tables.lock().unwrap().clone()
+    }
+
+    /// Mark the given tables as having been re-snapshot
+    pub(crate) fn complete(&self, tables: &HashSet<Relation>) {
+        self.tables
+            .lock()
+            .unwrap()
+            .retain(|table| !tables.contains(table));
+    }
+
+    /// Wait until at least one table has been requested to be re-snapshot
+    pub(crate) async fn requested(&self) {
+        while !self.has_pending() {
+            self.notify.notified().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn requested_waits_for_request() {
+        let requests = ResnapshotRequests::new();
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), requests.requested())
+                .await
+                .is_err()
+        );
+
+        let waiter = tokio::spawn({
+            let requests = requests.clone();
+            async move { requests.requested().await }
+        });
+        requests.request("t1".into());
+        waiter.await.unwrap();
+        assert!(requests.has_pending());
+
+        let pending = requests.pending();
+        requests.request("t2".into());
+        requests.complete(&pending);
+        assert_eq!(requests.pending(), HashSet::from(["t2".into()]));
+    }
+}
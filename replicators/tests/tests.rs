@@ -16,7 +16,7 @@ use readyset_data::{Collation, DfValue, Dialect, TinyText};
 use readyset_server::Builder;
 use readyset_telemetry_reporter::{TelemetryEvent, TelemetryInitializer, TelemetrySender};
 use replicators::db_util::error_is_slot_not_found;
-use replicators::NoriaAdapter;
+use replicators::{NoriaAdapter, ResnapshotRequests};
 use test_utils::slow;
 use tracing::{error, trace};
 
@@ -118,6 +118,7 @@ struct TestHandle {
     // connection spawns a background task we can only terminate by dropping the runtime
     replication_rt: Option<tokio::runtime::Runtime>,
     ready_notify: Option<Arc<tokio::sync::Notify>>,
+    resnapshot_requests: ResnapshotRequests,
 }
 
 impl Drop for TestHandle {
@@ -251,6 +252,7 @@ impl TestHandle {
             authority,
             replication_rt: None,
             ready_notify: Some(Default::default()),
+            resnapshot_requests: ResnapshotRequests::new(),
         };
 
         handle.start_repl(config, telemetry_sender).await?;
@@ -284,6 +286,7 @@ impl TestHandle {
 
         let url = self.url.clone().into();
         let ready_notify = self.ready_notify.clone();
+        let resnapshot_requests = self.resnapshot_requests.clone();
         let _ = runtime.spawn(async move {
             if let Err(error) = NoriaAdapter::start(
                 controller,
@@ -293,6 +296,7 @@ impl TestHandle {
                 },
                 ready_notify.clone(),
                 telemetry_sender,
+                resnapshot_requests,
             )
             .await
             {
@@ -499,6 +503,12 @@ async fn mysql_datetime_replication() -> ReadySetResult<()> {
     mysql_datetime_replication_inner().await
 }
 
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+async fn mysql_date_replication() -> ReadySetResult<()> {
+    mysql_date_replication_inner().await
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[serial_test::serial]
 async fn pgsql_skip_unparsable() -> ReadySetResult<()> {
@@ -547,6 +557,18 @@ async fn mysql_replication_resnapshot() -> ReadySetResult<()> {
     resnapshot_inner(&mysql_url()).await
 }
 
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+async fn pgsql_resnapshot_table() -> ReadySetResult<()> {
+    resnapshot_table_inner(&pgsql_url()).await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+async fn mysql_resnapshot_table() -> ReadySetResult<()> {
+    resnapshot_table_inner(&mysql_url()).await
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[serial_test::serial]
 async fn psql14_ddl_replicate_drop_table() {
@@ -885,6 +907,60 @@ async fn mysql_datetime_replication_inner() -> ReadySetResult<()> {
     Ok(())
 }
 
+async fn mysql_date_replication_inner() -> ReadySetResult<()> {
+    let url = &mysql_url();
+    let mut client = DbConnection::connect(url).await?;
+    client
+        .query(
+            "
+            DROP TABLE IF EXISTS `d_test` CASCADE;
+            DROP VIEW IF EXISTS d_test_view;
+            CREATE TABLE `d_test` (
+                id int NOT NULL PRIMARY KEY,
+                d date
+            );
+            CREATE VIEW d_test_view AS SELECT * FROM `d_test` ORDER BY id ASC;
+            INSERT INTO `d_test` VALUES (1, '2024-01-01')",
+        )
+        .await?;
+
+    let mut ctx = TestHandle::start_noria(url.to_string(), None).await?;
+    ctx.ready_notify.as_ref().unwrap().notified().await;
+
+    // Repeat the insert using binlog replication, so that both paths are covered
+    client
+        .query("INSERT INTO `d_test` VALUES (2, '2024-01-02')")
+        .await?;
+
+    ctx.check_results(
+        "d_test_view",
+        "Date",
+        &[
+            &[
+                DfValue::Int(1),
+                chrono::NaiveDate::from_ymd(2024, 1, 1).into(),
+            ],
+            &[
+                DfValue::Int(2),
+                chrono::NaiveDate::from_ymd(2024, 1, 2).into(),
+            ],
+        ],
+    )
+    .await?;
+
+    // Comparisons ignore the time component, so also make sure there isn't a spurious one
+    let results = ctx.check_results_inner("d_test_view").await?;
+    let dates = results
+        .iter()
+        .map(|row| row[1].to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(dates, vec!["2024-01-01", "2024-01-02"]);
+
+    client.stop().await;
+    ctx.stop().await;
+    Ok(())
+}
+
 async fn replication_skip_unparsable_inner(url: &str) -> ReadySetResult<()> {
     readyset_tracing::init_test_logging();
     let mut client = DbConnection::connect(url).await?;
@@ -1126,6 +1202,140 @@ async fn replication_all_schemas_inner(url: &str) -> ReadySetResult<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+async fn pgsql_resume_after_schema_change() -> ReadySetResult<()> {
+    resume_after_schema_change_inner(&pgsql_url()).await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+async fn mysql_resume_after_schema_change() -> ReadySetResult<()> {
+    resume_after_schema_change_inner(&mysql_url()).await
+}
+
+/// Tests that when a table is altered upstream while the replicator isn't running, replication
+/// resumes by replaying the ALTER TABLE and resnapshotting the table, rather than applying rows
+/// that don't match the table ReadySet has.
+async fn resume_after_schema_change_inner(url: &str) -> ReadySetResult<()> {
+    let mut client = DbConnection::connect(url).await?;
+    client.query(CREATE_SCHEMA).await?;
+    client.query(POPULATE_SCHEMA).await?;
+
+    let mut ctx = TestHandle::start_noria(url.to_string(), None).await?;
+    ctx.ready_notify.as_ref().unwrap().notified().await;
+    ctx.check_results("noria_view", "Snapshot", SNAPSHOT_RESULT)
+        .await?;
+
+    ctx.stop_repl().await;
+    client
+        .query("ALTER TABLE `groups` ADD COLUMN extra int")
+        .await?;
+    client
+        .query("INSERT INTO `groups` VALUES (5, 'xyz', 4, 1)")
+        .await?;
+
+    ctx.start_repl(None, TelemetrySender::new_no_op()).await?;
+    ctx.ready_notify.as_ref().unwrap().notified().await;
+
+    // TODO(fran): In theory the view should have been recreated properly, and this step should be
+    // redundant
+    client.query("DROP VIEW noria_view").await?;
+    client
+        .query("CREATE VIEW noria_view AS SELECT id,string,bignum FROM `groups` ORDER BY id ASC")
+        .await?;
+
+    ctx.check_results(
+        "noria_view",
+        "Resumed after schema change",
+        &[
+            &[DfValue::Int(1), tiny(b"abc"), DfValue::Int(2)],
+            &[DfValue::Int(2), tiny(b"bcd"), DfValue::Int(3)],
+            &[DfValue::Int(3), DfValue::None, DfValue::None],
+            &[DfValue::Int(5), tiny(b"xyz"), DfValue::Int(4)],
+            &[DfValue::Int(40), tiny(b"xyz"), DfValue::Int(4)],
+        ],
+    )
+    .await?;
+
+    let schema = ctx
+        .controller()
+        .await
+        .table(Relation {
+            schema: Some("public".into()),
+            name: "groups".into(),
+        })
+        .await?
+        .schema()
+        .cloned()
+        .unwrap();
+    assert!(
+        schema.fields.iter().any(|f| f.column.name == "extra"),
+        "{schema}"
+    );
+
+    ctx.stop().await;
+    client.stop().await;
+
+    Ok(())
+}
+
+/// Tests that the replicator refuses to resume replication into a table whose schema no longer
+/// matches the upstream, when nothing in the binlog accounts for the difference.
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+async fn mysql_resume_with_mismatched_schema() -> ReadySetResult<()> {
+    let url = mysql_url();
+    let mut client = DbConnection::connect(&url).await?;
+    client.query(CREATE_SCHEMA).await?;
+    client.query(POPULATE_SCHEMA).await?;
+
+    let mut ctx = TestHandle::start_noria(url.clone(), None).await?;
+    ctx.ready_notify.as_ref().unwrap().notified().await;
+    ctx.check_results("noria_view", "resume_mismatched_schema", SNAPSHOT_RESULT)
+        .await?;
+
+    // Change the upstream table while the replicator isn't running, without writing the change to
+    // the binlog, so that the base table no longer matches it when replication resumes
+    ctx.stop_repl().await;
+    client.query("SET SESSION sql_log_bin = 0").await?;
+    client
+        .query("ALTER TABLE `groups` ADD COLUMN extra int")
+        .await?;
+
+    let res = tokio::time::timeout(
+        Duration::from_secs(60),
+        NoriaAdapter::start(
+            ctx.controller().await,
+            Config {
+                upstream_db_url: Some(url.into()),
+                ..Default::default()
+            },
+            None,
+            TelemetrySender::new_no_op(),
+            ResnapshotRequests::new(),
+        ),
+    )
+    .await
+    .expect("Replicator should refuse to start rather than resuming replication");
+
+    match res.unwrap_err() {
+        ReadySetError::UpstreamSchemaMismatch { table, mismatches } => {
+            assert!(table.contains("groups"), "{table}");
+            assert!(
+                mismatches.iter().any(|m| m.contains("extra")),
+                "{mismatches:?}"
+            );
+        }
+        e => panic!("Expected a schema mismatch error, got {e}"),
+    }
+
+    ctx.stop().await;
+    client.stop().await;
+
+    Ok(())
+}
+
 /// Tests that on encountering an ALTER TABLE statement the replicator does a proper resnapshot that
 /// results in the proper schema being present.
 async fn resnapshot_inner(url: &str) -> ReadySetResult<()> {
@@ -1252,6 +1462,135 @@ async fn resnapshot_inner(url: &str) -> ReadySetResult<()> {
     Ok(())
 }
 
+/// Tests that requesting a resnapshot of a single table replaces that table's contents with the
+/// upstream's, without touching any other tables, and that replication continues afterwards.
+async fn resnapshot_table_inner(url: &str) -> ReadySetResult<()> {
+    let mut client = DbConnection::connect(url).await?;
+    client
+        .query(
+            "
+            DROP TABLE IF EXISTS repl1 CASCADE;
+            DROP TABLE IF EXISTS repl2 CASCADE;
+            DROP VIEW IF EXISTS repl1_view;
+            DROP VIEW IF EXISTS repl2_view;
+            CREATE TABLE repl1 (
+                id int,
+                val varchar(255)
+            );
+            CREATE TABLE repl2 (
+                id int,
+                val varchar(255)
+            );
+            CREATE VIEW repl1_view AS SELECT * FROM repl1;
+            CREATE VIEW repl2_view AS SELECT * FROM repl2;",
+        )
+        .await?;
+
+    const ROWS: usize = 5;
+
+    for i in 0..ROWS {
+        client
+            .query(&format!("INSERT INTO repl1 VALUES ({i}, 'I am a teapot')"))
+            .await?;
+        client
+            .query(&format!("INSERT INTO repl2 VALUES ({i}, 'I am a teapot')"))
+            .await?;
+    }
+
+    let mut ctx = TestHandle::start_noria(url.to_string(), None).await?;
+    ctx.ready_notify.as_ref().unwrap().notified().await;
+
+    let upstream_rows = |n: usize| -> Vec<[DfValue; 2]> {
+        (0..n)
+            .map(|i| [DfValue::from(i as i32), DfValue::from("I am a teapot")])
+            .collect()
+    };
+    let bogus_row = [DfValue::from(100), DfValue::from("not upstream")];
+
+    let rs = upstream_rows(ROWS);
+    let rs: Vec<&[DfValue]> = rs.iter().map(|r| r.as_slice()).collect();
+    ctx.check_results("repl1_view", "Resnapshot table initial", rs.as_slice())
+        .await
+        .unwrap();
+    ctx.check_results("repl2_view", "Resnapshot table initial", rs.as_slice())
+        .await
+        .unwrap();
+
+    // Make both tables in ReadySet diverge from the upstream
+    for name in ["repl1", "repl2"] {
+        ctx.noria
+            .table(Relation {
+                schema: Some("public".into()),
+                name: name.into(),
+            })
+            .await?
+            .insert(bogus_row.to_vec())
+            .await?;
+    }
+
+    let mut diverged = upstream_rows(ROWS);
+    diverged.push(bogus_row.clone());
+    let rs: Vec<&[DfValue]> = diverged.iter().map(|r| r.as_slice()).collect();
+    ctx.check_results("repl1_view", "Resnapshot table diverged", rs.as_slice())
+        .await
+        .unwrap();
+
+    ctx.resnapshot_requests.request(Relation {
+        schema: Some("public".into()),
+        name: "repl1".into(),
+    });
+
+    // Only the resnapshot table is brought back in line with the upstream
+    let rs = upstream_rows(ROWS);
+    let rs: Vec<&[DfValue]> = rs.iter().map(|r| r.as_slice()).collect();
+    ctx.check_results("repl1_view", "Resnapshot table repl1", rs.as_slice())
+        .await
+        .unwrap();
+    let rs: Vec<&[DfValue]> = diverged.iter().map(|r| r.as_slice()).collect();
+    ctx.check_results("repl2_view", "Resnapshot table repl2", rs.as_slice())
+        .await
+        .unwrap();
+
+    // Replication continues for both tables after the resnapshot
+    client
+        .query(&format!(
+            "INSERT INTO repl1 VALUES ({ROWS}, 'I am a teapot')"
+        ))
+        .await?;
+    client
+        .query(&format!(
+            "INSERT INTO repl2 VALUES ({ROWS}, 'I am a teapot')"
+        ))
+        .await?;
+
+    let rs = upstream_rows(ROWS + 1);
+    let rs: Vec<&[DfValue]> = rs.iter().map(|r| r.as_slice()).collect();
+    ctx.check_results("repl1_view", "Resnapshot table repl1 after", rs.as_slice())
+        .await
+        .unwrap();
+    let mut diverged = upstream_rows(ROWS + 1);
+    diverged.push(bogus_row);
+    let rs: Vec<&[DfValue]> = diverged.iter().map(|r| r.as_slice()).collect();
+    ctx.check_results("repl2_view", "Resnapshot table repl2 after", rs.as_slice())
+        .await
+        .unwrap();
+
+    ctx.stop().await;
+
+    client
+        .query(
+            "DROP TABLE IF EXISTS repl1 CASCADE;
+             DROP TABLE IF EXISTS repl2 CASCADE;
+             DROP VIEW IF EXISTS repl1_view;
+             DROP VIEW IF EXISTS repl2_view;",
+        )
+        .await?;
+
+    client.stop().await;
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[serial_test::serial]
 async fn mysql_enum_replication() -> ReadySetResult<()> {